@@ -0,0 +1,648 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Persistent cvars the user set away from their launch defaults are saved here, in the
+/// project root.
+pub const USER_CVARS_FILE_NAME: &str = "user_cvars.json";
+const USER_CVARS_FILE_VERSION: u32 = 1;
+
+/// Current value of a cvar. Enum cvars hold their choice as a `String`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CvarValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl CvarValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Bool(value) => Value::Bool(*value),
+            Self::Int(value) => Value::from(*value),
+            Self::Float(value) => Value::from(*value),
+            Self::String(value) => Value::String(value.clone()),
+        }
+    }
+}
+
+/// Bools print as `0`/`1` to match the console's `key:value` output.
+impl fmt::Display for CvarValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", u8::from(*value)),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::String(value) => f.write_str(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CvarKind {
+    Bool,
+    Int { min: i64, max: i64 },
+    Float { min: f64, max: f64 },
+    String,
+    Enum { choices: Vec<String> },
+}
+
+/// A typed console variable: its name, default, allowed values and help text. Engine cvars are
+/// built in; games add theirs through `LoopConfig::cvars`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvarDef {
+    name: String,
+    help: String,
+    kind: CvarKind,
+    default: CvarValue,
+    persistent: bool,
+}
+
+impl CvarDef {
+    pub fn bool(name: impl Into<String>, default: bool, help: impl Into<String>) -> Self {
+        Self::new(name, help, CvarKind::Bool, CvarValue::Bool(default))
+    }
+
+    pub fn int(
+        name: impl Into<String>,
+        default: i64,
+        range: RangeInclusive<i64>,
+        help: impl Into<String>,
+    ) -> Self {
+        let kind = CvarKind::Int {
+            min: *range.start(),
+            max: *range.end(),
+        };
+        Self::new(name, help, kind, CvarValue::Int(default))
+    }
+
+    pub fn float(
+        name: impl Into<String>,
+        default: f64,
+        range: RangeInclusive<f64>,
+        help: impl Into<String>,
+    ) -> Self {
+        let kind = CvarKind::Float {
+            min: *range.start(),
+            max: *range.end(),
+        };
+        Self::new(name, help, kind, CvarValue::Float(default))
+    }
+
+    pub fn string(
+        name: impl Into<String>,
+        default: impl Into<String>,
+        help: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            name,
+            help,
+            CvarKind::String,
+            CvarValue::String(default.into()),
+        )
+    }
+
+    /// A string cvar limited to `choices`; input matches them case-insensitively.
+    pub fn choice(
+        name: impl Into<String>,
+        default: &str,
+        choices: &[&str],
+        help: impl Into<String>,
+    ) -> Self {
+        let kind = CvarKind::Enum {
+            choices: choices.iter().map(|choice| choice.to_string()).collect(),
+        };
+        Self::new(name, help, kind, CvarValue::String(default.to_string()))
+    }
+
+    /// Saves the value to `user_cvars.json` whenever it changes.
+    pub fn persistent(mut self) -> Self {
+        self.persistent = true;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn help(&self) -> &str {
+        &self.help
+    }
+
+    pub fn default_value(&self) -> &CvarValue {
+        &self.default
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        self.persistent
+    }
+
+    /// Type and allowed values, e.g. `int 0..=1000` or `enum off|csv|json`.
+    pub fn schema(&self) -> String {
+        match &self.kind {
+            CvarKind::Bool => "bool".to_string(),
+            CvarKind::Int { min, max } => format!("int {min}..={max}"),
+            CvarKind::Float { min, max } => format!("float {min}..={max}"),
+            CvarKind::String => "string".to_string(),
+            CvarKind::Enum { choices } => format!("enum {}", choices.join("|")),
+        }
+    }
+
+    fn new(
+        name: impl Into<String>,
+        help: impl Into<String>,
+        kind: CvarKind,
+        default: CvarValue,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            help: help.into(),
+            kind,
+            default,
+            persistent: false,
+        }
+    }
+
+    /// Parses console input into a value of this cvar's type and checks it is allowed.
+    pub fn parse_value(&self, raw: &str) -> Result<CvarValue, String> {
+        let value = match &self.kind {
+            CvarKind::Bool => match raw.to_ascii_lowercase().as_str() {
+                "1" | "on" | "true" => CvarValue::Bool(true),
+                "0" | "off" | "false" => CvarValue::Bool(false),
+                _ => return Err(format!("invalid bool '{raw}' (expected 0|1|on|off)")),
+            },
+            CvarKind::Int { .. } => raw
+                .parse::<i64>()
+                .map(CvarValue::Int)
+                .map_err(|_| format!("invalid int '{raw}'"))?,
+            CvarKind::Float { .. } => match raw.parse::<f64>() {
+                Ok(value) if value.is_finite() => CvarValue::Float(value),
+                _ => return Err(format!("invalid float '{raw}'")),
+            },
+            CvarKind::String => CvarValue::String(raw.to_string()),
+            CvarKind::Enum { choices } => choices
+                .iter()
+                .find(|choice| choice.eq_ignore_ascii_case(raw))
+                .map(|choice| CvarValue::String(choice.clone()))
+                .ok_or_else(|| {
+                    format!("invalid choice '{raw}' (expected {})", choices.join("|"))
+                })?,
+        };
+        self.check(&value)?;
+        Ok(value)
+    }
+
+    fn check(&self, value: &CvarValue) -> Result<(), String> {
+        match (&self.kind, value) {
+            (CvarKind::Bool, CvarValue::Bool(_)) | (CvarKind::String, CvarValue::String(_)) => {
+                Ok(())
+            }
+            (CvarKind::Int { min, max }, CvarValue::Int(value)) => {
+                if (*min..=*max).contains(value) {
+                    Ok(())
+                } else {
+                    Err(format!("{value} is out of range {min}..={max}"))
+                }
+            }
+            (CvarKind::Float { min, max }, CvarValue::Float(value)) => {
+                if (*min..=*max).contains(value) {
+                    Ok(())
+                } else {
+                    Err(format!("{value} is out of range {min}..={max}"))
+                }
+            }
+            (CvarKind::Enum { choices }, CvarValue::String(value)) => {
+                if choices.contains(value) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "invalid choice '{value}' (expected {})",
+                        choices.join("|")
+                    ))
+                }
+            }
+            _ => Err(format!("expected a {} value", self.schema())),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Cvar {
+    def: CvarDef,
+    value: CvarValue,
+    /// The value kept in `user_cvars.json`; it can differ from `value` for pinned cvars.
+    saved: Option<CvarValue>,
+}
+
+impl Cvar {
+    fn store(&mut self, value: CvarValue) {
+        self.saved = (self.def.persistent && value != self.def.default).then(|| value.clone());
+        self.value = value;
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UserCvarsFile {
+    version: u32,
+    cvars: BTreeMap<String, Value>,
+}
+
+/// Registered cvars and their current values, in registration order.
+#[derive(Debug, Default)]
+pub(crate) struct CvarRegistry {
+    cvars: Vec<Cvar>,
+    lookup_by_lower_name: HashMap<String, usize>,
+}
+
+impl CvarRegistry {
+    pub(crate) fn register(&mut self, def: CvarDef) -> Result<(), String> {
+        if def.name.is_empty() || def.name.contains(char::is_whitespace) {
+            return Err(format!("invalid cvar name '{}'", def.name));
+        }
+        let lower = def.name.to_ascii_lowercase();
+        if self.lookup_by_lower_name.contains_key(&lower) {
+            return Err(format!("duplicate cvar registration: {}", def.name));
+        }
+        def.check(&def.default)
+            .map_err(|error| format!("cvar {} default: {error}", def.name))?;
+
+        self.cvars.push(Cvar {
+            value: def.default.clone(),
+            saved: None,
+            def,
+        });
+        self.lookup_by_lower_name
+            .insert(lower, self.cvars.len() - 1);
+        Ok(())
+    }
+
+    pub(crate) fn lookup(&self, name: &str) -> Option<(&CvarDef, &CvarValue)> {
+        let cvar = self.cvar(name)?;
+        Some((&cvar.def, &cvar.value))
+    }
+
+    pub(crate) fn value(&self, name: &str) -> Option<&CvarValue> {
+        self.cvar(name).map(|cvar| &cvar.value)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&CvarDef, &CvarValue)> {
+        self.cvars.iter().map(|cvar| (&cvar.def, &cvar.value))
+    }
+
+    /// Parses and stores `raw`. Returns the registered definition and the new value.
+    pub(crate) fn set(&mut self, name: &str, raw: &str) -> Result<(&CvarDef, &CvarValue), String> {
+        let cvar = self.cvar_mut(name)?;
+        let value = cvar.def.parse_value(raw)?;
+        cvar.store(value);
+        Ok((&cvar.def, &cvar.value))
+    }
+
+    /// Stores an already-typed value, e.g. to mirror a toggle changed outside the console.
+    pub(crate) fn set_value(&mut self, name: &str, value: CvarValue) -> Result<(), String> {
+        let cvar = self.cvar_mut(name)?;
+        cvar.def.check(&value)?;
+        cvar.store(value);
+        Ok(())
+    }
+
+    pub(crate) fn reset(&mut self, name: &str) -> Result<(&CvarDef, &CvarValue), String> {
+        let cvar = self.cvar_mut(name)?;
+        cvar.store(cvar.def.default.clone());
+        Ok((&cvar.def, &cvar.value))
+    }
+
+    /// Applies saved values over the defaults, except for `pinned` cvars (set explicitly at
+    /// launch), which keep their default but still carry the saved value into the next save.
+    /// A missing file is not an error; entries that are unknown or no longer valid are skipped
+    /// and returned as warnings.
+    pub(crate) fn load_user_file(
+        &mut self,
+        path: &Path,
+        pinned: &[String],
+    ) -> Result<Vec<String>, String> {
+        let mut skipped = Vec::new();
+        for (name, saved) in read_user_cvars(path)? {
+            let raw = match &saved {
+                Value::Bool(value) => value.to_string(),
+                Value::Number(value) => value.to_string(),
+                Value::String(value) => value.clone(),
+                _ => {
+                    skipped.push(format!("{name}: unsupported value {saved}"));
+                    continue;
+                }
+            };
+            let Some(index) = self.lookup_by_lower_name.get(&name.to_ascii_lowercase()) else {
+                skipped.push(format!("{name}: unknown cvar"));
+                continue;
+            };
+            let cvar = &mut self.cvars[*index];
+            if !cvar.def.persistent {
+                skipped.push(format!("{name}: not a persistent cvar"));
+                continue;
+            }
+            match cvar.def.parse_value(&raw) {
+                Ok(value) => {
+                    if pinned
+                        .iter()
+                        .any(|pinned| pinned.eq_ignore_ascii_case(&cvar.def.name))
+                    {
+                        cvar.saved = Some(value);
+                    } else {
+                        cvar.store(value);
+                    }
+                }
+                Err(error) => skipped.push(format!("{name}: {error}")),
+            }
+        }
+        Ok(skipped)
+    }
+
+    /// Writes every saved persistent value: those set away from their default this run, plus
+    /// those loaded for pinned cvars.
+    pub(crate) fn save_user_file(&self, path: &Path) -> io::Result<()> {
+        let file = UserCvarsFile {
+            version: USER_CVARS_FILE_VERSION,
+            cvars: self
+                .cvars
+                .iter()
+                .filter_map(|cvar| {
+                    let saved = cvar.saved.as_ref()?;
+                    Some((cvar.def.name.clone(), saved.to_json()))
+                })
+                .collect(),
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(io::Error::other)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, json)
+    }
+
+    fn cvar(&self, name: &str) -> Option<&Cvar> {
+        let index = self.lookup_by_lower_name.get(&name.to_ascii_lowercase())?;
+        self.cvars.get(*index)
+    }
+
+    fn cvar_mut(&mut self, name: &str) -> Result<&mut Cvar, String> {
+        let index = *self
+            .lookup_by_lower_name
+            .get(&name.to_ascii_lowercase())
+            .ok_or_else(|| format!("unknown cvar '{name}'"))?;
+        Ok(&mut self.cvars[index])
+    }
+}
+
+/// Raw saved entries of a `user_cvars.json`, by cvar name; a missing file reads as empty.
+pub fn read_user_cvars(path: &Path) -> Result<BTreeMap<String, Value>, String> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(error) => return Err(format!("failed to read {}: {error}", path.display())),
+    };
+    let file: UserCvarsFile = serde_json::from_str(&raw)
+        .map_err(|error| format!("invalid {}: {error}", path.display()))?;
+    if file.version != USER_CVARS_FILE_VERSION {
+        return Err(format!(
+            "unsupported {} version {} (expected {USER_CVARS_FILE_VERSION})",
+            path.display(),
+            file.version
+        ));
+    }
+    Ok(file.cvars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_registry() -> CvarRegistry {
+        let mut registry = CvarRegistry::default();
+        registry
+            .register(CvarDef::int("render.fps_cap", 0, 0..=1000, "FPS cap").persistent())
+            .expect("register");
+        registry
+            .register(CvarDef::float(
+                "budgets.sim_ms",
+                0.0,
+                0.0..=100.0,
+                "Sim budget",
+            ))
+            .expect("register");
+        registry
+            .register(CvarDef::bool("debug.overlay", true, "Overlay").persistent())
+            .expect("register");
+        registry
+            .register(CvarDef::choice(
+                "metrics.export_on_exit",
+                "off",
+                &["off", "csv", "json"],
+                "Export",
+            ))
+            .expect("register");
+        registry
+            .register(CvarDef::string("game.label", "", "Label").persistent())
+            .expect("register");
+        registry
+    }
+
+    #[test]
+    fn set_parses_by_type_and_enforces_ranges_and_choices() {
+        let mut registry = test_registry();
+        assert_eq!(
+            registry.register(CvarDef::bool("DEBUG.OVERLAY", false, "")),
+            Err("duplicate cvar registration: DEBUG.OVERLAY".to_string())
+        );
+        assert_eq!(
+            registry.register(CvarDef::int("bad", 5, 0..=1, "")),
+            Err("cvar bad default: 5 is out of range 0..=1".to_string())
+        );
+
+        let (def, value) = registry.set("Render.FPS_Cap", "144").expect("set");
+        assert_eq!(
+            (def.name(), value),
+            ("render.fps_cap", &CvarValue::Int(144))
+        );
+        assert_eq!(
+            registry.set("render.fps_cap", "1001").map(|_| ()),
+            Err("1001 is out of range 0..=1000".to_string())
+        );
+        assert_eq!(registry.value("render.fps_cap"), Some(&CvarValue::Int(144)));
+        assert_eq!(
+            registry.set("budgets.sim_ms", "NaN").map(|_| ()),
+            Err("invalid float 'NaN'".to_string())
+        );
+        registry.set("budgets.sim_ms", "4.5").expect("set");
+        registry.set("debug.overlay", "off").expect("set");
+        registry.set("metrics.export_on_exit", "JSON").expect("set");
+        assert_eq!(
+            registry
+                .value("metrics.export_on_exit")
+                .and_then(CvarValue::as_str),
+            Some("json")
+        );
+        assert_eq!(
+            registry.set("metrics.export_on_exit", "xml").map(|_| ()),
+            Err("invalid choice 'xml' (expected off|csv|json)".to_string())
+        );
+        assert_eq!(
+            registry.set("nope", "1").map(|_| ()),
+            Err("unknown cvar 'nope'".to_string())
+        );
+        assert_eq!(
+            registry.set_value("debug.overlay", CvarValue::Int(1)),
+            Err("expected a bool value".to_string())
+        );
+
+        let (_, value) = registry.reset("debug.overlay").expect("reset");
+        assert_eq!(value, &CvarValue::Bool(true));
+        let listed: Vec<String> = registry
+            .iter()
+            .map(|(def, value)| format!("{}={value} {}", def.name(), def.schema()))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                "render.fps_cap=144 int 0..=1000",
+                "budgets.sim_ms=4.5 float 0..=100",
+                "debug.overlay=1 bool",
+                "metrics.export_on_exit=json enum off|csv|json",
+                "game.label= string",
+            ]
+        );
+    }
+
+    #[test]
+    fn user_file_round_trips_changed_persistent_cvars_and_skips_bad_entries() {
+        let dir = std::env::temp_dir().join(format!("protoge_cvars_{}", std::process::id()));
+        let path = dir.join(USER_CVARS_FILE_NAME);
+        let mut registry = test_registry();
+        assert_eq!(registry.load_user_file(&path, &[]), Ok(Vec::new()));
+
+        registry.set("render.fps_cap", "90").expect("set");
+        registry.set("debug.overlay", "0").expect("set");
+        registry.set("budgets.sim_ms", "8").expect("set");
+        registry.set("game.label", "night shift").expect("set");
+        registry.save_user_file(&path).expect("save");
+        let saved: Value =
+            serde_json::from_str(&fs::read_to_string(&path).expect("read")).expect("json");
+        assert_eq!(
+            saved,
+            serde_json::json!({
+                "version": 1,
+                "cvars": {
+                    "debug.overlay": false,
+                    "game.label": "night shift",
+                    "render.fps_cap": 90,
+                },
+            })
+        );
+
+        let mut loaded = test_registry();
+        assert_eq!(loaded.load_user_file(&path, &[]), Ok(Vec::new()));
+        assert_eq!(loaded.value("render.fps_cap"), Some(&CvarValue::Int(90)));
+        assert_eq!(loaded.value("debug.overlay"), Some(&CvarValue::Bool(false)));
+        assert_eq!(loaded.value("budgets.sim_ms"), Some(&CvarValue::Float(0.0)));
+
+        fs::write(
+            &path,
+            r#"{"version":1,"cvars":{"render.fps_cap":5000,"budgets.sim_ms":2,"gone":1,"debug.overlay":[]}}"#,
+        )
+        .expect("write");
+        let mut loaded = test_registry();
+        assert_eq!(
+            loaded.load_user_file(&path, &[]),
+            Ok(vec![
+                "budgets.sim_ms: not a persistent cvar".to_string(),
+                "debug.overlay: unsupported value []".to_string(),
+                "gone: unknown cvar".to_string(),
+                "render.fps_cap: 5000 is out of range 0..=1000".to_string(),
+            ])
+        );
+        assert_eq!(loaded.value("render.fps_cap"), Some(&CvarValue::Int(0)));
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn pinned_cvars_keep_launch_values_and_their_saved_values() {
+        let dir = std::env::temp_dir().join(format!("protoge_pinned_cvars_{}", std::process::id()));
+        let path = dir.join(USER_CVARS_FILE_NAME);
+        fs::create_dir_all(&dir).expect("dir");
+        fs::write(
+            &path,
+            r#"{"version":1,"cvars":{"render.fps_cap":30,"game.label":"saved"}}"#,
+        )
+        .expect("write");
+
+        let mut pinned_launch = CvarRegistry::default();
+        pinned_launch
+            .register(CvarDef::int("render.fps_cap", 144, 0..=1000, "FPS cap").persistent())
+            .expect("register");
+        pinned_launch
+            .register(CvarDef::string("game.label", "", "Label").persistent())
+            .expect("register");
+        let pinned = ["RENDER.FPS_CAP".to_string()];
+        assert_eq!(pinned_launch.load_user_file(&path, &pinned), Ok(Vec::new()));
+        assert_eq!(
+            pinned_launch.value("render.fps_cap"),
+            Some(&CvarValue::Int(144))
+        );
+        assert_eq!(
+            pinned_launch.value("game.label"),
+            Some(&CvarValue::String("saved".to_string()))
+        );
+
+        pinned_launch.set("game.label", "renamed").expect("set");
+        pinned_launch.save_user_file(&path).expect("save");
+        let mut next_launch = test_registry();
+        assert_eq!(next_launch.load_user_file(&path, &[]), Ok(Vec::new()));
+        assert_eq!(
+            next_launch.value("render.fps_cap"),
+            Some(&CvarValue::Int(30))
+        );
+
+        next_launch.reset("render.fps_cap").expect("reset");
+        next_launch.save_user_file(&path).expect("save");
+        let saved: Value =
+            serde_json::from_str(&fs::read_to_string(&path).expect("read")).expect("json");
+        assert_eq!(
+            saved["cvars"],
+            serde_json::json!({ "game.label": "renamed" })
+        );
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
@@ -1,3 +1,21 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::Deserialize;
+use thiserror::Error;
+use winit::event::MouseButton;
+use winit::keyboard::{KeyCode, ModifiersState};
+
+/// Input map file read from the project root at startup.
+pub const INPUT_MAP_FILE_NAME: &str = "input_map.json";
+const INPUT_MAP_FILE_VERSION: u32 = 1;
+
+/// Game crates may register `InputAction::Custom(0..MAX_CUSTOM_INPUT_ACTIONS)`.
+pub const MAX_CUSTOM_INPUT_ACTIONS: u8 = 46;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InputAction {
     MoveUp,
@@ -10,24 +28,49 @@ pub enum InputAction {
     CameraRight,
     ToggleOverlay,
     Quit,
+    ToggleConsole,
+    SwitchScene,
+    Save,
+    Load,
+    ZoomIn,
+    ZoomOut,
+    SimSpeedUp,
+    SimSpeedDown,
+    /// Game-defined action, registered through [`InputActionDef`].
+    Custom(u8),
 }
 
-const ACTION_COUNT: usize = 10;
+const BUILTIN_ACTION_COUNT: usize = 18;
 
-#[derive(Debug, Clone, Copy, Default)]
-pub(crate) struct ActionStates {
-    down: [bool; ACTION_COUNT],
-}
-
-impl ActionStates {
-    pub(crate) fn set(&mut self, action: InputAction, is_down: bool) {
-        self.down[action.index()] = is_down;
-    }
-
-    pub(crate) fn is_down(&self, action: InputAction) -> bool {
-        self.down[action.index()]
-    }
-}
+/// Engine actions with their input map names and default bindings.
+const BUILTIN_ACTIONS: [(InputAction, &str, &[&str]); BUILTIN_ACTION_COUNT] = [
+    (InputAction::MoveUp, "move_up", &["W", "ArrowUp"]),
+    (InputAction::MoveDown, "move_down", &["S", "ArrowDown"]),
+    (InputAction::MoveLeft, "move_left", &["A", "ArrowLeft"]),
+    (InputAction::MoveRight, "move_right", &["D", "ArrowRight"]),
+    (InputAction::CameraUp, "camera_up", &["I"]),
+    (InputAction::CameraDown, "camera_down", &["K"]),
+    (InputAction::CameraLeft, "camera_left", &["J"]),
+    (InputAction::CameraRight, "camera_right", &["L"]),
+    (InputAction::ToggleOverlay, "toggle_overlay", &["F3"]),
+    (InputAction::Quit, "quit", &["Escape"]),
+    (InputAction::ToggleConsole, "toggle_console", &["Backquote"]),
+    (InputAction::SwitchScene, "switch_scene", &["Tab"]),
+    (InputAction::Save, "save", &["F5"]),
+    (InputAction::Load, "load", &["F9"]),
+    (InputAction::ZoomIn, "zoom_in", &["Equal", "NumpadAdd"]),
+    (
+        InputAction::ZoomOut,
+        "zoom_out",
+        &["Minus", "NumpadSubtract"],
+    ),
+    (InputAction::SimSpeedUp, "sim_speed_up", &["BracketRight"]),
+    (
+        InputAction::SimSpeedDown,
+        "sim_speed_down",
+        &["BracketLeft"],
+    ),
+];
 
 impl InputAction {
     const fn index(self) -> usize {
@@ -42,6 +85,757 @@ impl InputAction {
             InputAction::CameraRight => 7,
             InputAction::ToggleOverlay => 8,
             InputAction::Quit => 9,
+            InputAction::ToggleConsole => 10,
+            InputAction::SwitchScene => 11,
+            InputAction::Save => 12,
+            InputAction::Load => 13,
+            InputAction::ZoomIn => 14,
+            InputAction::ZoomOut => 15,
+            InputAction::SimSpeedUp => 16,
+            InputAction::SimSpeedDown => 17,
+            InputAction::Custom(id) => BUILTIN_ACTION_COUNT + id as usize,
+        }
+    }
+}
+
+/// Held state for every action, one bit per [`InputAction::index`].
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ActionStates {
+    down: u64,
+}
+
+impl ActionStates {
+    pub(crate) fn set(&mut self, action: InputAction, is_down: bool) {
+        let Some(bit) = action_bit(action) else {
+            return;
+        };
+        if is_down {
+            self.down |= bit;
+        } else {
+            self.down &= !bit;
+        }
+    }
+
+    pub(crate) fn is_down(&self, action: InputAction) -> bool {
+        action_bit(action).is_some_and(|bit| self.down & bit != 0)
+    }
+
+    pub(crate) fn bits(self) -> u64 {
+        self.down
+    }
+
+    pub(crate) fn from_bits(down: u64) -> Self {
+        Self { down }
+    }
+}
+
+fn action_bit(action: InputAction) -> Option<u64> {
+    1u64.checked_shl(action.index() as u32)
+}
+
+/// A game-registered action: its input map name, id and the bindings used when the input map
+/// file does not mention it. Bindings use the input map syntax, e.g. `"Ctrl+E"` or `"MouseMiddle"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputActionDef {
+    pub name: String,
+    pub action: InputAction,
+    pub default_bindings: Vec<String>,
+}
+
+impl InputActionDef {
+    pub fn new(name: impl Into<String>, id: u8, default_bindings: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            action: InputAction::Custom(id),
+            default_bindings: default_bindings.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum InputMapError {
+    #[error("failed to read input map {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse input map {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("unsupported input map version {found}; expected {expected}")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error("unknown input action '{0}'")]
+    UnknownAction(String),
+    #[error("input action '{0}' is registered twice")]
+    DuplicateAction(String),
+    #[error("input action '{name}' must be InputAction::Custom(0..{max})")]
+    InvalidCustomAction { name: String, max: u8 },
+    #[error("invalid binding '{binding}': {reason}")]
+    InvalidBinding { binding: String, reason: String },
+    #[error("binding {binding} is bound to both '{first}' and '{second}'")]
+    Conflict {
+        binding: InputBinding,
+        first: String,
+        second: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputTrigger {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct InputModifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+impl InputModifiers {
+    fn contains(self, other: Self) -> bool {
+        (self.ctrl || !other.ctrl)
+            && (self.shift || !other.shift)
+            && (self.alt || !other.alt)
+            && (self.logo || !other.logo)
+    }
+
+    fn count(self) -> u32 {
+        u32::from(self.ctrl) + u32::from(self.shift) + u32::from(self.alt) + u32::from(self.logo)
+    }
+}
+
+impl From<ModifiersState> for InputModifiers {
+    fn from(state: ModifiersState) -> Self {
+        Self {
+            ctrl: state.control_key(),
+            shift: state.shift_key(),
+            alt: state.alt_key(),
+            logo: state.super_key(),
+        }
+    }
+}
+
+/// A key or mouse button plus the modifiers that must be held with it. Written as
+/// `[Ctrl+][Shift+][Alt+][Super+]<Key|MouseButton>`, e.g. `Ctrl+S`, `F5`, `MouseMiddle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputBinding {
+    pub trigger: InputTrigger,
+    pub modifiers: InputModifiers,
+}
+
+impl FromStr for InputBinding {
+    type Err = InputMapError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| InputMapError::InvalidBinding {
+            binding: raw.to_string(),
+            reason,
+        };
+        let mut parts = raw.trim().split('+').map(str::trim).collect::<Vec<_>>();
+        let Some(trigger_name) = parts.pop().filter(|name| !name.is_empty()) else {
+            return Err(invalid("missing key or mouse button".to_string()));
+        };
+
+        let mut modifiers = InputModifiers::default();
+        for part in parts {
+            let flag = match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => &mut modifiers.ctrl,
+                "shift" => &mut modifiers.shift,
+                "alt" => &mut modifiers.alt,
+                "super" | "logo" => &mut modifiers.logo,
+                _ => return Err(invalid(format!("unknown modifier '{part}'"))),
+            };
+            if *flag {
+                return Err(invalid(format!("modifier '{part}' repeated")));
+            }
+            *flag = true;
+        }
+
+        let trigger = parse_trigger_name(trigger_name)
+            .ok_or_else(|| invalid(format!("unknown key or mouse button '{trigger_name}'")))?;
+        Ok(Self { trigger, modifiers })
+    }
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (held, name) in [
+            (self.modifiers.ctrl, "Ctrl"),
+            (self.modifiers.shift, "Shift"),
+            (self.modifiers.alt, "Alt"),
+            (self.modifiers.logo, "Super"),
+        ] {
+            if held {
+                write!(f, "{name}+")?;
+            }
+        }
+        match trigger_name(self.trigger) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:?}", self.trigger),
+        }
+    }
+}
+
+const MOUSE_BUTTON_NAMES: [(&str, MouseButton); 5] = [
+    ("MouseLeft", MouseButton::Left),
+    ("MouseRight", MouseButton::Right),
+    ("MouseMiddle", MouseButton::Middle),
+    ("MouseBack", MouseButton::Back),
+    ("MouseForward", MouseButton::Forward),
+];
+
+const KEY_NAMES: [(&str, KeyCode); 99] = [
+    ("A", KeyCode::KeyA),
+    ("B", KeyCode::KeyB),
+    ("C", KeyCode::KeyC),
+    ("D", KeyCode::KeyD),
+    ("E", KeyCode::KeyE),
+    ("F", KeyCode::KeyF),
+    ("G", KeyCode::KeyG),
+    ("H", KeyCode::KeyH),
+    ("I", KeyCode::KeyI),
+    ("J", KeyCode::KeyJ),
+    ("K", KeyCode::KeyK),
+    ("L", KeyCode::KeyL),
+    ("M", KeyCode::KeyM),
+    ("N", KeyCode::KeyN),
+    ("O", KeyCode::KeyO),
+    ("P", KeyCode::KeyP),
+    ("Q", KeyCode::KeyQ),
+    ("R", KeyCode::KeyR),
+    ("S", KeyCode::KeyS),
+    ("T", KeyCode::KeyT),
+    ("U", KeyCode::KeyU),
+    ("V", KeyCode::KeyV),
+    ("W", KeyCode::KeyW),
+    ("X", KeyCode::KeyX),
+    ("Y", KeyCode::KeyY),
+    ("Z", KeyCode::KeyZ),
+    ("0", KeyCode::Digit0),
+    ("1", KeyCode::Digit1),
+    ("2", KeyCode::Digit2),
+    ("3", KeyCode::Digit3),
+    ("4", KeyCode::Digit4),
+    ("5", KeyCode::Digit5),
+    ("6", KeyCode::Digit6),
+    ("7", KeyCode::Digit7),
+    ("8", KeyCode::Digit8),
+    ("9", KeyCode::Digit9),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("ArrowUp", KeyCode::ArrowUp),
+    ("ArrowDown", KeyCode::ArrowDown),
+    ("ArrowLeft", KeyCode::ArrowLeft),
+    ("ArrowRight", KeyCode::ArrowRight),
+    ("Escape", KeyCode::Escape),
+    ("Tab", KeyCode::Tab),
+    ("Backquote", KeyCode::Backquote),
+    ("Space", KeyCode::Space),
+    ("Enter", KeyCode::Enter),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Minus", KeyCode::Minus),
+    ("Equal", KeyCode::Equal),
+    ("BracketLeft", KeyCode::BracketLeft),
+    ("BracketRight", KeyCode::BracketRight),
+    ("Backslash", KeyCode::Backslash),
+    ("Semicolon", KeyCode::Semicolon),
+    ("Quote", KeyCode::Quote),
+    ("Comma", KeyCode::Comma),
+    ("Period", KeyCode::Period),
+    ("Slash", KeyCode::Slash),
+    ("Numpad0", KeyCode::Numpad0),
+    ("Numpad1", KeyCode::Numpad1),
+    ("Numpad2", KeyCode::Numpad2),
+    ("Numpad3", KeyCode::Numpad3),
+    ("Numpad4", KeyCode::Numpad4),
+    ("Numpad5", KeyCode::Numpad5),
+    ("Numpad6", KeyCode::Numpad6),
+    ("Numpad7", KeyCode::Numpad7),
+    ("Numpad8", KeyCode::Numpad8),
+    ("Numpad9", KeyCode::Numpad9),
+    ("NumpadAdd", KeyCode::NumpadAdd),
+    ("NumpadSubtract", KeyCode::NumpadSubtract),
+    ("NumpadMultiply", KeyCode::NumpadMultiply),
+    ("NumpadDivide", KeyCode::NumpadDivide),
+    ("NumpadDecimal", KeyCode::NumpadDecimal),
+    ("NumpadEnter", KeyCode::NumpadEnter),
+    ("ShiftLeft", KeyCode::ShiftLeft),
+    ("ShiftRight", KeyCode::ShiftRight),
+    ("ControlLeft", KeyCode::ControlLeft),
+    ("ControlRight", KeyCode::ControlRight),
+    ("AltLeft", KeyCode::AltLeft),
+    ("AltRight", KeyCode::AltRight),
+    ("SuperLeft", KeyCode::SuperLeft),
+    ("SuperRight", KeyCode::SuperRight),
+    ("CapsLock", KeyCode::CapsLock),
+];
+
+fn parse_trigger_name(name: &str) -> Option<InputTrigger> {
+    // `KeyW`/`Digit1` are accepted as aliases so winit's own key names also work.
+    let short = ["key", "digit"]
+        .iter()
+        .find_map(|prefix| {
+            name.get(..prefix.len())
+                .filter(|head| head.eq_ignore_ascii_case(prefix))
+                .map(|_| &name[prefix.len()..])
+        })
+        .filter(|rest| rest.len() == 1)
+        .unwrap_or(name);
+    if let Some((_, code)) = KEY_NAMES
+        .iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(short))
+    {
+        return Some(InputTrigger::Key(*code));
+    }
+    MOUSE_BUTTON_NAMES
+        .iter()
+        .find(|(button_name, _)| button_name.eq_ignore_ascii_case(name))
+        .map(|(_, button)| InputTrigger::Mouse(*button))
+}
+
+fn trigger_name(trigger: InputTrigger) -> Option<&'static str> {
+    match trigger {
+        InputTrigger::Key(code) => KEY_NAMES
+            .iter()
+            .find(|(_, key)| *key == code)
+            .map(|(name, _)| *name),
+        InputTrigger::Mouse(button) => MOUSE_BUTTON_NAMES
+            .iter()
+            .find(|(_, candidate)| *candidate == button)
+            .map(|(name, _)| *name),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct InputMapFile {
+    version: u32,
+    /// Action name to its full binding list; actions not listed keep their defaults.
+    #[serde(default)]
+    bindings: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+struct InputMapEntry {
+    name: String,
+    action: InputAction,
+    bindings: Vec<InputBinding>,
+}
+
+/// Named actions and the bindings that drive them, engine actions first, then game actions in
+/// registration order. No binding is ever shared by two actions.
+#[derive(Debug, Clone)]
+pub(crate) struct InputMap {
+    entries: Vec<InputMapEntry>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self::with_actions(&[]).expect("built-in input bindings should be valid")
+    }
+}
+
+impl InputMap {
+    /// Engine actions plus `custom` game actions, each with its default bindings.
+    pub(crate) fn with_actions(custom: &[InputActionDef]) -> Result<Self, InputMapError> {
+        let mut entries = Vec::with_capacity(BUILTIN_ACTION_COUNT + custom.len());
+        for (action, name, defaults) in BUILTIN_ACTIONS {
+            entries.push(InputMapEntry {
+                name: name.to_string(),
+                action,
+                bindings: parse_bindings(defaults.iter().copied())?,
+            });
+        }
+        for def in custom {
+            let InputAction::Custom(id) = def.action else {
+                return Err(InputMapError::InvalidCustomAction {
+                    name: def.name.clone(),
+                    max: MAX_CUSTOM_INPUT_ACTIONS,
+                });
+            };
+            if id >= MAX_CUSTOM_INPUT_ACTIONS || def.name.trim().is_empty() {
+                return Err(InputMapError::InvalidCustomAction {
+                    name: def.name.clone(),
+                    max: MAX_CUSTOM_INPUT_ACTIONS,
+                });
+            }
+            if entries
+                .iter()
+                .any(|entry| entry.name == def.name || entry.action == def.action)
+            {
+                return Err(InputMapError::DuplicateAction(def.name.clone()));
+            }
+            entries.push(InputMapEntry {
+                name: def.name.clone(),
+                action: def.action,
+                bindings: parse_bindings(def.default_bindings.iter().map(String::as_str))?,
+            });
+        }
+
+        let map = Self { entries };
+        map.validate()?;
+        Ok(map)
+    }
+
+    /// Loads `path` over the defaults from [`InputMap::with_actions`]. A missing file is not an
+    /// error; an invalid or conflicting one is.
+    pub(crate) fn load(path: &Path, custom: &[InputActionDef]) -> Result<Self, InputMapError> {
+        let mut map = Self::with_actions(custom)?;
+        if !path.is_file() {
+            return Ok(map);
+        }
+        let bytes = fs::read(path).map_err(|source| InputMapError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let file: InputMapFile =
+            serde_json::from_slice(&bytes).map_err(|source| InputMapError::Parse {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        if file.version != INPUT_MAP_FILE_VERSION {
+            return Err(InputMapError::UnsupportedVersion {
+                found: file.version,
+                expected: INPUT_MAP_FILE_VERSION,
+            });
+        }
+        for (name, bindings) in &file.bindings {
+            let bindings = parse_bindings(bindings.iter().map(String::as_str))?;
+            map.entry_mut(name)?.bindings = bindings;
+        }
+        map.validate()?;
+        Ok(map)
+    }
+
+    /// Replaces every binding of `name`. On conflict the map is left unchanged.
+    pub(crate) fn rebind(
+        &mut self,
+        name: &str,
+        bindings: Vec<InputBinding>,
+    ) -> Result<(), InputMapError> {
+        let mut bindings = bindings;
+        dedup_in_order(&mut bindings);
+        let previous = std::mem::replace(&mut self.entry_mut(name)?.bindings, bindings);
+        if let Err(error) = self.validate() {
+            self.entry_mut(name)?.bindings = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn bindings(&self, name: &str) -> Option<&[InputBinding]> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            .map(|entry| entry.bindings.as_slice())
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &[InputBinding])> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.bindings.as_slice()))
+    }
+
+    /// Actions fired by `trigger` while `held` modifiers are down. A binding matches when its
+    /// modifiers are all held; only the most specific matches fire, so `Ctrl+S` shadows `S`.
+    pub(crate) fn actions_for(
+        &self,
+        trigger: InputTrigger,
+        held: InputModifiers,
+    ) -> Vec<InputAction> {
+        let matches = self
+            .entries
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .bindings
+                    .iter()
+                    .map(move |binding| (entry.action, binding))
+            })
+            .filter(|(_, binding)| binding.trigger == trigger && held.contains(binding.modifiers))
+            .collect::<Vec<_>>();
+        let Some(best) = matches
+            .iter()
+            .map(|(_, binding)| binding.modifiers.count())
+            .max()
+        else {
+            return Vec::new();
+        };
+        matches
+            .into_iter()
+            .filter(|(_, binding)| binding.modifiers.count() == best)
+            .map(|(action, _)| action)
+            .collect()
+    }
+
+    fn entry_mut(&mut self, name: &str) -> Result<&mut InputMapEntry, InputMapError> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| InputMapError::UnknownAction(name.to_string()))
+    }
+
+    fn validate(&self) -> Result<(), InputMapError> {
+        for (index, entry) in self.entries.iter().enumerate() {
+            for other in &self.entries[index + 1..] {
+                if let Some(binding) = entry
+                    .bindings
+                    .iter()
+                    .find(|binding| other.bindings.contains(binding))
+                {
+                    return Err(InputMapError::Conflict {
+                        binding: *binding,
+                        first: entry.name.clone(),
+                        second: other.name.clone(),
+                    });
+                }
+            }
         }
+        Ok(())
+    }
+}
+
+fn parse_bindings<'a>(
+    raw: impl Iterator<Item = &'a str>,
+) -> Result<Vec<InputBinding>, InputMapError> {
+    let mut bindings = raw
+        .map(InputBinding::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
+    dedup_in_order(&mut bindings);
+    Ok(bindings)
+}
+
+fn dedup_in_order(bindings: &mut Vec<InputBinding>) {
+    let mut seen = Vec::with_capacity(bindings.len());
+    bindings.retain(|binding| {
+        let first = !seen.contains(binding);
+        seen.push(*binding);
+        first
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_input_map_path(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("protoge_input_map_{name}_{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("temp dir");
+        dir.join(INPUT_MAP_FILE_NAME)
+    }
+
+    fn binding(raw: &str) -> InputBinding {
+        raw.parse().expect("binding")
+    }
+
+    #[test]
+    fn bindings_parse_modifier_chords_and_round_trip() {
+        let chord = binding("ctrl+Shift+s");
+        assert_eq!(chord.trigger, InputTrigger::Key(KeyCode::KeyS));
+        assert!(chord.modifiers.ctrl && chord.modifiers.shift);
+        assert!(!chord.modifiers.alt && !chord.modifiers.logo);
+        assert_eq!(chord.to_string(), "Ctrl+Shift+S");
+
+        assert_eq!(binding("KeyW"), binding("w"));
+        assert_eq!(binding("Digit1").to_string(), "1");
+        assert_eq!(
+            binding("MouseMiddle").trigger,
+            InputTrigger::Mouse(MouseButton::Middle)
+        );
+        assert_eq!(binding("Alt+MouseBack").to_string(), "Alt+MouseBack");
+
+        assert!("Hyper+W".parse::<InputBinding>().is_err());
+        assert!("Ctrl+".parse::<InputBinding>().is_err());
+        assert!("Ctrl+Ctrl+W".parse::<InputBinding>().is_err());
+        assert!("Nope".parse::<InputBinding>().is_err());
+    }
+
+    #[test]
+    fn defaults_keep_the_legacy_layout() {
+        let map = InputMap::default();
+        let none = InputModifiers::default();
+        assert_eq!(
+            map.actions_for(InputTrigger::Key(KeyCode::ArrowUp), none),
+            vec![InputAction::MoveUp]
+        );
+        assert_eq!(
+            map.actions_for(InputTrigger::Key(KeyCode::NumpadSubtract), none),
+            vec![InputAction::ZoomOut]
+        );
+        assert_eq!(
+            map.bindings("toggle_console"),
+            Some(&[binding("Backquote")][..])
+        );
+        assert!(map
+            .actions_for(InputTrigger::Mouse(MouseButton::Left), none)
+            .is_empty());
+    }
+
+    #[test]
+    fn most_specific_chord_shadows_plain_binding() {
+        let mut map = InputMap::default();
+        map.rebind("save", vec![binding("Ctrl+S")]).expect("rebind");
+        let ctrl = InputModifiers {
+            ctrl: true,
+            ..InputModifiers::default()
+        };
+        let shift = InputModifiers {
+            shift: true,
+            ..InputModifiers::default()
+        };
+        let key_s = InputTrigger::Key(KeyCode::KeyS);
+
+        assert_eq!(map.actions_for(key_s, ctrl), vec![InputAction::Save]);
+        assert_eq!(map.actions_for(key_s, shift), vec![InputAction::MoveDown]);
+        assert_eq!(
+            map.actions_for(key_s, InputModifiers::default()),
+            vec![InputAction::MoveDown]
+        );
+    }
+
+    #[test]
+    fn file_overrides_listed_actions_and_keeps_other_defaults() {
+        let path = temp_input_map_path("override");
+        fs::write(
+            &path,
+            r#"{"version":1,"bindings":{"move_up":["Space","MouseMiddle"],"camera_up":[]}}"#,
+        )
+        .expect("write");
+
+        let map = InputMap::load(&path, &[]).expect("load");
+        let none = InputModifiers::default();
+        assert_eq!(
+            map.actions_for(InputTrigger::Mouse(MouseButton::Middle), none),
+            vec![InputAction::MoveUp]
+        );
+        assert!(map
+            .actions_for(InputTrigger::Key(KeyCode::KeyW), none)
+            .is_empty());
+        assert_eq!(map.bindings("camera_up"), Some(&[][..]));
+        assert_eq!(
+            map.actions_for(InputTrigger::Key(KeyCode::KeyD), none),
+            vec![InputAction::MoveRight]
+        );
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn shipped_input_map_matches_the_defaults() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../..")
+            .join(INPUT_MAP_FILE_NAME);
+        let shipped = InputMap::load(&path, &[]).expect("shipped input map");
+        let defaults = InputMap::default();
+        assert!(shipped.iter().eq(defaults.iter()));
+    }
+
+    #[test]
+    fn conflicting_or_unknown_file_entries_fail_to_load() {
+        let path = temp_input_map_path("conflict");
+        fs::write(&path, r#"{"version":1,"bindings":{"save":["W"]}}"#).expect("write");
+        match InputMap::load(&path, &[]) {
+            Err(InputMapError::Conflict {
+                binding: conflict,
+                first,
+                second,
+            }) => {
+                assert_eq!(conflict, binding("W"));
+                assert_eq!(first, "move_up");
+                assert_eq!(second, "save");
+            }
+            other => panic!("expected conflict, got {other:?}"),
+        }
+
+        fs::write(&path, r#"{"version":1,"bindings":{"jump":["Space"]}}"#).expect("write");
+        assert!(matches!(
+            InputMap::load(&path, &[]),
+            Err(InputMapError::UnknownAction(name)) if name == "jump"
+        ));
+
+        fs::write(&path, r#"{"version":2}"#).expect("write");
+        assert!(matches!(
+            InputMap::load(&path, &[]),
+            Err(InputMapError::UnsupportedVersion { found: 2, .. })
+        ));
+
+        let _ = fs::remove_file(&path);
+        assert!(InputMap::load(&path, &[]).is_ok());
+    }
+
+    #[test]
+    fn rebind_conflict_leaves_map_unchanged() {
+        let mut map = InputMap::default();
+        let error = map
+            .rebind("zoom_in", vec![binding("E"), binding("Tab")])
+            .expect_err("Tab belongs to switch_scene");
+        assert!(matches!(error, InputMapError::Conflict { .. }));
+        assert_eq!(
+            map.bindings("zoom_in"),
+            Some(&[binding("Equal"), binding("NumpadAdd")][..])
+        );
+        assert!(matches!(
+            map.rebind("fly", vec![binding("E")]),
+            Err(InputMapError::UnknownAction(_))
+        ));
+    }
+
+    #[test]
+    fn game_actions_register_after_engine_actions() {
+        let custom = [
+            InputActionDef::new("interact", 0, &["E"]),
+            InputActionDef::new("inventory", 45, &["Ctrl+I"]),
+        ];
+        let map = InputMap::with_actions(&custom).expect("custom actions");
+        let ctrl = InputModifiers {
+            ctrl: true,
+            ..InputModifiers::default()
+        };
+        assert_eq!(
+            map.actions_for(InputTrigger::Key(KeyCode::KeyI), ctrl),
+            vec![InputAction::Custom(45)]
+        );
+        assert_eq!(map.iter().last().map(|(name, _)| name), Some("inventory"));
+
+        let mut states = ActionStates::default();
+        states.set(InputAction::Custom(45), true);
+        assert!(states.is_down(InputAction::Custom(45)));
+        assert!(!states.is_down(InputAction::ZoomOut));
+
+        assert!(matches!(
+            InputMap::with_actions(&[InputActionDef::new("too_far", 46, &[])]),
+            Err(InputMapError::InvalidCustomAction { .. })
+        ));
+        assert!(matches!(
+            InputMap::with_actions(&[InputActionDef::new("quit", 1, &[])]),
+            Err(InputMapError::DuplicateAction(_))
+        ));
+        assert!(matches!(
+            InputMap::with_actions(&[InputActionDef::new("grab", 2, &["Escape"])]),
+            Err(InputMapError::Conflict { .. })
+        ));
     }
 }
@@ -1,5 +1,5 @@
 use std::collections::VecDeque;
-use std::env;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -11,7 +11,7 @@ use winit::dpi::LogicalSize;
 use winit::error::{EventLoopError, OsError};
 use winit::event::{ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
-use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::keyboard::PhysicalKey;
 use winit::window::WindowBuilder;
 
 use crate::{
@@ -19,21 +19,36 @@ use crate::{
     StartupError,
 };
 
+use super::cvars::{CvarDef, CvarRegistry, CvarValue};
+use super::input::{ActionStates, InputMap, InputModifiers, InputTrigger};
 use super::metrics::MetricsAccumulator;
+use super::metrics_history::{MetricsExportFormat, MetricsHistory};
+use super::profiling::{profile_zone, TraceCapture, ZoneProfiler};
+use super::rendering::minimap_world_position_at;
+use super::replay::{ReplayHeader, ReplayPlayer, ReplayRecorder};
+use super::rewind::{RewindError, RewindHistory};
 use super::scene::SceneMachine;
 use super::tools::console_commands::{InjectedInputEvent, InjectedKey, InjectedMouseButton};
+use super::tools::{PerfGraphData, PERF_GRAPH_SAMPLES};
 use super::{
     format_spawn_command, CommandPaletteButtonKind, CommandPaletteState, ConsoleCommandProcessor,
-    ConsoleState, DebugCommand, InputAction, InputSnapshot, MetricsHandle, OverlayData, PerfStats,
-    Renderer, Scene, SceneCommand, SceneDebugCommand, SceneDebugCommandResult, SceneDebugContext,
-    SceneKey,
+    ConsoleState, DebugCommand, InputAction, InputActionDef, InputBinding, InputMapError,
+    InputSnapshot, MetricsHandle, OverlayData, PerfStats, Renderer, Scene, SceneCommand,
+    SceneDebugCommand, SceneDebugCommandResult, SceneDebugContext, SceneKey, SceneWorld,
 };
 
 pub const SLOW_FRAME_ENV_VAR: &str = "PROTOGE_SLOW_FRAME_MS";
-const THRUPORT_TELEMETRY_ENV_VAR: &str = "PROTOGE_THRUPORT_TELEMETRY";
-const THRUPORT_DIAG_ENV_VAR: &str = "PROTOGE_THRUPORT_DIAG";
 const SOFT_BUDGET_CONSECUTIVE_BREACH_FRAMES: u32 = 3;
 const MAX_PENDING_INJECTED_EVENTS: usize = 256;
+/// Slowest zones listed in the overlay.
+const OVERLAY_ZONE_LIMIT: usize = 6;
+const FPS_CAP_CVAR: &str = "render.fps_cap";
+const SIM_BUDGET_CVAR: &str = "budgets.sim_ms";
+const RENDER_BUDGET_CVAR: &str = "budgets.render_ms";
+const THRUPORT_TELEMETRY_CVAR: &str = "thruport.telemetry";
+const SLOW_FRAME_CVAR: &str = "debug.slow_frame_ms";
+const OVERLAY_CVAR: &str = "debug.overlay";
+const METRICS_EXPORT_ON_EXIT_CVAR: &str = "metrics.export_on_exit";
 
 pub trait RemoteConsoleLinePump: Send {
     fn poll_lines(&mut self, out: &mut Vec<String>);
@@ -58,6 +73,8 @@ pub trait RemoteConsoleLinePump: Send {
 #[derive(Default)]
 pub struct LoopRuntimeHooks {
     pub remote_console_pump: Option<Box<dyn RemoteConsoleLinePump>>,
+    /// Logs every remote line and command the loop handles (`thruport.diag`).
+    pub thruport_diag: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -73,7 +90,28 @@ pub struct LoopConfig {
     pub fps_cap: Option<u32>,
     pub sim_budget_ms: Option<f32>,
     pub render_budget_ms: Option<f32>,
+    /// Initial state of `thruport.telemetry`; the console command toggles it at runtime.
+    pub thruport_telemetry: bool,
     pub content_plan_request: ContentPlanRequest,
+    /// Game actions added to the input map alongside the engine's own.
+    pub input_actions: Vec<InputActionDef>,
+    /// Ticks between rewind snapshots of the active scene; 0 disables rewind.
+    pub rewind_interval_ticks: u32,
+    /// Rewind snapshots kept; history reaches back about `interval * count` ticks.
+    pub rewind_snapshot_count: usize,
+    /// Frames kept for `metrics.summary`, `metrics.export` and the perf graph; 0 disables.
+    pub metrics_history_len: usize,
+    /// Game cvars registered after the engine's own; `Scene::apply_cvar` receives changes.
+    pub cvars: Vec<CvarDef>,
+    /// Cvars set explicitly at launch (env or CLI). Their `user_cvars.json` values are kept
+    /// for later runs but not applied, so launch settings win over persisted ones.
+    pub pinned_cvars: Vec<String>,
+    /// Initial `metrics.export_on_exit` choice: `off`, `csv` or `json`.
+    pub metrics_export_on_exit: String,
+    /// Replay file to play back from the first tick.
+    pub replay_path: Option<PathBuf>,
+    /// Replay file to record this run into; ignored while a replay plays.
+    pub record_replay_path: Option<PathBuf>,
 }
 
 impl Default for LoopConfig {
@@ -90,7 +128,17 @@ impl Default for LoopConfig {
             fps_cap: None,
             sim_budget_ms: None,
             render_budget_ms: None,
+            thruport_telemetry: false,
             content_plan_request: ContentPlanRequest::default(),
+            input_actions: Vec::new(),
+            rewind_interval_ticks: 60,
+            rewind_snapshot_count: 30,
+            metrics_history_len: 600,
+            cvars: Vec::new(),
+            pinned_cvars: Vec::new(),
+            metrics_export_on_exit: "off".to_string(),
+            replay_path: None,
+            record_replay_path: None,
         }
     }
 }
@@ -109,6 +157,10 @@ pub enum AppError {
     ContentPipeline(#[from] ContentPipelineError),
     #[error("event loop failed: {0}")]
     EventLoopRun(#[source] EventLoopError),
+    #[error("invalid game input actions: {0}")]
+    InputActions(#[source] InputMapError),
+    #[error("invalid cvar registration: {0}")]
+    Cvars(String),
 }
 
 pub fn run_app(
@@ -168,6 +220,18 @@ fn run_app_with_metrics_and_hooks(
         "startup"
     );
     let def_database = build_or_load_def_database(&app_paths, &config.content_plan_request)?;
+    let input_map = load_input_map(
+        &app_paths.root.join(super::INPUT_MAP_FILE_NAME),
+        &config.input_actions,
+    )
+    .map_err(AppError::InputActions)?;
+    let mut cvars = build_cvar_registry(&config).map_err(AppError::Cvars)?;
+    let user_cvars_path = app_paths.root.join(super::USER_CVARS_FILE_NAME);
+    load_user_cvars(&mut cvars, &user_cvars_path, &config.pinned_cvars);
+    let mut live = LiveSettings::from_cvars(&cvars);
+    for (def, value) in cvars.iter() {
+        scenes.apply_cvar_for_all(def.name(), value);
+    }
 
     let event_loop = EventLoop::new().map_err(AppError::CreateEventLoop)?;
     let window = Arc::new(
@@ -196,21 +260,51 @@ fn run_app_with_metrics_and_hooks(
         normalize_non_zero_duration(config.metrics_log_interval, Duration::from_secs(1));
     let fixed_dt = Duration::from_secs_f64(1.0 / target_tps as f64);
     let fixed_dt_seconds = fixed_dt.as_secs_f32();
-    let slow_frame_delay = resolve_slow_frame_delay(config.simulated_slow_frame_ms);
-    let effective_render_cap = normalize_render_fps_cap(config.fps_cap);
-    let sim_budget_ms = normalize_soft_budget_ms(config.sim_budget_ms);
-    let render_budget_ms = normalize_soft_budget_ms(config.render_budget_ms);
-    let mut sim_budget_gate = sim_budget_ms.map(|threshold| {
-        SoftBudgetWarningGate::new(threshold, SOFT_BUDGET_CONSECUTIVE_BREACH_FRAMES)
-    });
-    let mut render_budget_gate = render_budget_ms.map(|threshold| {
-        SoftBudgetWarningGate::new(threshold, SOFT_BUDGET_CONSECUTIVE_BREACH_FRAMES)
-    });
-    let render_frame_target = target_frame_duration(effective_render_cap);
     let mut input_collector = InputCollector::new(config.window_width, config.window_height);
+    input_collector.set_input_map(input_map);
+    let content_hash = def_database.content_hash_sha256_hex().to_string();
+    let mut replay_player = config
+        .replay_path
+        .as_deref()
+        .and_then(|path| open_replay(path, &content_hash, target_tps));
+    if let Some(player) = replay_player.as_ref() {
+        scenes.set_particle_seed_for_all(player.header().seed);
+    }
     scenes.set_def_database_for_all(def_database);
     scenes.load_active();
     scenes.apply_pending_active();
+    if let Some(scene) = replay_player
+        .as_ref()
+        .and_then(|player| player.header().scene_key())
+    {
+        if scenes.switch_to(scene) {
+            scenes.apply_pending_active();
+        }
+    }
+    let mut replay_recorder = if replay_player.is_some() {
+        if let Some(path) = config.record_replay_path.as_deref() {
+            warn!(path = %path.display(), "replay_record_ignored_while_replaying");
+        }
+        None
+    } else {
+        config.record_replay_path.as_deref().and_then(|path| {
+            create_replay_recorder(
+                path,
+                ReplayHeader::new(
+                    &content_hash,
+                    scenes.active_world().particle_seed(),
+                    scenes.active_scene(),
+                    target_tps,
+                ),
+            )
+        })
+    };
+    let mut replay_commands = Vec::<DebugCommand>::new();
+    let mut rewind_history = RewindHistory::new(
+        config.rewind_interval_ticks,
+        config.rewind_snapshot_count,
+        &scenes,
+    );
     info!(
         scene = ?scenes.active_scene(),
         entity_count = scenes.active_world().entity_count(),
@@ -222,8 +316,8 @@ fn run_app_with_metrics_and_hooks(
         max_frame_delta_ms = max_frame_delta.as_millis() as u64,
         max_ticks_per_frame,
         metrics_log_interval_ms = metrics_log_interval.as_millis() as u64,
-        slow_frame_delay_ms = slow_frame_delay.as_millis() as u64,
-        render_fps_cap = %format_render_cap(effective_render_cap),
+        slow_frame_delay_ms = live.slow_frame_delay.as_millis() as u64,
+        render_fps_cap = %format_render_cap(live.render_fps_cap),
         "loop_config"
     );
 
@@ -232,8 +326,9 @@ fn run_app_with_metrics_and_hooks(
     let mut last_present_instant = Instant::now();
     let mut metrics_accumulator = MetricsAccumulator::new(metrics_log_interval);
     let mut perf_stats = PerfStats::new();
+    let mut zone_profiler = ZoneProfiler::new();
+    let mut metrics_history = MetricsHistory::new(config.metrics_history_len);
     let mut last_applied_title: Option<String> = None;
-    let mut overlay_visible = true;
     let mut sim_paused = false;
     let mut queued_manual_ticks = 0u32;
     let mut tick_counter = 0u64;
@@ -249,15 +344,15 @@ fn run_app_with_metrics_and_hooks(
     let mut drained_debug_commands = Vec::<DebugCommand>::new();
     let mut remote_console_lines = Vec::<String>::new();
     let mut remote_console_output_lines = Vec::<String>::new();
-    let mut thruport_telemetry_enabled = parse_enabled_flag_from_env(THRUPORT_TELEMETRY_ENV_VAR);
+    let mut sim_control = SimRunControl::default();
     info!(
         perf_stats_enabled_by_default = PerfStats::enabled_by_default(),
         perf_window_frames = PerfStats::window_len(),
         "perf_stats_config"
     );
     info!(
-        sim_budget_ms,
-        render_budget_ms,
+        sim_budget_ms = live.sim_budget_ms,
+        render_budget_ms = live.render_budget_ms,
         consecutive_breach_frames = SOFT_BUDGET_CONSECUTIVE_BREACH_FRAMES,
         "perf_budget_config"
     );
@@ -304,10 +399,18 @@ fn run_app_with_metrics_and_hooks(
                             )
                         {
                             // Palette consumed this press; do not route to gameplay input edges.
+                        } else if state == ElementState::Pressed
+                            && !console.is_open()
+                            && handle_minimap_mouse_press(&mut scenes, &input_collector, button)
+                        {
+                            // Minimap click recentered the camera; not a gameplay click.
                         } else if !console.is_open() {
                             input_collector.handle_mouse_input(button, state);
                         }
                     }
+                    WindowEvent::ModifiersChanged(modifiers) => {
+                        input_collector.set_modifiers(modifiers.state().into());
+                    }
                     WindowEvent::MouseWheel { delta, .. } => {
                         if !console.is_open() {
                             input_collector.handle_mouse_wheel(delta);
@@ -327,14 +430,20 @@ fn run_app_with_metrics_and_hooks(
                     }
                     WindowEvent::RedrawRequested => {
                         if input_collector.take_overlay_toggle_pressed() {
-                            overlay_visible = !overlay_visible;
-                            info!(overlay_visible, "overlay_toggled");
+                            live.overlay_visible = !live.overlay_visible;
+                            info!(overlay_visible = live.overlay_visible, "overlay_toggled");
                         }
                         if input_collector.take_console_toggle_pressed() {
                             console.toggle_open();
                             input_collector.reset_gameplay_inputs();
                             info!(console_open = console.is_open(), "console_toggled");
                         }
+                        let sim_speed_steps = input_collector.take_sim_speed_steps();
+                        if sim_speed_steps != 0 {
+                            let sim_speed = sim_control.step_speed(sim_speed_steps);
+                            info!(sim_speed, "sim_speed_changed");
+                        }
+                        let commands_zone = profile_zone("commands");
                         poll_remote_console_lines_into_console(
                             &mut runtime_hooks,
                             &mut console,
@@ -344,6 +453,25 @@ fn run_app_with_metrics_and_hooks(
                         drained_debug_commands.clear();
                         console_command_processor
                             .drain_pending_debug_commands_into(&mut drained_debug_commands);
+                        if let Some(recorder) = replay_recorder.as_mut() {
+                            recorder.note_commands(
+                                &drained_debug_commands,
+                                cursor_world_from_input(&scenes, &input_collector),
+                            );
+                        }
+                        let restarts_rewind_history =
+                            drained_debug_commands.iter().any(|command| {
+                                matches!(
+                                    command,
+                                    DebugCommand::ResetScene | DebugCommand::SwitchScene { .. }
+                                )
+                            });
+                        if let Some(history) = rewind_history.as_mut() {
+                            history.note_commands(
+                                &drained_debug_commands,
+                                cursor_world_from_input(&scenes, &input_collector),
+                            );
+                        }
                         if execute_drained_debug_commands(
                             &mut drained_debug_commands,
                             &mut scenes,
@@ -352,11 +480,114 @@ fn run_app_with_metrics_and_hooks(
                             &mut sim_paused,
                             &mut queued_manual_ticks,
                             &mut runtime_hooks,
-                            &mut thruport_telemetry_enabled,
+                            &mut live.thruport_telemetry,
+                            &mut sim_control,
                         ) {
                             info!(reason = "console_quit_command", "shutdown_requested");
                             window_target.exit();
                         }
+                        if let (true, Some(history)) =
+                            (restarts_rewind_history, rewind_history.as_mut())
+                        {
+                            history.restart(&scenes);
+                        }
+                        if let Some(ticks) = sim_control.rewind_request.take() {
+                            let rewound = match rewind_history.as_mut() {
+                                None => Err(RewindError::Disabled),
+                                Some(_) if replay_player.is_some() || replay_recorder.is_some() => {
+                                    Err(RewindError::ReplayActive)
+                                }
+                                Some(history) => history
+                                    .rewind(u64::from(ticks), &mut scenes)
+                                    .map(|resim| (history, resim)),
+                            };
+                            match rewound {
+                                Ok((history, resim)) => {
+                                    // Re-run the recorded frames the way the tick loop ran
+                                    // them, recording them again as we go.
+                                    for frame in &resim.frames {
+                                        parse_recorded_commands(
+                                            frame.tick,
+                                            &frame.commands,
+                                            &console_command_processor,
+                                            &mut console,
+                                            &mut replay_commands,
+                                        );
+                                        history.note_commands(&replay_commands, None);
+                                        execute_drained_debug_commands(
+                                            &mut replay_commands,
+                                            &mut scenes,
+                                            &mut console,
+                                            &mut input_collector,
+                                            &mut sim_paused,
+                                            &mut queued_manual_ticks,
+                                            &mut runtime_hooks,
+                                            &mut live.thruport_telemetry,
+                                            &mut sim_control,
+                                        );
+                                        let input = frame.input.to_snapshot();
+                                        let _ = scenes.update_active(fixed_dt_seconds, &input);
+                                        scenes.apply_pending_active();
+                                        history.record_tick(&input, &scenes);
+                                    }
+                                    sim_paused = true;
+                                    queued_manual_ticks = 0;
+                                    sim_control.tick_until = None;
+                                    // Restored scene state predates any cvar changes since.
+                                    for (def, value) in cvars.iter() {
+                                        scenes.apply_cvar_for_all(def.name(), value);
+                                    }
+                                    info!(
+                                        ticks,
+                                        from_tick = resim.from_tick,
+                                        tick = resim.target_tick,
+                                        "rewound"
+                                    );
+                                    console.append_output_line(format!(
+                                        "ok: rewind v1 ticks:{ticks} tick:{} from:{} resim:{}",
+                                        resim.target_tick,
+                                        resim.from_tick,
+                                        resim.frames.len()
+                                    ));
+                                }
+                                Err(error) => console
+                                    .append_output_line(format!("error: rewind {ticks}: {error}")),
+                            }
+                        }
+                        if let Some(ticks) = sim_control.profile_capture_request.take() {
+                            match zone_profiler.start_capture(ticks) {
+                                Ok(()) => {
+                                    info!(ticks, "profile_capture_started");
+                                    console.append_output_line(format!(
+                                        "ok: profile.capture started ticks:{ticks}"
+                                    ));
+                                }
+                                Err(error) => console.append_output_line(format!(
+                                    "error: profile.capture {ticks}: {error}"
+                                )),
+                            }
+                        }
+                        for request in sim_control.metrics_requests.drain(..) {
+                            run_metrics_request(
+                                request,
+                                metrics_history.as_ref(),
+                                &app_paths.cache_dir,
+                                &mut console,
+                            );
+                        }
+                        sync_toggle_cvars(&mut cvars, &live);
+                        for request in sim_control.cvar_requests.drain(..) {
+                            run_cvar_request(
+                                request,
+                                &mut cvars,
+                                &user_cvars_path,
+                                &mut live,
+                                &mut scenes,
+                                &mut console,
+                            );
+                        }
+                        drop(commands_zone);
+                        zone_profiler.finish_frame();
                         forward_console_output_lines_to_remote(
                             &mut runtime_hooks,
                             &mut console,
@@ -367,9 +598,9 @@ fn run_app_with_metrics_and_hooks(
                             &mut input_collector,
                         );
 
-                        if slow_frame_delay > Duration::ZERO {
+                        if live.slow_frame_delay > Duration::ZERO {
                             // Explicit debug perturbation only; this is not the FPS cap.
-                            thread::sleep(slow_frame_delay);
+                            thread::sleep(live.slow_frame_delay);
                         }
 
                         let now = Instant::now();
@@ -384,6 +615,7 @@ fn run_app_with_metrics_and_hooks(
                             clamped_frame_dt,
                             fixed_dt,
                             max_ticks_per_frame,
+                            sim_control.speed,
                         );
                         queued_manual_ticks = tick_plan.remaining_manual_ticks;
                         accumulator = tick_plan.remaining_accumulator;
@@ -391,10 +623,46 @@ fn run_app_with_metrics_and_hooks(
                         // starts immediately before the fixed-step tick loop for this frame.
                         let sim_timer_start = Instant::now();
                         for tick_index in 0..tick_plan.ticks_to_run {
-                            let input_snapshot =
+                            let tick_zone = profile_zone("tick");
+                            let mut input_snapshot =
                                 input_collector.snapshot_for_tick(console.is_open());
+                            if let Some(player) = replay_player.as_mut() {
+                                // Live input is still drained so its edges do not pile up, but
+                                // the tick consumes the recorded snapshot instead.
+                                let Some(replay_tick) = player.next_tick() else {
+                                    let ticks = player.tick_count();
+                                    info!(ticks, "replay_finished");
+                                    console.append_output_line(format!(
+                                        "replay: finished after {ticks} ticks; sim paused, live input restored"
+                                    ));
+                                    replay_player = None;
+                                    sim_paused = true;
+                                    break;
+                                };
+                                input_snapshot = replay_tick.input.to_snapshot();
+                                parse_recorded_commands(
+                                    replay_tick.tick,
+                                    &replay_tick.commands,
+                                    &console_command_processor,
+                                    &mut console,
+                                    &mut replay_commands,
+                                );
+                                execute_drained_debug_commands(
+                                    &mut replay_commands,
+                                    &mut scenes,
+                                    &mut console,
+                                    &mut input_collector,
+                                    &mut sim_paused,
+                                    &mut queued_manual_ticks,
+                                    &mut runtime_hooks,
+                                    &mut live.thruport_telemetry,
+                                    &mut sim_control,
+                                );
+                            }
+                            let update_zone = profile_zone("scene.update");
                             let command = scenes.update_active(fixed_dt_seconds, &input_snapshot);
                             scenes.apply_pending_active();
+                            drop(update_zone);
 
                             let switched = match command {
                                 SceneCommand::SwitchTo(next_scene) => scenes.switch_to(next_scene),
@@ -411,20 +679,98 @@ fn run_app_with_metrics_and_hooks(
                                     "scene_switched"
                                 );
                             }
+                            if let Some(history) = rewind_history.as_mut() {
+                                history.record_tick(&input_snapshot, &scenes);
+                                if switched {
+                                    history.restart(&scenes);
+                                }
+                            }
+                            let state_hash = (live.thruport_telemetry
+                                || replay_recorder.is_some()
+                                || replay_player.is_some())
+                            .then(|| {
+                                let _zone = profile_zone("state.hash");
+                                scenes.state_hash_active()
+                            });
+                            if let (Some(recorder), Some(state_hash)) =
+                                (replay_recorder.as_mut(), state_hash)
+                            {
+                                if let Err(error) = recorder.record_tick(&input_snapshot, state_hash)
+                                {
+                                    warn!(error = %error, "replay_record_failed");
+                                    replay_recorder = None;
+                                }
+                            }
+                            let diverged = replay_player
+                                .as_mut()
+                                .zip(state_hash)
+                                .and_then(|(player, state_hash)| player.verify(state_hash));
                             metrics_accumulator.record_tick();
                             emit_thruport_tick_telemetry_if_enabled(
-                                thruport_telemetry_enabled,
+                                live.thruport_telemetry,
                                 &mut tick_counter,
                                 sim_paused,
                                 queued_manual_ticks,
                                 tick_plan,
                                 tick_index,
                                 scenes.debug_info_snapshot_active(),
+                                state_hash,
                                 &mut runtime_hooks,
                             );
+                            drop(tick_zone);
+                            if let Some(capture) = zone_profiler.finish_tick() {
+                                write_profile_capture(&capture, &app_paths.cache_dir, &mut console);
+                            }
+                            if let Some(divergence) = diverged {
+                                warn!(
+                                    tick = divergence.tick,
+                                    expected = %format!("{:016x}", divergence.expected),
+                                    actual = %format!("{:016x}", divergence.actual),
+                                    "replay_diverged"
+                                );
+                                console.append_output_line(format!(
+                                    "replay: diverged at tick {} (expected {:016x}, got {:016x}); sim paused",
+                                    divergence.tick, divergence.expected, divergence.actual
+                                ));
+                                sim_paused = true;
+                                break;
+                            }
+                            if let Some((until, fired)) =
+                                sim_control.record_tick_until(|event| {
+                                    scenes.debug_event_fired_last_tick_active(event) == Some(true)
+                                })
+                            {
+                                info!(
+                                    event = %until.event,
+                                    fired,
+                                    ticks = until.ticks_run,
+                                    "tick_until_finished"
+                                );
+                                console.append_output_line(format!(
+                                    "ok: tick.until v1 event:{} fired:{} ticks:{}",
+                                    until.event,
+                                    u8::from(fired),
+                                    until.ticks_run
+                                ));
+                                queued_manual_ticks = 0;
+                                if sim_paused {
+                                    break;
+                                }
+                            }
                         }
 
-                        if tick_plan.dropped_backlog > Duration::ZERO {
+                        let speed_capped = sim_control.speed > 1
+                            && tick_plan.dropped_backlog > Duration::ZERO;
+                        if speed_capped != sim_control.speed_capped {
+                            sim_control.speed_capped = speed_capped;
+                            if speed_capped {
+                                info!(
+                                    sim_speed = sim_control.speed,
+                                    max_ticks_per_frame, "sim_speed_capped"
+                                );
+                            }
+                        }
+                        if tick_plan.dropped_backlog > Duration::ZERO && !speed_capped {
                             warn!(
                                 dropped_backlog_ms = tick_plan.dropped_backlog.as_millis() as u64,
                                 max_ticks_per_frame, "sim_clamp_triggered"
@@ -440,22 +786,37 @@ fn run_app_with_metrics_and_hooks(
                         let elapsed_since_last_present =
                             Instant::now().saturating_duration_since(last_present_instant);
                         let cap_sleep =
-                            compute_cap_sleep(elapsed_since_last_present, render_frame_target);
+                            compute_cap_sleep(elapsed_since_last_present, live.render_frame_target);
                         if cap_sleep > Duration::ZERO {
                             thread::sleep(cap_sleep);
                         }
 
-                        let overlay = overlay_visible.then(|| OverlayData {
+                        let overlay = live.overlay_visible.then(|| OverlayData {
                             metrics: metrics_handle.snapshot(),
                             perf: perf_stats.snapshot(),
-                            render_fps_cap: effective_render_cap,
-                            slow_frame_delay_ms: slow_frame_delay.as_millis() as u64,
+                            render_fps_cap: live.render_fps_cap,
+                            slow_frame_delay_ms: live.slow_frame_delay.as_millis() as u64,
+                            sim_speed: sim_control.speed,
+                            sim_speed_capped: sim_control.speed_capped,
                             entity_count: scenes.active_world().entity_count(),
                             content_status: "loaded",
                             selected_entity: scenes.debug_selected_entity_active(),
                             selected_target: scenes.debug_selected_target_active(),
                             resource_count: scenes.debug_resource_count_active(),
                             debug_info: scenes.debug_info_snapshot_active(),
+                            zones: zone_profiler.zone_stats(OVERLAY_ZONE_LIMIT),
+                            perf_graph: metrics_history.as_ref().map(|history| PerfGraphData {
+                                sim_ms: history
+                                    .recent(PERF_GRAPH_SAMPLES)
+                                    .map(|sample| sample.sim_ms)
+                                    .collect(),
+                                render_ms: history
+                                    .recent(PERF_GRAPH_SAMPLES)
+                                    .map(|sample| sample.render_ms)
+                                    .collect(),
+                                sim_budget_ms: live.sim_budget_ms,
+                                render_budget_ms: live.render_budget_ms,
+                            }),
                         });
                         command_palette.rebuild_layout(
                             input_collector.window_size(),
@@ -471,6 +832,7 @@ fn run_app_with_metrics_and_hooks(
                         // starts immediately before scene render preparation.
                         // Excludes cap sleep and non-render loop housekeeping.
                         let render_timer_start = Instant::now();
+                        let render_zone = profile_zone("render");
                         scenes.render_active();
                         let render_result = renderer.render_world(
                             scenes.active_world(),
@@ -483,13 +845,34 @@ fn run_app_with_metrics_and_hooks(
                         // ends immediately after renderer.render_world returns.
                         // Includes scenes.render_active + renderer.render_world only.
                         let render_duration = render_timer_start.elapsed();
+                        drop(render_zone);
+                        zone_profiler.finish_frame();
 
                         perf_stats.record_frame(sim_duration, render_duration);
+                        if let Some(history) = metrics_history.as_mut() {
+                            history.record(
+                                raw_frame_dt.as_secs_f32() * 1000.0,
+                                sim_duration.as_secs_f32() * 1000.0,
+                                render_duration.as_secs_f32() * 1000.0,
+                                tick_plan.ticks_to_run,
+                            );
+                        }
                         let perf_snapshot = perf_stats.snapshot();
-                        maybe_warn_budget_breach("sim", &mut sim_budget_gate, perf_snapshot.sim);
-                        maybe_warn_budget_breach(
+                        let sim_over_budget = maybe_warn_budget_breach(
+                            "sim",
+                            &mut live.sim_budget_gate,
+                            perf_snapshot.sim,
+                        );
+                        if sim_over_budget && sim_control.speed > 1 {
+                            let sim_speed = sim_control.step_speed(-1);
+                            warn!(sim_speed, "sim_speed_throttled");
+                            console.append_output_line(format!(
+                                "sim: over budget; speed lowered to x{sim_speed}"
+                            ));
+                        }
+                        let _ = maybe_warn_budget_breach(
                             "render",
-                            &mut render_budget_gate,
+                            &mut live.render_budget_gate,
                             perf_snapshot.ren,
                         );
 
@@ -528,6 +911,26 @@ fn run_app_with_metrics_and_hooks(
                 window_for_loop.request_redraw();
             }
             Event::LoopExiting => {
+                if let Some(recorder) = replay_recorder.as_mut() {
+                    match recorder.flush() {
+                        Ok(()) => info!(ticks = recorder.ticks_recorded(), "replay_recorded"),
+                        Err(error) => warn!(error = %error, "replay_record_failed"),
+                    }
+                }
+                if let (Some(format), Some(history)) =
+                    (live.metrics_export_on_exit, metrics_history.as_ref())
+                {
+                    match history.export(format, &metrics_export_dir(&app_paths.cache_dir), unix_millis())
+                    {
+                        Ok(path) => info!(
+                            format = format.token(),
+                            samples = history.len(),
+                            path = %path.display(),
+                            "metrics_exported"
+                        ),
+                        Err(error) => warn!(error = %error, "metrics_export_failed"),
+                    }
+                }
                 scenes.shutdown_all();
                 info!("shutdown");
             }
@@ -539,20 +942,18 @@ fn run_app_with_metrics_and_hooks(
 #[derive(Debug, Default)]
 struct InputCollector {
     quit_requested: bool,
-    console_toggle_is_down: bool,
+    input_map: InputMap,
+    modifiers: InputModifiers,
+    /// Triggers currently held down and the action each one is holding.
+    held_bindings: Vec<(InputTrigger, InputAction)>,
     console_toggle_pressed_edge: bool,
-    tab_is_down: bool,
     switch_scene_pressed_edge: bool,
-    overlay_toggle_is_down: bool,
     overlay_toggle_pressed_edge: bool,
-    save_key_is_down: bool,
     save_pressed_edge: bool,
-    load_key_is_down: bool,
     load_pressed_edge: bool,
-    zoom_in_key_is_down: bool,
-    zoom_out_key_is_down: bool,
     pending_zoom_steps: i32,
-    action_states: super::input::ActionStates,
+    pending_sim_speed_steps: i32,
+    action_states: ActionStates,
     cursor_position_px: Option<super::Vec2>,
     injected_cursor_position_px: Option<super::Vec2>,
     left_mouse_is_down: bool,
@@ -561,7 +962,7 @@ struct InputCollector {
     right_mouse_is_down: bool,
     right_click_pressed_edge: bool,
     injected_pending_events: VecDeque<InjectedInputEvent>,
-    injected_action_states: super::input::ActionStates,
+    injected_action_states: ActionStates,
     injected_left_mouse_is_down: bool,
     injected_left_click_pressed_edge: bool,
     injected_left_click_released_edge: bool,
@@ -585,39 +986,105 @@ impl InputCollector {
         self.quit_requested = true;
     }
 
+    fn set_input_map(&mut self, input_map: InputMap) {
+        self.input_map = input_map;
+    }
+
+    fn set_modifiers(&mut self, modifiers: InputModifiers) {
+        self.modifiers = modifiers;
+    }
+
+    fn rebind(&mut self, action: &str, bindings: Vec<InputBinding>) -> Result<(), InputMapError> {
+        self.input_map.rebind(action, bindings)
+    }
+
+    fn input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
     fn handle_keyboard_input(
         &mut self,
         key_event: &winit::event::KeyEvent,
         console_open: bool,
     ) -> bool {
-        self.handle_console_toggle_key_state(is_console_toggle_key(key_event), key_event.state);
-        if console_open {
-            return !is_console_toggle_key(key_event);
-        }
-
-        self.update_action_state_from_key_event(key_event);
-        self.handle_key_state(is_tab_key(key_event), key_event.state);
-        self.handle_overlay_toggle_key_state(is_overlay_toggle_key(key_event), key_event.state);
-        self.handle_save_key_state(is_save_key(key_event), key_event.state);
-        self.handle_load_key_state(is_load_key(key_event), key_event.state);
-        self.handle_zoom_in_key_state(is_zoom_in_key(key_event), key_event.state);
-        self.handle_zoom_out_key_state(is_zoom_out_key(key_event), key_event.state);
-        false
+        let PhysicalKey::Code(code) = key_event.physical_key else {
+            return console_open;
+        };
+        let is_pressed = key_event.state == ElementState::Pressed;
+        let toggles_console =
+            self.apply_input_trigger(InputTrigger::Key(code), is_pressed, console_open);
+        console_open && !toggles_console
     }
 
-    fn handle_key_state(&mut self, is_tab: bool, state: ElementState) {
-        if !is_tab {
-            return;
+    /// Presses or releases `trigger`, holding every action its bindings map to until the same
+    /// trigger is released. While the console is open only the console toggle is live. Returns
+    /// whether the trigger drives the console toggle.
+    fn apply_input_trigger(
+        &mut self,
+        trigger: InputTrigger,
+        is_pressed: bool,
+        console_open: bool,
+    ) -> bool {
+        let mut toggles_console = false;
+        if !is_pressed {
+            let mut released = Vec::new();
+            self.held_bindings.retain(|&(held, action)| {
+                if held == trigger {
+                    released.push(action);
+                }
+                held != trigger
+            });
+            for action in released {
+                toggles_console |= action == InputAction::ToggleConsole;
+                if !self.held_bindings.iter().any(|&(_, held)| held == action) {
+                    self.action_states.set(action, false);
+                }
+            }
+            return toggles_console;
         }
 
-        match state {
-            ElementState::Pressed => {
-                if !self.tab_is_down {
-                    self.switch_scene_pressed_edge = true;
-                }
-                self.tab_is_down = true;
+        if self.held_bindings.iter().any(|&(held, _)| held == trigger) {
+            // Key repeat: the trigger already holds its actions.
+            return self
+                .held_bindings
+                .iter()
+                .any(|&(held, action)| held == trigger && action == InputAction::ToggleConsole);
+        }
+        for action in self.input_map.actions_for(trigger, self.modifiers) {
+            if console_open && action != InputAction::ToggleConsole {
+                continue;
+            }
+            toggles_console |= action == InputAction::ToggleConsole;
+            self.held_bindings.push((trigger, action));
+            if !self.action_states.is_down(action) {
+                self.handle_action_pressed(action);
+            }
+            self.action_states.set(action, true);
+        }
+        toggles_console
+    }
+
+    fn handle_action_pressed(&mut self, action: InputAction) {
+        match action {
+            InputAction::ToggleConsole => self.console_toggle_pressed_edge = true,
+            InputAction::ToggleOverlay => self.overlay_toggle_pressed_edge = true,
+            InputAction::SwitchScene => self.switch_scene_pressed_edge = true,
+            InputAction::Save => self.save_pressed_edge = true,
+            InputAction::Load => self.load_pressed_edge = true,
+            InputAction::ZoomIn => {
+                self.pending_zoom_steps = self.pending_zoom_steps.saturating_add(1);
+            }
+            InputAction::ZoomOut => {
+                self.pending_zoom_steps = self.pending_zoom_steps.saturating_sub(1);
+            }
+            InputAction::SimSpeedUp => {
+                self.pending_sim_speed_steps = self.pending_sim_speed_steps.saturating_add(1);
+            }
+            InputAction::SimSpeedDown => {
+                self.pending_sim_speed_steps = self.pending_sim_speed_steps.saturating_sub(1);
             }
-            ElementState::Released => self.tab_is_down = false,
+            InputAction::Quit => self.mark_quit_requested(),
+            _ => {}
         }
     }
 
@@ -637,7 +1104,7 @@ impl InputCollector {
             InputSnapshot::new(
                 false,
                 false,
-                super::input::ActionStates::default(),
+                ActionStates::default(),
                 merged_cursor_position_px,
                 false,
                 false,
@@ -685,6 +1152,10 @@ impl InputCollector {
         was_pressed
     }
 
+    fn take_sim_speed_steps(&mut self) -> i32 {
+        std::mem::take(&mut self.pending_sim_speed_steps)
+    }
+
     fn take_console_toggle_pressed(&mut self) -> bool {
         let was_pressed = self.console_toggle_pressed_edge;
         self.console_toggle_pressed_edge = false;
@@ -692,16 +1163,17 @@ impl InputCollector {
     }
 
     fn reset_gameplay_inputs(&mut self) {
-        self.action_states = super::input::ActionStates::default();
-        self.injected_action_states = super::input::ActionStates::default();
-        self.tab_is_down = false;
+        // The console toggle stays held so its release still pairs with the press.
+        self.held_bindings
+            .retain(|&(_, action)| action == InputAction::ToggleConsole);
+        self.action_states = ActionStates::default();
+        if !self.held_bindings.is_empty() {
+            self.action_states.set(InputAction::ToggleConsole, true);
+        }
+        self.injected_action_states = ActionStates::default();
         self.switch_scene_pressed_edge = false;
-        self.save_key_is_down = false;
         self.save_pressed_edge = false;
-        self.load_key_is_down = false;
         self.load_pressed_edge = false;
-        self.zoom_in_key_is_down = false;
-        self.zoom_out_key_is_down = false;
         self.pending_zoom_steps = 0;
         self.injected_cursor_position_px = None;
         self.left_mouse_is_down = false;
@@ -731,7 +1203,7 @@ impl InputCollector {
     }
 
     fn clear_injected_held_inputs(&mut self) {
-        self.injected_action_states = super::input::ActionStates::default();
+        self.injected_action_states = ActionStates::default();
         self.injected_cursor_position_px = None;
         self.injected_left_mouse_is_down = false;
         self.injected_left_click_pressed_edge = false;
@@ -763,7 +1235,7 @@ impl InputCollector {
         }
     }
 
-    fn merged_action_states(&self) -> super::input::ActionStates {
+    fn merged_action_states(&self) -> ActionStates {
         let mut merged = self.action_states;
         const MERGEABLE_ACTIONS: [InputAction; 8] = [
             InputAction::MoveUp,
@@ -850,143 +1322,6 @@ impl InputCollector {
         }
     }
 
-    fn update_action_state_from_key_event(&mut self, key_event: &winit::event::KeyEvent) {
-        let is_pressed = key_event.state == ElementState::Pressed;
-        self.update_action_state_from_physical_key(key_event.physical_key, is_pressed);
-    }
-
-    fn handle_overlay_toggle_key_state(&mut self, is_toggle_key: bool, state: ElementState) {
-        if !is_toggle_key {
-            return;
-        }
-
-        match state {
-            ElementState::Pressed => {
-                if !self.overlay_toggle_is_down {
-                    self.overlay_toggle_pressed_edge = true;
-                }
-                self.overlay_toggle_is_down = true;
-            }
-            ElementState::Released => self.overlay_toggle_is_down = false,
-        }
-    }
-
-    fn handle_console_toggle_key_state(&mut self, is_toggle_key: bool, state: ElementState) {
-        if !is_toggle_key {
-            return;
-        }
-
-        match state {
-            ElementState::Pressed => {
-                if !self.console_toggle_is_down {
-                    self.console_toggle_pressed_edge = true;
-                }
-                self.console_toggle_is_down = true;
-            }
-            ElementState::Released => self.console_toggle_is_down = false,
-        }
-    }
-
-    fn update_action_state_from_physical_key(&mut self, key: PhysicalKey, is_pressed: bool) {
-        match key {
-            PhysicalKey::Code(KeyCode::KeyW) | PhysicalKey::Code(KeyCode::ArrowUp) => {
-                self.action_states.set(InputAction::MoveUp, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::KeyS) | PhysicalKey::Code(KeyCode::ArrowDown) => {
-                self.action_states.set(InputAction::MoveDown, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::KeyA) | PhysicalKey::Code(KeyCode::ArrowLeft) => {
-                self.action_states.set(InputAction::MoveLeft, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::KeyD) | PhysicalKey::Code(KeyCode::ArrowRight) => {
-                self.action_states.set(InputAction::MoveRight, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::KeyI) => {
-                self.action_states.set(InputAction::CameraUp, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::KeyK) => {
-                self.action_states.set(InputAction::CameraDown, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::KeyJ) => {
-                self.action_states.set(InputAction::CameraLeft, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::KeyL) => {
-                self.action_states.set(InputAction::CameraRight, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::F3) => {
-                self.action_states
-                    .set(InputAction::ToggleOverlay, is_pressed);
-            }
-            PhysicalKey::Code(KeyCode::Escape) => {
-                self.action_states.set(InputAction::Quit, is_pressed);
-                if is_pressed {
-                    self.mark_quit_requested();
-                }
-            }
-            _ => {}
-        }
-    }
-
-    fn handle_save_key_state(&mut self, is_save_key: bool, state: ElementState) {
-        if !is_save_key {
-            return;
-        }
-        match state {
-            ElementState::Pressed => {
-                if !self.save_key_is_down {
-                    self.save_pressed_edge = true;
-                }
-                self.save_key_is_down = true;
-            }
-            ElementState::Released => self.save_key_is_down = false,
-        }
-    }
-
-    fn handle_load_key_state(&mut self, is_load_key: bool, state: ElementState) {
-        if !is_load_key {
-            return;
-        }
-        match state {
-            ElementState::Pressed => {
-                if !self.load_key_is_down {
-                    self.load_pressed_edge = true;
-                }
-                self.load_key_is_down = true;
-            }
-            ElementState::Released => self.load_key_is_down = false,
-        }
-    }
-
-    fn handle_zoom_in_key_state(&mut self, is_zoom_in_key: bool, state: ElementState) {
-        if !is_zoom_in_key {
-            return;
-        }
-        match state {
-            ElementState::Pressed => {
-                if !self.zoom_in_key_is_down {
-                    self.pending_zoom_steps = self.pending_zoom_steps.saturating_add(1);
-                }
-                self.zoom_in_key_is_down = true;
-            }
-            ElementState::Released => self.zoom_in_key_is_down = false,
-        }
-    }
-
-    fn handle_zoom_out_key_state(&mut self, is_zoom_out_key: bool, state: ElementState) {
-        if !is_zoom_out_key {
-            return;
-        }
-        match state {
-            ElementState::Pressed => {
-                if !self.zoom_out_key_is_down {
-                    self.pending_zoom_steps = self.pending_zoom_steps.saturating_sub(1);
-                }
-                self.zoom_out_key_is_down = true;
-            }
-            ElementState::Released => self.zoom_out_key_is_down = false,
-        }
-    }
-
     fn set_window_size(&mut self, width: u32, height: u32) {
         self.window_width = width;
         self.window_height = height;
@@ -1014,6 +1349,11 @@ impl InputCollector {
     }
 
     fn handle_mouse_input(&mut self, button: MouseButton, state: ElementState) {
+        self.apply_input_trigger(
+            InputTrigger::Mouse(button),
+            state == ElementState::Pressed,
+            false,
+        );
         match button {
             MouseButton::Left => match state {
                 ElementState::Pressed => {
@@ -1054,7 +1394,7 @@ fn poll_remote_console_lines_into_console(
 
     scratch.clear();
     pump.poll_lines(scratch);
-    if thruport_diag_enabled() && !scratch.is_empty() {
+    if hooks.thruport_diag && !scratch.is_empty() {
         for line in scratch.iter() {
             info!(line = %line, "thruport_diag_engine_polled_remote_line");
         }
@@ -1075,7 +1415,7 @@ fn forward_console_output_lines_to_remote(
 
     scratch.clear();
     console.drain_new_output_lines_into(scratch);
-    if thruport_diag_enabled() && !scratch.is_empty() {
+    if hooks.thruport_diag && !scratch.is_empty() {
         for line in scratch.iter() {
             info!(line = %line, "thruport_diag_engine_forward_output_line");
         }
@@ -1094,12 +1434,13 @@ fn execute_drained_debug_commands(
     queued_manual_ticks: &mut u32,
     runtime_hooks: &mut LoopRuntimeHooks,
     thruport_telemetry_enabled: &mut bool,
+    sim_control: &mut SimRunControl,
 ) -> bool {
     let mut quit_requested = false;
     let mut should_apply_after_batch = false;
 
     for command in commands.drain(..) {
-        if thruport_diag_enabled() {
+        if runtime_hooks.thruport_diag {
             info!(
                 command = %debug_command_token(&command),
                 "thruport_diag_engine_execute_command"
@@ -1168,6 +1509,82 @@ fn execute_drained_debug_commands(
                     scenes.execute_debug_command_active(SceneDebugCommand::DumpAi, context);
                 append_scene_debug_result(console, result);
             }
+            DebugCommand::TimersList => {
+                append_timers_list(console, scenes.active_world());
+            }
+            DebugCommand::StateHash => {
+                console.append_output_line(format!(
+                    "ok: state.hash v1 tick:{} hash:{:016x}",
+                    scenes.active_world().timer_tick(),
+                    scenes.state_hash_active()
+                ));
+            }
+            DebugCommand::SimSpeed { multiplier } => match multiplier {
+                Some(multiplier) if multiplier > MAX_SIM_SPEED => {
+                    console.append_output_line(format!(
+                        "error: sim speed must be 1..={MAX_SIM_SPEED} (got {multiplier})"
+                    ));
+                }
+                multiplier => {
+                    if let Some(multiplier) = multiplier {
+                        sim_control.speed = multiplier;
+                        info!(sim_speed = multiplier, "sim_speed_changed");
+                    }
+                    console.append_output_line(format!(
+                        "ok: sim.speed v1 speed:{}",
+                        sim_control.speed
+                    ));
+                }
+            },
+            DebugCommand::TickUntil { event, max_ticks } => {
+                if scenes.debug_event_fired_last_tick_active(&event).is_none() {
+                    console.append_output_line(format!(
+                        "error: unknown event '{event}' for active scene"
+                    ));
+                } else {
+                    let max_ticks = max_ticks.unwrap_or(TICK_UNTIL_DEFAULT_MAX_TICKS);
+                    *sim_paused = true;
+                    *queued_manual_ticks = max_ticks;
+                    console.append_output_line(format!(
+                        "ok: tick.until started event:{event} max_ticks:{max_ticks}"
+                    ));
+                    sim_control.tick_until = Some(TickUntil {
+                        event,
+                        max_ticks,
+                        ticks_run: 0,
+                    });
+                }
+            }
+            DebugCommand::Rewind { ticks } => {
+                // Needs the loop's rewind history, so the loop runs it after this batch.
+                sim_control.rewind_request = Some(ticks);
+            }
+            DebugCommand::ProfileCapture { ticks } => {
+                // The zone profiler is loop-owned, so the loop starts the capture after this batch.
+                sim_control.profile_capture_request = Some(ticks);
+            }
+            DebugCommand::MetricsSummary => {
+                sim_control.metrics_requests.push(MetricsRequest::Summary);
+            }
+            DebugCommand::MetricsExport { format } => {
+                sim_control
+                    .metrics_requests
+                    .push(MetricsRequest::Export(format));
+            }
+            DebugCommand::CvarSet { name, value } => {
+                sim_control
+                    .cvar_requests
+                    .push(CvarRequest::Set { name, value });
+            }
+            DebugCommand::CvarGet { name } => {
+                sim_control.cvar_requests.push(CvarRequest::Get { name });
+            }
+            DebugCommand::CvarList { prefix } => {
+                sim_control.cvar_requests.push(CvarRequest::List { prefix });
+            }
+            DebugCommand::CvarReset { name } => {
+                sim_control.cvar_requests.push(CvarRequest::Reset { name });
+            }
             DebugCommand::ScenarioSetup { scenario_id } => {
                 let context = SceneDebugContext {
                     cursor_world: cursor_world_from_input(scenes, input_collector),
@@ -1233,12 +1650,14 @@ fn execute_drained_debug_commands(
                 append_scene_debug_result(console, result);
                 should_apply_after_batch = true;
             }
-            DebugCommand::OrderMove { x, y } => {
+            DebugCommand::OrderMove { x, y, floor } => {
                 let context = SceneDebugContext {
                     cursor_world: cursor_world_from_input(scenes, input_collector),
                 };
-                let result = scenes
-                    .execute_debug_command_active(SceneDebugCommand::OrderMove { x, y }, context);
+                let result = scenes.execute_debug_command_active(
+                    SceneDebugCommand::OrderMove { x, y, floor },
+                    context,
+                );
                 append_scene_debug_result(console, result);
                 should_apply_after_batch = true;
             }
@@ -1260,6 +1679,16 @@ fn execute_drained_debug_commands(
                     injected_event_debug_text(event)
                 ));
             }
+            DebugCommand::InputBind { action, bindings } => {
+                let line = format!("{action} {}", format_input_bindings(&bindings));
+                match input_collector.rebind(&action, bindings) {
+                    Ok(()) => console.append_output_line(format!("ok: input.bind {line}")),
+                    Err(error) => console.append_output_line(format!("error: {error}")),
+                }
+            }
+            DebugCommand::InputBindings { action } => {
+                append_input_bindings(console, input_collector.input_map(), action.as_deref());
+            }
         }
     }
 
@@ -1270,74 +1699,434 @@ fn execute_drained_debug_commands(
     quit_requested
 }
 
-fn debug_command_token(command: &DebugCommand) -> &'static str {
-    match command {
-        DebugCommand::Quit => "quit",
-        DebugCommand::ResetScene => "reset_scene",
-        DebugCommand::Sync => "sync",
-        DebugCommand::ThruportStatus => "thruport.status",
-        DebugCommand::ThruportTelemetry { .. } => "thruport.telemetry",
-        DebugCommand::PauseSim => "pause_sim",
-        DebugCommand::ResumeSim => "resume_sim",
-        DebugCommand::Tick { .. } => "tick",
-        DebugCommand::DumpState => "dump.state",
-        DebugCommand::DumpAi => "dump.ai",
-        DebugCommand::ScenarioSetup { .. } => "scenario.setup",
-        DebugCommand::FloorSet { .. } => "floor.set",
-        DebugCommand::SwitchScene { .. } => "switch_scene",
-        DebugCommand::Spawn { .. } => "spawn",
-        DebugCommand::Despawn { .. } => "despawn",
-        DebugCommand::Select { .. } => "select",
-        DebugCommand::OrderMove { .. } => "order.move",
-        DebugCommand::OrderInteract { .. } => "order.interact",
-        DebugCommand::InjectInput { .. } => "inject_input",
+/// Writes a finished `profile.capture` under `<cache>/profiles/` and reports it on the console.
+fn write_profile_capture(capture: &TraceCapture, cache_dir: &Path, console: &mut ConsoleState) {
+    let path = cache_dir
+        .join("profiles")
+        .join(format!("trace_{}.json", unix_millis()));
+    match capture.write_to(&path) {
+        Ok(()) => {
+            info!(
+                ticks = capture.ticks,
+                events = capture.event_count(),
+                dropped_events = capture.dropped_events(),
+                path = %path.display(),
+                "profile_capture_written"
+            );
+            console.append_output_line(format!(
+                "ok: profile.capture v1 ticks:{} events:{} path:{}",
+                capture.ticks,
+                capture.event_count(),
+                path.display()
+            ));
+        }
+        Err(error) => {
+            warn!(error = %error, path = %path.display(), "profile_capture_write_failed");
+            console.append_output_line(format!(
+                "error: profile.capture failed to write {}: {error}",
+                path.display()
+            ));
+        }
     }
 }
 
-fn thruport_diag_enabled() -> bool {
-    matches!(
-        std::env::var(THRUPORT_DIAG_ENV_VAR).ok().as_deref(),
-        Some("1")
-    )
-}
-
-fn mark_injected_reset_if_remote_disconnected(
-    hooks: &mut LoopRuntimeHooks,
-    input_collector: &mut InputCollector,
+fn run_metrics_request(
+    request: MetricsRequest,
+    history: Option<&MetricsHistory>,
+    cache_dir: &Path,
+    console: &mut ConsoleState,
 ) {
-    let Some(pump) = hooks.remote_console_pump.as_mut() else {
+    let name = match request {
+        MetricsRequest::Summary => "metrics.summary",
+        MetricsRequest::Export(_) => "metrics.export",
+    };
+    let Some(history) = history else {
+        console.append_output_line(format!(
+            "error: {name}: metrics history is disabled (metrics_history_len is 0)"
+        ));
         return;
     };
-
-    if pump.take_disconnect_reset_requested() {
-        input_collector.mark_injected_disconnect_reset_pending();
-    }
-}
-
-fn injected_event_debug_text(event: InjectedInputEvent) -> String {
-    match event {
-        InjectedInputEvent::KeyDown { key } => {
-            format!("input.key_down {}", injected_key_token(key))
-        }
-        InjectedInputEvent::KeyUp { key } => {
-            format!("input.key_up {}", injected_key_token(key))
-        }
-        InjectedInputEvent::MouseMove { x, y } => {
-            format!("input.mouse_move {} {}", x, y)
-        }
-        InjectedInputEvent::MouseDown { button } => {
-            format!("input.mouse_down {}", injected_mouse_button_token(button))
+    match request {
+        MetricsRequest::Summary => {
+            let summary = history.summary();
+            let format_ms = |p: super::metrics_history::Percentiles| {
+                format!("{:.2}/{:.2}/{:.2}", p.p50, p.p95, p.p99)
+            };
+            console.append_output_line(format!(
+                "ok: metrics.summary v1 samples:{} frame_ms:{} sim_ms:{} render_ms:{}",
+                summary.samples,
+                format_ms(summary.frame_ms),
+                format_ms(summary.sim_ms),
+                format_ms(summary.render_ms)
+            ));
         }
-        InjectedInputEvent::MouseUp { button } => {
-            format!("input.mouse_up {}", injected_mouse_button_token(button))
+        MetricsRequest::Export(format) => {
+            match history.export(format, &metrics_export_dir(cache_dir), unix_millis()) {
+                Ok(path) => {
+                    info!(
+                        format = format.token(),
+                        samples = history.len(),
+                        path = %path.display(),
+                        "metrics_exported"
+                    );
+                    console.append_output_line(format!(
+                        "ok: metrics.export v1 format:{} samples:{} path:{}",
+                        format.token(),
+                        history.len(),
+                        path.display()
+                    ));
+                }
+                Err(error) => {
+                    warn!(error = %error, "metrics_export_failed");
+                    console.append_output_line(format!("error: metrics.export: {error}"));
+                }
+            }
         }
     }
 }
 
-fn injected_key_token(key: InjectedKey) -> &'static str {
-    match key {
-        InjectedKey::W => "w",
-        InjectedKey::A => "a",
+fn metrics_export_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("metrics")
+}
+
+/// Engine cvars with defaults from `config`, then the game's `LoopConfig::cvars`.
+fn build_cvar_registry(config: &LoopConfig) -> Result<CvarRegistry, String> {
+    // Through the f32's shortest decimal, so a 16.6 ms budget reads back as 16.6.
+    let budget_default = |budget: Option<f32>| {
+        normalize_soft_budget_ms(budget).map_or(0.0, |ms| ms.to_string().parse().unwrap_or(0.0))
+    };
+    let engine_cvars = [
+        CvarDef::int(
+            FPS_CAP_CVAR,
+            i64::from(normalize_render_fps_cap(config.fps_cap).unwrap_or(0)),
+            0..=1000,
+            "Render FPS cap; 0 is uncapped",
+        )
+        .persistent(),
+        CvarDef::float(
+            SIM_BUDGET_CVAR,
+            budget_default(config.sim_budget_ms),
+            0.0..=1000.0,
+            "Sim soft budget in ms; 0 disables warnings and the speed throttle",
+        )
+        .persistent(),
+        CvarDef::float(
+            RENDER_BUDGET_CVAR,
+            budget_default(config.render_budget_ms),
+            0.0..=1000.0,
+            "Render soft budget in ms; 0 disables warnings",
+        )
+        .persistent(),
+        CvarDef::bool(
+            THRUPORT_TELEMETRY_CVAR,
+            config.thruport_telemetry,
+            "Push per-tick telemetry frames to thruport clients",
+        ),
+        CvarDef::int(
+            SLOW_FRAME_CVAR,
+            config.simulated_slow_frame_ms.min(1000) as i64,
+            0..=1000,
+            "Artificial per-frame delay in ms",
+        ),
+        CvarDef::bool(OVERLAY_CVAR, true, "Show the debug overlay (F3)"),
+        CvarDef::choice(
+            METRICS_EXPORT_ON_EXIT_CVAR,
+            &config.metrics_export_on_exit,
+            &["off", "csv", "json"],
+            "Export metrics history to the cache on exit",
+        )
+        .persistent(),
+    ];
+
+    let mut registry = CvarRegistry::default();
+    for def in engine_cvars.into_iter().chain(config.cvars.iter().cloned()) {
+        registry.register(def)?;
+    }
+    Ok(registry)
+}
+
+fn load_user_cvars(cvars: &mut CvarRegistry, path: &Path, pinned: &[String]) {
+    match cvars.load_user_file(path, pinned) {
+        Ok(skipped) => {
+            info!(
+                path = %path.display(),
+                exists = path.is_file(),
+                pinned = pinned.len(),
+                "user_cvars_loaded"
+            );
+            for entry in skipped {
+                warn!(path = %path.display(), entry = %entry, "user_cvar_skipped");
+            }
+        }
+        Err(error) => warn!(error = %error, "user cvars rejected; using defaults"),
+    }
+}
+
+/// Loop state driven by engine cvars. Telemetry and the overlay can also be toggled by the
+/// `thruport.telemetry` command and F3; `sync_toggle_cvars` mirrors those back.
+struct LiveSettings {
+    render_fps_cap: Option<u32>,
+    render_frame_target: Option<Duration>,
+    sim_budget_ms: Option<f32>,
+    render_budget_ms: Option<f32>,
+    sim_budget_gate: Option<SoftBudgetWarningGate>,
+    render_budget_gate: Option<SoftBudgetWarningGate>,
+    slow_frame_delay: Duration,
+    thruport_telemetry: bool,
+    overlay_visible: bool,
+    metrics_export_on_exit: Option<MetricsExportFormat>,
+}
+
+impl LiveSettings {
+    fn from_cvars(cvars: &CvarRegistry) -> Self {
+        let mut live = Self {
+            render_fps_cap: None,
+            render_frame_target: None,
+            sim_budget_ms: None,
+            render_budget_ms: None,
+            sim_budget_gate: None,
+            render_budget_gate: None,
+            slow_frame_delay: Duration::ZERO,
+            thruport_telemetry: false,
+            overlay_visible: true,
+            metrics_export_on_exit: None,
+        };
+        for (def, value) in cvars.iter() {
+            live.apply(def.name(), value);
+        }
+        live
+    }
+
+    /// Applies an engine cvar's new value; other names are ignored.
+    fn apply(&mut self, name: &str, value: &CvarValue) {
+        let budget = |value: &CvarValue| {
+            normalize_soft_budget_ms(value.as_float().map(|budget_ms| budget_ms as f32))
+        };
+        let budget_gate = |budget_ms: Option<f32>| {
+            budget_ms.map(|threshold| {
+                SoftBudgetWarningGate::new(threshold, SOFT_BUDGET_CONSECUTIVE_BREACH_FRAMES)
+            })
+        };
+        match name {
+            FPS_CAP_CVAR => {
+                let cap = value.as_int().and_then(|cap| u32::try_from(cap).ok());
+                self.render_fps_cap = normalize_render_fps_cap(cap);
+                self.render_frame_target = target_frame_duration(self.render_fps_cap);
+            }
+            SIM_BUDGET_CVAR => {
+                self.sim_budget_ms = budget(value);
+                self.sim_budget_gate = budget_gate(self.sim_budget_ms);
+            }
+            RENDER_BUDGET_CVAR => {
+                self.render_budget_ms = budget(value);
+                self.render_budget_gate = budget_gate(self.render_budget_ms);
+            }
+            THRUPORT_TELEMETRY_CVAR => {
+                self.thruport_telemetry = value.as_bool().unwrap_or(false);
+            }
+            SLOW_FRAME_CVAR => {
+                let delay_ms = value.as_int().unwrap_or(0).max(0) as u64;
+                self.slow_frame_delay = Duration::from_millis(delay_ms);
+            }
+            OVERLAY_CVAR => self.overlay_visible = value.as_bool().unwrap_or(true),
+            METRICS_EXPORT_ON_EXIT_CVAR => {
+                self.metrics_export_on_exit = value.as_str().and_then(MetricsExportFormat::parse);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn sync_toggle_cvars(cvars: &mut CvarRegistry, live: &LiveSettings) {
+    for (name, enabled) in [
+        (THRUPORT_TELEMETRY_CVAR, live.thruport_telemetry),
+        (OVERLAY_CVAR, live.overlay_visible),
+    ] {
+        let value = CvarValue::Bool(enabled);
+        if cvars.value(name) != Some(&value) {
+            let _ = cvars.set_value(name, value);
+        }
+    }
+}
+
+fn run_cvar_request(
+    request: CvarRequest,
+    cvars: &mut CvarRegistry,
+    user_cvars_path: &Path,
+    live: &mut LiveSettings,
+    scenes: &mut SceneMachine,
+    console: &mut ConsoleState,
+) {
+    let (command, changed) = match request {
+        CvarRequest::Get { name } => {
+            match cvars.lookup(&name) {
+                Some((def, value)) => console.append_output_line(format!(
+                    "ok: get v1 name:{} value:{value} default:{}",
+                    def.name(),
+                    def.default_value()
+                )),
+                None => console.append_output_line(format!("error: get: unknown cvar '{name}'")),
+            }
+            return;
+        }
+        CvarRequest::List { prefix } => {
+            let prefix = prefix.unwrap_or_default().to_ascii_lowercase();
+            let mut count = 0;
+            for (def, value) in cvars
+                .iter()
+                .filter(|(def, _)| def.name().to_ascii_lowercase().starts_with(&prefix))
+            {
+                let persistent = if def.is_persistent() {
+                    ", persistent"
+                } else {
+                    ""
+                };
+                console.append_output_line(format!(
+                    "{} {value} ({}, default {}{persistent}) - {}",
+                    def.name(),
+                    def.schema(),
+                    def.default_value(),
+                    def.help()
+                ));
+                count += 1;
+            }
+            console.append_output_line(format!("ok: cvarlist v1 count:{count}"));
+            return;
+        }
+        CvarRequest::Set { name, value } => ("set", cvars.set(&name, &value)),
+        CvarRequest::Reset { name } => ("reset", cvars.reset(&name)),
+    };
+    let (name, value, persistent) = match changed {
+        Ok((def, value)) => (def.name().to_string(), value.clone(), def.is_persistent()),
+        Err(error) => {
+            console.append_output_line(format!("error: {command}: {error}"));
+            return;
+        }
+    };
+
+    live.apply(&name, &value);
+    scenes.apply_cvar_for_all(&name, &value);
+    info!(name = %name, value = %value, "cvar_changed");
+    console.append_output_line(format!("ok: {command} v1 name:{name} value:{value}"));
+    if persistent {
+        if let Err(error) = cvars.save_user_file(user_cvars_path) {
+            warn!(error = %error, path = %user_cvars_path.display(), "user_cvars_save_failed");
+            console.append_output_line(format!(
+                "error: {command}: failed to save {}: {error}",
+                user_cvars_path.display()
+            ));
+        }
+    }
+}
+
+fn unix_millis() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis())
+}
+
+fn debug_command_token(command: &DebugCommand) -> &'static str {
+    match command {
+        DebugCommand::Quit => "quit",
+        DebugCommand::ResetScene => "reset_scene",
+        DebugCommand::Sync => "sync",
+        DebugCommand::ThruportStatus => "thruport.status",
+        DebugCommand::ThruportTelemetry { .. } => "thruport.telemetry",
+        DebugCommand::PauseSim => "pause_sim",
+        DebugCommand::ResumeSim => "resume_sim",
+        DebugCommand::Tick { .. } => "tick",
+        DebugCommand::DumpState => "dump.state",
+        DebugCommand::DumpAi => "dump.ai",
+        DebugCommand::TimersList => "timers.list",
+        DebugCommand::StateHash => "state.hash",
+        DebugCommand::SimSpeed { .. } => "sim.speed",
+        DebugCommand::TickUntil { .. } => "tick.until",
+        DebugCommand::Rewind { .. } => "rewind",
+        DebugCommand::ProfileCapture { .. } => "profile.capture",
+        DebugCommand::MetricsSummary => "metrics.summary",
+        DebugCommand::MetricsExport { .. } => "metrics.export",
+        DebugCommand::CvarSet { .. } => "set",
+        DebugCommand::CvarGet { .. } => "get",
+        DebugCommand::CvarList { .. } => "cvarlist",
+        DebugCommand::CvarReset { .. } => "reset",
+        DebugCommand::ScenarioSetup { .. } => "scenario.setup",
+        DebugCommand::FloorSet { .. } => "floor.set",
+        DebugCommand::SwitchScene { .. } => "switch_scene",
+        DebugCommand::Spawn { .. } => "spawn",
+        DebugCommand::Despawn { .. } => "despawn",
+        DebugCommand::Select { .. } => "select",
+        DebugCommand::OrderMove { .. } => "order.move",
+        DebugCommand::OrderInteract { .. } => "order.interact",
+        DebugCommand::InjectInput { .. } => "inject_input",
+        DebugCommand::InputBind { .. } => "input.bind",
+        DebugCommand::InputBindings { .. } => "input.bindings",
+    }
+}
+
+fn append_input_bindings(console: &mut ConsoleState, input_map: &InputMap, action: Option<&str>) {
+    match action {
+        Some(name) => match input_map.bindings(name) {
+            Some(bindings) => {
+                console.append_output_line(format!("{name} {}", format_input_bindings(bindings)));
+            }
+            None => console.append_output_line(format!("error: unknown input action '{name}'")),
+        },
+        None => {
+            for (name, bindings) in input_map.iter() {
+                console.append_output_line(format!("{name} {}", format_input_bindings(bindings)));
+            }
+        }
+    }
+}
+
+fn format_input_bindings(bindings: &[InputBinding]) -> String {
+    if bindings.is_empty() {
+        return "(unbound)".to_string();
+    }
+    bindings
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn mark_injected_reset_if_remote_disconnected(
+    hooks: &mut LoopRuntimeHooks,
+    input_collector: &mut InputCollector,
+) {
+    let Some(pump) = hooks.remote_console_pump.as_mut() else {
+        return;
+    };
+
+    if pump.take_disconnect_reset_requested() {
+        input_collector.mark_injected_disconnect_reset_pending();
+    }
+}
+
+fn injected_event_debug_text(event: InjectedInputEvent) -> String {
+    match event {
+        InjectedInputEvent::KeyDown { key } => {
+            format!("input.key_down {}", injected_key_token(key))
+        }
+        InjectedInputEvent::KeyUp { key } => {
+            format!("input.key_up {}", injected_key_token(key))
+        }
+        InjectedInputEvent::MouseMove { x, y } => {
+            format!("input.mouse_move {} {}", x, y)
+        }
+        InjectedInputEvent::MouseDown { button } => {
+            format!("input.mouse_down {}", injected_mouse_button_token(button))
+        }
+        InjectedInputEvent::MouseUp { button } => {
+            format!("input.mouse_up {}", injected_mouse_button_token(button))
+        }
+    }
+}
+
+fn injected_key_token(key: InjectedKey) -> &'static str {
+    match key {
+        InjectedKey::W => "w",
+        InjectedKey::A => "a",
         InjectedKey::S => "s",
         InjectedKey::D => "d",
         InjectedKey::Up => "up",
@@ -1372,6 +2161,28 @@ fn append_scene_debug_result(console: &mut ConsoleState, result: SceneDebugComma
     }
 }
 
+fn append_timers_list(console: &mut ConsoleState, world: &SceneWorld) {
+    let timers = world.timers();
+    console.append_output_line(format!(
+        "ok: timers.list v1 tick:{} count:{}",
+        world.timer_tick(),
+        timers.len()
+    ));
+    for timer in timers {
+        let owner = timer
+            .owner
+            .map_or_else(|| "-".to_string(), |owner| owner.0.to_string());
+        console.append_output_line(format!(
+            "timer id:{} owner:{} tag:{} due_in:{} repeat:{}",
+            timer.id.0,
+            owner,
+            timer.tag,
+            world.timer_remaining_ticks(timer),
+            timer.repeat_every_ticks.unwrap_or(0)
+        ));
+    }
+}
+
 fn enqueue_console_submission_line(console: &mut ConsoleState, line: &str) {
     console.append_output_line(format!("> {line}"));
     console.enqueue_pending_line(line.to_string());
@@ -1391,6 +2202,28 @@ fn collect_spawn_def_names(scenes: &SceneMachine) -> Vec<String> {
     defs
 }
 
+fn handle_minimap_mouse_press(
+    scenes: &mut SceneMachine,
+    input_collector: &InputCollector,
+    button: MouseButton,
+) -> bool {
+    if button != MouseButton::Left {
+        return false;
+    }
+    let Some(cursor_px) = input_collector.cursor_position_px() else {
+        return false;
+    };
+    let Some(target) = minimap_world_position_at(
+        scenes.active_world(),
+        input_collector.window_size(),
+        cursor_px,
+    ) else {
+        return false;
+    };
+    scenes.recenter_active_camera(target);
+    true
+}
+
 fn handle_command_palette_mouse_press(
     command_palette: &mut CommandPaletteState,
     console: &mut ConsoleState,
@@ -1479,7 +2312,7 @@ fn cursor_world_from_input(
     ))
 }
 
-fn scene_key_token(scene: SceneKey) -> &'static str {
+pub(super) fn scene_key_token(scene: SceneKey) -> &'static str {
     match scene {
         SceneKey::A => "a",
         SceneKey::B => "b",
@@ -1493,6 +2326,87 @@ struct StepPlan {
     dropped_backlog: Duration,
 }
 
+/// Speeds the `[`/`]` keys step through; `sim.speed` accepts anything in `1..=MAX_SIM_SPEED`.
+const SIM_SPEED_STEPS: [u32; 5] = [1, 2, 3, 5, 10];
+const MAX_SIM_SPEED: u32 = 10;
+const TICK_UNTIL_DEFAULT_MAX_TICKS: u32 = 3600;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TickUntil {
+    event: String,
+    max_ticks: u32,
+    ticks_run: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SimRunControl {
+    speed: u32,
+    /// Set while `max_ticks_per_frame` holds the sim below `speed`.
+    speed_capped: bool,
+    tick_until: Option<TickUntil>,
+    rewind_request: Option<u32>,
+    profile_capture_request: Option<u32>,
+    metrics_requests: Vec<MetricsRequest>,
+    cvar_requests: Vec<CvarRequest>,
+}
+
+/// `metrics.*` commands waiting for the loop-owned metrics history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricsRequest {
+    Summary,
+    Export(MetricsExportFormat),
+}
+
+/// `set`, `get`, `cvarlist` and `reset` waiting for the loop-owned cvar registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CvarRequest {
+    Set { name: String, value: String },
+    Get { name: String },
+    List { prefix: Option<String> },
+    Reset { name: String },
+}
+
+impl Default for SimRunControl {
+    fn default() -> Self {
+        Self {
+            speed: 1,
+            speed_capped: false,
+            tick_until: None,
+            rewind_request: None,
+            profile_capture_request: None,
+            metrics_requests: Vec::new(),
+            cvar_requests: Vec::new(),
+        }
+    }
+}
+
+impl SimRunControl {
+    /// Moves `steps` entries along `SIM_SPEED_STEPS` from the largest step not above the
+    /// current speed, clamped to the ends, and returns the new speed.
+    fn step_speed(&mut self, steps: i32) -> u32 {
+        let current = SIM_SPEED_STEPS
+            .iter()
+            .rposition(|step| *step <= self.speed)
+            .unwrap_or(0);
+        let last = SIM_SPEED_STEPS.len() as i32 - 1;
+        let next = (current as i32).saturating_add(steps).clamp(0, last);
+        self.speed = SIM_SPEED_STEPS[next as usize];
+        self.speed
+    }
+
+    /// Counts one finished tick against the running `tick.until`, if any. Returns the finished
+    /// request and whether its event fired once the event fires or the tick cap is reached.
+    fn record_tick_until(&mut self, fired: impl FnOnce(&str) -> bool) -> Option<(TickUntil, bool)> {
+        let until = self.tick_until.as_mut()?;
+        until.ticks_run = until.ticks_run.saturating_add(1);
+        let fired = fired(&until.event);
+        if fired || until.ticks_run >= until.max_ticks {
+            return self.tick_until.take().map(|until| (until, fired));
+        }
+        None
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct TickExecutionPlan {
     ticks_to_run: u32,
@@ -1508,7 +2422,11 @@ fn compute_tick_execution_plan(
     clamped_frame_dt: Duration,
     fixed_dt: Duration,
     max_ticks_per_frame: u32,
+    sim_speed: u32,
 ) -> TickExecutionPlan {
+    // Speed scales only the simulated time fed to the accumulator; `max_ticks_per_frame` stays a
+    // hard limit, so a speed the frame cannot reach drops backlog instead of spiralling.
+    let sim_speed = sim_speed.max(1);
     if sim_paused {
         let ticks_to_run = queued_manual_ticks.min(max_ticks_per_frame);
         TickExecutionPlan {
@@ -1518,7 +2436,8 @@ fn compute_tick_execution_plan(
             remaining_manual_ticks: queued_manual_ticks.saturating_sub(ticks_to_run),
         }
     } else {
-        let next_accumulator = accumulator.saturating_add(clamped_frame_dt);
+        let next_accumulator =
+            accumulator.saturating_add(clamped_frame_dt.saturating_mul(sim_speed));
         let step_plan = plan_sim_steps(next_accumulator, fixed_dt, max_ticks_per_frame);
         TickExecutionPlan {
             ticks_to_run: step_plan.ticks_to_run,
@@ -1562,6 +2481,95 @@ fn clamp_frame_delta(frame_dt: Duration, max_frame_delta: Duration) -> Duration
     frame_dt.min(max_frame_delta)
 }
 
+/// Loads the input map file over the default bindings. Invalid game action definitions abort
+/// startup; a broken file only costs the player their custom bindings.
+fn load_input_map(
+    path: &std::path::Path,
+    input_actions: &[InputActionDef],
+) -> Result<InputMap, InputMapError> {
+    let defaults = InputMap::with_actions(input_actions)?;
+    match InputMap::load(path, input_actions) {
+        Ok(input_map) => {
+            info!(path = %path.display(), exists = path.is_file(), "input_map_loaded");
+            Ok(input_map)
+        }
+        Err(error) => {
+            warn!(
+                path = %path.display(),
+                error = %error,
+                "input map rejected; falling back to default bindings"
+            );
+            Ok(defaults)
+        }
+    }
+}
+
+fn open_replay(path: &Path, content_hash: &str, target_tps: u32) -> Option<ReplayPlayer> {
+    let player = match ReplayPlayer::open(path) {
+        Ok(player) => player,
+        Err(error) => {
+            warn!(path = %path.display(), error = %error, "replay_load_failed");
+            return None;
+        }
+    };
+    let header = player.header();
+    if header.content_hash != content_hash {
+        warn!(
+            recorded = %header.content_hash,
+            current = %content_hash,
+            "replay_content_hash_mismatch"
+        );
+    }
+    if header.target_tps != target_tps {
+        warn!(
+            recorded = header.target_tps,
+            current = target_tps,
+            "replay_target_tps_mismatch"
+        );
+    }
+    info!(
+        path = %path.display(),
+        ticks = player.tick_count(),
+        seed = header.seed,
+        scene = %header.scene,
+        "replay_loaded"
+    );
+    Some(player)
+}
+
+fn create_replay_recorder(path: &Path, header: ReplayHeader) -> Option<ReplayRecorder> {
+    match ReplayRecorder::create(path, &header) {
+        Ok(recorder) => {
+            info!(path = %path.display(), seed = header.seed, "replay_recording");
+            Some(recorder)
+        }
+        Err(error) => {
+            warn!(path = %path.display(), error = %error, "replay_record_failed");
+            None
+        }
+    }
+}
+
+/// Parses command lines stored by a replay file or the rewind history for tick `tick`.
+fn parse_recorded_commands(
+    tick: u64,
+    lines: &[String],
+    processor: &ConsoleCommandProcessor,
+    console: &mut ConsoleState,
+    out: &mut Vec<DebugCommand>,
+) {
+    for line in lines {
+        match processor.parse_debug_command_line(line) {
+            Ok(command) => out.push(command),
+            Err(error) => {
+                warn!(tick, line = %line, error = %error, "recorded_command_invalid");
+                console
+                    .append_output_line(format!("error: recorded tick {tick} '{line}': {error}"));
+            }
+        }
+    }
+}
+
 fn normalize_non_zero_duration(value: Duration, fallback: Duration) -> Duration {
     if value.is_zero() {
         fallback
@@ -1647,12 +2655,12 @@ fn maybe_warn_budget_breach(
     path: &'static str,
     gate: &mut Option<SoftBudgetWarningGate>,
     stats: super::tools::RollingMsStats,
-) {
+) -> bool {
     let Some(active_gate) = gate.as_mut() else {
-        return;
+        return false;
     };
     let Some(event) = active_gate.record_and_maybe_trigger(stats.last_ms) else {
-        return;
+        return false;
     };
 
     warn!(
@@ -1665,6 +2673,7 @@ fn maybe_warn_budget_breach(
         max_ms = stats.max_ms,
         "perf_budget_exceeded"
     );
+    true
 }
 
 fn telemetry_qtick_after_current_tick(
@@ -1693,6 +2702,7 @@ fn emit_thruport_tick_telemetry_if_enabled(
     tick_plan: TickExecutionPlan,
     tick_index: u32,
     snapshot: Option<super::DebugInfoSnapshot>,
+    state_hash: Option<u64>,
     runtime_hooks: &mut LoopRuntimeHooks,
 ) {
     *tick_counter = tick_counter.saturating_add(1);
@@ -1704,10 +2714,13 @@ fn emit_thruport_tick_telemetry_if_enabled(
     let paused_value = if sim_paused { 1 } else { 0 };
     let qtick_value =
         telemetry_qtick_after_current_tick(sim_paused, queued_manual_ticks, tick_plan, tick_index);
-    let line = format!(
+    let mut line = format!(
         "thruport.frame v1 tick:{} paused:{} qtick:{} ev:{} in:{} in_bad:{}",
         *tick_counter, paused_value, qtick_value, ev, intents, in_bad
     );
+    if let Some(state_hash) = state_hash {
+        line.push_str(&format!(" hash:{state_hash:016x}"));
+    }
     if let Some(pump) = runtime_hooks.remote_console_pump.as_mut() {
         pump.send_thruport_frame(&line);
     }
@@ -1743,72 +2756,6 @@ fn parse_prefixed_u32(line: &str, prefix: &str) -> Option<u32> {
     value.trim().parse::<u32>().ok()
 }
 
-fn parse_enabled_flag_from_env(var_name: &str) -> bool {
-    matches!(env::var(var_name).ok().as_deref(), Some("1"))
-}
-
-fn resolve_slow_frame_delay(config_slow_frame_ms: u64) -> Duration {
-    match env::var(SLOW_FRAME_ENV_VAR) {
-        Ok(value) => match value.parse::<u64>() {
-            Ok(ms) => Duration::from_millis(ms),
-            Err(_) => {
-                warn!(
-                    env_var = SLOW_FRAME_ENV_VAR,
-                    value = value.as_str(),
-                    "invalid slow-frame env var value; falling back to config"
-                );
-                Duration::from_millis(config_slow_frame_ms)
-            }
-        },
-        Err(env::VarError::NotPresent) => Duration::from_millis(config_slow_frame_ms),
-        Err(err) => {
-            warn!(
-                env_var = SLOW_FRAME_ENV_VAR,
-                error = %err,
-                "unable to read slow-frame env var; falling back to config"
-            );
-            Duration::from_millis(config_slow_frame_ms)
-        }
-    }
-}
-
-fn is_tab_key(key_event: &winit::event::KeyEvent) -> bool {
-    matches!(key_event.physical_key, PhysicalKey::Code(KeyCode::Tab))
-}
-
-fn is_overlay_toggle_key(key_event: &winit::event::KeyEvent) -> bool {
-    matches!(key_event.physical_key, PhysicalKey::Code(KeyCode::F3))
-}
-
-fn is_console_toggle_key(key_event: &winit::event::KeyEvent) -> bool {
-    matches!(
-        key_event.physical_key,
-        PhysicalKey::Code(KeyCode::Backquote)
-    )
-}
-
-fn is_save_key(key_event: &winit::event::KeyEvent) -> bool {
-    matches!(key_event.physical_key, PhysicalKey::Code(KeyCode::F5))
-}
-
-fn is_load_key(key_event: &winit::event::KeyEvent) -> bool {
-    matches!(key_event.physical_key, PhysicalKey::Code(KeyCode::F9))
-}
-
-fn is_zoom_in_key(key_event: &winit::event::KeyEvent) -> bool {
-    matches!(
-        key_event.physical_key,
-        PhysicalKey::Code(KeyCode::Equal) | PhysicalKey::Code(KeyCode::NumpadAdd)
-    )
-}
-
-fn is_zoom_out_key(key_event: &winit::event::KeyEvent) -> bool {
-    matches!(
-        key_event.physical_key,
-        PhysicalKey::Code(KeyCode::Minus) | PhysicalKey::Code(KeyCode::NumpadSubtract)
-    )
-}
-
 fn zoom_steps_from_scroll_delta(delta: MouseScrollDelta) -> i32 {
     match delta {
         MouseScrollDelta::LineDelta(_, y) => y.round() as i32,
@@ -1833,6 +2780,11 @@ mod tests {
 
     use super::*;
     use tempfile::TempDir;
+    use winit::keyboard::KeyCode;
+
+    fn press_key(input: &mut InputCollector, code: KeyCode, is_pressed: bool) {
+        input.apply_input_trigger(InputTrigger::Key(code), is_pressed, false);
+    }
 
     struct NoopScene;
 
@@ -1853,6 +2805,30 @@ mod tests {
         fn unload(&mut self, _world: &mut super::super::SceneWorld) {}
     }
 
+    struct TilemapScene;
+
+    impl Scene for TilemapScene {
+        fn load(&mut self, world: &mut super::super::SceneWorld) {
+            let tilemap =
+                super::super::Tilemap::new(40, 40, Vec2 { x: 0.0, y: 0.0 }, vec![0; 40 * 40])
+                    .expect("tilemap");
+            world.set_tilemap(tilemap);
+        }
+
+        fn update(
+            &mut self,
+            _fixed_dt_seconds: f32,
+            _input: &InputSnapshot,
+            _world: &mut super::super::SceneWorld,
+        ) -> SceneCommand {
+            SceneCommand::None
+        }
+
+        fn render(&mut self, _world: &super::super::SceneWorld) {}
+
+        fn unload(&mut self, _world: &mut super::super::SceneWorld) {}
+    }
+
     struct LoadQueuesOneEntityScene;
 
     impl Scene for LoadQueuesOneEntityScene {
@@ -2030,6 +3006,7 @@ mod tests {
     fn remote_pump_lines_are_enqueued_before_processing() {
         let mut hooks = LoopRuntimeHooks {
             remote_console_pump: Some(Box::new(SingleLinePump { emitted: false })),
+            thruport_diag: false,
         };
         let mut console = ConsoleState::default();
         let mut remote_lines = Vec::<String>::new();
@@ -2048,6 +3025,7 @@ mod tests {
             remote_console_pump: Some(Box::new(OutputCapturePump {
                 captured: Arc::clone(&captured),
             })),
+            thruport_diag: false,
         };
         let mut console = ConsoleState::default();
         let mut scratch = Vec::<String>::new();
@@ -2221,6 +3199,7 @@ mod tests {
             remote_console_pump: Some(Box::new(FrameCapturePump {
                 frames: Arc::clone(&frames),
             })),
+            thruport_diag: false,
         };
         let tick_plan = TickExecutionPlan {
             ticks_to_run: 5,
@@ -2229,28 +3208,239 @@ mod tests {
             remaining_manual_ticks: 0,
         };
 
-        let mut tick_counter = 0u64;
-        for tick_index in 0..tick_plan.ticks_to_run {
-            emit_thruport_tick_telemetry_if_enabled(
-                true,
-                &mut tick_counter,
-                true,
-                0,
-                tick_plan,
-                tick_index,
-                None,
-                &mut hooks,
-            );
+        let mut tick_counter = 0u64;
+        for tick_index in 0..tick_plan.ticks_to_run {
+            emit_thruport_tick_telemetry_if_enabled(
+                true,
+                &mut tick_counter,
+                true,
+                0,
+                tick_plan,
+                tick_index,
+                None,
+                None,
+                &mut hooks,
+            );
+        }
+
+        let captured = frames.lock().expect("lock");
+        assert_eq!(captured.len(), 5);
+    }
+
+    #[test]
+    fn queueable_execution_emits_only_ok_or_error_lines() {
+        let mut scenes = SceneMachine::new(
+            Box::new(SceneWithDebugHook),
+            Box::new(NoopScene),
+            SceneKey::A,
+        );
+        scenes.load_active();
+        scenes.apply_pending_active();
+
+        let mut console = ConsoleState::default();
+        let mut input_collector = InputCollector::new(1280, 720);
+        let mut commands = vec![
+            DebugCommand::Spawn {
+                def_name: "proto.worker".to_string(),
+                position: None,
+            },
+            DebugCommand::Despawn { entity_id: 999_999 },
+        ];
+        let mut sim_paused = false;
+        let mut queued_manual_ticks = 0u32;
+        let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
+        let mut hooks = LoopRuntimeHooks::default();
+
+        let quit = execute_drained_debug_commands(
+            &mut commands,
+            &mut scenes,
+            &mut console,
+            &mut input_collector,
+            &mut sim_paused,
+            &mut queued_manual_ticks,
+            &mut hooks,
+            &mut thruport_telemetry_enabled,
+            &mut sim_control,
+        );
+
+        assert!(!quit);
+        let lines = console.output_lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines
+            .iter()
+            .all(|line| line.starts_with("ok:") || line.starts_with("error:")));
+        assert!(lines.iter().all(|line| !line.starts_with("queued:")));
+        assert_eq!(scenes.active_world().entity_count(), 1);
+    }
+
+    #[test]
+    fn cvar_requests_apply_live_settings_and_save_persistent_values() {
+        let duplicate = LoopConfig {
+            cvars: vec![CvarDef::bool(FPS_CAP_CVAR, true, "")],
+            ..LoopConfig::default()
+        };
+        assert_eq!(
+            build_cvar_registry(&duplicate).err(),
+            Some("duplicate cvar registration: render.fps_cap".to_string())
+        );
+        let config = LoopConfig {
+            fps_cap: Some(60),
+            sim_budget_ms: Some(16.6),
+            cvars: vec![CvarDef::bool("game.flag", true, "Game flag").persistent()],
+            ..LoopConfig::default()
+        };
+        let mut cvars = build_cvar_registry(&config).expect("registry");
+        let mut live = LiveSettings::from_cvars(&cvars);
+        assert_eq!(live.render_fps_cap, Some(60));
+        assert_eq!(live.sim_budget_ms, Some(16.6));
+        assert!(live.sim_budget_gate.is_some() && live.render_budget_gate.is_none());
+
+        let dir = std::env::temp_dir().join(format!("protoge_loop_cvars_{}", std::process::id()));
+        let path = dir.join(super::super::USER_CVARS_FILE_NAME);
+        let mut scenes = SceneMachine::new(Box::new(NoopScene), Box::new(NoopScene), SceneKey::A);
+        let mut console = ConsoleState::default();
+        let set = |name: &str, value: &str| CvarRequest::Set {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        for request in [
+            set(FPS_CAP_CVAR, "0"),
+            set(SIM_BUDGET_CVAR, "2.5"),
+            set("game.flag", "off"),
+            CvarRequest::Get {
+                name: FPS_CAP_CVAR.to_string(),
+            },
+            set(FPS_CAP_CVAR, "5000"),
+            CvarRequest::List {
+                prefix: Some("BUDGETS.".to_string()),
+            },
+            CvarRequest::Reset {
+                name: SIM_BUDGET_CVAR.to_string(),
+            },
+            CvarRequest::Get {
+                name: "nope".to_string(),
+            },
+        ] {
+            run_cvar_request(
+                request,
+                &mut cvars,
+                &path,
+                &mut live,
+                &mut scenes,
+                &mut console,
+            );
+        }
+
+        assert_eq!(
+            console.output_lines().collect::<Vec<_>>(),
+            [
+                "ok: set v1 name:render.fps_cap value:0",
+                "ok: set v1 name:budgets.sim_ms value:2.5",
+                "ok: set v1 name:game.flag value:0",
+                "ok: get v1 name:render.fps_cap value:0 default:60",
+                "error: set: 5000 is out of range 0..=1000",
+                "budgets.sim_ms 2.5 (float 0..=1000, default 16.6, persistent) - Sim soft budget in ms; 0 disables warnings and the speed throttle",
+                "budgets.render_ms 0 (float 0..=1000, default 0, persistent) - Render soft budget in ms; 0 disables warnings",
+                "ok: cvarlist v1 count:2",
+                "ok: reset v1 name:budgets.sim_ms value:16.6",
+                "error: get: unknown cvar 'nope'",
+            ]
+        );
+        assert_eq!(live.render_fps_cap, None);
+        assert_eq!(live.render_frame_target, None);
+        assert_eq!(live.sim_budget_ms, Some(16.6));
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("saved")).expect("json");
+        assert_eq!(
+            saved["cvars"],
+            serde_json::json!({ "game.flag": false, "render.fps_cap": 0 })
+        );
+
+        live.overlay_visible = false;
+        sync_toggle_cvars(&mut cvars, &live);
+        assert_eq!(cvars.value(OVERLAY_CVAR), Some(&CvarValue::Bool(false)));
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn sim_speed_scales_fixed_steps_but_not_the_per_frame_cap() {
+        let fixed_dt = Duration::from_millis(16);
+        let frame_dt = Duration::from_millis(32);
+        let normal =
+            compute_tick_execution_plan(false, 0, Duration::ZERO, frame_dt, fixed_dt, 5, 1);
+        let fast = compute_tick_execution_plan(false, 0, Duration::ZERO, frame_dt, fixed_dt, 8, 3);
+        assert_eq!(normal.ticks_to_run, 2);
+        assert_eq!(fast.ticks_to_run, 6);
+        assert_eq!(fast.dropped_backlog, Duration::ZERO);
+
+        let clamped = compute_tick_execution_plan(
+            false,
+            0,
+            Duration::ZERO,
+            Duration::from_millis(250),
+            fixed_dt,
+            5,
+            10,
+        );
+        assert_eq!(clamped.ticks_to_run, 5);
+        assert!(clamped.dropped_backlog > Duration::ZERO);
+
+        let manual =
+            compute_tick_execution_plan(true, 100, Duration::ZERO, frame_dt, fixed_dt, 5, 2);
+        assert_eq!(manual.ticks_to_run, 5);
+        assert_eq!(manual.remaining_manual_ticks, 95);
+    }
+
+    #[test]
+    fn sim_speed_steps_walk_the_presets_and_clamp() {
+        let mut control = SimRunControl::default();
+        assert_eq!(control.step_speed(1), 2);
+        assert_eq!(control.step_speed(2), 5);
+        assert_eq!(control.step_speed(5), 10);
+        assert_eq!(control.step_speed(-1), 5);
+
+        control.speed = 4;
+        assert_eq!(control.step_speed(1), 5);
+        control.speed = 4;
+        assert_eq!(control.step_speed(-1), 2);
+        assert_eq!(control.step_speed(-9), 1);
+    }
+
+    struct PingAfterScene {
+        updates: u32,
+        ping_on_update: u32,
+    }
+
+    impl Scene for PingAfterScene {
+        fn load(&mut self, _world: &mut super::super::SceneWorld) {}
+
+        fn update(
+            &mut self,
+            _fixed_dt_seconds: f32,
+            _input: &InputSnapshot,
+            _world: &mut super::super::SceneWorld,
+        ) -> SceneCommand {
+            self.updates += 1;
+            SceneCommand::None
         }
 
-        let captured = frames.lock().expect("lock");
-        assert_eq!(captured.len(), 5);
+        fn render(&mut self, _world: &super::super::SceneWorld) {}
+
+        fn unload(&mut self, _world: &mut super::super::SceneWorld) {}
+
+        fn debug_event_fired_last_tick(&self, event: &str) -> Option<bool> {
+            (event == "ping").then_some(self.updates == self.ping_on_update)
+        }
     }
 
     #[test]
-    fn queueable_execution_emits_only_ok_or_error_lines() {
+    fn tick_until_pauses_and_stops_on_the_event_or_the_cap() {
         let mut scenes = SceneMachine::new(
-            Box::new(SceneWithDebugHook),
+            Box::new(PingAfterScene {
+                updates: 0,
+                ping_on_update: 3,
+            }),
             Box::new(NoopScene),
             SceneKey::A,
         );
@@ -2259,19 +3449,115 @@ mod tests {
 
         let mut console = ConsoleState::default();
         let mut input_collector = InputCollector::new(1280, 720);
-        let mut commands = vec![
-            DebugCommand::Spawn {
-                def_name: "proto.worker".to_string(),
-                position: None,
+        let mut sim_paused = false;
+        let mut queued_manual_ticks = 0u32;
+        let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
+        let mut hooks = LoopRuntimeHooks::default();
+        let mut run = |command: DebugCommand,
+                       scenes: &mut SceneMachine,
+                       sim_paused: &mut bool,
+                       queued_manual_ticks: &mut u32,
+                       sim_control: &mut SimRunControl,
+                       console: &mut ConsoleState| {
+            execute_drained_debug_commands(
+                &mut vec![command],
+                scenes,
+                console,
+                &mut input_collector,
+                sim_paused,
+                queued_manual_ticks,
+                &mut hooks,
+                &mut thruport_telemetry_enabled,
+                sim_control,
+            );
+        };
+
+        run(
+            DebugCommand::TickUntil {
+                event: "nope".to_string(),
+                max_ticks: None,
             },
-            DebugCommand::Despawn { entity_id: 999_999 },
-        ];
+            &mut scenes,
+            &mut sim_paused,
+            &mut queued_manual_ticks,
+            &mut sim_control,
+            &mut console,
+        );
+        assert!(!sim_paused);
+        assert_eq!(sim_control.tick_until, None);
+
+        for (max_ticks, expected) in [
+            (None, "ok: tick.until v1 event:ping fired:1 ticks:3"),
+            (Some(2), "ok: tick.until v1 event:ping fired:0 ticks:2"),
+        ] {
+            run(
+                DebugCommand::TickUntil {
+                    event: "ping".to_string(),
+                    max_ticks,
+                },
+                &mut scenes,
+                &mut sim_paused,
+                &mut queued_manual_ticks,
+                &mut sim_control,
+                &mut console,
+            );
+            assert!(sim_paused);
+            assert_eq!(
+                queued_manual_ticks,
+                max_ticks.unwrap_or(TICK_UNTIL_DEFAULT_MAX_TICKS)
+            );
+
+            let mut finished = None;
+            while finished.is_none() && queued_manual_ticks > 0 {
+                queued_manual_ticks -= 1;
+                let _ = scenes.update_active(1.0 / 60.0, &InputSnapshot::empty());
+                finished = sim_control.record_tick_until(|event| {
+                    scenes.debug_event_fired_last_tick_active(event) == Some(true)
+                });
+            }
+            let (until, fired) = finished.expect("tick.until should finish");
+            assert_eq!(
+                format!(
+                    "ok: tick.until v1 event:{} fired:{} ticks:{}",
+                    until.event,
+                    u8::from(fired),
+                    until.ticks_run
+                ),
+                expected
+            );
+            assert_eq!(sim_control.tick_until, None);
+        }
+
+        let lines = console.output_lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "error: unknown event 'nope' for active scene");
+        assert_eq!(lines[1], "ok: tick.until started event:ping max_ticks:3600");
+        assert_eq!(lines[2], "ok: tick.until started event:ping max_ticks:2");
+    }
+
+    #[test]
+    fn sim_speed_command_reports_and_validates_the_multiplier() {
+        let mut scenes = SceneMachine::new(Box::new(NoopScene), Box::new(NoopScene), SceneKey::A);
+        scenes.load_active();
+        let mut console = ConsoleState::default();
+        let mut input_collector = InputCollector::new(1280, 720);
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
+        let mut commands = vec![
+            DebugCommand::SimSpeed { multiplier: None },
+            DebugCommand::SimSpeed {
+                multiplier: Some(4),
+            },
+            DebugCommand::SimSpeed {
+                multiplier: Some(11),
+            },
+            DebugCommand::Rewind { ticks: 30 },
+        ];
 
-        let quit = execute_drained_debug_commands(
+        execute_drained_debug_commands(
             &mut commands,
             &mut scenes,
             &mut console,
@@ -2280,16 +3566,107 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
-        assert!(!quit);
+        assert_eq!(sim_control.speed, 4);
+        assert_eq!(sim_control.rewind_request, Some(30));
+        assert_eq!(
+            console.output_lines().collect::<Vec<_>>(),
+            vec![
+                "ok: sim.speed v1 speed:1",
+                "ok: sim.speed v1 speed:4",
+                "error: sim speed must be 1..=10 (got 11)",
+            ]
+        );
+    }
+
+    #[test]
+    fn state_hash_command_reports_the_active_scene_hash() {
+        let mut scenes = SceneMachine::new(
+            Box::new(SceneWithDebugHook),
+            Box::new(NoopScene),
+            SceneKey::A,
+        );
+        scenes.load_active();
+        scenes.apply_pending_active();
+
+        let mut console = ConsoleState::default();
+        let mut input_collector = InputCollector::new(1280, 720);
+        let mut sim_paused = false;
+        let mut queued_manual_ticks = 0u32;
+        let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
+        let mut hooks = LoopRuntimeHooks::default();
+        let empty_hash = scenes.state_hash_active();
+        let commands = vec![
+            DebugCommand::StateHash,
+            DebugCommand::Spawn {
+                def_name: "proto.worker".to_string(),
+                position: Some((1.0, 2.0)),
+            },
+        ];
+
+        for mut batch in [commands, vec![DebugCommand::StateHash]] {
+            execute_drained_debug_commands(
+                &mut batch,
+                &mut scenes,
+                &mut console,
+                &mut input_collector,
+                &mut sim_paused,
+                &mut queued_manual_ticks,
+                &mut hooks,
+                &mut thruport_telemetry_enabled,
+                &mut sim_control,
+            );
+        }
+
+        let spawned_hash = scenes.state_hash_active();
+        assert_ne!(spawned_hash, empty_hash);
         let lines = console.output_lines().collect::<Vec<_>>();
-        assert_eq!(lines.len(), 2);
-        assert!(lines
-            .iter()
-            .all(|line| line.starts_with("ok:") || line.starts_with("error:")));
-        assert!(lines.iter().all(|line| !line.starts_with("queued:")));
-        assert_eq!(scenes.active_world().entity_count(), 1);
+        assert_eq!(
+            lines[0],
+            format!("ok: state.hash v1 tick:0 hash:{empty_hash:016x}")
+        );
+        assert_eq!(
+            lines[2],
+            format!("ok: state.hash v1 tick:0 hash:{spawned_hash:016x}")
+        );
+    }
+
+    #[test]
+    fn telemetry_frame_appends_state_hash_when_computed() {
+        let frames = Arc::new(Mutex::new(Vec::<String>::new()));
+        let mut hooks = LoopRuntimeHooks {
+            remote_console_pump: Some(Box::new(FrameCapturePump {
+                frames: Arc::clone(&frames),
+            })),
+            thruport_diag: false,
+        };
+        let tick_plan = TickExecutionPlan {
+            ticks_to_run: 1,
+            remaining_accumulator: Duration::ZERO,
+            dropped_backlog: Duration::ZERO,
+            remaining_manual_ticks: 0,
+        };
+        let mut tick_counter = 0u64;
+        emit_thruport_tick_telemetry_if_enabled(
+            true,
+            &mut tick_counter,
+            false,
+            0,
+            tick_plan,
+            0,
+            None,
+            Some(0xab),
+            &mut hooks,
+        );
+
+        let captured = frames.lock().expect("lock");
+        assert_eq!(
+            captured.as_slice(),
+            ["thruport.frame v1 tick:1 paused:0 qtick:0 ev:0 in:0 in_bad:0 hash:00000000000000ab"]
+        );
     }
 
     #[test]
@@ -2468,6 +3845,41 @@ mod tests {
         assert!(pending_lines.is_empty());
     }
 
+    #[test]
+    fn minimap_left_click_recenters_the_camera_and_is_consumed() {
+        let mut scenes =
+            SceneMachine::new(Box::new(TilemapScene), Box::new(NoopScene), SceneKey::A);
+        scenes.load_active();
+        let mut input_collector = InputCollector::new(1280, 720);
+
+        input_collector.set_cursor_position_px(640.0, 360.0);
+        assert!(!handle_minimap_mouse_press(
+            &mut scenes,
+            &input_collector,
+            MouseButton::Left
+        ));
+
+        let target_px =
+            minimap_world_position_at(scenes.active_world(), (1280, 720), Vec2 { x: 0.0, y: 0.0 });
+        assert_eq!(target_px, None);
+        // The 40x40 map fills a 160 px box in the bottom-right corner: 4 px per tile.
+        input_collector.set_cursor_position_px(1280.0 - 12.0 - 80.0, 720.0 - 12.0 - 40.0);
+        assert!(!handle_minimap_mouse_press(
+            &mut scenes,
+            &input_collector,
+            MouseButton::Right
+        ));
+        assert!(handle_minimap_mouse_press(
+            &mut scenes,
+            &input_collector,
+            MouseButton::Left
+        ));
+        assert_eq!(
+            scenes.active_world().camera().position,
+            Vec2 { x: 20.0, y: 10.0 }
+        );
+    }
+
     #[test]
     fn dump_commands_route_through_scene_debug_result_append() {
         let mut scenes = SceneMachine::new(
@@ -2483,6 +3895,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut commands = vec![DebugCommand::DumpState, DebugCommand::DumpAi];
         let mut hooks = LoopRuntimeHooks::default();
 
@@ -2495,6 +3908,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2507,6 +3921,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn timers_list_reports_active_world_timers_in_firing_order() {
+        let mut scenes = SceneMachine::new(
+            Box::new(SceneWithDebugHook),
+            Box::new(NoopScene),
+            SceneKey::A,
+        );
+        scenes.load_active();
+        scenes.apply_pending_active();
+        let world = scenes.active_world_mut();
+        world.tick_timers();
+        world
+            .schedule_repeating_timer(None, "pulse", 4, 2)
+            .expect("pulse");
+        world.schedule_timer(None, "once", 1).expect("once");
+
+        let mut console = ConsoleState::default();
+        let mut input_collector = InputCollector::new(1280, 720);
+        let mut sim_paused = false;
+        let mut queued_manual_ticks = 0u32;
+        let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
+        let mut commands = vec![DebugCommand::TimersList];
+        let mut hooks = LoopRuntimeHooks::default();
+
+        execute_drained_debug_commands(
+            &mut commands,
+            &mut scenes,
+            &mut console,
+            &mut input_collector,
+            &mut sim_paused,
+            &mut queued_manual_ticks,
+            &mut hooks,
+            &mut thruport_telemetry_enabled,
+            &mut sim_control,
+        );
+
+        assert_eq!(
+            console.output_lines().collect::<Vec<_>>(),
+            vec![
+                "ok: timers.list v1 tick:1 count:2",
+                "timer id:1 owner:- tag:once due_in:1 repeat:0",
+                "timer id:0 owner:- tag:pulse due_in:4 repeat:2",
+            ]
+        );
+    }
+
     #[test]
     fn select_and_order_commands_route_through_scene_debug_result_append() {
         let mut scenes = SceneMachine::new(
@@ -2522,14 +3983,19 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut commands = vec![
             DebugCommand::Select { entity_id: 1 },
-            DebugCommand::OrderMove { x: 1.0, y: -2.0 },
+            DebugCommand::OrderMove {
+                x: 1.0,
+                y: -2.0,
+                floor: None,
+            },
             DebugCommand::OrderInteract {
                 target_entity_id: 2,
             },
             DebugCommand::FloorSet {
-                floor: super::super::FloorId::Basement,
+                floor: "basement".to_string(),
             },
             DebugCommand::ScenarioSetup {
                 scenario_id: "combat_chaser".to_string(),
@@ -2546,6 +4012,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2577,6 +4044,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
 
         let _ = execute_drained_debug_commands(
@@ -2588,6 +4056,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert_eq!(scenes.active_scene(), SceneKey::B);
@@ -2615,6 +4084,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
 
         let _ = execute_drained_debug_commands(
@@ -2626,6 +4096,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert_eq!(scenes.active_world().entity_count(), 1);
@@ -2677,6 +4148,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
         let mut commands = vec![
             DebugCommand::PauseSim,
@@ -2693,6 +4165,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2715,6 +4188,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
         let mut commands = vec![
             DebugCommand::PauseSim,
@@ -2731,6 +4205,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2752,6 +4227,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
         let mut commands = vec![
             DebugCommand::ResetScene,
@@ -2768,6 +4244,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2790,11 +4267,13 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = true;
+        let mut sim_control = SimRunControl::default();
         let mut commands = vec![DebugCommand::ThruportStatus];
         let mut hooks = LoopRuntimeHooks {
             remote_console_pump: Some(Box::new(StatusLinePump {
                 status_line_text: "thruport.status v1 enabled:1 telemetry:1 clients:3".to_string(),
             })),
+            thruport_diag: false,
         };
 
         let quit = execute_drained_debug_commands(
@@ -2806,6 +4285,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2826,6 +4306,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = true;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
         let mut commands = vec![
             DebugCommand::ThruportTelemetry { enabled: false },
@@ -2841,6 +4322,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2867,12 +4349,14 @@ mod tests {
                 outputs: Arc::clone(&outputs),
                 frames: Arc::clone(&frames),
             })),
+            thruport_diag: false,
         };
         let mut console = ConsoleState::default();
         let mut input_collector = InputCollector::new(1280, 720);
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = true;
+        let mut sim_control = SimRunControl::default();
         let mut commands = vec![
             DebugCommand::ThruportTelemetry { enabled: false },
             DebugCommand::Tick { steps: 1 },
@@ -2888,6 +4372,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
         assert!(!quit);
         assert!(!thruport_telemetry_enabled);
@@ -2936,6 +4421,7 @@ mod tests {
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut hooks = LoopRuntimeHooks::default();
         let mut commands = vec![DebugCommand::InjectInput {
             event: InjectedInputEvent::KeyDown {
@@ -2952,6 +4438,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
 
         assert!(!quit);
@@ -2961,6 +4448,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn input_bind_command_rebinds_and_rejects_conflicts() {
+        let mut scenes = SceneMachine::new(Box::new(NoopScene), Box::new(NoopScene), SceneKey::A);
+        scenes.load_active();
+        scenes.apply_pending_active();
+
+        let mut console = ConsoleState::default();
+        let mut input_collector = InputCollector::new(1280, 720);
+        let mut sim_paused = false;
+        let mut queued_manual_ticks = 0u32;
+        let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
+        let mut hooks = LoopRuntimeHooks::default();
+        let mut commands = vec![
+            DebugCommand::InputBind {
+                action: "move_up".to_string(),
+                bindings: vec![
+                    "Space".parse().expect("binding"),
+                    "MouseMiddle".parse().expect("binding"),
+                ],
+            },
+            DebugCommand::InputBind {
+                action: "save".to_string(),
+                bindings: vec!["Space".parse().expect("binding")],
+            },
+            DebugCommand::InputBindings {
+                action: Some("move_up".to_string()),
+            },
+            DebugCommand::InputBindings {
+                action: Some("fly".to_string()),
+            },
+        ];
+
+        let _ = execute_drained_debug_commands(
+            &mut commands,
+            &mut scenes,
+            &mut console,
+            &mut input_collector,
+            &mut sim_paused,
+            &mut queued_manual_ticks,
+            &mut hooks,
+            &mut thruport_telemetry_enabled,
+            &mut sim_control,
+        );
+
+        assert_eq!(
+            console.output_lines().collect::<Vec<_>>(),
+            vec![
+                "ok: input.bind move_up Space MouseMiddle",
+                "error: binding Space is bound to both 'move_up' and 'save'",
+                "move_up Space MouseMiddle",
+                "error: unknown input action 'fly'",
+            ]
+        );
+
+        press_key(&mut input_collector, KeyCode::KeyW, true);
+        assert!(!input_collector
+            .snapshot_for_tick(false)
+            .is_down(InputAction::MoveUp));
+        input_collector.handle_mouse_input(MouseButton::Middle, ElementState::Pressed);
+        assert!(input_collector
+            .snapshot_for_tick(false)
+            .is_down(InputAction::MoveUp));
+    }
+
     #[test]
     fn injected_input_ok_line_flows_through_remote_output_path() {
         let mut scenes = SceneMachine::new(Box::new(NoopScene), Box::new(NoopScene), SceneKey::A);
@@ -2972,12 +4524,14 @@ mod tests {
             remote_console_pump: Some(Box::new(OutputCapturePump {
                 captured: Arc::clone(&captured),
             })),
+            thruport_diag: false,
         };
         let mut console = ConsoleState::default();
         let mut input_collector = InputCollector::new(1280, 720);
         let mut sim_paused = false;
         let mut queued_manual_ticks = 0u32;
         let mut thruport_telemetry_enabled = false;
+        let mut sim_control = SimRunControl::default();
         let mut commands = vec![DebugCommand::InjectInput {
             event: InjectedInputEvent::KeyUp {
                 key: InjectedKey::A,
@@ -2993,6 +4547,7 @@ mod tests {
             &mut queued_manual_ticks,
             &mut hooks,
             &mut thruport_telemetry_enabled,
+            &mut sim_control,
         );
         assert!(!quit);
 
@@ -3028,6 +4583,7 @@ mod tests {
             Duration::from_millis(32),
             fixed_dt,
             5,
+            1,
         );
         let _remaining_after_first = first_plan.remaining_manual_ticks;
         accumulator = first_plan.remaining_accumulator;
@@ -3045,6 +4601,7 @@ mod tests {
             Duration::from_millis(64),
             fixed_dt,
             5,
+            1,
         );
         for _ in 0..second_plan.ticks_to_run {
             let _ = scenes.update_active(fixed_dt_seconds, &InputSnapshot::empty());
@@ -3056,7 +4613,6 @@ mod tests {
     #[test]
     fn tab_press_is_edge_triggered_for_single_tick() {
         let mut input = InputCollector::default();
-        input.tab_is_down = false;
         input.switch_scene_pressed_edge = true;
 
         let first = input.snapshot_for_tick(false);
@@ -3080,14 +4636,14 @@ mod tests {
     fn held_tab_does_not_spam_press_edges() {
         let mut input = InputCollector::default();
 
-        input.handle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Tab, true);
         let first = input.snapshot_for_tick(false);
 
-        input.handle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Tab, true);
         let second = input.snapshot_for_tick(false);
 
-        input.handle_key_state(true, ElementState::Released);
-        input.handle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Tab, false);
+        press_key(&mut input, KeyCode::Tab, true);
         let third = input.snapshot_for_tick(false);
 
         assert!(first.switch_scene_pressed());
@@ -3099,8 +4655,8 @@ mod tests {
     fn wasd_and_arrow_keys_map_to_actions() {
         let mut input = InputCollector::default();
 
-        input.update_action_state_from_physical_key(PhysicalKey::Code(KeyCode::KeyW), true);
-        input.update_action_state_from_physical_key(PhysicalKey::Code(KeyCode::ArrowLeft), true);
+        press_key(&mut input, KeyCode::KeyW, true);
+        press_key(&mut input, KeyCode::ArrowLeft, true);
 
         let snapshot = input.snapshot_for_tick(false);
         assert!(snapshot.is_down(InputAction::MoveUp));
@@ -3110,18 +4666,68 @@ mod tests {
     #[test]
     fn key_release_clears_action_state() {
         let mut input = InputCollector::default();
-        input.update_action_state_from_physical_key(PhysicalKey::Code(KeyCode::KeyD), true);
-        input.update_action_state_from_physical_key(PhysicalKey::Code(KeyCode::KeyD), false);
+        press_key(&mut input, KeyCode::KeyD, true);
+        press_key(&mut input, KeyCode::KeyD, false);
 
         let snapshot = input.snapshot_for_tick(false);
         assert!(!snapshot.is_down(InputAction::MoveRight));
     }
 
+    #[test]
+    fn action_bound_twice_stays_down_until_both_keys_release() {
+        let mut input = InputCollector::default();
+        press_key(&mut input, KeyCode::KeyW, true);
+        press_key(&mut input, KeyCode::ArrowUp, true);
+        press_key(&mut input, KeyCode::KeyW, false);
+        assert!(input.snapshot_for_tick(false).is_down(InputAction::MoveUp));
+
+        press_key(&mut input, KeyCode::ArrowUp, false);
+        assert!(!input.snapshot_for_tick(false).is_down(InputAction::MoveUp));
+    }
+
+    #[test]
+    fn modifier_chord_fires_instead_of_the_plain_binding() {
+        let mut input = InputCollector::default();
+        input
+            .rebind("save", vec!["Ctrl+S".parse().expect("binding")])
+            .expect("rebind");
+        input.set_modifiers(InputModifiers {
+            ctrl: true,
+            ..InputModifiers::default()
+        });
+        press_key(&mut input, KeyCode::KeyS, true);
+        let chord = input.snapshot_for_tick(false);
+        assert!(chord.save_pressed());
+        assert!(!chord.is_down(InputAction::MoveDown));
+
+        press_key(&mut input, KeyCode::KeyS, false);
+        input.set_modifiers(InputModifiers::default());
+        press_key(&mut input, KeyCode::KeyS, true);
+        let plain = input.snapshot_for_tick(false);
+        assert!(!plain.save_pressed());
+        assert!(plain.is_down(InputAction::MoveDown));
+    }
+
+    #[test]
+    fn open_console_only_reacts_to_the_console_toggle() {
+        let mut input = InputCollector::default();
+        assert!(input.apply_input_trigger(InputTrigger::Key(KeyCode::Backquote), true, true));
+        assert!(input.take_console_toggle_pressed());
+        assert!(!input.apply_input_trigger(InputTrigger::Key(KeyCode::F5), true, true));
+        assert!(!input.apply_input_trigger(InputTrigger::Key(KeyCode::KeyW), true, true));
+        let snapshot = input.snapshot_for_tick(false);
+        assert!(!snapshot.save_pressed());
+        assert!(!snapshot.is_down(InputAction::MoveUp));
+
+        input.reset_gameplay_inputs();
+        assert!(input.apply_input_trigger(InputTrigger::Key(KeyCode::Backquote), false, true));
+    }
+
     #[test]
     fn camera_pan_keys_map_to_camera_actions() {
         let mut input = InputCollector::default();
-        input.update_action_state_from_physical_key(PhysicalKey::Code(KeyCode::KeyI), true);
-        input.update_action_state_from_physical_key(PhysicalKey::Code(KeyCode::KeyL), true);
+        press_key(&mut input, KeyCode::KeyI, true);
+        press_key(&mut input, KeyCode::KeyL, true);
         let snapshot = input.snapshot_for_tick(false);
         assert!(snapshot.is_down(InputAction::CameraUp));
         assert!(snapshot.is_down(InputAction::CameraRight));
@@ -3180,29 +4786,42 @@ mod tests {
     fn f3_toggle_is_edge_triggered() {
         let mut input = InputCollector::default();
 
-        input.handle_overlay_toggle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F3, true);
         assert!(input.take_overlay_toggle_pressed());
 
-        input.handle_overlay_toggle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F3, true);
         assert!(!input.take_overlay_toggle_pressed());
 
-        input.handle_overlay_toggle_key_state(true, ElementState::Released);
-        input.handle_overlay_toggle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F3, false);
+        press_key(&mut input, KeyCode::F3, true);
         assert!(input.take_overlay_toggle_pressed());
     }
 
+    #[test]
+    fn bracket_keys_queue_sim_speed_steps() {
+        let mut input = InputCollector::default();
+
+        press_key(&mut input, KeyCode::BracketRight, true);
+        press_key(&mut input, KeyCode::BracketRight, false);
+        press_key(&mut input, KeyCode::BracketRight, true);
+        press_key(&mut input, KeyCode::BracketRight, false);
+        press_key(&mut input, KeyCode::BracketLeft, true);
+        assert_eq!(input.take_sim_speed_steps(), 1);
+        assert_eq!(input.take_sim_speed_steps(), 0);
+    }
+
     #[test]
     fn backquote_console_toggle_is_edge_triggered() {
         let mut input = InputCollector::default();
 
-        input.handle_console_toggle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Backquote, true);
         assert!(input.take_console_toggle_pressed());
 
-        input.handle_console_toggle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Backquote, true);
         assert!(!input.take_console_toggle_pressed());
 
-        input.handle_console_toggle_key_state(true, ElementState::Released);
-        input.handle_console_toggle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Backquote, false);
+        press_key(&mut input, KeyCode::Backquote, true);
         assert!(input.take_console_toggle_pressed());
     }
 
@@ -3292,12 +4911,12 @@ mod tests {
     #[test]
     fn console_open_suppresses_gameplay_snapshot_inputs() {
         let mut input = InputCollector::new(1280, 720);
-        input.update_action_state_from_physical_key(PhysicalKey::Code(KeyCode::KeyW), true);
-        input.handle_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::KeyW, true);
+        press_key(&mut input, KeyCode::Tab, true);
         input.handle_mouse_input(MouseButton::Left, ElementState::Pressed);
         input.handle_mouse_input(MouseButton::Right, ElementState::Pressed);
-        input.handle_save_key_state(true, ElementState::Pressed);
-        input.handle_load_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F5, true);
+        press_key(&mut input, KeyCode::F9, true);
         input.handle_mouse_wheel(MouseScrollDelta::LineDelta(0.0, 2.0));
 
         let snapshot = input.snapshot_for_tick(true);
@@ -3339,7 +4958,7 @@ mod tests {
     #[test]
     fn save_key_edge_is_single_tick() {
         let mut input = InputCollector::new(1280, 720);
-        input.handle_save_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F5, true);
         let first = input.snapshot_for_tick(false);
         let second = input.snapshot_for_tick(false);
 
@@ -3350,7 +4969,7 @@ mod tests {
     #[test]
     fn load_key_edge_is_single_tick() {
         let mut input = InputCollector::new(1280, 720);
-        input.handle_load_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F9, true);
         let first = input.snapshot_for_tick(false);
         let second = input.snapshot_for_tick(false);
 
@@ -3362,20 +4981,20 @@ mod tests {
     fn held_save_load_do_not_retrigger_without_release() {
         let mut input = InputCollector::new(1280, 720);
 
-        input.handle_save_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F5, true);
         assert!(input.snapshot_for_tick(false).save_pressed());
-        input.handle_save_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F5, true);
         assert!(!input.snapshot_for_tick(false).save_pressed());
-        input.handle_save_key_state(true, ElementState::Released);
-        input.handle_save_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F5, false);
+        press_key(&mut input, KeyCode::F5, true);
         assert!(input.snapshot_for_tick(false).save_pressed());
 
-        input.handle_load_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F9, true);
         assert!(input.snapshot_for_tick(false).load_pressed());
-        input.handle_load_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F9, true);
         assert!(!input.snapshot_for_tick(false).load_pressed());
-        input.handle_load_key_state(true, ElementState::Released);
-        input.handle_load_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::F9, false);
+        press_key(&mut input, KeyCode::F9, true);
         assert!(input.snapshot_for_tick(false).load_pressed());
     }
 
@@ -3383,17 +5002,17 @@ mod tests {
     fn zoom_keys_are_edge_triggered_only() {
         let mut input = InputCollector::new(1280, 720);
 
-        input.handle_zoom_in_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Equal, true);
         assert_eq!(input.snapshot_for_tick(false).zoom_delta_steps(), 1);
 
-        input.handle_zoom_in_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Equal, true);
         assert_eq!(input.snapshot_for_tick(false).zoom_delta_steps(), 0);
 
-        input.handle_zoom_in_key_state(true, ElementState::Released);
-        input.handle_zoom_in_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Equal, false);
+        press_key(&mut input, KeyCode::Equal, true);
         assert_eq!(input.snapshot_for_tick(false).zoom_delta_steps(), 1);
 
-        input.handle_zoom_out_key_state(true, ElementState::Pressed);
+        press_key(&mut input, KeyCode::Minus, true);
         assert_eq!(input.snapshot_for_tick(false).zoom_delta_steps(), -1);
     }
 
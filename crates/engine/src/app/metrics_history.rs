@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// Game config env override for the initial `metrics.export_on_exit` (`csv` or `json`).
+pub const METRICS_EXPORT_ENV_VAR: &str = "PROTOGE_METRICS_EXPORT";
+const METRICS_EXPORT_VERSION: u32 = 1;
+
+/// Timings of one presented frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub(crate) struct FrameSample {
+    pub frame: u64,
+    pub frame_ms: f32,
+    pub sim_ms: f32,
+    pub render_ms: f32,
+    pub ticks: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub(crate) struct Percentiles {
+    pub p50: f32,
+    pub p95: f32,
+    pub p99: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub(crate) struct MetricsSummary {
+    pub samples: usize,
+    pub frame_ms: Percentiles,
+    pub sim_ms: Percentiles,
+    pub render_ms: Percentiles,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MetricsExportFormat {
+    Csv,
+    Json,
+}
+
+impl MetricsExportFormat {
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn token(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct MetricsExportFile<'a> {
+    version: u32,
+    capacity: usize,
+    summary: MetricsSummary,
+    samples: &'a VecDeque<FrameSample>,
+}
+
+/// Ring buffer of the last `capacity` frame samples. Frames count from loop start.
+#[derive(Debug)]
+pub(crate) struct MetricsHistory {
+    capacity: usize,
+    next_frame: u64,
+    samples: VecDeque<FrameSample>,
+}
+
+impl MetricsHistory {
+    /// `None` when `capacity` is zero, which disables the history.
+    pub(crate) fn new(capacity: usize) -> Option<Self> {
+        (capacity > 0).then(|| Self {
+            capacity,
+            next_frame: 0,
+            samples: VecDeque::with_capacity(capacity),
+        })
+    }
+
+    pub(crate) fn record(&mut self, frame_ms: f32, sim_ms: f32, render_ms: f32, ticks: u32) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(FrameSample {
+            frame: self.next_frame,
+            frame_ms,
+            sim_ms,
+            render_ms,
+            ticks,
+        });
+        self.next_frame += 1;
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// The newest `count` samples, oldest first.
+    pub(crate) fn recent(&self, count: usize) -> impl Iterator<Item = &FrameSample> {
+        self.samples
+            .iter()
+            .skip(self.samples.len().saturating_sub(count))
+    }
+
+    pub(crate) fn summary(&self) -> MetricsSummary {
+        MetricsSummary {
+            samples: self.samples.len(),
+            frame_ms: self.percentiles(|sample| sample.frame_ms),
+            sim_ms: self.percentiles(|sample| sample.sim_ms),
+            render_ms: self.percentiles(|sample| sample.render_ms),
+        }
+    }
+
+    /// Nearest-rank percentiles of one sample field.
+    fn percentiles(&self, field: impl Fn(&FrameSample) -> f32) -> Percentiles {
+        let mut values: Vec<f32> = self.samples.iter().map(field).collect();
+        if values.is_empty() {
+            return Percentiles::default();
+        }
+        values.sort_by(f32::total_cmp);
+        let rank = |percent: usize| {
+            let index = (percent * values.len()).div_ceil(100).max(1) - 1;
+            values[index]
+        };
+        Percentiles {
+            p50: rank(50),
+            p95: rank(95),
+            p99: rank(99),
+        }
+    }
+
+    pub(crate) fn to_csv(&self) -> String {
+        let mut csv = String::from("frame,frame_ms,sim_ms,render_ms,ticks\n");
+        for sample in &self.samples {
+            let _ = writeln!(
+                csv,
+                "{},{:.3},{:.3},{:.3},{}",
+                sample.frame, sample.frame_ms, sample.sim_ms, sample.render_ms, sample.ticks
+            );
+        }
+        csv
+    }
+
+    pub(crate) fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&MetricsExportFile {
+            version: METRICS_EXPORT_VERSION,
+            capacity: self.capacity,
+            summary: self.summary(),
+            samples: &self.samples,
+        })
+    }
+
+    /// Writes the history to `<dir>/metrics_<stamp>.<csv|json>` and returns the path.
+    pub(crate) fn export(
+        &self,
+        format: MetricsExportFormat,
+        dir: &Path,
+        stamp: u128,
+    ) -> io::Result<PathBuf> {
+        let contents = match format {
+            MetricsExportFormat::Csv => self.to_csv(),
+            MetricsExportFormat::Json => self.to_json().map_err(io::Error::other)?,
+        };
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("metrics_{stamp}.{}", format.token()));
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_with_frames(capacity: usize, count: u32) -> MetricsHistory {
+        let mut history = MetricsHistory::new(capacity).expect("enabled");
+        for index in 1..=count {
+            let ms = index as f32;
+            history.record(ms, ms / 2.0, ms / 4.0, index % 3);
+        }
+        history
+    }
+
+    #[test]
+    fn ring_keeps_newest_samples_and_reports_nearest_rank_percentiles() {
+        assert!(MetricsHistory::new(0).is_none());
+        let history = history_with_frames(100, 150);
+        assert_eq!(history.len(), 100);
+        assert_eq!(
+            history.recent(2).map(|s| s.frame).collect::<Vec<_>>(),
+            [148, 149]
+        );
+        assert_eq!(history.recent(500).count(), 100);
+
+        let summary = history.summary();
+        assert_eq!(summary.samples, 100);
+        assert_eq!(
+            summary.frame_ms,
+            Percentiles {
+                p50: 100.0,
+                p95: 145.0,
+                p99: 149.0,
+            }
+        );
+        assert_eq!(summary.sim_ms.p50, 50.0);
+        assert_eq!(
+            MetricsHistory::new(4).expect("enabled").summary(),
+            MetricsSummary::default()
+        );
+    }
+
+    #[test]
+    fn exports_csv_and_json() {
+        let history = history_with_frames(4, 2);
+        assert_eq!(
+            history.to_csv(),
+            "frame,frame_ms,sim_ms,render_ms,ticks\n0,1.000,0.500,0.250,1\n1,2.000,1.000,0.500,2\n"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&history.to_json().expect("json")).expect("parse");
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["capacity"], 4);
+        assert_eq!(json["summary"]["samples"], 2);
+        assert_eq!(json["summary"]["frame_ms"]["p99"], 2.0);
+        assert_eq!(json["samples"][1]["sim_ms"], 1.0);
+
+        assert_eq!(
+            MetricsExportFormat::parse(" JSON "),
+            Some(MetricsExportFormat::Json)
+        );
+        assert_eq!(MetricsExportFormat::parse("xml"), None);
+    }
+}
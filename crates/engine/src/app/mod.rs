@@ -1,28 +1,55 @@
+mod cvars;
 mod input;
 mod loop_runner;
 mod metrics;
+mod metrics_history;
+mod profiling;
 mod rendering;
+mod replay;
+mod rewind;
 mod scene;
 mod tools;
 
-pub use input::InputAction;
+pub use cvars::{read_user_cvars, CvarDef, CvarValue, USER_CVARS_FILE_NAME};
+pub use input::{
+    InputAction, InputActionDef, InputBinding, InputMapError, InputModifiers, InputTrigger,
+    INPUT_MAP_FILE_NAME, MAX_CUSTOM_INPUT_ACTIONS,
+};
 pub use loop_runner::{
     run_app, run_app_with_hooks, run_app_with_metrics, AppError, LoopConfig, LoopRuntimeHooks,
     RemoteConsoleLinePump, SLOW_FRAME_ENV_VAR,
 };
 pub use metrics::{LoopMetricsSnapshot, MetricsHandle};
+pub use metrics_history::METRICS_EXPORT_ENV_VAR;
+pub use profiling::{profile_zone, ProfileZone};
 pub use rendering::{
     screen_to_world_px, world_to_screen, world_to_screen_px, Renderer, Viewport, PIXELS_PER_WORLD,
     PLACEHOLDER_HALF_SIZE_PX,
 };
+pub use replay::{RECORD_REPLAY_ENV_VAR, REPLAY_ENV_VAR, REPLAY_FILE_VERSION};
 pub use scene::{
-    ActionParams, ActionState, ActionTargetHint, Camera2D, CardinalFacing, DebugInfoSnapshot,
-    DebugJobState, DebugMarker, DebugMarkerKind, Entity, EntityActionVisual, EntityId, FloorId,
-    InputSnapshot, Interactable, InteractableKind, OrderState, RenderableDesc, RenderableKind,
-    Scene, SceneCommand, SceneDebugCommand, SceneDebugCommandResult, SceneDebugContext, SceneKey,
-    SceneVisualState, SceneWorld, SpriteAnchorName, SpriteAnchorPx, SpriteAnchors, Tilemap,
-    TilemapError, Transform, Vec2, CAMERA_ZOOM_DEFAULT, CAMERA_ZOOM_MAX, CAMERA_ZOOM_MIN,
-    CAMERA_ZOOM_STEP,
+    validate_floor_declaration, ActionParams, ActionState, ActionTargetHint, Camera2D,
+    CameraControllerConfig, CardinalFacing, DebugInfoSnapshot, DebugJobState, DebugMarker,
+    DebugMarkerKind, Entity, EntityActionVisual, EntityId, EntitySight, FactionId, FloorError,
+    FloorId, InputSnapshot, Interactable, InteractableKind, LightAnchor, LightError, LightId,
+    MinimapMarker, MinimapMarkers, MinimapRole, OrderState, PaletteSwapEntry, Particle,
+    ParticleEmitterDef, ParticleEmitterId, ParticleRange, PointLight, PointLightDef, RenderLayer,
+    RenderModifier, RenderOrderKey, RenderableDesc, RenderableKind, ResolvedLight, Scene,
+    SceneCommand, SceneDebugCommand, SceneDebugCommandResult, SceneDebugContext, SceneKey,
+    SceneVisualState, SceneWorld, ScheduledTimer, SightError, SnapshotActionState,
+    SnapshotActionTargetHint, SnapshotCamera, SnapshotDebugMarker, SnapshotEntity,
+    SnapshotEntityActionVisual, SnapshotEntityRemap, SnapshotEntityRenderModifier,
+    SnapshotEntitySight, SnapshotFacing, SnapshotFloor, SnapshotInteractable,
+    SnapshotInteractableKind, SnapshotLight, SnapshotLightAnchor, SnapshotOrderState,
+    SnapshotRenderLayer, SnapshotRenderableKind, SnapshotSpriteAnchors, SnapshotStairLink,
+    SnapshotTilemap, SnapshotTimer, SnapshotVec2, SnapshotVisibilityGrid, SnapshotVisualState,
+    SpriteAnchorName, SpriteAnchorPx, SpriteAnchors, StairLink, StateHasher, TileChannel,
+    TileChunkCoord, TileFlags, TileRect, TileVisibility, Tilemap, TilemapChange, TilemapError,
+    TimerError, TimerFired, TimerId, Transform, Vec2, WidgetAnchor, WorldSnapshot,
+    WorldSnapshotError, WorldWidget, WorldWidgetKind, WorldWidgets, AMBIENT_UNLIT,
+    CAMERA_ZOOM_DEFAULT, CAMERA_ZOOM_MAX, CAMERA_ZOOM_MIN, CAMERA_ZOOM_STEP, LEGACY_FLOOR_NAMES,
+    MAX_FLOORS, MAX_PARTICLES, MAX_POINT_LIGHTS, TILEMAP_CHANGE_LOG_CAPACITY, TILEMAP_CHUNK_SIZE,
+    TILE_EMPTY, TILE_LAYER_TERRAIN, WORLD_SNAPSHOT_VERSION, WORLD_WIDGET_LABEL_MAX_CHARS,
 };
 pub(crate) use tools::{
     format_spawn_command, CommandPaletteButtonKind, CommandPaletteRenderData, CommandPaletteState,
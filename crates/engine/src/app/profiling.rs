@@ -0,0 +1,357 @@
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use super::tools::{RollingMsStats, RollingWindowMs};
+
+/// Folds a zone may go unseen before it drops out of the overlay list.
+const ZONE_STALE_AFTER_FOLDS: u64 = 240;
+/// Zone events buffered for one trace capture; later events are dropped.
+const MAX_CAPTURE_EVENTS: usize = 1 << 20;
+
+thread_local! {
+    static RECORDER: RefCell<ZoneRecorder> = RefCell::new(ZoneRecorder::default());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ZoneEvent {
+    name: &'static str,
+    start: Instant,
+    end: Instant,
+}
+
+#[derive(Debug, Default)]
+struct ZoneRecorder {
+    enabled: bool,
+    open: Vec<(&'static str, Instant)>,
+    events: Vec<ZoneEvent>,
+}
+
+/// Guard returned by `profile_zone`; the zone ends when it drops. Not `Send`, since zones
+/// are recorded per thread.
+#[must_use = "the zone ends as soon as the guard is dropped"]
+pub struct ProfileZone {
+    active: bool,
+    _thread_bound: PhantomData<*const ()>,
+}
+
+/// Opens a profiling zone named `name` on this thread. Zones nest and are only recorded on
+/// the thread running the main loop; elsewhere, and outside the loop (unit tests), this is a
+/// no-op.
+pub fn profile_zone(name: &'static str) -> ProfileZone {
+    let active = RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        if recorder.enabled {
+            recorder.open.push((name, Instant::now()));
+        }
+        recorder.enabled
+    });
+    ProfileZone {
+        active,
+        _thread_bound: PhantomData,
+    }
+}
+
+impl Drop for ProfileZone {
+    fn drop(&mut self) {
+        if !self.active {
+            return;
+        }
+        let end = Instant::now();
+        RECORDER.with(|recorder| {
+            let mut recorder = recorder.borrow_mut();
+            if let Some((name, start)) = recorder.open.pop() {
+                recorder.events.push(ZoneEvent { name, start, end });
+            }
+        });
+    }
+}
+
+fn set_recording_enabled(enabled: bool) {
+    RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        recorder.enabled = enabled;
+        recorder.open.clear();
+        recorder.events.clear();
+    });
+}
+
+fn drain_events(out: &mut Vec<ZoneEvent>) {
+    RECORDER.with(|recorder| out.append(&mut recorder.borrow_mut().events));
+}
+
+/// Rolling per-zone time for the overlay: each fold sums a zone's time since the last fold.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ZoneStat {
+    pub name: &'static str,
+    pub ms: RollingMsStats,
+}
+
+#[derive(Debug)]
+struct ZoneWindow {
+    name: &'static str,
+    window: RollingWindowMs,
+    last_fold: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct TraceEvent {
+    name: &'static str,
+    cat: &'static str,
+    ph: &'static str,
+    ts: f64,
+    dur: f64,
+    pid: u32,
+    tid: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceFile<'a> {
+    trace_events: &'a [TraceEvent],
+    display_time_unit: &'static str,
+}
+
+/// A finished `profile.capture`, ready to be written as Chrome trace-event JSON.
+#[derive(Debug)]
+pub(crate) struct TraceCapture {
+    pub ticks: u32,
+    events: Vec<TraceEvent>,
+    dropped_events: usize,
+}
+
+impl TraceCapture {
+    pub(crate) fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    pub(crate) fn dropped_events(&self) -> usize {
+        self.dropped_events
+    }
+
+    pub(crate) fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&TraceFile {
+            trace_events: &self.events,
+            display_time_unit: "ms",
+        })
+    }
+
+    pub(crate) fn write_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_json().map_err(io::Error::other)?)
+    }
+}
+
+#[derive(Debug)]
+struct ActiveCapture {
+    ticks: u32,
+    ticks_left: u32,
+    events: Vec<TraceEvent>,
+    dropped_events: usize,
+}
+
+/// Owned by the loop: turns the zones recorded on its thread into overlay stats and, while
+/// `profile.capture` runs, trace events. Enables zone recording for its lifetime.
+#[derive(Debug)]
+pub(crate) struct ZoneProfiler {
+    origin: Instant,
+    folds: u64,
+    zones: Vec<ZoneWindow>,
+    scratch: Vec<ZoneEvent>,
+    capture: Option<ActiveCapture>,
+}
+
+impl ZoneProfiler {
+    pub(crate) fn new() -> Self {
+        set_recording_enabled(true);
+        Self {
+            origin: Instant::now(),
+            folds: 0,
+            zones: Vec::new(),
+            scratch: Vec::new(),
+            capture: None,
+        }
+    }
+
+    /// Captures the zones of the next `ticks` fixed ticks and the frames around them.
+    pub(crate) fn start_capture(&mut self, ticks: u32) -> Result<(), String> {
+        if let Some(capture) = &self.capture {
+            return Err(format!(
+                "capture already running ({} of {} ticks left)",
+                capture.ticks_left, capture.ticks
+            ));
+        }
+        self.capture = Some(ActiveCapture {
+            ticks,
+            ticks_left: ticks,
+            events: Vec::new(),
+            dropped_events: 0,
+        });
+        Ok(())
+    }
+
+    /// Folds the zones recorded since the last fold as one tick. Returns the capture once its
+    /// last tick is folded.
+    pub(crate) fn finish_tick(&mut self) -> Option<TraceCapture> {
+        self.fold("tick");
+        let capture = self.capture.as_mut()?;
+        capture.ticks_left = capture.ticks_left.saturating_sub(1);
+        if capture.ticks_left > 0 {
+            return None;
+        }
+        self.capture.take().map(|capture| TraceCapture {
+            ticks: capture.ticks,
+            events: capture.events,
+            dropped_events: capture.dropped_events,
+        })
+    }
+
+    /// Folds the zones recorded since the last fold as frame work (rendering, commands).
+    pub(crate) fn finish_frame(&mut self) {
+        self.fold("frame");
+    }
+
+    /// Zones seen recently, slowest average first.
+    pub(crate) fn zone_stats(&self, limit: usize) -> Vec<ZoneStat> {
+        let mut stats: Vec<ZoneStat> = self
+            .zones
+            .iter()
+            .filter(|zone| self.folds - zone.last_fold <= ZONE_STALE_AFTER_FOLDS)
+            .map(|zone| ZoneStat {
+                name: zone.name,
+                ms: zone.window.snapshot(),
+            })
+            .collect();
+        stats.sort_by(|a, b| b.ms.avg_ms.total_cmp(&a.ms.avg_ms));
+        stats.truncate(limit);
+        stats
+    }
+
+    fn fold(&mut self, category: &'static str) {
+        drain_events(&mut self.scratch);
+        self.folds += 1;
+        let mut totals: Vec<(&'static str, f32)> = Vec::new();
+        for event in &self.scratch {
+            let ms = event
+                .end
+                .saturating_duration_since(event.start)
+                .as_secs_f32()
+                * 1000.0;
+            match totals.iter_mut().find(|(name, _)| *name == event.name) {
+                Some((_, total)) => *total += ms,
+                None => totals.push((event.name, ms)),
+            }
+        }
+        for (name, ms) in totals {
+            let index = match self.zones.iter().position(|zone| zone.name == name) {
+                Some(index) => index,
+                None => {
+                    self.zones.push(ZoneWindow {
+                        name,
+                        window: RollingWindowMs::default(),
+                        last_fold: self.folds,
+                    });
+                    self.zones.len() - 1
+                }
+            };
+            let zone = &mut self.zones[index];
+            zone.window.push_ms(ms);
+            zone.last_fold = self.folds;
+        }
+        if let Some(capture) = self.capture.as_mut() {
+            for event in &self.scratch {
+                if capture.events.len() >= MAX_CAPTURE_EVENTS {
+                    capture.dropped_events += 1;
+                    continue;
+                }
+                capture.events.push(TraceEvent {
+                    name: event.name,
+                    cat: category,
+                    ph: "X",
+                    ts: micros_since(self.origin, event.start),
+                    dur: micros_since(event.start, event.end),
+                    pid: 1,
+                    tid: 1,
+                });
+            }
+        }
+        self.scratch.clear();
+    }
+}
+
+impl Drop for ZoneProfiler {
+    fn drop(&mut self) {
+        set_recording_enabled(false);
+    }
+}
+
+fn micros_since(from: Instant, to: Instant) -> f64 {
+    to.saturating_duration_since(from).as_secs_f64() * 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zones_are_ignored_without_a_profiler() {
+        drop(profile_zone("idle"));
+        let mut events = Vec::new();
+        drain_events(&mut events);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn folds_sum_zones_per_tick_and_capture_writes_chrome_trace_events() {
+        let mut profiler = ZoneProfiler::new();
+        {
+            let _outer = profile_zone("tick");
+            drop(profile_zone("AI"));
+            drop(profile_zone("AI"));
+        }
+        assert!(profiler.finish_tick().is_none());
+        let names: Vec<_> = profiler
+            .zone_stats(8)
+            .iter()
+            .map(|zone| zone.name)
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"tick") && names.contains(&"AI"));
+        assert_eq!(profiler.zone_stats(1).len(), 1);
+
+        profiler.start_capture(2).expect("capture");
+        assert!(profiler.start_capture(1).is_err());
+        drop(profile_zone("render"));
+        profiler.finish_frame();
+        drop(profile_zone("AI"));
+        assert!(profiler.finish_tick().is_none());
+        drop(profile_zone("AI"));
+        let capture = profiler.finish_tick().expect("capture done");
+        assert_eq!(capture.ticks, 2);
+        assert_eq!(capture.event_count(), 3);
+        assert_eq!(capture.dropped_events(), 0);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&capture.to_json().expect("json")).expect("parse");
+        let events = json["traceEvents"].as_array().expect("events");
+        assert_eq!(events[0]["name"], "render");
+        assert_eq!(events[0]["cat"], "frame");
+        assert_eq!(events[1]["cat"], "tick");
+        assert_eq!(events[1]["ph"], "X");
+        assert!(events[2]["ts"].as_f64() >= events[1]["ts"].as_f64());
+        assert_eq!(json["displayTimeUnit"], "ms");
+
+        drop(profiler);
+        drop(profile_zone("after"));
+        let mut leftover = Vec::new();
+        drain_events(&mut leftover);
+        assert!(leftover.is_empty());
+    }
+}
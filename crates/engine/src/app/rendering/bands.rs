@@ -0,0 +1,226 @@
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// A band is only split off while every band keeps at least this many rows.
+const MIN_BAND_ROWS: u32 = 32;
+const MAX_BANDS: usize = 16;
+
+/// Rows `top..bottom` of a `width x height` RGBA framebuffer. Primitives clip to the band and
+/// index relative to its first row, so disjoint bands can be drawn on separate threads and give
+/// the same pixels as a single full-frame band.
+pub(super) struct FrameBand<'a> {
+    bytes: &'a mut [u8],
+    width: u32,
+    height: u32,
+    top: i32,
+    bottom: i32,
+}
+
+impl<'a> FrameBand<'a> {
+    pub(super) fn full(bytes: &'a mut [u8], width: u32, height: u32) -> Self {
+        Self {
+            bytes,
+            width,
+            height,
+            top: 0,
+            bottom: height as i32,
+        }
+    }
+
+    /// Splits the frame into `count` bands of near-equal height (at least one, at most one per
+    /// row), top to bottom.
+    pub(super) fn split(bytes: &'a mut [u8], width: u32, height: u32, count: usize) -> Vec<Self> {
+        let count = count.clamp(1, height.max(1) as usize);
+        let row_bytes = width as usize * 4;
+        let mut bands = Vec::with_capacity(count);
+        let mut rest = bytes;
+        let mut top = 0u32;
+        for index in 1..=count {
+            let bottom = (u64::from(height) * index as u64 / count as u64) as u32;
+            let len = ((bottom - top) as usize * row_bytes).min(rest.len());
+            let (band, tail) = rest.split_at_mut(len);
+            rest = tail;
+            bands.push(Self {
+                bytes: band,
+                width,
+                height,
+                top: top as i32,
+                bottom: bottom as i32,
+            });
+            top = bottom;
+        }
+        bands
+    }
+
+    pub(super) fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height of the whole frame, which screen projection is relative to.
+    pub(super) fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub(super) fn top(&self) -> i32 {
+        self.top
+    }
+
+    pub(super) fn bottom(&self) -> i32 {
+        self.bottom
+    }
+
+    pub(super) fn bytes(&self) -> &[u8] {
+        self.bytes
+    }
+
+    pub(super) fn bytes_mut(&mut self) -> &mut [u8] {
+        self.bytes
+    }
+
+    /// Offset of frame row `y` within this band's bytes; `y` must lie in `top..bottom`.
+    pub(super) fn row_offset(&self, y: i32) -> usize {
+        (y - self.top) as usize * self.width as usize * 4
+    }
+
+    /// Offset of pixel `(x, y)` within this band's bytes, or `None` outside the band.
+    pub(super) fn pixel_offset(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || x >= self.width as i32 || y < self.top || y >= self.bottom {
+            return None;
+        }
+        let offset = self.row_offset(y) + x as usize * 4;
+        (offset + 4 <= self.bytes.len()).then_some(offset)
+    }
+}
+
+pub(super) fn available_raster_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Bands to split a frame of `height` rows into on `threads` threads.
+pub(super) fn band_count_for(height: u32, threads: usize) -> usize {
+    let by_rows = (height / MIN_BAND_ROWS).max(1) as usize;
+    threads.clamp(1, MAX_BANDS).min(by_rows)
+}
+
+/// Long-lived raster workers, so each frame's band passes reuse the same OS threads instead of
+/// spawning new ones. Bands borrow the frame, so they go through a rayon scope, which joins every
+/// band before returning.
+pub(super) struct BandPool {
+    workers: Option<rayon::ThreadPool>,
+}
+
+impl BandPool {
+    /// Spawns one worker per thread after the first, which is the calling thread.
+    pub(super) fn new(threads: usize) -> Self {
+        let worker_count = threads.clamp(1, MAX_BANDS) - 1;
+        let workers = (worker_count > 0)
+            .then(|| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(worker_count)
+                    .thread_name(|index| format!("raster-band-{}", index + 1))
+                    .build()
+                    .ok()
+            })
+            .flatten();
+        Self { workers }
+    }
+
+    /// Runs `draw` on every item: the first on the calling thread, the rest on the workers (or
+    /// inline without any). Returns once all items are drawn; a panic in any band is re-raised
+    /// here.
+    pub(super) fn for_each_band<T: Send>(&self, items: Vec<T>, draw: impl Fn(T) + Sync) {
+        let mut items = items.into_iter();
+        let Some(first) = items.next() else {
+            return;
+        };
+        let Some(workers) = self.workers.as_ref() else {
+            draw(first);
+            items.for_each(&draw);
+            return;
+        };
+        let draw = &draw;
+        workers.in_place_scope(|scope| {
+            for item in items {
+                scope.spawn(move |_| draw(item));
+            }
+            draw(first);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::*;
+
+    #[test]
+    fn split_covers_every_row_once_in_order() {
+        let mut frame = vec![0u8; 5 * 7 * 4];
+        let bands = FrameBand::split(&mut frame, 5, 7, 3);
+        let ranges: Vec<(i32, i32)> = bands.iter().map(|b| (b.top(), b.bottom())).collect();
+        assert_eq!(ranges, vec![(0, 2), (2, 4), (4, 7)]);
+        for band in &bands {
+            assert_eq!(
+                band.bytes().len(),
+                (band.bottom() - band.top()) as usize * 5 * 4
+            );
+            assert_eq!(band.height(), 7);
+        }
+
+        let mut frame = vec![0u8; 2 * 2 * 4];
+        assert_eq!(FrameBand::split(&mut frame, 2, 2, 8).len(), 2);
+    }
+
+    #[test]
+    fn pixel_offsets_are_band_relative_and_clipped_to_the_band() {
+        let mut frame = vec![0u8; 4 * 6 * 4];
+        let bands = FrameBand::split(&mut frame, 4, 6, 2);
+        assert_eq!(bands[1].pixel_offset(1, 3), Some(4));
+        assert_eq!(bands[1].pixel_offset(1, 2), None);
+        assert_eq!(bands[0].pixel_offset(4, 0), None);
+        assert_eq!(bands[0].pixel_offset(3, 2), Some((2 * 4 + 3) * 4));
+    }
+
+    #[test]
+    fn band_count_keeps_a_minimum_band_height() {
+        assert_eq!(band_count_for(720, 8), 8);
+        assert_eq!(band_count_for(720, 64), MAX_BANDS);
+        assert_eq!(band_count_for(64, 8), 2);
+        assert_eq!(band_count_for(10, 8), 1);
+        assert_eq!(band_count_for(720, 0), 1);
+    }
+
+    #[test]
+    fn pool_draws_every_band_across_frames_and_reraises_panics() {
+        let pool = BandPool::new(4);
+        assert_eq!(
+            pool.workers
+                .as_ref()
+                .map(rayon::ThreadPool::current_num_threads),
+            Some(3)
+        );
+        assert!(BandPool::new(1).workers.is_none());
+        for _ in 0..3 {
+            let mut frame = vec![0u8; 4 * 8 * 4];
+            let bands = FrameBand::split(&mut frame, 4, 8, 6);
+            pool.for_each_band(bands, |mut band| {
+                let top = band.top() as u8;
+                band.bytes_mut().fill(top + 1);
+            });
+            let band_tops = [0, 1, 2, 2, 4, 5, 6, 6];
+            for (row, bytes) in frame.chunks_exact(4 * 4).enumerate() {
+                assert!(bytes.iter().all(|byte| *byte == band_tops[row] + 1));
+            }
+        }
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.for_each_band(vec![0, 1, 2], |item| assert_ne!(item, 2));
+        }));
+        assert!(result.is_err());
+        let mut drawn = vec![false; 4];
+        let cells: Vec<_> = drawn.iter_mut().collect();
+        pool.for_each_band(cells, |cell| *cell = true);
+        assert!(drawn.iter().all(|cell| *cell));
+    }
+}
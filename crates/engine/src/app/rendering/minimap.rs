@@ -0,0 +1,592 @@
+use crate::app::{
+    FloorId, MinimapRole, SceneWorld, TileChannel, TileRect, TileVisibility, Tilemap, Vec2,
+    TILE_EMPTY,
+};
+
+use super::renderer::tile_fallback_color;
+use super::transform::camera_pixels_per_world;
+
+/// Largest side of the map image; the map is fitted inside this box keeping its aspect ratio.
+const MINIMAP_BOX_PX: i32 = 160;
+const MINIMAP_MARGIN_PX: i32 = 12;
+/// Frames a cached image is reused while the floor and layout are unchanged, so dots and fog
+/// stay roughly current without resampling the map every frame. Tile edits in between only
+/// repaint the changed cells.
+const MINIMAP_REDRAW_INTERVAL_FRAMES: u32 = 10;
+const MINIMAP_EMPTY_COLOR: [u8; 4] = [12, 14, 18, 255];
+const MINIMAP_UNEXPLORED_COLOR: [u8; 4] = [6, 7, 10, 255];
+const MINIMAP_EXPLORED_GAIN: [u16; 3] = [104, 108, 120];
+const MINIMAP_BORDER_COLOR: [u8; 4] = [92, 106, 126, 255];
+const MINIMAP_VIEW_RECT_COLOR: [u8; 4] = [240, 244, 250, 255];
+const MINIMAP_DOT_HALF_SIZE_PX: i32 = 1;
+const MINIMAP_PLAYER_COLOR: [u8; 4] = [80, 220, 255, 255];
+const MINIMAP_SETTLER_COLOR: [u8; 4] = [120, 235, 120, 255];
+const MINIMAP_NPC_COLOR: [u8; 4] = [255, 90, 80, 255];
+const MINIMAP_INTERACTABLE_COLOR: [u8; 4] = [255, 210, 70, 255];
+
+/// Screen placement of the minimap for the active floor's tilemap: bottom-right corner, one
+/// image pixel per `1 / px_per_tile` tiles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MinimapLayout {
+    left: i32,
+    top: i32,
+    width: i32,
+    height: i32,
+    tiles_x: u32,
+    tiles_y: u32,
+    origin: Vec2,
+    px_per_tile: f32,
+}
+
+impl MinimapLayout {
+    /// `None` when the active floor has no tilemap or the window is too small to fit the panel.
+    pub(crate) fn for_world(world: &SceneWorld, viewport: (u32, u32)) -> Option<Self> {
+        let tilemap = world.tilemap()?;
+        let (tiles_x, tiles_y) = (tilemap.width(), tilemap.height());
+        if tiles_x == 0 || tiles_y == 0 {
+            return None;
+        }
+        let px_per_tile =
+            (MINIMAP_BOX_PX as f32 / tiles_x as f32).min(MINIMAP_BOX_PX as f32 / tiles_y as f32);
+        let width = ((tiles_x as f32 * px_per_tile).round() as i32).max(1);
+        let height = ((tiles_y as f32 * px_per_tile).round() as i32).max(1);
+        let left = viewport.0 as i32 - MINIMAP_MARGIN_PX - width;
+        let top = viewport.1 as i32 - MINIMAP_MARGIN_PX - height;
+        if left < MINIMAP_MARGIN_PX || top < MINIMAP_MARGIN_PX {
+            return None;
+        }
+        Some(Self {
+            left,
+            top,
+            width,
+            height,
+            tiles_x,
+            tiles_y,
+            origin: tilemap.origin(),
+            px_per_tile,
+        })
+    }
+
+    /// World position under `screen_px`, or `None` when the cursor is outside the map image.
+    pub(crate) fn world_at(&self, screen_px: Vec2) -> Option<Vec2> {
+        let local_x = screen_px.x - self.left as f32;
+        let local_y = screen_px.y - self.top as f32;
+        if local_x < 0.0
+            || local_y < 0.0
+            || local_x >= self.width as f32
+            || local_y >= self.height as f32
+        {
+            return None;
+        }
+        Some(Vec2 {
+            x: self.origin.x + local_x / self.px_per_tile,
+            y: self.origin.y + self.tiles_y as f32 - local_y / self.px_per_tile,
+        })
+    }
+
+    /// Image-local pixel of `world`; screen y grows down while world y grows up.
+    fn local_px(&self, world: Vec2) -> (i32, i32) {
+        let x = (world.x - self.origin.x) * self.px_per_tile;
+        let y = (self.origin.y + self.tiles_y as f32 - world.y) * self.px_per_tile;
+        (x.floor() as i32, y.floor() as i32)
+    }
+
+    /// Tile sampled for image pixel `(x, y)`, taken at the pixel center.
+    fn tile_at_px(&self, x: i32, y: i32) -> (u32, u32) {
+        let tile_x = ((x as f32 + 0.5) / self.px_per_tile) as u32;
+        let row_from_top = ((y as f32 + 0.5) / self.px_per_tile) as u32;
+        let tile_y = self
+            .tiles_y
+            .saturating_sub(1)
+            .saturating_sub(row_from_top.min(self.tiles_y - 1));
+        (tile_x.min(self.tiles_x - 1), tile_y)
+    }
+}
+
+/// Inputs the cached image was sampled from; a change forces a redraw before the interval.
+#[derive(Debug, Clone, PartialEq)]
+struct MinimapKey {
+    floor: FloorId,
+    layout: MinimapLayout,
+}
+
+/// Downsampled tilemap, fog and entity dots; the view rectangle is drawn over it every frame.
+#[derive(Debug, Default)]
+pub(crate) struct MinimapCache {
+    key: Option<MinimapKey>,
+    tilemap_epoch: u64,
+    frames_since_redraw: u32,
+    rgba: Vec<u8>,
+}
+
+impl MinimapCache {
+    /// Redraws the image when its key changed or the throttle interval elapsed, and otherwise
+    /// repaints only the cells named by the tilemap change log; returns whether the image changed.
+    fn refresh(&mut self, world: &SceneWorld, layout: MinimapLayout) -> bool {
+        let key = MinimapKey {
+            floor: world.active_floor(),
+            layout,
+        };
+        let tilemap_epoch = world.tilemap_epoch();
+        if self.key.as_ref() == Some(&key)
+            && self.frames_since_redraw < MINIMAP_REDRAW_INTERVAL_FRAMES
+        {
+            if tilemap_epoch == self.tilemap_epoch {
+                self.frames_since_redraw += 1;
+                return false;
+            }
+            if let Some(changes) = world.tilemap_changes_since(self.tilemap_epoch) {
+                let mut repainted = false;
+                for change in changes {
+                    if change.floor != key.floor || change.channel == TileChannel::Flags {
+                        continue;
+                    }
+                    repaint_minimap_tiles(&mut self.rgba, world, &layout, change.rect);
+                    repainted = true;
+                }
+                if repainted {
+                    draw_minimap_dots(&mut self.rgba, world, &layout);
+                }
+                self.tilemap_epoch = tilemap_epoch;
+                self.frames_since_redraw += 1;
+                return repainted;
+            }
+        }
+        rebuild_minimap_image(&mut self.rgba, world, &layout);
+        self.key = Some(key);
+        self.tilemap_epoch = tilemap_epoch;
+        self.frames_since_redraw = 1;
+        true
+    }
+}
+
+/// World position a click at `screen_px` should center the camera on, if it hit the minimap.
+pub(crate) fn minimap_world_position_at(
+    world: &SceneWorld,
+    viewport: (u32, u32),
+    screen_px: Vec2,
+) -> Option<Vec2> {
+    MinimapLayout::for_world(world, viewport)?.world_at(screen_px)
+}
+
+pub(super) fn draw_minimap(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    world: &SceneWorld,
+    cache: &mut MinimapCache,
+) {
+    let Some(layout) = MinimapLayout::for_world(world, (width, height)) else {
+        return;
+    };
+    cache.refresh(world, layout);
+
+    let row_bytes = layout.width as usize * 4;
+    for (row, source) in cache.rgba.chunks_exact(row_bytes).enumerate() {
+        let y = layout.top as usize + row;
+        let start = (y * width as usize + layout.left as usize) * 4;
+        if let Some(target) = frame.get_mut(start..start + row_bytes) {
+            target.copy_from_slice(source);
+        }
+    }
+    draw_rect_outline(
+        frame,
+        width,
+        height,
+        layout.left - 1,
+        layout.top - 1,
+        layout.left + layout.width,
+        layout.top + layout.height,
+        MINIMAP_BORDER_COLOR,
+    );
+
+    let camera = world.camera();
+    let pixels_per_world = camera_pixels_per_world(camera);
+    if !pixels_per_world.is_finite() || pixels_per_world <= f32::EPSILON {
+        return;
+    }
+    let half_view = Vec2 {
+        x: width as f32 / (2.0 * pixels_per_world),
+        y: height as f32 / (2.0 * pixels_per_world),
+    };
+    let (view_left, view_top) = layout.local_px(Vec2 {
+        x: camera.position.x - half_view.x,
+        y: camera.position.y + half_view.y,
+    });
+    let (view_right, view_bottom) = layout.local_px(Vec2 {
+        x: camera.position.x + half_view.x,
+        y: camera.position.y - half_view.y,
+    });
+    let clamp_x = |x: i32| x.clamp(0, layout.width - 1) + layout.left;
+    let clamp_y = |y: i32| y.clamp(0, layout.height - 1) + layout.top;
+    if view_right < 0 || view_bottom < 0 || view_left >= layout.width || view_top >= layout.height {
+        return;
+    }
+    draw_rect_outline(
+        frame,
+        width,
+        height,
+        clamp_x(view_left),
+        clamp_y(view_top),
+        clamp_x(view_right),
+        clamp_y(view_bottom),
+        MINIMAP_VIEW_RECT_COLOR,
+    );
+}
+
+fn rebuild_minimap_image(rgba: &mut Vec<u8>, world: &SceneWorld, layout: &MinimapLayout) {
+    rgba.clear();
+    rgba.resize(layout.width as usize * layout.height as usize * 4, 0);
+    let Some(tilemap) = world.tilemap() else {
+        return;
+    };
+    for y in 0..layout.height {
+        for x in 0..layout.width {
+            let (tile_x, tile_y) = layout.tile_at_px(x, y);
+            let offset = (y as usize * layout.width as usize + x as usize) * 4;
+            rgba[offset..offset + 4]
+                .copy_from_slice(&minimap_tile_color(world, tilemap, tile_x, tile_y));
+        }
+    }
+    draw_minimap_dots(rgba, world, layout);
+}
+
+/// Repaints the image pixels sampling a tile inside `rect`; dots under them must be redrawn.
+fn repaint_minimap_tiles(
+    rgba: &mut [u8],
+    world: &SceneWorld,
+    layout: &MinimapLayout,
+    rect: TileRect,
+) {
+    let Some(tilemap) = world.tilemap() else {
+        return;
+    };
+    // Pixel span of the rect, padded by one pixel on each side for rounding in `tile_at_px`.
+    let px = |tiles: u32| tiles as f32 * layout.px_per_tile;
+    let x_start = (px(rect.x).floor() as i32 - 1).max(0);
+    let x_end = (px(rect.x + rect.width).ceil() as i32 + 1).min(layout.width);
+    let y_start =
+        (px(layout.tiles_y.saturating_sub(rect.y + rect.height)).floor() as i32 - 1).max(0);
+    let y_end = (px(layout.tiles_y.saturating_sub(rect.y)).ceil() as i32 + 1).min(layout.height);
+    for y in y_start..y_end {
+        for x in x_start..x_end {
+            let (tile_x, tile_y) = layout.tile_at_px(x, y);
+            if tile_x < rect.x
+                || tile_y < rect.y
+                || tile_x >= rect.x + rect.width
+                || tile_y >= rect.y + rect.height
+            {
+                continue;
+            }
+            let offset = (y as usize * layout.width as usize + x as usize) * 4;
+            rgba[offset..offset + 4]
+                .copy_from_slice(&minimap_tile_color(world, tilemap, tile_x, tile_y));
+        }
+    }
+}
+
+/// Topmost non-empty layer's color, darkened or hidden by the fog viewer's visibility.
+fn minimap_tile_color(world: &SceneWorld, tilemap: &Tilemap, tile_x: u32, tile_y: u32) -> [u8; 4] {
+    let top_tile = (0..tilemap.layer_count())
+        .rev()
+        .filter_map(|layer| tilemap.layer_tile_at(layer, tile_x, tile_y))
+        .find(|tile_id| *tile_id != TILE_EMPTY);
+    let mut color = top_tile.map_or(MINIMAP_EMPTY_COLOR, tile_fallback_color);
+    let floor = world.active_floor();
+    match world
+        .fog_viewer()
+        .map(|viewer| world.tile_visibility(viewer, floor, tile_x, tile_y))
+    {
+        Some(TileVisibility::Unexplored) => color = MINIMAP_UNEXPLORED_COLOR,
+        Some(TileVisibility::Explored) => {
+            for (channel, gain) in color.iter_mut().zip(MINIMAP_EXPLORED_GAIN) {
+                *channel = ((*channel as u16 * gain) >> 8) as u8;
+            }
+        }
+        Some(TileVisibility::Visible) | None => {}
+    }
+    color
+}
+
+fn draw_minimap_dots(rgba: &mut [u8], world: &SceneWorld, layout: &MinimapLayout) {
+    let mut dots = world
+        .minimap_markers()
+        .iter()
+        .filter_map(|marker| {
+            let position = world.minimap_marker_position(*marker)?;
+            Some((minimap_role_draw_rank(marker.role), marker.role, position))
+        })
+        .collect::<Vec<_>>();
+    dots.sort_by_key(|(rank, _, _)| *rank);
+    for (_, role, position) in dots {
+        let (cx, cy) = layout.local_px(position);
+        let color = minimap_role_color(role);
+        for y in (cy - MINIMAP_DOT_HALF_SIZE_PX)..=(cy + MINIMAP_DOT_HALF_SIZE_PX) {
+            for x in (cx - MINIMAP_DOT_HALF_SIZE_PX)..=(cx + MINIMAP_DOT_HALF_SIZE_PX) {
+                if x < 0 || y < 0 || x >= layout.width || y >= layout.height {
+                    continue;
+                }
+                let offset = (y as usize * layout.width as usize + x as usize) * 4;
+                rgba[offset..offset + 4].copy_from_slice(&color);
+            }
+        }
+    }
+}
+
+fn minimap_role_color(role: MinimapRole) -> [u8; 4] {
+    match role {
+        MinimapRole::Player => MINIMAP_PLAYER_COLOR,
+        MinimapRole::Settler => MINIMAP_SETTLER_COLOR,
+        MinimapRole::Npc => MINIMAP_NPC_COLOR,
+        MinimapRole::Interactable => MINIMAP_INTERACTABLE_COLOR,
+    }
+}
+
+/// Later ranks draw on top, so the player dot is never covered.
+fn minimap_role_draw_rank(role: MinimapRole) -> u8 {
+    match role {
+        MinimapRole::Interactable => 0,
+        MinimapRole::Npc => 1,
+        MinimapRole::Settler => 2,
+        MinimapRole::Player => 3,
+    }
+}
+
+/// One-pixel outline of the inclusive screen rect `[left, right] x [top, bottom]`.
+#[allow(clippy::too_many_arguments)]
+fn draw_rect_outline(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    color: [u8; 4],
+) {
+    let mut write = |x: i32, y: i32| {
+        if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+            return;
+        }
+        let offset = (y as usize * width as usize + x as usize) * 4;
+        if let Some(pixel) = frame.get_mut(offset..offset + 4) {
+            pixel.copy_from_slice(&color);
+        }
+    };
+    for x in left..=right {
+        write(x, top);
+        write(x, bottom);
+    }
+    for y in top..=bottom {
+        write(left, y);
+        write(right, y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{
+        EntitySight, FactionId, MinimapMarker, RenderableDesc, RenderableKind, TileFlags, Tilemap,
+        Transform,
+    };
+
+    const VIEWPORT: (u32, u32) = (640, 480);
+
+    fn world_with_tilemap(width: u32, height: u32) -> SceneWorld {
+        let mut world = SceneWorld::default();
+        let tiles = vec![0u16; (width * height) as usize];
+        world.set_tilemap(
+            Tilemap::new(width, height, Vec2 { x: -10.0, y: -5.0 }, tiles).expect("tilemap"),
+        );
+        world
+    }
+
+    fn pixel(frame: &[u8], x: i32, y: i32) -> [u8; 4] {
+        let offset = (y as usize * VIEWPORT.0 as usize + x as usize) * 4;
+        frame[offset..offset + 4].try_into().expect("pixel")
+    }
+
+    #[test]
+    fn layout_fits_the_map_in_the_bottom_right_box_and_maps_clicks_to_world() {
+        let world = world_with_tilemap(80, 40);
+        let layout = MinimapLayout::for_world(&world, VIEWPORT).expect("layout");
+        assert_eq!((layout.width, layout.height), (160, 80));
+        assert_eq!(layout.left, 640 - MINIMAP_MARGIN_PX - 160);
+        assert_eq!(layout.top, 480 - MINIMAP_MARGIN_PX - 80);
+
+        let top_left = Vec2 {
+            x: layout.left as f32,
+            y: layout.top as f32,
+        };
+        assert_eq!(layout.world_at(top_left), Some(Vec2 { x: -10.0, y: 35.0 }));
+        let center = Vec2 {
+            x: layout.left as f32 + 80.0,
+            y: layout.top as f32 + 40.0,
+        };
+        assert_eq!(
+            minimap_world_position_at(&world, VIEWPORT, center),
+            Some(Vec2 { x: 30.0, y: 15.0 })
+        );
+        assert_eq!(layout.world_at(Vec2 { x: 0.0, y: 0.0 }), None);
+
+        assert!(MinimapLayout::for_world(&world, (100, 100)).is_none());
+        assert!(MinimapLayout::for_world(&SceneWorld::default(), VIEWPORT).is_none());
+    }
+
+    #[test]
+    fn cache_redraws_on_tilemap_epoch_change_or_after_the_throttle_interval() {
+        let mut world = world_with_tilemap(16, 16);
+        let layout = MinimapLayout::for_world(&world, VIEWPORT).expect("layout");
+        let mut cache = MinimapCache::default();
+        assert!(cache.refresh(&world, layout));
+        for _ in 1..MINIMAP_REDRAW_INTERVAL_FRAMES {
+            assert!(!cache.refresh(&world, layout));
+        }
+        assert!(cache.refresh(&world, layout));
+
+        assert!(!cache.refresh(&world, layout));
+        let terrain = 0;
+        assert!(world.set_tile(terrain, 3, 3, 1));
+        assert!(cache.refresh(&world, layout));
+    }
+
+    #[test]
+    fn cache_repaints_only_changed_cells_from_the_tilemap_change_log() {
+        let mut world = world_with_tilemap(16, 16);
+        let layout = MinimapLayout::for_world(&world, VIEWPORT).expect("layout");
+        let mut cache = MinimapCache::default();
+        assert!(cache.refresh(&world, layout));
+        let offset = |x: i32, y: i32| (y as usize * layout.width as usize + x as usize) * 4;
+        let poison = [1, 2, 3, 255];
+        cache.rgba[offset(0, 0)..offset(0, 0) + 4].copy_from_slice(&poison);
+
+        let terrain = 0;
+        assert!(world.set_tile(terrain, 3, 3, 1));
+        assert!(cache.refresh(&world, layout));
+        let (x, y) = layout.local_px(Vec2 {
+            x: -10.0 + 3.5,
+            y: -5.0 + 3.5,
+        });
+        assert_eq!(
+            cache.rgba[offset(x, y)..offset(x, y) + 4],
+            tile_fallback_color(1)
+        );
+        assert_eq!(cache.rgba[offset(0, 0)..offset(0, 0) + 4], poison);
+
+        assert!(world.set_tile_flags(5, 5, TileFlags::BLOCKS_MOVEMENT));
+        assert!(!cache.refresh(&world, layout));
+
+        world.set_tilemap(
+            Tilemap::new(16, 16, Vec2 { x: -10.0, y: -5.0 }, vec![0u16; 256]).expect("tilemap"),
+        );
+        assert!(cache.refresh(&world, layout));
+        assert_ne!(cache.rgba[offset(0, 0)..offset(0, 0) + 4], poison);
+    }
+
+    #[test]
+    fn dots_use_role_colors_and_skip_fogged_entities() {
+        let mut world = world_with_tilemap(16, 16);
+        let settler = world.spawn(
+            Transform {
+                position: Vec2 { x: -2.5, y: 2.5 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "settler",
+            },
+        );
+        let npc = world.spawn(
+            Transform {
+                position: Vec2 { x: 4.5, y: 8.5 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "npc",
+            },
+        );
+        world.apply_pending();
+        world
+            .minimap_markers_mut()
+            .push(settler, MinimapRole::Settler);
+        world.minimap_markers_mut().push(npc, MinimapRole::Npc);
+        let layout = MinimapLayout::for_world(&world, VIEWPORT).expect("layout");
+
+        let mut frame = vec![0u8; VIEWPORT.0 as usize * VIEWPORT.1 as usize * 4];
+        draw_minimap(
+            &mut frame,
+            VIEWPORT.0,
+            VIEWPORT.1,
+            &world,
+            &mut MinimapCache::default(),
+        );
+        let screen = |position: Vec2| {
+            let (x, y) = layout.local_px(position);
+            (layout.left + x, layout.top + y)
+        };
+        let (sx, sy) = screen(Vec2 { x: -2.5, y: 2.5 });
+        assert_eq!(pixel(&frame, sx, sy), MINIMAP_SETTLER_COLOR);
+        let (nx, ny) = screen(Vec2 { x: 4.5, y: 8.5 });
+        assert_eq!(pixel(&frame, nx, ny), MINIMAP_NPC_COLOR);
+        assert_eq!(
+            pixel(&frame, layout.left - 1, layout.top - 1),
+            MINIMAP_BORDER_COLOR
+        );
+
+        world
+            .set_entity_sight(
+                settler,
+                EntitySight {
+                    faction: FactionId(0),
+                    radius: 2.0,
+                },
+            )
+            .expect("sight");
+        world.set_fog_viewer(Some(FactionId(0)));
+        world.update_visibility();
+        let mut fogged = vec![0u8; frame.len()];
+        draw_minimap(
+            &mut fogged,
+            VIEWPORT.0,
+            VIEWPORT.1,
+            &world,
+            &mut MinimapCache::default(),
+        );
+        assert_eq!(pixel(&fogged, sx, sy), MINIMAP_SETTLER_COLOR);
+        assert_eq!(pixel(&fogged, nx, ny), MINIMAP_UNEXPLORED_COLOR);
+        assert!(world
+            .minimap_marker_position(MinimapMarker {
+                entity: npc,
+                role: MinimapRole::Npc,
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn view_rectangle_tracks_the_camera() {
+        let mut world = world_with_tilemap(64, 64);
+        world.recenter_camera(Vec2 { x: 22.0, y: 27.0 });
+        let layout = MinimapLayout::for_world(&world, VIEWPORT).expect("layout");
+        let mut frame = vec![0u8; VIEWPORT.0 as usize * VIEWPORT.1 as usize * 4];
+        draw_minimap(
+            &mut frame,
+            VIEWPORT.0,
+            VIEWPORT.1,
+            &world,
+            &mut MinimapCache::default(),
+        );
+        // 640x480 at 32 px per tile is a 20x15 tile view: tiles x 12..32, y 19.5..34.5.
+        let (left, top) = layout.local_px(Vec2 { x: 12.0, y: 34.5 });
+        assert_eq!(
+            pixel(&frame, layout.left + left, layout.top + top),
+            MINIMAP_VIEW_RECT_COLOR
+        );
+        let (right, _) = layout.local_px(Vec2 { x: 32.0, y: 34.5 });
+        assert_eq!(
+            pixel(&frame, layout.left + right, layout.top + top),
+            MINIMAP_VIEW_RECT_COLOR
+        );
+    }
+}
@@ -1,7 +1,12 @@
+mod bands;
+mod minimap;
 mod renderer;
+mod text;
 mod transform;
 
+pub(crate) use minimap::minimap_world_position_at;
 pub use renderer::Renderer;
+pub(crate) use text::{draw_text, BitmapFont, TextAlign, TextStyle, UI_LINE_HEIGHT_PX};
 pub use transform::{
     screen_to_world_px, world_to_screen, world_to_screen_px, Viewport, PIXELS_PER_WORLD,
 };
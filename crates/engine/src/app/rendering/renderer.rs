@@ -8,14 +8,19 @@ use tracing::warn;
 use winit::window::Window;
 
 use crate::app::{
+    profile_zone,
     tools::{draw_command_palette, draw_console, draw_overlay},
     ActionState, Camera2D, CardinalFacing, CommandPaletteRenderData, ConsoleState, DebugMarkerKind,
-    Entity, EntityActionVisual, FloorId, OverlayData, RenderableKind, SceneWorld, SpriteAnchorName,
-    SpriteAnchorPx, SpriteAnchors, Tilemap, Vec2,
+    Entity, EntityActionVisual, FloorId, OverlayData, RenderModifier, RenderOrderKey,
+    RenderableKind, ResolvedLight, SceneWorld, SpriteAnchorName, SpriteAnchorPx, SpriteAnchors,
+    TileVisibility, Tilemap, Transform, Vec2, WorldWidgetKind, AMBIENT_UNLIT, TILE_EMPTY,
 };
 use crate::content::DefDatabase;
 use crate::sprite_keys::validate_sprite_key;
 
+use super::bands::{available_raster_threads, band_count_for, BandPool, FrameBand};
+use super::minimap::{draw_minimap, MinimapCache};
+use super::text::{draw_text, BitmapFont, FontLibrary, TextAlign, TextStyle};
 use super::transform::camera_pixels_per_world;
 use super::{world_to_screen_px, Viewport, PIXELS_PER_WORLD, PLACEHOLDER_HALF_SIZE_PX};
 use std::f32::consts::TAU;
@@ -41,7 +46,20 @@ const HOVER_HIGHLIGHT_HALF_SIZE_PX: i32 = 8;
 const ORDER_MARKER_HALF_SIZE_PX: i32 = 6;
 const VIEW_CULL_PADDING_PX: f32 = 16.0;
 const ENTITY_CULL_RADIUS_WORLD_TILES: f32 = 0.5;
+const WORLD_WIDGET_CULL_RADIUS_WORLD_TILES: f32 = 2.0;
+const WORLD_WIDGET_SCALE_MIN: f32 = 0.5;
+const WORLD_WIDGET_SCALE_MAX: f32 = 2.0;
+const WORLD_WIDGET_LABEL_MIN_ZOOM: f32 = 0.75;
+const WORLD_WIDGET_LABEL_SHADOW_COLOR: [u8; 4] = [0, 0, 0, 255];
+const WORLD_WIDGET_LABEL_LINE_HEIGHT_PX: i32 = 7;
+/// Labels wrap past this width (per text scale step) and stay centred on their anchor.
+const WORLD_WIDGET_LABEL_WRAP_WIDTH_PX: i32 = 112;
+const WORLD_WIDGET_ARC_TRACK_ALPHA_DIVISOR: u8 = 3;
 const MICRO_GRID_RESOLUTION_PX: i32 = 1;
+const LIGHT_CELLS_PER_WORLD: f32 = 4.0;
+const LIGHT_UNIT_GAIN: u16 = 256;
+const FOG_UNEXPLORED_COLOR: [u8; 4] = [6, 7, 10, 255];
+const FOG_EXPLORED_GAIN: [u16; 3] = [104, 108, 120];
 const IDLE_BOB_AMPLITUDE_PX: f32 = 0.35;
 const IDLE_BOB_CYCLES_PER_TICK: f32 = 0.0125;
 const WALK_BOB_AMPLITUDE_PX: f32 = 1.0;
@@ -118,29 +136,94 @@ struct ProceduralVisualOffset {
     rotation_radians: f32,
 }
 
+/// Inputs the lightmap was built from; the cells are reused while these stay equal.
+#[derive(Debug, Clone, PartialEq)]
+struct LightmapKey {
+    floor: FloorId,
+    ambient_rgb: [u8; 3],
+    cell_x_min: i32,
+    cell_y_min: i32,
+    columns: usize,
+    rows: usize,
+    lights: Vec<ResolvedLight>,
+}
+
+/// Per-cell channel gains on a world-aligned grid of `1 / LIGHT_CELLS_PER_WORLD` tiles, in
+/// units of `1 / LIGHT_UNIT_GAIN`, row-major from `cell_y_min`.
+#[derive(Debug, Default)]
+struct LightmapCache {
+    key: Option<LightmapKey>,
+    gains: Vec<[u16; 3]>,
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 struct WalkSpringState {
     amplitude: f32,
     last_tick: u64,
 }
 
+/// Clear color, tilemap and grid of the last frame whose inputs match the key; re-blitted each
+/// frame until the camera, viewport, floor or tilemap change.
+#[derive(Debug, Clone, PartialEq)]
+struct BackgroundKey {
+    world_identity: usize,
+    floor: FloorId,
+    tilemap_epoch: u64,
+    view_center: Vec2,
+    zoom: f32,
+    viewport: (u32, u32),
+}
+
+impl BackgroundKey {
+    fn for_world(world: &SceneWorld, viewport: (u32, u32)) -> Self {
+        Self {
+            world_identity: world as *const SceneWorld as usize,
+            floor: world.active_floor(),
+            tilemap_epoch: world.tilemap_epoch(),
+            view_center: world.camera().view_center(),
+            zoom: world.camera().effective_zoom(),
+            viewport,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BackgroundCache {
+    key: Option<BackgroundKey>,
+    rgba: Vec<u8>,
+}
+
+/// A visible entity with its offset and sprites resolved on the render thread, so bands only
+/// read shared data.
+struct EntityDraw<'a> {
+    entity: &'a Entity,
+    action_visual: &'a EntityActionVisual,
+    offset_px: Vec2,
+    modifier: Option<&'a RenderModifier>,
+    sprite: Option<(&'a LoadedSprite, bool)>,
+    carry: Option<CarryDraw<'a>>,
+}
+
+struct CarryDraw<'a> {
+    center_px: (i32, i32),
+    sprite: &'a LoadedSprite,
+    pixel_scale: u8,
+}
+
 pub struct Renderer {
     window: Arc<Window>,
     pixels: Pixels<'static>,
     viewport: Viewport,
-    asset_root: PathBuf,
-    sprite_cache: HashMap<String, Option<LoadedSprite>>,
-    warned_missing_sprite_keys: HashSet<String>,
-    visible_entity_draw_indices: Vec<usize>,
-    carry_sprite_cache: HashMap<String, Option<CachedCarrySprite>>,
-    last_def_db_identity: Option<usize>,
-    walk_spring_by_entity: HashMap<crate::app::EntityId, WalkSpringState>,
+    world_rasterizer: WorldRasterizer,
+    minimap_cache: MinimapCache,
+    fonts: FontLibrary,
 }
 
 impl Renderer {
     pub fn new(window: Arc<Window>, asset_root: PathBuf) -> Result<Self, Error> {
         let size = window.inner_size();
         let pixels = Self::build_pixels(Arc::clone(&window), size.width, size.height)?;
+        let fonts = FontLibrary::load(&asset_root);
         Ok(Self {
             window,
             pixels,
@@ -148,13 +231,9 @@ impl Renderer {
                 width: size.width,
                 height: size.height,
             },
-            asset_root,
-            sprite_cache: HashMap::new(),
-            warned_missing_sprite_keys: HashSet::new(),
-            visible_entity_draw_indices: Vec::new(),
-            carry_sprite_cache: HashMap::new(),
-            last_def_db_identity: None,
-            walk_spring_by_entity: HashMap::new(),
+            world_rasterizer: WorldRasterizer::new(asset_root, available_raster_threads()),
+            minimap_cache: MinimapCache::default(),
+            fonts,
         })
     }
 
@@ -188,204 +267,386 @@ impl Renderer {
             return Ok(());
         }
 
-        let asset_root = self.asset_root.as_path();
-        let sprite_cache = &mut self.sprite_cache;
-        let warned_missing_sprite_keys = &mut self.warned_missing_sprite_keys;
-        let visible_entity_draw_indices = &mut self.visible_entity_draw_indices;
-        let carry_sprite_cache = &mut self.carry_sprite_cache;
-        let walk_spring_by_entity = &mut self.walk_spring_by_entity;
+        let ui_font = self.fonts.ui();
         let frame = self.pixels.frame_mut();
+        self.world_rasterizer.draw(
+            frame,
+            self.viewport.width,
+            self.viewport.height,
+            world,
+            sim_tick_counter,
+            ui_font,
+        );
+        let minimap_zone = profile_zone("render.minimap");
+        draw_minimap(
+            frame,
+            self.viewport.width,
+            self.viewport.height,
+            world,
+            &mut self.minimap_cache,
+        );
+        drop(minimap_zone);
+
+        let ui_zone = profile_zone("render.ui");
+        if let Some(data) = overlay_data {
+            draw_overlay(
+                frame,
+                self.viewport.width,
+                self.viewport.height,
+                ui_font,
+                data,
+            );
+        }
+        if let Some(palette) = command_palette {
+            draw_command_palette(
+                frame,
+                self.viewport.width,
+                self.viewport.height,
+                ui_font,
+                palette,
+            );
+        }
+        if let Some(console) = console_state {
+            draw_console(
+                frame,
+                self.viewport.width,
+                self.viewport.height,
+                ui_font,
+                console,
+            );
+        }
+        drop(ui_zone);
+
+        let _present_zone = profile_zone("render.present");
+        self.pixels.render()
+    }
+}
+
+/// Draws the world layers (tiles, grid, entities, particles, lighting, fog, affordances and
+/// widgets) and owns the caches they read. Tiles, grid, entities, lighting and fog are drawn in
+/// horizontal bands on up to `raster_threads` threads; the output matches a single band.
+struct WorldRasterizer {
+    asset_root: PathBuf,
+    raster_threads: usize,
+    band_pool: BandPool,
+    sprite_cache: HashMap<String, Option<LoadedSprite>>,
+    warned_missing_sprite_keys: HashSet<String>,
+    visible_entity_draw_indices: Vec<usize>,
+    entity_offsets_px: Vec<Vec2>,
+    carry_sprite_cache: HashMap<String, Option<CachedCarrySprite>>,
+    last_def_db_identity: Option<usize>,
+    walk_spring_by_entity: HashMap<crate::app::EntityId, WalkSpringState>,
+    lightmap_cache: LightmapCache,
+    background_cache: BackgroundCache,
+}
+
+impl WorldRasterizer {
+    fn new(asset_root: PathBuf, raster_threads: usize) -> Self {
+        Self {
+            asset_root,
+            raster_threads,
+            band_pool: BandPool::new(raster_threads),
+            sprite_cache: HashMap::new(),
+            warned_missing_sprite_keys: HashSet::new(),
+            visible_entity_draw_indices: Vec::new(),
+            entity_offsets_px: Vec::new(),
+            carry_sprite_cache: HashMap::new(),
+            last_def_db_identity: None,
+            walk_spring_by_entity: HashMap::new(),
+            lightmap_cache: LightmapCache::default(),
+            background_cache: BackgroundCache::default(),
+        }
+    }
+
+    fn draw(
+        &mut self,
+        frame: &mut [u8],
+        width: u32,
+        height: u32,
+        world: &SceneWorld,
+        sim_tick_counter: u64,
+        ui_font: &BitmapFont,
+    ) {
+        let world_zone = profile_zone("render.world");
         let def_db = world.def_database();
         let def_db_identity = def_db.map(|db| db as *const DefDatabase as usize);
         if self.last_def_db_identity != def_db_identity {
-            carry_sprite_cache.clear();
-            walk_spring_by_entity.clear();
+            self.carry_sprite_cache.clear();
+            self.walk_spring_by_entity.clear();
             self.last_def_db_identity = def_db_identity;
         }
         let active_floor = world.active_floor();
-        let clear_color = clear_color_for_floor(active_floor);
-        for chunk in frame.chunks_exact_mut(4) {
-            chunk.copy_from_slice(&clear_color);
-        }
-        let view_bounds = view_bounds_world(
-            world.camera(),
-            (self.viewport.width, self.viewport.height),
-            VIEW_CULL_PADDING_PX,
-        );
+        let view_bounds = view_bounds_world(world.camera(), (width, height), VIEW_CULL_PADDING_PX);
         collect_sorted_visible_entity_draw_indices(
             world,
             active_floor,
             &view_bounds,
-            visible_entity_draw_indices,
+            &mut self.visible_entity_draw_indices,
         );
 
-        draw_tilemap(
+        let background_key = BackgroundKey::for_world(world, (width, height));
+        let redraw_background = self.background_cache.key.as_ref() != Some(&background_key)
+            || self.background_cache.rgba.len() != frame.len();
+        if redraw_background {
+            load_visible_tile_sprites(
+                world,
+                &view_bounds,
+                &mut self.sprite_cache,
+                &mut self.warned_missing_sprite_keys,
+                &self.asset_root,
+            );
+            self.background_cache.rgba.resize(frame.len(), 0);
+            self.background_cache.key = Some(background_key);
+        }
+        self.load_entity_sprites(world, sim_tick_counter);
+
+        let sprite_cache = &self.sprite_cache;
+        let default_action_visual = EntityActionVisual::default();
+        let entity_draws: Vec<EntityDraw> = self
+            .visible_entity_draw_indices
+            .iter()
+            .zip(self.entity_offsets_px.iter().copied())
+            .map(|(&entity_index, offset_px)| {
+                let entity = &world.entities()[entity_index];
+                let action_visual = world
+                    .entity_action_visual_ref(entity.id)
+                    .unwrap_or(&default_action_visual);
+                let sprite = match &entity.renderable.kind {
+                    RenderableKind::Sprite {
+                        key, mirror_west, ..
+                    } => cached_sprite_for_action_visual(
+                        sprite_cache,
+                        key,
+                        action_visual,
+                        *mirror_west,
+                    ),
+                    RenderableKind::Placeholder => None,
+                };
+                let carry = held_attachment_anchor_name(entity, action_visual.action_state)
+                    .and_then(|anchor_name| {
+                        let carry_sprite = self
+                            .carry_sprite_cache
+                            .get(action_visual.held_visual.as_deref()?)?
+                            .as_ref()?;
+                        Some(CarryDraw {
+                            center_px: entity_carry_anchor_screen_position_px(
+                                world.camera(),
+                                (width, height),
+                                entity,
+                                action_visual.action_params.facing,
+                                offset_px,
+                                anchor_name,
+                            ),
+                            sprite: cached_sprite(sprite_cache, &carry_sprite.sprite_key)?,
+                            pixel_scale: carry_sprite.pixel_scale,
+                        })
+                    });
+                EntityDraw {
+                    entity,
+                    action_visual,
+                    offset_px,
+                    modifier: world.entity_render_modifier(entity.id),
+                    sprite,
+                    carry,
+                }
+            })
+            .collect();
+
+        let band_count = band_count_for(height, self.raster_threads);
+        let clear_color = clear_color_for_floor(active_floor);
+        let bands: Vec<_> = FrameBand::split(frame, width, height, band_count)
+            .into_iter()
+            .zip(FrameBand::split(
+                &mut self.background_cache.rgba,
+                width,
+                height,
+                band_count,
+            ))
+            .collect();
+        self.band_pool
+            .for_each_band(bands, |(mut band, mut background)| {
+                if redraw_background {
+                    for pixel in background.bytes_mut().chunks_exact_mut(4) {
+                        pixel.copy_from_slice(&clear_color);
+                    }
+                    draw_tilemap(&mut background, world, &view_bounds, sprite_cache);
+                    draw_world_grid(&mut background, world);
+                }
+                band.bytes_mut().copy_from_slice(background.bytes());
+                for entity_draw in &entity_draws {
+                    draw_entity(&mut band, world.camera(), entity_draw, sim_tick_counter);
+                }
+            });
+        drop(world_zone);
+
+        let particles_zone = profile_zone("render.particles");
+        draw_particles(
             frame,
-            self.viewport.width,
-            self.viewport.height,
+            width,
+            height,
             world,
             &view_bounds,
-            sprite_cache,
-            warned_missing_sprite_keys,
-            asset_root,
+            &mut self.sprite_cache,
+            &mut self.warned_missing_sprite_keys,
+            &self.asset_root,
         );
-        draw_world_grid(frame, self.viewport.width, self.viewport.height, world);
-        let default_action_visual = EntityActionVisual::default();
+        drop(particles_zone);
+        let lighting_zone = profile_zone("render.lighting");
+        let lit = update_lightmap(world, &view_bounds, &mut self.lightmap_cache);
+        let lightmap_cache = &self.lightmap_cache;
+        self.band_pool.for_each_band(
+            FrameBand::split(frame, width, height, band_count),
+            |mut band| {
+                if lit {
+                    apply_lighting(&mut band, world.camera(), lightmap_cache);
+                }
+                apply_fog(&mut band, world, &view_bounds);
+            },
+        );
+        drop(lighting_zone);
+        let _overlays_zone = profile_zone("render.overlays");
+        draw_affordances(frame, width, height, world, &view_bounds);
+        draw_world_widgets(frame, width, height, world, &view_bounds, ui_font);
+    }
 
-        for entity_index in visible_entity_draw_indices.iter().copied() {
+    /// Advances walk springs into `entity_offsets_px` and loads every sprite the visible
+    /// entities draw, in draw order.
+    fn load_entity_sprites(&mut self, world: &SceneWorld, sim_tick_counter: u64) {
+        let def_db = world.def_database();
+        let default_action_visual = EntityActionVisual::default();
+        self.entity_offsets_px.clear();
+        for entity_index in self.visible_entity_draw_indices.iter().copied() {
             let entity = &world.entities()[entity_index];
             let action_visual = world
                 .entity_action_visual_ref(entity.id)
                 .unwrap_or(&default_action_visual);
             let procedural_offset = compute_procedural_offset(
-                walk_spring_by_entity,
+                &mut self.walk_spring_by_entity,
                 entity.id,
                 action_visual,
                 sim_tick_counter,
             );
-            if action_visual.action_state == ActionState::UseTool {
-                draw_use_tool_flicker_halo(
-                    frame,
-                    self.viewport.width,
-                    self.viewport.height,
-                    world.camera(),
-                    entity.transform.position,
-                    procedural_offset.offset_px,
-                    entity.id,
-                    sim_tick_counter,
+            self.entity_offsets_px.push(procedural_offset.offset_px);
+            if let RenderableKind::Sprite {
+                key, mirror_west, ..
+            } = &entity.renderable.kind
+            {
+                load_sprite_for_action_visual(
+                    &mut self.sprite_cache,
+                    &mut self.warned_missing_sprite_keys,
+                    &self.asset_root,
+                    key,
+                    action_visual,
+                    *mirror_west,
                 );
             }
-            draw_renderable_at_world_position(
-                frame,
-                self.viewport.width,
-                self.viewport.height,
-                world.camera(),
-                entity.transform.position,
-                procedural_offset.offset_px,
-                action_visual,
-                &entity.renderable.kind,
-                sprite_cache,
-                warned_missing_sprite_keys,
-                asset_root,
-                true,
-            );
-
-            let Some(anchor_name) = held_attachment_anchor_name(entity, action_visual.action_state)
-            else {
+            if held_attachment_anchor_name(entity, action_visual.action_state).is_none() {
                 continue;
-            };
+            }
             let Some(held_visual_def_name) = action_visual.held_visual.as_deref() else {
                 continue;
             };
-            let Some(carry_sprite) =
-                resolve_cached_carry_sprite(carry_sprite_cache, def_db, held_visual_def_name)
-            else {
-                continue;
-            };
-            let (carry_x, carry_y) = entity_carry_anchor_screen_position_px(
-                world.camera(),
-                (self.viewport.width, self.viewport.height),
-                entity,
-                action_visual.action_params.facing,
-                procedural_offset.offset_px,
-                anchor_name,
-            );
-            draw_cached_carry_sprite_at_screen_position(
-                frame,
-                self.viewport.width,
-                self.viewport.height,
-                world.camera(),
-                carry_x,
-                carry_y,
-                carry_sprite,
-                sprite_cache,
-                warned_missing_sprite_keys,
-                asset_root,
-            );
+            if let Some(carry_sprite) = resolve_cached_carry_sprite(
+                &mut self.carry_sprite_cache,
+                def_db,
+                held_visual_def_name,
+            ) {
+                resolve_cached_sprite(
+                    &mut self.sprite_cache,
+                    &mut self.warned_missing_sprite_keys,
+                    &self.asset_root,
+                    &carry_sprite.sprite_key,
+                );
+            }
         }
+    }
+}
 
-        draw_affordances(
-            frame,
-            self.viewport.width,
-            self.viewport.height,
-            world,
-            &view_bounds,
+fn draw_entity(
+    target: &mut FrameBand,
+    camera: &Camera2D,
+    entity_draw: &EntityDraw,
+    sim_tick_counter: u64,
+) {
+    let entity = entity_draw.entity;
+    if entity_draw.action_visual.action_state == ActionState::UseTool {
+        draw_use_tool_flicker_halo(
+            target,
+            camera,
+            entity.transform.position,
+            entity_draw.offset_px,
+            entity.id,
+            sim_tick_counter,
+        );
+    }
+    draw_renderable_at_world_position(
+        target,
+        camera,
+        &entity.transform,
+        entity_draw.offset_px,
+        &entity.renderable.kind,
+        entity_draw.sprite,
+        entity_draw.modifier,
+        true,
+    );
+    if let Some(carry) = &entity_draw.carry {
+        draw_sprite_centered_scaled(
+            target,
+            carry.center_px.0,
+            carry.center_px.1,
+            carry.sprite,
+            carry.pixel_scale as f32 * camera.effective_zoom(),
         );
-
-        if let Some(data) = overlay_data {
-            draw_overlay(frame, self.viewport.width, self.viewport.height, data);
-        }
-        if let Some(palette) = command_palette {
-            draw_command_palette(frame, self.viewport.width, self.viewport.height, palette);
-        }
-        if let Some(console) = console_state {
-            draw_console(frame, self.viewport.width, self.viewport.height, console);
-        }
-
-        self.pixels.render()
     }
 }
 
+/// `sprite` is the resolved art and horizontal flip for a `Sprite` renderable.
+#[allow(clippy::too_many_arguments)]
 fn draw_renderable_at_world_position(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
+    target: &mut FrameBand,
     camera: &Camera2D,
-    world_position: Vec2,
+    transform: &Transform,
     visual_offset_px: Vec2,
-    action_visual: &EntityActionVisual,
     renderable: &RenderableKind,
-    sprite_cache: &mut HashMap<String, Option<LoadedSprite>>,
-    warned_missing_sprite_keys: &mut HashSet<String>,
-    asset_root: &Path,
+    sprite: Option<(&LoadedSprite, bool)>,
+    modifier: Option<&RenderModifier>,
     draw_placeholder_on_missing_sprite: bool,
 ) {
+    let placeholder_color = modifier.map_or(PLACEHOLDER_COLOR, |modifier| {
+        modifier.apply(PLACEHOLDER_COLOR)
+    });
     let (cx, cy) = world_to_snapped_screen_px_with_offset(
         camera,
-        (width, height),
-        world_position,
+        (target.width(), target.height()),
+        transform.position,
         visual_offset_px,
     );
     match renderable {
         RenderableKind::Placeholder => {
-            draw_square(
-                frame,
-                width,
-                height,
-                cx,
-                cy,
-                PLACEHOLDER_HALF_SIZE_PX,
-                PLACEHOLDER_COLOR,
-            );
+            draw_square_source_over(target, cx, cy, PLACEHOLDER_HALF_SIZE_PX, placeholder_color);
         }
-        RenderableKind::Sprite {
-            key, pixel_scale, ..
-        } => {
-            if let Some(sprite) = resolve_sprite_for_action_visual(
-                sprite_cache,
-                warned_missing_sprite_keys,
-                asset_root,
-                key,
-                action_visual,
-            ) {
-                draw_sprite_centered_scaled(
-                    frame,
-                    width,
-                    height,
+        RenderableKind::Sprite { pixel_scale, .. } => {
+            if let Some((sprite, flip_x)) = sprite {
+                let scale = *pixel_scale as f32 * camera.effective_zoom();
+                draw_sprite_centered_transformed(
+                    target,
                     cx,
                     cy,
                     sprite,
-                    *pixel_scale as f32 * camera.effective_zoom(),
+                    SpriteBlit {
+                        rotation_radians: transform.rotation_radians.unwrap_or(0.0),
+                        flip_x,
+                        modifier,
+                        ..SpriteBlit::uniform(scale)
+                    },
                 );
             } else if draw_placeholder_on_missing_sprite {
-                draw_square(
-                    frame,
-                    width,
-                    height,
+                draw_square_source_over(
+                    target,
                     cx,
                     cy,
                     PLACEHOLDER_HALF_SIZE_PX,
-                    PLACEHOLDER_COLOR,
+                    placeholder_color,
                 );
             }
         }
@@ -414,67 +675,84 @@ fn held_attachment_anchor_name(
     }
 }
 
-fn resolve_sprite_for_action_visual<'a>(
-    sprite_cache: &'a mut HashMap<String, Option<LoadedSprite>>,
+/// Loads the sprite drawn for an action visual: for `visual_test/` keys the state-and-facing
+/// variant, then the state variant (neither warns when missing), then the base key.
+fn load_sprite_for_action_visual(
+    sprite_cache: &mut HashMap<String, Option<LoadedSprite>>,
     warned_missing_sprite_keys: &mut HashSet<String>,
     asset_root: &Path,
     base_key: &str,
     action_visual: &EntityActionVisual,
-) -> Option<&'a LoadedSprite> {
-    let Some((state_and_facing_key, state_key)) =
-        visual_test_variant_candidate_keys(base_key, action_visual)
-    else {
-        return resolve_cached_sprite(
-            sprite_cache,
-            warned_missing_sprite_keys,
-            asset_root,
-            base_key,
-        );
-    };
-
-    if let Some(sprite_ptr) = resolve_cached_sprite_with_missing_policy(
-        sprite_cache,
-        warned_missing_sprite_keys,
-        asset_root,
-        &state_and_facing_key,
-        false,
-    )
-    .map(|sprite| sprite as *const LoadedSprite)
-    {
-        // SAFETY: pointer originates from `sprite_cache` and remains valid for the cache borrow.
-        return unsafe { sprite_ptr.as_ref() };
-    }
-
-    if let Some(sprite_ptr) = resolve_cached_sprite_with_missing_policy(
-        sprite_cache,
-        warned_missing_sprite_keys,
-        asset_root,
-        &state_key,
-        false,
-    )
-    .map(|sprite| sprite as *const LoadedSprite)
+    mirror_west: bool,
+) {
+    let (facing, _) = sprite_facing_for_mirror(action_visual.action_params.facing, mirror_west);
+    if let Some((state_and_facing_key, state_key)) =
+        visual_test_variant_candidate_keys(base_key, action_visual.action_state, facing)
     {
-        // SAFETY: pointer originates from `sprite_cache` and remains valid for the cache borrow.
-        return unsafe { sprite_ptr.as_ref() };
+        for key in [state_and_facing_key, state_key] {
+            if resolve_cached_sprite_with_missing_policy(
+                sprite_cache,
+                warned_missing_sprite_keys,
+                asset_root,
+                &key,
+                false,
+            )
+            .is_some()
+            {
+                return;
+            }
+        }
     }
-
     resolve_cached_sprite(
         sprite_cache,
         warned_missing_sprite_keys,
         asset_root,
         base_key,
-    )
+    );
 }
 
-fn visual_test_variant_candidate_keys(
+/// Sprite and horizontal flip for an action visual, from sprites already loaded by
+/// `load_sprite_for_action_visual`.
+fn cached_sprite_for_action_visual<'a>(
+    sprite_cache: &'a HashMap<String, Option<LoadedSprite>>,
     base_key: &str,
     action_visual: &EntityActionVisual,
+    mirror_west: bool,
+) -> Option<(&'a LoadedSprite, bool)> {
+    let (facing, flip_x) =
+        sprite_facing_for_mirror(action_visual.action_params.facing, mirror_west);
+    visual_test_variant_candidate_keys(base_key, action_visual.action_state, facing)
+        .and_then(|(state_and_facing_key, state_key)| {
+            cached_sprite(sprite_cache, &state_and_facing_key)
+                .or_else(|| cached_sprite(sprite_cache, &state_key))
+        })
+        .or_else(|| cached_sprite(sprite_cache, base_key))
+        .map(|sprite| (sprite, flip_x))
+}
+
+/// Facing whose art variant is drawn, and whether it is flipped horizontally. Sprites that
+/// mirror west treat their art as east-facing and draw it flipped.
+fn sprite_facing_for_mirror(
+    facing: Option<CardinalFacing>,
+    mirror_west: bool,
+) -> (Option<CardinalFacing>, bool) {
+    if mirror_west && facing == Some(CardinalFacing::West) {
+        (Some(CardinalFacing::East), true)
+    } else {
+        (facing, false)
+    }
+}
+
+fn visual_test_variant_candidate_keys(
+    base_key: &str,
+    action_state: ActionState,
+    facing: Option<CardinalFacing>,
 ) -> Option<(String, String)> {
     if !base_key.starts_with("visual_test/") {
         return None;
     }
-    let state_token = action_state_variant_token(action_visual.action_state)?;
-    let facing_token = facing_variant_token(action_visual.action_params.facing)?;
+    let state_token = action_state_variant_token(action_state)?;
+    let facing_token = facing_variant_token(facing)?;
     Some((
         format!("{base_key}__{state_token}_{facing_token}"),
         format!("{base_key}__{state_token}"),
@@ -517,6 +795,7 @@ fn resolve_cached_carry_sprite<'a>(
                     key,
                     pixel_scale,
                     anchors,
+                    ..
                 } => Some(CachedCarrySprite {
                     sprite_key: key.clone(),
                     pixel_scale: *pixel_scale,
@@ -529,37 +808,6 @@ fn resolve_cached_carry_sprite<'a>(
     cache.get(held_visual_def_name).and_then(Option::as_ref)
 }
 
-fn draw_cached_carry_sprite_at_screen_position(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
-    camera: &Camera2D,
-    cx: i32,
-    cy: i32,
-    cached_carry_sprite: &CachedCarrySprite,
-    sprite_cache: &mut HashMap<String, Option<LoadedSprite>>,
-    warned_missing_sprite_keys: &mut HashSet<String>,
-    asset_root: &Path,
-) {
-    let Some(sprite) = resolve_cached_sprite(
-        sprite_cache,
-        warned_missing_sprite_keys,
-        asset_root,
-        &cached_carry_sprite.sprite_key,
-    ) else {
-        return;
-    };
-    draw_sprite_centered_scaled(
-        frame,
-        width,
-        height,
-        cx,
-        cy,
-        sprite,
-        cached_carry_sprite.pixel_scale as f32 * camera.effective_zoom(),
-    );
-}
-
 fn entity_carry_anchor_screen_position_px(
     camera: &Camera2D,
     window_size: (u32, u32),
@@ -746,9 +994,10 @@ fn anchor_screen_delta_px(anchor: SpriteAnchorPx, scale: f32) -> (i32, i32) {
 
 fn clear_color_for_floor(floor: FloorId) -> [u8; 4] {
     match floor {
-        FloorId::Rooftop => CLEAR_COLOR_ROOFTOP,
-        FloorId::Main => CLEAR_COLOR_MAIN,
-        FloorId::Basement => CLEAR_COLOR_BASEMENT,
+        FloorId::ROOFTOP => CLEAR_COLOR_ROOFTOP,
+        FloorId::MAIN => CLEAR_COLOR_MAIN,
+        FloorId::BASEMENT => CLEAR_COLOR_BASEMENT,
+        _ => CLEAR_COLOR_MAIN,
     }
 }
 
@@ -773,7 +1022,9 @@ fn collect_sorted_visible_entity_draw_indices(
 ) {
     out.clear();
     for (index, entity) in world.entities().iter().enumerate() {
-        if entity_visible_on_active_floor(entity, active_floor, view_bounds) {
+        if entity_visible_on_active_floor(entity, active_floor, view_bounds)
+            && !world.entity_hidden_by_fog(entity)
+        {
             out.push(index);
         }
     }
@@ -829,6 +1080,7 @@ fn draw_affordances(
     world: &SceneWorld,
     view_bounds: &WorldBounds,
 ) {
+    let mut target = FrameBand::full(frame, width, height);
     let visuals = world.visual_state();
     let active_floor = world.active_floor();
 
@@ -846,9 +1098,7 @@ fn draw_affordances(
                         entity.transform.position,
                     );
                     draw_square_outline(
-                        frame,
-                        width,
-                        height,
+                        &mut target,
                         cx,
                         cy,
                         SELECTED_HIGHLIGHT_HALF_SIZE_PX,
@@ -866,9 +1116,7 @@ fn draw_affordances(
                         entity.renderer_overlap_order_key(),
                     ) {
                         draw_square_outline(
-                            frame,
-                            width,
-                            height,
+                            &mut target,
                             cx,
                             cy,
                             SELECTED_HIGHLIGHT_HALF_SIZE_PX,
@@ -894,9 +1142,7 @@ fn draw_affordances(
                         entity.transform.position,
                     );
                     draw_square_outline(
-                        frame,
-                        width,
-                        height,
+                        &mut target,
                         cx,
                         cy,
                         HOVER_HIGHLIGHT_HALF_SIZE_PX,
@@ -931,9 +1177,7 @@ fn draw_affordances(
                         entity.renderer_overlap_order_key(),
                     ) {
                         draw_square_outline(
-                            frame,
-                            width,
-                            height,
+                            &mut target,
                             cx,
                             cy,
                             HOVER_HIGHLIGHT_HALF_SIZE_PX,
@@ -957,9 +1201,7 @@ fn draw_affordances(
             let (cx, cy) =
                 snapped_world_to_screen_px(world.camera(), (width, height), marker.position_world);
             draw_cross(
-                frame,
-                width,
-                height,
+                &mut target,
                 cx,
                 cy,
                 ORDER_MARKER_HALF_SIZE_PX,
@@ -969,6 +1211,394 @@ fn draw_affordances(
     }
 }
 
+/// Particles draw in spawn order above entities and below affordances; sprite particles are
+/// tinted by their ramp color, the rest are zoom-scaled squares.
+#[allow(clippy::too_many_arguments)]
+fn draw_particles(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    world: &SceneWorld,
+    view_bounds: &WorldBounds,
+    sprite_cache: &mut HashMap<String, Option<LoadedSprite>>,
+    warned_missing_sprite_keys: &mut HashSet<String>,
+    asset_root: &Path,
+) {
+    let mut target = FrameBand::full(frame, width, height);
+    let active_floor = world.active_floor();
+    let zoom = world.camera().effective_zoom();
+    for particle in world.particles() {
+        if particle.floor != active_floor
+            || !bounds_intersects_point_radius(
+                view_bounds,
+                particle.position,
+                ENTITY_CULL_RADIUS_WORLD_TILES,
+            )
+            || world.viewer_visibility_at(active_floor, particle.position)
+                != TileVisibility::Visible
+        {
+            continue;
+        }
+        let color = particle.color();
+        if color[3] == 0 {
+            continue;
+        }
+        let (cx, cy) =
+            snapped_world_to_screen_px(world.camera(), (width, height), particle.position);
+        if let Some(key) = particle.emitter.sprite_key.as_deref() {
+            if let Some(sprite) =
+                resolve_cached_sprite(sprite_cache, warned_missing_sprite_keys, asset_root, key)
+            {
+                let tint = RenderModifier {
+                    tint_rgba: color,
+                    ..RenderModifier::default()
+                };
+                draw_sprite_centered_transformed(
+                    &mut target,
+                    cx,
+                    cy,
+                    sprite,
+                    SpriteBlit {
+                        modifier: Some(&tint),
+                        ..SpriteBlit::uniform(zoom)
+                    },
+                );
+                continue;
+            }
+        }
+        let size = ((particle.emitter.size_px as f32 * zoom).round() as i32).max(1);
+        fill_rect_source_over(&mut target, cx - size / 2, cy - size / 2, size, size, color);
+    }
+}
+
+/// Brings the lightmap up to date for the active floor and view; returns `false` when the floor
+/// is left at `AMBIENT_UNLIT` and the lighting pass is skipped, since no light can raise a gain
+/// above 1.
+fn update_lightmap(
+    world: &SceneWorld,
+    view_bounds: &WorldBounds,
+    cache: &mut LightmapCache,
+) -> bool {
+    let floor = world.active_floor();
+    let ambient_rgb = world.floor_ambient(floor);
+    if ambient_rgb == AMBIENT_UNLIT {
+        return false;
+    }
+    let cell_x_min = (view_bounds.min_x * LIGHT_CELLS_PER_WORLD).floor() as i32;
+    let cell_y_min = (view_bounds.min_y * LIGHT_CELLS_PER_WORLD).floor() as i32;
+    let cell_x_max = (view_bounds.max_x * LIGHT_CELLS_PER_WORLD).ceil() as i32;
+    let cell_y_max = (view_bounds.max_y * LIGHT_CELLS_PER_WORLD).ceil() as i32;
+    let key = LightmapKey {
+        floor,
+        ambient_rgb,
+        cell_x_min,
+        cell_y_min,
+        columns: (cell_x_max - cell_x_min).max(0) as usize,
+        rows: (cell_y_max - cell_y_min).max(0) as usize,
+        lights: world
+            .resolved_lights_on_floor(floor)
+            .into_iter()
+            .filter(|light| {
+                bounds_intersects_point_radius(view_bounds, light.position, light.radius)
+            })
+            .collect(),
+    };
+    if cache.key.as_ref() != Some(&key) {
+        rebuild_lightmap(cache, key);
+    }
+    true
+}
+
+/// Multiplies the world layers of the band by the lightmap from `update_lightmap`, clamped to 1.
+/// Affordances, widgets and UI draw afterwards and stay unlit.
+fn apply_lighting(target: &mut FrameBand, camera: &Camera2D, cache: &LightmapCache) {
+    let Some(key) = cache.key.as_ref() else {
+        return;
+    };
+    let window_size = (target.width(), target.height());
+    let cell_world = 1.0 / LIGHT_CELLS_PER_WORLD;
+    for row in 0..key.rows {
+        let y_min = (key.cell_y_min + row as i32) as f32 * cell_world;
+        for column in 0..key.columns {
+            let gain = cache.gains[row * key.columns + column];
+            if gain == [LIGHT_UNIT_GAIN; 3] {
+                continue;
+            }
+            let x_min = (key.cell_x_min + column as i32) as f32 * cell_world;
+            let (left, top) = world_to_screen_px(
+                camera,
+                window_size,
+                Vec2 {
+                    x: x_min,
+                    y: y_min + cell_world,
+                },
+            );
+            let (right, bottom) = world_to_screen_px(
+                camera,
+                window_size,
+                Vec2 {
+                    x: x_min + cell_world,
+                    y: y_min,
+                },
+            );
+            multiply_rect(target, left, top, right, bottom, gain);
+        }
+    }
+}
+
+fn rebuild_lightmap(cache: &mut LightmapCache, key: LightmapKey) {
+    let ambient = key.ambient_rgb.map(|channel| f32::from(channel) / 255.0);
+    let cell_world = 1.0 / LIGHT_CELLS_PER_WORLD;
+    cache.gains.clear();
+    cache.gains.reserve(key.columns * key.rows);
+    for row in 0..key.rows {
+        for column in 0..key.columns {
+            let center = Vec2 {
+                x: (key.cell_x_min + column as i32) as f32 * cell_world + cell_world * 0.5,
+                y: (key.cell_y_min + row as i32) as f32 * cell_world + cell_world * 0.5,
+            };
+            let mut light = ambient;
+            for resolved in &key.lights {
+                let added = resolved.contribution_at(center);
+                for (channel, value) in light.iter_mut().zip(added) {
+                    *channel += value;
+                }
+            }
+            cache.gains.push(light.map(|channel| {
+                (channel.clamp(0.0, 1.0) * f32::from(LIGHT_UNIT_GAIN)).round() as u16
+            }));
+        }
+    }
+    cache.key = Some(key);
+}
+
+/// Covers unexplored tiles of the active floor's tilemap and dims explored ones for the fog
+/// viewer. Runs after lighting so explored areas stay darker than lit ones.
+fn apply_fog(target: &mut FrameBand, world: &SceneWorld, view_bounds: &WorldBounds) {
+    let Some(viewer) = world.fog_viewer() else {
+        return;
+    };
+    let floor = world.active_floor();
+    let Some(tilemap) = world.floor_tilemap(floor) else {
+        return;
+    };
+    let Some(visible_rect) = visible_tile_rect(tilemap, view_bounds) else {
+        return;
+    };
+    let origin = tilemap.origin();
+    let camera = world.camera();
+    let window_size = (target.width(), target.height());
+    for y in visible_rect.y_min..=visible_rect.y_max {
+        for x in visible_rect.x_min..=visible_rect.x_max {
+            let visibility = world.tile_visibility(viewer, floor, x, y);
+            if visibility == TileVisibility::Visible {
+                continue;
+            }
+            let (left, top) = world_to_screen_px(
+                camera,
+                window_size,
+                Vec2 {
+                    x: origin.x + x as f32,
+                    y: origin.y + y as f32 + 1.0,
+                },
+            );
+            let (right, bottom) = world_to_screen_px(
+                camera,
+                window_size,
+                Vec2 {
+                    x: origin.x + x as f32 + 1.0,
+                    y: origin.y + y as f32,
+                },
+            );
+            if visibility == TileVisibility::Unexplored {
+                fill_rect_source_over(
+                    target,
+                    left,
+                    top,
+                    right - left,
+                    bottom - top,
+                    FOG_UNEXPLORED_COLOR,
+                );
+            } else {
+                multiply_rect(target, left, top, right, bottom, FOG_EXPLORED_GAIN);
+            }
+        }
+    }
+}
+
+/// Scales RGB of the half-open screen rect `[left, right) x [top, bottom)` by `gain`.
+fn multiply_rect(
+    target: &mut FrameBand,
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    gain: [u16; 3],
+) {
+    let x_start = left.max(0) as usize;
+    let x_end = right.min(target.width() as i32).max(0) as usize;
+    let y_start = top.max(target.top());
+    let y_end = bottom.min(target.bottom());
+    if x_start >= x_end {
+        return;
+    }
+    for y in y_start..y_end {
+        let row_start = target.row_offset(y) + x_start * 4;
+        let row_end = target.row_offset(y) + x_end * 4;
+        for pixel in target.bytes_mut()[row_start..row_end].chunks_exact_mut(4) {
+            for (channel, gain) in pixel.iter_mut().zip(gain) {
+                *channel =
+                    (u32::from(*channel) * u32::from(gain) / u32::from(LIGHT_UNIT_GAIN)) as u8;
+            }
+        }
+    }
+}
+
+fn draw_world_widgets(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    world: &SceneWorld,
+    view_bounds: &WorldBounds,
+    font: &BitmapFont,
+) {
+    let mut target = FrameBand::full(frame, width, height);
+    let zoom = world.camera().effective_zoom();
+    let scale = zoom.clamp(WORLD_WIDGET_SCALE_MIN, WORLD_WIDGET_SCALE_MAX);
+    for widget in world.world_widgets() {
+        let Some(position) = world.world_widget_anchor_position(widget.anchor) else {
+            continue;
+        };
+        if !bounds_intersects_point_radius(
+            view_bounds,
+            position,
+            WORLD_WIDGET_CULL_RADIUS_WORLD_TILES,
+        ) || world.viewer_visibility_at(world.active_floor(), position)
+            != TileVisibility::Visible
+        {
+            continue;
+        }
+        let (anchor_x, anchor_y) =
+            snapped_world_to_screen_px(world.camera(), (width, height), position);
+        let cx = anchor_x + (widget.offset_px.x * scale).round() as i32;
+        let cy = anchor_y + (widget.offset_px.y * scale).round() as i32;
+        match &widget.kind {
+            WorldWidgetKind::Bar {
+                fraction,
+                width_px,
+                height_px,
+                fill_rgba,
+                back_rgba,
+            } => {
+                let bar_w = ((*width_px as f32 * scale).round() as i32).max(2);
+                let bar_h = ((*height_px as f32 * scale).round() as i32).max(1);
+                let left = cx - bar_w / 2;
+                let top = cy - bar_h / 2;
+                let fill_w = (bar_w as f32 * fraction).round() as i32;
+                fill_rect_source_over(&mut target, left, top, bar_w, bar_h, *back_rgba);
+                fill_rect_source_over(&mut target, left, top, fill_w, bar_h, *fill_rgba);
+            }
+            WorldWidgetKind::ProgressArc {
+                fraction,
+                radius_px,
+                thickness_px,
+                rgba,
+            } => {
+                let radius = ((*radius_px as f32 * scale).round() as i32).max(2);
+                let thickness = ((*thickness_px as f32 * scale).round() as i32).clamp(1, radius);
+                draw_progress_arc(&mut target, cx, cy, radius, thickness, *fraction, *rgba);
+            }
+            WorldWidgetKind::Label { text, rgba } => {
+                if zoom < WORLD_WIDGET_LABEL_MIN_ZOOM {
+                    continue;
+                }
+                let text_scale =
+                    font.scale_for_line_height(world_widget_label_line_height_px(zoom));
+                let wrap_width = WORLD_WIDGET_LABEL_WRAP_WIDTH_PX * text_scale;
+                let style = TextStyle::new(*rgba, text_scale)
+                    .with_align(TextAlign::Center)
+                    .with_max_width(wrap_width);
+                let left = cx - wrap_width / 2;
+                let top = cy - font.measure(text, &style).1 / 2;
+                let shadow = TextStyle {
+                    color: WORLD_WIDGET_LABEL_SHADOW_COLOR,
+                    ..style
+                };
+                let frame = target.bytes_mut();
+                draw_text(frame, width, height, font, left + 1, top + 1, text, &shadow);
+                draw_text(frame, width, height, font, left, top, text, &style);
+            }
+        }
+    }
+}
+
+fn world_widget_label_line_height_px(zoom: f32) -> i32 {
+    if zoom >= 1.5 {
+        WORLD_WIDGET_LABEL_LINE_HEIGHT_PX * 2
+    } else {
+        WORLD_WIDGET_LABEL_LINE_HEIGHT_PX
+    }
+}
+
+fn fill_rect_source_over(
+    target: &mut FrameBand,
+    left: i32,
+    top: i32,
+    rect_w: i32,
+    rect_h: i32,
+    color: [u8; 4],
+) {
+    let x_start = left.max(0);
+    let y_start = top.max(target.top());
+    let x_end = left.saturating_add(rect_w).min(target.width() as i32);
+    let y_end = top.saturating_add(rect_h).min(target.bottom());
+    for y in y_start..y_end {
+        for x in x_start..x_end {
+            blend_source_over_pixel_clipped(target, x, y, color);
+        }
+    }
+}
+
+/// Ring of `thickness` pixels inside `radius`; the first `fraction` of it, clockwise from
+/// twelve o'clock, uses `color` and the rest a faded track of the same color.
+#[allow(clippy::too_many_arguments)]
+fn draw_progress_arc(
+    target: &mut FrameBand,
+    cx: i32,
+    cy: i32,
+    radius: i32,
+    thickness: i32,
+    fraction: f32,
+    color: [u8; 4],
+) {
+    let outer_sq = (radius * radius) as f32;
+    let inner = (radius - thickness) as f32;
+    let inner_sq = inner * inner;
+    let filled_radians = fraction.clamp(0.0, 1.0) * TAU;
+    let track = [
+        color[0],
+        color[1],
+        color[2],
+        color[3] / WORLD_WIDGET_ARC_TRACK_ALPHA_DIVISOR,
+    ];
+    for y in (cy - radius)..=(cy + radius) {
+        for x in (cx - radius)..=(cx + radius) {
+            let dx = (x - cx) as f32;
+            let dy = (y - cy) as f32;
+            let dist_sq = dx * dx + dy * dy;
+            if dist_sq > outer_sq || dist_sq <= inner_sq {
+                continue;
+            }
+            let mut angle = dx.atan2(-dy);
+            if angle < 0.0 {
+                angle += TAU;
+            }
+            let pixel = if angle < filled_radians { color } else { track };
+            blend_source_over_pixel_clipped(target, x, y, pixel);
+        }
+    }
+}
+
 fn screen_rect_from_center(cx: i32, cy: i32, half_size: i32) -> ScreenRectPx {
     ScreenRectPx {
         left: cx - half_size,
@@ -991,11 +1621,11 @@ fn screen_rects_overlap(a: ScreenRectPx, b: ScreenRectPx) -> bool {
     !(a.right < b.left || b.right < a.left || a.bottom < b.top || b.bottom < a.top)
 }
 
-fn is_occluded_by_front_overlap(
+fn is_occluded_by_front_overlap<K: Ord>(
     subject_rect: ScreenRectPx,
-    subject_order_key: u64,
+    subject_order_key: K,
     occluder_rect: ScreenRectPx,
-    occluder_order_key: u64,
+    occluder_order_key: K,
 ) -> bool {
     occluder_order_key > subject_order_key && screen_rects_overlap(subject_rect, occluder_rect)
 }
@@ -1006,7 +1636,7 @@ fn is_entity_occluded_by_front_overlap(
     active_floor: FloorId,
     subject_id: crate::app::EntityId,
     subject_rect: ScreenRectPx,
-    subject_order_key: u64,
+    subject_order_key: RenderOrderKey,
 ) -> bool {
     world.entities().iter().any(|candidate| {
         if candidate.id == subject_id || candidate.floor != active_floor {
@@ -1022,51 +1652,62 @@ fn is_entity_occluded_by_front_overlap(
     })
 }
 
-fn draw_tilemap(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
+/// Tiles of the active floor inside `view_bounds` in draw order (layer, then row, then column),
+/// with their world centers. Empty tiles are skipped above the ground layer.
+fn visible_tiles<'a>(
+    world: &'a SceneWorld,
+    view_bounds: &WorldBounds,
+) -> impl Iterator<Item = (u16, Vec2)> + 'a {
+    let tilemap = world.tilemap();
+    let visible_rect = tilemap.and_then(|tilemap| visible_tile_rect(tilemap, view_bounds));
+    tilemap
+        .zip(visible_rect)
+        .into_iter()
+        .flat_map(|(tilemap, rect)| {
+            (0..tilemap.layer_count()).flat_map(move |layer| {
+                (rect.y_min..=rect.y_max).flat_map(move |y| {
+                    (rect.x_min..=rect.x_max).filter_map(move |x| {
+                        let tile_id = tilemap.layer_tile_at(layer, x, y)?;
+                        if layer > 0 && tile_id == TILE_EMPTY {
+                            return None;
+                        }
+                        Some((tile_id, tilemap.tile_center_world(x, y)?))
+                    })
+                })
+            })
+        })
+}
+
+/// Loads the sprites of the tiles `draw_tilemap` will draw, so bands can share the cache.
+fn load_visible_tile_sprites(
     world: &SceneWorld,
     view_bounds: &WorldBounds,
     sprite_cache: &mut HashMap<String, Option<LoadedSprite>>,
     warned_missing_sprite_keys: &mut HashSet<String>,
     asset_root: &Path,
 ) {
-    let Some(tilemap) = world.tilemap() else {
-        return;
-    };
-    let Some(visible_rect) = visible_tile_rect(tilemap, view_bounds) else {
-        return;
-    };
-    let pixels_per_world = camera_pixels_per_world(world.camera());
+    for (tile_id, _) in visible_tiles(world, view_bounds) {
+        if let Some(key) = tile_sprite_key(tile_id) {
+            resolve_cached_sprite(sprite_cache, warned_missing_sprite_keys, asset_root, key);
+        }
+    }
+}
 
-    for y in visible_rect.y_min..=visible_rect.y_max {
-        for x in visible_rect.x_min..=visible_rect.x_max {
-            let Some(tile_id) = tilemap.tile_at(x, y) else {
-                continue;
-            };
-            let Some(center_world) = tilemap.tile_center_world(x, y) else {
-                continue;
-            };
-            let (cx, cy) =
-                snapped_world_to_screen_px(world.camera(), (width, height), center_world);
-            if let Some(key) = tile_sprite_key(tile_id) {
-                if let Some(sprite) =
-                    resolve_cached_sprite(sprite_cache, warned_missing_sprite_keys, asset_root, key)
-                {
-                    draw_sprite_centered_scaled(
-                        frame,
-                        width,
-                        height,
-                        cx,
-                        cy,
-                        sprite,
-                        world.camera().effective_zoom(),
-                    );
-                    continue;
-                }
+fn draw_tilemap(
+    target: &mut FrameBand,
+    world: &SceneWorld,
+    view_bounds: &WorldBounds,
+    sprite_cache: &HashMap<String, Option<LoadedSprite>>,
+) {
+    let window_size = (target.width(), target.height());
+    let pixels_per_world = camera_pixels_per_world(world.camera());
+    for (tile_id, center_world) in visible_tiles(world, view_bounds) {
+        let (cx, cy) = snapped_world_to_screen_px(world.camera(), window_size, center_world);
+        match tile_sprite_key(tile_id).and_then(|key| cached_sprite(sprite_cache, key)) {
+            Some(sprite) => {
+                draw_sprite_centered_scaled(target, cx, cy, sprite, world.camera().effective_zoom())
             }
-            draw_tile_fallback(frame, width, height, cx, cy, tile_id, pixels_per_world);
+            None => draw_tile_fallback(target, cx, cy, tile_id, pixels_per_world),
         }
     }
 }
@@ -1082,11 +1723,12 @@ fn view_bounds_world(camera: &Camera2D, window_size: (u32, u32), padding_px: f32
     let half_h_world = window_size.1 as f32 / (2.0 * safe_pixels_per_world);
     let padding_world = (padding_px.max(0.0) / safe_pixels_per_world).max(0.0);
 
+    let center = camera.view_center();
     WorldBounds {
-        min_x: camera.position.x - half_w_world - padding_world,
-        max_x: camera.position.x + half_w_world + padding_world,
-        min_y: camera.position.y - half_h_world - padding_world,
-        max_y: camera.position.y + half_h_world + padding_world,
+        min_x: center.x - half_w_world - padding_world,
+        max_x: center.x + half_w_world + padding_world,
+        min_y: center.y - half_h_world - padding_world,
+        max_y: center.y + half_h_world + padding_world,
     }
 }
 
@@ -1140,29 +1782,24 @@ fn tile_sprite_key(tile_id: u16) -> Option<&'static str> {
 }
 
 fn draw_tile_fallback(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
+    target: &mut FrameBand,
     center_x: i32,
     center_y: i32,
     tile_id: u16,
     pixels_per_world: f32,
 ) {
-    let color = match tile_id {
+    let color = tile_fallback_color(tile_id);
+    let half_size = (pixels_per_world / 2.0).round() as i32;
+    draw_square(target, center_x, center_y, half_size.max(1), color);
+}
+
+/// Flat color for `tile_id`, used when its sprite is missing and by the minimap.
+pub(super) fn tile_fallback_color(tile_id: u16) -> [u8; 4] {
+    match tile_id {
         0 => TILE_FALLBACK_GRASS_COLOR,
         1 => TILE_FALLBACK_DIRT_COLOR,
         _ => TILE_FALLBACK_UNKNOWN_COLOR,
-    };
-    let half_size = (pixels_per_world / 2.0).round() as i32;
-    draw_square(
-        frame,
-        width,
-        height,
-        center_x,
-        center_y,
-        half_size.max(1),
-        color,
-    );
+    }
 }
 
 fn resolve_cached_sprite<'a>(
@@ -1180,6 +1817,13 @@ fn resolve_cached_sprite<'a>(
     )
 }
 
+fn cached_sprite<'a>(
+    cache: &'a HashMap<String, Option<LoadedSprite>>,
+    key: &str,
+) -> Option<&'a LoadedSprite> {
+    cache.get(key).and_then(Option::as_ref)
+}
+
 fn resolve_cached_sprite_with_missing_policy<'a>(
     cache: &'a mut HashMap<String, Option<LoadedSprite>>,
     warned_missing_sprite_keys: &mut HashSet<String>,
@@ -1260,14 +1904,19 @@ fn warn_sprite_load_once(
     );
 }
 
-fn draw_world_grid(frame: &mut [u8], width: u32, height: u32, world: &SceneWorld) {
+fn draw_world_grid(target: &mut FrameBand, world: &SceneWorld) {
+    let (width, height) = (target.width(), target.height());
     if width == 0 || height == 0 {
         return;
     }
 
     let pixels_per_world = camera_pixels_per_world(world.camera());
-    let (ix_start, ix_end, iy_start, iy_end) =
-        visible_grid_index_bounds(world.camera().position, width, height, pixels_per_world);
+    let (ix_start, ix_end, iy_start, iy_end) = visible_grid_index_bounds(
+        world.camera().view_center(),
+        width,
+        height,
+        pixels_per_world,
+    );
 
     for ix in ix_start..=ix_end {
         let world_x = ix as f32 * GRID_CELL_WORLD;
@@ -1276,7 +1925,7 @@ fn draw_world_grid(frame: &mut [u8], width: u32, height: u32, world: &SceneWorld
             (width, height),
             Vec2 {
                 x: world_x,
-                y: world.camera().position.y,
+                y: world.camera().view_center().y,
             },
         );
         let color = if is_major_index(ix) {
@@ -1284,7 +1933,7 @@ fn draw_world_grid(frame: &mut [u8], width: u32, height: u32, world: &SceneWorld
         } else {
             GRID_MINOR_COLOR
         };
-        draw_vertical_line_clipped(frame, width, height, screen_x, color);
+        draw_vertical_line_clipped(target, screen_x, color);
     }
 
     for iy in iy_start..=iy_end {
@@ -1293,7 +1942,7 @@ fn draw_world_grid(frame: &mut [u8], width: u32, height: u32, world: &SceneWorld
             world.camera(),
             (width, height),
             Vec2 {
-                x: world.camera().position.x,
+                x: world.camera().view_center().x,
                 y: world_y,
             },
         );
@@ -1302,7 +1951,7 @@ fn draw_world_grid(frame: &mut [u8], width: u32, height: u32, world: &SceneWorld
         } else {
             GRID_MINOR_COLOR
         };
-        draw_horizontal_line_clipped(frame, width, height, screen_y, color);
+        draw_horizontal_line_clipped(target, screen_y, color);
     }
 }
 
@@ -1330,102 +1979,74 @@ fn is_major_index(idx: i32) -> bool {
     idx.rem_euclid(GRID_MAJOR_EVERY) == 0
 }
 
-fn draw_vertical_line_clipped(frame: &mut [u8], width: u32, height: u32, x: i32, color: [u8; 4]) {
-    if width == 0 || height == 0 || x < 0 || x >= width as i32 {
+fn draw_vertical_line_clipped(target: &mut FrameBand, x: i32, color: [u8; 4]) {
+    if x < 0 || x >= target.width() as i32 {
         return;
     }
-    for y in 0..height as i32 {
-        write_pixel_rgba_clipped(frame, width as usize, x, y, color);
+    for y in target.top()..target.bottom() {
+        write_pixel_rgba_clipped(target, x, y, color);
     }
 }
 
-fn draw_horizontal_line_clipped(frame: &mut [u8], width: u32, height: u32, y: i32, color: [u8; 4]) {
-    if width == 0 || height == 0 || y < 0 || y >= height as i32 {
+fn draw_horizontal_line_clipped(target: &mut FrameBand, y: i32, color: [u8; 4]) {
+    if y < target.top() || y >= target.bottom() {
         return;
     }
-    for x in 0..width as i32 {
-        write_pixel_rgba_clipped(frame, width as usize, x, y, color);
+    for x in 0..target.width() as i32 {
+        write_pixel_rgba_clipped(target, x, y, color);
     }
 }
 
-fn write_pixel_rgba_clipped(frame: &mut [u8], width: usize, x: i32, y: i32, color: [u8; 4]) {
-    if x < 0 || y < 0 {
-        return;
-    }
-    let x = x as usize;
-    let y = y as usize;
-    let Some(pixel_offset) = y.checked_mul(width).and_then(|row| row.checked_add(x)) else {
+fn write_pixel_rgba_clipped(target: &mut FrameBand, x: i32, y: i32, color: [u8; 4]) {
+    let Some(byte_offset) = target.pixel_offset(x, y) else {
         return;
     };
-    let Some(byte_offset) = pixel_offset.checked_mul(4) else {
-        return;
-    };
-    let Some(end) = byte_offset.checked_add(4) else {
-        return;
-    };
-    if end > frame.len() {
-        return;
-    }
-    frame[byte_offset..end].copy_from_slice(&color);
+    target.bytes_mut()[byte_offset..byte_offset + 4].copy_from_slice(&color);
 }
 
-fn draw_square(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
-    cx: i32,
-    cy: i32,
-    half_size: i32,
-    color: [u8; 4],
-) {
-    for y in (cy - half_size)..=(cy + half_size) {
+fn draw_square(target: &mut FrameBand, cx: i32, cy: i32, half_size: i32, color: [u8; 4]) {
+    let top = (cy - half_size).max(target.top());
+    let bottom = (cy + half_size).min(target.bottom() - 1);
+    for y in top..=bottom {
         for x in (cx - half_size)..=(cx + half_size) {
-            if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
-                continue;
-            }
-            write_pixel_rgba_clipped(frame, width as usize, x, y, color);
+            write_pixel_rgba_clipped(target, x, y, color);
         }
     }
 }
 
-fn draw_square_outline(
-    frame: &mut [u8],
-    width: u32,
-    _height: u32,
+fn draw_square_source_over(
+    target: &mut FrameBand,
     cx: i32,
     cy: i32,
     half_size: i32,
     color: [u8; 4],
 ) {
+    let size = half_size * 2 + 1;
+    fill_rect_source_over(target, cx - half_size, cy - half_size, size, size, color);
+}
+
+fn draw_square_outline(target: &mut FrameBand, cx: i32, cy: i32, half_size: i32, color: [u8; 4]) {
     let left = cx - half_size;
     let right = cx + half_size;
     let top = cy - half_size;
     let bottom = cy + half_size;
 
     for x in left..=right {
-        write_pixel_rgba_clipped(frame, width as usize, x, top, color);
-        write_pixel_rgba_clipped(frame, width as usize, x, bottom, color);
+        write_pixel_rgba_clipped(target, x, top, color);
+        write_pixel_rgba_clipped(target, x, bottom, color);
     }
     for y in top..=bottom {
-        write_pixel_rgba_clipped(frame, width as usize, left, y, color);
-        write_pixel_rgba_clipped(frame, width as usize, right, y, color);
+        write_pixel_rgba_clipped(target, left, y, color);
+        write_pixel_rgba_clipped(target, right, y, color);
     }
 }
 
-fn draw_cross(
-    frame: &mut [u8],
-    width: u32,
-    _height: u32,
-    cx: i32,
-    cy: i32,
-    half_size: i32,
-    color: [u8; 4],
-) {
+fn draw_cross(target: &mut FrameBand, cx: i32, cy: i32, half_size: i32, color: [u8; 4]) {
     for x in (cx - half_size)..=(cx + half_size) {
-        write_pixel_rgba_clipped(frame, width as usize, x, cy, color);
+        write_pixel_rgba_clipped(target, x, cy, color);
     }
     for y in (cy - half_size)..=(cy + half_size) {
-        write_pixel_rgba_clipped(frame, width as usize, cx, y, color);
+        write_pixel_rgba_clipped(target, cx, y, color);
     }
 }
 
@@ -1441,16 +2062,14 @@ fn use_tool_flicker_alpha_and_radius(
 }
 
 fn draw_use_tool_flicker_halo(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
+    target: &mut FrameBand,
     camera: &Camera2D,
     world_position: Vec2,
     visual_offset_px: Vec2,
     entity_id: crate::app::EntityId,
     sim_tick_counter: u64,
 ) {
-    if width == 0 || height == 0 {
+    if target.width() == 0 || target.height() == 0 {
         return;
     }
     let (alpha, radius_px) = use_tool_flicker_alpha_and_radius(entity_id, sim_tick_counter);
@@ -1459,14 +2078,12 @@ fn draw_use_tool_flicker_halo(
     }
     let (cx, cy) = world_to_snapped_screen_px_with_offset(
         camera,
-        (width, height),
+        (target.width(), target.height()),
         world_position,
         visual_offset_px,
     );
     draw_filled_circle_source_over(
-        frame,
-        width,
-        height,
+        target,
         cx,
         cy + USE_TOOL_FLICKER_Y_OFFSET_PX,
         radius_px,
@@ -1480,9 +2097,7 @@ fn draw_use_tool_flicker_halo(
 }
 
 fn draw_filled_circle_source_over(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
+    target: &mut FrameBand,
     cx: i32,
     cy: i32,
     radius_px: i32,
@@ -1490,7 +2105,7 @@ fn draw_filled_circle_source_over(
 ) {
     let radius = radius_px.max(0);
     let radius_sq = radius.saturating_mul(radius);
-    for y in (cy - radius)..=(cy + radius) {
+    for y in (cy - radius).max(target.top())..=(cy + radius).min(target.bottom() - 1) {
         let dy = y - cy;
         let dy_sq = dy.saturating_mul(dy);
         for x in (cx - radius)..=(cx + radius) {
@@ -1498,38 +2113,16 @@ fn draw_filled_circle_source_over(
             if dx.saturating_mul(dx).saturating_add(dy_sq) > radius_sq {
                 continue;
             }
-            blend_source_over_pixel_clipped(frame, width, height, x, y, color);
+            blend_source_over_pixel_clipped(target, x, y, color);
         }
     }
-}
-
-fn blend_source_over_pixel_clipped(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
-    x: i32,
-    y: i32,
-    src_rgba: [u8; 4],
-) {
-    if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
-        return;
-    }
-    let x = x as usize;
-    let y = y as usize;
-    let width = width as usize;
-    let Some(pixel_offset) = y.checked_mul(width).and_then(|row| row.checked_add(x)) else {
-        return;
-    };
-    let Some(byte_offset) = pixel_offset.checked_mul(4) else {
-        return;
-    };
-    let Some(end) = byte_offset.checked_add(4) else {
+}
+
+fn blend_source_over_pixel_clipped(target: &mut FrameBand, x: i32, y: i32, src_rgba: [u8; 4]) {
+    let Some(byte_offset) = target.pixel_offset(x, y) else {
         return;
     };
-    if end > frame.len() {
-        return;
-    }
-    blend_source_over_pixel_at_offset(frame, byte_offset, src_rgba);
+    blend_source_over_pixel_at_offset(target.bytes_mut(), byte_offset, src_rgba);
 }
 
 fn blend_source_over_pixel_at_offset(frame: &mut [u8], dst_offset: usize, src_rgba: [u8; 4]) {
@@ -1575,23 +2168,72 @@ fn normalized_sprite_scale(scale: f32) -> f32 {
     }
 }
 
-fn scaled_sprite_dimensions(sprite: &LoadedSprite, scale: f32) -> (u32, u32) {
-    let scale = normalized_sprite_scale(scale);
-    let width = (sprite.width as f32 * scale).round().max(1.0) as u32;
-    let height = (sprite.height as f32 * scale).round().max(1.0) as u32;
+fn scaled_sprite_dimensions(sprite: &LoadedSprite, scale_x: f32, scale_y: f32) -> (u32, u32) {
+    let scale_x = normalized_sprite_scale(scale_x);
+    let scale_y = normalized_sprite_scale(scale_y);
+    let width = (sprite.width as f32 * scale_x).round().max(1.0) as u32;
+    let height = (sprite.height as f32 * scale_y).round().max(1.0) as u32;
     (width, height)
 }
 
+/// Per-draw sprite transform for the software blitter. Flips apply in sprite space before the
+/// per-axis scale and rotation; rotation is counter-clockwise in world space (y up).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SpriteBlit<'a> {
+    scale_x: f32,
+    scale_y: f32,
+    rotation_radians: f32,
+    flip_x: bool,
+    flip_y: bool,
+    /// Applied to every source texel before source-over blending.
+    modifier: Option<&'a RenderModifier>,
+}
+
+impl SpriteBlit<'_> {
+    fn uniform(scale: f32) -> Self {
+        Self {
+            scale_x: scale,
+            scale_y: scale,
+            rotation_radians: 0.0,
+            flip_x: false,
+            flip_y: false,
+            modifier: None,
+        }
+    }
+
+    fn modulate(&self, src_rgba: [u8; 4]) -> [u8; 4] {
+        match self.modifier {
+            Some(modifier) => modifier.apply(src_rgba),
+            None => src_rgba,
+        }
+    }
+}
+
 fn draw_sprite_centered_scaled(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
+    target: &mut FrameBand,
     center_x: i32,
     center_y: i32,
     sprite: &LoadedSprite,
     scale: f32,
 ) {
-    if sprite.width == 0 || sprite.height == 0 || width == 0 || height == 0 {
+    draw_sprite_centered_transformed(
+        target,
+        center_x,
+        center_y,
+        sprite,
+        SpriteBlit::uniform(scale),
+    );
+}
+
+fn draw_sprite_centered_transformed(
+    target: &mut FrameBand,
+    center_x: i32,
+    center_y: i32,
+    sprite: &LoadedSprite,
+    blit: SpriteBlit,
+) {
+    let width = target.width();
+    if sprite.width == 0 || sprite.height == 0 || width == 0 || target.height() == 0 {
         return;
     }
     let expected_rgba_len = sprite.width as usize * sprite.height as usize * 4;
@@ -1599,36 +2241,107 @@ fn draw_sprite_centered_scaled(
         return;
     }
 
-    let scale = normalized_sprite_scale(scale);
-    let inv_scale = scale.recip();
-    let (scaled_w, scaled_h) = scaled_sprite_dimensions(sprite, scale);
+    let scale_x = normalized_sprite_scale(blit.scale_x);
+    let scale_y = normalized_sprite_scale(blit.scale_y);
+    let (scaled_w, scaled_h) = scaled_sprite_dimensions(sprite, scale_x, scale_y);
+    let rotation = if blit.rotation_radians.is_finite() {
+        blit.rotation_radians.rem_euclid(TAU)
+    } else {
+        0.0
+    };
+    if rotation == 0.0 {
+        draw_sprite_axis_aligned(
+            target,
+            (center_x, center_y),
+            sprite,
+            (scaled_w, scaled_h),
+            blit,
+        );
+        return;
+    }
+
+    let (sin, cos) = rotation.sin_cos();
+    let half_w = scaled_w as f32 / 2.0;
+    let half_h = scaled_h as f32 / 2.0;
+    let extent_x = (half_w * cos.abs() + half_h * sin.abs()).ceil() as i32;
+    let extent_y = (half_w * sin.abs() + half_h * cos.abs()).ceil() as i32;
+    let draw_left = (center_x - extent_x).max(0);
+    let draw_top = (center_y - extent_y).max(target.top());
+    let draw_right = (center_x + extent_x).min(width as i32);
+    let draw_bottom = (center_y + extent_y).min(target.bottom());
+    if draw_left >= draw_right || draw_top >= draw_bottom {
+        return;
+    }
+
+    let sprite_width = sprite.width as usize;
+    let inv_scale_x = scale_x.recip();
+    let inv_scale_y = scale_y.recip();
+    for out_y in draw_top..draw_bottom {
+        let dy = out_y as f32 + 0.5 - center_y as f32;
+        let dst_row_offset = target.row_offset(out_y);
+        for out_x in draw_left..draw_right {
+            let dx = out_x as f32 + 0.5 - center_x as f32;
+            // Screen y points down, so the inverse of a counter-clockwise world rotation is
+            // this transpose in screen coordinates.
+            let u = dx * cos - dy * sin + half_w;
+            let v = dx * sin + dy * cos + half_h;
+            if u < 0.0 || v < 0.0 || u >= scaled_w as f32 || v >= scaled_h as f32 {
+                continue;
+            }
+            let src_x = sprite_source_index((u * inv_scale_x) as u32, sprite.width, blit.flip_x);
+            let src_y = sprite_source_index((v * inv_scale_y) as u32, sprite.height, blit.flip_y);
+            let src_offset = (src_y * sprite_width + src_x) * 4;
+            let dst_offset = dst_row_offset + out_x as usize * 4;
+            let src_rgba = [
+                sprite.rgba[src_offset],
+                sprite.rgba[src_offset + 1],
+                sprite.rgba[src_offset + 2],
+                sprite.rgba[src_offset + 3],
+            ];
+            blend_source_over_pixel_at_offset(
+                target.bytes_mut(),
+                dst_offset,
+                blit.modulate(src_rgba),
+            );
+        }
+    }
+}
+
+fn draw_sprite_axis_aligned(
+    target: &mut FrameBand,
+    (center_x, center_y): (i32, i32),
+    sprite: &LoadedSprite,
+    (scaled_w, scaled_h): (u32, u32),
+    blit: SpriteBlit,
+) {
+    let inv_scale_x = normalized_sprite_scale(blit.scale_x).recip();
+    let inv_scale_y = normalized_sprite_scale(blit.scale_y).recip();
     let left = center_x - (scaled_w as i32 / 2);
     let top = center_y - (scaled_h as i32 / 2);
     let right = left + scaled_w as i32;
     let bottom = top + scaled_h as i32;
 
     let draw_left = left.max(0);
-    let draw_top = top.max(0);
-    let draw_right = right.min(width as i32);
-    let draw_bottom = bottom.min(height as i32);
+    let draw_top = top.max(target.top());
+    let draw_right = right.min(target.width() as i32);
+    let draw_bottom = bottom.min(target.bottom());
     if draw_left >= draw_right || draw_top >= draw_bottom {
         return;
     }
 
-    let frame_width = width as usize;
     let sprite_width = sprite.width as usize;
 
     for out_y in draw_top..draw_bottom {
         let dy = out_y - top;
-        let src_y = ((dy as f32) * inv_scale).floor() as u32;
-        let src_y = src_y.min(sprite.height - 1) as usize;
+        let src_y = ((dy as f32) * inv_scale_y).floor() as u32;
+        let src_y = sprite_source_index(src_y, sprite.height, blit.flip_y);
         let src_row_offset = src_y * sprite_width * 4;
-        let dst_row_offset = out_y as usize * frame_width * 4;
+        let dst_row_offset = target.row_offset(out_y);
 
         for out_x in draw_left..draw_right {
             let dx = out_x - left;
-            let src_x = ((dx as f32) * inv_scale).floor() as u32;
-            let src_x = src_x.min(sprite.width - 1) as usize;
+            let src_x = ((dx as f32) * inv_scale_x).floor() as u32;
+            let src_x = sprite_source_index(src_x, sprite.width, blit.flip_x);
             let src_offset = src_row_offset + src_x * 4;
             let dst_offset = dst_row_offset + out_x as usize * 4;
             let src_rgba = [
@@ -1637,17 +2350,33 @@ fn draw_sprite_centered_scaled(
                 sprite.rgba[src_offset + 2],
                 sprite.rgba[src_offset + 3],
             ];
-            blend_source_over_pixel_at_offset(frame, dst_offset, src_rgba);
+            blend_source_over_pixel_at_offset(
+                target.bytes_mut(),
+                dst_offset,
+                blit.modulate(src_rgba),
+            );
         }
     }
 }
 
+/// Clamps a sampled texel index into the sprite and mirrors it when `flip` is set.
+fn sprite_source_index(sampled: u32, extent: u32, flip: bool) -> usize {
+    let index = sampled.min(extent - 1);
+    if flip {
+        (extent - 1 - index) as usize
+    } else {
+        index as usize
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app::{
-        ActionParams, Camera2D, CardinalFacing, DebugMarker, DebugMarkerKind, EntityId, FloorId,
-        SpriteAnchorName, SpriteAnchorPx, SpriteAnchors, Tilemap,
+        ActionParams, Camera2D, CardinalFacing, DebugMarker, DebugMarkerKind, EntityId,
+        EntitySight, FactionId, FloorId, LightAnchor, ParticleEmitterDef, ParticleRange,
+        PointLight, RenderLayer, SpriteAnchorName, SpriteAnchorPx, SpriteAnchors, Tilemap,
+        WidgetAnchor, WorldWidget,
     };
     use crate::content::{DefDatabase, EntityArchetype, EntityDefId};
     use tempfile::TempDir;
@@ -1672,12 +2401,13 @@ mod tests {
 
     #[test]
     fn clear_color_varies_by_active_floor() {
-        assert_eq!(clear_color_for_floor(FloorId::Main), CLEAR_COLOR_MAIN);
-        assert_eq!(clear_color_for_floor(FloorId::Rooftop), CLEAR_COLOR_ROOFTOP);
+        assert_eq!(clear_color_for_floor(FloorId::MAIN), CLEAR_COLOR_MAIN);
+        assert_eq!(clear_color_for_floor(FloorId::ROOFTOP), CLEAR_COLOR_ROOFTOP);
         assert_eq!(
-            clear_color_for_floor(FloorId::Basement),
+            clear_color_for_floor(FloorId::BASEMENT),
             CLEAR_COLOR_BASEMENT
         );
+        assert_eq!(clear_color_for_floor(FloorId(7)), CLEAR_COLOR_MAIN);
         assert_ne!(CLEAR_COLOR_MAIN, CLEAR_COLOR_ROOFTOP);
         assert_ne!(CLEAR_COLOR_MAIN, CLEAR_COLOR_BASEMENT);
     }
@@ -1686,7 +2416,7 @@ mod tests {
     fn render_list_selection_filters_to_active_floor_deterministically() {
         let mut world = SceneWorld::default();
 
-        world.set_active_floor(FloorId::Main);
+        world.set_active_floor(FloorId::MAIN);
         let main_visible = world.spawn(
             crate::app::Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -1698,7 +2428,7 @@ mod tests {
             },
         );
 
-        world.set_active_floor(FloorId::Basement);
+        world.set_active_floor(FloorId::BASEMENT);
         let basement_visible = world.spawn(
             crate::app::Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -1710,7 +2440,7 @@ mod tests {
             },
         );
 
-        world.set_active_floor(FloorId::Main);
+        world.set_active_floor(FloorId::MAIN);
         world.spawn(
             crate::app::Transform {
                 position: Vec2 { x: 200.0, y: 200.0 },
@@ -1735,7 +2465,7 @@ mod tests {
         assert_eq!(first_main, vec![main_visible]);
         assert_eq!(second_main, first_main);
 
-        world.set_active_floor(FloorId::Basement);
+        world.set_active_floor(FloorId::BASEMENT);
         assert_eq!(
             collect_visible_entity_ids_for_active_floor(&world, &bounds),
             vec![basement_visible]
@@ -1745,7 +2475,7 @@ mod tests {
     #[test]
     fn sorted_draw_list_helper_uses_overlap_order_not_storage_order() {
         let mut world = SceneWorld::default();
-        world.set_active_floor(FloorId::Main);
+        world.set_active_floor(FloorId::MAIN);
         let first = world.spawn(
             crate::app::Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -1789,10 +2519,44 @@ mod tests {
         assert_eq!(ids, vec![first, second, third]);
     }
 
+    #[test]
+    fn sorted_draw_list_helper_orders_by_layer_then_pivot_y() {
+        let mut world = SceneWorld::default();
+        world.set_active_floor(FloorId::MAIN);
+        let mut spawn_at = |y: f32| {
+            world.spawn(
+                crate::app::Transform {
+                    position: Vec2 { x: 0.0, y },
+                    rotation_radians: None,
+                },
+                crate::app::RenderableDesc {
+                    kind: crate::app::RenderableKind::Placeholder,
+                    debug_name: "y_sorted",
+                },
+            )
+        };
+        let front = spawn_at(-0.5);
+        let back = spawn_at(0.5);
+        let decal = spawn_at(-0.9);
+        let roof = spawn_at(0.9);
+        world.set_entity_render_order(decal, RenderLayer::GroundDecal, 0.0);
+        world.set_entity_render_order(roof, RenderLayer::Roof, 0.0);
+        world.apply_pending();
+
+        let bounds = WorldBounds {
+            min_x: -1.0,
+            max_x: 1.0,
+            min_y: -1.0,
+            max_y: 1.0,
+        };
+        let ids = collect_visible_entity_ids_for_active_floor(&world, &bounds);
+        assert_eq!(ids, vec![decal, back, front, roof]);
+    }
+
     #[test]
     fn sorted_draw_list_helper_is_repeatable() {
         let mut world = SceneWorld::default();
-        world.set_active_floor(FloorId::Main);
+        world.set_active_floor(FloorId::MAIN);
         world.spawn(
             crate::app::Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -1862,9 +2626,12 @@ mod tests {
             },
             held_visual: None,
         };
-        let (state_facing, state_only) =
-            visual_test_variant_candidate_keys("visual_test/pawn_blue", &visual)
-                .expect("variant keys");
+        let (state_facing, state_only) = visual_test_variant_candidate_keys(
+            "visual_test/pawn_blue",
+            visual.action_state,
+            visual.action_params.facing,
+        )
+        .expect("variant keys");
         assert_eq!(state_facing, "visual_test/pawn_blue__walk_west");
         assert_eq!(state_only, "visual_test/pawn_blue__walk");
     }
@@ -1879,7 +2646,12 @@ mod tests {
             },
             held_visual: None,
         };
-        assert!(visual_test_variant_candidate_keys("npc/chaser_red", &visual).is_none());
+        assert!(visual_test_variant_candidate_keys(
+            "npc/chaser_red",
+            visual.action_state,
+            visual.action_params.facing
+        )
+        .is_none());
     }
 
     fn spawn_sprite_entity_for_anchor_test(anchors: SpriteAnchors) -> Entity {
@@ -1894,6 +2666,7 @@ mod tests {
                     key: "visual_test/pawn_blue".to_string(),
                     pixel_scale: 3,
                     anchors,
+                    mirror_west: false,
                 },
                 debug_name: "anchor_test",
             },
@@ -2062,9 +2835,7 @@ mod tests {
         let mut frame = vec![0u8; 16 * 16 * 4];
         let before = frame.clone();
         draw_use_tool_flicker_halo(
-            &mut frame,
-            0,
-            0,
+            &mut FrameBand::full(&mut frame, 0, 0),
             &Camera2D::default(),
             Vec2 { x: 0.0, y: 0.0 },
             Vec2 { x: 0.0, y: 0.0 },
@@ -2150,8 +2921,8 @@ mod tests {
             height: 6,
             rgba: vec![255; 4 * 6 * 4],
         };
-        assert_eq!(scaled_sprite_dimensions(&sprite, 1.0), (4, 6));
-        assert_eq!(scaled_sprite_dimensions(&sprite, 2.0), (8, 12));
+        assert_eq!(scaled_sprite_dimensions(&sprite, 1.0, 1.0), (4, 6));
+        assert_eq!(scaled_sprite_dimensions(&sprite, 2.0, 2.0), (8, 12));
     }
 
     #[test]
@@ -2163,7 +2934,7 @@ mod tests {
             rgba: vec![200, 100, 50, 0],
         };
 
-        draw_sprite_centered_scaled(&mut frame, 1, 1, 0, 0, &sprite, 1.0);
+        draw_sprite_centered_scaled(&mut FrameBand::full(&mut frame, 1, 1), 0, 0, &sprite, 1.0);
 
         assert_eq!(frame, vec![10u8, 20, 30, 40]);
     }
@@ -2177,7 +2948,7 @@ mod tests {
             rgba: vec![200, 100, 50, 255],
         };
 
-        draw_sprite_centered_scaled(&mut frame, 1, 1, 0, 0, &sprite, 1.0);
+        draw_sprite_centered_scaled(&mut FrameBand::full(&mut frame, 1, 1), 0, 0, &sprite, 1.0);
 
         assert_eq!(frame, vec![200u8, 100, 50, 255]);
     }
@@ -2191,11 +2962,232 @@ mod tests {
             rgba: vec![200, 100, 50, 128],
         };
 
-        draw_sprite_centered_scaled(&mut frame, 1, 1, 0, 0, &sprite, 1.0);
+        draw_sprite_centered_scaled(&mut FrameBand::full(&mut frame, 1, 1), 0, 0, &sprite, 1.0);
 
         assert_eq!(frame, vec![105u8, 60, 40, 255]);
     }
 
+    fn red_blue_sprite() -> LoadedSprite {
+        LoadedSprite {
+            width: 2,
+            height: 1,
+            rgba: vec![255, 0, 0, 255, 0, 0, 255, 255],
+        }
+    }
+
+    fn frame_pixel(frame: &[u8], frame_width: usize, x: usize, y: usize) -> [u8; 4] {
+        let offset = (y * frame_width + x) * 4;
+        [
+            frame[offset],
+            frame[offset + 1],
+            frame[offset + 2],
+            frame[offset + 3],
+        ]
+    }
+
+    #[test]
+    fn draw_sprite_flip_x_mirrors_columns_and_per_axis_scale_stretches() {
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let mut frame = vec![0u8; 2 * 4];
+        let blit = SpriteBlit {
+            flip_x: true,
+            ..SpriteBlit::uniform(1.0)
+        };
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut frame, 2, 1),
+            1,
+            0,
+            &red_blue_sprite(),
+            blit,
+        );
+        assert_eq!(frame_pixel(&frame, 2, 0, 0), blue);
+        assert_eq!(frame_pixel(&frame, 2, 1, 0), red);
+
+        let mut frame = vec![0u8; 4 * 2 * 4];
+        let blit = SpriteBlit {
+            scale_x: 2.0,
+            scale_y: 2.0,
+            ..SpriteBlit::uniform(1.0)
+        };
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut frame, 4, 2),
+            2,
+            1,
+            &red_blue_sprite(),
+            blit,
+        );
+        assert_eq!(frame_pixel(&frame, 4, 1, 1), red);
+        assert_eq!(frame_pixel(&frame, 4, 2, 0), blue);
+
+        let mut frame = vec![0u8; 4 * 4];
+        let blit = SpriteBlit {
+            scale_x: 2.0,
+            scale_y: 1.0,
+            ..SpriteBlit::uniform(1.0)
+        };
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut frame, 4, 1),
+            2,
+            0,
+            &red_blue_sprite(),
+            blit,
+        );
+        let row = (0..4)
+            .map(|x| frame_pixel(&frame, 4, x, 0))
+            .collect::<Vec<_>>();
+        assert_eq!(row, vec![red, red, blue, blue]);
+    }
+
+    #[test]
+    fn draw_sprite_flip_y_mirrors_rows() {
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let red_over_blue = LoadedSprite {
+            width: 1,
+            height: 2,
+            rgba: vec![255, 0, 0, 255, 0, 0, 255, 255],
+        };
+        let blit = SpriteBlit {
+            flip_y: true,
+            ..SpriteBlit::uniform(1.0)
+        };
+        let mut frame = vec![0u8; 2 * 4];
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut frame, 1, 2),
+            0,
+            1,
+            &red_over_blue,
+            blit,
+        );
+        assert_eq!(frame_pixel(&frame, 1, 0, 0), blue);
+        assert_eq!(frame_pixel(&frame, 1, 0, 1), red);
+
+        let mut frame = vec![0u8; 8 * 16 * 4];
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut frame, 8, 16),
+            4,
+            8,
+            &red_over_blue,
+            SpriteBlit {
+                rotation_radians: 0.01,
+                flip_y: true,
+                ..SpriteBlit::uniform(4.0)
+            },
+        );
+        assert_eq!(frame_pixel(&frame, 8, 4, 6), blue);
+        assert_eq!(frame_pixel(&frame, 8, 4, 10), red);
+    }
+
+    #[test]
+    fn draw_sprite_rotation_turns_counter_clockwise_in_world_space() {
+        let mut frame = vec![0u8; 2 * 2 * 4];
+        let blit = SpriteBlit {
+            rotation_radians: std::f32::consts::FRAC_PI_2,
+            ..SpriteBlit::uniform(1.0)
+        };
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut frame, 2, 2),
+            1,
+            1,
+            &red_blue_sprite(),
+            blit,
+        );
+        // The left (red) texel rotates to the bottom; nothing lands in the right column.
+        assert_eq!(frame_pixel(&frame, 2, 0, 0), [0, 0, 255, 255]);
+        assert_eq!(frame_pixel(&frame, 2, 0, 1), [255, 0, 0, 255]);
+        assert_eq!(frame_pixel(&frame, 2, 1, 0), [0, 0, 0, 0]);
+        assert_eq!(frame_pixel(&frame, 2, 1, 1), [0, 0, 0, 0]);
+
+        let mut unrotated = vec![0u8; 2 * 4];
+        let mut full_turn = vec![0u8; 2 * 4];
+        draw_sprite_centered_scaled(
+            &mut FrameBand::full(&mut unrotated, 2, 1),
+            1,
+            0,
+            &red_blue_sprite(),
+            1.0,
+        );
+        let blit = SpriteBlit {
+            rotation_radians: std::f32::consts::TAU,
+            ..SpriteBlit::uniform(1.0)
+        };
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut full_turn, 2, 1),
+            1,
+            0,
+            &red_blue_sprite(),
+            blit,
+        );
+        assert_eq!(unrotated, full_turn);
+    }
+
+    #[test]
+    fn draw_sprite_modifier_tints_and_fades_source_before_blending() {
+        let modifier = RenderModifier {
+            tint_rgba: [255, 255, 0, 255],
+            alpha: 128,
+            palette_swap: vec![crate::app::PaletteSwapEntry {
+                from_rgb: [0, 0, 255],
+                to_rgb: [0, 255, 255],
+            }],
+        };
+        let mut frame = vec![0, 0, 0, 255, 0, 0, 0, 255];
+        let blit = SpriteBlit {
+            modifier: Some(&modifier),
+            ..SpriteBlit::uniform(1.0)
+        };
+        draw_sprite_centered_transformed(
+            &mut FrameBand::full(&mut frame, 2, 1),
+            1,
+            0,
+            &red_blue_sprite(),
+            blit,
+        );
+        assert_eq!(frame_pixel(&frame, 2, 0, 0), [128, 0, 0, 255]);
+        assert_eq!(frame_pixel(&frame, 2, 1, 0), [0, 128, 0, 255]);
+    }
+
+    #[test]
+    fn placeholder_modifier_alpha_blends_source_over() {
+        let modifier = RenderModifier {
+            alpha: 128,
+            ..RenderModifier::default()
+        };
+        let mut frame = Vec::with_capacity(32 * 32 * 4);
+        for _ in 0..32 * 32 {
+            frame.extend_from_slice(&[0, 0, 0, 255]);
+        }
+        draw_renderable_at_world_position(
+            &mut FrameBand::full(&mut frame, 32, 32),
+            &Camera2D::default(),
+            &crate::app::Transform::default(),
+            Vec2 { x: 0.0, y: 0.0 },
+            &RenderableKind::Placeholder,
+            None,
+            Some(&modifier),
+            true,
+        );
+        let center = frame_pixel(&frame, 32, 16, 16);
+        assert_eq!(center, [110, 110, 120, 255]);
+    }
+
+    #[test]
+    fn mirror_west_draws_east_variant_flipped() {
+        assert_eq!(
+            sprite_facing_for_mirror(Some(CardinalFacing::West), true),
+            (Some(CardinalFacing::East), true)
+        );
+        assert_eq!(
+            sprite_facing_for_mirror(Some(CardinalFacing::West), false),
+            (Some(CardinalFacing::West), false)
+        );
+        assert_eq!(
+            sprite_facing_for_mirror(Some(CardinalFacing::North), true),
+            (Some(CardinalFacing::North), false)
+        );
+    }
+
     #[test]
     fn sprite_anchor_lookup_falls_back_to_origin_when_missing() {
         let anchors = SpriteAnchors::default();
@@ -2238,6 +3230,7 @@ mod tests {
                     key: "visual_test/carry_item".to_string(),
                     pixel_scale: 3,
                     anchors: SpriteAnchors::default(),
+                    mirror_west: false,
                 },
                 move_speed: 5.0,
                 health_max: None,
@@ -2246,6 +3239,12 @@ mod tests {
                 attack_range: None,
                 attack_cooldown_seconds: None,
                 tags: Vec::new(),
+                render_layer: RenderLayer::Standing,
+                sort_pivot_y: 0.0,
+                palette_swap: Vec::new(),
+                particle_emitters: Vec::new(),
+                light: None,
+                sight_radius: None,
             },
             EntityArchetype {
                 id: EntityDefId(0),
@@ -2259,6 +3258,12 @@ mod tests {
                 attack_range: None,
                 attack_cooldown_seconds: None,
                 tags: Vec::new(),
+                render_layer: RenderLayer::Standing,
+                sort_pivot_y: 0.0,
+                palette_swap: Vec::new(),
+                particle_emitters: Vec::new(),
+                light: None,
+                sight_radius: None,
             },
         ]);
 
@@ -2367,15 +3372,17 @@ mod tests {
     #[test]
     fn grid_draw_is_safe_for_tiny_or_zero_viewports() {
         let mut zero = vec![];
-        draw_vertical_line_clipped(&mut zero, 0, 0, 0, GRID_MINOR_COLOR);
-        draw_horizontal_line_clipped(&mut zero, 0, 0, 0, GRID_MINOR_COLOR);
-        write_pixel_rgba_clipped(&mut zero, 0, 0, 0, GRID_MINOR_COLOR);
+        let mut zero_band = FrameBand::full(&mut zero, 0, 0);
+        draw_vertical_line_clipped(&mut zero_band, 0, GRID_MINOR_COLOR);
+        draw_horizontal_line_clipped(&mut zero_band, 0, GRID_MINOR_COLOR);
+        write_pixel_rgba_clipped(&mut zero_band, 0, 0, GRID_MINOR_COLOR);
 
         let mut tiny = vec![0u8; 4];
-        draw_vertical_line_clipped(&mut tiny, 1, 1, -1, GRID_MINOR_COLOR);
-        draw_vertical_line_clipped(&mut tiny, 1, 1, 0, GRID_MINOR_COLOR);
-        draw_horizontal_line_clipped(&mut tiny, 1, 1, 0, GRID_MINOR_COLOR);
-        write_pixel_rgba_clipped(&mut tiny, 1, 99, 99, GRID_MINOR_COLOR);
+        let mut tiny_band = FrameBand::full(&mut tiny, 1, 1);
+        draw_vertical_line_clipped(&mut tiny_band, -1, GRID_MINOR_COLOR);
+        draw_vertical_line_clipped(&mut tiny_band, 0, GRID_MINOR_COLOR);
+        draw_horizontal_line_clipped(&mut tiny_band, 0, GRID_MINOR_COLOR);
+        write_pixel_rgba_clipped(&mut tiny_band, 99, 99, GRID_MINOR_COLOR);
         assert_eq!(tiny.len(), 4);
     }
 
@@ -2398,6 +3405,7 @@ mod tests {
         let camera = Camera2D {
             position: Vec2 { x: -10.0, y: -5.0 },
             zoom: 2.0,
+            ..Camera2D::default()
         };
         let bounds = view_bounds_world(&camera, (128, 64), VIEW_CULL_PADDING_PX);
         assert!(bounds.min_x < -10.0);
@@ -2609,6 +3617,51 @@ mod tests {
         assert!(load_sprite_rgba(&grass_path).is_err());
     }
 
+    #[test]
+    fn tilemap_layers_draw_in_order_and_skip_empty_tiles() {
+        let temp = TempDir::new().expect("temp");
+        let mut tilemap =
+            Tilemap::new(2, 1, Vec2 { x: -1.0, y: -0.5 }, vec![0, 0]).expect("tilemap");
+        let overlay = tilemap.add_layer("overlay").expect("overlay");
+        tilemap.set_layer_tile(overlay, 1, 0, 1);
+        let mut world = SceneWorld::default();
+        world.set_tilemap(tilemap);
+
+        let (width, height) = (128u32, 64u32);
+        let mut frame = vec![0u8; (width * height * 4) as usize];
+        let bounds = view_bounds_world(world.camera(), (width, height), VIEW_CULL_PADDING_PX);
+        let mut sprite_cache = HashMap::new();
+        load_visible_tile_sprites(
+            &world,
+            &bounds,
+            &mut sprite_cache,
+            &mut HashSet::new(),
+            temp.path(),
+        );
+        draw_tilemap(
+            &mut FrameBand::full(&mut frame, width, height),
+            &world,
+            &bounds,
+            &sprite_cache,
+        );
+
+        let pixel_at = |center: Vec2| {
+            let (x, y) = world_to_screen_px(world.camera(), (width, height), center);
+            let offset = ((y as u32 * width + x as u32) * 4) as usize;
+            [
+                frame[offset],
+                frame[offset + 1],
+                frame[offset + 2],
+                frame[offset + 3],
+            ]
+        };
+        assert_eq!(
+            pixel_at(Vec2 { x: -0.5, y: 0.0 }),
+            TILE_FALLBACK_GRASS_COLOR
+        );
+        assert_eq!(pixel_at(Vec2 { x: 0.5, y: 0.0 }), TILE_FALLBACK_DIRT_COLOR);
+    }
+
     #[test]
     fn affordances_skip_stale_visual_ids_without_panic() {
         let mut world = SceneWorld::default();
@@ -2636,4 +3689,388 @@ mod tests {
         draw_affordances(&mut frame, 64, 64, &world, &bounds);
         assert!(frame.iter().any(|byte| *byte != 0));
     }
+
+    #[test]
+    fn world_widgets_draw_bar_fill_arc_and_label_at_anchor() {
+        const FILL: [u8; 4] = [40, 220, 60, 255];
+        const ARC: [u8; 4] = [250, 200, 40, 255];
+        const TEXT: [u8; 4] = [240, 240, 240, 255];
+        let mut world = SceneWorld::default();
+        let origin = WidgetAnchor::World(Vec2 { x: 0.0, y: 0.0 });
+        world
+            .world_widgets_mut()
+            .push(WorldWidget::bar(origin, 0.5, FILL));
+        world
+            .world_widgets_mut()
+            .push(WorldWidget::progress_arc(origin, 0.25, ARC).with_offset_px(0.0, -16.0));
+        world
+            .world_widgets_mut()
+            .push(WorldWidget::label(origin, "ab", TEXT).with_offset_px(0.0, 16.0));
+        let pixel = |frame: &[u8], x: usize, y: usize| {
+            let offset = (y * 64 + x) * 4;
+            [
+                frame[offset],
+                frame[offset + 1],
+                frame[offset + 2],
+                frame[offset + 3],
+            ]
+        };
+
+        let mut frame = vec![0u8; 64 * 64 * 4];
+        let bounds = view_bounds_world(world.camera(), (64, 64), VIEW_CULL_PADDING_PX);
+        draw_world_widgets(&mut frame, 64, 64, &world, &bounds, &BitmapFont::builtin());
+        // Bar: 24x3 centered on (32, 32), left half filled.
+        assert_eq!(pixel(&frame, 21, 32), FILL);
+        assert_ne!(pixel(&frame, 42, 32), FILL);
+        assert_ne!(pixel(&frame, 42, 32), [0; 4]);
+        // Arc: radius 7 around (32, 16); the first quarter runs from twelve to three o'clock.
+        assert_eq!(pixel(&frame, 34, 10), ARC);
+        assert_ne!(pixel(&frame, 30, 10), ARC);
+        assert_ne!(pixel(&frame, 30, 10), [0; 4]);
+        let label_rows = &frame[(46 * 64 * 4)..(51 * 64 * 4)];
+        assert!(label_rows.chunks_exact(4).any(|px| px == TEXT));
+
+        let mut zoomed_out = vec![0u8; 64 * 64 * 4];
+        world.camera_mut().zoom = 0.5;
+        let bounds = view_bounds_world(world.camera(), (64, 64), VIEW_CULL_PADDING_PX);
+        draw_world_widgets(
+            &mut zoomed_out,
+            64,
+            64,
+            &world,
+            &bounds,
+            &BitmapFont::builtin(),
+        );
+        assert!(zoomed_out.chunks_exact(4).all(|px| px != TEXT));
+        assert_eq!(pixel(&zoomed_out, 27, 32), FILL);
+        assert_ne!(pixel(&zoomed_out, 20, 32), FILL);
+    }
+
+    #[test]
+    fn particles_draw_ramp_colored_squares_on_the_active_floor_only() {
+        let mut world = SceneWorld::default();
+        let def = ParticleEmitterDef {
+            burst: 1,
+            lifetime_seconds: ParticleRange::constant(1.0),
+            size_px: 4,
+            colors: vec![[200, 40, 40, 255]],
+            ..ParticleEmitterDef::default()
+        };
+        world.start_particle_emitter(&def, FloorId::MAIN, Vec2 { x: 0.0, y: 0.0 });
+        let temp = TempDir::new().expect("temp");
+        let mut sprite_cache = HashMap::new();
+        let mut warned = HashSet::new();
+        let draw = |world: &SceneWorld,
+                    sprite_cache: &mut HashMap<String, Option<LoadedSprite>>,
+                    warned: &mut HashSet<String>| {
+            let mut frame = vec![0u8; 64 * 64 * 4];
+            let bounds = view_bounds_world(world.camera(), (64, 64), VIEW_CULL_PADDING_PX);
+            draw_particles(
+                &mut frame,
+                64,
+                64,
+                world,
+                &bounds,
+                sprite_cache,
+                warned,
+                temp.path(),
+            );
+            frame
+        };
+
+        let frame = draw(&world, &mut sprite_cache, &mut warned);
+        let center = (32 * 64 + 32) * 4;
+        assert_eq!(&frame[center..center + 4], &[200, 40, 40, 255]);
+        let outside = (32 * 64 + 36) * 4;
+        assert_eq!(&frame[outside..outside + 4], &[0, 0, 0, 0]);
+
+        world.set_active_floor(FloorId::BASEMENT);
+        let frame = draw(&world, &mut sprite_cache, &mut warned);
+        assert!(frame.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn lighting_darkens_by_ambient_brightens_near_lights_and_reuses_the_lightmap() {
+        let mut world = SceneWorld::default();
+        let bounds = view_bounds_world(world.camera(), (64, 64), VIEW_CULL_PADDING_PX);
+        let mut cache = LightmapCache::default();
+        let lit = |world: &SceneWorld, cache: &mut LightmapCache| {
+            let mut frame = vec![200u8; 64 * 64 * 4];
+            if update_lightmap(world, &bounds, cache) {
+                apply_lighting(
+                    &mut FrameBand::full(&mut frame, 64, 64),
+                    world.camera(),
+                    cache,
+                );
+            }
+            frame
+        };
+        let pixel = |frame: &[u8], x: usize, y: usize| {
+            let offset = (y * 64 + x) * 4;
+            [
+                frame[offset],
+                frame[offset + 1],
+                frame[offset + 2],
+                frame[offset + 3],
+            ]
+        };
+
+        assert!(lit(&world, &mut cache).iter().all(|byte| *byte == 200));
+        assert!(cache.key.is_none(), "unlit floors skip the pass");
+
+        world.set_floor_ambient(FloorId::MAIN, [128, 64, 0]);
+        let frame = lit(&world, &mut cache);
+        assert_eq!(pixel(&frame, 0, 0), [100, 50, 0, 200]);
+        assert_eq!(pixel(&frame, 63, 63), [100, 50, 0, 200]);
+
+        world
+            .add_light(PointLight {
+                anchor: LightAnchor::World {
+                    floor: FloorId::MAIN,
+                    position: Vec2 { x: 0.0, y: 0.0 },
+                },
+                offset: Vec2 { x: 0.0, y: 0.0 },
+                radius: 0.5,
+                rgb: [255, 255, 255],
+                intensity: 2.0,
+            })
+            .expect("light");
+        let frame = lit(&world, &mut cache);
+        assert_eq!(pixel(&frame, 32, 32), [200, 200, 200, 200]);
+        assert_eq!(pixel(&frame, 0, 0), [100, 50, 0, 200]);
+        let cached_key = cache.key.clone();
+        lit(&world, &mut cache);
+        assert_eq!(cache.key, cached_key);
+
+        world.set_active_floor(FloorId::BASEMENT);
+        assert!(lit(&world, &mut cache).iter().all(|byte| *byte == 200));
+    }
+
+    #[test]
+    fn fog_covers_unexplored_dims_explored_and_hides_entities_in_it() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(
+            Tilemap::new(2, 2, Vec2 { x: -1.0, y: -1.0 }, vec![0; 4]).expect("tilemap"),
+        );
+        let spawn = |world: &mut SceneWorld, x: f32, y: f32| {
+            world.spawn(
+                Transform {
+                    position: Vec2 { x, y },
+                    rotation_radians: None,
+                },
+                crate::app::RenderableDesc {
+                    kind: RenderableKind::Placeholder,
+                    debug_name: "pawn",
+                },
+            )
+        };
+        let scout = spawn(&mut world, -0.5, -0.5);
+        let stranger = spawn(&mut world, 0.5, 0.5);
+        world
+            .set_entity_sight(
+                scout,
+                EntitySight {
+                    faction: FactionId(0),
+                    radius: 0.0,
+                },
+            )
+            .expect("sight");
+        world.apply_pending();
+        world.update_visibility();
+        let bounds = view_bounds_world(world.camera(), (64, 64), VIEW_CULL_PADDING_PX);
+        let fogged = |world: &SceneWorld| {
+            let mut frame = vec![200u8; 64 * 64 * 4];
+            apply_fog(&mut FrameBand::full(&mut frame, 64, 64), world, &bounds);
+            frame
+        };
+        let pixel = |frame: &[u8], x: usize, y: usize| {
+            let offset = (y * 64 + x) * 4;
+            [
+                frame[offset],
+                frame[offset + 1],
+                frame[offset + 2],
+                frame[offset + 3],
+            ]
+        };
+        let mut draw_indices = Vec::new();
+        let drawn_ids = |world: &SceneWorld, draw_indices: &mut Vec<usize>| {
+            collect_sorted_visible_entity_draw_indices(
+                world,
+                world.active_floor(),
+                &bounds,
+                draw_indices,
+            );
+            draw_indices
+                .iter()
+                .map(|index| world.entities()[*index].id)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(fogged(&world).iter().all(|byte| *byte == 200));
+        assert_eq!(drawn_ids(&world, &mut draw_indices), vec![stranger, scout]);
+
+        world.set_fog_viewer(Some(FactionId(0)));
+        let frame = fogged(&world);
+        assert_eq!(pixel(&frame, 10, 50), [200, 200, 200, 200]);
+        assert_eq!(pixel(&frame, 50, 10), FOG_UNEXPLORED_COLOR);
+        assert_eq!(drawn_ids(&world, &mut draw_indices), vec![scout]);
+
+        world
+            .find_entity_mut(scout)
+            .expect("scout")
+            .transform
+            .position = Vec2 { x: 0.5, y: -0.5 };
+        world.update_visibility();
+        let frame = fogged(&world);
+        assert_eq!(pixel(&frame, 10, 50), [81, 84, 93, 200]);
+        assert_eq!(pixel(&frame, 50, 50), [200, 200, 200, 200]);
+    }
+
+    #[test]
+    fn world_widgets_skip_entity_anchors_that_are_missing() {
+        let mut world = SceneWorld::default();
+        world.world_widgets_mut().push(WorldWidget::bar(
+            WidgetAnchor::Entity(EntityId(404)),
+            1.0,
+            [255; 4],
+        ));
+        let mut frame = vec![0u8; 64 * 64 * 4];
+        let bounds = view_bounds_world(world.camera(), (64, 64), VIEW_CULL_PADDING_PX);
+        draw_world_widgets(&mut frame, 64, 64, &world, &bounds, &BitmapFont::builtin());
+        assert!(frame.iter().all(|byte| *byte == 0));
+    }
+
+    fn write_test_sprite(asset_root: &Path, key: &str, width: u32, height: u32) {
+        let path = resolve_sprite_image_path(asset_root, key).expect("sprite path");
+        std::fs::create_dir_all(path.parent().expect("sprite dir")).expect("create sprite dir");
+        image::RgbaImage::from_fn(width, height, |x, y| {
+            let alpha = if (x + y) % 3 == 0 { 128 } else { 255 };
+            image::Rgba([(x * 29) as u8, (y * 41) as u8, 120, alpha])
+        })
+        .save(&path)
+        .expect("write sprite");
+    }
+
+    /// Tiles (two layers, with and without sprites), placeholders, a rotated sprite, a point light
+    /// and fog, spread across the whole view.
+    fn banded_raster_test_world() -> SceneWorld {
+        let mut world = SceneWorld::default();
+        let tiles = (0..14 * 12).map(|index| (index % 3) as u16).collect();
+        let mut tilemap = Tilemap::new(14, 12, Vec2 { x: -7.0, y: -6.0 }, tiles).expect("tilemap");
+        let overlay = tilemap.add_layer("overlay").expect("overlay");
+        tilemap.set_layer_tile(overlay, 3, 4, 1);
+        world.set_tilemap(tilemap);
+        world.set_floor_ambient(FloorId::MAIN, [150, 140, 170]);
+        world
+            .add_light(PointLight {
+                anchor: LightAnchor::World {
+                    floor: FloorId::MAIN,
+                    position: Vec2 { x: 1.0, y: 1.0 },
+                },
+                offset: Vec2 { x: 0.0, y: 0.0 },
+                radius: 3.0,
+                rgb: [255, 220, 160],
+                intensity: 1.0,
+            })
+            .expect("light");
+        for index in 0..24 {
+            let kind = if index % 4 == 0 {
+                RenderableKind::Sprite {
+                    key: "raster/pawn".to_string(),
+                    pixel_scale: 2,
+                    anchors: SpriteAnchors::default(),
+                    mirror_west: false,
+                }
+            } else {
+                RenderableKind::Placeholder
+            };
+            let id = world.spawn(
+                Transform {
+                    position: Vec2 {
+                        x: -3.0 + (index % 6) as f32 * 1.3,
+                        y: -2.5 + (index / 6) as f32 * 1.4,
+                    },
+                    rotation_radians: (index % 8 == 0).then_some(0.6),
+                },
+                crate::app::RenderableDesc {
+                    kind,
+                    debug_name: "raster",
+                },
+            );
+            if index == 7 {
+                world
+                    .set_entity_sight(
+                        id,
+                        EntitySight {
+                            faction: FactionId(0),
+                            radius: 3.0,
+                        },
+                    )
+                    .expect("sight");
+            }
+        }
+        world.set_fog_viewer(Some(FactionId(0)));
+        world.apply_pending();
+        world.update_visibility();
+        world
+    }
+
+    fn rasterize(rasterizer: &mut WorldRasterizer, world: &SceneWorld) -> Vec<u8> {
+        let (width, height) = (200u32, 180u32);
+        let mut frame = vec![0u8; (width * height * 4) as usize];
+        rasterizer.draw(&mut frame, width, height, world, 30, &BitmapFont::builtin());
+        frame
+    }
+
+    #[test]
+    fn banded_rasterization_matches_a_single_band() {
+        let temp = TempDir::new().expect("temp");
+        write_test_sprite(temp.path(), "tile/grass", 32, 32);
+        write_test_sprite(temp.path(), "raster/pawn", 7, 9);
+        let world = banded_raster_test_world();
+        assert_eq!(band_count_for(180, 6), 5);
+
+        let single = rasterize(&mut WorldRasterizer::new(temp.path().into(), 1), &world);
+        let banded = rasterize(&mut WorldRasterizer::new(temp.path().into(), 6), &world);
+        assert!(single == banded, "banded output differs from one band");
+        assert!(single.chunks_exact(4).any(|px| px == FOG_UNEXPLORED_COLOR));
+    }
+
+    #[test]
+    fn cached_background_is_reused_until_the_camera_moves_and_matches_a_fresh_draw() {
+        let temp = TempDir::new().expect("temp");
+        write_test_sprite(temp.path(), "tile/grass", 32, 32);
+        let mut world = banded_raster_test_world();
+        let mut rasterizer = WorldRasterizer::new(temp.path().into(), 4);
+        rasterize(&mut rasterizer, &world);
+        let first_key = rasterizer.background_cache.key.clone();
+
+        let mover = world.entities()[3].id;
+        world
+            .find_entity_mut(mover)
+            .expect("mover")
+            .transform
+            .position = Vec2 { x: 2.0, y: 2.0 };
+        let reused = rasterize(&mut rasterizer, &world);
+        assert_eq!(rasterizer.background_cache.key, first_key);
+        let fresh = rasterize(&mut WorldRasterizer::new(temp.path().into(), 1), &world);
+        assert!(
+            reused == fresh,
+            "re-blitted background differs from a fresh draw"
+        );
+
+        world.camera_mut().position = Vec2 { x: 0.75, y: -0.5 };
+        let panned = rasterize(&mut rasterizer, &world);
+        assert_ne!(rasterizer.background_cache.key, first_key);
+        let fresh = rasterize(&mut WorldRasterizer::new(temp.path().into(), 1), &world);
+        assert!(
+            panned == fresh,
+            "redrawn background differs from a fresh draw"
+        );
+
+        assert!(world.set_tile(0, 7, 6, 2));
+        let edited = rasterize(&mut rasterizer, &world);
+        let fresh = rasterize(&mut WorldRasterizer::new(temp.path().into(), 1), &world);
+        assert!(edited == fresh, "tile edits must redraw the background");
+    }
 }
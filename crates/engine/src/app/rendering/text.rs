@@ -0,0 +1,1142 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::ImageReader;
+use thiserror::Error;
+use tracing::warn;
+
+/// Font tools panels and world-space labels draw with; falls back to the built-in face.
+pub(crate) const UI_FONT_NAME: &str = "ui";
+/// Line height tools panels lay out for; fonts are scaled by the largest integer that fits.
+pub(crate) const UI_LINE_HEIGHT_PX: i32 = 21;
+
+const BUILTIN_GLYPH_WIDTH: u32 = 3;
+const BUILTIN_GLYPH_HEIGHT: u32 = 5;
+const BUILTIN_GLYPH_ADVANCE: i32 = 4;
+const BUILTIN_LINE_HEIGHT: i32 = 7;
+const BUILTIN_FIRST_CHAR: char = ' ';
+const BUILTIN_LAST_CHAR: char = '~';
+
+#[derive(Debug, Error)]
+pub(crate) enum FontError {
+    #[error("read font {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("parse font {path} line {line}: {message}")]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    #[error("load font page {path}: {message}")]
+    Page { path: PathBuf, message: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct FontGlyph {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    x_offset: i32,
+    y_offset: i32,
+    x_advance: i32,
+    page: usize,
+}
+
+/// One atlas page as per-pixel coverage (alpha times brightness).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FontPage {
+    width: u32,
+    height: u32,
+    coverage: Vec<u8>,
+}
+
+/// Bitmap font in BMFont terms: glyph rects on atlas pages keyed by Unicode scalar, a line
+/// height, the baseline distance from the line top, and pair kerning.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BitmapFont {
+    line_height: i32,
+    base: i32,
+    glyphs: HashMap<char, FontGlyph>,
+    kerning: HashMap<(char, char), i32>,
+    pages: Vec<FontPage>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// How `draw_text` lays out a string. With `max_width`, lines wrap at word boundaries and
+/// align inside `[x, x + max_width)`; without it `x` is the left edge, centre or right edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TextStyle {
+    pub color: [u8; 4],
+    pub scale: i32,
+    pub align: TextAlign,
+    pub max_width: Option<i32>,
+    /// Drawn for characters the font lacks; `None` leaves a space-sized gap.
+    pub fallback: Option<char>,
+}
+
+impl TextStyle {
+    pub(crate) fn new(color: [u8; 4], scale: i32) -> Self {
+        Self {
+            color,
+            scale,
+            align: TextAlign::Left,
+            max_width: None,
+            fallback: Some('?'),
+        }
+    }
+
+    pub(crate) fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub(crate) fn with_max_width(mut self, max_width: i32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub(crate) fn with_fallback(mut self, fallback: Option<char>) -> Self {
+        self.fallback = fallback;
+        self
+    }
+}
+
+impl BitmapFont {
+    /// The engine's 3x5 ASCII face (advance 4, line height 7).
+    pub(crate) fn builtin() -> Self {
+        let glyph_count = BUILTIN_LAST_CHAR as u32 - BUILTIN_FIRST_CHAR as u32 + 1;
+        let page_width = glyph_count * BUILTIN_GLYPH_WIDTH;
+        let mut coverage = vec![0u8; (page_width * BUILTIN_GLYPH_HEIGHT) as usize];
+        let mut glyphs = HashMap::with_capacity(glyph_count as usize);
+        for (index, ch) in (BUILTIN_FIRST_CHAR..=BUILTIN_LAST_CHAR).enumerate() {
+            let left = index as u32 * BUILTIN_GLYPH_WIDTH;
+            let rows = builtin_ascii_glyph(ch).rows;
+            for (row, bits) in rows.iter().enumerate() {
+                for col in 0..BUILTIN_GLYPH_WIDTH {
+                    if bits & (1 << (BUILTIN_GLYPH_WIDTH - 1 - col)) != 0 {
+                        coverage[row * page_width as usize + (left + col) as usize] = 255;
+                    }
+                }
+            }
+            glyphs.insert(
+                ch,
+                FontGlyph {
+                    x: left,
+                    y: 0,
+                    width: BUILTIN_GLYPH_WIDTH,
+                    height: BUILTIN_GLYPH_HEIGHT,
+                    x_offset: 0,
+                    y_offset: 0,
+                    x_advance: BUILTIN_GLYPH_ADVANCE,
+                    page: 0,
+                },
+            );
+        }
+        Self {
+            line_height: BUILTIN_LINE_HEIGHT,
+            base: BUILTIN_GLYPH_HEIGHT as i32,
+            glyphs,
+            kerning: HashMap::new(),
+            pages: vec![FontPage {
+                width: page_width,
+                height: BUILTIN_GLYPH_HEIGHT,
+                coverage,
+            }],
+        }
+    }
+
+    /// Loads a BMFont text-format descriptor (`.fnt`) and its PNG pages, resolved relative to
+    /// the descriptor. Unknown tags and keys are ignored.
+    pub(crate) fn load_bmfont(path: &Path) -> Result<Self, FontError> {
+        let source = fs::read_to_string(path).map_err(|source| FontError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        parse_bmfont(path, &source, |file| load_font_page(&dir.join(file)))
+    }
+
+    pub(crate) fn line_height(&self) -> i32 {
+        self.line_height
+    }
+
+    #[cfg(test)]
+    pub(crate) fn has_glyph(&self, ch: char) -> bool {
+        self.glyphs.contains_key(&ch)
+    }
+
+    /// Largest integer scale whose line height fits `line_height_px`, at least 1.
+    pub(crate) fn scale_for_line_height(&self, line_height_px: i32) -> i32 {
+        (line_height_px / self.line_height.max(1)).max(1)
+    }
+
+    /// Pixel size of `text` laid out with `style`: the widest line's ink extent by the
+    /// height from the first line top to the last baseline.
+    pub(crate) fn measure(&self, text: &str, style: &TextStyle) -> (i32, i32) {
+        let scale = style.scale.max(1);
+        let lines = self.layout_lines(text, style);
+        let width = lines
+            .iter()
+            .map(|line| self.line_width(line, style.fallback))
+            .max()
+            .unwrap_or(0);
+        let height = (lines.len().max(1) as i32 - 1) * self.line_height + self.base;
+        (width * scale, height * scale)
+    }
+
+    /// Greedy word wrap at `max_width` pixels for `scale`; explicit newlines always break and
+    /// words wider than a line break between characters.
+    pub(crate) fn wrap(
+        &self,
+        text: &str,
+        scale: i32,
+        max_width: i32,
+        fallback: Option<char>,
+    ) -> Vec<String> {
+        let scale = scale.max(1);
+        let fits = |line: &str| self.line_width(line, fallback) * scale <= max_width;
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{line} {word}")
+                };
+                if fits(&candidate) {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                for ch in word.chars() {
+                    line.push(ch);
+                    if !fits(&line) && line.chars().count() > 1 {
+                        line.pop();
+                        lines.push(std::mem::take(&mut line));
+                        line.push(ch);
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn layout_lines(&self, text: &str, style: &TextStyle) -> Vec<String> {
+        match style.max_width {
+            Some(max_width) => self.wrap(text, style.scale, max_width, style.fallback),
+            None => text.split('\n').map(str::to_string).collect(),
+        }
+    }
+
+    fn resolve(&self, ch: char, fallback: Option<char>) -> Option<(char, &FontGlyph)> {
+        if let Some(glyph) = self.glyphs.get(&ch) {
+            return Some((ch, glyph));
+        }
+        fallback
+            .into_iter()
+            .chain([' '])
+            .find_map(|candidate| self.glyphs.get(&candidate).map(|glyph| (candidate, glyph)))
+    }
+
+    /// Unscaled pen positions and glyphs for one line, kerning applied between pairs.
+    fn line_glyphs(&self, line: &str, fallback: Option<char>) -> Vec<(i32, &FontGlyph)> {
+        let mut out = Vec::with_capacity(line.len());
+        let mut pen = 0;
+        let mut previous = None::<char>;
+        for ch in line.chars() {
+            let Some((resolved, glyph)) = self.resolve(ch, fallback) else {
+                continue;
+            };
+            if let Some(previous) = previous {
+                pen += self
+                    .kerning
+                    .get(&(previous, resolved))
+                    .copied()
+                    .unwrap_or(0);
+            }
+            out.push((pen, glyph));
+            pen += glyph.x_advance;
+            previous = Some(resolved);
+        }
+        out
+    }
+
+    fn line_width(&self, line: &str, fallback: Option<char>) -> i32 {
+        self.line_glyphs(line, fallback)
+            .last()
+            .map_or(0, |(pen, glyph)| pen + glyph.x_offset + glyph.width as i32)
+            .max(0)
+    }
+}
+
+/// Draws `text` with its first line top at `y`, clipped to the frame; see [`TextStyle`] for
+/// wrapping and alignment.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn draw_text(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    font: &BitmapFont,
+    x: i32,
+    y: i32,
+    text: &str,
+    style: &TextStyle,
+) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let scale = style.scale.max(1);
+    let lines = font.layout_lines(text, style);
+    for (index, line) in lines.iter().enumerate() {
+        let line_width = font.line_width(line, style.fallback) * scale;
+        let left = match (style.align, style.max_width) {
+            (TextAlign::Left, _) => x,
+            (TextAlign::Center, Some(max_width)) => x + (max_width - line_width) / 2,
+            (TextAlign::Center, None) => x - line_width / 2,
+            (TextAlign::Right, Some(max_width)) => x + max_width - line_width,
+            (TextAlign::Right, None) => x - line_width,
+        };
+        let top = y + index as i32 * font.line_height * scale;
+        for (pen, glyph) in font.line_glyphs(line, style.fallback) {
+            draw_glyph(
+                frame,
+                width,
+                height,
+                font,
+                glyph,
+                left + (pen + glyph.x_offset) * scale,
+                top + glyph.y_offset * scale,
+                style.color,
+                scale,
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_glyph(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    font: &BitmapFont,
+    glyph: &FontGlyph,
+    x: i32,
+    y: i32,
+    color: [u8; 4],
+    scale: i32,
+) {
+    let Some(page) = font.pages.get(glyph.page) else {
+        return;
+    };
+    for row in 0..glyph.height {
+        let top = y + row as i32 * scale;
+        if top + scale <= 0 || top >= height as i32 {
+            continue;
+        }
+        let source_row = ((glyph.y + row) * page.width) as usize;
+        for col in 0..glyph.width {
+            let coverage = page.coverage[source_row + (glyph.x + col) as usize];
+            if coverage == 0 {
+                continue;
+            }
+            let left = x + col as i32 * scale;
+            for py in top.max(0)..(top + scale).min(height as i32) {
+                for px in left.max(0)..(left + scale).min(width as i32) {
+                    let offset = (py as usize * width as usize + px as usize) * 4;
+                    let Some(dst) = frame.get_mut(offset..offset + 4) else {
+                        continue;
+                    };
+                    blend_text_pixel(dst, color, coverage);
+                }
+            }
+        }
+    }
+}
+
+fn blend_text_pixel(dst: &mut [u8], color: [u8; 4], coverage: u8) {
+    let alpha = u32::from(color[3]) * u32::from(coverage) / 255;
+    if alpha == 255 {
+        dst.copy_from_slice(&color);
+        return;
+    }
+    let inverse = 255 - alpha;
+    for channel in 0..3 {
+        dst[channel] =
+            ((u32::from(color[channel]) * alpha + u32::from(dst[channel]) * inverse + 127) / 255)
+                as u8;
+    }
+    dst[3] = (alpha + u32::from(dst[3]) * inverse / 255).min(255) as u8;
+}
+
+/// Named fonts loaded from `<asset_root>/base/fonts/*.fnt`, keyed by file stem.
+#[derive(Debug)]
+pub(crate) struct FontLibrary {
+    builtin: BitmapFont,
+    fonts: HashMap<String, BitmapFont>,
+}
+
+impl Default for FontLibrary {
+    fn default() -> Self {
+        Self {
+            builtin: BitmapFont::builtin(),
+            fonts: HashMap::new(),
+        }
+    }
+}
+
+impl FontLibrary {
+    /// Loads every font in the asset fonts directory; fonts that fail to load are skipped
+    /// with a warning so text still renders with the built-in face.
+    pub(crate) fn load(asset_root: &Path) -> Self {
+        let mut library = Self::default();
+        let dir = asset_root.join("base").join("fonts");
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return library;
+        };
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "fnt"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            match BitmapFont::load_bmfont(&path) {
+                Ok(font) => {
+                    library.fonts.insert(name.to_string(), font);
+                }
+                Err(error) => warn!(font = name, %error, "font_load_failed_using_builtin"),
+            }
+        }
+        library
+    }
+
+    pub(crate) fn font(&self, name: &str) -> &BitmapFont {
+        self.fonts.get(name).unwrap_or(&self.builtin)
+    }
+
+    pub(crate) fn ui(&self) -> &BitmapFont {
+        self.font(UI_FONT_NAME)
+    }
+}
+
+fn load_font_page(path: &Path) -> Result<FontPage, String> {
+    let image = ImageReader::open(path)
+        .map_err(|error| format!("file_open_failed:{error}"))?
+        .decode()
+        .map_err(|error| format!("decode_failed:{error}"))?
+        .to_rgba8();
+    let coverage = image
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            (u16::from(a) * u16::from(r.max(g).max(b)) / 255) as u8
+        })
+        .collect();
+    Ok(FontPage {
+        width: image.width(),
+        height: image.height(),
+        coverage,
+    })
+}
+
+fn parse_bmfont(
+    path: &Path,
+    source: &str,
+    mut load_page: impl FnMut(&str) -> Result<FontPage, String>,
+) -> Result<BitmapFont, FontError> {
+    let parse_error = |line: usize, message: String| FontError::Parse {
+        path: path.to_path_buf(),
+        line,
+        message,
+    };
+    let mut common = None::<(i32, i32)>;
+    let mut page_files = Vec::<(usize, String)>::new();
+    let mut glyphs = HashMap::new();
+    let mut kerning = HashMap::new();
+    for (index, raw_line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let mut tokens = bmfont_tokens(raw_line).into_iter();
+        let Some((tag, _)) = tokens.next() else {
+            continue;
+        };
+        let fields = tokens.collect::<HashMap<_, _>>();
+        let int = |key: &str| -> Result<i32, FontError> {
+            let value = fields
+                .get(key)
+                .ok_or_else(|| parse_error(line_number, format!("<{tag}> missing '{key}'")))?;
+            value.parse::<i32>().map_err(|_| {
+                parse_error(
+                    line_number,
+                    format!("<{tag}> '{key}' must be an integer, got '{value}'"),
+                )
+            })
+        };
+        let non_negative = |key: &str| -> Result<u32, FontError> {
+            u32::try_from(int(key)?)
+                .map_err(|_| parse_error(line_number, format!("<{tag}> '{key}' must be >= 0")))
+        };
+        let scalar = |key: &str| -> Result<char, FontError> {
+            non_negative(key).and_then(|code| {
+                char::from_u32(code).ok_or_else(|| {
+                    parse_error(
+                        line_number,
+                        format!("<{tag}> '{key}' {code} is not a character"),
+                    )
+                })
+            })
+        };
+        match tag.as_str() {
+            "common" => {
+                let line_height = int("lineHeight")?;
+                if line_height <= 0 {
+                    return Err(parse_error(
+                        line_number,
+                        "lineHeight must be > 0".to_string(),
+                    ));
+                }
+                let base = fields
+                    .contains_key("base")
+                    .then(|| int("base"))
+                    .transpose()?
+                    .unwrap_or(line_height);
+                common = Some((line_height, base));
+            }
+            "page" => {
+                let file = fields
+                    .get("file")
+                    .ok_or_else(|| parse_error(line_number, "<page> missing 'file'".to_string()))?;
+                page_files.push((non_negative("id")? as usize, file.clone()));
+            }
+            "char" => {
+                let glyph = FontGlyph {
+                    x: non_negative("x")?,
+                    y: non_negative("y")?,
+                    width: non_negative("width")?,
+                    height: non_negative("height")?,
+                    x_offset: int("xoffset")?,
+                    y_offset: int("yoffset")?,
+                    x_advance: int("xadvance")?,
+                    page: fields
+                        .contains_key("page")
+                        .then(|| non_negative("page"))
+                        .transpose()?
+                        .unwrap_or(0) as usize,
+                };
+                glyphs.insert(scalar("id")?, (line_number, glyph));
+            }
+            "kerning" => {
+                kerning.insert((scalar("first")?, scalar("second")?), int("amount")?);
+            }
+            _ => {}
+        }
+    }
+    let Some((line_height, base)) = common else {
+        return Err(parse_error(0, "missing <common> line".to_string()));
+    };
+    page_files.sort_by_key(|(id, _)| *id);
+    let mut pages = Vec::with_capacity(page_files.len());
+    for (expected, (id, file)) in page_files.iter().enumerate() {
+        if *id != expected {
+            return Err(parse_error(
+                0,
+                format!("page ids must run 0..n, found {id}"),
+            ));
+        }
+        let page = load_page(file).map_err(|message| FontError::Page {
+            path: path.parent().unwrap_or(Path::new("")).join(file),
+            message,
+        })?;
+        pages.push(page);
+    }
+    let glyphs = glyphs
+        .into_iter()
+        .map(|(ch, (line_number, glyph))| {
+            let in_bounds = pages.get(glyph.page).is_some_and(|page| {
+                glyph.x + glyph.width <= page.width && glyph.y + glyph.height <= page.height
+            });
+            if in_bounds {
+                Ok((ch, glyph))
+            } else {
+                Err(parse_error(
+                    line_number,
+                    format!("glyph {:?} lies outside page {}", ch, glyph.page),
+                ))
+            }
+        })
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(BitmapFont {
+        line_height,
+        base,
+        glyphs,
+        kerning,
+        pages,
+    })
+}
+
+/// Splits a BMFont line into its tag and `key=value` pairs; quoted values may hold spaces.
+fn bmfont_tokens(line: &str) -> Vec<(String, String)> {
+    let mut tokens = Vec::new();
+    let mut chars = line.trim().chars().peekable();
+    while chars.peek().is_some() {
+        while chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+        let key = std::iter::from_fn(|| chars.next_if(|ch| !ch.is_whitespace() && *ch != '='))
+            .collect::<String>();
+        let mut value = String::new();
+        if chars.next_if_eq(&'=').is_some() {
+            if chars.next_if_eq(&'"').is_some() {
+                value = std::iter::from_fn(|| chars.next_if(|ch| *ch != '"')).collect();
+                chars.next();
+            } else {
+                value = std::iter::from_fn(|| chars.next_if(|ch| !ch.is_whitespace())).collect();
+            }
+        }
+        if !key.is_empty() {
+            tokens.push((key, value));
+        }
+    }
+    tokens
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BuiltinGlyph {
+    rows: [u8; BUILTIN_GLYPH_HEIGHT as usize],
+}
+
+const BUILTIN_SPACE_GLYPH: BuiltinGlyph = BuiltinGlyph {
+    rows: [0, 0, 0, 0, 0],
+};
+
+fn builtin_ascii_glyph(ch: char) -> BuiltinGlyph {
+    match ch {
+        ' ' => BUILTIN_SPACE_GLYPH,
+        '!' => BuiltinGlyph {
+            rows: [0b010, 0b010, 0b010, 0b000, 0b010],
+        },
+        '"' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b000, 0b000, 0b000],
+        },
+        '#' => BuiltinGlyph {
+            rows: [0b101, 0b111, 0b101, 0b111, 0b101],
+        },
+        '$' => BuiltinGlyph {
+            rows: [0b111, 0b110, 0b111, 0b011, 0b111],
+        },
+        '%' => BuiltinGlyph {
+            rows: [0b101, 0b001, 0b010, 0b100, 0b101],
+        },
+        '&' => BuiltinGlyph {
+            rows: [0b010, 0b101, 0b010, 0b101, 0b011],
+        },
+        '\'' => BuiltinGlyph {
+            rows: [0b010, 0b010, 0b000, 0b000, 0b000],
+        },
+        '(' => BuiltinGlyph {
+            rows: [0b001, 0b010, 0b010, 0b010, 0b001],
+        },
+        ')' => BuiltinGlyph {
+            rows: [0b100, 0b010, 0b010, 0b010, 0b100],
+        },
+        '*' => BuiltinGlyph {
+            rows: [0b000, 0b101, 0b010, 0b101, 0b000],
+        },
+        '+' => BuiltinGlyph {
+            rows: [0b000, 0b010, 0b111, 0b010, 0b000],
+        },
+        ',' => BuiltinGlyph {
+            rows: [0b000, 0b000, 0b000, 0b010, 0b100],
+        },
+        '-' => BuiltinGlyph {
+            rows: [0b000, 0b000, 0b111, 0b000, 0b000],
+        },
+        '.' => BuiltinGlyph {
+            rows: [0b000, 0b000, 0b000, 0b000, 0b010],
+        },
+        '/' => BuiltinGlyph {
+            rows: [0b001, 0b001, 0b010, 0b100, 0b100],
+        },
+        '0' => BuiltinGlyph {
+            rows: [0b111, 0b101, 0b101, 0b101, 0b111],
+        },
+        '1' => BuiltinGlyph {
+            rows: [0b010, 0b110, 0b010, 0b010, 0b111],
+        },
+        '2' => BuiltinGlyph {
+            rows: [0b111, 0b001, 0b111, 0b100, 0b111],
+        },
+        '3' => BuiltinGlyph {
+            rows: [0b111, 0b001, 0b111, 0b001, 0b111],
+        },
+        '4' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b111, 0b001, 0b001],
+        },
+        '5' => BuiltinGlyph {
+            rows: [0b111, 0b100, 0b111, 0b001, 0b111],
+        },
+        '6' => BuiltinGlyph {
+            rows: [0b111, 0b100, 0b111, 0b101, 0b111],
+        },
+        '7' => BuiltinGlyph {
+            rows: [0b111, 0b001, 0b010, 0b010, 0b010],
+        },
+        '8' => BuiltinGlyph {
+            rows: [0b111, 0b101, 0b111, 0b101, 0b111],
+        },
+        '9' => BuiltinGlyph {
+            rows: [0b111, 0b101, 0b111, 0b001, 0b111],
+        },
+        ':' => BuiltinGlyph {
+            rows: [0b000, 0b010, 0b000, 0b010, 0b000],
+        },
+        ';' => BuiltinGlyph {
+            rows: [0b000, 0b010, 0b000, 0b010, 0b100],
+        },
+        '<' => BuiltinGlyph {
+            rows: [0b001, 0b010, 0b100, 0b010, 0b001],
+        },
+        '=' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b000, 0b111, 0b000],
+        },
+        '>' => BuiltinGlyph {
+            rows: [0b100, 0b010, 0b001, 0b010, 0b100],
+        },
+        '?' => BuiltinGlyph {
+            rows: [0b111, 0b001, 0b011, 0b000, 0b010],
+        },
+        '@' => BuiltinGlyph {
+            rows: [0b111, 0b101, 0b111, 0b100, 0b111],
+        },
+        'A' => BuiltinGlyph {
+            rows: [0b010, 0b101, 0b111, 0b101, 0b101],
+        },
+        'B' => BuiltinGlyph {
+            rows: [0b110, 0b101, 0b110, 0b101, 0b110],
+        },
+        'C' => BuiltinGlyph {
+            rows: [0b111, 0b100, 0b100, 0b100, 0b111],
+        },
+        'D' => BuiltinGlyph {
+            rows: [0b110, 0b101, 0b101, 0b101, 0b110],
+        },
+        'E' => BuiltinGlyph {
+            rows: [0b111, 0b100, 0b110, 0b100, 0b111],
+        },
+        'F' => BuiltinGlyph {
+            rows: [0b111, 0b100, 0b110, 0b100, 0b100],
+        },
+        'G' => BuiltinGlyph {
+            rows: [0b111, 0b100, 0b101, 0b101, 0b111],
+        },
+        'H' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b111, 0b101, 0b101],
+        },
+        'I' => BuiltinGlyph {
+            rows: [0b111, 0b010, 0b010, 0b010, 0b111],
+        },
+        'J' => BuiltinGlyph {
+            rows: [0b111, 0b001, 0b001, 0b101, 0b111],
+        },
+        'K' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b110, 0b101, 0b101],
+        },
+        'L' => BuiltinGlyph {
+            rows: [0b100, 0b100, 0b100, 0b100, 0b111],
+        },
+        'M' => BuiltinGlyph {
+            rows: [0b101, 0b111, 0b111, 0b101, 0b101],
+        },
+        'N' => BuiltinGlyph {
+            rows: [0b101, 0b111, 0b111, 0b111, 0b101],
+        },
+        'O' => BuiltinGlyph {
+            rows: [0b111, 0b101, 0b101, 0b101, 0b111],
+        },
+        'P' => BuiltinGlyph {
+            rows: [0b110, 0b101, 0b110, 0b100, 0b100],
+        },
+        'Q' => BuiltinGlyph {
+            rows: [0b111, 0b101, 0b101, 0b111, 0b001],
+        },
+        'R' => BuiltinGlyph {
+            rows: [0b110, 0b101, 0b110, 0b101, 0b101],
+        },
+        'S' => BuiltinGlyph {
+            rows: [0b111, 0b100, 0b111, 0b001, 0b111],
+        },
+        'T' => BuiltinGlyph {
+            rows: [0b111, 0b010, 0b010, 0b010, 0b010],
+        },
+        'U' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b101, 0b101, 0b111],
+        },
+        'V' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b101, 0b101, 0b010],
+        },
+        'W' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b111, 0b111, 0b101],
+        },
+        'X' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b010, 0b101, 0b101],
+        },
+        'Y' => BuiltinGlyph {
+            rows: [0b101, 0b101, 0b010, 0b010, 0b010],
+        },
+        'Z' => BuiltinGlyph {
+            rows: [0b111, 0b001, 0b010, 0b100, 0b111],
+        },
+        '[' => BuiltinGlyph {
+            rows: [0b110, 0b100, 0b100, 0b100, 0b110],
+        },
+        '\\' => BuiltinGlyph {
+            rows: [0b100, 0b100, 0b010, 0b001, 0b001],
+        },
+        ']' => BuiltinGlyph {
+            rows: [0b011, 0b001, 0b001, 0b001, 0b011],
+        },
+        '^' => BuiltinGlyph {
+            rows: [0b010, 0b101, 0b000, 0b000, 0b000],
+        },
+        '_' => BuiltinGlyph {
+            rows: [0b000, 0b000, 0b000, 0b000, 0b111],
+        },
+        '`' => BuiltinGlyph {
+            rows: [0b100, 0b010, 0b000, 0b000, 0b000],
+        },
+        'a' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b001, 0b111, 0b111],
+        },
+        'b' => BuiltinGlyph {
+            rows: [0b100, 0b100, 0b110, 0b101, 0b110],
+        },
+        'c' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b100, 0b100, 0b111],
+        },
+        'd' => BuiltinGlyph {
+            rows: [0b001, 0b001, 0b111, 0b101, 0b111],
+        },
+        'e' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b110, 0b100, 0b111],
+        },
+        'f' => BuiltinGlyph {
+            rows: [0b011, 0b100, 0b110, 0b100, 0b100],
+        },
+        'g' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b101, 0b111, 0b001],
+        },
+        'h' => BuiltinGlyph {
+            rows: [0b100, 0b100, 0b110, 0b101, 0b101],
+        },
+        'i' => BuiltinGlyph {
+            rows: [0b010, 0b000, 0b010, 0b010, 0b010],
+        },
+        'j' => BuiltinGlyph {
+            rows: [0b001, 0b000, 0b001, 0b101, 0b010],
+        },
+        'k' => BuiltinGlyph {
+            rows: [0b100, 0b101, 0b110, 0b101, 0b101],
+        },
+        'l' => BuiltinGlyph {
+            rows: [0b100, 0b100, 0b100, 0b100, 0b111],
+        },
+        'm' => BuiltinGlyph {
+            rows: [0b000, 0b110, 0b111, 0b101, 0b101],
+        },
+        'n' => BuiltinGlyph {
+            rows: [0b000, 0b110, 0b101, 0b101, 0b101],
+        },
+        'o' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b101, 0b101, 0b111],
+        },
+        'p' => BuiltinGlyph {
+            rows: [0b000, 0b110, 0b101, 0b110, 0b100],
+        },
+        'q' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b101, 0b111, 0b001],
+        },
+        'r' => BuiltinGlyph {
+            rows: [0b000, 0b110, 0b101, 0b100, 0b100],
+        },
+        's' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b110, 0b001, 0b111],
+        },
+        't' => BuiltinGlyph {
+            rows: [0b010, 0b111, 0b010, 0b010, 0b011],
+        },
+        'u' => BuiltinGlyph {
+            rows: [0b000, 0b101, 0b101, 0b101, 0b111],
+        },
+        'v' => BuiltinGlyph {
+            rows: [0b000, 0b101, 0b101, 0b101, 0b010],
+        },
+        'w' => BuiltinGlyph {
+            rows: [0b000, 0b101, 0b101, 0b111, 0b010],
+        },
+        'x' => BuiltinGlyph {
+            rows: [0b000, 0b101, 0b010, 0b010, 0b101],
+        },
+        'y' => BuiltinGlyph {
+            rows: [0b000, 0b101, 0b101, 0b111, 0b001],
+        },
+        'z' => BuiltinGlyph {
+            rows: [0b000, 0b111, 0b001, 0b010, 0b111],
+        },
+        '{' => BuiltinGlyph {
+            rows: [0b011, 0b010, 0b110, 0b010, 0b011],
+        },
+        '|' => BuiltinGlyph {
+            rows: [0b010, 0b010, 0b010, 0b010, 0b010],
+        },
+        '}' => BuiltinGlyph {
+            rows: [0b110, 0b010, 0b011, 0b010, 0b110],
+        },
+        '~' => BuiltinGlyph {
+            rows: [0b000, 0b011, 0b110, 0b000, 0b000],
+        },
+        _ => BUILTIN_SPACE_GLYPH,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::RgbaImage;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    fn lit_columns(frame: &[u8], width: usize) -> Vec<usize> {
+        let mut columns = frame
+            .chunks_exact(4)
+            .enumerate()
+            .filter(|(_, px)| px.iter().any(|byte| *byte != 0))
+            .map(|(index, _)| index % width)
+            .collect::<Vec<_>>();
+        columns.sort_unstable();
+        columns.dedup();
+        columns
+    }
+
+    /// Writes a 4x2 white atlas holding `A` (left half) and `é` (right half) with a kerning
+    /// pair, plus the descriptor, and returns the descriptor path.
+    fn write_test_font(dir: &Path, name: &str) -> PathBuf {
+        let atlas = RgbaImage::from_raw(4, 2, [255u8; 4 * 4 * 2].to_vec()).expect("atlas");
+        atlas.save(dir.join(format!("{name}.png"))).expect("png");
+        let descriptor = dir.join(format!("{name}.fnt"));
+        fs::write(
+            &descriptor,
+            format!(
+                "info face=\"Pixel Test\" size=3\n\
+                 common lineHeight=3 base=2 scaleW=4 scaleH=2 pages=1\n\
+                 page id=0 file=\"{name}.png\"\n\
+                 chars count=2\n\
+                 char id=65 x=0 y=0 width=2 height=2 xoffset=0 yoffset=0 xadvance=3 page=0\n\
+                 char id=233 x=2 y=0 width=2 height=2 xoffset=0 yoffset=1 xadvance=3 page=0\n\
+                 kerning first=65 second=233 amount=-1\n"
+            ),
+        )
+        .expect("fnt");
+        descriptor
+    }
+
+    #[test]
+    fn builtin_font_covers_the_ascii_printable_range_only() {
+        let font = BitmapFont::builtin();
+        for code in 32u8..=126u8 {
+            let ch = char::from(code);
+            assert!(
+                font.has_glyph(ch),
+                "missing glyph for ASCII code {code} ('{ch}')"
+            );
+        }
+        assert!(!font.has_glyph('\u{7f}'));
+        assert!(!font.has_glyph('é'));
+    }
+
+    #[test]
+    fn builtin_metrics_keep_the_legacy_tool_layout() {
+        let font = BitmapFont::builtin();
+        let scale = font.scale_for_line_height(UI_LINE_HEIGHT_PX);
+        assert_eq!(scale, 3);
+        assert_eq!(font.line_height() * scale, 21);
+        let style = TextStyle::new(WHITE, scale);
+        assert_eq!(font.measure("ab", &style), (21, 15));
+        assert_eq!(font.measure("", &style), (0, 15));
+        assert_eq!(font.measure("ab\nc", &TextStyle::new(WHITE, 1)), (7, 12));
+    }
+
+    #[test]
+    fn missing_characters_draw_the_fallback_or_nothing() {
+        let font = BitmapFont::builtin();
+        let mut frame = vec![0u8; 16 * 16 * 4];
+        let no_fallback = TextStyle::new(WHITE, 1).with_fallback(None);
+        draw_text(&mut frame, 16, 16, &font, 0, 0, "\u{1f642}", &no_fallback);
+        assert!(frame.iter().all(|byte| *byte == 0));
+
+        draw_text(
+            &mut frame,
+            16,
+            16,
+            &font,
+            0,
+            0,
+            "\u{1f642}",
+            &TextStyle::new(WHITE, 1),
+        );
+        assert!(frame.iter().any(|byte| *byte != 0));
+    }
+
+    #[test]
+    fn clipped_draws_never_write_out_of_bounds() {
+        let font = BitmapFont::builtin();
+        let style = TextStyle::new(WHITE, 3);
+        let mut frame = vec![0u8; 8 * 8 * 4];
+        draw_text(&mut frame, 8, 8, &font, -2, -2, "FPS", &style);
+        assert_eq!(frame.len(), 8 * 8 * 4);
+
+        let mut beyond = vec![0u8; 8 * 8 * 4];
+        draw_text(&mut beyond, 8, 8, &font, 64, 64, "TPS", &style);
+        assert!(beyond.iter().all(|byte| *byte == 0));
+
+        draw_text(&mut [0u8; 4], 1, 1, &font, -10, -10, "Frame", &style);
+        draw_text(&mut [], 0, 8, &font, 0, 0, "Entities", &style);
+        draw_text(&mut [], 8, 0, &font, 0, 0, "Content", &style);
+    }
+
+    #[test]
+    fn wrap_breaks_at_words_and_splits_words_wider_than_a_line() {
+        let font = BitmapFont::builtin();
+        assert_eq!(
+            font.wrap("aaa bb cccccccc", 1, 15, None),
+            vec!["aaa", "bb", "cccc", "cccc"]
+        );
+        assert_eq!(font.wrap("ab cd\nef", 1, 100, None), vec!["ab cd", "ef"]);
+        let style = TextStyle::new(WHITE, 1).with_max_width(15);
+        assert_eq!(font.measure("aaa bb cccccccc", &style), (15, 26));
+    }
+
+    #[test]
+    fn alignment_positions_lines_inside_the_box_or_around_the_anchor() {
+        let font = BitmapFont::builtin();
+        let draw = |x: i32, style: TextStyle| {
+            let mut frame = vec![0u8; 32 * 8 * 4];
+            draw_text(&mut frame, 32, 8, &font, x, 0, "H", &style);
+            lit_columns(&frame, 32)
+        };
+        let boxed = TextStyle::new(WHITE, 1).with_max_width(20);
+        assert_eq!(draw(0, boxed), vec![0, 1, 2]);
+        assert_eq!(draw(0, boxed.with_align(TextAlign::Center)), vec![8, 9, 10]);
+        assert_eq!(
+            draw(0, boxed.with_align(TextAlign::Right)),
+            vec![17, 18, 19]
+        );
+        let anchored = TextStyle::new(WHITE, 1);
+        assert_eq!(
+            draw(10, anchored.with_align(TextAlign::Center)),
+            vec![9, 10, 11]
+        );
+        assert_eq!(
+            draw(10, anchored.with_align(TextAlign::Right)),
+            vec![7, 8, 9]
+        );
+    }
+
+    #[test]
+    fn bmfont_loads_utf8_glyphs_offsets_and_kerning() {
+        let temp = TempDir::new().expect("temp");
+        let font = BitmapFont::load_bmfont(&write_test_font(temp.path(), "pixel")).expect("load");
+        assert!(font.has_glyph('é'));
+        assert_eq!(font.line_height(), 3);
+        let style = TextStyle::new(WHITE, 1);
+        assert_eq!(font.measure("Aé", &style), (4, 2));
+        assert_eq!(font.measure("éA", &style), (5, 2));
+
+        let mut frame = vec![0u8; 4 * 4 * 4];
+        draw_text(&mut frame, 4, 4, &font, 0, 0, "é", &style);
+        let lit_rows = frame
+            .chunks_exact(4 * 4)
+            .map(|row| row.iter().any(|byte| *byte != 0))
+            .collect::<Vec<_>>();
+        assert_eq!(lit_rows, vec![false, true, true, false]);
+    }
+
+    #[test]
+    fn bmfont_errors_name_the_problem() {
+        let temp = TempDir::new().expect("temp");
+        let path = temp.path().join("broken.fnt");
+        let parse = |source: &str| {
+            parse_bmfont(&path, source, |_| {
+                Ok(FontPage {
+                    width: 2,
+                    height: 2,
+                    coverage: vec![255; 4],
+                })
+            })
+        };
+        let missing_common = parse("page id=0 file=\"a.png\"").expect_err("common");
+        assert!(missing_common.to_string().contains("missing <common>"));
+        let outside = parse(
+            "common lineHeight=2 base=2\npage id=0 file=\"a.png\"\nchar id=65 x=1 y=0 width=2 height=2 xoffset=0 yoffset=0 xadvance=2",
+        )
+        .expect_err("outside");
+        assert!(
+            matches!(outside, FontError::Parse { line: 3, .. }),
+            "{outside}"
+        );
+        let bad_number = parse("common lineHeight=tall").expect_err("number");
+        assert!(bad_number.to_string().contains("lineHeight"));
+
+        fs::write(
+            &path,
+            "common lineHeight=2\npage id=0 file=\"missing.png\"\n",
+        )
+        .expect("fnt");
+        let missing_page = BitmapFont::load_bmfont(&path).expect_err("page");
+        assert!(matches!(missing_page, FontError::Page { .. }));
+    }
+
+    #[test]
+    fn library_loads_asset_fonts_by_stem_and_falls_back_to_builtin() {
+        let temp = TempDir::new().expect("temp");
+        assert_eq!(FontLibrary::load(temp.path()).ui(), &BitmapFont::builtin());
+
+        let fonts_dir = temp.path().join("base").join("fonts");
+        fs::create_dir_all(&fonts_dir).expect("fonts dir");
+        write_test_font(&fonts_dir, UI_FONT_NAME);
+        fs::write(fonts_dir.join("broken.fnt"), "info face=broken\n").expect("broken");
+        let library = FontLibrary::load(temp.path());
+        assert!(library.ui().has_glyph('é'));
+        assert_eq!(library.font("broken"), &BitmapFont::builtin());
+    }
+
+    #[test]
+    fn bmfont_tokens_keep_quoted_values_with_spaces() {
+        assert_eq!(
+            bmfont_tokens("info face=\"Pixel Sans\" size=12 bold"),
+            vec![
+                ("info".to_string(), String::new()),
+                ("face".to_string(), "Pixel Sans".to_string()),
+                ("size".to_string(), "12".to_string()),
+                ("bold".to_string(), String::new()),
+            ]
+        );
+    }
+}
@@ -33,9 +33,10 @@ pub fn screen_to_world_px(camera: &Camera2D, window_size: (u32, u32), screen_px:
     let half_width = window_size.0 as f32 * 0.5;
     let half_height = window_size.1 as f32 * 0.5;
     let pixels_per_world = camera_pixels_per_world(camera);
+    let center = camera.view_center();
     Vec2 {
-        x: camera.position.x + (screen_px.x - half_width) / pixels_per_world,
-        y: camera.position.y - (screen_px.y - half_height) / pixels_per_world,
+        x: center.x + (screen_px.x - half_width) / pixels_per_world,
+        y: center.y - (screen_px.y - half_height) / pixels_per_world,
     }
 }
 
@@ -45,8 +46,9 @@ pub fn world_to_screen(
     viewport: Viewport,
     pixels_per_world: f32,
 ) -> (i32, i32) {
-    let x = (world.x - camera.position.x) * pixels_per_world + viewport.width as f32 * 0.5;
-    let y = viewport.height as f32 * 0.5 - (world.y - camera.position.y) * pixels_per_world;
+    let center = camera.view_center();
+    let x = (world.x - center.x) * pixels_per_world + viewport.width as f32 * 0.5;
+    let y = viewport.height as f32 * 0.5 - (world.y - center.y) * pixels_per_world;
     (x.round() as i32, y.round() as i32)
 }
 
@@ -117,6 +119,7 @@ mod tests {
             let camera = Camera2D {
                 position: Vec2 { x: 3.0, y: 1.0 },
                 zoom,
+                ..Camera2D::default()
             };
             let input = Vec2 { x: -1.75, y: 6.25 };
             let (sx, sy) = world_to_screen_px(&camera, (1280, 720), input);
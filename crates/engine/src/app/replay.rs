@@ -0,0 +1,534 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::input::ActionStates;
+use super::loop_runner::scene_key_token;
+use super::{DebugCommand, InputSnapshot, SceneKey, Vec2};
+
+pub const RECORD_REPLAY_ENV_VAR: &str = "PROTOGE_RECORD_REPLAY";
+pub const REPLAY_ENV_VAR: &str = "PROTOGE_REPLAY";
+pub const REPLAY_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub(crate) enum ReplayError {
+    #[error("replay file {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("encode replay json: {0}")]
+    Encode(#[source] serde_json::Error),
+    #[error("invalid replay file {path} at line {line}: {message}")]
+    Invalid {
+        path: String,
+        line: usize,
+        message: String,
+    },
+    #[error("unsupported replay version: expected {expected}, got {actual}")]
+    UnsupportedVersion { expected: u32, actual: u32 },
+}
+
+/// First line of a replay file: what the recording assumed about the run it came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReplayHeader {
+    pub(crate) version: u32,
+    pub(crate) content_hash: String,
+    pub(crate) seed: u64,
+    pub(crate) scene: String,
+    pub(crate) target_tps: u32,
+}
+
+impl ReplayHeader {
+    pub(crate) fn new(content_hash: &str, seed: u64, scene: SceneKey, target_tps: u32) -> Self {
+        Self {
+            version: REPLAY_FILE_VERSION,
+            content_hash: content_hash.to_string(),
+            seed,
+            scene: scene_key_token(scene).to_string(),
+            target_tps,
+        }
+    }
+
+    pub(crate) fn scene_key(&self) -> Option<SceneKey> {
+        match self.scene.as_str() {
+            "a" => Some(SceneKey::A),
+            "b" => Some(SceneKey::B),
+            _ => None,
+        }
+    }
+}
+
+/// One fixed tick: the console commands executed right before it, the input it consumed and
+/// the world checksum right after it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReplayTick {
+    pub(crate) tick: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "ReplayInput::is_idle")]
+    pub(crate) input: ReplayInput,
+    pub(crate) checksum: u64,
+}
+
+/// `InputSnapshot` as stored on disk. Actions are the raw `ActionStates` bits, so custom game
+/// actions replay by id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReplayInput {
+    #[serde(default, skip_serializing_if = "is_zero")]
+    actions: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cursor: Option<[f32; 2]>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    quit: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    switch_scene: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    left_pressed: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    left_held: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    left_released: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    right_pressed: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    save: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    load: bool,
+    #[serde(default, skip_serializing_if = "is_zero_i32")]
+    zoom: i32,
+    #[serde(default)]
+    window: [u32; 2],
+}
+
+impl ReplayInput {
+    pub(crate) fn from_snapshot(snapshot: &InputSnapshot) -> Self {
+        let (width, height) = snapshot.window_size();
+        Self {
+            actions: snapshot.actions().bits(),
+            cursor: snapshot
+                .cursor_position_px()
+                .map(|cursor| [cursor.x, cursor.y]),
+            quit: snapshot.quit_requested(),
+            switch_scene: snapshot.switch_scene_pressed(),
+            left_pressed: snapshot.left_click_pressed(),
+            left_held: snapshot.left_mouse_held(),
+            left_released: snapshot.left_click_released(),
+            right_pressed: snapshot.right_click_pressed(),
+            save: snapshot.save_pressed(),
+            load: snapshot.load_pressed(),
+            zoom: snapshot.zoom_delta_steps(),
+            window: [width, height],
+        }
+    }
+
+    pub(crate) fn to_snapshot(self) -> InputSnapshot {
+        InputSnapshot::new(
+            self.quit,
+            self.switch_scene,
+            ActionStates::from_bits(self.actions),
+            self.cursor.map(|[x, y]| Vec2 { x, y }),
+            self.left_pressed,
+            self.left_held,
+            self.left_released,
+            self.right_pressed,
+            self.save,
+            self.load,
+            self.zoom,
+            self.window[0],
+            self.window[1],
+        )
+    }
+
+    fn is_idle(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Writes a replay file tick by tick. Commands noted between two ticks are attached to the
+/// next recorded tick, which is where they take effect.
+pub(crate) struct ReplayRecorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    next_tick: u64,
+    pending_commands: Vec<String>,
+}
+
+impl ReplayRecorder {
+    pub(crate) fn create(path: &Path, header: &ReplayHeader) -> Result<Self, ReplayError> {
+        let file = File::create(path).map_err(|source| io_error(path, source))?;
+        let mut recorder = Self {
+            path: path.to_path_buf(),
+            writer: BufWriter::new(file),
+            next_tick: 0,
+            pending_commands: Vec::new(),
+        };
+        recorder.write_line(header)?;
+        Ok(recorder)
+    }
+
+    /// Keeps the commands that change simulation state. `cursor_world` pins cursor-relative
+    /// spawns to the position they actually used.
+    pub(crate) fn note_commands(&mut self, commands: &[DebugCommand], cursor_world: Option<Vec2>) {
+        self.pending_commands.extend(
+            commands
+                .iter()
+                .filter_map(|command| replay_command_line(command, cursor_world)),
+        );
+    }
+
+    pub(crate) fn record_tick(
+        &mut self,
+        input: &InputSnapshot,
+        checksum: u64,
+    ) -> Result<(), ReplayError> {
+        let tick = ReplayTick {
+            tick: self.next_tick,
+            commands: std::mem::take(&mut self.pending_commands),
+            input: ReplayInput::from_snapshot(input),
+            checksum,
+        };
+        self.write_line(&tick)?;
+        self.next_tick += 1;
+        Ok(())
+    }
+
+    pub(crate) fn ticks_recorded(&self) -> u64 {
+        self.next_tick
+    }
+
+    pub(crate) fn flush(&mut self) -> Result<(), ReplayError> {
+        self.writer
+            .flush()
+            .map_err(|source| io_error(&self.path, source))
+    }
+
+    fn write_line<T: Serialize>(&mut self, value: &T) -> Result<(), ReplayError> {
+        serde_json::to_writer(&mut self.writer, value).map_err(ReplayError::Encode)?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|source| io_error(&self.path, source))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ReplayDivergence {
+    pub(crate) tick: u64,
+    pub(crate) expected: u64,
+    pub(crate) actual: u64,
+}
+
+/// A fully loaded replay file handed out one tick at a time.
+#[derive(Debug)]
+pub(crate) struct ReplayPlayer {
+    header: ReplayHeader,
+    ticks: Vec<ReplayTick>,
+    next_index: usize,
+    diverged: bool,
+}
+
+impl ReplayPlayer {
+    pub(crate) fn open(path: &Path) -> Result<Self, ReplayError> {
+        let file = File::open(path).map_err(|source| io_error(path, source))?;
+        Self::from_reader(path, BufReader::new(file))
+    }
+
+    fn from_reader(path: &Path, reader: impl BufRead) -> Result<Self, ReplayError> {
+        let invalid = |line: usize, message: String| ReplayError::Invalid {
+            path: path.display().to_string(),
+            line,
+            message,
+        };
+        let mut header = None;
+        let mut ticks = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.map_err(|source| io_error(path, source))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if header.is_none() {
+                let parsed: ReplayHeader = serde_json::from_str(&line)
+                    .map_err(|error| invalid(line_number, error.to_string()))?;
+                if parsed.version != REPLAY_FILE_VERSION {
+                    return Err(ReplayError::UnsupportedVersion {
+                        expected: REPLAY_FILE_VERSION,
+                        actual: parsed.version,
+                    });
+                }
+                header = Some(parsed);
+                continue;
+            }
+            let tick: ReplayTick = serde_json::from_str(&line)
+                .map_err(|error| invalid(line_number, error.to_string()))?;
+            if tick.tick != ticks.len() as u64 {
+                return Err(invalid(
+                    line_number,
+                    format!("expected tick {}, got {}", ticks.len(), tick.tick),
+                ));
+            }
+            ticks.push(tick);
+        }
+        let header = header.ok_or_else(|| invalid(1, "missing header line".to_string()))?;
+        Ok(Self {
+            header,
+            ticks,
+            next_index: 0,
+            diverged: false,
+        })
+    }
+
+    pub(crate) fn header(&self) -> &ReplayHeader {
+        &self.header
+    }
+
+    pub(crate) fn tick_count(&self) -> u64 {
+        self.ticks.len() as u64
+    }
+
+    pub(crate) fn next_tick(&mut self) -> Option<&ReplayTick> {
+        let tick = self.ticks.get(self.next_index)?;
+        self.next_index += 1;
+        Some(tick)
+    }
+
+    /// Compares the checksum after the tick last returned by `next_tick`. Only the first
+    /// divergence is reported; everything after it is expected to differ too.
+    pub(crate) fn verify(&mut self, actual: u64) -> Option<ReplayDivergence> {
+        if self.diverged {
+            return None;
+        }
+        let tick = self.ticks.get(self.next_index.checked_sub(1)?)?;
+        if tick.checksum == actual {
+            return None;
+        }
+        self.diverged = true;
+        Some(ReplayDivergence {
+            tick: tick.tick,
+            expected: tick.checksum,
+            actual,
+        })
+    }
+}
+
+/// Canonical console line for a command that changes simulation state, or `None` for
+/// commands that only inspect, step or talk to tooling. Input injection and rebinding are
+/// left out because their effect is already captured in the recorded input.
+pub(crate) fn replay_command_line(
+    command: &DebugCommand,
+    cursor_world: Option<Vec2>,
+) -> Option<String> {
+    let line = match command {
+        DebugCommand::ResetScene => "reset_scene".to_string(),
+        DebugCommand::ScenarioSetup { scenario_id } => {
+            format!("scenario.setup {}", quote_arg(scenario_id))
+        }
+        DebugCommand::FloorSet { floor } => format!("floor.set {}", quote_arg(floor)),
+        DebugCommand::SwitchScene { scene } => format!("switch_scene {}", scene_key_token(*scene)),
+        DebugCommand::Spawn { def_name, position } => {
+            match position.or(cursor_world.map(|cursor| (cursor.x, cursor.y))) {
+                Some((x, y)) => format!("spawn {} {x} {y}", quote_arg(def_name)),
+                None => format!("spawn {}", quote_arg(def_name)),
+            }
+        }
+        DebugCommand::Despawn { entity_id } => format!("despawn {entity_id}"),
+        DebugCommand::Select { entity_id } => format!("select {entity_id}"),
+        DebugCommand::OrderMove { x, y, floor } => match floor {
+            Some(floor) => format!("order.move {x} {y} {}", quote_arg(floor)),
+            None => format!("order.move {x} {y}"),
+        },
+        DebugCommand::OrderInteract { target_entity_id } => {
+            format!("order.interact {target_entity_id}")
+        }
+        DebugCommand::Quit
+        | DebugCommand::Sync
+        | DebugCommand::ThruportStatus
+        | DebugCommand::ThruportTelemetry { .. }
+        | DebugCommand::PauseSim
+        | DebugCommand::ResumeSim
+        | DebugCommand::Tick { .. }
+        | DebugCommand::DumpState
+        | DebugCommand::DumpAi
+        | DebugCommand::TimersList
+        | DebugCommand::StateHash
+        | DebugCommand::SimSpeed { .. }
+        | DebugCommand::TickUntil { .. }
+        | DebugCommand::Rewind { .. }
+        | DebugCommand::ProfileCapture { .. }
+        | DebugCommand::MetricsSummary
+        | DebugCommand::MetricsExport { .. }
+        | DebugCommand::CvarSet { .. }
+        | DebugCommand::CvarGet { .. }
+        | DebugCommand::CvarList { .. }
+        | DebugCommand::CvarReset { .. }
+        | DebugCommand::InjectInput { .. }
+        | DebugCommand::InputBind { .. }
+        | DebugCommand::InputBindings { .. } => return None,
+    };
+    Some(line)
+}
+
+fn quote_arg(value: &str) -> String {
+    if value.is_empty() || value.chars().any(char::is_whitespace) {
+        format!("\"{value}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> ReplayError {
+    ReplayError::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn is_zero_i32(value: &i32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ConsoleCommandProcessor, InputAction};
+
+    fn sample_input() -> InputSnapshot {
+        InputSnapshot::empty()
+            .with_action_down(InputAction::MoveUp, true)
+            .with_action_down(InputAction::Custom(3), true)
+            .with_cursor_position_px(Some(Vec2 { x: 10.5, y: -2.25 }))
+            .with_left_click_pressed(true)
+            .with_zoom_delta_steps(-2)
+            .with_window_size((1280, 720))
+    }
+
+    #[test]
+    fn replay_input_round_trips_every_snapshot_field() {
+        let snapshot = sample_input();
+        let stored = ReplayInput::from_snapshot(&snapshot);
+        let json = serde_json::to_string(&stored).expect("encode");
+        let decoded: ReplayInput = serde_json::from_str(&json).expect("decode");
+        assert_eq!(decoded, stored);
+        assert_eq!(ReplayInput::from_snapshot(&decoded.to_snapshot()), stored);
+        assert_eq!(stored.actions, snapshot.actions().bits());
+    }
+
+    #[test]
+    fn recorded_file_plays_back_commands_input_and_checksums() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("run.replay.jsonl");
+        let header = ReplayHeader::new("abc123", 7, SceneKey::B, 60);
+        let mut recorder = ReplayRecorder::create(&path, &header).expect("create");
+        recorder.note_commands(
+            &[
+                DebugCommand::DumpState,
+                DebugCommand::Spawn {
+                    def_name: "proto.player".to_string(),
+                    position: None,
+                },
+            ],
+            Some(Vec2 { x: 1.5, y: -0.1 }),
+        );
+        recorder.record_tick(&sample_input(), 11).expect("tick 0");
+        recorder
+            .record_tick(&InputSnapshot::empty(), 22)
+            .expect("tick 1");
+        assert_eq!(recorder.ticks_recorded(), 2);
+        recorder.flush().expect("flush");
+
+        let mut player = ReplayPlayer::open(&path).expect("open");
+        assert_eq!(player.header(), &header);
+        assert_eq!(player.header().scene_key(), Some(SceneKey::B));
+        assert_eq!(player.tick_count(), 2);
+
+        let first = player.next_tick().expect("tick 0").clone();
+        assert_eq!(first.commands, vec!["spawn proto.player 1.5 -0.1"]);
+        assert_eq!(first.input, ReplayInput::from_snapshot(&sample_input()));
+        assert_eq!(player.verify(11), None);
+
+        let second = player.next_tick().expect("tick 1").clone();
+        assert!(second.commands.is_empty());
+        assert_eq!(
+            player.verify(23),
+            Some(ReplayDivergence {
+                tick: 1,
+                expected: 22,
+                actual: 23,
+            })
+        );
+        assert_eq!(player.verify(24), None, "only the first divergence reports");
+        assert!(player.next_tick().is_none());
+    }
+
+    #[test]
+    fn replay_command_lines_parse_back_to_the_same_command() {
+        let processor = ConsoleCommandProcessor::new();
+        let commands = [
+            DebugCommand::ResetScene,
+            DebugCommand::ScenarioSetup {
+                scenario_id: "visual_sandbox".to_string(),
+            },
+            DebugCommand::FloorSet {
+                floor: "upper deck".to_string(),
+            },
+            DebugCommand::SwitchScene { scene: SceneKey::A },
+            DebugCommand::Spawn {
+                def_name: "proto.npc_dummy".to_string(),
+                position: Some((0.1, -3.75)),
+            },
+            DebugCommand::Despawn { entity_id: 4 },
+            DebugCommand::Select { entity_id: 9 },
+            DebugCommand::OrderMove {
+                x: 1.0 / 3.0,
+                y: 2.5,
+                floor: Some("basement".to_string()),
+            },
+            DebugCommand::OrderInteract {
+                target_entity_id: 12,
+            },
+        ];
+        for command in commands {
+            let line = replay_command_line(&command, None).expect("recorded command");
+            assert_eq!(
+                processor.parse_debug_command_line(&line),
+                Ok(command),
+                "{line}"
+            );
+        }
+        assert_eq!(replay_command_line(&DebugCommand::PauseSim, None), None);
+    }
+
+    #[test]
+    fn player_rejects_gaps_and_other_versions() {
+        let path = Path::new("bad.jsonl");
+        let gap =
+            "{\"version\":1,\"content_hash\":\"\",\"seed\":0,\"scene\":\"a\",\"target_tps\":60}\n\
+                   {\"tick\":1,\"checksum\":0}\n";
+        assert!(matches!(
+            ReplayPlayer::from_reader(path, gap.as_bytes()),
+            Err(ReplayError::Invalid { line: 2, .. })
+        ));
+
+        let future =
+            "{\"version\":2,\"content_hash\":\"\",\"seed\":0,\"scene\":\"a\",\"target_tps\":60}\n";
+        assert!(matches!(
+            ReplayPlayer::from_reader(path, future.as_bytes()),
+            Err(ReplayError::UnsupportedVersion {
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::VecDeque;
+
+use thiserror::Error;
+
+use super::replay::{replay_command_line, ReplayInput};
+use super::scene::{SceneMachine, SceneRewindSnapshot};
+use super::{DebugCommand, InputSnapshot, Vec2};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum RewindError {
+    #[error("rewind is disabled (rewind_interval_ticks or rewind_snapshot_count is 0)")]
+    Disabled,
+    #[error("not available while a replay is playing or recording")]
+    ReplayActive,
+    #[error("only {available} ticks of history")]
+    NotEnoughHistory { available: u64 },
+}
+
+/// Active-scene state at a tick boundary: after tick `tick - 1`, before the commands and
+/// update of tick `tick`.
+struct RewindSnapshot {
+    tick: u64,
+    scene: SceneRewindSnapshot,
+}
+
+/// What tick `tick` consumed: the scene-changing commands run before it and its input.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RewindFrame {
+    pub(crate) tick: u64,
+    pub(crate) commands: Vec<String>,
+    pub(crate) input: ReplayInput,
+}
+
+/// Frames to run again after `RewindHistory::rewind` restored the snapshot at `from_tick`;
+/// the sim ends up at `target_tick`.
+#[derive(Debug)]
+pub(crate) struct RewindResim {
+    pub(crate) from_tick: u64,
+    pub(crate) target_tick: u64,
+    pub(crate) frames: Vec<RewindFrame>,
+}
+
+/// In-memory ring of active-scene snapshots taken every `interval_ticks`, plus every frame
+/// since the oldest one. Ticks count from loop start and keep counting across restarts.
+pub(crate) struct RewindHistory {
+    interval_ticks: u64,
+    capacity: usize,
+    next_tick: u64,
+    snapshots: VecDeque<RewindSnapshot>,
+    frames: VecDeque<RewindFrame>,
+    pending_commands: Vec<String>,
+}
+
+impl RewindHistory {
+    /// `None` when either setting is zero, which disables rewind.
+    pub(crate) fn new(interval_ticks: u32, capacity: usize, scenes: &SceneMachine) -> Option<Self> {
+        if interval_ticks == 0 || capacity == 0 {
+            return None;
+        }
+        let mut history = Self {
+            interval_ticks: u64::from(interval_ticks),
+            capacity,
+            next_tick: 0,
+            snapshots: VecDeque::with_capacity(capacity),
+            frames: VecDeque::new(),
+            pending_commands: Vec::new(),
+        };
+        history.restart(scenes);
+        Some(history)
+    }
+
+    /// Ticks `rewind` can currently reach back.
+    pub(crate) fn available_ticks(&self) -> u64 {
+        self.snapshots
+            .front()
+            .map_or(0, |oldest| self.next_tick - oldest.tick)
+    }
+
+    /// Drops all history and snapshots the current state. Called after the active scene was
+    /// switched or reset, since history never spans two scene lifetimes.
+    pub(crate) fn restart(&mut self, scenes: &SceneMachine) {
+        self.snapshots.clear();
+        self.frames.clear();
+        self.pending_commands.clear();
+        self.snapshots.push_back(RewindSnapshot {
+            tick: self.next_tick,
+            scene: scenes.capture_rewind_snapshot_active(),
+        });
+    }
+
+    /// Keeps the commands that change simulation state, the same ones replay files record.
+    pub(crate) fn note_commands(&mut self, commands: &[DebugCommand], cursor_world: Option<Vec2>) {
+        self.pending_commands.extend(
+            commands
+                .iter()
+                .filter_map(|command| replay_command_line(command, cursor_world)),
+        );
+    }
+
+    /// Records the tick that just ran and snapshots the scene on interval boundaries.
+    pub(crate) fn record_tick(&mut self, input: &InputSnapshot, scenes: &SceneMachine) {
+        self.frames.push_back(RewindFrame {
+            tick: self.next_tick,
+            commands: std::mem::take(&mut self.pending_commands),
+            input: ReplayInput::from_snapshot(input),
+        });
+        self.next_tick += 1;
+        if !self.next_tick.is_multiple_of(self.interval_ticks) {
+            return;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(RewindSnapshot {
+            tick: self.next_tick,
+            scene: scenes.capture_rewind_snapshot_active(),
+        });
+        let oldest = self.snapshots.front().map_or(0, |snapshot| snapshot.tick);
+        while self.frames.front().is_some_and(|frame| frame.tick < oldest) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Restores the newest snapshot at or before `ticks` ago and forgets everything after it.
+    /// The returned frames must be run again, through `note_commands` and `record_tick`, to
+    /// reach the target tick.
+    pub(crate) fn rewind(
+        &mut self,
+        ticks: u64,
+        scenes: &mut SceneMachine,
+    ) -> Result<RewindResim, RewindError> {
+        let not_enough = || RewindError::NotEnoughHistory {
+            available: self.available_ticks(),
+        };
+        let target_tick = self.next_tick.checked_sub(ticks).ok_or_else(not_enough)?;
+        let index = self
+            .snapshots
+            .iter()
+            .rposition(|snapshot| snapshot.tick <= target_tick)
+            .ok_or_else(not_enough)?;
+        self.snapshots.truncate(index + 1);
+        let snapshot = &self.snapshots[index];
+        let from_tick = snapshot.tick;
+        scenes.restore_rewind_snapshot(&snapshot.scene);
+
+        let keep = self
+            .frames
+            .iter()
+            .position(|frame| frame.tick >= from_tick)
+            .unwrap_or(self.frames.len());
+        let frames = self
+            .frames
+            .drain(keep..)
+            .take_while(|frame| frame.tick < target_tick)
+            .collect();
+        self.pending_commands.clear();
+        self.next_tick = from_tick;
+        Ok(RewindResim {
+            from_tick,
+            target_tick,
+            frames,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{InputAction, Scene, SceneCommand, SceneKey, SceneWorld};
+
+    /// Counts updates in scene-owned state and moves one entity right on every tick.
+    struct StepScene {
+        updates: u32,
+    }
+
+    impl Scene for StepScene {
+        fn load(&mut self, world: &mut SceneWorld) {
+            world.spawn(
+                crate::app::Transform::default(),
+                crate::app::RenderableDesc {
+                    kind: crate::app::RenderableKind::Placeholder,
+                    debug_name: "mover",
+                },
+            );
+        }
+
+        fn update(
+            &mut self,
+            _fixed_dt_seconds: f32,
+            input: &InputSnapshot,
+            world: &mut SceneWorld,
+        ) -> SceneCommand {
+            self.updates += 1;
+            let step = if input.is_down(InputAction::MoveRight) {
+                2.0
+            } else {
+                1.0
+            };
+            if let Some(entity) = world.entities_mut().first_mut() {
+                entity.transform.position.x += step;
+            }
+            SceneCommand::None
+        }
+
+        fn render(&mut self, _world: &SceneWorld) {}
+
+        fn unload(&mut self, _world: &mut SceneWorld) {}
+
+        fn capture_rewind_state(&self) -> Option<Box<dyn std::any::Any>> {
+            Some(Box::new(self.updates))
+        }
+
+        fn restore_rewind_state(&mut self, state: &dyn std::any::Any) {
+            if let Some(updates) = state.downcast_ref::<u32>() {
+                self.updates = *updates;
+            }
+        }
+
+        fn hash_state(&self, _world: &SceneWorld, hasher: &mut crate::app::StateHasher) {
+            hasher.write_u32(self.updates);
+        }
+    }
+
+    fn scenes() -> SceneMachine {
+        let mut scenes = SceneMachine::new(
+            Box::new(StepScene { updates: 0 }),
+            Box::new(StepScene { updates: 0 }),
+            SceneKey::A,
+        );
+        scenes.load_active();
+        scenes.apply_pending_active();
+        scenes
+    }
+
+    fn input_for(tick: u64) -> InputSnapshot {
+        if tick.is_multiple_of(3) {
+            InputSnapshot::empty().with_action_down(InputAction::MoveRight, true)
+        } else {
+            InputSnapshot::empty()
+        }
+    }
+
+    fn run_ticks(history: &mut RewindHistory, scenes: &mut SceneMachine, count: u64) -> Vec<u64> {
+        let mut hashes = Vec::new();
+        for _ in 0..count {
+            let input = input_for(history.next_tick);
+            let _ = scenes.update_active(1.0 / 60.0, &input);
+            scenes.apply_pending_active();
+            history.record_tick(&input, scenes);
+            hashes.push(scenes.state_hash_active());
+        }
+        hashes
+    }
+
+    #[test]
+    fn rewind_restores_a_snapshot_and_resimulates_to_the_same_state() {
+        let mut scenes = scenes();
+        let mut history = RewindHistory::new(4, 3, &scenes).expect("enabled");
+        let hashes = run_ticks(&mut history, &mut scenes, 10);
+
+        let resim = history.rewind(3, &mut scenes).expect("rewind");
+        assert_eq!((resim.from_tick, resim.target_tick), (4, 7));
+        assert_eq!(
+            resim
+                .frames
+                .iter()
+                .map(|frame| frame.tick)
+                .collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+        for frame in resim.frames {
+            let input = frame.input.to_snapshot();
+            let _ = scenes.update_active(1.0 / 60.0, &input);
+            scenes.apply_pending_active();
+            history.record_tick(&input, &scenes);
+        }
+        assert_eq!(history.next_tick, 7);
+        assert_eq!(scenes.state_hash_active(), hashes[6]);
+
+        let replayed = run_ticks(&mut history, &mut scenes, 3);
+        assert_eq!(replayed, hashes[7..10]);
+    }
+
+    #[test]
+    fn ring_drops_old_snapshots_and_bounds_rewind() {
+        let mut scenes = scenes();
+        assert!(RewindHistory::new(0, 3, &scenes).is_none());
+        let mut history = RewindHistory::new(4, 2, &scenes).expect("enabled");
+        run_ticks(&mut history, &mut scenes, 13);
+
+        assert_eq!(history.available_ticks(), 5);
+        assert_eq!(
+            history.rewind(6, &mut scenes).unwrap_err(),
+            RewindError::NotEnoughHistory { available: 5 }
+        );
+        assert_eq!(
+            history.rewind(14, &mut scenes).unwrap_err(),
+            RewindError::NotEnoughHistory { available: 5 }
+        );
+        let resim = history.rewind(5, &mut scenes).expect("rewind");
+        assert_eq!((resim.from_tick, resim.target_tick), (8, 8));
+        assert!(resim.frames.is_empty());
+    }
+}
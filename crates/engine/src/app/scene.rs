@@ -1,9 +1,60 @@
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 
+use super::cvars::CvarValue;
 use super::input::{ActionStates, InputAction};
 use super::rendering::{world_to_screen_px, PLACEHOLDER_HALF_SIZE_PX};
 use crate::content::DefDatabase;
-use thiserror::Error;
+
+mod camera;
+mod checksum;
+mod floors;
+mod lighting;
+mod minimap;
+mod particles;
+mod render_modifier;
+mod render_order;
+mod snapshot;
+mod tilemap;
+mod timers;
+mod visibility;
+mod widgets;
+
+pub use camera::CameraControllerConfig;
+pub use checksum::StateHasher;
+pub use floors::{
+    validate_floor_declaration, FloorError, FloorId, StairLink, LEGACY_FLOOR_NAMES, MAX_FLOORS,
+};
+
+pub use lighting::{
+    LightAnchor, LightError, LightId, PointLight, PointLightDef, ResolvedLight, AMBIENT_UNLIT,
+    MAX_POINT_LIGHTS,
+};
+pub use minimap::{MinimapMarker, MinimapMarkers, MinimapRole};
+pub use particles::{
+    Particle, ParticleEmitterDef, ParticleEmitterId, ParticleRange, MAX_PARTICLES,
+};
+pub use render_modifier::{PaletteSwapEntry, RenderModifier};
+pub use render_order::{RenderLayer, RenderOrderKey};
+pub use snapshot::{
+    SnapshotActionState, SnapshotActionTargetHint, SnapshotCamera, SnapshotDebugMarker,
+    SnapshotEntity, SnapshotEntityActionVisual, SnapshotEntityRemap, SnapshotEntityRenderModifier,
+    SnapshotEntitySight, SnapshotFacing, SnapshotFloor, SnapshotInteractable,
+    SnapshotInteractableKind, SnapshotLight, SnapshotLightAnchor, SnapshotOrderState,
+    SnapshotRenderLayer, SnapshotRenderableKind, SnapshotSpriteAnchors, SnapshotStairLink,
+    SnapshotTilemap, SnapshotTimer, SnapshotVec2, SnapshotVisibilityGrid, SnapshotVisualState,
+    WorldSnapshot, WorldSnapshotError, WORLD_SNAPSHOT_VERSION,
+};
+pub use tilemap::{
+    TileChannel, TileChunkCoord, TileFlags, TileRect, Tilemap, TilemapChange, TilemapError,
+    TILEMAP_CHANGE_LOG_CAPACITY, TILEMAP_CHUNK_SIZE, TILE_EMPTY, TILE_LAYER_TERRAIN,
+};
+pub use timers::{ScheduledTimer, TimerError, TimerFired, TimerId};
+pub use visibility::{EntitySight, FactionId, SightError, TileVisibility};
+pub use widgets::{
+    WidgetAnchor, WorldWidget, WorldWidgetKind, WorldWidgets, WORLD_WIDGET_LABEL_MAX_CHARS,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SceneKey {
@@ -33,12 +84,15 @@ pub enum SceneDebugCommand {
     OrderMove {
         x: f32,
         y: f32,
+        /// Floor name or index; `None` keeps the actor's current floor.
+        floor: Option<String>,
     },
     OrderInteract {
         target_entity_id: u64,
     },
+    /// Floor name or index, resolved by the scene against the world's declared floors.
     FloorSet {
-        floor: FloorId,
+        floor: String,
     },
     DumpState,
     DumpAi,
@@ -125,6 +179,10 @@ impl InputSnapshot {
         self.actions.is_down(action)
     }
 
+    pub(crate) fn actions(&self) -> ActionStates {
+        self.actions
+    }
+
     pub fn with_action_down(mut self, action: InputAction, is_down: bool) -> Self {
         self.actions.set(action, is_down);
         self
@@ -216,14 +274,6 @@ impl InputSnapshot {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EntityId(pub u64);
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
-pub enum FloorId {
-    Rooftop,
-    #[default]
-    Main,
-    Basement,
-}
-
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct Vec2 {
     pub x: f32,
@@ -336,6 +386,9 @@ pub const CAMERA_ZOOM_STEP: f32 = 0.1;
 pub struct Camera2D {
     pub position: Vec2,
     pub zoom: f32,
+    /// Screen-shake displacement added to `position` when projecting; written by
+    /// `SceneWorld::tick_camera` and never saved.
+    pub shake_offset: Vec2,
 }
 
 impl Default for Camera2D {
@@ -343,11 +396,20 @@ impl Default for Camera2D {
         Self {
             position: Vec2::default(),
             zoom: CAMERA_ZOOM_DEFAULT,
+            shake_offset: Vec2::default(),
         }
     }
 }
 
 impl Camera2D {
+    /// World point drawn at the center of the screen.
+    pub fn view_center(&self) -> Vec2 {
+        Vec2 {
+            x: self.position.x + self.shake_offset.x,
+            y: self.position.y + self.shake_offset.y,
+        }
+    }
+
     pub fn effective_zoom(&self) -> f32 {
         clamp_camera_zoom(self.zoom)
     }
@@ -387,76 +449,6 @@ impl Default for Transform {
     }
 }
 
-/// Tilemap origin convention:
-/// - `origin` is the world position of tile (0,0) bottom-left corner.
-/// - The center of tile (x,y) is `origin + (x + 0.5, y + 0.5)`.
-#[derive(Debug, Clone, PartialEq)]
-pub struct Tilemap {
-    width: u32,
-    height: u32,
-    origin: Vec2,
-    tiles: Vec<u16>,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
-pub enum TilemapError {
-    #[error("tile count mismatch: expected {expected}, got {actual}")]
-    TileCountMismatch { expected: usize, actual: usize },
-}
-
-impl Tilemap {
-    pub fn new(
-        width: u32,
-        height: u32,
-        origin: Vec2,
-        tiles: Vec<u16>,
-    ) -> Result<Self, TilemapError> {
-        let expected = width as usize * height as usize;
-        let actual = tiles.len();
-        if expected != actual {
-            return Err(TilemapError::TileCountMismatch { expected, actual });
-        }
-        Ok(Self {
-            width,
-            height,
-            origin,
-            tiles,
-        })
-    }
-
-    pub fn width(&self) -> u32 {
-        self.width
-    }
-
-    pub fn height(&self) -> u32 {
-        self.height
-    }
-
-    pub fn origin(&self) -> Vec2 {
-        self.origin
-    }
-
-    pub fn index_of(&self, x: u32, y: u32) -> Option<usize> {
-        if x >= self.width || y >= self.height {
-            return None;
-        }
-        Some(y as usize * self.width as usize + x as usize)
-    }
-
-    pub fn tile_at(&self, x: u32, y: u32) -> Option<u16> {
-        self.index_of(x, y)
-            .and_then(|index| self.tiles.get(index).copied())
-    }
-
-    pub fn tile_center_world(&self, x: u32, y: u32) -> Option<Vec2> {
-        self.index_of(x, y)?;
-        Some(Vec2 {
-            x: self.origin.x + x as f32 + 0.5,
-            y: self.origin.y + y as f32 + 0.5,
-        })
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RenderableKind {
     Placeholder,
@@ -464,6 +456,8 @@ pub enum RenderableKind {
         key: String,
         pixel_scale: u8,
         anchors: SpriteAnchors,
+        /// Draw west-facing variants as the east variant flipped horizontally.
+        mirror_west: bool,
     },
 }
 
@@ -546,6 +540,7 @@ pub struct SceneVisualState {
     pub hovered_interactable: Option<EntityId>,
     pub targeted_interactable: Option<EntityId>,
     pub entity_action_visuals: HashMap<EntityId, EntityActionVisual>,
+    pub entity_render_modifiers: HashMap<EntityId, RenderModifier>,
 }
 
 #[derive(Debug, Clone)]
@@ -558,16 +553,14 @@ pub struct Entity {
     pub actor: bool,
     pub order_state: OrderState,
     pub interactable: Option<Interactable>,
+    pub stair: Option<StairLink>,
+    pub render_layer: RenderLayer,
+    /// World-unit offset from `transform.position.y` to the y-sort pivot (typically the feet).
+    pub sort_pivot_y: f32,
     applied_spawn_order: u64,
 }
 
-impl Entity {
-    pub fn renderer_overlap_order_key(&self) -> u64 {
-        self.applied_spawn_order
-    }
-}
-
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct EntityIdAllocator {
     next: u64,
 }
@@ -580,7 +573,7 @@ impl EntityIdAllocator {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct SceneWorld {
     allocator: EntityIdAllocator,
     entities: Vec<Entity>,
@@ -588,12 +581,22 @@ pub struct SceneWorld {
     pending_despawns: Vec<EntityId>,
     next_applied_spawn_order: u64,
     camera: Camera2D,
+    camera_controller: camera::CameraController,
     active_floor: FloorId,
-    tilemap: Option<Tilemap>,
+    floors: floors::FloorTable,
     tilemap_epoch: u64,
+    tilemap_changes: VecDeque<TilemapChange>,
+    tilemap_changes_complete_after: u64,
     visual_state: SceneVisualState,
     debug_markers: Vec<DebugMarker>,
-    def_database: Option<DefDatabase>,
+    world_widgets: WorldWidgets,
+    minimap_markers: MinimapMarkers,
+    particles: particles::ParticleSystem,
+    lighting: lighting::LightingState,
+    visibility: visibility::VisibilityState,
+    timers: timers::TimerService,
+    /// Shared so world clones (rewind snapshots) do not copy content.
+    def_database: Option<Arc<DefDatabase>>,
 }
 
 impl SceneWorld {
@@ -630,6 +633,9 @@ impl SceneWorld {
             actor,
             order_state: OrderState::Idle,
             interactable: None,
+            stair: None,
+            render_layer: RenderLayer::default(),
+            sort_pivot_y: 0.0,
             applied_spawn_order: 0,
         });
         id
@@ -657,7 +663,11 @@ impl SceneWorld {
             });
             for id in pending {
                 self.visual_state.entity_action_visuals.remove(id);
+                self.visual_state.entity_render_modifiers.remove(id);
             }
+            self.timers.cancel_owned_by(pending);
+            self.lighting.remove_anchored_to(pending);
+            self.visibility.remove_entities(pending);
             self.pending_despawns.clear();
         }
 
@@ -676,23 +686,30 @@ impl SceneWorld {
         self.pending_despawns.clear();
         self.next_applied_spawn_order = 0;
         self.camera = Camera2D::default();
-        self.active_floor = FloorId::Main;
+        self.camera_controller.clear();
+        self.active_floor = self.floors.default_floor();
         self.visual_state = SceneVisualState::default();
         self.debug_markers.clear();
+        self.world_widgets.clear();
+        self.minimap_markers.clear();
+        self.particles.clear();
+        self.lighting.clear();
+        self.visibility.clear();
+        self.timers.clear();
     }
 
+    /// Installs `tilemap` on the active floor.
     pub fn set_tilemap(&mut self, tilemap: Tilemap) {
-        self.tilemap = Some(tilemap);
-        self.tilemap_epoch = self.tilemap_epoch.saturating_add(1);
+        self.set_floor_tilemap(self.active_floor, tilemap);
     }
 
     pub fn clear_tilemap(&mut self) {
-        self.tilemap = None;
-        self.tilemap_epoch = self.tilemap_epoch.saturating_add(1);
+        self.clear_floor_tilemap(self.active_floor);
     }
 
+    /// Tilemap of the active floor.
     pub fn tilemap(&self) -> Option<&Tilemap> {
-        self.tilemap.as_ref()
+        self.floors.tilemap(self.active_floor)
     }
 
     pub fn tilemap_epoch(&self) -> u64 {
@@ -821,10 +838,10 @@ impl SceneWorld {
     ) -> Option<EntityId> {
         let cursor_x = cursor_position_px.x.round() as i32;
         let cursor_y = cursor_position_px.y.round() as i32;
-        let mut best: Option<(u64, EntityId)> = None;
+        let mut best: Option<(RenderOrderKey, EntityId)> = None;
 
         for entity in &self.entities {
-            if !entity.selectable {
+            if !entity.selectable || self.entity_hidden_by_fog(entity) {
                 continue;
             }
             if let Some(required_floor) = floor_filter {
@@ -860,10 +877,10 @@ impl SceneWorld {
     ) -> Option<EntityId> {
         let cursor_x = cursor_position_px.x.round() as i32;
         let cursor_y = cursor_position_px.y.round() as i32;
-        let mut best: Option<(u64, EntityId)> = None;
+        let mut best: Option<(RenderOrderKey, EntityId)> = None;
 
         for entity in &self.entities {
-            if entity.interactable.is_none() {
+            if entity.interactable.is_none() || self.entity_hidden_by_fog(entity) {
                 continue;
             }
             if let Some(required_floor) = floor_filter {
@@ -892,11 +909,11 @@ impl SceneWorld {
     }
 
     pub fn set_def_database(&mut self, def_database: DefDatabase) {
-        self.def_database = Some(def_database);
+        self.def_database = Some(Arc::new(def_database));
     }
 
     pub fn def_database(&self) -> Option<&DefDatabase> {
-        self.def_database.as_ref()
+        self.def_database.as_deref()
     }
 }
 
@@ -909,6 +926,12 @@ pub trait Scene {
         world: &mut SceneWorld,
     ) -> SceneCommand;
     fn render(&mut self, world: &SceneWorld);
+    /// Fills the world-space widget list drawn this frame; called after `render` with an
+    /// emptied list.
+    fn submit_world_widgets(&mut self, _world: &SceneWorld, _widgets: &mut WorldWidgets) {}
+    /// Fills the entity dots shown on the minimap this frame; called after
+    /// `submit_world_widgets` with an emptied list.
+    fn submit_minimap_markers(&mut self, _world: &SceneWorld, _markers: &mut MinimapMarkers) {}
     fn unload(&mut self, world: &mut SceneWorld);
     fn debug_title(&self, _world: &SceneWorld) -> Option<String> {
         None
@@ -925,6 +948,25 @@ pub trait Scene {
     fn debug_info_snapshot(&self, _world: &SceneWorld) -> Option<DebugInfoSnapshot> {
         None
     }
+    /// Writes scene-owned simulation state into the per-tick state hash, after the world's
+    /// own state. Scenes without extra simulation state can keep the default.
+    fn hash_state(&self, _world: &SceneWorld, _hasher: &mut StateHasher) {}
+    /// Whether the named gameplay event fired during the last tick, for `tick.until`. `None`
+    /// means the scene does not know the event name.
+    fn debug_event_fired_last_tick(&self, _event: &str) -> Option<bool> {
+        None
+    }
+    /// Scene-owned simulation state for a rewind snapshot, taken right after the world is
+    /// cloned. Scenes keeping no simulation state outside the world can return `None`.
+    fn capture_rewind_state(&self) -> Option<Box<dyn Any>> {
+        None
+    }
+    /// Puts back state from `capture_rewind_state`; the world has already been restored.
+    fn restore_rewind_state(&mut self, _state: &dyn Any) {}
+    /// Called for every cvar at startup, after a rewind and whenever one changes. Engine cvars
+    /// arrive too; scenes only need to match the names they registered through
+    /// `LoopConfig::cvars`.
+    fn apply_cvar(&mut self, _name: &str, _value: &CvarValue) {}
     fn execute_debug_command(
         &mut self,
         _command: SceneDebugCommand,
@@ -941,6 +983,14 @@ struct SceneRuntime {
     is_loaded: bool,
 }
 
+/// A clone of one scene's world plus whatever the scene keeps through
+/// `Scene::capture_rewind_state`.
+pub(crate) struct SceneRewindSnapshot {
+    scene: SceneKey,
+    world: SceneWorld,
+    scene_state: Option<Box<dyn Any>>,
+}
+
 pub(crate) struct SceneMachine {
     scene_a: SceneRuntime,
     scene_b: SceneRuntime,
@@ -977,6 +1027,41 @@ impl SceneMachine {
         self.scene_b.world.set_def_database(def_database);
     }
 
+    pub(crate) fn apply_cvar_for_all(&mut self, name: &str, value: &CvarValue) {
+        self.scene_a.scene.apply_cvar(name, value);
+        self.scene_b.scene.apply_cvar(name, value);
+    }
+
+    pub(crate) fn state_hash_active(&self) -> u64 {
+        let runtime = self.runtime_ref(self.active_scene);
+        runtime.world.state_hash(runtime.scene.as_ref())
+    }
+
+    pub(crate) fn capture_rewind_snapshot_active(&self) -> SceneRewindSnapshot {
+        let runtime = self.active_runtime_ref();
+        SceneRewindSnapshot {
+            scene: self.active_scene,
+            world: runtime.world.clone(),
+            scene_state: runtime.scene.capture_rewind_state(),
+        }
+    }
+
+    /// Makes the snapshot's scene active again with a copy of its state; the snapshot stays
+    /// usable for later rewinds.
+    pub(crate) fn restore_rewind_snapshot(&mut self, snapshot: &SceneRewindSnapshot) {
+        self.active_scene = snapshot.scene;
+        let runtime = self.runtime_mut(snapshot.scene);
+        runtime.world = snapshot.world.clone();
+        if let Some(state) = snapshot.scene_state.as_deref() {
+            runtime.scene.restore_rewind_state(state);
+        }
+    }
+
+    pub(crate) fn set_particle_seed_for_all(&mut self, seed: u64) {
+        self.scene_a.world.set_particle_seed(seed);
+        self.scene_b.world.set_particle_seed(seed);
+    }
+
     pub(crate) fn load_active(&mut self) {
         if self.active_runtime_ref().is_loaded {
             return;
@@ -1004,6 +1089,22 @@ impl SceneMachine {
     pub(crate) fn render_active(&mut self) {
         let runtime = self.active_runtime_mut();
         runtime.scene.render(&runtime.world);
+        let mut widgets = std::mem::take(&mut runtime.world.world_widgets);
+        widgets.clear();
+        runtime
+            .scene
+            .submit_world_widgets(&runtime.world, &mut widgets);
+        runtime.world.world_widgets = widgets;
+        let mut markers = std::mem::take(&mut runtime.world.minimap_markers);
+        markers.clear();
+        runtime
+            .scene
+            .submit_minimap_markers(&runtime.world, &mut markers);
+        runtime.world.minimap_markers = markers;
+    }
+
+    pub(crate) fn recenter_active_camera(&mut self, position: Vec2) {
+        self.active_runtime_mut().world.recenter_camera(position);
     }
 
     pub(crate) fn active_world(&self) -> &SceneWorld {
@@ -1033,6 +1134,12 @@ impl SceneMachine {
         self.active_runtime_ref().scene.debug_resource_count()
     }
 
+    pub(crate) fn debug_event_fired_last_tick_active(&self, event: &str) -> Option<bool> {
+        self.active_runtime_ref()
+            .scene
+            .debug_event_fired_last_tick(event)
+    }
+
     pub(crate) fn debug_info_snapshot_active(&self) -> Option<DebugInfoSnapshot> {
         let runtime = self.active_runtime_ref();
         runtime.scene.debug_info_snapshot(&runtime.world)
@@ -1289,9 +1396,63 @@ mod tests {
 
         fn render(&mut self, _world: &SceneWorld) {}
 
+        fn submit_world_widgets(&mut self, world: &SceneWorld, widgets: &mut WorldWidgets) {
+            for entity in world.entities() {
+                widgets.push(WorldWidget::bar(
+                    WidgetAnchor::Entity(entity.id),
+                    entity.transform.position.x,
+                    [255; 4],
+                ));
+            }
+        }
+
+        fn submit_minimap_markers(&mut self, world: &SceneWorld, markers: &mut MinimapMarkers) {
+            if let Some(entity) = world.entities().first() {
+                markers.push(entity.id, MinimapRole::Player);
+            }
+        }
+
         fn unload(&mut self, _world: &mut SceneWorld) {}
     }
 
+    #[test]
+    fn render_active_rebuilds_world_widgets_and_minimap_markers_each_frame() {
+        let mut machine = SceneMachine::new(
+            Box::new(SteppingScene {
+                spawn_count: 2,
+                step_x: 0.25,
+            }),
+            Box::new(TestScene { spawn_count: 1 }),
+            SceneKey::A,
+        );
+        machine.load_active();
+        assert!(machine.active_world().world_widgets().is_empty());
+
+        machine.render_active();
+        machine.render_active();
+        assert_eq!(machine.active_world().world_widgets().len(), 2);
+
+        let _ = machine.update_active(1.0 / 60.0, &InputSnapshot::empty());
+        machine.render_active();
+        let widgets = machine.active_world().world_widgets();
+        assert_eq!(widgets.len(), 2);
+        assert!(matches!(
+            widgets[0].kind,
+            WorldWidgetKind::Bar { fraction, .. } if fraction == 0.25
+        ));
+
+        assert_eq!(machine.active_world().minimap_markers().len(), 1);
+        assert_eq!(
+            machine.active_world().minimap_markers()[0].role,
+            MinimapRole::Player
+        );
+
+        assert!(machine.switch_to(SceneKey::B));
+        machine.render_active();
+        assert!(machine.active_world().world_widgets().is_empty());
+        assert!(machine.active_world().minimap_markers().is_empty());
+    }
+
     #[test]
     fn allocator_never_reuses_ids() {
         let mut allocator = EntityIdAllocator::default();
@@ -1546,6 +1707,20 @@ mod tests {
         assert_eq!(tilemap.tile_at(1, 1), Some(7));
     }
 
+    #[test]
+    fn set_tilemap_stamps_every_chunk_with_new_epoch() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(make_tilemap(40, 20, Vec2 { x: 0.0, y: 0.0 }, 0));
+        let epoch = world.tilemap_epoch();
+        let tilemap = world.tilemap().expect("tilemap");
+
+        for y in 0..tilemap.chunks_y() {
+            for x in 0..tilemap.chunks_x() {
+                assert_eq!(tilemap.chunk_epoch(TileChunkCoord { x, y }), Some(epoch));
+            }
+        }
+    }
+
     #[test]
     fn clear_tilemap_explicitly_removes_tilemap() {
         let mut world = SceneWorld::default();
@@ -1768,6 +1943,43 @@ mod tests {
         assert_ne!(picked, Some(first));
     }
 
+    #[test]
+    fn pick_topmost_selectable_prefers_front_pivot_then_layer() {
+        let mut world = SceneWorld::default();
+        let front = world.spawn_selectable(
+            Transform {
+                position: Vec2 { x: 0.0, y: -0.05 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "front",
+            },
+        );
+        let back = world.spawn_selectable(
+            Transform {
+                position: Vec2 { x: 0.0, y: 0.05 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "back",
+            },
+        );
+        world.apply_pending();
+        let cursor = Vec2 { x: 640.0, y: 360.0 };
+        assert_eq!(
+            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), None),
+            Some(front)
+        );
+
+        world.set_entity_render_order(back, RenderLayer::Roof, 0.0);
+        assert_eq!(
+            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), None),
+            Some(back)
+        );
+    }
+
     #[test]
     fn pick_topmost_selectable_stable_after_unrelated_despawn() {
         let mut world = SceneWorld::default();
@@ -1870,6 +2082,7 @@ mod tests {
                     key: "ui/icons/worker_1".to_string(),
                     pixel_scale: 1,
                     anchors: SpriteAnchors::default(),
+                    mirror_west: false,
                 },
                 debug_name: "sprite_selectable",
             },
@@ -1913,14 +2126,14 @@ mod tests {
         world.apply_pending();
         assert_eq!(
             world.find_entity(entity_id).expect("entity").floor,
-            FloorId::Main
+            FloorId::MAIN
         );
     }
 
     #[test]
     fn spawn_uses_active_floor_for_new_entities() {
         let mut world = SceneWorld::default();
-        world.set_active_floor(FloorId::Basement);
+        world.set_active_floor(FloorId::BASEMENT);
         let entity_id = world.spawn(
             Transform::default(),
             RenderableDesc {
@@ -1931,14 +2144,14 @@ mod tests {
         world.apply_pending();
         assert_eq!(
             world.find_entity(entity_id).expect("entity").floor,
-            FloorId::Basement
+            FloorId::BASEMENT
         );
     }
 
     #[test]
     fn pick_topmost_selectable_optional_floor_filter_is_deterministic() {
         let mut world = SceneWorld::default();
-        world.set_active_floor(FloorId::Main);
+        world.set_active_floor(FloorId::MAIN);
         let main = world.spawn_selectable(
             Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -1949,7 +2162,7 @@ mod tests {
                 debug_name: "main_selectable",
             },
         );
-        world.set_active_floor(FloorId::Rooftop);
+        world.set_active_floor(FloorId::ROOFTOP);
         let rooftop = world.spawn_selectable(
             Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -1968,11 +2181,11 @@ mod tests {
             Some(rooftop)
         );
         assert_eq!(
-            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), Some(FloorId::Main)),
+            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), Some(FloorId::MAIN)),
             Some(main)
         );
         assert_eq!(
-            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), Some(FloorId::Basement)),
+            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), Some(FloorId::BASEMENT)),
             None
         );
     }
@@ -2008,6 +2221,76 @@ mod tests {
         assert_eq!(picked, Some(interactable_id));
     }
 
+    #[test]
+    fn picking_ignores_entities_hidden_by_the_viewer_fog() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(
+            Tilemap::new(8, 2, Vec2 { x: -4.0, y: -1.0 }, vec![0; 16]).expect("tilemap"),
+        );
+        let pile = world.spawn_selectable(
+            Transform {
+                position: Vec2 { x: 0.0, y: 0.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "pile",
+            },
+        );
+        let scout = world.spawn(
+            Transform {
+                position: Vec2 { x: -3.5, y: 0.5 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "scout",
+            },
+        );
+        world
+            .set_entity_sight(
+                scout,
+                EntitySight {
+                    faction: FactionId(0),
+                    radius: 2.0,
+                },
+            )
+            .expect("sight");
+        world.apply_pending();
+        world.find_entity_mut(pile).expect("exists").interactable = Some(Interactable {
+            kind: InteractableKind::ResourcePile,
+            interaction_radius: 0.75,
+            remaining_uses: 3,
+        });
+        world.update_visibility();
+        world.set_fog_viewer(Some(FactionId(0)));
+        let cursor = Vec2 { x: 640.0, y: 360.0 };
+
+        assert_eq!(
+            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), None),
+            None
+        );
+        assert_eq!(
+            world.pick_topmost_interactable_at_cursor(cursor, (1280, 720), None),
+            None
+        );
+
+        world
+            .find_entity_mut(scout)
+            .expect("scout")
+            .transform
+            .position = Vec2 { x: -0.5, y: 0.5 };
+        world.update_visibility();
+        assert_eq!(
+            world.pick_topmost_selectable_at_cursor(cursor, (1280, 720), None),
+            Some(pile)
+        );
+        assert_eq!(
+            world.pick_topmost_interactable_at_cursor(cursor, (1280, 720), None),
+            Some(pile)
+        );
+    }
+
     #[test]
     fn pick_topmost_interactable_uses_last_applied_spawn_order() {
         let mut world = SceneWorld::default();
@@ -2064,6 +2347,7 @@ mod tests {
                     key: "objects/resource_pile".to_string(),
                     pixel_scale: 1,
                     anchors: SpriteAnchors::default(),
+                    mirror_west: false,
                 },
                 debug_name: "sprite_interactable",
             },
@@ -2089,7 +2373,7 @@ mod tests {
     #[test]
     fn pick_topmost_interactable_optional_floor_filter_is_deterministic() {
         let mut world = SceneWorld::default();
-        world.set_active_floor(FloorId::Main);
+        world.set_active_floor(FloorId::MAIN);
         let main = world.spawn(
             Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -2100,7 +2384,7 @@ mod tests {
                 debug_name: "main_interactable",
             },
         );
-        world.set_active_floor(FloorId::Basement);
+        world.set_active_floor(FloorId::BASEMENT);
         let basement = world.spawn(
             Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -2132,11 +2416,11 @@ mod tests {
             Some(basement)
         );
         assert_eq!(
-            world.pick_topmost_interactable_at_cursor(cursor, (1280, 720), Some(FloorId::Main)),
+            world.pick_topmost_interactable_at_cursor(cursor, (1280, 720), Some(FloorId::MAIN)),
             Some(main)
         );
         assert_eq!(
-            world.pick_topmost_interactable_at_cursor(cursor, (1280, 720), Some(FloorId::Rooftop)),
+            world.pick_topmost_interactable_at_cursor(cursor, (1280, 720), Some(FloorId::ROOFTOP)),
             None
         );
     }
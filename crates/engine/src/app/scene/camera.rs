@@ -0,0 +1,392 @@
+use super::{EntityId, SceneWorld, Vec2, CAMERA_ZOOM_STEP};
+use crate::app::{screen_to_world_px, PIXELS_PER_WORLD};
+
+/// Trauma is kept in `[0, CAMERA_TRAUMA_MAX]`; shake amplitude scales with its square.
+const CAMERA_TRAUMA_MAX: f32 = 1.0;
+const CONTINUOUS_ZOOM_SNAP_EPSILON: f32 = 1e-3;
+const SHAKE_SEED_X: u64 = 0x5348_414b_455f_0058;
+const SHAKE_SEED_Y: u64 = 0x5348_414b_455f_0059;
+
+/// Tuning for `SceneWorld::tick_camera`. Rates are exponential per second, so the result only
+/// depends on the sequence of tick lengths.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraControllerConfig {
+    /// Follow rate toward the followed entity; zero or less snaps onto it each tick.
+    pub follow_sharpness: f32,
+    /// When set, zoom steps move a target that the zoom eases toward at `zoom_sharpness`
+    /// instead of applying at once.
+    pub continuous_zoom: bool,
+    pub zoom_sharpness: f32,
+    /// Keeps the view inside the active floor's tilemap, centered on axes where the map is
+    /// smaller than the view.
+    pub clamp_to_bounds: bool,
+    /// Shake offset in world units at full trauma.
+    pub shake_max_offset: f32,
+    /// Shake noise samples per second; the offset is interpolated between samples.
+    pub shake_frequency_hz: f32,
+    pub trauma_decay_per_second: f32,
+}
+
+impl Default for CameraControllerConfig {
+    fn default() -> Self {
+        Self {
+            follow_sharpness: 8.0,
+            continuous_zoom: false,
+            zoom_sharpness: 12.0,
+            clamp_to_bounds: false,
+            shake_max_offset: 0.4,
+            shake_frequency_hz: 18.0,
+            trauma_decay_per_second: 1.2,
+        }
+    }
+}
+
+/// Runtime controller state. Follow target, zoom target and trauma are presentation state and
+/// are not part of world snapshots.
+#[derive(Debug, Clone, Default)]
+pub(super) struct CameraController {
+    config: CameraControllerConfig,
+    follow: Option<EntityId>,
+    zoom_target: Option<f32>,
+    zoom_anchor: Option<ZoomAnchor>,
+    trauma: f32,
+    shake_seconds: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ZoomAnchor {
+    cursor_px: Vec2,
+    window_size: (u32, u32),
+}
+
+impl CameraController {
+    /// Drops runtime state but keeps the configuration.
+    pub(super) fn clear(&mut self) {
+        *self = Self {
+            config: self.config,
+            ..Self::default()
+        };
+    }
+}
+
+impl SceneWorld {
+    pub fn set_camera_controller_config(&mut self, config: CameraControllerConfig) {
+        self.camera_controller.config = config;
+    }
+
+    pub fn camera_controller_config(&self) -> CameraControllerConfig {
+        self.camera_controller.config
+    }
+
+    /// Entity the camera eases toward in `tick_camera`; cleared once the entity is gone.
+    pub fn set_camera_follow(&mut self, entity: Option<EntityId>) {
+        self.camera_controller.follow = entity;
+    }
+
+    pub fn camera_follow(&self) -> Option<EntityId> {
+        self.camera_controller.follow
+    }
+
+    /// Zooms by `steps` of `CAMERA_ZOOM_STEP` keeping the world point under `cursor_px` fixed on
+    /// screen; without a cursor the view center stays fixed. With continuous zoom this only
+    /// moves the target.
+    pub fn zoom_camera_at(&mut self, steps: i32, cursor_px: Option<Vec2>, window_size: (u32, u32)) {
+        if steps == 0 {
+            return;
+        }
+        let anchor = cursor_px.map(|cursor_px| ZoomAnchor {
+            cursor_px,
+            window_size,
+        });
+        if self.camera_controller.config.continuous_zoom {
+            let from = self
+                .camera_controller
+                .zoom_target
+                .unwrap_or(self.camera.effective_zoom());
+            let mut target = self.camera;
+            target.set_zoom_clamped(from + steps as f32 * CAMERA_ZOOM_STEP);
+            self.camera_controller.zoom_target = Some(target.zoom);
+            self.camera_controller.zoom_anchor = anchor;
+            return;
+        }
+        let zoom = self.camera.effective_zoom() + steps as f32 * CAMERA_ZOOM_STEP;
+        self.set_camera_zoom_anchored(zoom, anchor);
+    }
+
+    /// Adds screen-shake trauma, clamped to `CAMERA_TRAUMA_MAX`; negative or non-finite amounts
+    /// are ignored.
+    pub fn add_camera_trauma(&mut self, amount: f32) {
+        if !amount.is_finite() || amount <= 0.0 {
+            return;
+        }
+        let controller = &mut self.camera_controller;
+        controller.trauma = (controller.trauma + amount).min(CAMERA_TRAUMA_MAX);
+    }
+
+    pub fn camera_trauma(&self) -> f32 {
+        self.camera_controller.trauma
+    }
+
+    /// Advances follow, continuous zoom, bounds clamping and shake by one tick of
+    /// `fixed_dt_seconds` for a view of `window_size` pixels.
+    pub fn tick_camera(&mut self, fixed_dt_seconds: f32, window_size: (u32, u32)) {
+        let dt = if fixed_dt_seconds.is_finite() {
+            fixed_dt_seconds.max(0.0)
+        } else {
+            0.0
+        };
+        let config = self.camera_controller.config;
+
+        if let Some(follow) = self.camera_controller.follow {
+            match self.find_entity(follow) {
+                Some(entity) if entity.floor == self.active_floor => {
+                    let target = entity.transform.position;
+                    let alpha = smoothing_alpha(config.follow_sharpness, dt);
+                    let position = &mut self.camera.position;
+                    position.x += (target.x - position.x) * alpha;
+                    position.y += (target.y - position.y) * alpha;
+                }
+                Some(_) => {}
+                None => self.camera_controller.follow = None,
+            }
+        }
+
+        if let Some(target) = self.camera_controller.zoom_target {
+            let zoom = self.camera.effective_zoom();
+            let mut next = zoom + (target - zoom) * smoothing_alpha(config.zoom_sharpness, dt);
+            if (target - next).abs() <= CONTINUOUS_ZOOM_SNAP_EPSILON {
+                next = target;
+                self.camera_controller.zoom_target = None;
+            }
+            let anchor = self.camera_controller.zoom_anchor;
+            self.set_camera_zoom_anchored(next, anchor);
+        }
+
+        if config.clamp_to_bounds {
+            self.clamp_camera_to_tilemap(window_size);
+        }
+
+        let controller = &mut self.camera_controller;
+        controller.trauma = (controller.trauma - config.trauma_decay_per_second * dt).max(0.0);
+        if controller.trauma <= 0.0 {
+            controller.shake_seconds = 0.0;
+            self.camera.shake_offset = Vec2::default();
+            return;
+        }
+        controller.shake_seconds += dt;
+        let amplitude = config.shake_max_offset * controller.trauma * controller.trauma;
+        let t = controller.shake_seconds * config.shake_frequency_hz;
+        self.camera.shake_offset = Vec2 {
+            x: amplitude * shake_noise(SHAKE_SEED_X, t),
+            y: amplitude * shake_noise(SHAKE_SEED_Y, t),
+        };
+    }
+
+    fn set_camera_zoom_anchored(&mut self, zoom: f32, anchor: Option<ZoomAnchor>) {
+        let Some(anchor) = anchor else {
+            self.camera.set_zoom_clamped(zoom);
+            return;
+        };
+        let before = screen_to_world_px(&self.camera, anchor.window_size, anchor.cursor_px);
+        self.camera.set_zoom_clamped(zoom);
+        let after = screen_to_world_px(&self.camera, anchor.window_size, anchor.cursor_px);
+        self.camera.position.x += before.x - after.x;
+        self.camera.position.y += before.y - after.y;
+    }
+
+    fn clamp_camera_to_tilemap(&mut self, window_size: (u32, u32)) {
+        if window_size.0 == 0 || window_size.1 == 0 {
+            return;
+        }
+        let Some(tilemap) = self.tilemap() else {
+            return;
+        };
+        let origin = tilemap.origin();
+        let (map_w, map_h) = (tilemap.width() as f32, tilemap.height() as f32);
+        let pixels_per_world = PIXELS_PER_WORLD * self.camera.effective_zoom();
+        let half_w = window_size.0 as f32 / (2.0 * pixels_per_world);
+        let half_h = window_size.1 as f32 / (2.0 * pixels_per_world);
+        let position = &mut self.camera.position;
+        position.x = clamp_axis(position.x, origin.x, map_w, half_w);
+        position.y = clamp_axis(position.y, origin.y, map_h, half_h);
+    }
+}
+
+/// Center on an axis so `[center - half_view, center + half_view]` stays inside the map span.
+fn clamp_axis(center: f32, map_min: f32, map_len: f32, half_view: f32) -> f32 {
+    if map_len <= half_view * 2.0 {
+        return map_min + map_len * 0.5;
+    }
+    center.clamp(map_min + half_view, map_min + map_len - half_view)
+}
+
+/// Fraction of the remaining gap closed over `dt` at exponential `rate`.
+fn smoothing_alpha(rate: f32, dt: f32) -> f32 {
+    if !rate.is_finite() || rate <= 0.0 {
+        return 1.0;
+    }
+    1.0 - (-rate * dt).exp()
+}
+
+/// Smoothstep-interpolated value noise in `[-1, 1]` over lattice points at integer `t`.
+fn shake_noise(seed: u64, t: f32) -> f32 {
+    let cell = t.floor();
+    let frac = t - cell;
+    let a = lattice_value(seed, cell as i64);
+    let b = lattice_value(seed, cell as i64 + 1);
+    let s = frac * frac * (3.0 - 2.0 * frac);
+    a + (b - a) * s
+}
+
+/// splitmix64 of the lattice index mapped to `[-1, 1]`.
+fn lattice_value(seed: u64, index: i64) -> f32 {
+    let mut z = seed.wrapping_add((index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{FloorId, RenderableDesc, RenderableKind, Tilemap, Transform};
+    use super::*;
+
+    const WINDOW: (u32, u32) = (640, 480);
+    const DT: f32 = 1.0 / 60.0;
+
+    fn spawn_at(world: &mut SceneWorld, x: f32, y: f32) -> EntityId {
+        let id = world.spawn(
+            Transform {
+                position: Vec2 { x, y },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "camera_target",
+            },
+        );
+        world.apply_pending();
+        id
+    }
+
+    fn assert_close(actual: Vec2, expected: Vec2) {
+        assert!(
+            (actual.x - expected.x).abs() < 1e-4 && (actual.y - expected.y).abs() < 1e-4,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn follow_eases_toward_the_entity_and_drops_despawned_targets() {
+        let mut world = SceneWorld::default();
+        let id = spawn_at(&mut world, 10.0, -4.0);
+        world.set_camera_follow(Some(id));
+        world.tick_camera(DT, WINDOW);
+        let first = world.camera().position;
+        assert!(first.x > 0.0 && first.x < 10.0, "{first:?}");
+        for _ in 0..600 {
+            world.tick_camera(DT, WINDOW);
+        }
+        assert_close(world.camera().position, Vec2 { x: 10.0, y: -4.0 });
+
+        world.find_entity_mut(id).expect("entity").floor = FloorId(2);
+        world
+            .find_entity_mut(id)
+            .expect("entity")
+            .transform
+            .position
+            .x = 50.0;
+        world.tick_camera(DT, WINDOW);
+        assert_close(world.camera().position, Vec2 { x: 10.0, y: -4.0 });
+
+        world.despawn(id);
+        world.apply_pending();
+        world.tick_camera(DT, WINDOW);
+        assert_eq!(world.camera_follow(), None);
+    }
+
+    #[test]
+    fn zoom_at_cursor_keeps_the_world_point_under_the_cursor() {
+        let mut world = SceneWorld::default();
+        let cursor = Vec2 { x: 600.0, y: 40.0 };
+        let before = screen_to_world_px(world.camera(), WINDOW, cursor);
+        world.zoom_camera_at(5, Some(cursor), WINDOW);
+        assert!((world.camera().zoom - 1.5).abs() < 1e-5);
+        assert_close(screen_to_world_px(world.camera(), WINDOW, cursor), before);
+
+        world.zoom_camera_at(-3, None, WINDOW);
+        assert!((world.camera().zoom - 1.2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn continuous_zoom_eases_to_the_target_around_the_anchor() {
+        let mut world = SceneWorld::default();
+        world.set_camera_controller_config(CameraControllerConfig {
+            continuous_zoom: true,
+            ..CameraControllerConfig::default()
+        });
+        let cursor = Vec2 { x: 100.0, y: 400.0 };
+        let before = screen_to_world_px(world.camera(), WINDOW, cursor);
+        world.zoom_camera_at(4, Some(cursor), WINDOW);
+        assert_eq!(world.camera().zoom, 1.0);
+        world.tick_camera(DT, WINDOW);
+        let eased = world.camera().zoom;
+        assert!(eased > 1.0 && eased < 1.4, "{eased}");
+        for _ in 0..120 {
+            world.tick_camera(DT, WINDOW);
+        }
+        assert!((world.camera().zoom - 1.4).abs() < 1e-5);
+        assert_close(screen_to_world_px(world.camera(), WINDOW, cursor), before);
+    }
+
+    #[test]
+    fn bounds_clamp_keeps_the_view_inside_or_centers_small_maps() {
+        let mut world = SceneWorld::default();
+        world.set_camera_controller_config(CameraControllerConfig {
+            clamp_to_bounds: true,
+            ..CameraControllerConfig::default()
+        });
+        world.set_tilemap(
+            Tilemap::new(100, 10, Vec2 { x: 0.0, y: 0.0 }, vec![0; 1000]).expect("tilemap"),
+        );
+        world.camera_mut().position = Vec2 { x: -50.0, y: 3.0 };
+        world.tick_camera(DT, WINDOW);
+        // 640x480 at 32 px per tile is a 20x15 tile view; the map is only 10 tiles tall.
+        assert_close(world.camera().position, Vec2 { x: 10.0, y: 5.0 });
+        world.camera_mut().position.x = 500.0;
+        world.tick_camera(DT, WINDOW);
+        assert_close(world.camera().position, Vec2 { x: 90.0, y: 5.0 });
+    }
+
+    #[test]
+    fn shake_is_deterministic_decays_and_never_moves_the_camera_position() {
+        let run = || {
+            let mut world = SceneWorld::default();
+            world.add_camera_trauma(0.8);
+            world.add_camera_trauma(0.8);
+            assert_eq!(world.camera_trauma(), CAMERA_TRAUMA_MAX);
+            let mut offsets = Vec::new();
+            for _ in 0..90 {
+                world.tick_camera(DT, WINDOW);
+                offsets.push(world.camera().shake_offset);
+            }
+            assert_eq!(world.camera().position, Vec2::default());
+            offsets
+        };
+        let offsets = run();
+        assert_eq!(offsets, run());
+        assert!(offsets[..10]
+            .iter()
+            .any(|offset| offset.x != 0.0 || offset.y != 0.0));
+        assert!(offsets
+            .iter()
+            .all(|offset| offset.x.abs() <= 0.4 && offset.y.abs() <= 0.4));
+        assert_eq!(*offsets.last().expect("offset"), Vec2::default());
+
+        let mut world = SceneWorld::default();
+        world.add_camera_trauma(f32::NAN);
+        world.add_camera_trauma(-1.0);
+        assert_eq!(world.camera_trauma(), 0.0);
+    }
+}
@@ -0,0 +1,250 @@
+use super::{OrderState, Scene, SceneWorld, Tilemap, Vec2};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a over explicitly written fields. Floats hash their bit patterns, so the hash is only
+/// stable for bit-identical simulation, which is the point. Scenes feed their own state in
+/// through `Scene::hash_state`; write collections in a stable order, never `HashMap` order.
+#[derive(Debug, Clone, Copy)]
+pub struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+}
+
+impl StateHasher {
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_bytes(&[u8::from(value)]);
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write_bytes(value.as_bytes());
+    }
+
+    pub fn write_vec2(&mut self, value: Vec2) {
+        self.write_f32(value.x);
+        self.write_f32(value.y);
+    }
+
+    pub fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+impl SceneWorld {
+    /// Checksum of the simulation state the world owns: applied entities in spawn order, the
+    /// active floor, every floor's tiles and the pending timers. Camera, particles, lighting,
+    /// widgets and other presentation state are left out, as are queued spawns and despawns.
+    pub fn state_checksum(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        self.write_state(&mut hasher);
+        hasher.finish()
+    }
+
+    /// `state_checksum` followed by the state `scene` adds through `Scene::hash_state`.
+    pub fn state_hash(&self, scene: &dyn Scene) -> u64 {
+        let mut hasher = StateHasher::default();
+        self.write_state(&mut hasher);
+        scene.hash_state(self, &mut hasher);
+        hasher.finish()
+    }
+
+    fn write_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u64(self.entities.len() as u64);
+        for entity in &self.entities {
+            hasher.write_u64(entity.id.0);
+            hasher.write_vec2(entity.transform.position);
+            hasher.write_f32(entity.transform.rotation_radians.unwrap_or(f32::NAN));
+            hasher.write_u64(entity.floor.index() as u64);
+            hasher.write_bool(entity.selectable);
+            hasher.write_bool(entity.actor);
+            write_order_state(hasher, entity.order_state);
+            if let Some(interactable) = entity.interactable {
+                hasher.write_f32(interactable.interaction_radius);
+                hasher.write_u32(interactable.remaining_uses);
+            }
+        }
+
+        hasher.write_u64(self.active_floor.index() as u64);
+        for floor in self.floor_ids() {
+            match self.floor_tilemap(floor) {
+                Some(tilemap) => write_tilemap(hasher, tilemap),
+                None => hasher.write_bool(false),
+            }
+        }
+
+        hasher.write_u64(self.timer_tick());
+        for timer in self.timers() {
+            hasher.write_u64(timer.id.0);
+            hasher.write_u64(timer.owner.map_or(u64::MAX, |owner| owner.0));
+            hasher.write_str(&timer.tag);
+            hasher.write_u64(timer.due_tick);
+            hasher.write_u32(timer.repeat_every_ticks.unwrap_or(0));
+        }
+    }
+}
+
+fn write_order_state(hasher: &mut StateHasher, order_state: OrderState) {
+    match order_state {
+        OrderState::Idle => hasher.write_bytes(&[0]),
+        OrderState::MoveTo { point } => {
+            hasher.write_bytes(&[1]);
+            hasher.write_vec2(point);
+        }
+        OrderState::Interact { target_save_id } => {
+            hasher.write_bytes(&[2]);
+            hasher.write_u64(target_save_id);
+        }
+        OrderState::Working {
+            target_save_id,
+            remaining_time,
+        } => {
+            hasher.write_bytes(&[3]);
+            hasher.write_u64(target_save_id);
+            hasher.write_f32(remaining_time);
+        }
+    }
+}
+
+fn write_tilemap(hasher: &mut StateHasher, tilemap: &Tilemap) {
+    hasher.write_bool(true);
+    hasher.write_u32(tilemap.width());
+    hasher.write_u32(tilemap.height());
+    hasher.write_vec2(tilemap.origin());
+    for layer in 0..tilemap.layer_count() {
+        for y in 0..tilemap.height() {
+            for x in 0..tilemap.width() {
+                hasher.write_u16(tilemap.layer_tile_at(layer, x, y).unwrap_or(0));
+            }
+        }
+    }
+    for y in 0..tilemap.height() {
+        for x in 0..tilemap.width() {
+            hasher.write_u16(tilemap.flags_at(x, y).map_or(0, |flags| flags.bits()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{RenderableDesc, RenderableKind, Transform};
+
+    fn world_with_entity() -> (SceneWorld, crate::app::EntityId) {
+        let mut world = SceneWorld::default();
+        let id = world.spawn_actor(
+            Transform::default(),
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "actor",
+            },
+        );
+        world.apply_pending();
+        (world, id)
+    }
+
+    #[test]
+    fn checksum_tracks_simulation_state_but_not_presentation() {
+        let (mut world, id) = world_with_entity();
+        let (twin, _) = world_with_entity();
+        let baseline = world.state_checksum();
+        assert_eq!(baseline, twin.state_checksum());
+
+        world.camera_mut().position = Vec2 { x: 4.0, y: 2.0 };
+        world.add_camera_trauma(0.5);
+        assert_eq!(world.state_checksum(), baseline);
+
+        world
+            .find_entity_mut(id)
+            .expect("entity")
+            .transform
+            .position
+            .x = 0.5;
+        let moved = world.state_checksum();
+        assert_ne!(moved, baseline);
+
+        world.find_entity_mut(id).expect("entity").order_state = OrderState::MoveTo {
+            point: Vec2 { x: 1.0, y: 1.0 },
+        };
+        assert_ne!(world.state_checksum(), moved);
+    }
+
+    #[test]
+    fn checksum_covers_tiles_and_timers() {
+        let (mut world, _) = world_with_entity();
+        let floor = world.active_floor();
+        world.set_floor_tilemap(
+            floor,
+            Tilemap::new(2, 2, Vec2::default(), vec![0; 4]).expect("tilemap"),
+        );
+        let baseline = world.state_checksum();
+
+        world.set_tile(0, 1, 1, 7);
+        let tiled = world.state_checksum();
+        assert_ne!(tiled, baseline);
+
+        world
+            .schedule_timer(None, "ping", 3)
+            .expect("schedule timer");
+        assert_ne!(world.state_checksum(), tiled);
+    }
+
+    struct CountingScene(u32);
+
+    impl Scene for CountingScene {
+        fn load(&mut self, _world: &mut SceneWorld) {}
+        fn update(
+            &mut self,
+            _fixed_dt_seconds: f32,
+            _input: &crate::app::InputSnapshot,
+            _world: &mut SceneWorld,
+        ) -> crate::app::SceneCommand {
+            crate::app::SceneCommand::None
+        }
+        fn render(&mut self, _world: &SceneWorld) {}
+        fn unload(&mut self, _world: &mut SceneWorld) {}
+        fn hash_state(&self, _world: &SceneWorld, hasher: &mut StateHasher) {
+            hasher.write_u32(self.0);
+        }
+    }
+
+    #[test]
+    fn state_hash_folds_in_scene_state() {
+        let (world, _) = world_with_entity();
+        let first = world.state_hash(&CountingScene(1));
+        assert_eq!(first, world.state_hash(&CountingScene(1)));
+        assert_ne!(first, world.state_hash(&CountingScene(2)));
+        assert_ne!(first, world.state_checksum());
+    }
+}
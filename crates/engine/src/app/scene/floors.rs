@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use super::{SceneWorld, Tilemap, Vec2, AMBIENT_UNLIT};
+
+/// Floors every world starts with until it calls `SceneWorld::declare_floors`.
+pub const LEGACY_FLOOR_NAMES: [&str; 3] = ["rooftop", "main", "basement"];
+/// Upper bound on declared floors per world.
+pub const MAX_FLOORS: usize = 64;
+
+/// Index into the floors declared by a `SceneWorld`.
+///
+/// The associated constants name the legacy floor layout; worlds that declare their own floors
+/// address them purely by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FloorId(pub u16);
+
+impl FloorId {
+    pub const ROOFTOP: Self = Self(0);
+    pub const MAIN: Self = Self(1);
+    pub const BASEMENT: Self = Self(2);
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Default for FloorId {
+    fn default() -> Self {
+        Self::MAIN
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FloorError {
+    #[error("a world must declare at least one floor")]
+    NoFloors,
+    #[error("too many floors: {count} (max {max})")]
+    TooMany { count: usize, max: usize },
+    #[error("invalid floor name '{name}' (expected non-empty and not a plain number)")]
+    InvalidName { name: String },
+    #[error("duplicate floor name '{name}'")]
+    DuplicateName { name: String },
+    #[error("default floor {floor} is out of range for {count} floors")]
+    DefaultOutOfRange { floor: u16, count: usize },
+}
+
+/// Stair/ladder component: an actor that reaches the owning entity continues from
+/// `to_position` on `to_floor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StairLink {
+    pub to_floor: FloorId,
+    pub to_position: Vec2,
+}
+
+#[derive(Debug, Clone)]
+struct FloorSlot {
+    name: String,
+    tilemap: Option<Tilemap>,
+    ambient_rgb: [u8; 3],
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct FloorTable {
+    slots: Vec<FloorSlot>,
+    default_floor: FloorId,
+}
+
+impl Default for FloorTable {
+    fn default() -> Self {
+        Self {
+            slots: LEGACY_FLOOR_NAMES
+                .iter()
+                .map(|name| FloorSlot {
+                    name: (*name).to_string(),
+                    tilemap: None,
+                    ambient_rgb: AMBIENT_UNLIT,
+                })
+                .collect(),
+            default_floor: FloorId::MAIN,
+        }
+    }
+}
+
+impl FloorTable {
+    pub(super) fn default_floor(&self) -> FloorId {
+        self.default_floor
+    }
+
+    pub(super) fn tilemap(&self, floor: FloorId) -> Option<&Tilemap> {
+        self.slots.get(floor.index())?.tilemap.as_ref()
+    }
+
+    pub(super) fn tilemap_mut(&mut self, floor: FloorId) -> Option<&mut Tilemap> {
+        self.slots.get_mut(floor.index())?.tilemap.as_mut()
+    }
+
+    pub(super) fn ambient(&self, floor: FloorId) -> Option<[u8; 3]> {
+        self.slots.get(floor.index()).map(|slot| slot.ambient_rgb)
+    }
+
+    pub(super) fn set_ambient(&mut self, floor: FloorId, rgb: [u8; 3]) -> bool {
+        let Some(slot) = self.slots.get_mut(floor.index()) else {
+            return false;
+        };
+        slot.ambient_rgb = rgb;
+        true
+    }
+}
+
+impl SceneWorld {
+    /// Replaces the floor list. Every floor starts without a tilemap and with an unlit ambient,
+    /// the active floor moves to `default_floor`, and entities keep their floor index unchanged.
+    pub fn declare_floors<S: AsRef<str>>(
+        &mut self,
+        names: &[S],
+        default_floor: FloorId,
+    ) -> Result<(), FloorError> {
+        validate_floor_declaration(names, default_floor)?;
+        self.floors = FloorTable {
+            slots: names
+                .iter()
+                .map(|name| FloorSlot {
+                    name: name.as_ref().to_string(),
+                    tilemap: None,
+                    ambient_rgb: AMBIENT_UNLIT,
+                })
+                .collect(),
+            default_floor,
+        };
+        self.active_floor = default_floor;
+        self.tilemap_epoch = self.tilemap_epoch.saturating_add(1);
+        self.reset_tilemap_changes();
+        Ok(())
+    }
+
+    /// Floor that `clear` makes active.
+    pub fn default_floor(&self) -> FloorId {
+        self.floors.default_floor()
+    }
+
+    pub fn floor_count(&self) -> usize {
+        self.floors.slots.len()
+    }
+
+    pub fn floor_ids(&self) -> impl Iterator<Item = FloorId> {
+        (0..self.floors.slots.len() as u16).map(FloorId)
+    }
+
+    pub fn has_floor(&self, floor: FloorId) -> bool {
+        floor.index() < self.floors.slots.len()
+    }
+
+    pub fn floor_name(&self, floor: FloorId) -> Option<&str> {
+        self.floors
+            .slots
+            .get(floor.index())
+            .map(|slot| slot.name.as_str())
+    }
+
+    pub fn floor_by_name(&self, name: &str) -> Option<FloorId> {
+        self.floors
+            .slots
+            .iter()
+            .position(|slot| slot.name == name)
+            .map(|index| FloorId(index as u16))
+    }
+
+    /// Resolves a console-style floor token: a declared floor index or a (case-insensitive)
+    /// floor name.
+    pub fn resolve_floor_token(&self, token: &str) -> Option<FloorId> {
+        if let Ok(index) = token.parse::<u16>() {
+            let floor = FloorId(index);
+            return self.has_floor(floor).then_some(floor);
+        }
+        self.floors
+            .slots
+            .iter()
+            .position(|slot| slot.name.eq_ignore_ascii_case(token))
+            .map(|index| FloorId(index as u16))
+    }
+
+    pub fn floor_tilemap(&self, floor: FloorId) -> Option<&Tilemap> {
+        self.floors.tilemap(floor)
+    }
+
+    /// Installs `tilemap` on `floor`; returns `false` when the floor is not declared.
+    pub fn set_floor_tilemap(&mut self, floor: FloorId, mut tilemap: Tilemap) -> bool {
+        let Some(slot) = self.floors.slots.get_mut(floor.index()) else {
+            return false;
+        };
+        self.tilemap_epoch = self.tilemap_epoch.saturating_add(1);
+        tilemap.stamp_all_chunks(self.tilemap_epoch);
+        slot.tilemap = Some(tilemap);
+        self.reset_tilemap_changes();
+        true
+    }
+
+    pub fn clear_floor_tilemap(&mut self, floor: FloorId) -> bool {
+        let Some(slot) = self.floors.slots.get_mut(floor.index()) else {
+            return false;
+        };
+        slot.tilemap = None;
+        self.tilemap_epoch = self.tilemap_epoch.saturating_add(1);
+        self.reset_tilemap_changes();
+        true
+    }
+}
+
+/// Checks a floor list the way `SceneWorld::declare_floors` would, without mutating a world.
+pub fn validate_floor_declaration<S: AsRef<str>>(
+    names: &[S],
+    default_floor: FloorId,
+) -> Result<(), FloorError> {
+    if names.is_empty() {
+        return Err(FloorError::NoFloors);
+    }
+    if names.len() > MAX_FLOORS {
+        return Err(FloorError::TooMany {
+            count: names.len(),
+            max: MAX_FLOORS,
+        });
+    }
+    if default_floor.index() >= names.len() {
+        return Err(FloorError::DefaultOutOfRange {
+            floor: default_floor.0,
+            count: names.len(),
+        });
+    }
+    let mut seen = HashSet::with_capacity(names.len());
+    for name in names.iter().map(AsRef::as_ref) {
+        if name.trim().is_empty() || name.parse::<u16>().is_ok() {
+            return Err(FloorError::InvalidName {
+                name: name.to_string(),
+            });
+        }
+        if !seen.insert(name) {
+            return Err(FloorError::DuplicateName {
+                name: name.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat_tilemap() -> Tilemap {
+        Tilemap::new(2, 2, Vec2 { x: 0.0, y: 0.0 }, vec![0; 4]).expect("tilemap")
+    }
+
+    #[test]
+    fn default_world_exposes_legacy_floors() {
+        let world = SceneWorld::default();
+        assert_eq!(world.floor_count(), 3);
+        assert_eq!(world.active_floor(), FloorId::MAIN);
+        assert_eq!(world.floor_name(FloorId::ROOFTOP), Some("rooftop"));
+        assert_eq!(world.floor_by_name("basement"), Some(FloorId::BASEMENT));
+        assert_eq!(world.resolve_floor_token("2"), Some(FloorId::BASEMENT));
+        assert_eq!(world.resolve_floor_token("Rooftop"), Some(FloorId::ROOFTOP));
+        assert_eq!(world.resolve_floor_token("3"), None);
+        assert_eq!(world.resolve_floor_token("attic"), None);
+    }
+
+    #[test]
+    fn declare_floors_validates_names_and_default() {
+        let mut world = SceneWorld::default();
+        assert_eq!(
+            world.declare_floors::<&str>(&[], FloorId(0)),
+            Err(FloorError::NoFloors)
+        );
+        assert_eq!(
+            world.declare_floors(&["a", "a"], FloorId(0)),
+            Err(FloorError::DuplicateName {
+                name: "a".to_string()
+            })
+        );
+        assert_eq!(
+            world.declare_floors(&["a", "7"], FloorId(0)),
+            Err(FloorError::InvalidName {
+                name: "7".to_string()
+            })
+        );
+        assert_eq!(
+            world.declare_floors(&["a"], FloorId(1)),
+            Err(FloorError::DefaultOutOfRange { floor: 1, count: 1 })
+        );
+        assert_eq!(world.floor_count(), 3);
+
+        world
+            .declare_floors(&["cellar", "ground", "loft", "roof"], FloorId(1))
+            .expect("declare");
+        assert_eq!(world.floor_count(), 4);
+        assert_eq!(world.active_floor(), FloorId(1));
+        assert_eq!(world.resolve_floor_token("roof"), Some(FloorId(3)));
+        world.set_active_floor(FloorId(3));
+        world.clear();
+        assert_eq!(world.active_floor(), FloorId(1));
+    }
+
+    #[test]
+    fn each_floor_keeps_its_own_tilemap() {
+        let mut world = SceneWorld::default();
+        world
+            .declare_floors(&["lower", "upper"], FloorId(0))
+            .expect("declare");
+        world.set_tilemap(flat_tilemap());
+        assert!(world.set_floor_tilemap(
+            FloorId(1),
+            Tilemap::new(3, 1, Vec2 { x: 0.0, y: 0.0 }, vec![7; 3]).expect("tilemap"),
+        ));
+        assert!(!world.set_floor_tilemap(FloorId(2), flat_tilemap()));
+
+        assert_eq!(world.tilemap().map(Tilemap::width), Some(2));
+        world.set_active_floor(FloorId(1));
+        assert_eq!(world.tilemap().map(Tilemap::width), Some(3));
+
+        let before_edit = world.tilemap_epoch();
+        assert!(world.set_tile(0, 0, 0, 9));
+        assert_eq!(
+            world
+                .floor_tilemap(FloorId(1))
+                .and_then(|tilemap| tilemap.tile_at(0, 0)),
+            Some(9)
+        );
+        assert_eq!(
+            world
+                .floor_tilemap(FloorId(0))
+                .and_then(|tilemap| tilemap.tile_at(0, 0)),
+            Some(0)
+        );
+        let change = world
+            .tilemap_changes_since(before_edit)
+            .and_then(|mut changes| changes.next())
+            .expect("changes");
+        assert_eq!(change.floor, FloorId(1));
+    }
+}
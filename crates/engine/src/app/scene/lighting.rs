@@ -0,0 +1,366 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use super::{EntityId, FloorId, SceneWorld, Vec2};
+
+/// Point lights allowed per world.
+pub const MAX_POINT_LIGHTS: usize = 1024;
+/// Ambient of a floor that never set one; the lighting pass is skipped for it.
+pub const AMBIENT_UNLIT: [u8; 3] = [255, 255, 255];
+
+/// Stable id of a point light; never reused within one world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LightId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum LightError {
+    #[error("light radius must be finite and > 0, got {0}")]
+    InvalidRadius(f32),
+    #[error("light intensity must be finite and >= 0, got {0}")]
+    InvalidIntensity(f32),
+    #[error("light offset must be finite")]
+    InvalidOffset,
+    #[error("light floor {0} is not declared")]
+    UnknownFloor(u16),
+    #[error("too many point lights (max {max})")]
+    TooMany { max: usize },
+}
+
+/// Where a light sits. Entity anchors follow the entity across floors; tile anchors use the
+/// center of a tile of that floor's tilemap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LightAnchor {
+    Entity(EntityId),
+    Tile { floor: FloorId, x: u32, y: u32 },
+    World { floor: FloorId, position: Vec2 },
+}
+
+/// Content-defined light shape, attached to an anchor with `PointLightDef::at`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLightDef {
+    /// Distance in world units at which the light fades out.
+    pub radius: f32,
+    pub rgb: [u8; 3],
+    /// Multiplier on `rgb` at the center.
+    pub intensity: f32,
+}
+
+impl PointLightDef {
+    pub fn at(self, anchor: LightAnchor) -> PointLight {
+        PointLight {
+            anchor,
+            offset: Vec2 { x: 0.0, y: 0.0 },
+            radius: self.radius,
+            rgb: self.rgb,
+            intensity: self.intensity,
+        }
+    }
+}
+
+/// Radial light added on top of the floor ambient before the frame is multiplied by it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLight {
+    pub anchor: LightAnchor,
+    /// World-unit offset from the anchor position.
+    pub offset: Vec2,
+    pub radius: f32,
+    pub rgb: [u8; 3],
+    pub intensity: f32,
+}
+
+impl PointLight {
+    fn validate(&self) -> Result<(), LightError> {
+        if !self.radius.is_finite() || self.radius <= 0.0 {
+            return Err(LightError::InvalidRadius(self.radius));
+        }
+        if !self.intensity.is_finite() || self.intensity < 0.0 {
+            return Err(LightError::InvalidIntensity(self.intensity));
+        }
+        if !self.offset.x.is_finite() || !self.offset.y.is_finite() {
+            return Err(LightError::InvalidOffset);
+        }
+        Ok(())
+    }
+}
+
+/// A light placed at a world position on one floor for the current frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedLight {
+    pub id: LightId,
+    pub position: Vec2,
+    pub radius: f32,
+    pub rgb: [u8; 3],
+    pub intensity: f32,
+}
+
+impl ResolvedLight {
+    /// Linear RGB added at `point`, with a smooth `(1 - d²/r²)²` falloff.
+    pub fn contribution_at(&self, point: Vec2) -> [f32; 3] {
+        let dx = point.x - self.position.x;
+        let dy = point.y - self.position.y;
+        let t = (dx * dx + dy * dy) / (self.radius * self.radius);
+        if t >= 1.0 {
+            return [0.0; 3];
+        }
+        let falloff = (1.0 - t) * (1.0 - t) * self.intensity;
+        self.rgb.map(|channel| f32::from(channel) / 255.0 * falloff)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct LightingState {
+    next_id: u64,
+    lights: BTreeMap<LightId, PointLight>,
+}
+
+impl LightingState {
+    pub(super) fn clear(&mut self) {
+        self.lights.clear();
+    }
+
+    pub(super) fn remove_anchored_to(&mut self, entities: &[EntityId]) {
+        self.lights.retain(|_, light| match light.anchor {
+            LightAnchor::Entity(id) => !entities.contains(&id),
+            _ => true,
+        });
+    }
+}
+
+impl SceneWorld {
+    /// Ambient multiplier of `floor`; `AMBIENT_UNLIT` for undeclared floors.
+    pub fn floor_ambient(&self, floor: FloorId) -> [u8; 3] {
+        self.floors.ambient(floor).unwrap_or(AMBIENT_UNLIT)
+    }
+
+    /// Sets the ambient of `floor`; returns `false` when the floor is not declared.
+    pub fn set_floor_ambient(&mut self, floor: FloorId, rgb: [u8; 3]) -> bool {
+        self.floors.set_ambient(floor, rgb)
+    }
+
+    pub fn add_light(&mut self, light: PointLight) -> Result<LightId, LightError> {
+        light.validate()?;
+        match light.anchor {
+            LightAnchor::Tile { floor, .. } | LightAnchor::World { floor, .. }
+                if !self.has_floor(floor) =>
+            {
+                return Err(LightError::UnknownFloor(floor.0));
+            }
+            _ => {}
+        }
+        if self.lighting.lights.len() >= MAX_POINT_LIGHTS {
+            return Err(LightError::TooMany {
+                max: MAX_POINT_LIGHTS,
+            });
+        }
+        let id = LightId(self.lighting.next_id);
+        self.lighting.next_id = self.lighting.next_id.saturating_add(1);
+        self.lighting.lights.insert(id, light);
+        Ok(id)
+    }
+
+    pub fn remove_light(&mut self, id: LightId) -> bool {
+        self.lighting.lights.remove(&id).is_some()
+    }
+
+    pub fn light(&self, id: LightId) -> Option<&PointLight> {
+        self.lighting.lights.get(&id)
+    }
+
+    /// All lights in id order.
+    pub fn lights(&self) -> impl Iterator<Item = (LightId, &PointLight)> {
+        self.lighting.lights.iter().map(|(id, light)| (*id, light))
+    }
+
+    /// Lights that currently sit on `floor`, in id order. Entity anchors resolve only for applied
+    /// entities on that floor; tile anchors only for in-bounds tiles of the floor's tilemap.
+    pub fn resolved_lights_on_floor(&self, floor: FloorId) -> Vec<ResolvedLight> {
+        self.lighting
+            .lights
+            .iter()
+            .filter_map(|(id, light)| {
+                let anchor_position = match light.anchor {
+                    LightAnchor::Entity(entity_id) => self
+                        .find_entity(entity_id)
+                        .filter(|entity| entity.floor == floor)
+                        .map(|entity| entity.transform.position)?,
+                    LightAnchor::Tile {
+                        floor: tile_floor,
+                        x,
+                        y,
+                    } if tile_floor == floor => {
+                        self.floor_tilemap(floor)?.tile_center_world(x, y)?
+                    }
+                    LightAnchor::World {
+                        floor: world_floor,
+                        position,
+                    } if world_floor == floor => position,
+                    _ => return None,
+                };
+                Some(ResolvedLight {
+                    id: *id,
+                    position: Vec2 {
+                        x: anchor_position.x + light.offset.x,
+                        y: anchor_position.y + light.offset.y,
+                    },
+                    radius: light.radius,
+                    rgb: light.rgb,
+                    intensity: light.intensity,
+                })
+            })
+            .collect()
+    }
+
+    pub(super) fn restore_light(&mut self, id: LightId, light: PointLight) {
+        self.lighting.next_id = self.lighting.next_id.max(id.0.saturating_add(1));
+        self.lighting.lights.insert(id, light);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{RenderableDesc, RenderableKind, Tilemap, Transform};
+    use super::*;
+
+    fn lamp(anchor: LightAnchor) -> PointLight {
+        PointLightDef {
+            radius: 2.0,
+            rgb: [255, 200, 100],
+            intensity: 1.0,
+        }
+        .at(anchor)
+    }
+
+    #[test]
+    fn add_light_validates_shape_floor_and_cap() {
+        let mut world = SceneWorld::default();
+        let mut bad = lamp(LightAnchor::World {
+            floor: FloorId::MAIN,
+            position: Vec2 { x: 0.0, y: 0.0 },
+        });
+        bad.radius = 0.0;
+        assert_eq!(world.add_light(bad), Err(LightError::InvalidRadius(0.0)));
+        bad.radius = 1.0;
+        bad.intensity = f32::NAN;
+        assert!(matches!(
+            world.add_light(bad),
+            Err(LightError::InvalidIntensity(_))
+        ));
+        assert_eq!(
+            world.add_light(lamp(LightAnchor::Tile {
+                floor: FloorId(9),
+                x: 0,
+                y: 0,
+            })),
+            Err(LightError::UnknownFloor(9))
+        );
+
+        let first = world
+            .add_light(lamp(LightAnchor::Entity(EntityId(4))))
+            .expect("light");
+        assert!(world.remove_light(first));
+        let second = world
+            .add_light(lamp(LightAnchor::Entity(EntityId(4))))
+            .expect("light");
+        assert_ne!(first, second);
+        for _ in 1..MAX_POINT_LIGHTS {
+            world
+                .add_light(lamp(LightAnchor::Entity(EntityId(4))))
+                .expect("light");
+        }
+        assert_eq!(
+            world.add_light(lamp(LightAnchor::Entity(EntityId(4)))),
+            Err(LightError::TooMany {
+                max: MAX_POINT_LIGHTS
+            })
+        );
+    }
+
+    #[test]
+    fn lights_resolve_per_floor_and_entity_lights_go_away_with_their_entity() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(
+            Tilemap::new(4, 4, Vec2 { x: -2.0, y: -2.0 }, vec![0; 16]).expect("tilemap"),
+        );
+        let entity = world.spawn(
+            Transform {
+                position: Vec2 { x: 1.0, y: 1.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "lamp_post",
+            },
+        );
+        let mut entity_light = lamp(LightAnchor::Entity(entity));
+        entity_light.offset = Vec2 { x: 0.0, y: 0.5 };
+        let entity_light = world.add_light(entity_light).expect("entity light");
+        let tile_light = world
+            .add_light(lamp(LightAnchor::Tile {
+                floor: FloorId::MAIN,
+                x: 0,
+                y: 3,
+            }))
+            .expect("tile light");
+        world
+            .add_light(lamp(LightAnchor::World {
+                floor: FloorId::BASEMENT,
+                position: Vec2 { x: 0.0, y: 0.0 },
+            }))
+            .expect("basement light");
+
+        let resolved = world.resolved_lights_on_floor(FloorId::MAIN);
+        assert_eq!(resolved.len(), 1, "pending entities do not emit light");
+        assert_eq!(resolved[0].id, tile_light);
+        assert_eq!(resolved[0].position, Vec2 { x: -1.5, y: 1.5 });
+
+        world.apply_pending();
+        let resolved = world.resolved_lights_on_floor(FloorId::MAIN);
+        assert_eq!(
+            resolved.iter().map(|light| light.id).collect::<Vec<_>>(),
+            vec![entity_light, tile_light]
+        );
+        assert_eq!(resolved[0].position, Vec2 { x: 1.0, y: 1.5 });
+        assert_eq!(world.resolved_lights_on_floor(FloorId::BASEMENT).len(), 1);
+
+        world.despawn(entity);
+        world.apply_pending();
+        assert!(world.light(entity_light).is_none());
+        assert!(world.light(tile_light).is_some());
+        world.clear();
+        assert_eq!(world.lights().count(), 0);
+    }
+
+    #[test]
+    fn floor_ambient_defaults_to_unlit_and_resets_with_declared_floors() {
+        let mut world = SceneWorld::default();
+        assert_eq!(world.floor_ambient(FloorId::BASEMENT), AMBIENT_UNLIT);
+        assert!(world.set_floor_ambient(FloorId::BASEMENT, [40, 40, 60]));
+        assert!(!world.set_floor_ambient(FloorId(3), [0, 0, 0]));
+        assert_eq!(world.floor_ambient(FloorId::BASEMENT), [40, 40, 60]);
+        world.clear();
+        assert_eq!(world.floor_ambient(FloorId::BASEMENT), [40, 40, 60]);
+        world
+            .declare_floors(&["cellar", "ground"], FloorId(1))
+            .expect("declare");
+        assert_eq!(world.floor_ambient(FloorId(0)), AMBIENT_UNLIT);
+    }
+
+    #[test]
+    fn contribution_falls_off_smoothly_to_zero_at_the_radius() {
+        let light = ResolvedLight {
+            id: LightId(0),
+            position: Vec2 { x: 0.0, y: 0.0 },
+            radius: 2.0,
+            rgb: [255, 0, 255],
+            intensity: 2.0,
+        };
+        assert_eq!(
+            light.contribution_at(Vec2 { x: 0.0, y: 0.0 }),
+            [2.0, 0.0, 2.0]
+        );
+        let halfway = light.contribution_at(Vec2 { x: 1.0, y: 0.0 });
+        assert!((halfway[0] - 2.0 * 0.75 * 0.75).abs() < 1e-6);
+        assert_eq!(light.contribution_at(Vec2 { x: 0.0, y: 2.0 }), [0.0; 3]);
+    }
+}
@@ -0,0 +1,126 @@
+use super::{EntityId, SceneWorld, Vec2};
+
+/// Category a minimap dot is colored by; the engine has no notion of roles, so scenes decide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MinimapRole {
+    Player,
+    Settler,
+    Npc,
+    Interactable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinimapMarker {
+    pub entity: EntityId,
+    pub role: MinimapRole,
+}
+
+/// Per-frame marker list a scene fills in `Scene::submit_minimap_markers`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MinimapMarkers {
+    markers: Vec<MinimapMarker>,
+}
+
+impl MinimapMarkers {
+    pub fn push(&mut self, entity: EntityId, role: MinimapRole) {
+        self.markers.push(MinimapMarker { entity, role });
+    }
+
+    pub fn as_slice(&self) -> &[MinimapMarker] {
+        &self.markers
+    }
+
+    pub fn len(&self) -> usize {
+        self.markers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.markers.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.markers.clear();
+    }
+}
+
+impl SceneWorld {
+    /// Markers submitted for the current frame, in submission (draw) order.
+    pub fn minimap_markers(&self) -> &[MinimapMarker] {
+        self.minimap_markers.as_slice()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn minimap_markers_mut(&mut self) -> &mut MinimapMarkers {
+        &mut self.minimap_markers
+    }
+
+    /// Position of a marker's entity when it should show on the minimap: applied, on the active
+    /// floor, and not hidden by the fog viewer.
+    pub fn minimap_marker_position(&self, marker: MinimapMarker) -> Option<Vec2> {
+        self.find_entity(marker.entity)
+            .filter(|entity| entity.floor == self.active_floor)
+            .filter(|entity| !self.entity_hidden_by_fog(entity))
+            .map(|entity| entity.transform.position)
+    }
+
+    /// Moves the camera so `position` is at the center of the view and stops following; used by
+    /// minimap clicks.
+    pub fn recenter_camera(&mut self, position: Vec2) {
+        if position.x.is_finite() && position.y.is_finite() {
+            self.camera.position = position;
+            self.set_camera_follow(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{FloorId, RenderableDesc, RenderableKind, Transform};
+    use super::*;
+
+    fn spawn_at(world: &mut SceneWorld, x: f32, y: f32) -> EntityId {
+        world.spawn(
+            Transform {
+                position: Vec2 { x, y },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "minimap_marker",
+            },
+        )
+    }
+
+    #[test]
+    fn marker_positions_resolve_only_for_applied_entities_on_the_active_floor() {
+        let mut world = SceneWorld::default();
+        let id = spawn_at(&mut world, 4.0, -2.0);
+        let marker = MinimapMarker {
+            entity: id,
+            role: MinimapRole::Settler,
+        };
+        assert_eq!(world.minimap_marker_position(marker), None);
+
+        world.apply_pending();
+        assert_eq!(
+            world.minimap_marker_position(marker),
+            Some(Vec2 { x: 4.0, y: -2.0 })
+        );
+
+        world.find_entity_mut(id).expect("entity").floor = FloorId(2);
+        assert_eq!(world.minimap_marker_position(marker), None);
+    }
+
+    #[test]
+    fn recenter_camera_ignores_non_finite_targets() {
+        let mut world = SceneWorld::default();
+        world.recenter_camera(Vec2 { x: 12.5, y: -3.0 });
+        assert_eq!(world.camera().position, Vec2 { x: 12.5, y: -3.0 });
+
+        world.recenter_camera(Vec2 {
+            x: f32::NAN,
+            y: 0.0,
+        });
+        assert_eq!(world.camera().position, Vec2 { x: 12.5, y: -3.0 });
+    }
+}
@@ -0,0 +1,411 @@
+use std::sync::Arc;
+
+use super::{FloorId, SceneWorld, Vec2};
+
+/// Live particle cap per world; spawns past the cap are dropped.
+pub const MAX_PARTICLES: usize = 4096;
+const RATE_SPAWN_EPSILON: f32 = 1e-4;
+
+/// Inclusive range sampled uniformly per spawned particle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ParticleRange {
+    pub const fn constant(value: f32) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+
+    fn sample(self, rng: &mut ParticleRng) -> f32 {
+        self.min + (self.max - self.min) * rng.next_unit()
+    }
+}
+
+/// Content-defined emitter. A started emitter spawns `burst` particles immediately, then
+/// `rate_per_second` particles for `duration_seconds`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticleEmitterDef {
+    /// Gameplay trigger name that starts this emitter, e.g. `damaged`.
+    pub on: String,
+    pub burst: u16,
+    pub rate_per_second: f32,
+    pub duration_seconds: f32,
+    pub lifetime_seconds: ParticleRange,
+    /// Initial speed in world units per second.
+    pub speed: ParticleRange,
+    /// Initial direction in degrees; 0 is +x, counter-clockwise in world space.
+    pub direction_degrees: ParticleRange,
+    /// Downward acceleration in world units per second squared.
+    pub gravity: f32,
+    pub size_px: u8,
+    /// Color ramp over particle lifetime; stops are evenly spaced and linearly interpolated.
+    pub colors: Vec<[u8; 4]>,
+    /// Sprite tinted by the ramp color; square particles when unset.
+    pub sprite_key: Option<String>,
+}
+
+impl Default for ParticleEmitterDef {
+    fn default() -> Self {
+        Self {
+            on: String::new(),
+            burst: 0,
+            rate_per_second: 0.0,
+            duration_seconds: 0.0,
+            lifetime_seconds: ParticleRange::constant(1.0),
+            speed: ParticleRange::constant(0.0),
+            direction_degrees: ParticleRange {
+                min: 0.0,
+                max: 360.0,
+            },
+            gravity: 0.0,
+            size_px: 2,
+            colors: vec![[255; 4]],
+            sprite_key: None,
+        }
+    }
+}
+
+impl ParticleEmitterDef {
+    /// Ramp color at normalized lifetime `t` (0 at spawn, 1 at expiry).
+    pub fn color_at(&self, t: f32) -> [u8; 4] {
+        let Some(last) = self.colors.len().checked_sub(1) else {
+            return [255; 4];
+        };
+        if last == 0 {
+            return self.colors[0];
+        }
+        let scaled = t.clamp(0.0, 1.0) * last as f32;
+        let index = (scaled.floor() as usize).min(last - 1);
+        let frac = scaled - index as f32;
+        let (from, to) = (self.colors[index], self.colors[index + 1]);
+        std::array::from_fn(|channel| {
+            let a = from[channel] as f32;
+            let b = to[channel] as f32;
+            (a + (b - a) * frac).round() as u8
+        })
+    }
+}
+
+/// Stable id of a started emitter; never reused within one world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ParticleEmitterId(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct Particle {
+    pub floor: FloorId,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub age_seconds: f32,
+    pub lifetime_seconds: f32,
+    pub emitter: Arc<ParticleEmitterDef>,
+}
+
+impl Particle {
+    pub fn color(&self) -> [u8; 4] {
+        self.emitter
+            .color_at(self.age_seconds / self.lifetime_seconds)
+    }
+}
+
+/// splitmix64; each emitter owns a stream derived from the world seed and its id, so particle
+/// output depends only on the seed and the order emitters were started.
+#[derive(Debug, Clone)]
+struct ParticleRng(u64);
+
+impl ParticleRng {
+    fn for_emitter(seed: u64, id: ParticleEmitterId) -> Self {
+        Self(seed ^ id.0.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ActiveEmitter {
+    id: ParticleEmitterId,
+    def: Arc<ParticleEmitterDef>,
+    floor: FloorId,
+    position: Vec2,
+    elapsed_seconds: f32,
+    rate_spawned: u32,
+    rng: ParticleRng,
+}
+
+impl ActiveEmitter {
+    fn spawn(&mut self, count: u32, particles: &mut Vec<Particle>) {
+        for _ in 0..count {
+            if particles.len() >= MAX_PARTICLES {
+                return;
+            }
+            let lifetime_seconds = self.def.lifetime_seconds.sample(&mut self.rng);
+            let speed = self.def.speed.sample(&mut self.rng);
+            let direction = self
+                .def
+                .direction_degrees
+                .sample(&mut self.rng)
+                .to_radians();
+            if lifetime_seconds <= 0.0 {
+                continue;
+            }
+            particles.push(Particle {
+                floor: self.floor,
+                position: self.position,
+                velocity: Vec2 {
+                    x: direction.cos() * speed,
+                    y: direction.sin() * speed,
+                },
+                age_seconds: 0.0,
+                lifetime_seconds,
+                emitter: Arc::clone(&self.def),
+            });
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct ParticleSystem {
+    seed: u64,
+    next_emitter_id: u64,
+    emitters: Vec<ActiveEmitter>,
+    particles: Vec<Particle>,
+}
+
+impl ParticleSystem {
+    pub(super) fn clear(&mut self) {
+        self.next_emitter_id = 0;
+        self.emitters.clear();
+        self.particles.clear();
+    }
+}
+
+impl SceneWorld {
+    /// Seeds particle randomness for emitters started after this call.
+    pub fn set_particle_seed(&mut self, seed: u64) {
+        self.particles.seed = seed;
+    }
+
+    pub fn particle_seed(&self) -> u64 {
+        self.particles.seed
+    }
+
+    /// Starts `def` at `position` on `floor`; the burst spawns immediately.
+    pub fn start_particle_emitter(
+        &mut self,
+        def: &ParticleEmitterDef,
+        floor: FloorId,
+        position: Vec2,
+    ) -> ParticleEmitterId {
+        let system = &mut self.particles;
+        let id = ParticleEmitterId(system.next_emitter_id);
+        system.next_emitter_id = system.next_emitter_id.saturating_add(1);
+        let mut emitter = ActiveEmitter {
+            id,
+            def: Arc::new(def.clone()),
+            floor,
+            position,
+            elapsed_seconds: 0.0,
+            rate_spawned: 0,
+            rng: ParticleRng::for_emitter(system.seed, id),
+        };
+        emitter.spawn(def.burst as u32, &mut system.particles);
+        if def.rate_per_second > 0.0 && def.duration_seconds > 0.0 {
+            system.emitters.push(emitter);
+        }
+        id
+    }
+
+    /// Starts every emitter in `emitters` whose trigger is `on`; returns how many started.
+    pub fn start_particle_emitters_on(
+        &mut self,
+        emitters: &[ParticleEmitterDef],
+        on: &str,
+        floor: FloorId,
+        position: Vec2,
+    ) -> usize {
+        let mut started = 0;
+        for def in emitters.iter().filter(|def| def.on == on) {
+            self.start_particle_emitter(def, floor, position);
+            started += 1;
+        }
+        started
+    }
+
+    pub fn stop_particle_emitter(&mut self, id: ParticleEmitterId) -> bool {
+        let before = self.particles.emitters.len();
+        self.particles.emitters.retain(|emitter| emitter.id != id);
+        self.particles.emitters.len() != before
+    }
+
+    /// Advances live particles, then lets running emitters spawn for this tick.
+    pub fn tick_particles(&mut self, fixed_dt_seconds: f32) {
+        let system = &mut self.particles;
+        system.particles.retain_mut(|particle| {
+            particle.age_seconds += fixed_dt_seconds;
+            if particle.age_seconds >= particle.lifetime_seconds {
+                return false;
+            }
+            particle.velocity.y -= particle.emitter.gravity * fixed_dt_seconds;
+            particle.position.x += particle.velocity.x * fixed_dt_seconds;
+            particle.position.y += particle.velocity.y * fixed_dt_seconds;
+            true
+        });
+
+        let particles = &mut system.particles;
+        system.emitters.retain_mut(|emitter| {
+            emitter.elapsed_seconds += fixed_dt_seconds;
+            let active_seconds = emitter.elapsed_seconds.min(emitter.def.duration_seconds);
+            // The epsilon keeps float drift in `elapsed_seconds` from losing the last spawn.
+            let due = (emitter.def.rate_per_second * active_seconds + RATE_SPAWN_EPSILON) as u32;
+            emitter.spawn(due.saturating_sub(emitter.rate_spawned), particles);
+            emitter.rate_spawned = emitter.rate_spawned.max(due);
+            emitter.elapsed_seconds + RATE_SPAWN_EPSILON < emitter.def.duration_seconds
+        });
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles.particles
+    }
+
+    pub fn particle_emitter_count(&self) -> usize {
+        self.particles.emitters.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spark() -> ParticleEmitterDef {
+        ParticleEmitterDef {
+            on: "damaged".to_string(),
+            burst: 6,
+            lifetime_seconds: ParticleRange { min: 0.2, max: 0.4 },
+            speed: ParticleRange { min: 1.0, max: 2.0 },
+            colors: vec![[255, 200, 80, 255], [255, 40, 20, 0]],
+            ..ParticleEmitterDef::default()
+        }
+    }
+
+    fn positions(world: &SceneWorld) -> Vec<(f32, f32)> {
+        world
+            .particles()
+            .iter()
+            .map(|particle| (particle.position.x, particle.position.y))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_and_start_order_reproduce_particles_exactly() {
+        let run = |seed: u64| {
+            let mut world = SceneWorld::default();
+            world.set_particle_seed(seed);
+            world.start_particle_emitter(&spark(), FloorId::MAIN, Vec2 { x: 1.0, y: 1.0 });
+            world.start_particle_emitter(&spark(), FloorId::MAIN, Vec2 { x: -1.0, y: 0.0 });
+            for _ in 0..5 {
+                world.tick_particles(1.0 / 60.0);
+            }
+            positions(&world)
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+        assert_eq!(run(7).len(), 12);
+    }
+
+    #[test]
+    fn particles_expire_after_lifetime_and_fall_with_gravity() {
+        let mut world = SceneWorld::default();
+        let def = ParticleEmitterDef {
+            burst: 1,
+            lifetime_seconds: ParticleRange::constant(0.5),
+            gravity: 4.0,
+            ..ParticleEmitterDef::default()
+        };
+        world.start_particle_emitter(&def, FloorId::MAIN, Vec2 { x: 0.0, y: 0.0 });
+        world.tick_particles(0.25);
+        let particle = &world.particles()[0];
+        assert!(particle.position.y < 0.0);
+        assert_eq!(particle.color(), [255; 4]);
+        world.tick_particles(0.25);
+        assert!(world.particles().is_empty());
+    }
+
+    #[test]
+    fn rate_emitters_spawn_over_duration_then_retire() {
+        let mut world = SceneWorld::default();
+        let def = ParticleEmitterDef {
+            rate_per_second: 10.0,
+            duration_seconds: 0.5,
+            lifetime_seconds: ParticleRange::constant(10.0),
+            ..ParticleEmitterDef::default()
+        };
+        world.start_particle_emitter(&def, FloorId::MAIN, Vec2 { x: 0.0, y: 0.0 });
+        assert_eq!(world.particle_emitter_count(), 1);
+        for _ in 0..10 {
+            world.tick_particles(0.1);
+        }
+        assert_eq!(world.particles().len(), 5);
+        assert_eq!(world.particle_emitter_count(), 0);
+
+        let id = world.start_particle_emitter(&def, FloorId::MAIN, Vec2 { x: 0.0, y: 0.0 });
+        assert!(world.stop_particle_emitter(id));
+        assert!(!world.stop_particle_emitter(id));
+        world.clear();
+        assert!(world.particles().is_empty());
+    }
+
+    #[test]
+    fn trigger_filter_and_color_ramp() {
+        let mut world = SceneWorld::default();
+        let other = ParticleEmitterDef {
+            on: "died".to_string(),
+            burst: 3,
+            ..ParticleEmitterDef::default()
+        };
+        let started = world.start_particle_emitters_on(
+            &[spark(), other],
+            "damaged",
+            FloorId::MAIN,
+            Vec2::default(),
+        );
+        assert_eq!(started, 1);
+        assert_eq!(world.particles().len(), 6);
+
+        let ramp = spark();
+        assert_eq!(ramp.color_at(0.0), [255, 200, 80, 255]);
+        assert_eq!(ramp.color_at(0.5), [255, 120, 50, 128]);
+        assert_eq!(ramp.color_at(1.0), [255, 40, 20, 0]);
+    }
+
+    #[test]
+    fn emitters_spawn_on_the_requested_floor_not_the_active_one() {
+        let mut world = SceneWorld::default();
+        world.set_active_floor(FloorId::MAIN);
+        let def = ParticleEmitterDef {
+            rate_per_second: 10.0,
+            duration_seconds: 1.0,
+            ..spark()
+        };
+        world.start_particle_emitter(&def, FloorId::BASEMENT, Vec2 { x: 3.0, y: -2.0 });
+        world.tick_particles(0.2);
+        assert!(!world.particles().is_empty());
+        assert!(world
+            .particles()
+            .iter()
+            .all(|particle| particle.floor == FloorId::BASEMENT));
+    }
+}
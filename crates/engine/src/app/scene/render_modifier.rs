@@ -0,0 +1,134 @@
+use super::{EntityId, SceneWorld};
+
+/// Exact-color replacement applied to sprite texels before tinting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteSwapEntry {
+    pub from_rgb: [u8; 3],
+    pub to_rgb: [u8; 3],
+}
+
+/// Per-entity color modulation applied in the sprite blit path.
+///
+/// Each source texel is palette-swapped (first matching entry wins), multiplied by `tint_rgba`,
+/// and its alpha scaled by `alpha` before source-over blending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderModifier {
+    pub tint_rgba: [u8; 4],
+    pub alpha: u8,
+    pub palette_swap: Vec<PaletteSwapEntry>,
+}
+
+impl Default for RenderModifier {
+    fn default() -> Self {
+        Self {
+            tint_rgba: [255; 4],
+            alpha: 255,
+            palette_swap: Vec::new(),
+        }
+    }
+}
+
+impl RenderModifier {
+    pub fn is_identity(&self) -> bool {
+        self.tint_rgba == [255; 4] && self.alpha == 255 && self.palette_swap.is_empty()
+    }
+
+    pub fn apply(&self, src_rgba: [u8; 4]) -> [u8; 4] {
+        let [mut r, mut g, mut b, a] = src_rgba;
+        if a == 0 {
+            return src_rgba;
+        }
+        if let Some(entry) = self
+            .palette_swap
+            .iter()
+            .find(|entry| entry.from_rgb == [r, g, b])
+        {
+            [r, g, b] = entry.to_rgb;
+        }
+        let [tr, tg, tb, ta] = self.tint_rgba;
+        [
+            mul_u8(r, tr),
+            mul_u8(g, tg),
+            mul_u8(b, tb),
+            mul_u8(mul_u8(a, ta), self.alpha),
+        ]
+    }
+}
+
+fn mul_u8(value: u8, factor: u8) -> u8 {
+    ((value as u16 * factor as u16 + 127) / 255) as u8
+}
+
+impl SceneWorld {
+    /// Installs `modifier` for `entity_id`; identity modifiers clear the entry instead.
+    pub fn set_entity_render_modifier(&mut self, entity_id: EntityId, modifier: RenderModifier) {
+        if modifier.is_identity() {
+            self.clear_entity_render_modifier(entity_id);
+            return;
+        }
+        self.visual_state
+            .entity_render_modifiers
+            .insert(entity_id, modifier);
+    }
+
+    pub fn clear_entity_render_modifier(&mut self, entity_id: EntityId) {
+        self.visual_state.entity_render_modifiers.remove(&entity_id);
+    }
+
+    pub fn entity_render_modifier(&self, entity_id: EntityId) -> Option<&RenderModifier> {
+        self.visual_state.entity_render_modifiers.get(&entity_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{RenderableDesc, RenderableKind, Transform};
+    use super::*;
+
+    #[test]
+    fn apply_swaps_palette_then_tints_and_scales_alpha() {
+        let modifier = RenderModifier {
+            tint_rgba: [255, 128, 255, 255],
+            alpha: 128,
+            palette_swap: vec![PaletteSwapEntry {
+                from_rgb: [48, 96, 192],
+                to_rgb: [192, 64, 48],
+            }],
+        };
+        assert_eq!(modifier.apply([48, 96, 192, 255]), [192, 32, 48, 128]);
+        assert_eq!(modifier.apply([10, 200, 30, 255]), [10, 100, 30, 128]);
+        assert_eq!(modifier.apply([48, 96, 192, 0]), [48, 96, 192, 0]);
+        assert_eq!(RenderModifier::default().apply([1, 2, 3, 4]), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn modifiers_clear_on_identity_despawn_and_world_clear() {
+        let mut world = SceneWorld::default();
+        let id = world.spawn(
+            Transform::default(),
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "tinted",
+            },
+        );
+        world.apply_pending();
+        let ghost = RenderModifier {
+            alpha: 96,
+            ..RenderModifier::default()
+        };
+
+        world.set_entity_render_modifier(id, ghost.clone());
+        assert_eq!(world.entity_render_modifier(id), Some(&ghost));
+        world.set_entity_render_modifier(id, RenderModifier::default());
+        assert_eq!(world.entity_render_modifier(id), None);
+
+        world.set_entity_render_modifier(id, ghost.clone());
+        world.despawn(id);
+        world.apply_pending();
+        assert_eq!(world.entity_render_modifier(id), None);
+
+        world.set_entity_render_modifier(EntityId(77), ghost);
+        world.clear();
+        assert_eq!(world.entity_render_modifier(EntityId(77)), None);
+    }
+}
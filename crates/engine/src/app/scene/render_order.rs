@@ -0,0 +1,176 @@
+use std::cmp::Ordering;
+
+use super::{Entity, EntityId, SceneWorld, Vec2};
+
+/// Coarse draw bucket. Layers draw in declaration order; every entity in a later layer draws
+/// above every entity in an earlier one regardless of position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum RenderLayer {
+    GroundDecal,
+    FloorItem,
+    #[default]
+    Standing,
+    Roof,
+    Overlay,
+}
+
+impl RenderLayer {
+    pub const ALL: [Self; 5] = [
+        Self::GroundDecal,
+        Self::FloorItem,
+        Self::Standing,
+        Self::Roof,
+        Self::Overlay,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::GroundDecal => "ground_decal",
+            Self::FloorItem => "floor_item",
+            Self::Standing => "standing",
+            Self::Roof => "roof",
+            Self::Overlay => "overlay",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|layer| layer.as_str() == value)
+    }
+}
+
+/// Draw/pick order of one entity: layer first, then sort pivot y (higher world y is further
+/// back and draws first), then applied spawn order as the stable tiebreak.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOrderKey {
+    pub layer: RenderLayer,
+    pub sort_y: f32,
+    pub spawn_order: u64,
+}
+
+impl Ord for RenderOrderKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.layer
+            .cmp(&other.layer)
+            .then_with(|| other.sort_y.total_cmp(&self.sort_y))
+            .then_with(|| self.spawn_order.cmp(&other.spawn_order))
+    }
+}
+
+impl PartialOrd for RenderOrderKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for RenderOrderKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RenderOrderKey {}
+
+impl Entity {
+    /// World point used for y-sorting: the transform position shifted by `sort_pivot_y`.
+    pub fn sort_pivot_world(&self) -> Vec2 {
+        Vec2 {
+            x: self.transform.position.x,
+            y: self.transform.position.y + self.sort_pivot_y,
+        }
+    }
+
+    /// Key shared by the renderer draw list, occlusion checks, and cursor picking; larger keys
+    /// draw later (on top).
+    pub fn renderer_overlap_order_key(&self) -> RenderOrderKey {
+        RenderOrderKey {
+            layer: self.render_layer,
+            sort_y: self.sort_pivot_world().y,
+            spawn_order: self.applied_spawn_order,
+        }
+    }
+}
+
+impl SceneWorld {
+    /// Sets the draw layer and sort pivot of a live or pending entity; returns false if unknown.
+    pub fn set_entity_render_order(
+        &mut self,
+        id: EntityId,
+        layer: RenderLayer,
+        sort_pivot_y: f32,
+    ) -> bool {
+        let Some(entity) = self
+            .entities
+            .iter_mut()
+            .chain(self.pending_spawns.iter_mut())
+            .find(|entity| entity.id == id)
+        else {
+            return false;
+        };
+        entity.render_layer = layer;
+        entity.sort_pivot_y = sort_pivot_y;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{RenderableDesc, RenderableKind, SceneWorld, Transform};
+    use super::*;
+
+    fn spawn_at(world: &mut SceneWorld, x: f32, y: f32) -> super::super::EntityId {
+        world.spawn_selectable(
+            Transform {
+                position: Vec2 { x, y },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "render_order",
+            },
+        )
+    }
+
+    #[test]
+    fn layer_then_y_then_spawn_order() {
+        let mut world = SceneWorld::default();
+        let back = spawn_at(&mut world, 0.0, 1.0);
+        let front = spawn_at(&mut world, 0.0, -1.0);
+        let decal = spawn_at(&mut world, 0.0, -5.0);
+        let tie_late = spawn_at(&mut world, 0.0, 1.0);
+        assert!(world.set_entity_render_order(decal, RenderLayer::GroundDecal, 0.0));
+        world.apply_pending();
+
+        let key = |id| {
+            world
+                .find_entity(id)
+                .expect("entity")
+                .renderer_overlap_order_key()
+        };
+        assert!(key(decal) < key(back));
+        assert!(key(back) < key(tie_late));
+        assert!(key(tie_late) < key(front));
+    }
+
+    #[test]
+    fn sort_pivot_offsets_y_and_layers_parse_by_name() {
+        let mut world = SceneWorld::default();
+        let tall = spawn_at(&mut world, 0.0, 0.0);
+        let short = spawn_at(&mut world, 0.0, -0.25);
+        world.apply_pending();
+        world.find_entity_mut(tall).expect("tall").sort_pivot_y = -0.5;
+        let tall_key = world
+            .find_entity(tall)
+            .expect("tall")
+            .renderer_overlap_order_key();
+        let short_key = world
+            .find_entity(short)
+            .expect("short")
+            .renderer_overlap_order_key();
+        assert!(tall_key > short_key);
+
+        for layer in RenderLayer::ALL {
+            assert_eq!(RenderLayer::parse(layer.as_str()), Some(layer));
+        }
+        assert_eq!(RenderLayer::parse("attic"), None);
+    }
+}
@@ -0,0 +1,1934 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use super::{
+    validate_floor_declaration, ActionParams, ActionState, ActionTargetHint, CardinalFacing,
+    DebugMarker, DebugMarkerKind, Entity, EntityActionVisual, EntityId, EntitySight, FactionId,
+    FloorId, Interactable, InteractableKind, LightAnchor, LightId, OrderState, PaletteSwapEntry,
+    PointLight, RenderLayer, RenderModifier, RenderableDesc, RenderableKind, SceneVisualState,
+    SceneWorld, SpriteAnchorPx, SpriteAnchors, StairLink, TileFlags, TileVisibility, Tilemap,
+    TimerId, Transform, Vec2, AMBIENT_UNLIT, LEGACY_FLOOR_NAMES, TILE_LAYER_TERRAIN,
+};
+
+pub const WORLD_SNAPSHOT_VERSION: u32 = 9;
+/// Oldest snapshot version `WorldSnapshot::from_json` still migrates.
+const MIN_WORLD_SNAPSHOT_VERSION: u32 = 1;
+const RESTORED_ENTITY_DEBUG_NAME: &str = "snapshot";
+
+#[derive(Debug, Error)]
+pub enum WorldSnapshotError {
+    #[error("unsupported world snapshot version: expected {expected}, got {actual}")]
+    UnsupportedVersion { expected: u32, actual: u32 },
+    #[error("invalid world snapshot at {path}: {message}")]
+    Invalid { path: String, message: String },
+    #[error("encode world snapshot json: {0}")]
+    Encode(#[source] serde_json::Error),
+    #[error("decode world snapshot json: {0}")]
+    Decode(#[source] serde_json::Error),
+}
+
+/// Versioned, engine-owned capture of one `SceneWorld`.
+///
+/// Entity ids inside a snapshot are the ids the entities had when captured. Restoring allocates
+/// fresh runtime ids and reports the mapping through `SnapshotEntityRemap`; pending spawn and
+/// despawn queues are never captured. Fields added after version 3 default when missing, so
+/// older documents only need `migrate_snapshot_json` for structural changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub version: u32,
+    pub floors: Vec<SnapshotFloor>,
+    pub default_floor: u16,
+    pub active_floor: u16,
+    pub camera: SnapshotCamera,
+    pub entities: Vec<SnapshotEntity>,
+    pub visual_state: SnapshotVisualState,
+    pub debug_markers: Vec<SnapshotDebugMarker>,
+    /// Pending timers in firing order.
+    #[serde(default)]
+    pub timers: Vec<SnapshotTimer>,
+    /// Point lights in id order.
+    #[serde(default)]
+    pub lights: Vec<SnapshotLight>,
+    #[serde(default)]
+    pub fog_viewer: Option<u8>,
+    /// Faction visibility grids in `(faction, floor)` order.
+    #[serde(default)]
+    pub visibility: Vec<SnapshotVisibilityGrid>,
+}
+
+/// One declared floor; its index in `WorldSnapshot::floors` is its `FloorId`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotFloor {
+    pub name: String,
+    pub tilemap: Option<SnapshotTilemap>,
+    /// Missing in game saves written before floor ambient existed; those load unlit.
+    #[serde(default = "unlit_ambient_rgb")]
+    pub ambient_rgb: [u8; 3],
+}
+
+fn unlit_ambient_rgb() -> [u8; 3] {
+    AMBIENT_UNLIT
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotVec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotCamera {
+    pub position: SnapshotVec2,
+    pub zoom: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotTilemap {
+    pub width: u32,
+    pub height: u32,
+    pub origin: SnapshotVec2,
+    pub layers: Vec<SnapshotTileLayer>,
+    pub flags: Vec<u16>,
+}
+
+/// One tilemap layer in row-major order; layer 0 must be the terrain layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotTileLayer {
+    pub name: String,
+    pub tiles: Vec<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotRenderableKind {
+    Placeholder,
+    Sprite {
+        key: String,
+        pixel_scale: u8,
+        anchors: SnapshotSpriteAnchors,
+        #[serde(default)]
+        mirror_west: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotSpriteAnchors {
+    pub hand: Option<(i16, i16)>,
+    pub carry: Option<(i16, i16)>,
+    pub muzzle: Option<(i16, i16)>,
+    pub light_origin: Option<(i16, i16)>,
+    pub tool: Option<(i16, i16)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SnapshotOrderState {
+    Idle,
+    MoveTo {
+        point: SnapshotVec2,
+    },
+    Interact {
+        target_save_id: u64,
+    },
+    Working {
+        target_save_id: u64,
+        remaining_time: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotInteractable {
+    pub kind: SnapshotInteractableKind,
+    pub interaction_radius: f32,
+    pub remaining_uses: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotInteractableKind {
+    ResourcePile,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEntity {
+    pub id: u64,
+    pub position: SnapshotVec2,
+    pub rotation_radians: Option<f32>,
+    pub renderable: SnapshotRenderableKind,
+    pub floor: u16,
+    pub selectable: bool,
+    pub actor: bool,
+    pub order_state: SnapshotOrderState,
+    pub interactable: Option<SnapshotInteractable>,
+    #[serde(default)]
+    pub stair: Option<SnapshotStairLink>,
+    #[serde(default)]
+    pub render_layer: SnapshotRenderLayer,
+    #[serde(default)]
+    pub sort_pivot_y: f32,
+    #[serde(default)]
+    pub sight: Option<SnapshotEntitySight>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEntitySight {
+    pub faction: u8,
+    pub radius: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotStairLink {
+    pub to_floor: u16,
+    pub to_position: SnapshotVec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotActionState {
+    Idle,
+    Walk,
+    Interact,
+    Carry,
+    UseTool,
+    Hit,
+    Downed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotRenderLayer {
+    GroundDecal,
+    FloorItem,
+    #[default]
+    Standing,
+    Roof,
+    Overlay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotFacing {
+    North,
+    South,
+    East,
+    West,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SnapshotActionTargetHint {
+    Entity(u64),
+    Point(SnapshotVec2),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEntityActionVisual {
+    pub entity_id: u64,
+    pub action_state: SnapshotActionState,
+    pub phase: f32,
+    pub intensity: f32,
+    pub speed01: f32,
+    pub facing: Option<SnapshotFacing>,
+    pub target_hint: Option<SnapshotActionTargetHint>,
+    pub is_looping: bool,
+    pub held_visual: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotVisualState {
+    pub selected_actor: Option<u64>,
+    pub hovered_interactable: Option<u64>,
+    pub targeted_interactable: Option<u64>,
+    pub entity_action_visuals: Vec<SnapshotEntityActionVisual>,
+    #[serde(default)]
+    pub entity_render_modifiers: Vec<SnapshotEntityRenderModifier>,
+}
+
+/// Render modifier of one entity; palette swap entries are `(from_rgb, to_rgb)` pairs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntityRenderModifier {
+    pub entity_id: u64,
+    pub tint_rgba: [u8; 4],
+    pub alpha: u8,
+    pub palette_swap: Vec<([u8; 3], [u8; 3])>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDebugMarker {
+    pub position_world: SnapshotVec2,
+    pub ttl_seconds: f32,
+}
+
+/// Pending timer with its due tick stored relative to the capture tick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotTimer {
+    pub id: u64,
+    pub owner: Option<u64>,
+    pub tag: String,
+    pub remaining_ticks: u32,
+    pub repeat_every_ticks: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SnapshotLightAnchor {
+    Entity(u64),
+    Tile { floor: u16, x: u32, y: u32 },
+    World { floor: u16, position: SnapshotVec2 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotLight {
+    pub id: u64,
+    pub anchor: SnapshotLightAnchor,
+    pub offset: SnapshotVec2,
+    pub radius: f32,
+    pub rgb: [u8; 3],
+    pub intensity: f32,
+}
+
+/// Row-major tile states of one faction on one floor: 0 unexplored, 1 explored, 2 visible.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotVisibilityGrid {
+    pub faction: u8,
+    pub floor: u16,
+    pub width: u32,
+    pub height: u32,
+    pub cells: Vec<u8>,
+}
+
+impl SnapshotVisibilityGrid {
+    pub fn validate(&self) -> Result<(), WorldSnapshotError> {
+        let expected = self.width as usize * self.height as usize;
+        if self.cells.len() != expected {
+            return Err(invalid(
+                "visibility.cells",
+                format!("expected {expected} cells, got {}", self.cells.len()),
+            ));
+        }
+        if let Some(cell) = self
+            .cells
+            .iter()
+            .find(|cell| usize::from(**cell) >= TileVisibility::ALL.len())
+        {
+            return Err(invalid(
+                "visibility.cells",
+                format!("unknown tile visibility {cell}"),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn from_grid(
+        faction: FactionId,
+        floor: FloorId,
+        width: u32,
+        height: u32,
+        cells: &[TileVisibility],
+    ) -> Self {
+        Self {
+            faction: faction.0,
+            floor: floor.0,
+            width,
+            height,
+            cells: cells
+                .iter()
+                .map(|cell| {
+                    TileVisibility::ALL
+                        .iter()
+                        .position(|candidate| candidate == cell)
+                        .unwrap_or(0) as u8
+                })
+                .collect(),
+        }
+    }
+
+    /// Expects a grid that passed `validate`.
+    pub fn to_cells(&self) -> Vec<TileVisibility> {
+        self.cells
+            .iter()
+            .map(|cell| TileVisibility::ALL[usize::from(*cell)])
+            .collect()
+    }
+}
+
+/// Mapping from snapshot entity ids to the runtime ids allocated by `restore_snapshot`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotEntityRemap {
+    runtime_by_snapshot_id: BTreeMap<u64, EntityId>,
+}
+
+impl SnapshotEntityRemap {
+    pub fn runtime_id(&self, snapshot_id: u64) -> Option<EntityId> {
+        self.runtime_by_snapshot_id.get(&snapshot_id).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.runtime_by_snapshot_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runtime_by_snapshot_id.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u64, EntityId)> + '_ {
+        self.runtime_by_snapshot_id
+            .iter()
+            .map(|(snapshot_id, runtime_id)| (*snapshot_id, *runtime_id))
+    }
+}
+
+impl WorldSnapshot {
+    pub fn to_json(&self) -> Result<String, WorldSnapshotError> {
+        serde_json::to_string_pretty(self).map_err(WorldSnapshotError::Encode)
+    }
+
+    /// Decodes and validates a snapshot, migrating documents written by older versions first.
+    pub fn from_json(raw: &str) -> Result<Self, WorldSnapshotError> {
+        let document = serde_json::from_str::<Value>(raw).map_err(WorldSnapshotError::Decode)?;
+        Self::from_json_value(document)
+    }
+
+    /// `from_json` for a snapshot already parsed as part of a larger document, such as a save.
+    pub fn from_json_value(mut document: Value) -> Result<Self, WorldSnapshotError> {
+        migrate_snapshot_json(&mut document)?;
+        let snapshot = serde_json::from_value::<WorldSnapshot>(document)
+            .map_err(WorldSnapshotError::Decode)?;
+        snapshot.validate()?;
+        Ok(snapshot)
+    }
+
+    pub fn validate(&self) -> Result<(), WorldSnapshotError> {
+        if self.version != WORLD_SNAPSHOT_VERSION {
+            return Err(WorldSnapshotError::UnsupportedVersion {
+                expected: WORLD_SNAPSHOT_VERSION,
+                actual: self.version,
+            });
+        }
+        require_finite_vec2("camera.position", self.camera.position)?;
+        require_finite("camera.zoom", self.camera.zoom)?;
+        let floor_names = self
+            .floors
+            .iter()
+            .map(|floor| floor.name.as_str())
+            .collect::<Vec<_>>();
+        validate_floor_declaration(&floor_names, FloorId(self.default_floor))
+            .map_err(|error| invalid("floors", error.to_string()))?;
+        let floor_count = self.floors.len();
+        require_floor("active_floor", self.active_floor, floor_count)?;
+        for (index, floor) in self.floors.iter().enumerate() {
+            if let Some(tilemap) = &floor.tilemap {
+                tilemap.validate().map_err(|error| match error {
+                    WorldSnapshotError::Invalid { path, message } => {
+                        invalid(&format!("floors[{index}].{path}"), message)
+                    }
+                    other => other,
+                })?;
+            }
+        }
+
+        let mut known_ids = HashSet::with_capacity(self.entities.len());
+        for (index, entity) in self.entities.iter().enumerate() {
+            if !known_ids.insert(entity.id) {
+                return Err(invalid(
+                    &format!("entities[{index}].id"),
+                    format!("duplicate entity id {}", entity.id),
+                ));
+            }
+            require_finite_vec2(&format!("entities[{index}].position"), entity.position)?;
+            if let Some(sight) = entity.sight {
+                if !sight.radius.is_finite() || sight.radius < 0.0 {
+                    return Err(invalid(
+                        &format!("entities[{index}].sight.radius"),
+                        "must be finite and >= 0",
+                    ));
+                }
+            }
+            require_floor(
+                &format!("entities[{index}].floor"),
+                entity.floor,
+                floor_count,
+            )?;
+            if let Some(stair) = entity.stair {
+                require_floor(
+                    &format!("entities[{index}].stair.to_floor"),
+                    stair.to_floor,
+                    floor_count,
+                )?;
+                require_finite_vec2(
+                    &format!("entities[{index}].stair.to_position"),
+                    stair.to_position,
+                )?;
+            }
+            require_finite(
+                &format!("entities[{index}].sort_pivot_y"),
+                entity.sort_pivot_y,
+            )?;
+            match entity.order_state {
+                SnapshotOrderState::MoveTo { point } => {
+                    require_finite_vec2(&format!("entities[{index}].order_state.point"), point)?;
+                }
+                SnapshotOrderState::Working { remaining_time, .. }
+                    if !remaining_time.is_finite() || remaining_time < 0.0 =>
+                {
+                    return Err(invalid(
+                        &format!("entities[{index}].order_state.remaining_time"),
+                        "must be finite and >= 0",
+                    ));
+                }
+                _ => {}
+            }
+            if let Some(interactable) = entity.interactable {
+                if !interactable.interaction_radius.is_finite()
+                    || interactable.interaction_radius < 0.0
+                {
+                    return Err(invalid(
+                        &format!("entities[{index}].interactable.interaction_radius"),
+                        "must be finite and >= 0",
+                    ));
+                }
+            }
+            if let Some(rotation_radians) = entity.rotation_radians {
+                require_finite(
+                    &format!("entities[{index}].rotation_radians"),
+                    rotation_radians,
+                )?;
+            }
+        }
+
+        for (path, reference) in [
+            (
+                "visual_state.selected_actor",
+                self.visual_state.selected_actor,
+            ),
+            (
+                "visual_state.hovered_interactable",
+                self.visual_state.hovered_interactable,
+            ),
+            (
+                "visual_state.targeted_interactable",
+                self.visual_state.targeted_interactable,
+            ),
+        ] {
+            if let Some(entity_id) = reference {
+                if !known_ids.contains(&entity_id) {
+                    return Err(invalid(
+                        path,
+                        format!("references unknown entity id {entity_id}"),
+                    ));
+                }
+            }
+        }
+        for (index, visual) in self.visual_state.entity_action_visuals.iter().enumerate() {
+            if !known_ids.contains(&visual.entity_id) {
+                return Err(invalid(
+                    &format!("visual_state.entity_action_visuals[{index}].entity_id"),
+                    format!("references unknown entity id {}", visual.entity_id),
+                ));
+            }
+        }
+        for (index, modifier) in self.visual_state.entity_render_modifiers.iter().enumerate() {
+            if !known_ids.contains(&modifier.entity_id) {
+                return Err(invalid(
+                    &format!("visual_state.entity_render_modifiers[{index}].entity_id"),
+                    format!("references unknown entity id {}", modifier.entity_id),
+                ));
+            }
+        }
+
+        let mut timer_ids = HashSet::with_capacity(self.timers.len());
+        for (index, timer) in self.timers.iter().enumerate() {
+            if !timer_ids.insert(timer.id) {
+                return Err(invalid(
+                    &format!("timers[{index}].id"),
+                    format!("duplicate timer id {}", timer.id),
+                ));
+            }
+            if timer.tag.is_empty() {
+                return Err(invalid(
+                    &format!("timers[{index}].tag"),
+                    "must not be empty",
+                ));
+            }
+            if timer.remaining_ticks == 0 {
+                return Err(invalid(
+                    &format!("timers[{index}].remaining_ticks"),
+                    "must be at least 1",
+                ));
+            }
+            if timer.repeat_every_ticks == Some(0) {
+                return Err(invalid(
+                    &format!("timers[{index}].repeat_every_ticks"),
+                    "must be at least 1",
+                ));
+            }
+            if let Some(owner) = timer.owner {
+                if !known_ids.contains(&owner) {
+                    return Err(invalid(
+                        &format!("timers[{index}].owner"),
+                        format!("references unknown entity id {owner}"),
+                    ));
+                }
+            }
+        }
+
+        let mut light_ids = HashSet::with_capacity(self.lights.len());
+        for (index, light) in self.lights.iter().enumerate() {
+            if !light_ids.insert(light.id) {
+                return Err(invalid(
+                    &format!("lights[{index}].id"),
+                    format!("duplicate light id {}", light.id),
+                ));
+            }
+            if !light.radius.is_finite() || light.radius <= 0.0 {
+                return Err(invalid(
+                    &format!("lights[{index}].radius"),
+                    "must be finite and > 0",
+                ));
+            }
+            if !light.intensity.is_finite() || light.intensity < 0.0 {
+                return Err(invalid(
+                    &format!("lights[{index}].intensity"),
+                    "must be finite and >= 0",
+                ));
+            }
+            require_finite_vec2(&format!("lights[{index}].offset"), light.offset)?;
+            match light.anchor {
+                SnapshotLightAnchor::Entity(entity_id) => {
+                    if !known_ids.contains(&entity_id) {
+                        return Err(invalid(
+                            &format!("lights[{index}].anchor"),
+                            format!("references unknown entity id {entity_id}"),
+                        ));
+                    }
+                }
+                SnapshotLightAnchor::Tile { floor, .. } => {
+                    require_floor(&format!("lights[{index}].anchor.floor"), floor, floor_count)?;
+                }
+                SnapshotLightAnchor::World { floor, position } => {
+                    require_floor(&format!("lights[{index}].anchor.floor"), floor, floor_count)?;
+                    require_finite_vec2(&format!("lights[{index}].anchor.position"), position)?;
+                }
+            }
+        }
+
+        let mut grid_keys = HashSet::with_capacity(self.visibility.len());
+        for (index, grid) in self.visibility.iter().enumerate() {
+            require_floor(
+                &format!("visibility[{index}].floor"),
+                grid.floor,
+                floor_count,
+            )?;
+            if !grid_keys.insert((grid.faction, grid.floor)) {
+                return Err(invalid(
+                    &format!("visibility[{index}]"),
+                    format!(
+                        "duplicate grid for faction {} on floor {}",
+                        grid.faction, grid.floor
+                    ),
+                ));
+            }
+            grid.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SceneWorld {
+    /// Captures applied entities with their sight, camera, floors with their tilemaps and ambient,
+    /// visual state, pending timers, point lights, and fog of war.
+    pub fn capture_snapshot(&self) -> WorldSnapshot {
+        let mut ordered = self.entities.iter().collect::<Vec<_>>();
+        ordered.sort_by_key(|entity| entity.applied_spawn_order);
+
+        let mut entity_action_visuals = self
+            .visual_state
+            .entity_action_visuals
+            .iter()
+            .map(|(entity_id, visual)| snapshot_action_visual(*entity_id, visual))
+            .collect::<Vec<_>>();
+        entity_action_visuals.sort_by_key(|visual| visual.entity_id);
+        let mut entity_render_modifiers = self
+            .visual_state
+            .entity_render_modifiers
+            .iter()
+            .map(|(entity_id, modifier)| SnapshotEntityRenderModifier {
+                entity_id: entity_id.0,
+                tint_rgba: modifier.tint_rgba,
+                alpha: modifier.alpha,
+                palette_swap: modifier
+                    .palette_swap
+                    .iter()
+                    .map(|entry| (entry.from_rgb, entry.to_rgb))
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        entity_render_modifiers.sort_by_key(|modifier| modifier.entity_id);
+
+        WorldSnapshot {
+            version: WORLD_SNAPSHOT_VERSION,
+            floors: self
+                .floor_ids()
+                .map(|floor| SnapshotFloor {
+                    name: self.floor_name(floor).unwrap_or_default().to_string(),
+                    tilemap: self.floor_tilemap(floor).map(SnapshotTilemap::from_tilemap),
+                    ambient_rgb: self.floor_ambient(floor),
+                })
+                .collect(),
+            default_floor: self.default_floor().0,
+            active_floor: self.active_floor.0,
+            camera: SnapshotCamera {
+                position: SnapshotVec2::from_vec2(self.camera.position),
+                zoom: self.camera.zoom,
+            },
+            entities: ordered
+                .into_iter()
+                .map(|entity| snapshot_entity(entity, self.entity_sight(entity.id)))
+                .collect(),
+            visual_state: SnapshotVisualState {
+                selected_actor: self.visual_state.selected_actor.map(|id| id.0),
+                hovered_interactable: self.visual_state.hovered_interactable.map(|id| id.0),
+                targeted_interactable: self.visual_state.targeted_interactable.map(|id| id.0),
+                entity_action_visuals,
+                entity_render_modifiers,
+            },
+            debug_markers: self
+                .debug_markers
+                .iter()
+                .map(|marker| SnapshotDebugMarker {
+                    position_world: SnapshotVec2::from_vec2(marker.position_world),
+                    ttl_seconds: marker.ttl_seconds,
+                })
+                .collect(),
+            timers: self
+                .timers()
+                .iter()
+                .map(|timer| SnapshotTimer {
+                    id: timer.id.0,
+                    owner: timer.owner.map(|owner| owner.0),
+                    tag: timer.tag.clone(),
+                    remaining_ticks: self.timer_remaining_ticks(timer),
+                    repeat_every_ticks: timer.repeat_every_ticks,
+                })
+                .collect(),
+            lights: self
+                .lights()
+                .map(|(id, light)| SnapshotLight {
+                    id: id.0,
+                    anchor: SnapshotLightAnchor::from_light_anchor(light.anchor),
+                    offset: SnapshotVec2::from_vec2(light.offset),
+                    radius: light.radius,
+                    rgb: light.rgb,
+                    intensity: light.intensity,
+                })
+                .collect(),
+            fog_viewer: self.fog_viewer().map(|faction| faction.0),
+            visibility: self
+                .visibility_grids()
+                .map(|(faction, floor, width, height, cells)| {
+                    SnapshotVisibilityGrid::from_grid(faction, floor, width, height, cells)
+                })
+                .collect(),
+        }
+    }
+
+    /// Replaces world runtime state with `snapshot`.
+    ///
+    /// Validation runs before any mutation. The def database resource is kept, entity ids are
+    /// freshly allocated (never reused), and applied spawn order follows snapshot order.
+    pub fn restore_snapshot(
+        &mut self,
+        snapshot: &WorldSnapshot,
+    ) -> Result<SnapshotEntityRemap, WorldSnapshotError> {
+        snapshot.validate()?;
+        let floor_tilemaps = snapshot
+            .floors
+            .iter()
+            .map(|floor| {
+                floor
+                    .tilemap
+                    .as_ref()
+                    .map(SnapshotTilemap::to_tilemap)
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let floor_names = snapshot
+            .floors
+            .iter()
+            .map(|floor| floor.name.as_str())
+            .collect::<Vec<_>>();
+
+        self.declare_floors(&floor_names, FloorId(snapshot.default_floor))
+            .map_err(|error| invalid("floors", error.to_string()))?;
+        for (index, tilemap) in floor_tilemaps.into_iter().enumerate() {
+            if let Some(tilemap) = tilemap {
+                self.set_floor_tilemap(FloorId(index as u16), tilemap);
+            }
+        }
+        for (index, floor) in snapshot.floors.iter().enumerate() {
+            self.set_floor_ambient(FloorId(index as u16), floor.ambient_rgb);
+        }
+        self.clear();
+        let mut remap = SnapshotEntityRemap::default();
+        for saved in &snapshot.entities {
+            let id = self.allocator.allocate();
+            remap.runtime_by_snapshot_id.insert(saved.id, id);
+            self.pending_spawns.push(Entity {
+                id,
+                transform: Transform {
+                    position: saved.position.to_vec2(),
+                    rotation_radians: saved.rotation_radians,
+                },
+                renderable: RenderableDesc {
+                    kind: saved.renderable.to_renderable_kind(),
+                    debug_name: RESTORED_ENTITY_DEBUG_NAME,
+                },
+                floor: FloorId(saved.floor),
+                selectable: saved.selectable,
+                actor: saved.actor,
+                order_state: saved.order_state.to_order_state(),
+                interactable: saved
+                    .interactable
+                    .map(SnapshotInteractable::to_interactable),
+                stair: saved.stair.map(|stair| StairLink {
+                    to_floor: FloorId(stair.to_floor),
+                    to_position: stair.to_position.to_vec2(),
+                }),
+                render_layer: saved.render_layer.to_render_layer(),
+                sort_pivot_y: saved.sort_pivot_y,
+                applied_spawn_order: 0,
+            });
+            if let Some(sight) = saved.sight {
+                self.visibility.restore_sight(
+                    id,
+                    EntitySight {
+                        faction: FactionId(sight.faction),
+                        radius: sight.radius,
+                    },
+                );
+            }
+        }
+        self.apply_pending();
+
+        self.active_floor = FloorId(snapshot.active_floor);
+        self.camera.position = snapshot.camera.position.to_vec2();
+        self.camera.set_zoom_clamped(snapshot.camera.zoom);
+
+        let remap_id = |id: Option<u64>| id.and_then(|id| remap.runtime_id(id));
+        self.visual_state = SceneVisualState {
+            selected_actor: remap_id(snapshot.visual_state.selected_actor),
+            hovered_interactable: remap_id(snapshot.visual_state.hovered_interactable),
+            targeted_interactable: remap_id(snapshot.visual_state.targeted_interactable),
+            entity_action_visuals: snapshot
+                .visual_state
+                .entity_action_visuals
+                .iter()
+                .filter_map(|visual| {
+                    let entity_id = remap.runtime_id(visual.entity_id)?;
+                    Some((entity_id, restore_action_visual(visual, &remap)))
+                })
+                .collect(),
+            entity_render_modifiers: snapshot
+                .visual_state
+                .entity_render_modifiers
+                .iter()
+                .filter_map(|modifier| {
+                    let entity_id = remap.runtime_id(modifier.entity_id)?;
+                    Some((
+                        entity_id,
+                        RenderModifier {
+                            tint_rgba: modifier.tint_rgba,
+                            alpha: modifier.alpha,
+                            palette_swap: modifier
+                                .palette_swap
+                                .iter()
+                                .map(|&(from_rgb, to_rgb)| PaletteSwapEntry { from_rgb, to_rgb })
+                                .collect(),
+                        },
+                    ))
+                })
+                .collect(),
+        };
+        self.debug_markers = snapshot
+            .debug_markers
+            .iter()
+            .map(|marker| DebugMarker {
+                kind: DebugMarkerKind::Order,
+                position_world: marker.position_world.to_vec2(),
+                ttl_seconds: marker.ttl_seconds,
+            })
+            .collect();
+        for timer in &snapshot.timers {
+            self.restore_timer(
+                TimerId(timer.id),
+                timer.owner.and_then(|owner| remap.runtime_id(owner)),
+                timer.tag.clone(),
+                timer.remaining_ticks,
+                timer.repeat_every_ticks,
+            );
+        }
+        for light in &snapshot.lights {
+            let Some(anchor) = light.anchor.to_light_anchor(&remap) else {
+                continue;
+            };
+            self.restore_light(
+                LightId(light.id),
+                PointLight {
+                    anchor,
+                    offset: light.offset.to_vec2(),
+                    radius: light.radius,
+                    rgb: light.rgb,
+                    intensity: light.intensity,
+                },
+            );
+        }
+        self.set_fog_viewer(snapshot.fog_viewer.map(FactionId));
+        for grid in &snapshot.visibility {
+            self.restore_visibility_grid(
+                FactionId(grid.faction),
+                FloorId(grid.floor),
+                grid.width,
+                grid.height,
+                grid.to_cells(),
+            );
+        }
+
+        Ok(remap)
+    }
+}
+
+fn snapshot_entity(entity: &Entity, sight: Option<EntitySight>) -> SnapshotEntity {
+    SnapshotEntity {
+        id: entity.id.0,
+        position: SnapshotVec2::from_vec2(entity.transform.position),
+        rotation_radians: entity.transform.rotation_radians,
+        renderable: SnapshotRenderableKind::from_renderable_kind(&entity.renderable.kind),
+        floor: entity.floor.0,
+        selectable: entity.selectable,
+        actor: entity.actor,
+        order_state: SnapshotOrderState::from_order_state(entity.order_state),
+        interactable: entity
+            .interactable
+            .map(SnapshotInteractable::from_interactable),
+        stair: entity.stair.map(|stair| SnapshotStairLink {
+            to_floor: stair.to_floor.0,
+            to_position: SnapshotVec2::from_vec2(stair.to_position),
+        }),
+        render_layer: SnapshotRenderLayer::from_render_layer(entity.render_layer),
+        sort_pivot_y: entity.sort_pivot_y,
+        sight: sight.map(|sight| SnapshotEntitySight {
+            faction: sight.faction.0,
+            radius: sight.radius,
+        }),
+    }
+}
+
+fn snapshot_action_visual(
+    entity_id: EntityId,
+    visual: &EntityActionVisual,
+) -> SnapshotEntityActionVisual {
+    let params = visual.action_params;
+    SnapshotEntityActionVisual {
+        entity_id: entity_id.0,
+        action_state: SnapshotActionState::from_action_state(visual.action_state),
+        phase: params.phase,
+        intensity: params.intensity,
+        speed01: params.speed01,
+        facing: params.facing.map(SnapshotFacing::from_facing),
+        target_hint: params.target_hint.map(|hint| match hint {
+            ActionTargetHint::Entity(id) => SnapshotActionTargetHint::Entity(id.0),
+            ActionTargetHint::Point(point) => {
+                SnapshotActionTargetHint::Point(SnapshotVec2::from_vec2(point))
+            }
+        }),
+        is_looping: params.is_looping,
+        held_visual: visual.held_visual.clone(),
+    }
+}
+
+fn restore_action_visual(
+    visual: &SnapshotEntityActionVisual,
+    remap: &SnapshotEntityRemap,
+) -> EntityActionVisual {
+    EntityActionVisual {
+        action_state: visual.action_state.to_action_state(),
+        action_params: ActionParams {
+            phase: visual.phase,
+            intensity: visual.intensity,
+            speed01: visual.speed01,
+            facing: visual.facing.map(SnapshotFacing::to_facing),
+            target_hint: visual.target_hint.and_then(|hint| match hint {
+                SnapshotActionTargetHint::Entity(id) => {
+                    remap.runtime_id(id).map(ActionTargetHint::Entity)
+                }
+                SnapshotActionTargetHint::Point(point) => {
+                    Some(ActionTargetHint::Point(point.to_vec2()))
+                }
+            }),
+            is_looping: visual.is_looping,
+        },
+        held_visual: visual.held_visual.clone(),
+    }
+}
+
+/// Rewrites an older snapshot document to `WORLD_SNAPSHOT_VERSION`, one structural step at a
+/// time; purely additive versions need no step because their new fields default on decode.
+fn migrate_snapshot_json(document: &mut Value) -> Result<(), WorldSnapshotError> {
+    let version = document
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| invalid("version", "expected an unsigned integer"))?;
+    let version = u32::try_from(version).unwrap_or(u32::MAX);
+    if !(MIN_WORLD_SNAPSHOT_VERSION..=WORLD_SNAPSHOT_VERSION).contains(&version) {
+        return Err(WorldSnapshotError::UnsupportedVersion {
+            expected: WORLD_SNAPSHOT_VERSION,
+            actual: version,
+        });
+    }
+    if version < 2 {
+        migrate_v1_terrain_tilemap(document);
+    }
+    if version < 3 {
+        migrate_v2_fixed_floors(document);
+    }
+    document["version"] = Value::from(WORLD_SNAPSHOT_VERSION);
+    Ok(())
+}
+
+/// v2 split the single tile array into named layers plus a per-tile flag channel.
+fn migrate_v1_terrain_tilemap(document: &mut Value) {
+    let Some(tilemap) = document.get_mut("tilemap").and_then(Value::as_object_mut) else {
+        return;
+    };
+    let Some(tiles) = tilemap.remove("tiles") else {
+        return;
+    };
+    let tile_count = tiles.as_array().map_or(0, Vec::len);
+    tilemap.insert(
+        "layers".to_string(),
+        json!([{ "name": TILE_LAYER_TERRAIN, "tiles": tiles }]),
+    );
+    tilemap.insert("flags".to_string(), Value::from(vec![0u16; tile_count]));
+}
+
+/// v3 replaced the fixed rooftop/main/basement floors and the one world tilemap with declared
+/// floors; the old tilemap moves onto the main floor and floor names become indices.
+fn migrate_v2_fixed_floors(document: &mut Value) {
+    let Some(object) = document.as_object_mut() else {
+        return;
+    };
+    let mut tilemap = object.remove("tilemap").unwrap_or(Value::Null);
+    let floors = LEGACY_FLOOR_NAMES
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let tilemap = if index == FloorId::MAIN.index() {
+                tilemap.take()
+            } else {
+                Value::Null
+            };
+            json!({ "name": name, "tilemap": tilemap })
+        })
+        .collect();
+    object.insert("floors".to_string(), Value::Array(floors));
+    object.insert("default_floor".to_string(), Value::from(FloorId::MAIN.0));
+    legacy_floor_name_to_index(object.get_mut("active_floor"));
+    if let Some(entities) = object.get_mut("entities").and_then(Value::as_array_mut) {
+        for entity in entities {
+            legacy_floor_name_to_index(entity.get_mut("floor"));
+        }
+    }
+}
+
+fn legacy_floor_name_to_index(floor: Option<&mut Value>) {
+    let Some(floor) = floor else {
+        return;
+    };
+    let index = floor.as_str().and_then(|name| {
+        LEGACY_FLOOR_NAMES
+            .iter()
+            .position(|legacy| legacy.eq_ignore_ascii_case(name))
+    });
+    if let Some(index) = index {
+        *floor = Value::from(index);
+    }
+}
+
+fn invalid(path: &str, message: impl Into<String>) -> WorldSnapshotError {
+    WorldSnapshotError::Invalid {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+fn require_finite(path: &str, value: f32) -> Result<(), WorldSnapshotError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(invalid(
+            path,
+            format!("expected finite number, got {value}"),
+        ))
+    }
+}
+
+fn require_finite_vec2(path: &str, value: SnapshotVec2) -> Result<(), WorldSnapshotError> {
+    require_finite(&format!("{path}.x"), value.x)?;
+    require_finite(&format!("{path}.y"), value.y)
+}
+
+fn require_floor(path: &str, floor: u16, floor_count: usize) -> Result<(), WorldSnapshotError> {
+    if (floor as usize) < floor_count {
+        Ok(())
+    } else {
+        Err(invalid(
+            path,
+            format!("floor {floor} is not declared ({floor_count} floors)"),
+        ))
+    }
+}
+
+impl SnapshotVec2 {
+    fn from_vec2(value: Vec2) -> Self {
+        Self {
+            x: value.x,
+            y: value.y,
+        }
+    }
+
+    fn to_vec2(self) -> Vec2 {
+        Vec2 {
+            x: self.x,
+            y: self.y,
+        }
+    }
+}
+
+impl SnapshotTilemap {
+    pub fn validate(&self) -> Result<(), WorldSnapshotError> {
+        require_finite_vec2("tilemap.origin", self.origin)?;
+        let expected = self.width as usize * self.height as usize;
+        if self.layers.first().map(|layer| layer.name.as_str()) != Some(TILE_LAYER_TERRAIN) {
+            return Err(invalid(
+                "tilemap.layers[0].name",
+                format!("expected first layer '{TILE_LAYER_TERRAIN}'"),
+            ));
+        }
+        let mut layer_names = HashSet::with_capacity(self.layers.len());
+        for (index, layer) in self.layers.iter().enumerate() {
+            if !layer_names.insert(layer.name.as_str()) {
+                return Err(invalid(
+                    &format!("tilemap.layers[{index}].name"),
+                    format!("duplicate layer name '{}'", layer.name),
+                ));
+            }
+            if layer.tiles.len() != expected {
+                return Err(invalid(
+                    &format!("tilemap.layers[{index}].tiles"),
+                    format!("expected {expected} tiles, got {}", layer.tiles.len()),
+                ));
+            }
+        }
+        if self.flags.len() != expected {
+            return Err(invalid(
+                "tilemap.flags",
+                format!("expected {expected} flags, got {}", self.flags.len()),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn from_tilemap(tilemap: &Tilemap) -> Self {
+        let (width, height) = (tilemap.width(), tilemap.height());
+        let row_major = |read: &dyn Fn(u32, u32) -> u16| {
+            (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| read(x, y))
+                .collect::<Vec<_>>()
+        };
+        Self {
+            width,
+            height,
+            origin: SnapshotVec2::from_vec2(tilemap.origin()),
+            layers: (0..tilemap.layer_count())
+                .map(|layer| SnapshotTileLayer {
+                    name: tilemap.layer_name(layer).unwrap_or_default().to_string(),
+                    tiles: row_major(&|x, y| tilemap.layer_tile_at(layer, x, y).unwrap_or(0)),
+                })
+                .collect(),
+            flags: row_major(&|x, y| tilemap.flags_at(x, y).unwrap_or_default().bits()),
+        }
+    }
+
+    pub fn to_tilemap(&self) -> Result<Tilemap, WorldSnapshotError> {
+        self.validate()?;
+        let to_invalid = |error: super::TilemapError| invalid("tilemap", error.to_string());
+        let terrain = self
+            .layers
+            .first()
+            .ok_or_else(|| invalid("tilemap.layers", "missing terrain layer"))?;
+        let mut tilemap = Tilemap::new(
+            self.width,
+            self.height,
+            self.origin.to_vec2(),
+            terrain.tiles.clone(),
+        )
+        .map_err(to_invalid)?;
+        for layer in self.layers.iter().skip(1) {
+            let layer_index = tilemap.add_layer(&layer.name).map_err(to_invalid)?;
+            for (index, tile_id) in layer.tiles.iter().copied().enumerate() {
+                let (x, y) = self.coord_of(index);
+                tilemap.set_layer_tile(layer_index, x, y, tile_id);
+            }
+        }
+        for (index, bits) in self.flags.iter().copied().enumerate() {
+            let (x, y) = self.coord_of(index);
+            tilemap.set_flags(x, y, TileFlags(bits));
+        }
+        Ok(tilemap)
+    }
+
+    fn coord_of(&self, index: usize) -> (u32, u32) {
+        let width = self.width.max(1) as usize;
+        ((index % width) as u32, (index / width) as u32)
+    }
+}
+
+impl SnapshotRenderableKind {
+    fn from_renderable_kind(kind: &RenderableKind) -> Self {
+        match kind {
+            RenderableKind::Placeholder => Self::Placeholder,
+            RenderableKind::Sprite {
+                key,
+                pixel_scale,
+                anchors,
+                mirror_west,
+            } => Self::Sprite {
+                key: key.clone(),
+                pixel_scale: *pixel_scale,
+                anchors: SnapshotSpriteAnchors::from_anchors(*anchors),
+                mirror_west: *mirror_west,
+            },
+        }
+    }
+
+    fn to_renderable_kind(&self) -> RenderableKind {
+        match self {
+            Self::Placeholder => RenderableKind::Placeholder,
+            Self::Sprite {
+                key,
+                pixel_scale,
+                anchors,
+                mirror_west,
+            } => RenderableKind::Sprite {
+                key: key.clone(),
+                pixel_scale: *pixel_scale,
+                anchors: anchors.to_anchors(),
+                mirror_west: *mirror_west,
+            },
+        }
+    }
+}
+
+impl SnapshotSpriteAnchors {
+    fn from_anchors(anchors: SpriteAnchors) -> Self {
+        let pair = |anchor: Option<SpriteAnchorPx>| anchor.map(|px| (px.x_px, px.y_px));
+        Self {
+            hand: pair(anchors.hand),
+            carry: pair(anchors.carry),
+            muzzle: pair(anchors.muzzle),
+            light_origin: pair(anchors.light_origin),
+            tool: pair(anchors.tool),
+        }
+    }
+
+    fn to_anchors(self) -> SpriteAnchors {
+        let anchor =
+            |pair: Option<(i16, i16)>| pair.map(|(x_px, y_px)| SpriteAnchorPx { x_px, y_px });
+        SpriteAnchors {
+            hand: anchor(self.hand),
+            carry: anchor(self.carry),
+            muzzle: anchor(self.muzzle),
+            light_origin: anchor(self.light_origin),
+            tool: anchor(self.tool),
+        }
+    }
+}
+
+impl SnapshotOrderState {
+    fn from_order_state(order_state: OrderState) -> Self {
+        match order_state {
+            OrderState::Idle => Self::Idle,
+            OrderState::MoveTo { point } => Self::MoveTo {
+                point: SnapshotVec2::from_vec2(point),
+            },
+            OrderState::Interact { target_save_id } => Self::Interact { target_save_id },
+            OrderState::Working {
+                target_save_id,
+                remaining_time,
+            } => Self::Working {
+                target_save_id,
+                remaining_time,
+            },
+        }
+    }
+
+    fn to_order_state(self) -> OrderState {
+        match self {
+            Self::Idle => OrderState::Idle,
+            Self::MoveTo { point } => OrderState::MoveTo {
+                point: point.to_vec2(),
+            },
+            Self::Interact { target_save_id } => OrderState::Interact { target_save_id },
+            Self::Working {
+                target_save_id,
+                remaining_time,
+            } => OrderState::Working {
+                target_save_id,
+                remaining_time,
+            },
+        }
+    }
+}
+
+impl SnapshotInteractable {
+    fn from_interactable(interactable: Interactable) -> Self {
+        Self {
+            kind: match interactable.kind {
+                InteractableKind::ResourcePile => SnapshotInteractableKind::ResourcePile,
+            },
+            interaction_radius: interactable.interaction_radius,
+            remaining_uses: interactable.remaining_uses,
+        }
+    }
+
+    fn to_interactable(self) -> Interactable {
+        Interactable {
+            kind: match self.kind {
+                SnapshotInteractableKind::ResourcePile => InteractableKind::ResourcePile,
+            },
+            interaction_radius: self.interaction_radius,
+            remaining_uses: self.remaining_uses,
+        }
+    }
+}
+
+impl SnapshotActionState {
+    fn from_action_state(action_state: ActionState) -> Self {
+        match action_state {
+            ActionState::Idle => Self::Idle,
+            ActionState::Walk => Self::Walk,
+            ActionState::Interact => Self::Interact,
+            ActionState::Carry => Self::Carry,
+            ActionState::UseTool => Self::UseTool,
+            ActionState::Hit => Self::Hit,
+            ActionState::Downed => Self::Downed,
+        }
+    }
+
+    fn to_action_state(self) -> ActionState {
+        match self {
+            Self::Idle => ActionState::Idle,
+            Self::Walk => ActionState::Walk,
+            Self::Interact => ActionState::Interact,
+            Self::Carry => ActionState::Carry,
+            Self::UseTool => ActionState::UseTool,
+            Self::Hit => ActionState::Hit,
+            Self::Downed => ActionState::Downed,
+        }
+    }
+}
+
+impl SnapshotRenderLayer {
+    fn from_render_layer(layer: RenderLayer) -> Self {
+        match layer {
+            RenderLayer::GroundDecal => Self::GroundDecal,
+            RenderLayer::FloorItem => Self::FloorItem,
+            RenderLayer::Standing => Self::Standing,
+            RenderLayer::Roof => Self::Roof,
+            RenderLayer::Overlay => Self::Overlay,
+        }
+    }
+
+    fn to_render_layer(self) -> RenderLayer {
+        match self {
+            Self::GroundDecal => RenderLayer::GroundDecal,
+            Self::FloorItem => RenderLayer::FloorItem,
+            Self::Standing => RenderLayer::Standing,
+            Self::Roof => RenderLayer::Roof,
+            Self::Overlay => RenderLayer::Overlay,
+        }
+    }
+}
+
+impl SnapshotFacing {
+    fn from_facing(facing: CardinalFacing) -> Self {
+        match facing {
+            CardinalFacing::North => Self::North,
+            CardinalFacing::South => Self::South,
+            CardinalFacing::East => Self::East,
+            CardinalFacing::West => Self::West,
+        }
+    }
+
+    fn to_facing(self) -> CardinalFacing {
+        match self {
+            Self::North => CardinalFacing::North,
+            Self::South => CardinalFacing::South,
+            Self::East => CardinalFacing::East,
+            Self::West => CardinalFacing::West,
+        }
+    }
+}
+
+impl SnapshotLightAnchor {
+    fn from_light_anchor(anchor: LightAnchor) -> Self {
+        match anchor {
+            LightAnchor::Entity(entity_id) => Self::Entity(entity_id.0),
+            LightAnchor::Tile { floor, x, y } => Self::Tile {
+                floor: floor.0,
+                x,
+                y,
+            },
+            LightAnchor::World { floor, position } => Self::World {
+                floor: floor.0,
+                position: SnapshotVec2::from_vec2(position),
+            },
+        }
+    }
+
+    fn to_light_anchor(self, remap: &SnapshotEntityRemap) -> Option<LightAnchor> {
+        Some(match self {
+            Self::Entity(entity_id) => LightAnchor::Entity(remap.runtime_id(entity_id)?),
+            Self::Tile { floor, x, y } => LightAnchor::Tile {
+                floor: FloorId(floor),
+                x,
+                y,
+            },
+            Self::World { floor, position } => LightAnchor::World {
+                floor: FloorId(floor),
+                position: position.to_vec2(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn_placeholder(world: &mut SceneWorld, x: f32, y: f32) -> EntityId {
+        world.spawn(
+            Transform {
+                position: Vec2 { x, y },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "snapshot_test",
+            },
+        )
+    }
+
+    fn populated_world() -> (SceneWorld, EntityId, EntityId) {
+        let mut world = SceneWorld::default();
+        let mut tilemap =
+            Tilemap::new(2, 2, Vec2 { x: -1.0, y: -1.0 }, vec![0, 1, 1, 0]).expect("tilemap");
+        let walls = tilemap.add_layer("walls").expect("walls layer");
+        tilemap.set_layer_tile(walls, 1, 0, 4);
+        tilemap.set_flags(1, 0, TileFlags::BLOCKS_MOVEMENT);
+        world.set_tilemap(tilemap);
+        let actor = world.spawn_actor(
+            Transform {
+                position: Vec2 { x: 1.5, y: -2.0 },
+                rotation_radians: Some(0.25),
+            },
+            RenderableDesc {
+                kind: RenderableKind::Sprite {
+                    key: "player".to_string(),
+                    pixel_scale: 2,
+                    anchors: SpriteAnchors {
+                        hand: Some(SpriteAnchorPx { x_px: 3, y_px: -4 }),
+                        ..SpriteAnchors::default()
+                    },
+                    mirror_west: false,
+                },
+                debug_name: "actor",
+            },
+        );
+        world.set_active_floor(FloorId::BASEMENT);
+        let pile = spawn_placeholder(&mut world, 4.0, 5.0);
+        world.apply_pending();
+        world.find_entity_mut(pile).expect("pile").interactable = Some(Interactable {
+            kind: InteractableKind::ResourcePile,
+            interaction_radius: 0.75,
+            remaining_uses: 2,
+        });
+        world.set_entity_render_modifier(
+            pile,
+            RenderModifier {
+                tint_rgba: [255, 200, 200, 255],
+                alpha: 160,
+                palette_swap: vec![PaletteSwapEntry {
+                    from_rgb: [1, 2, 3],
+                    to_rgb: [4, 5, 6],
+                }],
+            },
+        );
+        world.find_entity_mut(pile).expect("pile").render_layer = RenderLayer::FloorItem;
+        world.find_entity_mut(pile).expect("pile").sort_pivot_y = -0.25;
+        world.find_entity_mut(pile).expect("pile").stair = Some(StairLink {
+            to_floor: FloorId::MAIN,
+            to_position: Vec2 { x: 0.5, y: 0.5 },
+        });
+        world.set_tilemap(Tilemap::new(1, 1, Vec2 { x: 0.0, y: 0.0 }, vec![3]).expect("tilemap"));
+        world.find_entity_mut(actor).expect("actor").order_state = OrderState::Working {
+            target_save_id: 9,
+            remaining_time: 1.5,
+        };
+        world.camera_mut().position = Vec2 { x: 3.0, y: -1.0 };
+        world.camera_mut().set_zoom_clamped(1.5);
+        world.set_selected_actor_visual(Some(actor));
+        world.set_targeted_interactable_visual(Some(pile));
+        world.set_entity_action_visual(
+            actor,
+            EntityActionVisual {
+                action_state: ActionState::UseTool,
+                action_params: ActionParams {
+                    facing: Some(CardinalFacing::West),
+                    target_hint: Some(ActionTargetHint::Entity(pile)),
+                    ..ActionParams::default()
+                },
+                held_visual: Some("proto.visual_carry_item".to_string()),
+            },
+        );
+        world.push_debug_marker(DebugMarker {
+            kind: DebugMarkerKind::Order,
+            position_world: Vec2 { x: 0.5, y: 0.5 },
+            ttl_seconds: 0.5,
+        });
+        world.tick_timers();
+        world
+            .schedule_repeating_timer(Some(pile), "regrow", 3, 5)
+            .expect("regrow");
+        world.schedule_timer(None, "wave", 2).expect("wave");
+        world.set_floor_ambient(FloorId::BASEMENT, [30, 34, 52]);
+        world
+            .add_light(PointLight {
+                anchor: LightAnchor::Entity(pile),
+                offset: Vec2 { x: 0.0, y: 0.25 },
+                radius: 3.0,
+                rgb: [255, 210, 140],
+                intensity: 1.25,
+            })
+            .expect("pile light");
+        world
+            .add_light(PointLight {
+                anchor: LightAnchor::Tile {
+                    floor: FloorId::MAIN,
+                    x: 1,
+                    y: 1,
+                },
+                offset: Vec2 { x: 0.0, y: 0.0 },
+                radius: 2.0,
+                rgb: [120, 160, 255],
+                intensity: 1.0,
+            })
+            .expect("tile light");
+        world
+            .set_entity_sight(
+                actor,
+                EntitySight {
+                    faction: FactionId(2),
+                    radius: 4.0,
+                },
+            )
+            .expect("sight");
+        world.set_fog_viewer(Some(FactionId(2)));
+        world.update_visibility();
+        (world, actor, pile)
+    }
+
+    #[test]
+    fn capture_restore_round_trip_preserves_world_state() {
+        let (world, actor, pile) = populated_world();
+        let snapshot = world.capture_snapshot();
+
+        let mut restored = SceneWorld::default();
+        let remap = restored.restore_snapshot(&snapshot).expect("restore");
+
+        assert_eq!(remap.len(), 2);
+        assert_eq!(restored.capture_snapshot().entities.len(), 2);
+        let restored_actor = remap.runtime_id(actor.0).expect("actor remap");
+        let restored_pile = remap.runtime_id(pile.0).expect("pile remap");
+        let entity = restored.find_entity(restored_actor).expect("actor");
+        assert!(entity.actor);
+        assert_eq!(entity.transform.position, Vec2 { x: 1.5, y: -2.0 });
+        assert_eq!(entity.transform.rotation_radians, Some(0.25));
+        assert_eq!(entity.floor, FloorId::MAIN);
+        assert_eq!(
+            entity.order_state,
+            OrderState::Working {
+                target_save_id: 9,
+                remaining_time: 1.5
+            }
+        );
+        let pile_entity = restored.find_entity(restored_pile).expect("pile");
+        assert_eq!(pile_entity.floor, FloorId::BASEMENT);
+        assert_eq!(pile_entity.render_layer, RenderLayer::FloorItem);
+        assert_eq!(
+            restored.entity_render_modifier(restored_pile),
+            world.entity_render_modifier(pile)
+        );
+        assert_eq!(pile_entity.sort_pivot_y, -0.25);
+        assert_eq!(restored.floor_ambient(FloorId::BASEMENT), [30, 34, 52]);
+        assert_eq!(
+            restored.floor_ambient(FloorId::MAIN),
+            world.floor_ambient(FloorId::MAIN)
+        );
+        let restored_lights = restored
+            .lights()
+            .map(|(_, light)| *light)
+            .collect::<Vec<_>>();
+        assert_eq!(restored_lights.len(), 2);
+        assert_eq!(
+            restored_lights[0].anchor,
+            LightAnchor::Entity(restored_pile)
+        );
+        assert_eq!(restored_lights[0].radius, 3.0);
+        assert_eq!(
+            restored.resolved_lights_on_floor(FloorId::MAIN),
+            world.resolved_lights_on_floor(FloorId::MAIN)
+        );
+        assert_eq!(
+            pile_entity.interactable.map(|value| value.remaining_uses),
+            Some(2)
+        );
+        assert_eq!(restored.active_floor(), FloorId::BASEMENT);
+        assert_eq!(restored.camera().position, Vec2 { x: 3.0, y: -1.0 });
+        assert_eq!(
+            pile_entity.stair.map(|stair| stair.to_floor),
+            Some(FloorId::MAIN)
+        );
+        assert_eq!(restored.floor_count(), 3);
+        assert_eq!(restored.tilemap(), world.tilemap());
+        assert_eq!(
+            restored.tilemap().and_then(|map| map.tile_at(0, 0)),
+            Some(3)
+        );
+        let restored_tilemap = restored.floor_tilemap(FloorId::MAIN).expect("tilemap");
+        assert_eq!(restored_tilemap.layer_index("walls"), Some(1));
+        assert_eq!(restored_tilemap.layer_tile_at(1, 1, 0), Some(4));
+        assert_eq!(
+            restored_tilemap.flags_at(1, 0),
+            Some(TileFlags::BLOCKS_MOVEMENT)
+        );
+        assert_eq!(restored.visual_state().selected_actor, Some(restored_actor));
+        assert_eq!(
+            restored.visual_state().targeted_interactable,
+            Some(restored_pile)
+        );
+        let visual = restored.entity_action_visual(restored_actor);
+        assert_eq!(visual.action_state, ActionState::UseTool);
+        assert_eq!(
+            visual.action_params.target_hint,
+            Some(ActionTargetHint::Entity(restored_pile))
+        );
+        assert_eq!(restored.debug_markers().len(), 1);
+        assert_eq!(
+            restored.entity_sight(restored_actor),
+            world.entity_sight(actor)
+        );
+        assert_eq!(restored.fog_viewer(), Some(FactionId(2)));
+        assert!(restored.visibility_grids().count() > 0);
+        assert!(restored.visibility_grids().eq(world.visibility_grids()));
+    }
+
+    #[test]
+    fn restore_keeps_timer_order_remaining_ticks_and_remapped_owners() {
+        let (world, _actor, pile) = populated_world();
+        let snapshot = world.capture_snapshot();
+        assert_eq!(
+            snapshot
+                .timers
+                .iter()
+                .map(|timer| (timer.tag.as_str(), timer.remaining_ticks))
+                .collect::<Vec<_>>(),
+            vec![("wave", 2), ("regrow", 3)]
+        );
+
+        let mut restored = SceneWorld::default();
+        restored.schedule_timer(None, "stale", 1).expect("stale");
+        let remap = restored.restore_snapshot(&snapshot).expect("restore");
+        let restored_pile = remap.runtime_id(pile.0).expect("pile remap");
+        assert_eq!(restored.timers().len(), 2);
+        assert_eq!(
+            restored
+                .find_owner_timer(restored_pile, "regrow")
+                .and_then(|timer| timer.repeat_every_ticks),
+            Some(5)
+        );
+
+        restored.tick_timers();
+        restored.tick_timers();
+        assert_eq!(restored.fired_timers()[0].tag, "wave");
+        restored.tick_timers();
+        assert_eq!(restored.fired_timers()[0].owner, Some(restored_pile));
+        assert_eq!(restored.capture_snapshot().timers.len(), 1);
+
+        let mut bad = snapshot.clone();
+        bad.timers[0].owner = Some(404);
+        let error = restored.restore_snapshot(&bad).expect_err("unknown owner");
+        assert!(error.to_string().contains("timers[0].owner"), "{error}");
+    }
+
+    #[test]
+    fn restore_allocates_fresh_ids_and_keeps_spawn_order() {
+        let (mut world, actor, pile) = populated_world();
+        let snapshot = world.capture_snapshot();
+
+        let remap = world.restore_snapshot(&snapshot).expect("restore");
+        let restored_actor = remap.runtime_id(actor.0).expect("actor");
+        let restored_pile = remap.runtime_id(pile.0).expect("pile");
+
+        assert_ne!(restored_actor, actor);
+        assert_ne!(restored_pile, pile);
+        assert!(world.find_entity(actor).is_none());
+        let actor_order = world
+            .find_entity(restored_actor)
+            .expect("actor")
+            .renderer_overlap_order_key()
+            .spawn_order;
+        let pile_order = world
+            .find_entity(restored_pile)
+            .expect("pile")
+            .renderer_overlap_order_key()
+            .spawn_order;
+        assert!(actor_order < pile_order);
+    }
+
+    #[test]
+    fn json_round_trip_is_lossless() {
+        let (world, _, _) = populated_world();
+        let snapshot = world.capture_snapshot();
+
+        let json = snapshot.to_json().expect("encode");
+        let decoded = WorldSnapshot::from_json(&json).expect("decode");
+
+        assert_eq!(decoded, snapshot);
+    }
+
+    #[test]
+    fn capture_is_deterministic_across_calls() {
+        let (world, _, _) = populated_world();
+
+        assert_eq!(world.capture_snapshot(), world.capture_snapshot());
+    }
+
+    #[test]
+    fn restore_rejects_invalid_snapshot_without_mutating_world() {
+        let (mut world, actor, _) = populated_world();
+        let mut snapshot = world.capture_snapshot();
+        snapshot.visual_state.selected_actor = Some(999);
+
+        let error = world.restore_snapshot(&snapshot).expect_err("invalid");
+
+        assert!(error.to_string().contains("visual_state.selected_actor"));
+        assert!(world.find_entity(actor).is_some());
+        assert_eq!(world.entity_count(), 2);
+    }
+
+    #[test]
+    fn restore_rejects_unsupported_version() {
+        let (world, _, _) = populated_world();
+        let mut snapshot = world.capture_snapshot();
+        snapshot.version = WORLD_SNAPSHOT_VERSION + 1;
+
+        let error = SceneWorld::default()
+            .restore_snapshot(&snapshot)
+            .expect_err("version");
+
+        assert!(matches!(
+            error,
+            WorldSnapshotError::UnsupportedVersion { .. }
+        ));
+    }
+
+    #[test]
+    fn from_json_rejects_versions_outside_the_migration_range() {
+        let (world, _, _) = populated_world();
+        for version in [0, WORLD_SNAPSHOT_VERSION + 1] {
+            let mut snapshot = world.capture_snapshot();
+            snapshot.version = version;
+            let raw = snapshot.to_json().expect("encode");
+
+            let error = WorldSnapshot::from_json(&raw).expect_err("version");
+
+            assert!(matches!(
+                error,
+                WorldSnapshotError::UnsupportedVersion { actual, .. } if actual == version
+            ));
+        }
+    }
+
+    #[test]
+    fn from_json_migrates_version_1_snapshots() {
+        let raw = r#"{
+            "version": 1,
+            "active_floor": "Basement",
+            "camera": { "position": { "x": 3.0, "y": -1.0 }, "zoom": 2.0 },
+            "tilemap": {
+                "width": 2,
+                "height": 1,
+                "origin": { "x": -1.0, "y": 0.0 },
+                "tiles": [5, 6]
+            },
+            "entities": [{
+                "id": 7,
+                "position": { "x": 0.5, "y": 0.5 },
+                "rotation_radians": null,
+                "renderable": {
+                    "Sprite": {
+                        "key": "pawn",
+                        "pixel_scale": 2,
+                        "anchors": {
+                            "hand": null,
+                            "carry": null,
+                            "muzzle": null,
+                            "light_origin": null,
+                            "tool": null
+                        }
+                    }
+                },
+                "floor": "Rooftop",
+                "selectable": true,
+                "actor": true,
+                "order_state": "Idle",
+                "interactable": null
+            }],
+            "visual_state": {
+                "selected_actor": 7,
+                "hovered_interactable": null,
+                "targeted_interactable": null,
+                "entity_action_visuals": []
+            },
+            "debug_markers": []
+        }"#;
+
+        let snapshot = WorldSnapshot::from_json(raw).expect("migrated");
+        let mut world = SceneWorld::default();
+        let remap = world.restore_snapshot(&snapshot).expect("restore");
+
+        assert_eq!(snapshot.version, WORLD_SNAPSHOT_VERSION);
+        assert_eq!(world.floor_count(), LEGACY_FLOOR_NAMES.len());
+        assert_eq!(world.default_floor(), FloorId::MAIN);
+        assert_eq!(world.active_floor(), FloorId::BASEMENT);
+        assert!(world.floor_tilemap(FloorId::BASEMENT).is_none());
+        let tilemap = world.floor_tilemap(FloorId::MAIN).expect("main tilemap");
+        assert_eq!(tilemap.layer_count(), 1);
+        assert_eq!(tilemap.layer_tile_at(0, 1, 0), Some(6));
+        assert_eq!(tilemap.flags_at(1, 0), Some(TileFlags::NONE));
+        let entity = world
+            .find_entity(remap.runtime_id(7).expect("remapped"))
+            .expect("entity");
+        assert_eq!(entity.floor, FloorId::ROOFTOP);
+        assert_eq!(entity.render_layer, RenderLayer::Standing);
+        assert!(snapshot.timers.is_empty() && snapshot.lights.is_empty());
+        assert_eq!(snapshot.floors[1].ambient_rgb, AMBIENT_UNLIT);
+    }
+
+    #[test]
+    fn restore_rejects_duplicate_entity_ids_and_bad_tile_counts() {
+        let (world, _, _) = populated_world();
+        let mut duplicate = world.capture_snapshot();
+        let first = duplicate.entities[0].clone();
+        duplicate.entities.push(first);
+        assert!(duplicate.validate().is_err());
+
+        let mut bad_tiles = world.capture_snapshot();
+        bad_tiles.floors[1]
+            .tilemap
+            .as_mut()
+            .expect("tilemap")
+            .layers[1]
+            .tiles
+            .pop();
+        assert!(bad_tiles.validate().is_err());
+
+        let mut bad_fog = world.capture_snapshot();
+        bad_fog.visibility[0].cells[0] = 3;
+        assert!(bad_fog.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_bad_order_state_and_interactable_numbers() {
+        let (world, _, _) = populated_world();
+        let mut bad_work = world.capture_snapshot();
+        bad_work.entities[0].order_state = SnapshotOrderState::Working {
+            target_save_id: 0,
+            remaining_time: -0.1,
+        };
+        let error = bad_work.validate().expect_err("negative remaining time");
+        assert!(error
+            .to_string()
+            .contains("entities[0].order_state.remaining_time"));
+
+        let mut bad_move = world.capture_snapshot();
+        bad_move.entities[0].order_state = SnapshotOrderState::MoveTo {
+            point: SnapshotVec2 {
+                x: f32::NEG_INFINITY,
+                y: 0.0,
+            },
+        };
+        let error = bad_move.validate().expect_err("non-finite move target");
+        assert!(error.to_string().contains("entities[0].order_state.point"));
+
+        let mut bad_radius = world.capture_snapshot();
+        bad_radius.entities[0].interactable = Some(SnapshotInteractable {
+            kind: SnapshotInteractableKind::ResourcePile,
+            interaction_radius: -0.1,
+            remaining_uses: 1,
+        });
+        let error = bad_radius.validate().expect_err("negative radius");
+        assert!(error
+            .to_string()
+            .contains("entities[0].interactable.interaction_radius"));
+    }
+
+    #[test]
+    fn restore_declares_snapshot_floors_and_rejects_undeclared_floor_refs() {
+        let mut world = SceneWorld::default();
+        world
+            .declare_floors(&["ground", "loft"], FloorId(0))
+            .expect("declare");
+        world.set_active_floor(FloorId(1));
+        spawn_placeholder(&mut world, 0.0, 0.0);
+        world.apply_pending();
+        let snapshot = world.capture_snapshot();
+
+        let mut restored = SceneWorld::default();
+        restored.restore_snapshot(&snapshot).expect("restore");
+        assert_eq!(restored.floor_count(), 2);
+        assert_eq!(restored.floor_name(FloorId(1)), Some("loft"));
+        assert_eq!(restored.active_floor(), FloorId(1));
+        assert_eq!(restored.default_floor(), FloorId(0));
+
+        let mut bad_floor = snapshot.clone();
+        bad_floor.entities[0].floor = 2;
+        let error = bad_floor.validate().expect_err("undeclared floor");
+        assert!(error.to_string().contains("entities[0].floor"));
+    }
+
+    #[test]
+    fn restore_without_tilemap_clears_existing_tilemap() {
+        let (mut world, _, _) = populated_world();
+        let mut snapshot = world.capture_snapshot();
+        for floor in &mut snapshot.floors {
+            floor.tilemap = None;
+        }
+        let epoch_before = world.tilemap_epoch();
+
+        world.restore_snapshot(&snapshot).expect("restore");
+
+        assert!(world
+            .floor_ids()
+            .all(|floor| world.floor_tilemap(floor).is_none()));
+        assert!(world.tilemap_epoch() > epoch_before);
+    }
+}
@@ -0,0 +1,643 @@
+use std::collections::vec_deque;
+use std::ops::BitOr;
+
+use thiserror::Error;
+
+use super::{FloorId, SceneWorld, Vec2};
+
+/// Edge length (in tiles) of one storage chunk.
+pub const TILEMAP_CHUNK_SIZE: u32 = 16;
+/// Name of the base layer created by `Tilemap::new`.
+pub const TILE_LAYER_TERRAIN: &str = "terrain";
+/// Tile id stored in non-terrain layers where nothing is drawn.
+pub const TILE_EMPTY: u16 = u16::MAX;
+
+/// Edits retained for `SceneWorld::tilemap_changes_since`; older history forces a full resync.
+pub const TILEMAP_CHANGE_LOG_CAPACITY: usize = 256;
+
+const CHUNK_TILE_COUNT: usize = (TILEMAP_CHUNK_SIZE * TILEMAP_CHUNK_SIZE) as usize;
+
+/// Per-tile flag channel shared by all layers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TileFlags(pub u16);
+
+impl TileFlags {
+    pub const NONE: Self = Self(0);
+    pub const BLOCKS_MOVEMENT: Self = Self(1 << 0);
+    pub const BLOCKS_SIGHT: Self = Self(1 << 1);
+
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for TileFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TileChunkCoord {
+    pub x: u32,
+    pub y: u32,
+}
+
+/// Tile-space rectangle; `x`/`y` is the bottom-left tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl TileRect {
+    pub fn single(x: u32, y: u32) -> Self {
+        Self {
+            x,
+            y,
+            width: 1,
+            height: 1,
+        }
+    }
+
+    fn clipped_to(self, width: u32, height: u32) -> Option<Self> {
+        let x_max = self.x.saturating_add(self.width).min(width);
+        let y_max = self.y.saturating_add(self.height).min(height);
+        if self.x >= x_max || self.y >= y_max {
+            return None;
+        }
+        Some(Self {
+            x: self.x,
+            y: self.y,
+            width: x_max - self.x,
+            height: y_max - self.y,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TileChannel {
+    Layer(usize),
+    Flags,
+}
+
+/// One applied tilemap edit. `rect` is the bounding box of tiles whose value actually changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TilemapChange {
+    pub epoch: u64,
+    pub floor: FloorId,
+    pub channel: TileChannel,
+    pub rect: TileRect,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TileChunk {
+    layers: Vec<Vec<u16>>,
+    flags: Vec<TileFlags>,
+}
+
+/// Layered tile grid stored in `TILEMAP_CHUNK_SIZE` square chunks.
+///
+/// Origin convention:
+/// - `origin` is the world position of tile (0,0) bottom-left corner.
+/// - The center of tile (x,y) is `origin + (x + 0.5, y + 0.5)`.
+///
+/// Layers draw in index order; layer 0 is always `TILE_LAYER_TERRAIN`. Chunk epochs are stamped
+/// by `SceneWorld` when the map is installed or edited, so caches can rebuild only the chunks
+/// whose epoch moved past the one they last saw.
+#[derive(Debug, Clone)]
+pub struct Tilemap {
+    width: u32,
+    height: u32,
+    origin: Vec2,
+    layer_names: Vec<String>,
+    chunks: Vec<TileChunk>,
+    chunk_epochs: Vec<u64>,
+}
+
+impl PartialEq for Tilemap {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.origin == other.origin
+            && self.layer_names == other.layer_names
+            && self.chunks == other.chunks
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TilemapError {
+    #[error("tile count mismatch: expected {expected}, got {actual}")]
+    TileCountMismatch { expected: usize, actual: usize },
+    #[error("duplicate tile layer name: {name}")]
+    DuplicateLayer { name: String },
+}
+
+impl Tilemap {
+    pub fn new(
+        width: u32,
+        height: u32,
+        origin: Vec2,
+        tiles: Vec<u16>,
+    ) -> Result<Self, TilemapError> {
+        let expected = width as usize * height as usize;
+        let actual = tiles.len();
+        if expected != actual {
+            return Err(TilemapError::TileCountMismatch { expected, actual });
+        }
+        let chunks_x = width.div_ceil(TILEMAP_CHUNK_SIZE);
+        let chunks_y = height.div_ceil(TILEMAP_CHUNK_SIZE);
+        let chunk_count = chunks_x as usize * chunks_y as usize;
+        let mut tilemap = Self {
+            width,
+            height,
+            origin,
+            layer_names: vec![TILE_LAYER_TERRAIN.to_string()],
+            chunks: vec![
+                TileChunk {
+                    layers: vec![vec![0; CHUNK_TILE_COUNT]],
+                    flags: vec![TileFlags::NONE; CHUNK_TILE_COUNT],
+                };
+                chunk_count
+            ],
+            chunk_epochs: vec![0; chunk_count],
+        };
+        for (index, tile_id) in tiles.into_iter().enumerate() {
+            let x = (index % width as usize) as u32;
+            let y = (index / width as usize) as u32;
+            tilemap.set_layer_tile(0, x, y, tile_id);
+        }
+        Ok(tilemap)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Row-major index of (x,y); stable regardless of chunk storage.
+    pub fn index_of(&self, x: u32, y: u32) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(y as usize * self.width as usize + x as usize)
+    }
+
+    /// Terrain layer tile id at (x,y).
+    pub fn tile_at(&self, x: u32, y: u32) -> Option<u16> {
+        self.layer_tile_at(0, x, y)
+    }
+
+    pub fn tile_center_world(&self, x: u32, y: u32) -> Option<Vec2> {
+        self.index_of(x, y)?;
+        Some(Vec2 {
+            x: self.origin.x + x as f32 + 0.5,
+            y: self.origin.y + y as f32 + 0.5,
+        })
+    }
+
+    /// Tile containing `position`, or `None` outside the map.
+    pub fn tile_at_world(&self, position: Vec2) -> Option<(u32, u32)> {
+        let x = (position.x - self.origin.x).floor();
+        let y = (position.y - self.origin.y).floor();
+        if !(x >= 0.0 && y >= 0.0 && x < self.width as f32 && y < self.height as f32) {
+            return None;
+        }
+        Some((x as u32, y as u32))
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layer_names.len()
+    }
+
+    pub fn layer_name(&self, layer: usize) -> Option<&str> {
+        self.layer_names.get(layer).map(String::as_str)
+    }
+
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layer_names
+            .iter()
+            .position(|layer_name| layer_name == name)
+    }
+
+    /// Appends a layer drawn above existing layers, filled with `TILE_EMPTY`.
+    pub fn add_layer(&mut self, name: &str) -> Result<usize, TilemapError> {
+        if self.layer_index(name).is_some() {
+            return Err(TilemapError::DuplicateLayer {
+                name: name.to_string(),
+            });
+        }
+        self.layer_names.push(name.to_string());
+        for chunk in &mut self.chunks {
+            chunk.layers.push(vec![TILE_EMPTY; CHUNK_TILE_COUNT]);
+        }
+        Ok(self.layer_names.len() - 1)
+    }
+
+    pub fn layer_tile_at(&self, layer: usize, x: u32, y: u32) -> Option<u16> {
+        let (chunk_index, local_index) = self.chunk_slot(x, y)?;
+        self.chunks[chunk_index]
+            .layers
+            .get(layer)
+            .map(|tiles| tiles[local_index])
+    }
+
+    /// Writes one tile into `layer`; returns false when out of bounds or the layer is unknown.
+    pub fn set_layer_tile(&mut self, layer: usize, x: u32, y: u32, tile_id: u16) -> bool {
+        let Some((chunk_index, local_index)) = self.chunk_slot(x, y) else {
+            return false;
+        };
+        let Some(tiles) = self.chunks[chunk_index].layers.get_mut(layer) else {
+            return false;
+        };
+        tiles[local_index] = tile_id;
+        true
+    }
+
+    pub fn flags_at(&self, x: u32, y: u32) -> Option<TileFlags> {
+        let (chunk_index, local_index) = self.chunk_slot(x, y)?;
+        Some(self.chunks[chunk_index].flags[local_index])
+    }
+
+    pub fn set_flags(&mut self, x: u32, y: u32, flags: TileFlags) -> bool {
+        let Some((chunk_index, local_index)) = self.chunk_slot(x, y) else {
+            return false;
+        };
+        self.chunks[chunk_index].flags[local_index] = flags;
+        true
+    }
+
+    pub fn chunks_x(&self) -> u32 {
+        self.width.div_ceil(TILEMAP_CHUNK_SIZE)
+    }
+
+    pub fn chunks_y(&self) -> u32 {
+        self.height.div_ceil(TILEMAP_CHUNK_SIZE)
+    }
+
+    pub fn chunk_of(&self, x: u32, y: u32) -> Option<TileChunkCoord> {
+        self.index_of(x, y)?;
+        Some(TileChunkCoord {
+            x: x / TILEMAP_CHUNK_SIZE,
+            y: y / TILEMAP_CHUNK_SIZE,
+        })
+    }
+
+    /// Epoch stamped on `chunk` the last time it was installed or edited through `SceneWorld`.
+    pub fn chunk_epoch(&self, chunk: TileChunkCoord) -> Option<u64> {
+        if chunk.x >= self.chunks_x() || chunk.y >= self.chunks_y() {
+            return None;
+        }
+        self.chunk_epochs
+            .get(chunk.y as usize * self.chunks_x() as usize + chunk.x as usize)
+            .copied()
+    }
+
+    /// Tile-space bounds of `chunk` as `(x_min, y_min, x_max_exclusive, y_max_exclusive)`.
+    pub fn chunk_tile_bounds(&self, chunk: TileChunkCoord) -> Option<(u32, u32, u32, u32)> {
+        self.chunk_epoch(chunk)?;
+        let x_min = chunk.x * TILEMAP_CHUNK_SIZE;
+        let y_min = chunk.y * TILEMAP_CHUNK_SIZE;
+        Some((
+            x_min,
+            y_min,
+            (x_min + TILEMAP_CHUNK_SIZE).min(self.width),
+            (y_min + TILEMAP_CHUNK_SIZE).min(self.height),
+        ))
+    }
+
+    pub(crate) fn stamp_all_chunks(&mut self, epoch: u64) {
+        self.chunk_epochs.fill(epoch);
+    }
+
+    fn stamp_chunk_at(&mut self, x: u32, y: u32, epoch: u64) {
+        if let Some((chunk_index, _)) = self.chunk_slot(x, y) {
+            self.chunk_epochs[chunk_index] = epoch;
+        }
+    }
+
+    fn chunk_slot(&self, x: u32, y: u32) -> Option<(usize, usize)> {
+        self.index_of(x, y)?;
+        let chunk_index = (y / TILEMAP_CHUNK_SIZE) as usize * self.chunks_x() as usize
+            + (x / TILEMAP_CHUNK_SIZE) as usize;
+        let local_index =
+            (y % TILEMAP_CHUNK_SIZE * TILEMAP_CHUNK_SIZE + x % TILEMAP_CHUNK_SIZE) as usize;
+        Some((chunk_index, local_index))
+    }
+}
+
+/// Runtime edits act on the active floor's tilemap; the change log spans all floors.
+impl SceneWorld {
+    /// Sets one tile in `layer`; returns true only when the stored value changed.
+    pub fn set_tile(&mut self, layer: usize, x: u32, y: u32, tile_id: u16) -> bool {
+        self.fill_rect(layer, TileRect::single(x, y), tile_id) > 0
+    }
+
+    /// Fills `rect` (clipped to the map) in `layer` and returns how many tiles changed.
+    pub fn fill_rect(&mut self, layer: usize, rect: TileRect, tile_id: u16) -> usize {
+        self.edit_tilemap_rect(TileChannel::Layer(layer), rect, |tilemap, x, y| {
+            if tilemap.layer_tile_at(layer, x, y) == Some(tile_id) {
+                return false;
+            }
+            tilemap.set_layer_tile(layer, x, y, tile_id)
+        })
+    }
+
+    /// Sets the flag channel of one tile; returns true only when the stored value changed.
+    pub fn set_tile_flags(&mut self, x: u32, y: u32, flags: TileFlags) -> bool {
+        self.fill_rect_flags(TileRect::single(x, y), flags) > 0
+    }
+
+    pub fn fill_rect_flags(&mut self, rect: TileRect, flags: TileFlags) -> usize {
+        self.edit_tilemap_rect(TileChannel::Flags, rect, |tilemap, x, y| {
+            if tilemap.flags_at(x, y) == Some(flags) {
+                return false;
+            }
+            tilemap.set_flags(x, y, flags)
+        })
+    }
+
+    /// Edits applied after `epoch`, oldest first.
+    ///
+    /// Returns `None` when the log no longer covers `epoch` (any floor's map replaced/cleared or
+    /// history trimmed); callers must then resync from the whole tilemap.
+    pub fn tilemap_changes_since(&self, epoch: u64) -> Option<vec_deque::Iter<'_, TilemapChange>> {
+        if epoch < self.tilemap_changes_complete_after {
+            return None;
+        }
+        let start = self
+            .tilemap_changes
+            .partition_point(|change| change.epoch <= epoch);
+        Some(self.tilemap_changes.range(start..))
+    }
+
+    pub(super) fn reset_tilemap_changes(&mut self) {
+        self.tilemap_changes.clear();
+        self.tilemap_changes_complete_after = self.tilemap_epoch;
+    }
+
+    fn edit_tilemap_rect(
+        &mut self,
+        channel: TileChannel,
+        rect: TileRect,
+        mut apply: impl FnMut(&mut Tilemap, u32, u32) -> bool,
+    ) -> usize {
+        let floor = self.active_floor;
+        let Some(tilemap) = self.floors.tilemap_mut(floor) else {
+            return 0;
+        };
+        let Some(rect) = rect.clipped_to(tilemap.width(), tilemap.height()) else {
+            return 0;
+        };
+        let epoch = self.tilemap_epoch.saturating_add(1);
+        let mut changed = 0usize;
+        let (mut x_min, mut y_min, mut x_max, mut y_max) = (u32::MAX, u32::MAX, 0u32, 0u32);
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                if !apply(tilemap, x, y) {
+                    continue;
+                }
+                tilemap.stamp_chunk_at(x, y, epoch);
+                changed += 1;
+                x_min = x_min.min(x);
+                y_min = y_min.min(y);
+                x_max = x_max.max(x);
+                y_max = y_max.max(y);
+            }
+        }
+        if changed == 0 {
+            return 0;
+        }
+
+        self.tilemap_epoch = epoch;
+        if self.tilemap_changes.len() == TILEMAP_CHANGE_LOG_CAPACITY {
+            if let Some(dropped) = self.tilemap_changes.pop_front() {
+                self.tilemap_changes_complete_after = dropped.epoch;
+            }
+        }
+        self.tilemap_changes.push_back(TilemapChange {
+            epoch,
+            floor,
+            channel,
+            rect: TileRect {
+                x: x_min,
+                y: y_min,
+                width: x_max - x_min + 1,
+                height: y_max - y_min + 1,
+            },
+        });
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequential_tilemap(width: u32, height: u32) -> Tilemap {
+        let tiles = (0..width * height).map(|value| value as u16).collect();
+        Tilemap::new(width, height, Vec2 { x: 0.0, y: 0.0 }, tiles).expect("tilemap")
+    }
+
+    #[test]
+    fn chunked_storage_preserves_row_major_tiles_across_chunk_edges() {
+        let tilemap = sequential_tilemap(37, 19);
+
+        assert_eq!(tilemap.chunks_x(), 3);
+        assert_eq!(tilemap.chunks_y(), 2);
+        for y in 0..19 {
+            for x in 0..37 {
+                assert_eq!(tilemap.tile_at(x, y), Some((y * 37 + x) as u16));
+            }
+        }
+        assert_eq!(tilemap.tile_at(37, 0), None);
+    }
+
+    #[test]
+    fn added_layers_start_empty_and_reject_duplicate_names() {
+        let mut tilemap = sequential_tilemap(4, 4);
+
+        let walls = tilemap.add_layer("walls").expect("walls");
+        assert_eq!(walls, 1);
+        assert_eq!(tilemap.layer_index("walls"), Some(1));
+        assert_eq!(tilemap.layer_name(0), Some(TILE_LAYER_TERRAIN));
+        assert_eq!(tilemap.layer_tile_at(walls, 2, 2), Some(TILE_EMPTY));
+        assert!(tilemap.set_layer_tile(walls, 2, 2, 5));
+        assert_eq!(tilemap.layer_tile_at(walls, 2, 2), Some(5));
+        assert_eq!(tilemap.tile_at(2, 2), Some(10));
+        assert!(!tilemap.set_layer_tile(7, 0, 0, 1));
+        assert_eq!(
+            tilemap.add_layer("walls"),
+            Err(TilemapError::DuplicateLayer {
+                name: "walls".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn flags_channel_is_independent_of_tile_ids() {
+        let mut tilemap = sequential_tilemap(20, 20);
+
+        assert!(tilemap.set_flags(17, 3, TileFlags::BLOCKS_MOVEMENT | TileFlags::BLOCKS_SIGHT));
+        let flags = tilemap.flags_at(17, 3).expect("flags");
+        assert!(flags.contains(TileFlags::BLOCKS_MOVEMENT));
+        assert!(flags.contains(TileFlags::BLOCKS_SIGHT));
+        assert_eq!(tilemap.flags_at(16, 3), Some(TileFlags::NONE));
+        assert_eq!(tilemap.tile_at(17, 3), Some(3 * 20 + 17));
+        assert!(!tilemap.set_flags(20, 0, TileFlags::BLOCKS_MOVEMENT));
+    }
+
+    #[test]
+    fn chunk_bounds_clip_to_map_edges() {
+        let tilemap = sequential_tilemap(20, 5);
+
+        assert_eq!(tilemap.chunk_of(19, 4), Some(TileChunkCoord { x: 1, y: 0 }));
+        assert_eq!(
+            tilemap.chunk_tile_bounds(TileChunkCoord { x: 1, y: 0 }),
+            Some((16, 0, 20, 5))
+        );
+        assert_eq!(tilemap.chunk_epoch(TileChunkCoord { x: 2, y: 0 }), None);
+    }
+
+    #[test]
+    fn equality_ignores_chunk_epochs() {
+        let first = sequential_tilemap(3, 3);
+        let mut second = first.clone();
+        second.stamp_all_chunks(9);
+
+        assert_eq!(first, second);
+        assert_eq!(second.chunk_epoch(TileChunkCoord { x: 0, y: 0 }), Some(9));
+    }
+
+    #[test]
+    fn world_edits_stamp_only_touched_chunks_and_log_changes() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(sequential_tilemap(40, 4));
+        let installed_epoch = world.tilemap_epoch();
+
+        assert!(world.set_tile(0, 33, 1, 7));
+        let edit_epoch = world.tilemap_epoch();
+        assert!(edit_epoch > installed_epoch);
+        let tilemap = world.tilemap().expect("tilemap");
+        assert_eq!(tilemap.tile_at(33, 1), Some(7));
+        assert_eq!(
+            tilemap.chunk_epoch(TileChunkCoord { x: 0, y: 0 }),
+            Some(installed_epoch)
+        );
+        assert_eq!(
+            tilemap.chunk_epoch(TileChunkCoord { x: 2, y: 0 }),
+            Some(edit_epoch)
+        );
+        assert_eq!(
+            world
+                .tilemap_changes_since(installed_epoch)
+                .map(|changes| changes.copied().collect::<Vec<_>>()),
+            Some(vec![TilemapChange {
+                epoch: edit_epoch,
+                floor: FloorId::MAIN,
+                channel: TileChannel::Layer(0),
+                rect: TileRect::single(33, 1),
+            }])
+        );
+        assert_eq!(
+            world
+                .tilemap_changes_since(edit_epoch)
+                .map(|changes| changes.len()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn fill_rect_clips_to_map_and_reports_changed_bounds() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(
+            Tilemap::new(4, 4, Vec2 { x: 0.0, y: 0.0 }, vec![0; 16]).expect("tilemap"),
+        );
+        world.set_tile(0, 3, 3, 5);
+        let before = world.tilemap_epoch();
+
+        let changed = world.fill_rect(
+            0,
+            TileRect {
+                x: 2,
+                y: 2,
+                width: 10,
+                height: 10,
+            },
+            5,
+        );
+
+        assert_eq!(changed, 3);
+        let change = *world
+            .tilemap_changes_since(before)
+            .and_then(|mut changes| changes.next())
+            .expect("changes");
+        assert_eq!(
+            change.rect,
+            TileRect {
+                x: 2,
+                y: 2,
+                width: 2,
+                height: 2
+            }
+        );
+        assert_eq!(world.fill_rect(0, TileRect::single(9, 9), 1), 0);
+        assert_eq!(world.tilemap_epoch(), change.epoch);
+    }
+
+    #[test]
+    fn unchanged_writes_and_missing_tilemap_do_not_bump_epoch() {
+        let mut world = SceneWorld::default();
+        assert!(!world.set_tile(0, 0, 0, 1));
+        world.set_tilemap(sequential_tilemap(2, 2));
+        let epoch = world.tilemap_epoch();
+
+        assert!(!world.set_tile(0, 1, 0, 1));
+        assert!(!world.set_tile_flags(0, 0, TileFlags::NONE));
+        assert!(world.set_tile_flags(0, 0, TileFlags::BLOCKS_MOVEMENT));
+        assert_eq!(world.tilemap_epoch(), epoch + 1);
+    }
+
+    #[test]
+    fn change_log_requires_resync_after_replace_or_trim() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(sequential_tilemap(20, 20));
+        let installed_epoch = world.tilemap_epoch();
+        assert!(world.tilemap_changes_since(installed_epoch - 1).is_none());
+
+        for index in 0..=TILEMAP_CHANGE_LOG_CAPACITY as u32 {
+            world.set_tile(0, index % 20, index / 20, u16::MAX - 1);
+        }
+        assert!(world.tilemap_changes_since(installed_epoch).is_none());
+        let changes = world
+            .tilemap_changes_since(installed_epoch + 1)
+            .expect("retained history");
+        assert_eq!(changes.len(), TILEMAP_CHANGE_LOG_CAPACITY);
+
+        world.set_tilemap(sequential_tilemap(2, 2));
+        assert!(world.tilemap_changes_since(installed_epoch + 1).is_none());
+    }
+}
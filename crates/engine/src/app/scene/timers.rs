@@ -0,0 +1,308 @@
+use thiserror::Error;
+
+use super::{EntityId, SceneWorld};
+
+/// Stable id of a scheduled timer; never reused within one world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimerId(pub u64);
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TimerError {
+    #[error("timer delay must be at least one tick")]
+    ZeroDelay,
+    #[error("repeating timer interval must be at least one tick")]
+    ZeroInterval,
+    #[error("timer tag must not be empty")]
+    EmptyTag,
+    #[error("timer owner entity {0} does not exist")]
+    UnknownOwner(u64),
+}
+
+/// Pending timer. `tag` names the callback the owning layer runs when the timer fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledTimer {
+    pub id: TimerId,
+    pub owner: Option<EntityId>,
+    pub tag: String,
+    pub due_tick: u64,
+    /// `Some(n)` reschedules the timer `n` ticks after each firing.
+    pub repeat_every_ticks: Option<u32>,
+}
+
+/// One timer firing reported by the most recent `SceneWorld::tick_timers` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimerFired {
+    pub id: TimerId,
+    pub owner: Option<EntityId>,
+    pub tag: String,
+    pub tick: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct TimerService {
+    now_tick: u64,
+    next_id: u64,
+    /// Sorted by `(due_tick, id)`, which is also the firing order.
+    timers: Vec<ScheduledTimer>,
+    fired: Vec<TimerFired>,
+    due_scratch: Vec<ScheduledTimer>,
+}
+
+impl TimerService {
+    pub(super) fn clear(&mut self) {
+        self.timers.clear();
+        self.fired.clear();
+    }
+
+    pub(super) fn cancel_owned_by(&mut self, owners: &[EntityId]) {
+        self.timers
+            .retain(|timer| timer.owner.is_none_or(|owner| !owners.contains(&owner)));
+    }
+
+    fn insert(&mut self, timer: ScheduledTimer) {
+        let index = self.timers.partition_point(|existing| {
+            (existing.due_tick, existing.id) < (timer.due_tick, timer.id)
+        });
+        self.timers.insert(index, timer);
+    }
+}
+
+impl SceneWorld {
+    /// Schedules a one-shot timer that fires `delay_ticks` calls of `tick_timers` from now.
+    pub fn schedule_timer(
+        &mut self,
+        owner: Option<EntityId>,
+        tag: impl Into<String>,
+        delay_ticks: u32,
+    ) -> Result<TimerId, TimerError> {
+        self.schedule_timer_internal(owner, tag.into(), delay_ticks, None)
+    }
+
+    /// Schedules a timer that first fires after `delay_ticks` and then every `interval_ticks`.
+    pub fn schedule_repeating_timer(
+        &mut self,
+        owner: Option<EntityId>,
+        tag: impl Into<String>,
+        delay_ticks: u32,
+        interval_ticks: u32,
+    ) -> Result<TimerId, TimerError> {
+        if interval_ticks == 0 {
+            return Err(TimerError::ZeroInterval);
+        }
+        self.schedule_timer_internal(owner, tag.into(), delay_ticks, Some(interval_ticks))
+    }
+
+    fn schedule_timer_internal(
+        &mut self,
+        owner: Option<EntityId>,
+        tag: String,
+        delay_ticks: u32,
+        repeat_every_ticks: Option<u32>,
+    ) -> Result<TimerId, TimerError> {
+        if delay_ticks == 0 {
+            return Err(TimerError::ZeroDelay);
+        }
+        if tag.is_empty() {
+            return Err(TimerError::EmptyTag);
+        }
+        if let Some(owner) = owner {
+            let exists = self.entities.iter().any(|entity| entity.id == owner)
+                || self.pending_spawns.iter().any(|entity| entity.id == owner);
+            if !exists {
+                return Err(TimerError::UnknownOwner(owner.0));
+            }
+        }
+        let service = &mut self.timers;
+        let id = TimerId(service.next_id);
+        service.next_id = service.next_id.saturating_add(1);
+        let due_tick = service.now_tick.saturating_add(u64::from(delay_ticks));
+        service.insert(ScheduledTimer {
+            id,
+            owner,
+            tag,
+            due_tick,
+            repeat_every_ticks,
+        });
+        Ok(id)
+    }
+
+    pub fn cancel_timer(&mut self, id: TimerId) -> bool {
+        let before = self.timers.timers.len();
+        self.timers.timers.retain(|timer| timer.id != id);
+        self.timers.timers.len() != before
+    }
+
+    /// Cancels every timer owned by `owner` whose tag is `tag`; returns how many were removed.
+    pub fn cancel_owner_timers(&mut self, owner: EntityId, tag: &str) -> usize {
+        let before = self.timers.timers.len();
+        self.timers
+            .timers
+            .retain(|timer| timer.owner != Some(owner) || timer.tag != tag);
+        before - self.timers.timers.len()
+    }
+
+    /// Advances the timer clock by one tick and fires every due timer in `(due_tick, id)` order.
+    ///
+    /// Firings replace the previous tick's `fired_timers`; repeating timers are rescheduled.
+    pub fn tick_timers(&mut self) {
+        let service = &mut self.timers;
+        service.now_tick = service.now_tick.saturating_add(1);
+        service.fired.clear();
+        let now = service.now_tick;
+        let due_count = service
+            .timers
+            .partition_point(|timer| timer.due_tick <= now);
+        let mut due = std::mem::take(&mut service.due_scratch);
+        due.extend(service.timers.drain(..due_count));
+        for mut timer in due.drain(..) {
+            service.fired.push(TimerFired {
+                id: timer.id,
+                owner: timer.owner,
+                tag: timer.tag.clone(),
+                tick: now,
+            });
+            if let Some(interval) = timer.repeat_every_ticks {
+                timer.due_tick = now.saturating_add(u64::from(interval));
+                service.insert(timer);
+            }
+        }
+        service.due_scratch = due;
+    }
+
+    pub fn fired_timers(&self) -> &[TimerFired] {
+        &self.timers.fired
+    }
+
+    /// Pending timers in firing order.
+    pub fn timers(&self) -> &[ScheduledTimer] {
+        &self.timers.timers
+    }
+
+    pub fn find_owner_timer(&self, owner: EntityId, tag: &str) -> Option<&ScheduledTimer> {
+        self.timers
+            .timers
+            .iter()
+            .find(|timer| timer.owner == Some(owner) && timer.tag == tag)
+    }
+
+    /// Number of `tick_timers` calls since the world was created.
+    pub fn timer_tick(&self) -> u64 {
+        self.timers.now_tick
+    }
+
+    /// Ticks left before `timer` fires, counted from the current timer tick.
+    pub fn timer_remaining_ticks(&self, timer: &ScheduledTimer) -> u32 {
+        timer
+            .due_tick
+            .saturating_sub(self.timers.now_tick)
+            .try_into()
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Reinstalls a snapshot timer under its captured id so firing order is preserved.
+    pub(super) fn restore_timer(
+        &mut self,
+        id: TimerId,
+        owner: Option<EntityId>,
+        tag: String,
+        remaining_ticks: u32,
+        repeat_every_ticks: Option<u32>,
+    ) {
+        let service = &mut self.timers;
+        service.next_id = service.next_id.max(id.0.saturating_add(1));
+        let due_tick = service.now_tick.saturating_add(u64::from(remaining_ticks));
+        service.insert(ScheduledTimer {
+            id,
+            owner,
+            tag,
+            due_tick,
+            repeat_every_ticks,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{RenderableDesc, RenderableKind, Transform, Vec2};
+    use super::*;
+
+    fn spawn_owner(world: &mut SceneWorld) -> EntityId {
+        let id = world.spawn(
+            Transform {
+                position: Vec2 { x: 0.0, y: 0.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "owner",
+            },
+        );
+        world.apply_pending();
+        id
+    }
+
+    fn fired_tags(world: &SceneWorld) -> Vec<&str> {
+        world
+            .fired_timers()
+            .iter()
+            .map(|fired| fired.tag.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn one_shot_and_repeating_timers_fire_in_due_then_id_order() {
+        let mut world = SceneWorld::default();
+        world.schedule_timer(None, "late", 3).expect("late");
+        world
+            .schedule_repeating_timer(None, "pulse", 1, 2)
+            .expect("pulse");
+        world.schedule_timer(None, "early", 1).expect("early");
+
+        world.tick_timers();
+        assert_eq!(fired_tags(&world), vec!["pulse", "early"]);
+        world.tick_timers();
+        assert!(world.fired_timers().is_empty());
+        world.tick_timers();
+        assert_eq!(fired_tags(&world), vec!["late", "pulse"]);
+        assert_eq!(world.timers().len(), 1);
+        assert_eq!(world.timer_remaining_ticks(&world.timers()[0]), 2);
+    }
+
+    #[test]
+    fn schedule_rejects_zero_delay_empty_tag_and_unknown_owner() {
+        let mut world = SceneWorld::default();
+        assert_eq!(
+            world.schedule_timer(None, "x", 0),
+            Err(TimerError::ZeroDelay)
+        );
+        assert_eq!(
+            world.schedule_repeating_timer(None, "x", 1, 0),
+            Err(TimerError::ZeroInterval)
+        );
+        assert_eq!(world.schedule_timer(None, "", 1), Err(TimerError::EmptyTag));
+        assert_eq!(
+            world.schedule_timer(Some(EntityId(99)), "x", 1),
+            Err(TimerError::UnknownOwner(99))
+        );
+    }
+
+    #[test]
+    fn owner_despawn_and_clear_cancel_timers() {
+        let mut world = SceneWorld::default();
+        let owner = spawn_owner(&mut world);
+        let other = spawn_owner(&mut world);
+        world.schedule_timer(Some(owner), "a", 2).expect("a");
+        world.schedule_timer(Some(owner), "b", 2).expect("b");
+        world.schedule_timer(Some(other), "a", 2).expect("other");
+        assert_eq!(world.cancel_owner_timers(owner, "b"), 1);
+        assert!(world.find_owner_timer(owner, "a").is_some());
+
+        world.despawn(owner);
+        world.apply_pending();
+        assert!(world.find_owner_timer(owner, "a").is_none());
+        assert_eq!(world.timers().len(), 1);
+
+        world.clear();
+        assert!(world.timers().is_empty());
+    }
+}
@@ -0,0 +1,563 @@
+use std::collections::{BTreeMap, HashMap};
+
+use thiserror::Error;
+
+use super::{Entity, EntityId, FloorId, SceneWorld, TileFlags, Tilemap, Vec2};
+
+/// Side an entity sees for; each faction keeps its own visibility grid per floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FactionId(pub u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileVisibility {
+    #[default]
+    Unexplored,
+    /// Seen before but not by any sighted entity of the faction right now.
+    Explored,
+    Visible,
+}
+
+impl TileVisibility {
+    pub const ALL: [Self; 3] = [Self::Unexplored, Self::Explored, Self::Visible];
+}
+
+/// Makes an entity reveal tiles for `faction` within `radius` world units (tiles).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntitySight {
+    pub faction: FactionId,
+    pub radius: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum SightError {
+    #[error("sight radius must be finite and >= 0, got {0}")]
+    InvalidRadius(f32),
+    #[error("sight entity {0} does not exist")]
+    UnknownEntity(u64),
+}
+
+/// Row-major tile states matching the size of the floor tilemap they were computed for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct VisibilityGrid {
+    width: u32,
+    height: u32,
+    cells: Vec<TileVisibility>,
+}
+
+impl VisibilityGrid {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![TileVisibility::Unexplored; width as usize * height as usize],
+        }
+    }
+
+    fn get(&self, x: u32, y: u32) -> TileVisibility {
+        if x >= self.width || y >= self.height {
+            return TileVisibility::Unexplored;
+        }
+        self.cells[y as usize * self.width as usize + x as usize]
+    }
+
+    fn reveal(&mut self, x: u32, y: u32) {
+        let index = y as usize * self.width as usize + x as usize;
+        self.cells[index] = TileVisibility::Visible;
+    }
+
+    fn demote_visible(&mut self) {
+        for cell in &mut self.cells {
+            if *cell == TileVisibility::Visible {
+                *cell = TileVisibility::Explored;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct VisibilityState {
+    viewer: Option<FactionId>,
+    sight: HashMap<EntityId, EntitySight>,
+    grids: BTreeMap<(FactionId, FloorId), VisibilityGrid>,
+}
+
+impl VisibilityState {
+    /// Drops sight and grids; the viewer faction is a presentation setting and stays.
+    pub(super) fn clear(&mut self) {
+        self.sight.clear();
+        self.grids.clear();
+    }
+
+    /// Restores validated sight without the existence check; snapshot restore queues the
+    /// entity first.
+    pub(super) fn restore_sight(&mut self, id: EntityId, sight: EntitySight) {
+        self.sight.insert(id, sight);
+    }
+
+    pub(super) fn remove_entities(&mut self, entities: &[EntityId]) {
+        for id in entities {
+            self.sight.remove(id);
+        }
+    }
+}
+
+/// Octant transforms `(xx, xy, yx, yy)` for recursive shadowcasting.
+const OCTANTS: [[i32; 4]; 8] = [
+    [1, 0, 0, 1],
+    [0, 1, 1, 0],
+    [0, -1, 1, 0],
+    [-1, 0, 0, 1],
+    [-1, 0, 0, -1],
+    [0, -1, -1, 0],
+    [0, 1, -1, 0],
+    [1, 0, 0, -1],
+];
+
+struct Shadowcaster<'a> {
+    tilemap: &'a Tilemap,
+    grid: &'a mut VisibilityGrid,
+    origin: (i32, i32),
+    radius: i32,
+    radius_sq: f32,
+}
+
+impl Shadowcaster<'_> {
+    fn run(&mut self) {
+        self.grid.reveal(self.origin.0 as u32, self.origin.1 as u32);
+        for octant in OCTANTS {
+            self.cast(1, 1.0, 0.0, octant);
+        }
+    }
+
+    fn blocks(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 {
+            return true;
+        }
+        self.tilemap
+            .flags_at(x as u32, y as u32)
+            .is_none_or(|flags| flags.contains(TileFlags::BLOCKS_SIGHT))
+    }
+
+    fn cast(&mut self, row: i32, mut start: f32, end: f32, [xx, xy, yx, yy]: [i32; 4]) {
+        if start < end {
+            return;
+        }
+        let mut next_start = start;
+        for distance in row..=self.radius {
+            let dy = -distance;
+            let mut blocked = false;
+            for dx in -distance..=0 {
+                let left_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+                let right_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+                if start < right_slope {
+                    continue;
+                }
+                if end > left_slope {
+                    break;
+                }
+                let x = self.origin.0 + dx * xx + dy * xy;
+                let y = self.origin.1 + dx * yx + dy * yy;
+                let in_range = (dx * dx + dy * dy) as f32 <= self.radius_sq;
+                let blocks = self.blocks(x, y);
+                if in_range
+                    && x >= 0
+                    && y >= 0
+                    && self.tilemap.index_of(x as u32, y as u32).is_some()
+                {
+                    self.grid.reveal(x as u32, y as u32);
+                }
+                if blocked {
+                    if blocks {
+                        next_start = right_slope;
+                    } else {
+                        blocked = false;
+                        start = next_start;
+                    }
+                } else if blocks && distance < self.radius {
+                    blocked = true;
+                    self.cast(distance + 1, start, left_slope, [xx, xy, yx, yy]);
+                    next_start = right_slope;
+                }
+            }
+            if blocked {
+                break;
+            }
+        }
+    }
+}
+
+impl SceneWorld {
+    /// Gives `id` sight for `sight.faction`; works on pending entities too.
+    pub fn set_entity_sight(&mut self, id: EntityId, sight: EntitySight) -> Result<(), SightError> {
+        if !sight.radius.is_finite() || sight.radius < 0.0 {
+            return Err(SightError::InvalidRadius(sight.radius));
+        }
+        let exists = self.entities.iter().any(|entity| entity.id == id)
+            || self.pending_spawns.iter().any(|entity| entity.id == id);
+        if !exists {
+            return Err(SightError::UnknownEntity(id.0));
+        }
+        self.visibility.sight.insert(id, sight);
+        Ok(())
+    }
+
+    pub fn clear_entity_sight(&mut self, id: EntityId) -> bool {
+        self.visibility.sight.remove(&id).is_some()
+    }
+
+    pub fn entity_sight(&self, id: EntityId) -> Option<EntitySight> {
+        self.visibility.sight.get(&id).copied()
+    }
+
+    /// Faction whose fog the renderer and picking apply; `None` (the default) disables fog.
+    pub fn set_fog_viewer(&mut self, viewer: Option<FactionId>) {
+        self.visibility.viewer = viewer;
+    }
+
+    pub fn fog_viewer(&self) -> Option<FactionId> {
+        self.visibility.viewer
+    }
+
+    /// Recomputes every faction grid: tiles visible last update become explored, then each
+    /// applied sighted entity reveals tiles around it on its floor's tilemap by shadowcasting
+    /// against `BLOCKS_SIGHT` tiles. Grids whose tilemap changed size start over unexplored.
+    pub fn update_visibility(&mut self) {
+        let mut factions = self
+            .visibility
+            .sight
+            .values()
+            .map(|sight| sight.faction)
+            .chain(self.visibility.grids.keys().map(|(faction, _)| *faction))
+            .collect::<Vec<_>>();
+        factions.sort();
+        factions.dedup();
+        for floor in self.floor_ids().collect::<Vec<_>>() {
+            let Some(tilemap) = self.floors.tilemap(floor) else {
+                continue;
+            };
+            for faction in &factions {
+                let grid = self
+                    .visibility
+                    .grids
+                    .entry((*faction, floor))
+                    .or_insert_with(|| VisibilityGrid::new(tilemap.width(), tilemap.height()));
+                if grid.width != tilemap.width() || grid.height != tilemap.height() {
+                    *grid = VisibilityGrid::new(tilemap.width(), tilemap.height());
+                }
+                grid.demote_visible();
+            }
+        }
+        for entity in &self.entities {
+            let Some(sight) = self.visibility.sight.get(&entity.id) else {
+                continue;
+            };
+            let Some(tilemap) = self.floors.tilemap(entity.floor) else {
+                continue;
+            };
+            let Some((x, y)) = tilemap.tile_at_world(entity.transform.position) else {
+                continue;
+            };
+            let Some(grid) = self
+                .visibility
+                .grids
+                .get_mut(&(sight.faction, entity.floor))
+            else {
+                continue;
+            };
+            Shadowcaster {
+                tilemap,
+                grid,
+                origin: (x as i32, y as i32),
+                radius: sight.radius.ceil() as i32,
+                radius_sq: sight.radius * sight.radius,
+            }
+            .run();
+        }
+    }
+
+    /// State of tile `(x, y)` on `floor` for `faction`; `Unexplored` before its first update.
+    pub fn tile_visibility(
+        &self,
+        faction: FactionId,
+        floor: FloorId,
+        x: u32,
+        y: u32,
+    ) -> TileVisibility {
+        self.visibility
+            .grids
+            .get(&(faction, floor))
+            .map_or(TileVisibility::Unexplored, |grid| grid.get(x, y))
+    }
+
+    /// Fog state for the viewer at a world point. Without a viewer, or on floors without a
+    /// tilemap, everything is `Visible`; points off the tilemap are `Unexplored`.
+    pub fn viewer_visibility_at(&self, floor: FloorId, position: Vec2) -> TileVisibility {
+        let Some(viewer) = self.visibility.viewer else {
+            return TileVisibility::Visible;
+        };
+        let Some(tilemap) = self.floors.tilemap(floor) else {
+            return TileVisibility::Visible;
+        };
+        match tilemap.tile_at_world(position) {
+            Some((x, y)) => self.tile_visibility(viewer, floor, x, y),
+            None => TileVisibility::Unexplored,
+        }
+    }
+
+    /// Whether fog hides `entity` from the viewer: it must stand on a currently visible tile
+    /// unless it sees for the viewer's faction itself.
+    pub fn entity_hidden_by_fog(&self, entity: &Entity) -> bool {
+        let Some(viewer) = self.visibility.viewer else {
+            return false;
+        };
+        if self
+            .visibility
+            .sight
+            .get(&entity.id)
+            .is_some_and(|sight| sight.faction == viewer)
+        {
+            return false;
+        }
+        self.viewer_visibility_at(entity.floor, entity.transform.position)
+            != TileVisibility::Visible
+    }
+
+    /// Grids in `(faction, floor)` order as `(faction, floor, width, height, cells)`.
+    pub fn visibility_grids(
+        &self,
+    ) -> impl Iterator<Item = (FactionId, FloorId, u32, u32, &[TileVisibility])> {
+        self.visibility
+            .grids
+            .iter()
+            .map(|((faction, floor), grid)| {
+                (
+                    *faction,
+                    *floor,
+                    grid.width,
+                    grid.height,
+                    grid.cells.as_slice(),
+                )
+            })
+    }
+
+    /// Replaces one faction grid, e.g. from a save; `cells` must hold `width * height` states.
+    /// A grid whose size no longer matches the floor tilemap restarts at the next update.
+    pub fn restore_visibility_grid(
+        &mut self,
+        faction: FactionId,
+        floor: FloorId,
+        width: u32,
+        height: u32,
+        cells: Vec<TileVisibility>,
+    ) {
+        self.visibility.grids.insert(
+            (faction, floor),
+            VisibilityGrid {
+                width,
+                height,
+                cells,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{RenderableDesc, RenderableKind, Transform};
+    use super::*;
+
+    const COLONY: FactionId = FactionId(0);
+    const RAIDERS: FactionId = FactionId(1);
+
+    fn world_with_open_map(width: u32, height: u32) -> SceneWorld {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(
+            Tilemap::new(
+                width,
+                height,
+                Vec2 { x: 0.0, y: 0.0 },
+                vec![0; (width * height) as usize],
+            )
+            .expect("tilemap"),
+        );
+        world
+    }
+
+    fn spawn_at(world: &mut SceneWorld, x: f32, y: f32) -> EntityId {
+        world.spawn(
+            Transform {
+                position: Vec2 { x, y },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "pawn",
+            },
+        )
+    }
+
+    #[test]
+    fn sight_reveals_within_radius_and_demotes_to_explored_when_the_viewer_leaves() {
+        let mut world = world_with_open_map(16, 4);
+        let scout = spawn_at(&mut world, 1.5, 1.5);
+        world
+            .set_entity_sight(
+                scout,
+                EntitySight {
+                    faction: COLONY,
+                    radius: 3.0,
+                },
+            )
+            .expect("sight");
+        world.apply_pending();
+        world.update_visibility();
+
+        let floor = FloorId::MAIN;
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 1, 1),
+            TileVisibility::Visible
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 4, 1),
+            TileVisibility::Visible
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 5, 1),
+            TileVisibility::Unexplored
+        );
+        assert_eq!(
+            world.tile_visibility(RAIDERS, floor, 1, 1),
+            TileVisibility::Unexplored
+        );
+
+        world
+            .find_entity_mut(scout)
+            .expect("scout")
+            .transform
+            .position = Vec2 { x: 12.5, y: 1.5 };
+        world.update_visibility();
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 1, 1),
+            TileVisibility::Explored
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 12, 1),
+            TileVisibility::Visible
+        );
+
+        world.despawn(scout);
+        world.apply_pending();
+        assert_eq!(world.entity_sight(scout), None);
+        world.update_visibility();
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 12, 1),
+            TileVisibility::Explored
+        );
+    }
+
+    #[test]
+    fn sight_blocking_tiles_are_seen_but_cast_shadows() {
+        let mut world = world_with_open_map(9, 9);
+        world.set_tile_flags(6, 4, TileFlags::BLOCKS_SIGHT);
+        let scout = spawn_at(&mut world, 4.5, 4.5);
+        world
+            .set_entity_sight(
+                scout,
+                EntitySight {
+                    faction: COLONY,
+                    radius: 8.0,
+                },
+            )
+            .expect("sight");
+        world.apply_pending();
+        world.update_visibility();
+
+        let floor = FloorId::MAIN;
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 5, 4),
+            TileVisibility::Visible
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 6, 4),
+            TileVisibility::Visible
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 7, 4),
+            TileVisibility::Unexplored
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 8, 4),
+            TileVisibility::Unexplored
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 4, 0),
+            TileVisibility::Visible
+        );
+        assert_eq!(
+            world.tile_visibility(COLONY, floor, 8, 8),
+            TileVisibility::Visible
+        );
+    }
+
+    #[test]
+    fn viewer_fog_hides_entities_off_visible_tiles_but_never_its_own_sighted_entities() {
+        let mut world = world_with_open_map(16, 4);
+        let scout = spawn_at(&mut world, 1.5, 1.5);
+        let near = spawn_at(&mut world, 2.5, 1.5);
+        let far = spawn_at(&mut world, 14.5, 1.5);
+        world
+            .set_entity_sight(
+                scout,
+                EntitySight {
+                    faction: COLONY,
+                    radius: 2.0,
+                },
+            )
+            .expect("sight");
+        assert_eq!(
+            world.set_entity_sight(
+                EntityId(99),
+                EntitySight {
+                    faction: COLONY,
+                    radius: 2.0,
+                }
+            ),
+            Err(SightError::UnknownEntity(99))
+        );
+        assert!(matches!(
+            world.set_entity_sight(
+                near,
+                EntitySight {
+                    faction: COLONY,
+                    radius: f32::NAN,
+                }
+            ),
+            Err(SightError::InvalidRadius(_))
+        ));
+        world.apply_pending();
+        world.update_visibility();
+
+        let hidden = |world: &SceneWorld, id: EntityId| {
+            world.entity_hidden_by_fog(world.find_entity(id).expect("entity"))
+        };
+        assert!(!hidden(&world, far), "no viewer means no fog");
+        world.set_fog_viewer(Some(COLONY));
+        assert!(!hidden(&world, near));
+        assert!(hidden(&world, far));
+        assert_eq!(
+            world.viewer_visibility_at(FloorId::MAIN, Vec2 { x: -3.0, y: 0.0 }),
+            TileVisibility::Unexplored
+        );
+        assert_eq!(
+            world.viewer_visibility_at(FloorId::BASEMENT, Vec2 { x: 14.5, y: 1.5 }),
+            TileVisibility::Visible,
+            "floors without a tilemap have no fog"
+        );
+
+        world.set_fog_viewer(Some(RAIDERS));
+        assert!(hidden(&world, scout));
+        world.clear();
+        assert_eq!(world.fog_viewer(), Some(RAIDERS));
+        assert_eq!(world.visibility_grids().count(), 0);
+    }
+}
@@ -0,0 +1,215 @@
+use super::{EntityId, SceneWorld, Vec2};
+
+/// Labels longer than this are truncated on submission.
+pub const WORLD_WIDGET_LABEL_MAX_CHARS: usize = 24;
+const DEFAULT_BAR_WIDTH_PX: u16 = 24;
+const DEFAULT_BAR_HEIGHT_PX: u16 = 3;
+const DEFAULT_BAR_BACK_RGBA: [u8; 4] = [12, 14, 18, 200];
+const DEFAULT_ARC_RADIUS_PX: u16 = 7;
+const DEFAULT_ARC_THICKNESS_PX: u16 = 2;
+
+/// Where a widget is pinned; entity anchors follow the entity and hide when it is gone or on
+/// another floor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WidgetAnchor {
+    Entity(EntityId),
+    World(Vec2),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorldWidgetKind {
+    /// Horizontal bar centered on the anchor, filled left to right by `fraction`.
+    Bar {
+        fraction: f32,
+        width_px: u16,
+        height_px: u16,
+        fill_rgba: [u8; 4],
+        back_rgba: [u8; 4],
+    },
+    /// Ring centered on the anchor, filled clockwise from twelve o'clock by `fraction`.
+    ProgressArc {
+        fraction: f32,
+        radius_px: u16,
+        thickness_px: u16,
+        rgba: [u8; 4],
+    },
+    /// Short text centered horizontally on the anchor.
+    Label { text: String, rgba: [u8; 4] },
+}
+
+/// One world-space UI element. Pixel sizes and `offset_px` are at zoom 1 (screen y down) and
+/// scale with the camera zoom when drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldWidget {
+    pub anchor: WidgetAnchor,
+    pub offset_px: Vec2,
+    pub kind: WorldWidgetKind,
+}
+
+impl WorldWidget {
+    pub fn bar(anchor: WidgetAnchor, fraction: f32, fill_rgba: [u8; 4]) -> Self {
+        Self {
+            anchor,
+            offset_px: Vec2 { x: 0.0, y: 0.0 },
+            kind: WorldWidgetKind::Bar {
+                fraction: clamp_fraction(fraction),
+                width_px: DEFAULT_BAR_WIDTH_PX,
+                height_px: DEFAULT_BAR_HEIGHT_PX,
+                fill_rgba,
+                back_rgba: DEFAULT_BAR_BACK_RGBA,
+            },
+        }
+    }
+
+    pub fn progress_arc(anchor: WidgetAnchor, fraction: f32, rgba: [u8; 4]) -> Self {
+        Self {
+            anchor,
+            offset_px: Vec2 { x: 0.0, y: 0.0 },
+            kind: WorldWidgetKind::ProgressArc {
+                fraction: clamp_fraction(fraction),
+                radius_px: DEFAULT_ARC_RADIUS_PX,
+                thickness_px: DEFAULT_ARC_THICKNESS_PX,
+                rgba,
+            },
+        }
+    }
+
+    pub fn label(anchor: WidgetAnchor, text: &str, rgba: [u8; 4]) -> Self {
+        Self {
+            anchor,
+            offset_px: Vec2 { x: 0.0, y: 0.0 },
+            kind: WorldWidgetKind::Label {
+                text: text.chars().take(WORLD_WIDGET_LABEL_MAX_CHARS).collect(),
+                rgba,
+            },
+        }
+    }
+
+    pub fn with_offset_px(mut self, x: f32, y: f32) -> Self {
+        self.offset_px = Vec2 { x, y };
+        self
+    }
+}
+
+fn clamp_fraction(fraction: f32) -> f32 {
+    if fraction.is_finite() {
+        fraction.clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Per-frame widget list a scene fills in `Scene::submit_world_widgets`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorldWidgets {
+    widgets: Vec<WorldWidget>,
+}
+
+impl WorldWidgets {
+    pub fn push(&mut self, widget: WorldWidget) {
+        self.widgets.push(widget);
+    }
+
+    pub fn as_slice(&self) -> &[WorldWidget] {
+        &self.widgets
+    }
+
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.widgets.clear();
+    }
+}
+
+impl SceneWorld {
+    /// Widgets submitted for the current frame, in submission (draw) order.
+    pub fn world_widgets(&self) -> &[WorldWidget] {
+        self.world_widgets.as_slice()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn world_widgets_mut(&mut self) -> &mut WorldWidgets {
+        &mut self.world_widgets
+    }
+
+    /// World position of `anchor`, or `None` when an entity anchor is not applied or not on the
+    /// active floor.
+    pub fn world_widget_anchor_position(&self, anchor: WidgetAnchor) -> Option<Vec2> {
+        match anchor {
+            WidgetAnchor::World(position) => Some(position),
+            WidgetAnchor::Entity(id) => self
+                .find_entity(id)
+                .filter(|entity| entity.floor == self.active_floor)
+                .map(|entity| entity.transform.position),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{FloorId, RenderableDesc, RenderableKind, Transform};
+    use super::*;
+
+    #[test]
+    fn constructors_clamp_fractions_and_truncate_labels() {
+        let anchor = WidgetAnchor::World(Vec2 { x: 1.0, y: 2.0 });
+        let WorldWidgetKind::Bar { fraction, .. } = WorldWidget::bar(anchor, 1.5, [0; 4]).kind
+        else {
+            panic!("expected bar");
+        };
+        assert_eq!(fraction, 1.0);
+        let WorldWidgetKind::ProgressArc { fraction, .. } =
+            WorldWidget::progress_arc(anchor, f32::NAN, [0; 4]).kind
+        else {
+            panic!("expected arc");
+        };
+        assert_eq!(fraction, 0.0);
+
+        let label = WorldWidget::label(anchor, &"x".repeat(40), [0; 4]).with_offset_px(0.0, -12.0);
+        assert_eq!(label.offset_px, Vec2 { x: 0.0, y: -12.0 });
+        let WorldWidgetKind::Label { text, .. } = label.kind else {
+            panic!("expected label");
+        };
+        assert_eq!(text.chars().count(), WORLD_WIDGET_LABEL_MAX_CHARS);
+    }
+
+    #[test]
+    fn entity_anchors_resolve_only_for_applied_entities_on_the_active_floor() {
+        let mut world = SceneWorld::default();
+        let id = world.spawn(
+            Transform {
+                position: Vec2 { x: 3.0, y: -1.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "widget_anchor",
+            },
+        );
+        assert_eq!(
+            world.world_widget_anchor_position(WidgetAnchor::Entity(id)),
+            None
+        );
+        world.apply_pending();
+        assert_eq!(
+            world.world_widget_anchor_position(WidgetAnchor::Entity(id)),
+            Some(Vec2 { x: 3.0, y: -1.0 })
+        );
+
+        world.find_entity_mut(id).expect("entity").floor = FloorId(2);
+        assert_eq!(
+            world.world_widget_anchor_position(WidgetAnchor::Entity(id)),
+            None
+        );
+        assert_eq!(
+            world.world_widget_anchor_position(WidgetAnchor::World(Vec2 { x: 5.0, y: 5.0 })),
+            Some(Vec2 { x: 5.0, y: 5.0 })
+        );
+    }
+}
@@ -3,13 +3,11 @@ use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
+use crate::app::rendering::{draw_text, BitmapFont, TextStyle, UI_LINE_HEIGHT_PX};
 use crate::app::Vec2;
 
-use super::overlay::draw_text_clipped_with_fallback;
-
-const GLYPH_HEIGHT: i32 = 5;
 const TEXT_SCALE: i32 = 3;
-const LINE_ADVANCE: i32 = (GLYPH_HEIGHT + 2) * TEXT_SCALE;
+const LINE_ADVANCE: i32 = UI_LINE_HEIGHT_PX;
 const PANEL_PADDING: i32 = 5 * TEXT_SCALE;
 const PANEL_GAP_PX: i32 = 3 * TEXT_SCALE;
 const PANEL_WIDTH_PX: i32 = 360;
@@ -241,25 +239,27 @@ pub(crate) fn draw_command_palette(
     frame: &mut [u8],
     width: u32,
     height: u32,
+    font: &BitmapFont,
     data: &CommandPaletteRenderData,
 ) {
     if width == 0 || height == 0 {
         return;
     }
+    let scale = font.scale_for_line_height(LINE_ADVANCE);
 
     draw_filled_rect(frame, width, height, data.panel_rect, PANEL_BG_COLOR);
     draw_rect_outline(frame, width, height, data.panel_rect, PANEL_BORDER_COLOR);
 
     for header in &data.headers {
-        draw_text_clipped_with_fallback(
+        draw_text(
             frame,
             width,
             height,
+            font,
             header.x,
             header.y,
             &header.text,
-            HEADER_COLOR,
-            '?',
+            &TextStyle::new(HEADER_COLOR, scale),
         );
     }
 
@@ -282,29 +282,29 @@ pub(crate) fn draw_command_palette(
         };
         draw_filled_rect(frame, width, height, button.rect, button_color);
         draw_rect_outline(frame, width, height, button.rect, BUTTON_BORDER_COLOR);
-        draw_text_clipped_with_fallback(
+        draw_text(
             frame,
             width,
             height,
+            font,
             button.rect.left + 6,
             button.rect.top + 3,
             &button.label,
-            BUTTON_TEXT_COLOR,
-            '?',
+            &TextStyle::new(BUTTON_TEXT_COLOR, scale),
         );
     }
 
     if let Some(status_line) = data.status_line.as_ref() {
         let status_y = data.panel_rect.bottom() - PANEL_PADDING - LINE_ADVANCE;
-        draw_text_clipped_with_fallback(
+        draw_text(
             frame,
             width,
             height,
+            font,
             data.panel_rect.left + PANEL_PADDING,
             status_y,
             status_line,
-            STATUS_TEXT_COLOR,
-            '?',
+            &TextStyle::new(STATUS_TEXT_COLOR, scale),
         );
     }
 
@@ -320,15 +320,15 @@ pub(crate) fn draw_command_palette(
         };
         draw_filled_rect(frame, width, height, tooltip_rect, TOOLTIP_BG_COLOR);
         draw_rect_outline(frame, width, height, tooltip_rect, TOOLTIP_BORDER_COLOR);
-        draw_text_clipped_with_fallback(
+        draw_text(
             frame,
             width,
             height,
+            font,
             tooltip_rect.left + 6,
             tooltip_rect.top + 4,
             tooltip,
-            TOOLTIP_TEXT_COLOR,
-            '?',
+            &TextStyle::new(TOOLTIP_TEXT_COLOR, scale),
         );
     }
 }
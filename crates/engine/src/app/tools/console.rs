@@ -3,11 +3,10 @@ use std::collections::VecDeque;
 use winit::event::{ElementState, KeyEvent};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
-use super::overlay::draw_text_clipped_with_fallback;
+use crate::app::rendering::{draw_text, BitmapFont, TextAlign, TextStyle, UI_LINE_HEIGHT_PX};
 
-const GLYPH_HEIGHT: i32 = 5;
 const TEXT_SCALE: i32 = 3;
-const LINE_ADVANCE: i32 = (GLYPH_HEIGHT + 2) * TEXT_SCALE;
+const LINE_ADVANCE: i32 = UI_LINE_HEIGHT_PX;
 const CONSOLE_PADDING: i32 = 6 * TEXT_SCALE;
 const CONSOLE_BG_COLOR: [u8; 4] = [16, 16, 18, 255];
 const CONSOLE_TEXT_COLOR: [u8; 4] = [210, 230, 210, 255];
@@ -197,15 +196,36 @@ fn push_bounded(queue: &mut VecDeque<String>, value: String, max_len: usize) {
     queue.push_back(value);
 }
 
-pub(crate) fn draw_console(frame: &mut [u8], width: u32, height: u32, state: &ConsoleState) {
+pub(crate) fn draw_console(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    font: &BitmapFont,
+    state: &ConsoleState,
+) {
     if !state.is_open() || width == 0 || height == 0 {
         return;
     }
 
+    let style = TextStyle::new(CONSOLE_TEXT_COLOR, font.scale_for_line_height(LINE_ADVANCE));
+    let text_width = width as i32 - 2 * CONSOLE_PADDING;
     let output_lines: Vec<&str> = state.output_lines().collect();
     let max_output_lines = max_visible_output_lines(height);
-    let output_line_count = output_lines.len().min(max_output_lines);
-    let console_line_count = output_line_count as i32 + 1;
+    // Newest output sits just above the prompt; long lines wrap to the console width.
+    let mut visible_lines = Vec::with_capacity(max_output_lines);
+    for line in output_lines.iter().rev() {
+        if visible_lines.len() == max_output_lines {
+            break;
+        }
+        let wrapped = font.wrap(line, style.scale, text_width, style.fallback);
+        for wrapped_line in wrapped.into_iter().rev() {
+            if visible_lines.len() == max_output_lines {
+                break;
+            }
+            visible_lines.push(wrapped_line);
+        }
+    }
+    let console_line_count = visible_lines.len() as i32 + 1;
     let panel_height = console_line_count * LINE_ADVANCE + 2 * CONSOLE_PADDING;
 
     let top = (height as i32 - panel_height).max(0);
@@ -220,31 +240,37 @@ pub(crate) fn draw_console(frame: &mut [u8], width: u32, height: u32, state: &Co
         CONSOLE_BG_COLOR,
     );
 
+    // A prompt wider than the console keeps its end, where typing happens, in view.
     let prompt = format!("{CONSOLE_PROMPT_PREFIX}{}", state.current_line());
     let prompt_y = height as i32 - CONSOLE_PADDING - LINE_ADVANCE;
-    draw_text_clipped_with_fallback(
-        frame,
-        width,
-        height,
-        CONSOLE_PADDING,
-        prompt_y,
-        &prompt,
-        CONSOLE_TEXT_COLOR,
-        '?',
-    );
+    if font.measure(&prompt, &style).0 > text_width {
+        let style = style.with_align(TextAlign::Right);
+        let right = width as i32 - CONSOLE_PADDING;
+        draw_text(frame, width, height, font, right, prompt_y, &prompt, &style);
+    } else {
+        draw_text(
+            frame,
+            width,
+            height,
+            font,
+            CONSOLE_PADDING,
+            prompt_y,
+            &prompt,
+            &style,
+        );
+    }
 
-    let first_output_index = output_lines.len().saturating_sub(output_line_count);
     let mut line_y = prompt_y - LINE_ADVANCE;
-    for line in output_lines.iter().skip(first_output_index).rev() {
-        draw_text_clipped_with_fallback(
+    for line in &visible_lines {
+        draw_text(
             frame,
             width,
             height,
+            font,
             CONSOLE_PADDING,
             line_y,
             line,
-            CONSOLE_TEXT_COLOR,
-            '?',
+            &style,
         );
         line_y -= LINE_ADVANCE;
     }
@@ -509,7 +535,40 @@ mod tests {
         let mut console = ConsoleState::default();
         console.toggle_open();
         console.current_line = "abc".to_string();
-        draw_console(&mut frame, 1, 1, &console);
+        draw_console(&mut frame, 1, 1, &BitmapFont::builtin(), &console);
         assert_eq!(frame.len(), 4);
     }
+
+    #[test]
+    fn draw_console_wraps_long_output_and_keeps_the_prompt_end_in_view() {
+        const WIDTH: usize = 120;
+        let mut frame = vec![0u8; WIDTH * 200 * 4];
+        let mut console = ConsoleState::default();
+        console.toggle_open();
+        console.append_output_line("aaaa bbbb");
+        console.current_line = "abcdefghij".to_string();
+        draw_console(
+            &mut frame,
+            WIDTH as u32,
+            200,
+            &BitmapFont::builtin(),
+            &console,
+        );
+
+        let text_columns_in_rows = |rows: std::ops::Range<usize>| {
+            rows.flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+                .filter(|(x, y)| {
+                    let offset = (y * WIDTH + x) * 4;
+                    frame[offset..offset + 4] == CONSOLE_TEXT_COLOR
+                })
+                .map(|(x, _)| x)
+                .collect::<Vec<_>>()
+        };
+        let prompt = text_columns_in_rows(161..176);
+        assert!(prompt.iter().all(|x| *x < 102));
+        assert!(prompt.iter().any(|x| *x >= 96));
+        assert!(!text_columns_in_rows(140..155).is_empty());
+        assert!(!text_columns_in_rows(119..134).is_empty());
+        assert!(text_columns_in_rows(98..113).is_empty());
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
-use crate::app::{FloorId, SceneKey};
+use crate::app::metrics_history::MetricsExportFormat;
+use crate::app::{InputBinding, SceneKey};
 
 use super::ConsoleState;
 
@@ -21,11 +22,12 @@ pub(crate) enum DebugCommand {
     },
     DumpState,
     DumpAi,
+    TimersList,
     ScenarioSetup {
         scenario_id: String,
     },
     FloorSet {
-        floor: FloorId,
+        floor: String,
     },
     SwitchScene {
         scene: SceneKey,
@@ -44,6 +46,7 @@ pub(crate) enum DebugCommand {
     OrderMove {
         x: f32,
         y: f32,
+        floor: Option<String>,
     },
     OrderInteract {
         target_entity_id: u64,
@@ -51,6 +54,44 @@ pub(crate) enum DebugCommand {
     InjectInput {
         event: InjectedInputEvent,
     },
+    InputBind {
+        action: String,
+        bindings: Vec<InputBinding>,
+    },
+    InputBindings {
+        action: Option<String>,
+    },
+    StateHash,
+    SimSpeed {
+        multiplier: Option<u32>,
+    },
+    TickUntil {
+        event: String,
+        max_ticks: Option<u32>,
+    },
+    Rewind {
+        ticks: u32,
+    },
+    ProfileCapture {
+        ticks: u32,
+    },
+    MetricsSummary,
+    MetricsExport {
+        format: MetricsExportFormat,
+    },
+    CvarSet {
+        name: String,
+        value: String,
+    },
+    CvarGet {
+        name: String,
+    },
+    CvarList {
+        prefix: Option<String>,
+    },
+    CvarReset {
+        name: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -213,6 +254,14 @@ impl ConsoleCommandRegistry {
                 parse_dump_ai_command,
             )
             .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "timers.list",
+                "List pending world timers",
+                "",
+                parse_timers_list_command,
+            )
+            .expect("built-in command registration should not fail");
         registry
             .register(
                 "scenario.setup",
@@ -225,7 +274,7 @@ impl ConsoleCommandRegistry {
             .register(
                 "floor.set",
                 "Set active floor",
-                "<rooftop|main|basement>",
+                "<floor:name|index>",
                 parse_floor_set_command,
             )
             .expect("built-in command registration should not fail");
@@ -268,7 +317,7 @@ impl ConsoleCommandRegistry {
             .register(
                 "order.move",
                 "Queue move order for selected actor",
-                "<x:f32> <y:f32>",
+                "<x:f32> <y:f32> [floor:name|index]",
                 parse_order_move_command,
             )
             .expect("built-in command registration should not fail");
@@ -321,6 +370,110 @@ impl ConsoleCommandRegistry {
             )
             .expect("built-in command registration should not fail");
         registry
+            .register(
+                "input.bind",
+                "Replace the bindings of an input action",
+                "<action:string> <binding...>",
+                parse_input_bind_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "input.bindings",
+                "List input action bindings",
+                "[action:string]",
+                parse_input_bindings_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "state.hash",
+                "Print the deterministic state hash",
+                "",
+                parse_state_hash_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "sim.speed",
+                "Show or set the simulation speed multiplier",
+                "[multiplier:u32]",
+                parse_sim_speed_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "tick.until",
+                "Tick until a gameplay event fires or the cap is hit",
+                "<event:string> [max_ticks:u32]",
+                parse_tick_until_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "rewind",
+                "Restore history and re-simulate to N ticks ago",
+                "<ticks:u32>",
+                parse_rewind_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "profile.capture",
+                "Write profiling zones of the next N ticks as a Chrome trace",
+                "<ticks:u32>",
+                parse_profile_capture_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "metrics.summary",
+                "Print p50/p95/p99 frame, sim and render ms from metrics history",
+                "",
+                parse_metrics_summary_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "metrics.export",
+                "Write metrics history to the cache as CSV or JSON",
+                "[format:csv|json]",
+                parse_metrics_export_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "set",
+                "Set a console variable",
+                "<name:string> <value>",
+                parse_cvar_set_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "get",
+                "Print a console variable",
+                "<name:string>",
+                parse_cvar_get_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "cvarlist",
+                "List console variables",
+                "[prefix:string]",
+                parse_cvar_list_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
+            .register(
+                "reset",
+                "Reset a console variable to its default",
+                "<name:string>",
+                parse_cvar_reset_command,
+            )
+            .expect("built-in command registration should not fail");
+        registry
     }
 
     pub(crate) fn register<F>(
@@ -409,6 +562,25 @@ impl ConsoleCommandProcessor {
         out.extend(self.pending_debug_commands.drain(..));
     }
 
+    /// Parses one line into the debug command it would queue, without touching the console.
+    /// Local commands such as `help` or `echo` are rejected.
+    pub(crate) fn parse_debug_command_line(&self, raw_line: &str) -> Result<DebugCommand, String> {
+        let tokens = tokenize_line(raw_line.trim())?;
+        let Some((command_name, args)) = tokens.split_first() else {
+            return Err("empty command line".to_string());
+        };
+        let Some(spec) = self.registry.lookup(command_name) else {
+            return Err(format!("unknown command '{command_name}'"));
+        };
+        match (spec.parse)(args) {
+            Ok(ParsedCommand::Queueable(command)) => Ok(command),
+            Ok(ParsedCommand::Local(_)) => {
+                Err(format!("'{command_name}' does not queue a debug command"))
+            }
+            Err(error) => Err(format!("{}. usage: {}", error.reason, error.usage)),
+        }
+    }
+
     fn process_line(&mut self, console: &mut ConsoleState, raw_line: &str) {
         let trimmed = raw_line.trim();
         if trimmed.is_empty() {
@@ -620,6 +792,164 @@ fn parse_dump_ai_command(args: &[String]) -> Result<ParsedCommand, CommandParseE
     Ok(ParsedCommand::Queueable(DebugCommand::DumpAi))
 }
 
+fn parse_timers_list_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    require_no_args(args, "timers.list")?;
+    Ok(ParsedCommand::Queueable(DebugCommand::TimersList))
+}
+
+fn parse_state_hash_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    require_no_args(args, "state.hash")?;
+    Ok(ParsedCommand::Queueable(DebugCommand::StateHash))
+}
+
+fn parse_sim_speed_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    if args.len() > 1 {
+        return Err(CommandParseError {
+            reason: "expected at most one argument [multiplier]".to_string(),
+            usage: "sim.speed [multiplier]".to_string(),
+        });
+    }
+    let multiplier = match args.first() {
+        Some(raw) => Some(parse_positive_u32(
+            raw,
+            "multiplier",
+            "sim.speed [multiplier]",
+        )?),
+        None => None,
+    };
+    Ok(ParsedCommand::Queueable(DebugCommand::SimSpeed {
+        multiplier,
+    }))
+}
+
+fn parse_tick_until_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    const USAGE: &str = "tick.until <event> [max_ticks]";
+    if args.is_empty() || args.len() > 2 {
+        return Err(CommandParseError {
+            reason: "expected <event> and optional [max_ticks]".to_string(),
+            usage: USAGE.to_string(),
+        });
+    }
+    let max_ticks = match args.get(1) {
+        Some(raw) => Some(parse_positive_u32(raw, "max_ticks", USAGE)?),
+        None => None,
+    };
+    Ok(ParsedCommand::Queueable(DebugCommand::TickUntil {
+        event: args[0].clone(),
+        max_ticks,
+    }))
+}
+
+fn parse_rewind_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    const USAGE: &str = "rewind <ticks>";
+    if args.len() != 1 {
+        return Err(CommandParseError {
+            reason: "expected exactly one argument <ticks>".to_string(),
+            usage: USAGE.to_string(),
+        });
+    }
+    let ticks = parse_positive_u32(&args[0], "ticks", USAGE)?;
+    Ok(ParsedCommand::Queueable(DebugCommand::Rewind { ticks }))
+}
+
+fn parse_profile_capture_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    const USAGE: &str = "profile.capture <ticks>";
+    if args.len() != 1 {
+        return Err(CommandParseError {
+            reason: "expected exactly one argument <ticks>".to_string(),
+            usage: USAGE.to_string(),
+        });
+    }
+    let ticks = parse_positive_u32(&args[0], "ticks", USAGE)?;
+    Ok(ParsedCommand::Queueable(DebugCommand::ProfileCapture {
+        ticks,
+    }))
+}
+
+fn parse_metrics_summary_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    require_no_args(args, "metrics.summary")?;
+    Ok(ParsedCommand::Queueable(DebugCommand::MetricsSummary))
+}
+
+fn parse_metrics_export_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    const USAGE: &str = "metrics.export [csv|json]";
+    if args.len() > 1 {
+        return Err(CommandParseError {
+            reason: "expected at most one argument [csv|json]".to_string(),
+            usage: USAGE.to_string(),
+        });
+    }
+    let format = match args.first() {
+        Some(raw) => MetricsExportFormat::parse(raw).ok_or_else(|| CommandParseError {
+            reason: format!("invalid format '{raw}' (expected csv|json)"),
+            usage: USAGE.to_string(),
+        })?,
+        None => MetricsExportFormat::Csv,
+    };
+    Ok(ParsedCommand::Queueable(DebugCommand::MetricsExport {
+        format,
+    }))
+}
+
+fn parse_cvar_set_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    if args.len() != 2 {
+        return Err(CommandParseError {
+            reason: "expected <name> and <value>".to_string(),
+            usage: "set <name> <value>".to_string(),
+        });
+    }
+    Ok(ParsedCommand::Queueable(DebugCommand::CvarSet {
+        name: args[0].clone(),
+        value: args[1].clone(),
+    }))
+}
+
+fn parse_cvar_get_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    if args.len() != 1 {
+        return Err(CommandParseError {
+            reason: "expected exactly one argument <name>".to_string(),
+            usage: "get <name>".to_string(),
+        });
+    }
+    Ok(ParsedCommand::Queueable(DebugCommand::CvarGet {
+        name: args[0].clone(),
+    }))
+}
+
+fn parse_cvar_list_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    if args.len() > 1 {
+        return Err(CommandParseError {
+            reason: "expected at most one argument [prefix]".to_string(),
+            usage: "cvarlist [prefix]".to_string(),
+        });
+    }
+    Ok(ParsedCommand::Queueable(DebugCommand::CvarList {
+        prefix: args.first().cloned(),
+    }))
+}
+
+fn parse_cvar_reset_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    if args.len() != 1 {
+        return Err(CommandParseError {
+            reason: "expected exactly one argument <name>".to_string(),
+            usage: "reset <name>".to_string(),
+        });
+    }
+    Ok(ParsedCommand::Queueable(DebugCommand::CvarReset {
+        name: args[0].clone(),
+    }))
+}
+
+fn parse_positive_u32(raw: &str, name: &str, usage: &str) -> Result<u32, CommandParseError> {
+    match raw.parse::<u32>() {
+        Ok(value) if value > 0 => Ok(value),
+        _ => Err(CommandParseError {
+            reason: format!("invalid {name} '{raw}' (expected u32 > 0)"),
+            usage: usage.to_string(),
+        }),
+    }
+}
+
 fn parse_scenario_setup_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
     if args.len() != 1 {
         return Err(CommandParseError {
@@ -635,26 +965,12 @@ fn parse_scenario_setup_command(args: &[String]) -> Result<ParsedCommand, Comman
 fn parse_floor_set_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
     if args.len() != 1 {
         return Err(CommandParseError {
-            reason: "expected exactly one argument <rooftop|main|basement>".to_string(),
-            usage: "floor.set <rooftop|main|basement>".to_string(),
+            reason: "expected exactly one argument <floor>".to_string(),
+            usage: "floor.set <floor>".to_string(),
         });
     }
 
-    let floor = match args[0].to_ascii_lowercase().as_str() {
-        "rooftop" => FloorId::Rooftop,
-        "main" => FloorId::Main,
-        "basement" => FloorId::Basement,
-        _ => {
-            return Err(CommandParseError {
-                reason: format!(
-                    "invalid floor '{}' (expected rooftop|main|basement)",
-                    args[0]
-                ),
-                usage: "floor.set <rooftop|main|basement>".to_string(),
-            });
-        }
-    };
-
+    let floor = args[0].clone();
     Ok(ParsedCommand::Queueable(DebugCommand::FloorSet { floor }))
 }
 
@@ -751,23 +1067,29 @@ fn parse_select_command(args: &[String]) -> Result<ParsedCommand, CommandParseEr
 }
 
 fn parse_order_move_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
-    if args.len() != 2 {
+    if !(2..=3).contains(&args.len()) {
         return Err(CommandParseError {
-            reason: "expected exactly two arguments <x> <y>".to_string(),
-            usage: "order.move <x> <y>".to_string(),
+            reason: "expected arguments <x> <y> [floor]".to_string(),
+            usage: "order.move <x> <y> [floor]".to_string(),
         });
     }
 
     let x = args[0].parse::<f32>().map_err(|_| CommandParseError {
         reason: format!("invalid x coordinate '{}' (expected f32)", args[0]),
-        usage: "order.move <x> <y>".to_string(),
+        usage: "order.move <x> <y> [floor]".to_string(),
     })?;
     let y = args[1].parse::<f32>().map_err(|_| CommandParseError {
         reason: format!("invalid y coordinate '{}' (expected f32)", args[1]),
-        usage: "order.move <x> <y>".to_string(),
+        usage: "order.move <x> <y> [floor]".to_string(),
     })?;
 
-    Ok(ParsedCommand::Queueable(DebugCommand::OrderMove { x, y }))
+    let floor = args.get(2).cloned();
+
+    Ok(ParsedCommand::Queueable(DebugCommand::OrderMove {
+        x,
+        y,
+        floor,
+    }))
 }
 
 fn parse_order_interact_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
@@ -838,6 +1160,40 @@ fn parse_input_mouse_up_command(args: &[String]) -> Result<ParsedCommand, Comman
     }))
 }
 
+fn parse_input_bind_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    const USAGE: &str = "input.bind <action> <binding...>";
+    if args.len() < 2 {
+        return Err(CommandParseError {
+            reason: "expected an action and at least one binding".to_string(),
+            usage: USAGE.to_string(),
+        });
+    }
+    let bindings = args[1..]
+        .iter()
+        .map(|raw| raw.parse::<InputBinding>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| CommandParseError {
+            reason: error.to_string(),
+            usage: USAGE.to_string(),
+        })?;
+    Ok(ParsedCommand::Queueable(DebugCommand::InputBind {
+        action: args[0].clone(),
+        bindings,
+    }))
+}
+
+fn parse_input_bindings_command(args: &[String]) -> Result<ParsedCommand, CommandParseError> {
+    if args.len() > 1 {
+        return Err(CommandParseError {
+            reason: "expected at most one argument [action]".to_string(),
+            usage: "input.bindings [action]".to_string(),
+        });
+    }
+    Ok(ParsedCommand::Queueable(DebugCommand::InputBindings {
+        action: args.first().cloned(),
+    }))
+}
+
 fn parse_single_injected_key_arg(
     args: &[String],
     usage: &str,
@@ -956,53 +1312,97 @@ mod tests {
         );
         assert_eq!(lines[10], "dump.state - Dump deterministic state probe");
         assert_eq!(lines[11], "dump.ai - Dump deterministic AI probe");
-        assert_eq!(
-            lines[12],
-            "scenario.setup <scenario_id:string> - Setup deterministic gameplay scenario"
-        );
+        assert_eq!(lines[12], "timers.list - List pending world timers");
         assert_eq!(
             lines[13],
-            "floor.set <rooftop|main|basement> - Set active floor"
+            "scenario.setup <scenario_id:string> - Setup deterministic gameplay scenario"
         );
+        assert_eq!(lines[14], "floor.set <floor:name|index> - Set active floor");
         assert_eq!(
-            lines[14],
+            lines[15],
             "switch_scene <scene_id:a|b> - Switch active scene"
         );
-        assert_eq!(lines[15], "quit - Quit app");
-        assert_eq!(lines[16], "despawn <entity_id:u64> - Despawn entity by id");
+        assert_eq!(lines[16], "quit - Quit app");
+        assert_eq!(lines[17], "despawn <entity_id:u64> - Despawn entity by id");
         assert_eq!(
-            lines[17],
+            lines[18],
             "spawn <def_name:string> [x:f32 y:f32] - Spawn entity by def name"
         );
-        assert_eq!(lines[18], "select <entity_id:u64> - Select entity by id");
+        assert_eq!(lines[19], "select <entity_id:u64> - Select entity by id");
         assert_eq!(
-            lines[19],
-            "order.move <x:f32> <y:f32> - Queue move order for selected actor"
+            lines[20],
+            "order.move <x:f32> <y:f32> [floor:name|index] - Queue move order for selected actor"
         );
         assert_eq!(
-            lines[20],
+            lines[21],
             "order.interact <target_entity_id:u64> - Queue interaction order for selected actor"
         );
         assert_eq!(
-            lines[21],
+            lines[22],
             "input.key_down <key:w|a|s|d|up|down|left|right|i|j|k|l> - Inject key down"
         );
         assert_eq!(
-            lines[22],
+            lines[23],
             "input.key_up <key:w|a|s|d|up|down|left|right|i|j|k|l> - Inject key up"
         );
         assert_eq!(
-            lines[23],
+            lines[24],
             "input.mouse_move <x:f32> <y:f32> - Inject mouse move (px)"
         );
         assert_eq!(
-            lines[24],
+            lines[25],
             "input.mouse_down <button:left|right> - Inject mouse down"
         );
         assert_eq!(
-            lines[25],
+            lines[26],
             "input.mouse_up <button:left|right> - Inject mouse up"
         );
+        assert_eq!(
+            lines[27],
+            "input.bind <action:string> <binding...> - Replace the bindings of an input action"
+        );
+        assert_eq!(
+            lines[28],
+            "input.bindings [action:string] - List input action bindings"
+        );
+        assert_eq!(lines[29], "state.hash - Print the deterministic state hash");
+        assert_eq!(
+            lines[30],
+            "sim.speed [multiplier:u32] - Show or set the simulation speed multiplier"
+        );
+        assert_eq!(
+            lines[31],
+            "tick.until <event:string> [max_ticks:u32] - Tick until a gameplay event fires or the cap is hit"
+        );
+        assert_eq!(
+            lines[32],
+            "rewind <ticks:u32> - Restore history and re-simulate to N ticks ago"
+        );
+        assert_eq!(
+            lines[33],
+            "profile.capture <ticks:u32> - Write profiling zones of the next N ticks as a Chrome trace"
+        );
+        assert_eq!(
+            lines[34],
+            "metrics.summary - Print p50/p95/p99 frame, sim and render ms from metrics history"
+        );
+        assert_eq!(
+            lines[35],
+            "metrics.export [format:csv|json] - Write metrics history to the cache as CSV or JSON"
+        );
+        assert_eq!(
+            lines[36],
+            "set <name:string> <value> - Set a console variable"
+        );
+        assert_eq!(lines[37], "get <name:string> - Print a console variable");
+        assert_eq!(
+            lines[38],
+            "cvarlist [prefix:string] - List console variables"
+        );
+        assert_eq!(
+            lines[39],
+            "reset <name:string> - Reset a console variable to its default"
+        );
     }
 
     #[test]
@@ -1063,6 +1463,22 @@ mod tests {
         console.push_pending_line_for_test("resume_sim");
         console.push_pending_line_for_test("dump.state");
         console.push_pending_line_for_test("dump.ai");
+        console.push_pending_line_for_test("timers.list");
+        console.push_pending_line_for_test("state.hash");
+        console.push_pending_line_for_test("sim.speed");
+        console.push_pending_line_for_test("sim.speed 5");
+        console.push_pending_line_for_test("tick.until entity_died");
+        console.push_pending_line_for_test("tick.until status_applied 600");
+        console.push_pending_line_for_test("rewind 90");
+        console.push_pending_line_for_test("profile.capture 120");
+        console.push_pending_line_for_test("metrics.summary");
+        console.push_pending_line_for_test("metrics.export");
+        console.push_pending_line_for_test("metrics.export JSON");
+        console.push_pending_line_for_test("set render.fps_cap 144");
+        console.push_pending_line_for_test("get render.fps_cap");
+        console.push_pending_line_for_test("cvarlist");
+        console.push_pending_line_for_test("cvarlist budgets.");
+        console.push_pending_line_for_test("reset render.fps_cap");
         console.push_pending_line_for_test("scenario.setup combat_chaser");
         console.push_pending_line_for_test("floor.set basement");
         console.push_pending_line_for_test("switch_scene a");
@@ -1071,6 +1487,7 @@ mod tests {
         console.push_pending_line_for_test("spawn proto.worker 1.5 -2.0");
         console.push_pending_line_for_test("select 43");
         console.push_pending_line_for_test("order.move 4.0 -8.0");
+        console.push_pending_line_for_test("order.move 1.0 2.0 2");
         console.push_pending_line_for_test("order.interact 44");
         console.push_pending_line_for_test("input.key_down w");
         console.push_pending_line_for_test("input.key_up right");
@@ -1095,11 +1512,48 @@ mod tests {
                 DebugCommand::ResumeSim,
                 DebugCommand::DumpState,
                 DebugCommand::DumpAi,
+                DebugCommand::TimersList,
+                DebugCommand::StateHash,
+                DebugCommand::SimSpeed { multiplier: None },
+                DebugCommand::SimSpeed {
+                    multiplier: Some(5)
+                },
+                DebugCommand::TickUntil {
+                    event: "entity_died".to_string(),
+                    max_ticks: None,
+                },
+                DebugCommand::TickUntil {
+                    event: "status_applied".to_string(),
+                    max_ticks: Some(600),
+                },
+                DebugCommand::Rewind { ticks: 90 },
+                DebugCommand::ProfileCapture { ticks: 120 },
+                DebugCommand::MetricsSummary,
+                DebugCommand::MetricsExport {
+                    format: MetricsExportFormat::Csv,
+                },
+                DebugCommand::MetricsExport {
+                    format: MetricsExportFormat::Json,
+                },
+                DebugCommand::CvarSet {
+                    name: "render.fps_cap".to_string(),
+                    value: "144".to_string(),
+                },
+                DebugCommand::CvarGet {
+                    name: "render.fps_cap".to_string(),
+                },
+                DebugCommand::CvarList { prefix: None },
+                DebugCommand::CvarList {
+                    prefix: Some("budgets.".to_string()),
+                },
+                DebugCommand::CvarReset {
+                    name: "render.fps_cap".to_string(),
+                },
                 DebugCommand::ScenarioSetup {
                     scenario_id: "combat_chaser".to_string(),
                 },
                 DebugCommand::FloorSet {
-                    floor: FloorId::Basement,
+                    floor: "basement".to_string(),
                 },
                 DebugCommand::SwitchScene { scene: SceneKey::A },
                 DebugCommand::Quit,
@@ -1109,7 +1563,16 @@ mod tests {
                     position: Some((1.5, -2.0)),
                 },
                 DebugCommand::Select { entity_id: 43 },
-                DebugCommand::OrderMove { x: 4.0, y: -8.0 },
+                DebugCommand::OrderMove {
+                    x: 4.0,
+                    y: -8.0,
+                    floor: None,
+                },
+                DebugCommand::OrderMove {
+                    x: 1.0,
+                    y: 2.0,
+                    floor: Some("2".to_string()),
+                },
                 DebugCommand::OrderInteract {
                     target_entity_id: 44
                 },
@@ -1165,6 +1628,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn input_bind_parses_chords_and_rejects_bad_bindings() {
+        let mut processor = ConsoleCommandProcessor::new();
+        let mut console = ConsoleState::default();
+        console.push_pending_line_for_test("input.bind save Ctrl+S F5");
+        console.push_pending_line_for_test("input.bindings");
+        console.push_pending_line_for_test("input.bindings zoom_in");
+        console.push_pending_line_for_test("input.bind save");
+        console.push_pending_line_for_test("input.bind save Hyper+S");
+        console.push_pending_line_for_test("input.bindings a b");
+
+        processor.process_pending_lines(&mut console);
+
+        let mut queued = Vec::new();
+        processor.drain_pending_debug_commands_into(&mut queued);
+        assert_eq!(
+            queued,
+            vec![
+                DebugCommand::InputBind {
+                    action: "save".to_string(),
+                    bindings: vec![
+                        "Ctrl+S".parse().expect("binding"),
+                        "F5".parse().expect("binding"),
+                    ],
+                },
+                DebugCommand::InputBindings { action: None },
+                DebugCommand::InputBindings {
+                    action: Some("zoom_in".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            collect_output(&console),
+            vec![
+                "error: expected an action and at least one binding. usage: input.bind <action> <binding...>",
+                "error: invalid binding 'Hyper+S': unknown modifier 'Hyper'. usage: input.bind <action> <binding...>",
+                "error: expected at most one argument [action]. usage: input.bindings [action]",
+            ]
+        );
+    }
+
     #[test]
     fn select_and_order_commands_validate_bad_args_with_usage() {
         let mut processor = ConsoleCommandProcessor::new();
@@ -1173,6 +1677,7 @@ mod tests {
         console.push_pending_line_for_test("select nope");
         console.push_pending_line_for_test("order.move 1");
         console.push_pending_line_for_test("order.move x 2");
+        console.push_pending_line_for_test("order.move 1 2 3 4");
         console.push_pending_line_for_test("order.interact");
         console.push_pending_line_for_test("order.interact nope");
 
@@ -1183,8 +1688,9 @@ mod tests {
             vec![
                 "error: expected exactly one argument <entity_id>. usage: select <entity_id>",
                 "error: invalid entity id 'nope' (expected u64). usage: select <entity_id>",
-                "error: expected exactly two arguments <x> <y>. usage: order.move <x> <y>",
-                "error: invalid x coordinate 'x' (expected f32). usage: order.move <x> <y>",
+                "error: expected arguments <x> <y> [floor]. usage: order.move <x> <y> [floor]",
+                "error: invalid x coordinate 'x' (expected f32). usage: order.move <x> <y> [floor]",
+                "error: expected arguments <x> <y> [floor]. usage: order.move <x> <y> [floor]",
                 "error: expected exactly one argument <target_entity_id>. usage: order.interact <target_entity_id>",
                 "error: invalid target entity id 'nope' (expected u64). usage: order.interact <target_entity_id>",
             ]
@@ -1215,16 +1721,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sim_speed_tick_until_rewind_and_profile_validate_bad_args_with_usage() {
+        let mut processor = ConsoleCommandProcessor::new();
+        let mut console = ConsoleState::default();
+        console.push_pending_line_for_test("sim.speed 0");
+        console.push_pending_line_for_test("sim.speed 2 3");
+        console.push_pending_line_for_test("tick.until");
+        console.push_pending_line_for_test("tick.until entity_died nope");
+        console.push_pending_line_for_test("rewind");
+        console.push_pending_line_for_test("rewind 0");
+        console.push_pending_line_for_test("profile.capture");
+        console.push_pending_line_for_test("profile.capture 0");
+
+        processor.process_pending_lines(&mut console);
+
+        assert_eq!(
+            collect_output(&console),
+            vec![
+                "error: invalid multiplier '0' (expected u32 > 0). usage: sim.speed [multiplier]",
+                "error: expected at most one argument [multiplier]. usage: sim.speed [multiplier]",
+                "error: expected <event> and optional [max_ticks]. usage: tick.until <event> [max_ticks]",
+                "error: invalid max_ticks 'nope' (expected u32 > 0). usage: tick.until <event> [max_ticks]",
+                "error: expected exactly one argument <ticks>. usage: rewind <ticks>",
+                "error: invalid ticks '0' (expected u32 > 0). usage: rewind <ticks>",
+                "error: expected exactly one argument <ticks>. usage: profile.capture <ticks>",
+                "error: invalid ticks '0' (expected u32 > 0). usage: profile.capture <ticks>",
+            ]
+        );
+    }
+
+    #[test]
+    fn cvar_commands_validate_bad_args_with_usage() {
+        let mut processor = ConsoleCommandProcessor::new();
+        let mut console = ConsoleState::default();
+        console.push_pending_line_for_test("set render.fps_cap");
+        console.push_pending_line_for_test("get");
+        console.push_pending_line_for_test("cvarlist render. budgets.");
+        console.push_pending_line_for_test("reset a b");
+
+        processor.process_pending_lines(&mut console);
+
+        assert_eq!(
+            collect_output(&console),
+            vec![
+                "error: expected <name> and <value>. usage: set <name> <value>",
+                "error: expected exactly one argument <name>. usage: get <name>",
+                "error: expected at most one argument [prefix]. usage: cvarlist [prefix]",
+                "error: expected exactly one argument <name>. usage: reset <name>",
+            ]
+        );
+    }
+
     #[test]
     fn dump_commands_validate_bad_args_with_usage() {
         let mut processor = ConsoleCommandProcessor::new();
         let mut console = ConsoleState::default();
         console.push_pending_line_for_test("dump.state now");
         console.push_pending_line_for_test("dump.ai now");
+        console.push_pending_line_for_test("timers.list now");
+        console.push_pending_line_for_test("state.hash now");
+        console.push_pending_line_for_test("metrics.summary now");
+        console.push_pending_line_for_test("metrics.export xml");
+        console.push_pending_line_for_test("metrics.export csv json");
         console.push_pending_line_for_test("scenario.setup");
         console.push_pending_line_for_test("scenario.setup combat chaser");
         console.push_pending_line_for_test("floor.set");
-        console.push_pending_line_for_test("floor.set attic");
         console.push_pending_line_for_test("floor.set main extra");
 
         processor.process_pending_lines(&mut console);
@@ -1234,11 +1796,15 @@ mod tests {
             vec![
                 "error: unexpected extra arguments. usage: dump.state",
                 "error: unexpected extra arguments. usage: dump.ai",
+                "error: unexpected extra arguments. usage: timers.list",
+                "error: unexpected extra arguments. usage: state.hash",
+                "error: unexpected extra arguments. usage: metrics.summary",
+                "error: invalid format 'xml' (expected csv|json). usage: metrics.export [csv|json]",
+                "error: expected at most one argument [csv|json]. usage: metrics.export [csv|json]",
                 "error: expected exactly one argument <scenario_id>. usage: scenario.setup <scenario_id>",
                 "error: expected exactly one argument <scenario_id>. usage: scenario.setup <scenario_id>",
-                "error: expected exactly one argument <rooftop|main|basement>. usage: floor.set <rooftop|main|basement>",
-                "error: invalid floor 'attic' (expected rooftop|main|basement). usage: floor.set <rooftop|main|basement>",
-                "error: expected exactly one argument <rooftop|main|basement>. usage: floor.set <rooftop|main|basement>",
+                "error: expected exactly one argument <floor>. usage: floor.set <floor>",
+                "error: expected exactly one argument <floor>. usage: floor.set <floor>",
             ]
         );
     }
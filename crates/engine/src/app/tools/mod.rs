@@ -10,5 +10,5 @@ pub(crate) use command_palette::{
 };
 pub(crate) use console::{draw_console, ConsoleState};
 pub(crate) use console_commands::{ConsoleCommandProcessor, DebugCommand};
-pub(crate) use overlay::{draw_overlay, OverlayData};
-pub(crate) use perf_stats::{PerfStats, PerfStatsSnapshot, RollingMsStats};
+pub(crate) use overlay::{draw_overlay, OverlayData, PerfGraphData, PERF_GRAPH_SAMPLES};
+pub(crate) use perf_stats::{PerfStats, PerfStatsSnapshot, RollingMsStats, RollingWindowMs};
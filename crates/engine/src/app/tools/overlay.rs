@@ -1,12 +1,10 @@
+use crate::app::profiling::ZoneStat;
+use crate::app::rendering::{draw_text, BitmapFont, TextStyle, UI_LINE_HEIGHT_PX};
 use crate::app::{DebugInfoSnapshot, DebugJobState, EntityId, LoopMetricsSnapshot};
 
 use super::PerfStatsSnapshot;
 
-const GLYPH_WIDTH: i32 = 3;
-const GLYPH_HEIGHT: i32 = 5;
 const TEXT_SCALE: i32 = 3;
-const GLYPH_ADVANCE: i32 = (GLYPH_WIDTH + 1) * TEXT_SCALE;
-const LINE_ADVANCE: i32 = (GLYPH_HEIGHT + 2) * TEXT_SCALE;
 const OVERLAY_PADDING: i32 = 6 * TEXT_SCALE;
 const OVERLAY_PANEL_INSET_X: i32 = 4 * TEXT_SCALE;
 const OVERLAY_PANEL_INSET_Y: i32 = 3 * TEXT_SCALE;
@@ -17,6 +15,17 @@ const OVERLAY_PANEL_BORDER_COLOR: [u8; 4] = [92, 106, 126, 255];
 const PERF_SECTION_LABEL: &str = "Perf";
 const SCENE_SECTION_LABEL: &str = "Scene";
 const INSPECT_SECTION_LABEL: &str = "Inspect";
+const ZONES_SECTION_LABEL: &str = "Zones";
+/// Samples shown by the perf graph, newest on the right.
+pub(crate) const PERF_GRAPH_SAMPLES: usize = 120;
+const PERF_GRAPH_SAMPLE_WIDTH_PX: i32 = 2;
+const PERF_GRAPH_HEIGHT_PX: i32 = 72;
+/// Smallest value the graph's top edge stands for, so idle frames do not fill it.
+const PERF_GRAPH_MIN_SCALE_MS: f32 = 4.0;
+const PERF_GRAPH_SIM_COLOR: [u8; 4] = [96, 220, 140, 255];
+const PERF_GRAPH_REN_COLOR: [u8; 4] = [110, 170, 255, 255];
+const PERF_GRAPH_SIM_BUDGET_COLOR: [u8; 4] = [48, 120, 76, 255];
+const PERF_GRAPH_REN_BUDGET_COLOR: [u8; 4] = [56, 88, 140, 255];
 
 #[derive(Debug, Clone)]
 pub(crate) struct OverlayData {
@@ -24,31 +33,73 @@ pub(crate) struct OverlayData {
     pub perf: PerfStatsSnapshot,
     pub render_fps_cap: Option<u32>,
     pub slow_frame_delay_ms: u64,
+    pub sim_speed: u32,
+    pub sim_speed_capped: bool,
     pub entity_count: usize,
     pub content_status: &'static str,
     pub selected_entity: Option<EntityId>,
     pub selected_target: Option<crate::app::Vec2>,
     pub resource_count: Option<u32>,
     pub debug_info: Option<DebugInfoSnapshot>,
+    pub zones: Vec<ZoneStat>,
+    pub perf_graph: Option<PerfGraphData>,
 }
 
-pub(crate) fn draw_overlay(frame: &mut [u8], width: u32, height: u32, data: &OverlayData) {
+/// Recent sim and render times for the overlay sparkline, oldest first, plus the soft
+/// budgets drawn as horizontal lines.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PerfGraphData {
+    pub sim_ms: Vec<f32>,
+    pub render_ms: Vec<f32>,
+    pub sim_budget_ms: Option<f32>,
+    pub render_budget_ms: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GraphRect {
+    left: i32,
+    top: i32,
+    width: i32,
+    height: i32,
+    scale_ms: f32,
+}
+
+impl GraphRect {
+    fn y_for(&self, value_ms: f32) -> i32 {
+        let fraction = (value_ms / self.scale_ms).clamp(0.0, 1.0);
+        self.top + self.height - 1 - (fraction * (self.height - 1) as f32).round() as i32
+    }
+}
+
+pub(crate) fn draw_overlay(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    font: &BitmapFont,
+    data: &OverlayData,
+) {
     if width == 0 || height == 0 {
         return;
     }
 
+    if let Some(graph) = data.perf_graph.as_ref() {
+        draw_perf_graph(frame, width, height, graph);
+    }
+
     let lines = build_overlay_lines(data);
     if lines.is_empty() {
         return;
     }
 
-    let longest_line_chars = lines
+    let style = overlay_text_style(font, OVERLAY_TEXT_PRIMARY_COLOR);
+    let line_advance = font.line_height() * style.scale;
+    let longest_line_width = lines
         .iter()
-        .map(|line| line.chars().count() as i32)
+        .map(|line| font.measure(line, &style).0)
         .max()
         .unwrap_or(0);
-    let panel_width = longest_line_chars * GLYPH_ADVANCE + OVERLAY_PANEL_INSET_X * 2;
-    let panel_height = lines.len() as i32 * LINE_ADVANCE + OVERLAY_PANEL_INSET_Y * 2;
+    let panel_width = longest_line_width + OVERLAY_PANEL_INSET_X * 2;
+    let panel_height = lines.len() as i32 * line_advance + OVERLAY_PANEL_INSET_Y * 2;
     let panel_left = OVERLAY_PADDING - OVERLAY_PANEL_INSET_X;
     let panel_top = OVERLAY_PADDING - OVERLAY_PANEL_INSET_Y;
     draw_filled_rect(
@@ -74,12 +125,29 @@ pub(crate) fn draw_overlay(frame: &mut [u8], width: u32, height: u32, data: &Ove
 
     let mut y = OVERLAY_PADDING;
     for line in lines {
-        let color = overlay_line_color(&line);
-        draw_text_clipped(frame, width, height, OVERLAY_PADDING, y, &line, color);
-        y += LINE_ADVANCE;
+        let style = TextStyle {
+            color: overlay_line_color(&line),
+            ..style
+        };
+        draw_text(
+            frame,
+            width,
+            height,
+            font,
+            OVERLAY_PADDING,
+            y,
+            &line,
+            &style,
+        );
+        y += line_advance;
     }
 }
 
+/// Overlay text leaves unknown characters blank rather than drawing a fallback glyph.
+fn overlay_text_style(font: &BitmapFont, color: [u8; 4]) -> TextStyle {
+    TextStyle::new(color, font.scale_for_line_height(UI_LINE_HEIGHT_PX)).with_fallback(None)
+}
+
 fn build_overlay_lines(data: &OverlayData) -> Vec<String> {
     let mut lines = vec![
         PERF_SECTION_LABEL.to_string(),
@@ -88,7 +156,7 @@ fn build_overlay_lines(data: &OverlayData) -> Vec<String> {
             data.render_fps_cap,
             data.slow_frame_delay_ms,
         ),
-        format!("TPS: {:.1}", data.metrics.tps),
+        format_tps_line(data.metrics.tps, data.sim_speed, data.sim_speed_capped),
         format!("Frame: {:.2} ms", data.metrics.frame_time_ms),
         format_perf_line("SIM", data.perf.sim),
         format_perf_line("REN", data.perf.ren),
@@ -144,13 +212,21 @@ fn build_overlay_lines(data: &OverlayData) -> Vec<String> {
         }
     }
 
+    if !data.zones.is_empty() {
+        lines.push(String::new());
+        lines.push(ZONES_SECTION_LABEL.to_string());
+        for zone in &data.zones {
+            lines.push(format_zone_line(zone));
+        }
+    }
+
     lines
 }
 
 fn overlay_line_color(line: &str) -> [u8; 4] {
     if matches!(
         line,
-        PERF_SECTION_LABEL | SCENE_SECTION_LABEL | INSPECT_SECTION_LABEL
+        PERF_SECTION_LABEL | SCENE_SECTION_LABEL | INSPECT_SECTION_LABEL | ZONES_SECTION_LABEL
     ) {
         OVERLAY_TEXT_DIM_COLOR
     } else {
@@ -169,6 +245,17 @@ fn format_fps_line(current_fps: f32, cap: Option<u32>, slow_frame_delay_ms: u64)
     )
 }
 
+/// `capped` marks a speed that `max_ticks_per_frame` keeps the sim from reaching.
+fn format_tps_line(tps: f32, sim_speed: u32, capped: bool) -> String {
+    if sim_speed > 1 && capped {
+        format!("TPS: {tps:.1} x{sim_speed} (tick cap)")
+    } else if sim_speed > 1 {
+        format!("TPS: {tps:.1} x{sim_speed}")
+    } else {
+        format!("TPS: {tps:.1}")
+    }
+}
+
 fn format_perf_line(label: &str, stats: super::RollingMsStats) -> String {
     format!(
         "{} l/a/m: {:.2}/{:.2}/{:.2} ms",
@@ -176,6 +263,13 @@ fn format_perf_line(label: &str, stats: super::RollingMsStats) -> String {
     )
 }
 
+fn format_zone_line(zone: &ZoneStat) -> String {
+    format!(
+        "{} a/m: {:.2}/{:.2} ms",
+        zone.name, zone.ms.avg_ms, zone.ms.max_ms
+    )
+}
+
 fn debug_job_state_text(state: DebugJobState) -> String {
     match state {
         DebugJobState::None => "none".to_string(),
@@ -184,86 +278,106 @@ fn debug_job_state_text(state: DebugJobState) -> String {
     }
 }
 
-fn draw_text_clipped(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
-    mut x: i32,
-    y: i32,
-    text: &str,
-    color: [u8; 4],
-) {
-    for ch in text.chars() {
-        let glyph = glyph_for(ch).unwrap_or(SPACE_GLYPH);
-        draw_glyph_clipped(frame, width, height, x, y, glyph, color);
-        x += GLYPH_ADVANCE;
+/// Bottom-left sparkline of sim (green) and render (blue) ms with their budget lines.
+fn draw_perf_graph(frame: &mut [u8], width: u32, height: u32, data: &PerfGraphData) {
+    let graph_width = PERF_GRAPH_SAMPLES as i32 * PERF_GRAPH_SAMPLE_WIDTH_PX;
+    let top = height as i32 - OVERLAY_PADDING - PERF_GRAPH_HEIGHT_PX;
+    if top < OVERLAY_PADDING || graph_width + OVERLAY_PADDING * 2 > width as i32 {
+        return;
     }
-}
-
-#[allow(clippy::too_many_arguments)]
-pub(crate) fn draw_text_clipped_with_fallback(
-    frame: &mut [u8],
-    width: u32,
-    height: u32,
-    mut x: i32,
-    y: i32,
-    text: &str,
-    color: [u8; 4],
-    fallback_char: char,
-) {
-    let fallback_glyph = glyph_for(fallback_char).unwrap_or(SPACE_GLYPH);
-    for ch in text.chars() {
-        let glyph = glyph_for(ch).unwrap_or(fallback_glyph);
-        draw_glyph_clipped(frame, width, height, x, y, glyph, color);
-        x += GLYPH_ADVANCE;
+    let scale_ms = data
+        .sim_ms
+        .iter()
+        .chain(&data.render_ms)
+        .chain(data.sim_budget_ms.iter())
+        .chain(data.render_budget_ms.iter())
+        .fold(PERF_GRAPH_MIN_SCALE_MS, |max, value| max.max(*value));
+    let rect = GraphRect {
+        left: OVERLAY_PADDING,
+        top,
+        width: graph_width,
+        height: PERF_GRAPH_HEIGHT_PX,
+        scale_ms,
+    };
+    draw_filled_rect(
+        frame,
+        width,
+        height,
+        rect.left - OVERLAY_PANEL_INSET_X,
+        rect.top - OVERLAY_PANEL_INSET_Y,
+        rect.width + OVERLAY_PANEL_INSET_X * 2,
+        rect.height + OVERLAY_PANEL_INSET_Y * 2,
+        OVERLAY_PANEL_BG_COLOR,
+    );
+    draw_rect_outline(
+        frame,
+        width,
+        height,
+        rect.left - OVERLAY_PANEL_INSET_X,
+        rect.top - OVERLAY_PANEL_INSET_Y,
+        rect.width + OVERLAY_PANEL_INSET_X * 2,
+        rect.height + OVERLAY_PANEL_INSET_Y * 2,
+        OVERLAY_PANEL_BORDER_COLOR,
+    );
+    for (budget, color) in [
+        (data.sim_budget_ms, PERF_GRAPH_SIM_BUDGET_COLOR),
+        (data.render_budget_ms, PERF_GRAPH_REN_BUDGET_COLOR),
+    ] {
+        if let Some(budget) = budget {
+            let y = rect.y_for(budget);
+            for x in (rect.left..rect.left + rect.width).step_by(4) {
+                draw_filled_rect(frame, width, height, x, y, 2, 1, color);
+            }
+        }
     }
+    draw_sparkline(
+        frame,
+        width,
+        height,
+        &rect,
+        &data.render_ms,
+        PERF_GRAPH_REN_COLOR,
+    );
+    draw_sparkline(
+        frame,
+        width,
+        height,
+        &rect,
+        &data.sim_ms,
+        PERF_GRAPH_SIM_COLOR,
+    );
 }
 
-fn draw_glyph_clipped(
+/// Right-aligns `samples` in `rect` and joins neighbours with vertical runs.
+fn draw_sparkline(
     frame: &mut [u8],
     width: u32,
     height: u32,
-    x: i32,
-    y: i32,
-    glyph: Glyph,
+    rect: &GraphRect,
+    samples: &[f32],
     color: [u8; 4],
 ) {
-    if width == 0 || height == 0 {
-        return;
-    }
-
-    let height_i32 = height as i32;
-    let width_i32 = width as i32;
-
-    for (row_index, row_bits) in glyph.rows.iter().enumerate() {
-        let glyph_y = y + row_index as i32 * TEXT_SCALE;
-
-        for col in 0..GLYPH_WIDTH {
-            if (row_bits & (1 << (GLYPH_WIDTH - 1 - col))) == 0 {
-                continue;
-            }
-
-            let glyph_x = x + col * TEXT_SCALE;
-            for sy in 0..TEXT_SCALE {
-                let pixel_y = glyph_y + sy;
-                if pixel_y < 0 || pixel_y >= height_i32 {
-                    continue;
-                }
-                for sx in 0..TEXT_SCALE {
-                    let pixel_x = glyph_x + sx;
-                    if pixel_x < 0 || pixel_x >= width_i32 {
-                        continue;
-                    }
-                    write_pixel_rgba(
-                        frame,
-                        width as usize,
-                        pixel_x as usize,
-                        pixel_y as usize,
-                        color,
-                    );
-                }
-            }
-        }
+    let shown = &samples[samples.len().saturating_sub(PERF_GRAPH_SAMPLES)..];
+    let first_x = rect.left + rect.width - shown.len() as i32 * PERF_GRAPH_SAMPLE_WIDTH_PX;
+    let mut previous_y = None;
+    for (index, value) in shown.iter().enumerate() {
+        let x = first_x + index as i32 * PERF_GRAPH_SAMPLE_WIDTH_PX;
+        let y = rect.y_for(*value);
+        let (run_top, run_bottom) = match previous_y {
+            Some(previous) => (y.min(previous), y.max(previous)),
+            None => (y, y),
+        };
+        draw_filled_rect(
+            frame,
+            width,
+            height,
+            x,
+            run_top,
+            PERF_GRAPH_SAMPLE_WIDTH_PX,
+            run_bottom - run_top + 1,
+            color,
+        );
+        previous_y = Some(y);
     }
 }
 
@@ -349,412 +463,18 @@ fn draw_rect_outline(
     );
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Glyph {
-    rows: [u8; GLYPH_HEIGHT as usize],
-}
-
-const SPACE_GLYPH: Glyph = Glyph {
-    rows: [0, 0, 0, 0, 0],
-};
-
-fn glyph_for(ch: char) -> Option<Glyph> {
-    match ch {
-        ' '..='~' => Some(ascii_glyph(ch)),
-        _ => None,
-    }
-}
-
-fn ascii_glyph(ch: char) -> Glyph {
-    match ch {
-        ' ' => SPACE_GLYPH,
-        '!' => Glyph {
-            rows: [0b010, 0b010, 0b010, 0b000, 0b010],
-        },
-        '"' => Glyph {
-            rows: [0b101, 0b101, 0b000, 0b000, 0b000],
-        },
-        '#' => Glyph {
-            rows: [0b101, 0b111, 0b101, 0b111, 0b101],
-        },
-        '$' => Glyph {
-            rows: [0b111, 0b110, 0b111, 0b011, 0b111],
-        },
-        '%' => Glyph {
-            rows: [0b101, 0b001, 0b010, 0b100, 0b101],
-        },
-        '&' => Glyph {
-            rows: [0b010, 0b101, 0b010, 0b101, 0b011],
-        },
-        '\'' => Glyph {
-            rows: [0b010, 0b010, 0b000, 0b000, 0b000],
-        },
-        '(' => Glyph {
-            rows: [0b001, 0b010, 0b010, 0b010, 0b001],
-        },
-        ')' => Glyph {
-            rows: [0b100, 0b010, 0b010, 0b010, 0b100],
-        },
-        '*' => Glyph {
-            rows: [0b000, 0b101, 0b010, 0b101, 0b000],
-        },
-        '+' => Glyph {
-            rows: [0b000, 0b010, 0b111, 0b010, 0b000],
-        },
-        ',' => Glyph {
-            rows: [0b000, 0b000, 0b000, 0b010, 0b100],
-        },
-        '-' => Glyph {
-            rows: [0b000, 0b000, 0b111, 0b000, 0b000],
-        },
-        '.' => Glyph {
-            rows: [0b000, 0b000, 0b000, 0b000, 0b010],
-        },
-        '/' => Glyph {
-            rows: [0b001, 0b001, 0b010, 0b100, 0b100],
-        },
-        '0' => Glyph {
-            rows: [0b111, 0b101, 0b101, 0b101, 0b111],
-        },
-        '1' => Glyph {
-            rows: [0b010, 0b110, 0b010, 0b010, 0b111],
-        },
-        '2' => Glyph {
-            rows: [0b111, 0b001, 0b111, 0b100, 0b111],
-        },
-        '3' => Glyph {
-            rows: [0b111, 0b001, 0b111, 0b001, 0b111],
-        },
-        '4' => Glyph {
-            rows: [0b101, 0b101, 0b111, 0b001, 0b001],
-        },
-        '5' => Glyph {
-            rows: [0b111, 0b100, 0b111, 0b001, 0b111],
-        },
-        '6' => Glyph {
-            rows: [0b111, 0b100, 0b111, 0b101, 0b111],
-        },
-        '7' => Glyph {
-            rows: [0b111, 0b001, 0b010, 0b010, 0b010],
-        },
-        '8' => Glyph {
-            rows: [0b111, 0b101, 0b111, 0b101, 0b111],
-        },
-        '9' => Glyph {
-            rows: [0b111, 0b101, 0b111, 0b001, 0b111],
-        },
-        ':' => Glyph {
-            rows: [0b000, 0b010, 0b000, 0b010, 0b000],
-        },
-        ';' => Glyph {
-            rows: [0b000, 0b010, 0b000, 0b010, 0b100],
-        },
-        '<' => Glyph {
-            rows: [0b001, 0b010, 0b100, 0b010, 0b001],
-        },
-        '=' => Glyph {
-            rows: [0b000, 0b111, 0b000, 0b111, 0b000],
-        },
-        '>' => Glyph {
-            rows: [0b100, 0b010, 0b001, 0b010, 0b100],
-        },
-        '?' => Glyph {
-            rows: [0b111, 0b001, 0b011, 0b000, 0b010],
-        },
-        '@' => Glyph {
-            rows: [0b111, 0b101, 0b111, 0b100, 0b111],
-        },
-        'A' => Glyph {
-            rows: [0b010, 0b101, 0b111, 0b101, 0b101],
-        },
-        'B' => Glyph {
-            rows: [0b110, 0b101, 0b110, 0b101, 0b110],
-        },
-        'C' => Glyph {
-            rows: [0b111, 0b100, 0b100, 0b100, 0b111],
-        },
-        'D' => Glyph {
-            rows: [0b110, 0b101, 0b101, 0b101, 0b110],
-        },
-        'E' => Glyph {
-            rows: [0b111, 0b100, 0b110, 0b100, 0b111],
-        },
-        'F' => Glyph {
-            rows: [0b111, 0b100, 0b110, 0b100, 0b100],
-        },
-        'G' => Glyph {
-            rows: [0b111, 0b100, 0b101, 0b101, 0b111],
-        },
-        'H' => Glyph {
-            rows: [0b101, 0b101, 0b111, 0b101, 0b101],
-        },
-        'I' => Glyph {
-            rows: [0b111, 0b010, 0b010, 0b010, 0b111],
-        },
-        'J' => Glyph {
-            rows: [0b111, 0b001, 0b001, 0b101, 0b111],
-        },
-        'K' => Glyph {
-            rows: [0b101, 0b101, 0b110, 0b101, 0b101],
-        },
-        'L' => Glyph {
-            rows: [0b100, 0b100, 0b100, 0b100, 0b111],
-        },
-        'M' => Glyph {
-            rows: [0b101, 0b111, 0b111, 0b101, 0b101],
-        },
-        'N' => Glyph {
-            rows: [0b101, 0b111, 0b111, 0b111, 0b101],
-        },
-        'O' => Glyph {
-            rows: [0b111, 0b101, 0b101, 0b101, 0b111],
-        },
-        'P' => Glyph {
-            rows: [0b110, 0b101, 0b110, 0b100, 0b100],
-        },
-        'Q' => Glyph {
-            rows: [0b111, 0b101, 0b101, 0b111, 0b001],
-        },
-        'R' => Glyph {
-            rows: [0b110, 0b101, 0b110, 0b101, 0b101],
-        },
-        'S' => Glyph {
-            rows: [0b111, 0b100, 0b111, 0b001, 0b111],
-        },
-        'T' => Glyph {
-            rows: [0b111, 0b010, 0b010, 0b010, 0b010],
-        },
-        'U' => Glyph {
-            rows: [0b101, 0b101, 0b101, 0b101, 0b111],
-        },
-        'V' => Glyph {
-            rows: [0b101, 0b101, 0b101, 0b101, 0b010],
-        },
-        'W' => Glyph {
-            rows: [0b101, 0b101, 0b111, 0b111, 0b101],
-        },
-        'X' => Glyph {
-            rows: [0b101, 0b101, 0b010, 0b101, 0b101],
-        },
-        'Y' => Glyph {
-            rows: [0b101, 0b101, 0b010, 0b010, 0b010],
-        },
-        'Z' => Glyph {
-            rows: [0b111, 0b001, 0b010, 0b100, 0b111],
-        },
-        '[' => Glyph {
-            rows: [0b110, 0b100, 0b100, 0b100, 0b110],
-        },
-        '\\' => Glyph {
-            rows: [0b100, 0b100, 0b010, 0b001, 0b001],
-        },
-        ']' => Glyph {
-            rows: [0b011, 0b001, 0b001, 0b001, 0b011],
-        },
-        '^' => Glyph {
-            rows: [0b010, 0b101, 0b000, 0b000, 0b000],
-        },
-        '_' => Glyph {
-            rows: [0b000, 0b000, 0b000, 0b000, 0b111],
-        },
-        '`' => Glyph {
-            rows: [0b100, 0b010, 0b000, 0b000, 0b000],
-        },
-        'a' => Glyph {
-            rows: [0b000, 0b111, 0b001, 0b111, 0b111],
-        },
-        'b' => Glyph {
-            rows: [0b100, 0b100, 0b110, 0b101, 0b110],
-        },
-        'c' => Glyph {
-            rows: [0b000, 0b111, 0b100, 0b100, 0b111],
-        },
-        'd' => Glyph {
-            rows: [0b001, 0b001, 0b111, 0b101, 0b111],
-        },
-        'e' => Glyph {
-            rows: [0b000, 0b111, 0b110, 0b100, 0b111],
-        },
-        'f' => Glyph {
-            rows: [0b011, 0b100, 0b110, 0b100, 0b100],
-        },
-        'g' => Glyph {
-            rows: [0b000, 0b111, 0b101, 0b111, 0b001],
-        },
-        'h' => Glyph {
-            rows: [0b100, 0b100, 0b110, 0b101, 0b101],
-        },
-        'i' => Glyph {
-            rows: [0b010, 0b000, 0b010, 0b010, 0b010],
-        },
-        'j' => Glyph {
-            rows: [0b001, 0b000, 0b001, 0b101, 0b010],
-        },
-        'k' => Glyph {
-            rows: [0b100, 0b101, 0b110, 0b101, 0b101],
-        },
-        'l' => Glyph {
-            rows: [0b100, 0b100, 0b100, 0b100, 0b111],
-        },
-        'm' => Glyph {
-            rows: [0b000, 0b110, 0b111, 0b101, 0b101],
-        },
-        'n' => Glyph {
-            rows: [0b000, 0b110, 0b101, 0b101, 0b101],
-        },
-        'o' => Glyph {
-            rows: [0b000, 0b111, 0b101, 0b101, 0b111],
-        },
-        'p' => Glyph {
-            rows: [0b000, 0b110, 0b101, 0b110, 0b100],
-        },
-        'q' => Glyph {
-            rows: [0b000, 0b111, 0b101, 0b111, 0b001],
-        },
-        'r' => Glyph {
-            rows: [0b000, 0b110, 0b101, 0b100, 0b100],
-        },
-        's' => Glyph {
-            rows: [0b000, 0b111, 0b110, 0b001, 0b111],
-        },
-        't' => Glyph {
-            rows: [0b010, 0b111, 0b010, 0b010, 0b011],
-        },
-        'u' => Glyph {
-            rows: [0b000, 0b101, 0b101, 0b101, 0b111],
-        },
-        'v' => Glyph {
-            rows: [0b000, 0b101, 0b101, 0b101, 0b010],
-        },
-        'w' => Glyph {
-            rows: [0b000, 0b101, 0b101, 0b111, 0b010],
-        },
-        'x' => Glyph {
-            rows: [0b000, 0b101, 0b010, 0b010, 0b101],
-        },
-        'y' => Glyph {
-            rows: [0b000, 0b101, 0b101, 0b111, 0b001],
-        },
-        'z' => Glyph {
-            rows: [0b000, 0b111, 0b001, 0b010, 0b111],
-        },
-        '{' => Glyph {
-            rows: [0b011, 0b010, 0b110, 0b010, 0b011],
-        },
-        '|' => Glyph {
-            rows: [0b010, 0b010, 0b010, 0b010, 0b010],
-        },
-        '}' => Glyph {
-            rows: [0b110, 0b010, 0b011, 0b010, 0b110],
-        },
-        '~' => Glyph {
-            rows: [0b000, 0b011, 0b110, 0b000, 0b000],
-        },
-        _ => SPACE_GLYPH,
-    }
-}
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn glyph_lookup_covers_ascii_printable_range() {
-        for code in 32u8..=126u8 {
-            let ch = char::from(code);
-            assert!(
-                glyph_for(ch).is_some(),
-                "missing glyph for ASCII code {code} ('{ch}')"
-            );
-        }
-    }
-
-    #[test]
-    fn non_ascii_printable_glyphs_use_fallback_path() {
-        assert!(glyph_for('\u{7f}').is_none());
-        assert!(glyph_for('é').is_none());
-    }
-
-    #[test]
-    fn console_acceptance_ascii_string_has_no_fallbacks() {
-        let acceptance = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 !\\\"#$%&'()*+,-./:;<=>?@[\\\\]^_`{|}~";
-        for ch in acceptance.chars() {
-            assert!(
-                glyph_for(ch).is_some(),
-                "acceptance character should have glyph: '{ch}'"
-            );
-        }
-    }
-
-    #[test]
-    fn unknown_character_is_safe_and_draws_like_space() {
-        let mut frame = vec![0u8; 16 * 16 * 4];
-        draw_text_clipped(
-            &mut frame,
-            16,
-            16,
-            0,
-            0,
-            "\u{1f642}",
-            OVERLAY_TEXT_PRIMARY_COLOR,
-        );
-        assert!(frame.iter().all(|byte| *byte == 0));
-    }
-
-    #[test]
-    fn clipped_glyph_draw_with_negative_origin_is_safe() {
-        let mut frame = vec![0u8; 8 * 8 * 4];
-        draw_text_clipped(&mut frame, 8, 8, -2, -2, "FPS", OVERLAY_TEXT_PRIMARY_COLOR);
-        assert_eq!(frame.len(), 8 * 8 * 4);
-    }
-
-    #[test]
-    fn clipped_glyph_draw_beyond_bounds_is_safe() {
-        let mut frame = vec![0u8; 8 * 8 * 4];
-        draw_text_clipped(&mut frame, 8, 8, 64, 64, "TPS", OVERLAY_TEXT_PRIMARY_COLOR);
-        assert!(frame.iter().all(|byte| *byte == 0));
-    }
-
-    #[test]
-    fn tiny_viewports_never_panic_or_write_oob() {
-        let mut frame_1x1 = vec![0u8; 4];
-        draw_text_clipped(
-            &mut frame_1x1,
-            1,
-            1,
-            -10,
-            -10,
-            "Frame",
-            OVERLAY_TEXT_PRIMARY_COLOR,
-        );
-
-        let mut frame_0x8 = vec![];
-        draw_text_clipped(
-            &mut frame_0x8,
-            0,
-            8,
-            0,
-            0,
-            "Entities",
-            OVERLAY_TEXT_PRIMARY_COLOR,
-        );
-
-        let mut frame_8x0 = vec![];
-        draw_text_clipped(
-            &mut frame_8x0,
-            8,
-            0,
-            0,
-            0,
-            "Content",
-            OVERLAY_TEXT_PRIMARY_COLOR,
-        );
-    }
-
     #[test]
     fn layout_metrics_follow_text_scale() {
+        let font = BitmapFont::builtin();
+        let style = overlay_text_style(&font, OVERLAY_TEXT_PRIMARY_COLOR);
         assert_eq!(TEXT_SCALE, 3);
-        assert_eq!(GLYPH_ADVANCE, 12);
-        assert_eq!(LINE_ADVANCE, 21);
+        assert_eq!(style.scale, 3);
+        assert_eq!(font.measure("FPS", &style).0, 33);
+        assert_eq!(font.line_height() * style.scale, 21);
         assert_eq!(OVERLAY_PADDING, 18);
     }
 
@@ -765,6 +485,8 @@ mod tests {
             perf: PerfStatsSnapshot::default(),
             render_fps_cap: Some(240),
             slow_frame_delay_ms: 0,
+            sim_speed: 1,
+            sim_speed_capped: false,
             entity_count: 3,
             content_status: "loaded",
             selected_entity: Some(EntityId(1)),
@@ -789,6 +511,8 @@ mod tests {
                     "evk: is:0 ic:0 dm:0 dd:0 sa:1 se:0".to_string(),
                 ]),
             }),
+            zones: Vec::new(),
+            perf_graph: None,
         };
         let lines = build_overlay_lines(&data);
         assert_eq!(lines.len(), 24);
@@ -801,7 +525,7 @@ mod tests {
         assert_eq!(lines[22], "ev: 1");
         assert_eq!(lines[23], "evk: is:0 ic:0 dm:0 dd:0 sa:1 se:0");
         assert_eq!(
-            OVERLAY_PADDING + (lines.len() as i32 - 1) * LINE_ADVANCE,
+            OVERLAY_PADDING + (lines.len() as i32 - 1) * UI_LINE_HEIGHT_PX,
             501
         );
     }
@@ -813,6 +537,8 @@ mod tests {
             perf: PerfStatsSnapshot::default(),
             render_fps_cap: Some(240),
             slow_frame_delay_ms: 0,
+            sim_speed: 1,
+            sim_speed_capped: false,
             entity_count: 1,
             content_status: "loaded",
             selected_entity: Some(EntityId(1)),
@@ -831,6 +557,8 @@ mod tests {
                 system_order: "test".to_string(),
                 extra_debug_lines: None,
             }),
+            zones: Vec::new(),
+            perf_graph: None,
         };
         let lines = build_overlay_lines(&data);
         assert!(!lines.iter().any(|line| line.starts_with("role: ")));
@@ -843,15 +571,19 @@ mod tests {
             perf: PerfStatsSnapshot::default(),
             render_fps_cap: Some(60),
             slow_frame_delay_ms: 0,
+            sim_speed: 1,
+            sim_speed_capped: false,
             entity_count: 1,
             content_status: "loaded",
             selected_entity: None,
             selected_target: None,
             resource_count: Some(0),
             debug_info: None,
+            zones: Vec::new(),
+            perf_graph: None,
         };
         let mut frame = vec![0u8; 320 * 180 * 4];
-        draw_overlay(&mut frame, 320, 180, &data);
+        draw_overlay(&mut frame, 320, 180, &BitmapFont::builtin(), &data);
 
         let has_backing_pixel = frame.chunks_exact(4).any(|px| {
             px[0] == OVERLAY_PANEL_BG_COLOR[0]
@@ -862,6 +594,94 @@ mod tests {
         assert!(has_backing_pixel);
     }
 
+    #[test]
+    fn zones_section_is_appended_only_when_zones_were_recorded() {
+        let mut data = OverlayData {
+            metrics: LoopMetricsSnapshot::default(),
+            perf: PerfStatsSnapshot::default(),
+            render_fps_cap: None,
+            slow_frame_delay_ms: 0,
+            sim_speed: 1,
+            sim_speed_capped: false,
+            entity_count: 0,
+            content_status: "loaded",
+            selected_entity: None,
+            selected_target: None,
+            resource_count: None,
+            debug_info: None,
+            zones: Vec::new(),
+            perf_graph: None,
+        };
+        let base_len = build_overlay_lines(&data).len();
+        data.zones = vec![ZoneStat {
+            name: "AI",
+            ms: super::super::RollingMsStats {
+                last_ms: 0.1,
+                avg_ms: 0.12,
+                max_ms: 0.4,
+            },
+        }];
+        let lines = build_overlay_lines(&data);
+        assert_eq!(lines.len(), base_len + 3);
+        assert_eq!(lines[base_len + 1], ZONES_SECTION_LABEL);
+        assert_eq!(lines[base_len + 2], "AI a/m: 0.12/0.40 ms");
+    }
+
+    #[test]
+    fn perf_graph_draws_samples_and_budget_lines_bottom_left() {
+        let (width, height) = (320u32, 180u32);
+        let pixel = |frame: &[u8], x: i32, y: i32| {
+            let offset = (y as usize * width as usize + x as usize) * 4;
+            [
+                frame[offset],
+                frame[offset + 1],
+                frame[offset + 2],
+                frame[offset + 3],
+            ]
+        };
+        let data = PerfGraphData {
+            sim_ms: vec![2.0; 10],
+            render_ms: vec![8.0],
+            sim_budget_ms: Some(4.0),
+            render_budget_ms: None,
+        };
+        let mut frame = vec![0u8; width as usize * height as usize * 4];
+        draw_perf_graph(&mut frame, width, height, &data);
+
+        let rect = GraphRect {
+            left: OVERLAY_PADDING,
+            top: height as i32 - OVERLAY_PADDING - PERF_GRAPH_HEIGHT_PX,
+            width: PERF_GRAPH_SAMPLES as i32 * PERF_GRAPH_SAMPLE_WIDTH_PX,
+            height: PERF_GRAPH_HEIGHT_PX,
+            scale_ms: 8.0,
+        };
+        let right = rect.left + rect.width - 1;
+        assert_eq!(pixel(&frame, right, rect.y_for(2.0)), PERF_GRAPH_SIM_COLOR);
+        assert_eq!(pixel(&frame, right, rect.top), PERF_GRAPH_REN_COLOR);
+        assert_eq!(
+            pixel(&frame, rect.left, rect.y_for(4.0)),
+            PERF_GRAPH_SIM_BUDGET_COLOR
+        );
+        assert_eq!(
+            pixel(&frame, rect.left, rect.y_for(2.0)),
+            OVERLAY_PANEL_BG_COLOR
+        );
+
+        let mut small = vec![0u8; 200 * 100 * 4];
+        draw_perf_graph(&mut small, 200, 100, &data);
+        assert!(small.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn tps_line_shows_speed_only_when_fast_forwarding() {
+        assert_eq!(format_tps_line(60.0, 1, false), "TPS: 60.0");
+        assert_eq!(format_tps_line(299.5, 5, false), "TPS: 299.5 x5");
+        assert_eq!(
+            format_tps_line(300.0, 10, true),
+            "TPS: 300.0 x10 (tick cap)"
+        );
+    }
+
     #[test]
     fn fps_line_formats_cap_on_and_debug_delay() {
         let line = format_fps_line(144.4, Some(240), 200);
@@ -49,7 +49,7 @@ impl PerfStats {
 }
 
 #[derive(Debug)]
-struct RollingWindowMs {
+pub(crate) struct RollingWindowMs {
     samples_ms: [f32; PERF_WINDOW_LEN],
     head: usize,
     count: usize,
@@ -70,7 +70,7 @@ impl Default for RollingWindowMs {
 }
 
 impl RollingWindowMs {
-    fn push_ms(&mut self, value_ms: f32) {
+    pub(crate) fn push_ms(&mut self, value_ms: f32) {
         self.last_ms = value_ms;
 
         if self.count < PERF_WINDOW_LEN {
@@ -87,7 +87,7 @@ impl RollingWindowMs {
         self.sum_ms += value_ms - evicted;
     }
 
-    fn snapshot(&self) -> RollingMsStats {
+    pub(crate) fn snapshot(&self) -> RollingMsStats {
         if self.count == 0 {
             return RollingMsStats::default();
         }
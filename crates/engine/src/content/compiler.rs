@@ -5,7 +5,10 @@ use std::path::{Path, PathBuf};
 
 use roxmltree::{Document, Node};
 
-use crate::app::{RenderableKind, SpriteAnchorName, SpriteAnchorPx, SpriteAnchors};
+use crate::app::{
+    PaletteSwapEntry, ParticleEmitterDef, ParticleRange, PointLightDef, RenderLayer,
+    RenderableKind, SpriteAnchorName, SpriteAnchorPx, SpriteAnchors,
+};
 use crate::sprite_keys::validate_sprite_key;
 use crate::AppPaths;
 
@@ -88,6 +91,12 @@ pub struct CompiledEntityDef {
     pub attack_range: Option<f32>,
     pub attack_cooldown_seconds: Option<f32>,
     pub tags: Option<Vec<String>>,
+    pub render_layer: Option<RenderLayer>,
+    pub sort_pivot_y: Option<f32>,
+    pub palette_swap: Option<Vec<PaletteSwapEntry>>,
+    pub particle_emitters: Option<Vec<ParticleEmitterDef>>,
+    pub light: Option<PointLightDef>,
+    pub sight_radius: Option<f32>,
     pub source_mod_id: String,
     pub source_file_path: PathBuf,
     pub source_location: Option<SourceLocation>,
@@ -104,6 +113,12 @@ struct MergedEntityDef {
     attack_range: Option<f32>,
     attack_cooldown_seconds: Option<f32>,
     tags: Option<Vec<String>>,
+    render_layer: Option<RenderLayer>,
+    sort_pivot_y: Option<f32>,
+    palette_swap: Option<Vec<PaletteSwapEntry>>,
+    particle_emitters: Option<Vec<ParticleEmitterDef>>,
+    light: Option<PointLightDef>,
+    sight_radius: Option<f32>,
 }
 
 pub fn compile_mod_entity_defs(
@@ -209,6 +224,24 @@ fn apply_patch(target: &mut MergedEntityDef, patch: &CompiledEntityDef) {
     if let Some(tags) = &patch.tags {
         target.tags = Some(tags.clone());
     }
+    if let Some(render_layer) = patch.render_layer {
+        target.render_layer = Some(render_layer);
+    }
+    if let Some(sort_pivot_y) = patch.sort_pivot_y {
+        target.sort_pivot_y = Some(sort_pivot_y);
+    }
+    if let Some(palette_swap) = &patch.palette_swap {
+        target.palette_swap = Some(palette_swap.clone());
+    }
+    if let Some(particle_emitters) = &patch.particle_emitters {
+        target.particle_emitters = Some(particle_emitters.clone());
+    }
+    if let Some(light) = patch.light {
+        target.light = Some(light);
+    }
+    if let Some(sight_radius) = patch.sight_radius {
+        target.sight_radius = Some(sight_radius);
+    }
 }
 
 fn materialize_database(merged: BTreeMap<String, MergedEntityDef>) -> DefDatabase {
@@ -230,6 +263,12 @@ fn materialize_database(merged: BTreeMap<String, MergedEntityDef>) -> DefDatabas
             attack_range: merged.attack_range,
             attack_cooldown_seconds: merged.attack_cooldown_seconds,
             tags: merged.tags.unwrap_or_default(),
+            render_layer: merged.render_layer.unwrap_or_default(),
+            sort_pivot_y: merged.sort_pivot_y.unwrap_or(0.0),
+            palette_swap: merged.palette_swap.unwrap_or_default(),
+            particle_emitters: merged.particle_emitters.unwrap_or_default(),
+            light: merged.light,
+            sight_radius: merged.sight_radius,
         })
         .collect::<Vec<_>>();
     DefDatabase::from_entity_defs(defs)
@@ -319,6 +358,12 @@ fn parse_entity_def(
     let mut attack_range = None::<f32>;
     let mut attack_cooldown_seconds = None::<f32>;
     let mut tags = None::<Vec<String>>;
+    let mut render_layer = None::<RenderLayer>;
+    let mut sort_pivot_y = None::<f32>;
+    let mut palette_swap = None::<Vec<PaletteSwapEntry>>;
+    let mut particle_emitters = None::<Vec<ParticleEmitterDef>>;
+    let mut light = None::<PointLightDef>;
+    let mut sight_radius = None::<f32>;
 
     for field in node.children().filter(|child| child.is_element()) {
         let field_name = field.tag_name().name().to_string();
@@ -428,6 +473,74 @@ fn parse_entity_def(
                 attack_cooldown_seconds = Some(parsed);
             }
             "tags" => tags = Some(parse_tags(mod_id, file_path, doc, field)?),
+            "render_layer" => {
+                let value = required_text(mod_id, file_path, doc, field, "render_layer")?;
+                let parsed = RenderLayer::parse(&value).ok_or_else(|| {
+                    error_at_node_with_context(
+                        ContentErrorCode::InvalidValue,
+                        format!(
+                            "invalid render_layer '{}'; allowed values: ground_decal, floor_item, standing, roof, overlay",
+                            value
+                        ),
+                        mod_id,
+                        file_path,
+                        doc,
+                        field,
+                        def_name_hint.as_deref(),
+                        Some("render_layer"),
+                    )
+                })?;
+                render_layer = Some(parsed);
+            }
+            "sort_pivot_y" => {
+                let parsed = parse_finite_f32_field(
+                    mod_id,
+                    file_path,
+                    doc,
+                    field,
+                    def_name_hint.as_deref(),
+                    "sort_pivot_y",
+                )?;
+                sort_pivot_y = Some(parsed);
+            }
+            "palette_swap" => {
+                palette_swap = Some(parse_palette_swap(
+                    mod_id,
+                    file_path,
+                    doc,
+                    field,
+                    def_name_hint.as_deref(),
+                )?)
+            }
+            "particles" => {
+                particle_emitters = Some(parse_particle_emitters(
+                    mod_id,
+                    file_path,
+                    doc,
+                    field,
+                    def_name_hint.as_deref(),
+                )?)
+            }
+            "light" => {
+                light = Some(parse_light(
+                    mod_id,
+                    file_path,
+                    doc,
+                    field,
+                    def_name_hint.as_deref(),
+                )?)
+            }
+            "sight_radius" => {
+                let parsed = parse_non_negative_f32_field(
+                    mod_id,
+                    file_path,
+                    doc,
+                    field,
+                    def_name_hint.as_deref(),
+                    "sight_radius",
+                )?;
+                sight_radius = Some(parsed);
+            }
             _ => {
                 return Err(error_at_node_with_context(
                     ContentErrorCode::UnknownField,
@@ -465,6 +578,12 @@ fn parse_entity_def(
         attack_range,
         attack_cooldown_seconds,
         tags,
+        render_layer,
+        sort_pivot_y,
+        palette_swap,
+        particle_emitters,
+        light,
+        sight_radius,
         source_mod_id: mod_id.to_string(),
         source_file_path: file_path.to_path_buf(),
         source_location: Some(SourceLocation {
@@ -513,6 +632,364 @@ fn parse_tags(
     Ok(tags)
 }
 
+fn parse_palette_swap(
+    mod_id: &str,
+    file_path: &Path,
+    doc: &Document<'_>,
+    node: Node<'_, '_>,
+    def_name: Option<&str>,
+) -> Result<Vec<PaletteSwapEntry>, ContentCompileError> {
+    let invalid = |message: String, at: Node<'_, '_>| {
+        error_at_node_with_context(
+            ContentErrorCode::InvalidValue,
+            message,
+            mod_id,
+            file_path,
+            doc,
+            at,
+            def_name,
+            Some("palette_swap"),
+        )
+    };
+    let mut entries = Vec::<PaletteSwapEntry>::new();
+    for child in node.children().filter(|child| child.is_element()) {
+        if child.tag_name().name() != "swap" {
+            return Err(error_at_node_with_context(
+                ContentErrorCode::UnknownField,
+                format!(
+                    "unknown field <{}> inside <palette_swap>; expected <swap from=\"#rrggbb\" to=\"#rrggbb\"/>",
+                    child.tag_name().name()
+                ),
+                mod_id,
+                file_path,
+                doc,
+                child,
+                def_name,
+                Some("palette_swap"),
+            ));
+        }
+        let mut colors = [[0u8; 3]; 2];
+        for (slot, attr) in colors.iter_mut().zip(["from", "to"]) {
+            let value = child
+                .attribute(attr)
+                .ok_or_else(|| invalid(format!("<swap> requires '{attr}'"), child))?;
+            *slot = parse_hex_rgb(value).ok_or_else(|| {
+                invalid(
+                    format!("invalid {attr} color '{value}'; expected #rrggbb"),
+                    child,
+                )
+            })?;
+        }
+        let [from_rgb, to_rgb] = colors;
+        if entries.iter().any(|entry| entry.from_rgb == from_rgb) {
+            return Err(invalid(
+                format!(
+                    "duplicate palette_swap source color '{}'",
+                    child.attribute("from").unwrap_or_default()
+                ),
+                child,
+            ));
+        }
+        entries.push(PaletteSwapEntry { from_rgb, to_rgb });
+    }
+    Ok(entries)
+}
+
+const PARTICLE_EMITTER_ATTRIBUTES: [&str; 11] = [
+    "on",
+    "burst",
+    "rate",
+    "duration",
+    "lifetime",
+    "speed",
+    "direction",
+    "gravity",
+    "size",
+    "colors",
+    "sprite",
+];
+const PARTICLE_MAX_COLOR_STOPS: usize = 8;
+
+fn parse_particle_emitters(
+    mod_id: &str,
+    file_path: &Path,
+    doc: &Document<'_>,
+    node: Node<'_, '_>,
+    def_name: Option<&str>,
+) -> Result<Vec<ParticleEmitterDef>, ContentCompileError> {
+    let invalid = |message: String, at: Node<'_, '_>| {
+        error_at_node_with_context(
+            ContentErrorCode::InvalidValue,
+            message,
+            mod_id,
+            file_path,
+            doc,
+            at,
+            def_name,
+            Some("particles"),
+        )
+    };
+    let mut emitters = Vec::<ParticleEmitterDef>::new();
+    for child in node.children().filter(|child| child.is_element()) {
+        if child.tag_name().name() != "emitter" {
+            return Err(error_at_node_with_context(
+                ContentErrorCode::UnknownField,
+                format!(
+                    "unknown field <{}> inside <particles>; expected <emitter on=\"...\"/>",
+                    child.tag_name().name()
+                ),
+                mod_id,
+                file_path,
+                doc,
+                child,
+                def_name,
+                Some("particles"),
+            ));
+        }
+        if let Some(attr) = child
+            .attributes()
+            .find(|attr| !PARTICLE_EMITTER_ATTRIBUTES.contains(&attr.name()))
+        {
+            return Err(invalid(
+                format!("unknown <emitter> attribute '{}'", attr.name()),
+                child,
+            ));
+        }
+        let mut emitter = ParticleEmitterDef::default();
+
+        let on = child.attribute("on").unwrap_or_default();
+        if on.is_empty()
+            || !on
+                .bytes()
+                .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'_')
+        {
+            return Err(invalid(
+                format!("<emitter> requires 'on' as a lowercase trigger name, got '{on}'"),
+                child,
+            ));
+        }
+        emitter.on = on.to_string();
+
+        if let Some(value) = child.attribute("burst") {
+            emitter.burst = value
+                .parse::<u16>()
+                .map_err(|_| invalid(format!("burst '{value}' is not a valid u16"), child))?;
+        }
+        let scalar = |attr: &str, min: f32| -> Result<Option<f32>, ContentCompileError> {
+            let Some(value) = child.attribute(attr) else {
+                return Ok(None);
+            };
+            match value.parse::<f32>() {
+                Ok(parsed) if parsed.is_finite() && parsed >= min => Ok(Some(parsed)),
+                _ => Err(invalid(
+                    format!("{attr} '{value}' must be a finite number >= {min}"),
+                    child,
+                )),
+            }
+        };
+        let range = |attr: &str, min: f32| -> Result<Option<ParticleRange>, ContentCompileError> {
+            let Some(value) = child.attribute(attr) else {
+                return Ok(None);
+            };
+            parse_particle_range(value, min).map(Some).ok_or_else(|| {
+                invalid(
+                    format!("{attr} '{value}' must be 'value' or 'min..max' with finite min <= max >= {min}"),
+                    child,
+                )
+            })
+        };
+        if let Some(rate) = scalar("rate", 0.0)? {
+            emitter.rate_per_second = rate;
+        }
+        if let Some(duration) = scalar("duration", 0.0)? {
+            emitter.duration_seconds = duration;
+        }
+        if let Some(gravity) = scalar("gravity", f32::MIN)? {
+            emitter.gravity = gravity;
+        }
+        if let Some(lifetime) = range("lifetime", 0.0)? {
+            if lifetime.max <= 0.0 {
+                return Err(invalid("lifetime must allow values > 0".to_string(), child));
+            }
+            emitter.lifetime_seconds = lifetime;
+        }
+        if let Some(speed) = range("speed", 0.0)? {
+            emitter.speed = speed;
+        }
+        if let Some(direction) = range("direction", f32::MIN)? {
+            emitter.direction_degrees = direction;
+        }
+        if let Some(value) = child.attribute("size") {
+            emitter.size_px = value
+                .parse::<u8>()
+                .ok()
+                .filter(|size| (1..=32).contains(size))
+                .ok_or_else(|| invalid(format!("size '{value}' must be 1..=32"), child))?;
+        }
+        if let Some(value) = child.attribute("colors") {
+            let colors = value
+                .split_whitespace()
+                .map(parse_hex_rgba)
+                .collect::<Option<Vec<_>>>()
+                .filter(|colors| (1..=PARTICLE_MAX_COLOR_STOPS).contains(&colors.len()))
+                .ok_or_else(|| {
+                    invalid(
+                        format!(
+                            "colors '{value}' must be 1..={PARTICLE_MAX_COLOR_STOPS} space-separated #rrggbb or #rrggbbaa stops"
+                        ),
+                        child,
+                    )
+                })?;
+            emitter.colors = colors;
+        }
+        if let Some(key) = child.attribute("sprite") {
+            validate_sprite_key(key).map_err(|error| {
+                invalid(
+                    format!("invalid particle sprite key '{key}': {error}"),
+                    child,
+                )
+            })?;
+            emitter.sprite_key = Some(key.to_string());
+        }
+        if emitter.burst == 0 && (emitter.rate_per_second <= 0.0 || emitter.duration_seconds <= 0.0)
+        {
+            return Err(invalid(
+                format!(
+                    "emitter '{}' never spawns; set burst > 0 or both rate and duration > 0",
+                    emitter.on
+                ),
+                child,
+            ));
+        }
+        emitters.push(emitter);
+    }
+    Ok(emitters)
+}
+
+const LIGHT_ATTRIBUTES: [&str; 3] = ["radius", "color", "intensity"];
+
+fn parse_light(
+    mod_id: &str,
+    file_path: &Path,
+    doc: &Document<'_>,
+    node: Node<'_, '_>,
+    def_name: Option<&str>,
+) -> Result<PointLightDef, ContentCompileError> {
+    let invalid = |message: String| {
+        error_at_node_with_context(
+            ContentErrorCode::InvalidValue,
+            message,
+            mod_id,
+            file_path,
+            doc,
+            node,
+            def_name,
+            Some("light"),
+        )
+    };
+    if let Some(child) = node.children().find(|child| child.is_element()) {
+        return Err(error_at_node_with_context(
+            ContentErrorCode::UnknownField,
+            format!(
+                "unknown field <{}> inside <light>; expected attributes only",
+                child.tag_name().name()
+            ),
+            mod_id,
+            file_path,
+            doc,
+            child,
+            def_name,
+            Some("light"),
+        ));
+    }
+    if let Some(attr) = node
+        .attributes()
+        .find(|attr| !LIGHT_ATTRIBUTES.contains(&attr.name()))
+    {
+        return Err(invalid(format!(
+            "unknown <light> attribute '{}'",
+            attr.name()
+        )));
+    }
+    let radius_text = node.attribute("radius").unwrap_or_default();
+    let radius = radius_text
+        .parse::<f32>()
+        .ok()
+        .filter(|radius| radius.is_finite() && *radius > 0.0)
+        .ok_or_else(|| {
+            invalid(format!(
+                "<light> requires 'radius' as a finite number > 0, got '{radius_text}'"
+            ))
+        })?;
+    let rgb = match node.attribute("color") {
+        Some(value) => parse_hex_rgb(value)
+            .ok_or_else(|| invalid(format!("light color '{value}' must be #rrggbb")))?,
+        None => [255, 255, 255],
+    };
+    let intensity = match node.attribute("intensity") {
+        Some(value) => value
+            .parse::<f32>()
+            .ok()
+            .filter(|intensity| intensity.is_finite() && *intensity >= 0.0)
+            .ok_or_else(|| {
+                invalid(format!(
+                    "light intensity '{value}' must be a finite number >= 0"
+                ))
+            })?,
+        None => 1.0,
+    };
+    Ok(PointLightDef {
+        radius,
+        rgb,
+        intensity,
+    })
+}
+
+fn parse_particle_range(value: &str, min: f32) -> Option<ParticleRange> {
+    let parse = |text: &str| {
+        text.trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|parsed| parsed.is_finite() && *parsed >= min)
+    };
+    let range = match value.split_once("..") {
+        Some((low, high)) => ParticleRange {
+            min: parse(low)?,
+            max: parse(high)?,
+        },
+        None => ParticleRange::constant(parse(value)?),
+    };
+    (range.min <= range.max).then_some(range)
+}
+
+fn parse_hex_rgba(value: &str) -> Option<[u8; 4]> {
+    let hex = value.strip_prefix('#')?;
+    match hex.len() {
+        6 => {
+            let [r, g, b] = parse_hex_rgb(value)?;
+            Some([r, g, b, 255])
+        }
+        8 => {
+            let [r, g, b] = parse_hex_rgb(&value[..7])?;
+            let alpha = &hex[6..8];
+            if !alpha.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return None;
+            }
+            Some([r, g, b, u8::from_str_radix(alpha, 16).ok()?])
+        }
+        _ => None,
+    }
+}
+
+fn parse_hex_rgb(value: &str) -> Option<[u8; 3]> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
 fn parse_renderable(
     mod_id: &str,
     file_path: &Path,
@@ -522,11 +999,14 @@ fn parse_renderable(
     let child_elements: Vec<Node<'_, '_>> =
         node.children().filter(|child| child.is_element()).collect();
     for attr in node.attributes() {
-        if attr.name() != "kind" && attr.name() != "spriteKey" && attr.name() != "pixelScale" {
+        if !matches!(
+            attr.name(),
+            "kind" | "spriteKey" | "pixelScale" | "mirrorWest"
+        ) {
             return Err(error_at_node(
                 ContentErrorCode::UnknownField,
                 format!(
-                    "unknown attribute '{}' on <renderable>; allowed attributes: kind, spriteKey, pixelScale",
+                    "unknown attribute '{}' on <renderable>; allowed attributes: kind, spriteKey, pixelScale, mirrorWest",
                     attr.name()
                 ),
                 mod_id,
@@ -540,6 +1020,7 @@ fn parse_renderable(
     let kind_attr = node.attribute("kind");
     let sprite_key_attr = node.attribute("spriteKey");
     let pixel_scale_attr = node.attribute("pixelScale");
+    let mirror_west_attr = node.attribute("mirrorWest");
     let text_value = node.text().map(str::trim).unwrap_or_default();
 
     if let Some(kind) = kind_attr {
@@ -559,11 +1040,12 @@ fn parse_renderable(
             "Placeholder" => {
                 if sprite_key_attr.is_some()
                     || pixel_scale_attr.is_some()
+                    || mirror_west_attr.is_some()
                     || !child_elements.is_empty()
                 {
                     Err(error_at_node(
                         ContentErrorCode::InvalidValue,
-                        "renderable kind='Placeholder' must not include spriteKey, pixelScale, or child elements".to_string(),
+                        "renderable kind='Placeholder' must not include spriteKey, pixelScale, mirrorWest, or child elements".to_string(),
                         mod_id,
                         file_path,
                         doc,
@@ -597,10 +1079,13 @@ fn parse_renderable(
                 let pixel_scale =
                     parse_sprite_pixel_scale(mod_id, file_path, doc, node, pixel_scale_attr)?;
                 let anchors = parse_sprite_anchors(mod_id, file_path, doc, &child_elements)?;
+                let mirror_west =
+                    parse_sprite_mirror_west(mod_id, file_path, doc, node, mirror_west_attr)?;
                 Ok(RenderableKind::Sprite {
                     key: key.to_string(),
                     pixel_scale,
                     anchors,
+                    mirror_west,
                 })
             }
             _ => Err(error_at_node(
@@ -617,10 +1102,10 @@ fn parse_renderable(
         };
     }
 
-    if sprite_key_attr.is_some() || pixel_scale_attr.is_some() {
+    if sprite_key_attr.is_some() || pixel_scale_attr.is_some() || mirror_west_attr.is_some() {
         return Err(error_at_node(
             ContentErrorCode::InvalidValue,
-            "renderable attributes spriteKey/pixelScale require kind".to_string(),
+            "renderable attributes spriteKey/pixelScale/mirrorWest require kind".to_string(),
             mod_id,
             file_path,
             doc,
@@ -657,6 +1142,7 @@ fn parse_renderable(
                 key: key.to_string(),
                 pixel_scale: 1,
                 anchors: SpriteAnchors::default(),
+                mirror_west: false,
             })
         }
         _ => Err(error_at_node(
@@ -896,6 +1382,27 @@ fn parse_sprite_pixel_scale(
     Ok(parsed)
 }
 
+fn parse_sprite_mirror_west(
+    mod_id: &str,
+    file_path: &Path,
+    doc: &Document<'_>,
+    node: Node<'_, '_>,
+    value: Option<&str>,
+) -> Result<bool, ContentCompileError> {
+    match value {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(value) => Err(error_at_node(
+            ContentErrorCode::InvalidValue,
+            format!("invalid mirrorWest '{value}': expected true or false"),
+            mod_id,
+            file_path,
+            doc,
+            node,
+        )),
+    }
+}
+
 fn required_text(
     mod_id: &str,
     file_path: &Path,
@@ -990,6 +1497,42 @@ fn parse_non_negative_f32_field(
     Ok(parsed)
 }
 
+fn parse_finite_f32_field(
+    mod_id: &str,
+    file_path: &Path,
+    doc: &Document<'_>,
+    node: Node<'_, '_>,
+    def_name: Option<&str>,
+    field_name: &str,
+) -> Result<f32, ContentCompileError> {
+    let value = required_text(mod_id, file_path, doc, node, field_name)?;
+    let parsed = value.parse::<f32>().map_err(|_| {
+        error_at_node_with_context(
+            ContentErrorCode::InvalidValue,
+            format!("{field_name} '{value}' is not a valid number"),
+            mod_id,
+            file_path,
+            doc,
+            node,
+            def_name,
+            Some(field_name),
+        )
+    })?;
+    if !parsed.is_finite() {
+        return Err(error_at_node_with_context(
+            ContentErrorCode::InvalidValue,
+            format!("{field_name} must be finite"),
+            mod_id,
+            file_path,
+            doc,
+            node,
+            def_name,
+            Some(field_name),
+        ));
+    }
+    Ok(parsed)
+}
+
 fn error_at_node(
     code: ContentErrorCode,
     message: String,
@@ -1259,6 +1802,7 @@ mod tests {
                 key: "player".to_string(),
                 pixel_scale: 1,
                 anchors: SpriteAnchors::default(),
+                mirror_west: false,
             }
         );
     }
@@ -1292,6 +1836,7 @@ mod tests {
                 key: "player".to_string(),
                 pixel_scale: 1,
                 anchors: SpriteAnchors::default(),
+                mirror_west: false,
             }
         );
     }
@@ -1313,6 +1858,7 @@ mod tests {
                 key: "player".to_string(),
                 pixel_scale: 2,
                 anchors: SpriteAnchors::default(),
+                mirror_west: false,
             }
         );
     }
@@ -1334,10 +1880,45 @@ mod tests {
                 key: "player".to_string(),
                 pixel_scale: 1,
                 anchors: SpriteAnchors::default(),
+                mirror_west: false,
             }
         );
     }
 
+    #[test]
+    fn renderable_sprite_mirror_west_parses_and_rejects_non_boolean_or_placeholder() {
+        let temp = TempDir::new().expect("temp");
+        let app = setup_app_paths(temp.path());
+        write_file(
+            &app.base_content_dir.join("defs.xml"),
+            r#"<Defs><EntityDef><defName>a</defName><label>A</label><renderable kind="Sprite" spriteKey="player" mirrorWest="true"/></EntityDef></Defs>"#,
+        );
+        let db = compile_def_database(&app, &ContentPlanRequest::default()).expect("compile");
+        let id = db.entity_def_id_by_name("a").expect("id");
+        let def = db.entity_def(id).expect("def");
+        assert!(matches!(
+            def.renderable,
+            RenderableKind::Sprite {
+                mirror_west: true,
+                ..
+            }
+        ));
+
+        for renderable in [
+            r#"<renderable kind="Sprite" spriteKey="player" mirrorWest="yes"/>"#,
+            r#"<renderable kind="Placeholder" mirrorWest="true"/>"#,
+        ] {
+            write_file(
+                &app.base_content_dir.join("defs.xml"),
+                &format!(
+                    "<Defs><EntityDef><defName>a</defName><label>A</label>{renderable}</EntityDef></Defs>"
+                ),
+            );
+            let err = compile_def_database(&app, &ContentPlanRequest::default()).expect_err("err");
+            assert_eq!(err.code, ContentErrorCode::InvalidValue);
+        }
+    }
+
     #[test]
     fn renderable_sprite_anchors_parse_when_valid() {
         let temp = TempDir::new().expect("temp");
@@ -1359,6 +1940,7 @@ mod tests {
                     carry: Some(SpriteAnchorPx { x_px: 3, y_px: -2 }),
                     ..SpriteAnchors::default()
                 },
+                mirror_west: false,
             }
         );
     }
@@ -1605,7 +2187,7 @@ mod tests {
         let app = setup_app_paths(temp.path());
         write_file(
             &app.base_content_dir.join("defs.xml"),
-            r#"<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><health_max>200</health_max><base_damage>40</base_damage><aggro_radius>10.0</aggro_radius><attack_range>1.5</attack_range><attack_cooldown_seconds>0.25</attack_cooldown_seconds></EntityDef></Defs>"#,
+            r#"<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><health_max>200</health_max><base_damage>40</base_damage><aggro_radius>10.0</aggro_radius><attack_range>1.5</attack_range><attack_cooldown_seconds>0.25</attack_cooldown_seconds><sight_radius>8</sight_radius></EntityDef></Defs>"#,
         );
         let db = compile_def_database(&app, &ContentPlanRequest::default()).expect("compile");
         let id = db.entity_def_id_by_name("a").expect("id");
@@ -1615,6 +2197,7 @@ mod tests {
         assert_eq!(def.aggro_radius, Some(10.0));
         assert_eq!(def.attack_range, Some(1.5));
         assert_eq!(def.attack_cooldown_seconds, Some(0.25));
+        assert_eq!(def.sight_radius, Some(8.0));
     }
 
     #[test]
@@ -1633,6 +2216,200 @@ mod tests {
         assert_eq!(def.aggro_radius, None);
         assert_eq!(def.attack_range, None);
         assert_eq!(def.attack_cooldown_seconds, None);
+        assert_eq!(def.sight_radius, None);
+    }
+
+    #[test]
+    fn render_layer_and_sort_pivot_parse_default_and_reject_invalid_values() {
+        let temp = TempDir::new().expect("temp");
+        let app = setup_app_paths(temp.path());
+        write_file(
+            &app.base_content_dir.join("defs.xml"),
+            r#"<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><render_layer>floor_item</render_layer><sort_pivot_y>-0.25</sort_pivot_y></EntityDef><EntityDef><defName>b</defName><label>B</label><renderable>Placeholder</renderable></EntityDef></Defs>"#,
+        );
+        let db = compile_def_database(&app, &ContentPlanRequest::default()).expect("compile");
+        let a = db
+            .entity_def(db.entity_def_id_by_name("a").expect("a"))
+            .expect("def a");
+        assert_eq!(a.render_layer, RenderLayer::FloorItem);
+        assert_eq!(a.sort_pivot_y, -0.25);
+        let b = db
+            .entity_def(db.entity_def_id_by_name("b").expect("b"))
+            .expect("def b");
+        assert_eq!(b.render_layer, RenderLayer::Standing);
+        assert_eq!(b.sort_pivot_y, 0.0);
+
+        for (field_name, value) in [("render_layer", "attic"), ("sort_pivot_y", "inf")] {
+            write_file(
+                &app.base_content_dir.join("defs.xml"),
+                &format!(
+                    "<Defs><EntityDef><defName>proto.bad</defName><label>Bad</label><renderable>Placeholder</renderable><{}>{}</{}></EntityDef></Defs>",
+                    field_name, value, field_name
+                ),
+            );
+            let err = compile_def_database(&app, &ContentPlanRequest::default()).expect_err("err");
+            assert_eq!(err.code, ContentErrorCode::InvalidValue);
+            assert_eq!(err.def_name.as_deref(), Some("proto.bad"));
+            assert_eq!(err.field_name.as_deref(), Some(field_name));
+        }
+    }
+
+    #[test]
+    fn palette_swap_parses_hex_pairs_and_rejects_bad_colors_or_duplicates() {
+        let temp = TempDir::new().expect("temp");
+        let app = setup_app_paths(temp.path());
+        write_file(
+            &app.base_content_dir.join("defs.xml"),
+            r##"<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><palette_swap><swap from="#3060C0" to="#c04030"/><swap from="#ffffff" to="#000000"/></palette_swap></EntityDef></Defs>"##,
+        );
+        let db = compile_def_database(&app, &ContentPlanRequest::default()).expect("compile");
+        let def = db
+            .entity_def(db.entity_def_id_by_name("a").expect("id"))
+            .expect("def");
+        assert_eq!(
+            def.palette_swap,
+            vec![
+                PaletteSwapEntry {
+                    from_rgb: [0x30, 0x60, 0xc0],
+                    to_rgb: [0xc0, 0x40, 0x30],
+                },
+                PaletteSwapEntry {
+                    from_rgb: [0xff, 0xff, 0xff],
+                    to_rgb: [0, 0, 0],
+                },
+            ]
+        );
+
+        for swaps in [
+            r##"<swap from="#3060c0"/>"##,
+            r##"<swap from="3060c0" to="#000000"/>"##,
+            r##"<swap from="#3060c0" to="#00000g"/>"##,
+            r##"<swap from="#3060c0" to="#000000"/><swap from="#3060c0" to="#ffffff"/>"##,
+        ] {
+            write_file(
+                &app.base_content_dir.join("defs.xml"),
+                &format!(
+                    "<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><palette_swap>{swaps}</palette_swap></EntityDef></Defs>"
+                ),
+            );
+            let err = compile_def_database(&app, &ContentPlanRequest::default()).expect_err("err");
+            assert_eq!(err.code, ContentErrorCode::InvalidValue, "{swaps}");
+            assert_eq!(err.field_name.as_deref(), Some("palette_swap"));
+        }
+    }
+
+    #[test]
+    fn particle_emitters_parse_ranges_and_ramps_and_reject_invalid_attributes() {
+        let temp = TempDir::new().expect("temp");
+        let app = setup_app_paths(temp.path());
+        write_file(
+            &app.base_content_dir.join("defs.xml"),
+            r##"<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><particles><emitter on="damaged" burst="8" lifetime="0.2..0.5" speed="1.5" direction="45..135" gravity="-3" size="3" colors="#ffd070 #ff402000" sprite="fx_spark"/><emitter on="interaction_completed" rate="20" duration="0.5"/></particles></EntityDef></Defs>"##,
+        );
+        let db = compile_def_database(&app, &ContentPlanRequest::default()).expect("compile");
+        let def = db
+            .entity_def(db.entity_def_id_by_name("a").expect("id"))
+            .expect("def");
+        assert_eq!(
+            def.particle_emitters,
+            vec![
+                ParticleEmitterDef {
+                    on: "damaged".to_string(),
+                    burst: 8,
+                    lifetime_seconds: ParticleRange { min: 0.2, max: 0.5 },
+                    speed: ParticleRange::constant(1.5),
+                    direction_degrees: ParticleRange {
+                        min: 45.0,
+                        max: 135.0,
+                    },
+                    gravity: -3.0,
+                    size_px: 3,
+                    colors: vec![[0xff, 0xd0, 0x70, 0xff], [0xff, 0x40, 0x20, 0]],
+                    sprite_key: Some("fx_spark".to_string()),
+                    ..ParticleEmitterDef::default()
+                },
+                ParticleEmitterDef {
+                    on: "interaction_completed".to_string(),
+                    rate_per_second: 20.0,
+                    duration_seconds: 0.5,
+                    ..ParticleEmitterDef::default()
+                },
+            ]
+        );
+
+        for emitter in [
+            r#"<emitter burst="4"/>"#,
+            r#"<emitter on="Damaged" burst="4"/>"#,
+            r#"<emitter on="damaged"/>"#,
+            r#"<emitter on="damaged" rate="10"/>"#,
+            r#"<emitter on="damaged" burst="4" lifetime="0.5..0.2"/>"#,
+            r#"<emitter on="damaged" burst="4" lifetime="0"/>"#,
+            r#"<emitter on="damaged" burst="4" speed="-1"/>"#,
+            r#"<emitter on="damaged" burst="4" size="0"/>"#,
+            r#"<emitter on="damaged" burst="4" colors="red"/>"#,
+            r#"<emitter on="damaged" burst="4" sprite="../x"/>"#,
+            r##"<emitter on="damaged" burst="4" tint="#ffffff"/>"##,
+        ] {
+            write_file(
+                &app.base_content_dir.join("defs.xml"),
+                &format!(
+                    "<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><particles>{emitter}</particles></EntityDef></Defs>"
+                ),
+            );
+            let err = compile_def_database(&app, &ContentPlanRequest::default()).expect_err("err");
+            assert_eq!(err.code, ContentErrorCode::InvalidValue, "{emitter}");
+            assert_eq!(err.field_name.as_deref(), Some("particles"));
+        }
+    }
+
+    #[test]
+    fn light_parses_defaults_and_rejects_invalid_attributes() {
+        let temp = TempDir::new().expect("temp");
+        let app = setup_app_paths(temp.path());
+        write_file(
+            &app.base_content_dir.join("defs.xml"),
+            r##"<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable><light radius="3.5" color="#ffd28c" intensity="1.5"/></EntityDef><EntityDef><defName>b</defName><label>B</label><renderable>Placeholder</renderable><light radius="2"/></EntityDef></Defs>"##,
+        );
+        let db = compile_def_database(&app, &ContentPlanRequest::default()).expect("compile");
+        let light_of = |name: &str| {
+            db.entity_def(db.entity_def_id_by_name(name).expect("id"))
+                .expect("def")
+                .light
+        };
+        assert_eq!(
+            light_of("a"),
+            Some(PointLightDef {
+                radius: 3.5,
+                rgb: [0xff, 0xd2, 0x8c],
+                intensity: 1.5,
+            })
+        );
+        assert_eq!(
+            light_of("b"),
+            Some(PointLightDef {
+                radius: 2.0,
+                rgb: [255, 255, 255],
+                intensity: 1.0,
+            })
+        );
+
+        for light in [
+            r#"<light/>"#,
+            r#"<light radius="0"/>"#,
+            r#"<light radius="2" color="orange"/>"#,
+            r#"<light radius="2" intensity="-1"/>"#,
+            r#"<light radius="2" falloff="1"/>"#,
+        ] {
+            write_file(
+                &app.base_content_dir.join("defs.xml"),
+                &format!(
+                    "<Defs><EntityDef><defName>a</defName><label>A</label><renderable>Placeholder</renderable>{light}</EntityDef></Defs>"
+                ),
+            );
+            let err = compile_def_database(&app, &ContentPlanRequest::default()).expect_err("err");
+            assert_eq!(err.code, ContentErrorCode::InvalidValue, "{light}");
+            assert_eq!(err.field_name.as_deref(), Some("light"));
+        }
     }
 
     #[test]
@@ -1683,6 +2460,7 @@ mod tests {
 
         for (field_name, value) in [
             ("aggro_radius", "-1.0"),
+            ("sight_radius", "-1.0"),
             ("attack_range", "-0.5"),
             ("attack_cooldown_seconds", "-0.25"),
         ] {
@@ -1814,6 +2592,7 @@ mod tests {
                 key: "player".to_string(),
                 pixel_scale: 1,
                 anchors: SpriteAnchors::default(),
+                mirror_west: false,
             }
         );
     }
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::app::RenderableKind;
+use crate::app::{
+    PaletteSwapEntry, ParticleEmitterDef, PointLightDef, RenderLayer, RenderableKind,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EntityDefId(pub u32);
@@ -18,12 +20,24 @@ pub struct EntityArchetype {
     pub attack_range: Option<f32>,
     pub attack_cooldown_seconds: Option<f32>,
     pub tags: Vec<String>,
+    pub render_layer: RenderLayer,
+    /// Added to the entity's position y to get its y-sort pivot.
+    pub sort_pivot_y: f32,
+    /// Default palette swap for entities spawned from this def; empty when unset.
+    pub palette_swap: Vec<PaletteSwapEntry>,
+    /// Particle emitters gameplay starts by trigger name (`ParticleEmitterDef::on`).
+    pub particle_emitters: Vec<ParticleEmitterDef>,
+    /// Point light gameplay attaches to entities spawned from this def.
+    pub light: Option<PointLightDef>,
+    /// Fog-of-war sight radius in tiles for entities spawned from this def.
+    pub sight_radius: Option<f32>,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct DefDatabase {
     entity_defs: Vec<EntityArchetype>,
     entity_ids_by_name: HashMap<String, EntityDefId>,
+    content_hash_sha256_hex: String,
 }
 
 impl DefDatabase {
@@ -37,9 +51,21 @@ impl DefDatabase {
         Self {
             entity_defs,
             entity_ids_by_name,
+            content_hash_sha256_hex: String::new(),
         }
     }
 
+    pub(crate) fn with_content_hash(mut self, content_hash_sha256_hex: String) -> Self {
+        self.content_hash_sha256_hex = content_hash_sha256_hex;
+        self
+    }
+
+    /// Hash of the enabled mods and their XML inputs this database was built from; empty for
+    /// databases built directly rather than through the content pipeline.
+    pub fn content_hash_sha256_hex(&self) -> &str {
+        &self.content_hash_sha256_hex
+    }
+
     pub fn entity_def_id_by_name(&self, name: &str) -> Option<EntityDefId> {
         self.entity_ids_by_name.get(name).copied()
     }
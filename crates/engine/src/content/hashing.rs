@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use sha2::{Digest, Sha256};
 
-use super::types::ContentPlanError;
+use super::types::{ContentPlanError, ModCompileDecision};
 
 #[derive(Debug, Clone)]
 pub(crate) struct ModInputHash {
@@ -20,6 +20,23 @@ pub(crate) fn hash_enabled_mods_list(mod_ids_in_order: &[String]) -> String {
     to_hex_lower(&hasher.finalize())
 }
 
+/// Identity of the loaded content: the enabled mod order plus every mod's XML input hash.
+pub(crate) fn hash_content_inputs(
+    enabled_mods_hash_hex: &str,
+    decisions: &[ModCompileDecision],
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(enabled_mods_hash_hex.as_bytes());
+    hasher.update([0u8]);
+    for decision in decisions {
+        hasher.update(decision.mod_id.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(decision.input_hash_sha256_hex.as_bytes());
+        hasher.update([0u8]);
+    }
+    to_hex_lower(&hasher.finalize())
+}
+
 pub(crate) fn hash_mod_xml_inputs(mod_dir: &Path) -> Result<ModInputHash, ContentPlanError> {
     let xml_files = collect_xml_files(mod_dir)?;
     let mut hasher = Sha256::new();
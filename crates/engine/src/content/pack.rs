@@ -5,7 +5,10 @@ use std::path::Path;
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
-use crate::app::{RenderableKind, SpriteAnchorPx, SpriteAnchors};
+use crate::app::{
+    PaletteSwapEntry, ParticleEmitterDef, ParticleRange, PointLightDef, RenderLayer,
+    RenderableKind, SpriteAnchorPx, SpriteAnchors,
+};
 
 use super::atomic_io::write_bytes_atomic;
 use super::compiler::{CompiledEntityDef, SourceLocation};
@@ -35,6 +38,12 @@ pub struct PackedEntityDef {
     pub attack_range: Option<f32>,
     pub attack_cooldown_seconds: Option<f32>,
     pub tags: Option<Vec<String>>,
+    pub render_layer: Option<RenderLayer>,
+    pub sort_pivot_y: Option<f32>,
+    pub palette_swap: Option<Vec<PaletteSwapEntry>>,
+    pub particle_emitters: Option<Vec<ParticleEmitterDef>>,
+    pub light: Option<PointLightDef>,
+    pub sight_radius: Option<f32>,
 }
 
 #[derive(Debug, Clone)]
@@ -154,6 +163,15 @@ fn encode_payload(records: &[CompiledEntityDef]) -> Result<Vec<u8>, ContentPackE
         if record.tags.is_some() {
             flags |= 1 << 3;
         }
+        if record.particle_emitters.is_some() {
+            flags |= 1 << 4;
+        }
+        if record.light.is_some() {
+            flags |= 1 << 5;
+        }
+        if record.sight_radius.is_some() {
+            flags |= 1 << 6;
+        }
         let mut ext_flags = 0u8;
         if record.health_max.is_some() {
             ext_flags |= 1 << 0;
@@ -170,6 +188,15 @@ fn encode_payload(records: &[CompiledEntityDef]) -> Result<Vec<u8>, ContentPackE
         if record.attack_cooldown_seconds.is_some() {
             ext_flags |= 1 << 4;
         }
+        if record.render_layer.is_some() {
+            ext_flags |= 1 << 5;
+        }
+        if record.sort_pivot_y.is_some() {
+            ext_flags |= 1 << 6;
+        }
+        if record.palette_swap.is_some() {
+            ext_flags |= 1 << 7;
+        }
         if ext_flags != 0 {
             flags |= 1 << 7;
         }
@@ -191,6 +218,7 @@ fn encode_payload(records: &[CompiledEntityDef]) -> Result<Vec<u8>, ContentPackE
                 key,
                 pixel_scale,
                 anchors,
+                mirror_west,
             } = renderable
             {
                 write_string(&mut payload, &key, path_for_payload())?;
@@ -222,6 +250,7 @@ fn encode_payload(records: &[CompiledEntityDef]) -> Result<Vec<u8>, ContentPackE
                     anchors.tool,
                     anchor_mask & (1 << 4) != 0,
                 );
+                payload.push(u8::from(mirror_west));
             }
         }
         if let Some(move_speed) = record.move_speed {
@@ -242,6 +271,25 @@ fn encode_payload(records: &[CompiledEntityDef]) -> Result<Vec<u8>, ContentPackE
         if let Some(attack_cooldown_seconds) = record.attack_cooldown_seconds {
             payload.extend_from_slice(&attack_cooldown_seconds.to_le_bytes());
         }
+        if let Some(render_layer) = record.render_layer {
+            payload.push(render_layer_code(render_layer));
+        }
+        if let Some(sort_pivot_y) = record.sort_pivot_y {
+            payload.extend_from_slice(&sort_pivot_y.to_le_bytes());
+        }
+        if let Some(palette_swap) = &record.palette_swap {
+            if palette_swap.len() > u16::MAX as usize {
+                return Err(invalid_format(
+                    path_for_payload(),
+                    "too many palette swap entries",
+                ));
+            }
+            payload.extend_from_slice(&(palette_swap.len() as u16).to_le_bytes());
+            for entry in palette_swap {
+                payload.extend_from_slice(&entry.from_rgb);
+                payload.extend_from_slice(&entry.to_rgb);
+            }
+        }
         if let Some(tags) = &record.tags {
             if tags.len() > u16::MAX as usize {
                 return Err(invalid_format(path_for_payload(), "too many tags"));
@@ -251,6 +299,26 @@ fn encode_payload(records: &[CompiledEntityDef]) -> Result<Vec<u8>, ContentPackE
                 write_string(&mut payload, tag, path_for_payload())?;
             }
         }
+        if let Some(particle_emitters) = &record.particle_emitters {
+            if particle_emitters.len() > u16::MAX as usize {
+                return Err(invalid_format(
+                    path_for_payload(),
+                    "too many particle emitters",
+                ));
+            }
+            payload.extend_from_slice(&(particle_emitters.len() as u16).to_le_bytes());
+            for emitter in particle_emitters {
+                encode_particle_emitter(&mut payload, emitter)?;
+            }
+        }
+        if let Some(light) = &record.light {
+            payload.extend_from_slice(&light.radius.to_le_bytes());
+            payload.extend_from_slice(&light.intensity.to_le_bytes());
+            payload.extend_from_slice(&light.rgb);
+        }
+        if let Some(sight_radius) = record.sight_radius {
+            payload.extend_from_slice(&sight_radius.to_le_bytes());
+        }
     }
     Ok(payload)
 }
@@ -335,10 +403,17 @@ fn decode_payload(
                             anchor_mask & (1 << 4) != 0,
                         )?,
                     };
+                    let sprite_flags = *read_exact(payload, &mut cursor, 1, path)?
+                        .first()
+                        .ok_or_else(|| invalid_format(path, "missing sprite flags"))?;
+                    if sprite_flags & !0b0000_0001 != 0 {
+                        return Err(invalid_format(path, "invalid sprite flags"));
+                    }
                     RenderableKind::Sprite {
                         key,
                         pixel_scale,
                         anchors,
+                        mirror_west: sprite_flags & (1 << 0) != 0,
                     }
                 }
                 _ => return Err(invalid_format(path, "invalid renderable kind")),
@@ -392,6 +467,42 @@ fn decode_payload(
         } else {
             None
         };
+        let render_layer = if ext_flags & (1 << 5) != 0 {
+            let code = *read_exact(payload, &mut cursor, 1, path)?
+                .first()
+                .ok_or_else(|| invalid_format(path, "missing render layer"))?;
+            Some(
+                RenderLayer::ALL
+                    .get(code as usize)
+                    .copied()
+                    .ok_or_else(|| invalid_format(path, "invalid render layer"))?,
+            )
+        } else {
+            None
+        };
+        let sort_pivot_y = if ext_flags & (1 << 6) != 0 {
+            Some(f32::from_le_bytes(
+                read_exact(payload, &mut cursor, 4, path)?
+                    .try_into()
+                    .map_err(|_| invalid_format(path, "invalid f32 encoding"))?,
+            ))
+        } else {
+            None
+        };
+        let palette_swap = if ext_flags & (1 << 7) != 0 {
+            let count = read_u16(payload, &mut cursor, path)? as usize;
+            let mut out = Vec::<PaletteSwapEntry>::with_capacity(count);
+            for _ in 0..count {
+                let bytes = read_exact(payload, &mut cursor, 6, path)?;
+                out.push(PaletteSwapEntry {
+                    from_rgb: [bytes[0], bytes[1], bytes[2]],
+                    to_rgb: [bytes[3], bytes[4], bytes[5]],
+                });
+            }
+            Some(out)
+        } else {
+            None
+        };
         let tags = if flags & (1 << 3) != 0 {
             let count = read_u16(payload, &mut cursor, path)? as usize;
             let mut out = Vec::<String>::with_capacity(count);
@@ -402,6 +513,35 @@ fn decode_payload(
         } else {
             None
         };
+        let particle_emitters = if flags & (1 << 4) != 0 {
+            let count = read_u16(payload, &mut cursor, path)? as usize;
+            let mut out = Vec::<ParticleEmitterDef>::with_capacity(count);
+            for _ in 0..count {
+                out.push(decode_particle_emitter(payload, &mut cursor, path)?);
+            }
+            Some(out)
+        } else {
+            None
+        };
+        let light = if flags & (1 << 5) != 0 {
+            let bytes = read_exact(payload, &mut cursor, 11, path)?;
+            Some(PointLightDef {
+                radius: f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+                intensity: f32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+                rgb: [bytes[8], bytes[9], bytes[10]],
+            })
+        } else {
+            None
+        };
+        let sight_radius = if flags & (1 << 6) != 0 {
+            Some(f32::from_le_bytes(
+                read_exact(payload, &mut cursor, 4, path)?
+                    .try_into()
+                    .map_err(|_| invalid_format(path, "invalid f32 encoding"))?,
+            ))
+        } else {
+            None
+        };
 
         records.push(PackedEntityDef {
             def_name,
@@ -414,6 +554,12 @@ fn decode_payload(
             attack_range,
             attack_cooldown_seconds,
             tags,
+            render_layer,
+            sort_pivot_y,
+            palette_swap,
+            particle_emitters,
+            light,
+            sight_radius,
         });
     }
     if cursor != payload.len() {
@@ -496,7 +642,7 @@ fn hex_to_32(hex: &str, path: &Path) -> Result<[u8; 32], ContentPackError> {
 }
 
 fn decode_hex(hex: &str, path: &Path) -> Result<Vec<u8>, ContentPackError> {
-    if hex.len() % 2 != 0 {
+    if !hex.len().is_multiple_of(2) {
         return Err(invalid_format(path, "hex string has odd length"));
     }
     let mut out = Vec::<u8>::with_capacity(hex.len() / 2);
@@ -542,6 +688,108 @@ fn path_for_payload() -> &'static Path {
     Path::new("<payload>")
 }
 
+fn render_layer_code(layer: RenderLayer) -> u8 {
+    RenderLayer::ALL
+        .iter()
+        .position(|candidate| *candidate == layer)
+        .expect("every layer is listed in RenderLayer::ALL") as u8
+}
+
+fn encode_particle_emitter(
+    payload: &mut Vec<u8>,
+    emitter: &ParticleEmitterDef,
+) -> Result<(), ContentPackError> {
+    write_string(payload, &emitter.on, path_for_payload())?;
+    payload.extend_from_slice(&emitter.burst.to_le_bytes());
+    for value in [
+        emitter.rate_per_second,
+        emitter.duration_seconds,
+        emitter.lifetime_seconds.min,
+        emitter.lifetime_seconds.max,
+        emitter.speed.min,
+        emitter.speed.max,
+        emitter.direction_degrees.min,
+        emitter.direction_degrees.max,
+        emitter.gravity,
+    ] {
+        payload.extend_from_slice(&value.to_le_bytes());
+    }
+    payload.push(emitter.size_px);
+    if emitter.colors.len() > u8::MAX as usize {
+        return Err(invalid_format(
+            path_for_payload(),
+            "too many particle color stops",
+        ));
+    }
+    payload.push(emitter.colors.len() as u8);
+    for color in &emitter.colors {
+        payload.extend_from_slice(color);
+    }
+    match &emitter.sprite_key {
+        Some(key) => {
+            payload.push(1);
+            write_string(payload, key, path_for_payload())?;
+        }
+        None => payload.push(0),
+    }
+    Ok(())
+}
+
+fn decode_particle_emitter(
+    payload: &[u8],
+    cursor: &mut usize,
+    path: &Path,
+) -> Result<ParticleEmitterDef, ContentPackError> {
+    let on = read_string(payload, cursor, path)?;
+    let burst = read_u16(payload, cursor, path)?;
+    let mut values = [0f32; 9];
+    for value in &mut values {
+        *value = f32::from_le_bytes(
+            read_exact(payload, cursor, 4, path)?
+                .try_into()
+                .map_err(|_| invalid_format(path, "invalid f32 encoding"))?,
+        );
+    }
+    let [rate_per_second, duration_seconds, lifetime_min, lifetime_max, speed_min, speed_max, direction_min, direction_max, gravity] =
+        values;
+    let header = read_exact(payload, cursor, 2, path)?;
+    let (size_px, color_count) = (header[0], header[1] as usize);
+    if color_count == 0 {
+        return Err(invalid_format(path, "particle emitter has no color stops"));
+    }
+    let colors = read_exact(payload, cursor, color_count * 4, path)?
+        .chunks_exact(4)
+        .map(|chunk| [chunk[0], chunk[1], chunk[2], chunk[3]])
+        .collect();
+    let sprite_key = match read_exact(payload, cursor, 1, path)?[0] {
+        0 => None,
+        1 => Some(read_string(payload, cursor, path)?),
+        _ => return Err(invalid_format(path, "invalid particle sprite flag")),
+    };
+    Ok(ParticleEmitterDef {
+        on,
+        burst,
+        rate_per_second,
+        duration_seconds,
+        lifetime_seconds: ParticleRange {
+            min: lifetime_min,
+            max: lifetime_max,
+        },
+        speed: ParticleRange {
+            min: speed_min,
+            max: speed_max,
+        },
+        direction_degrees: ParticleRange {
+            min: direction_min,
+            max: direction_max,
+        },
+        gravity,
+        size_px,
+        colors,
+        sprite_key,
+    })
+}
+
 fn sprite_anchor_mask(anchors: SpriteAnchors) -> u8 {
     let mut mask = 0u8;
     if anchors.hand.is_some() {
@@ -607,6 +855,12 @@ pub fn compiled_from_packed(
         attack_range: packed.attack_range,
         attack_cooldown_seconds: packed.attack_cooldown_seconds,
         tags: packed.tags,
+        render_layer: packed.render_layer,
+        sort_pivot_y: packed.sort_pivot_y,
+        palette_swap: packed.palette_swap,
+        particle_emitters: packed.particle_emitters,
+        light: packed.light,
+        sight_radius: packed.sight_radius,
         source_mod_id: mod_id.to_string(),
         source_file_path: source_path.to_path_buf(),
         source_location: None::<SourceLocation>,
@@ -644,6 +898,7 @@ mod tests {
                     hand: Some(SpriteAnchorPx { x_px: 1, y_px: -1 }),
                     ..SpriteAnchors::default()
                 },
+                mirror_west: true,
             }),
             move_speed: Some(5.0),
             health_max: Some(100),
@@ -652,6 +907,27 @@ mod tests {
             attack_range: Some(0.9),
             attack_cooldown_seconds: Some(1.0),
             tags: Some(vec!["colonist".to_string()]),
+            render_layer: Some(RenderLayer::FloorItem),
+            sort_pivot_y: Some(-0.25),
+            palette_swap: Some(vec![PaletteSwapEntry {
+                from_rgb: [48, 96, 192],
+                to_rgb: [192, 64, 48],
+            }]),
+            particle_emitters: Some(vec![ParticleEmitterDef {
+                on: "damaged".to_string(),
+                burst: 6,
+                lifetime_seconds: ParticleRange { min: 0.2, max: 0.5 },
+                gravity: -2.0,
+                colors: vec![[255, 200, 80, 255], [255, 40, 20, 0]],
+                sprite_key: Some("fx_spark".to_string()),
+                ..ParticleEmitterDef::default()
+            }]),
+            light: Some(PointLightDef {
+                radius: 3.5,
+                rgb: [255, 210, 140],
+                intensity: 1.25,
+            }),
+            sight_radius: Some(7.5),
             source_mod_id: "base".to_string(),
             source_file_path: Path::new("defs.xml").to_path_buf(),
             source_location: None,
@@ -671,11 +947,42 @@ mod tests {
                     hand: Some(SpriteAnchorPx { x_px: 1, y_px: -1 }),
                     ..SpriteAnchors::default()
                 },
+                mirror_west: true,
             })
         );
         assert_eq!(loaded.records[0].health_max, Some(100));
         assert_eq!(loaded.records[0].base_damage, Some(25));
         assert_eq!(loaded.records[0].tags, Some(vec!["colonist".to_string()]));
+        assert_eq!(loaded.records[0].render_layer, Some(RenderLayer::FloorItem));
+        assert_eq!(loaded.records[0].sort_pivot_y, Some(-0.25));
+        assert_eq!(
+            loaded.records[0].palette_swap,
+            Some(vec![PaletteSwapEntry {
+                from_rgb: [48, 96, 192],
+                to_rgb: [192, 64, 48],
+            }])
+        );
+        assert_eq!(
+            loaded.records[0].particle_emitters,
+            Some(vec![ParticleEmitterDef {
+                on: "damaged".to_string(),
+                burst: 6,
+                lifetime_seconds: ParticleRange { min: 0.2, max: 0.5 },
+                gravity: -2.0,
+                colors: vec![[255, 200, 80, 255], [255, 40, 20, 0]],
+                sprite_key: Some("fx_spark".to_string()),
+                ..ParticleEmitterDef::default()
+            }])
+        );
+        assert_eq!(
+            loaded.records[0].light,
+            Some(PointLightDef {
+                radius: 3.5,
+                rgb: [255, 210, 140],
+                intensity: 1.25,
+            })
+        );
+        assert_eq!(loaded.records[0].sight_radius, Some(7.5));
     }
 
     #[test]
@@ -702,6 +1009,12 @@ mod tests {
             attack_range: None,
             attack_cooldown_seconds: None,
             tags: None,
+            render_layer: None,
+            sort_pivot_y: None,
+            palette_swap: None,
+            particle_emitters: None,
+            light: None,
+            sight_radius: None,
             source_mod_id: "base".to_string(),
             source_file_path: Path::new("defs.xml").to_path_buf(),
             source_location: None,
@@ -715,6 +1028,12 @@ mod tests {
         assert_eq!(record.aggro_radius, None);
         assert_eq!(record.attack_range, None);
         assert_eq!(record.attack_cooldown_seconds, None);
+        assert_eq!(record.render_layer, None);
+        assert_eq!(record.sort_pivot_y, None);
+        assert_eq!(record.palette_swap, None);
+        assert_eq!(record.particle_emitters, None);
+        assert_eq!(record.light, None);
+        assert_eq!(record.sight_radius, None);
     }
 
     #[test]
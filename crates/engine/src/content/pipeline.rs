@@ -8,6 +8,7 @@ use super::compiler::{
     ContentCompileError,
 };
 use super::database::DefDatabase;
+use super::hashing::hash_content_inputs;
 use super::manifest::{
     read_manifest, write_manifest_atomic, ManifestReadState, ManifestV1,
     CONTENT_PACK_FORMAT_VERSION,
@@ -102,7 +103,11 @@ pub fn build_or_load_def_database(
         "content_pipeline_summary"
     );
 
-    Ok(def_database_from_compiled_defs(merged)?)
+    let content_hash = hash_content_inputs(
+        &compile_plan.enabled_mods_hash_sha256_hex,
+        &compile_plan.decisions,
+    );
+    Ok(def_database_from_compiled_defs(merged)?.with_content_hash(content_hash))
 }
 
 fn compile_and_write_mod(
@@ -329,7 +334,7 @@ mod tests {
         seed_base_and_mod(&app);
 
         let req = request();
-        let _ = build_or_load_def_database(&app, &req).expect("build");
+        let before = build_or_load_def_database(&app, &req).expect("build");
         write_xml(
             &app.mods_dir.join("moda").join("defs.xml"),
             r#"<Defs><EntityDef><defName>proto.player</defName><label>Moda2</label><renderable>Placeholder</renderable><moveSpeed>9.0</moveSpeed></EntityDef></Defs>"#,
@@ -338,6 +343,11 @@ mod tests {
         let id = db.entity_def_id_by_name("proto.player").expect("id");
         let player = db.entity_def(id).expect("player");
         assert_eq!(player.label, "Moda2");
+        assert_eq!(before.content_hash_sha256_hex().len(), 64);
+        assert_ne!(
+            before.content_hash_sha256_hex(),
+            db.content_hash_sha256_hex()
+        );
     }
 
     #[test]
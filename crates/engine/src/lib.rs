@@ -10,16 +10,35 @@ pub mod content;
 mod sprite_keys;
 
 pub use app::{
-    run_app, run_app_with_hooks, run_app_with_metrics, screen_to_world_px, world_to_screen_px,
-    ActionParams, ActionState, ActionTargetHint, AppError, Camera2D, CardinalFacing,
-    DebugInfoSnapshot, DebugJobState, DebugMarker, DebugMarkerKind, Entity, EntityActionVisual,
-    EntityId, FloorId, InputAction, InputSnapshot, Interactable, InteractableKind, LoopConfig,
-    LoopMetricsSnapshot, LoopRuntimeHooks, MetricsHandle, OrderState, RemoteConsoleLinePump,
-    RenderableDesc, RenderableKind, Renderer, Scene, SceneCommand, SceneDebugCommand,
-    SceneDebugCommandResult, SceneDebugContext, SceneKey, SceneVisualState, SceneWorld,
-    SpriteAnchorName, SpriteAnchorPx, SpriteAnchors, Tilemap, TilemapError, Transform, Vec2,
-    Viewport, CAMERA_ZOOM_DEFAULT, CAMERA_ZOOM_MAX, CAMERA_ZOOM_MIN, CAMERA_ZOOM_STEP,
-    PIXELS_PER_WORLD, PLACEHOLDER_HALF_SIZE_PX, SLOW_FRAME_ENV_VAR,
+    profile_zone, read_user_cvars, run_app, run_app_with_hooks, run_app_with_metrics,
+    screen_to_world_px, validate_floor_declaration, world_to_screen_px, ActionParams, ActionState,
+    ActionTargetHint, AppError, Camera2D, CameraControllerConfig, CardinalFacing, CvarDef,
+    CvarValue, DebugInfoSnapshot, DebugJobState, DebugMarker, DebugMarkerKind, Entity,
+    EntityActionVisual, EntityId, EntitySight, FactionId, FloorError, FloorId, InputAction,
+    InputActionDef, InputBinding, InputMapError, InputModifiers, InputSnapshot, InputTrigger,
+    Interactable, InteractableKind, LightAnchor, LightError, LightId, LoopConfig,
+    LoopMetricsSnapshot, LoopRuntimeHooks, MetricsHandle, MinimapMarker, MinimapMarkers,
+    MinimapRole, OrderState, PaletteSwapEntry, Particle, ParticleEmitterDef, ParticleEmitterId,
+    ParticleRange, PointLight, PointLightDef, ProfileZone, RemoteConsoleLinePump, RenderLayer,
+    RenderModifier, RenderOrderKey, RenderableDesc, RenderableKind, Renderer, ResolvedLight, Scene,
+    SceneCommand, SceneDebugCommand, SceneDebugCommandResult, SceneDebugContext, SceneKey,
+    SceneVisualState, SceneWorld, ScheduledTimer, SightError, SnapshotActionState,
+    SnapshotActionTargetHint, SnapshotCamera, SnapshotDebugMarker, SnapshotEntity,
+    SnapshotEntityActionVisual, SnapshotEntityRemap, SnapshotEntityRenderModifier,
+    SnapshotEntitySight, SnapshotFacing, SnapshotFloor, SnapshotInteractable,
+    SnapshotInteractableKind, SnapshotLight, SnapshotLightAnchor, SnapshotOrderState,
+    SnapshotRenderLayer, SnapshotRenderableKind, SnapshotSpriteAnchors, SnapshotStairLink,
+    SnapshotTilemap, SnapshotTimer, SnapshotVec2, SnapshotVisibilityGrid, SnapshotVisualState,
+    SpriteAnchorName, SpriteAnchorPx, SpriteAnchors, StairLink, StateHasher, TileChannel,
+    TileChunkCoord, TileFlags, TileRect, TileVisibility, Tilemap, TilemapChange, TilemapError,
+    TimerError, TimerFired, TimerId, Transform, Vec2, Viewport, WidgetAnchor, WorldSnapshot,
+    WorldSnapshotError, WorldWidget, WorldWidgetKind, WorldWidgets, AMBIENT_UNLIT,
+    CAMERA_ZOOM_DEFAULT, CAMERA_ZOOM_MAX, CAMERA_ZOOM_MIN, CAMERA_ZOOM_STEP, INPUT_MAP_FILE_NAME,
+    LEGACY_FLOOR_NAMES, MAX_CUSTOM_INPUT_ACTIONS, MAX_FLOORS, MAX_PARTICLES, MAX_POINT_LIGHTS,
+    METRICS_EXPORT_ENV_VAR, PIXELS_PER_WORLD, PLACEHOLDER_HALF_SIZE_PX, RECORD_REPLAY_ENV_VAR,
+    REPLAY_ENV_VAR, REPLAY_FILE_VERSION, SLOW_FRAME_ENV_VAR, TILEMAP_CHANGE_LOG_CAPACITY,
+    TILEMAP_CHUNK_SIZE, TILE_EMPTY, TILE_LAYER_TERRAIN, USER_CVARS_FILE_NAME,
+    WORLD_SNAPSHOT_VERSION, WORLD_WIDGET_LABEL_MAX_CHARS,
 };
 pub use content::{
     build_compile_plan, build_or_load_def_database, compile_def_database, CompileAction,
@@ -1,43 +1,64 @@
-use engine::{ContentPlanRequest, LoopConfig, Scene};
-use tracing::info;
+use engine::{resolve_app_paths, LoopConfig, Scene};
+use tracing::{debug, info};
 use tracing_subscriber::EnvFilter;
 
+use super::project_config::{load_project_config, CliArgs, ConfigSource, ResolvedConfig};
 use super::{dev_thruport, gameplay};
 
-const ENABLED_MODS_ENV_VAR: &str = "PROTOGE_ENABLED_MODS";
-
 pub(crate) struct AppWiring {
     pub(crate) config: LoopConfig,
     pub(crate) scene_a: Box<dyn Scene>,
     pub(crate) scene_b: Box<dyn Scene>,
     pub(crate) dev_thruport: dev_thruport::DevThruport,
+    pub(crate) thruport_diag: bool,
+}
+
+/// What `main` should do after reading the config.
+pub(crate) enum Startup {
+    Run(Box<AppWiring>),
+    /// `--dump-config` printed the effective config.
+    DumpedConfig,
 }
 
-pub(crate) fn build_app() -> AppWiring {
-    init_tracing();
+/// Resolves the project config from `args` (without the program name), then wires the app.
+/// Errors are reported before tracing starts, since the log filter comes from the config.
+pub(crate) fn build_app(args: impl IntoIterator<Item = String>) -> Result<Startup, String> {
+    let cli = CliArgs::parse(args).map_err(|error| error.to_string())?;
+    let app_paths = resolve_app_paths().map_err(|error| error.to_string())?;
+    let resolved = load_project_config(&app_paths.root, &cli, |var| std::env::var(var).ok())
+        .map_err(|error| error.to_string())?;
+    if cli.dump_config {
+        for line in resolved.dump_lines() {
+            println!("{line}");
+        }
+        return Ok(Startup::DumpedConfig);
+    }
+
+    init_tracing(&resolved.config.log.filter);
     info!("=== Proto GE Startup ===");
+    log_project_config(&resolved);
 
     let (scene_a, scene_b) = gameplay::build_scene_pair();
-    let dev_thruport = dev_thruport::initialize(dev_thruport::DevThruportHooks::no_op());
-    let config = LoopConfig {
-        content_plan_request: ContentPlanRequest {
-            enabled_mods: parse_enabled_mods_from_env(),
-            compiler_version: env!("CARGO_PKG_VERSION").to_string(),
-            game_version: env!("CARGO_PKG_VERSION").to_string(),
-        },
-        ..LoopConfig::default()
-    };
+    let dev_thruport = dev_thruport::initialize(
+        dev_thruport::DevThruportHooks::no_op(),
+        resolved.config.dev_thruport_config(),
+    );
 
-    AppWiring {
-        config,
+    Ok(Startup::Run(Box::new(AppWiring {
+        config: LoopConfig {
+            cvars: gameplay::cvar_defs(),
+            pinned_cvars: resolved.pinned_keys(),
+            ..resolved.config.loop_config()
+        },
         scene_a,
         scene_b,
         dev_thruport,
-    }
+        thruport_diag: resolved.config.thruport.diag,
+    })))
 }
 
-fn init_tracing() {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+fn init_tracing(filter: &str) {
+    let filter = EnvFilter::try_new(filter).unwrap_or_else(|_| EnvFilter::new("info"));
     tracing_subscriber::fmt()
         .with_env_filter(filter)
         .with_target(false)
@@ -46,15 +67,19 @@ fn init_tracing() {
         .init();
 }
 
-fn parse_enabled_mods_from_env() -> Vec<String> {
-    std::env::var(ENABLED_MODS_ENV_VAR)
-        .ok()
-        .map(|raw| {
-            raw.split(',')
-                .map(str::trim)
-                .filter(|entry| !entry.is_empty())
-                .map(ToString::to_string)
-                .collect::<Vec<_>>()
-        })
-        .unwrap_or_default()
+fn log_project_config(resolved: &ResolvedConfig) {
+    info!(
+        file = %resolved
+            .file_path
+            .as_ref()
+            .map_or_else(|| "none".to_string(), |path| path.display().to_string()),
+        file_keys = resolved.count_from(ConfigSource::File),
+        user_keys = resolved.count_from(ConfigSource::User),
+        env_keys = resolved.count_from(ConfigSource::Env),
+        cli_keys = resolved.count_from(ConfigSource::Cli),
+        "project_config_loaded"
+    );
+    for line in resolved.dump_lines().iter().skip(1) {
+        debug!(line = %line, "project_config_key");
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 use std::io::{self, Read, Write};
 use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use engine::RemoteConsoleLinePump;
 use tracing::{info, warn};
@@ -25,10 +26,8 @@ pub(crate) enum InjectedInput {
     MouseMove { x: f32, y: f32 },
 }
 
-const THRUPORT_ENV_VAR: &str = "PROTOGE_THRUPORT";
-const THRUPORT_PORT_ENV_VAR: &str = "PROTOGE_THRUPORT_PORT";
-const THRUPORT_DIAG_ENV_VAR: &str = "PROTOGE_THRUPORT_DIAG";
-const THRUPORT_DEFAULT_PORT: u16 = 46001;
+pub(crate) const THRUPORT_DIAG_ENV_VAR: &str = "PROTOGE_THRUPORT_DIAG";
+pub(crate) const THRUPORT_DEFAULT_PORT: u16 = 46001;
 const MAX_PENDING_TELEMETRY_BYTES_PER_CLIENT: usize = 256 * 1024;
 const MAX_PENDING_CONTROL_BYTES_PER_CLIENT: usize = 256 * 1024;
 const REMOTE_CONTROL_PREFIX: &str = "C ";
@@ -66,33 +65,16 @@ impl DevThruportHooks {
     }
 }
 
+/// Resolved from the project config (`thruport.enabled` / `thruport.port` / `thruport.diag`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct DevThruportConfig {
-    enabled: bool,
-    port: u16,
+pub(crate) struct DevThruportConfig {
+    pub(crate) enabled: bool,
+    pub(crate) port: u16,
+    pub(crate) diag: bool,
 }
 
-impl DevThruportConfig {
-    fn from_env() -> Self {
-        let enabled = parse_enabled_flag(std::env::var(THRUPORT_ENV_VAR).ok().as_deref());
-        let raw_port = std::env::var(THRUPORT_PORT_ENV_VAR).ok();
-        let port = match raw_port.as_deref() {
-            Some(value) => match value.parse::<u16>() {
-                Ok(parsed_port) => parsed_port,
-                Err(_) => {
-                    warn!(
-                        value,
-                        fallback_port = THRUPORT_DEFAULT_PORT,
-                        "thruport_invalid_port_using_default"
-                    );
-                    THRUPORT_DEFAULT_PORT
-                }
-            },
-            None => THRUPORT_DEFAULT_PORT,
-        };
-        Self { enabled, port }
-    }
-}
+/// Set once from `DevThruportConfig::diag`; read by the transport's diagnostic log sites.
+static THRUPORT_DIAG: AtomicBool = AtomicBool::new(false);
 
 #[derive(Debug)]
 enum DevThruportMode {
@@ -272,10 +254,10 @@ impl TcpRemoteConsoleTransport {
     }
 }
 
-pub(crate) fn initialize(hooks: DevThruportHooks) -> DevThruport {
+pub(crate) fn initialize(hooks: DevThruportHooks, config: DevThruportConfig) -> DevThruport {
     exercise_forward_contracts_noop();
+    THRUPORT_DIAG.store(config.diag, Ordering::Relaxed);
 
-    let config = DevThruportConfig::from_env();
     let mode = if config.enabled {
         match TcpRemoteConsoleTransport::bind_localhost(config.port) {
             Ok(transport) => {
@@ -385,23 +367,8 @@ fn localhost_bind_addr(port: u16) -> SocketAddr {
     SocketAddr::from(([127, 0, 0, 1], port))
 }
 
-fn parse_enabled_flag(raw: Option<&str>) -> bool {
-    matches!(raw, Some("1"))
-}
-
 fn thruport_diag_enabled() -> bool {
-    matches!(
-        std::env::var(THRUPORT_DIAG_ENV_VAR).ok().as_deref(),
-        Some("1")
-    )
-}
-
-#[cfg(test)]
-fn parse_port_or_default(raw: Option<&str>) -> u16 {
-    match raw.and_then(|value| value.parse::<u16>().ok()) {
-        Some(port) => port,
-        None => THRUPORT_DEFAULT_PORT,
-    }
+    THRUPORT_DIAG.load(Ordering::Relaxed)
 }
 
 fn drain_complete_lines(buffer: &mut Vec<u8>, out: &mut Vec<String>) {
@@ -682,9 +649,9 @@ mod tests {
     use super::{
         encode_line_payload, enqueue_control_line, enqueue_control_line_with_cap,
         enqueue_telemetry_line_with_cap, flush_pending_chunks, initialize, localhost_bind_addr,
-        parse_enabled_flag, parse_port_or_default, ready_line_text, DevThruport, DevThruportConfig,
-        DevThruportHooks, DevThruportMode, OutboundChunk, OutboundChunkState, OutboundClass,
-        TcpRemoteConsoleTransport, THRUPORT_DEFAULT_PORT,
+        ready_line_text, DevThruport, DevThruportConfig, DevThruportHooks, DevThruportMode,
+        OutboundChunk, OutboundChunkState, OutboundClass, TcpRemoteConsoleTransport,
+        THRUPORT_DEFAULT_PORT,
     };
 
     fn make_client_conn_for_queue_tests() -> super::ClientConn {
@@ -707,28 +674,15 @@ mod tests {
     #[test]
     fn initialize_no_op_constructs_without_panic() {
         let hooks = DevThruportHooks::no_op();
-        let _thruport = initialize(hooks);
-    }
-
-    #[test]
-    fn thruport_enablement_from_env_values() {
-        assert!(!parse_enabled_flag(None));
-        assert!(!parse_enabled_flag(Some("0")));
-        assert!(parse_enabled_flag(Some("1")));
-
-        assert_eq!(parse_port_or_default(None), THRUPORT_DEFAULT_PORT);
-        assert_eq!(parse_port_or_default(Some("46001")), 46001);
-        assert_eq!(
-            parse_port_or_default(Some("not-a-port")),
-            THRUPORT_DEFAULT_PORT
+        let thruport = initialize(
+            hooks,
+            DevThruportConfig {
+                enabled: false,
+                port: THRUPORT_DEFAULT_PORT,
+                diag: false,
+            },
         );
-
-        let config = DevThruportConfig {
-            enabled: parse_enabled_flag(Some("1")),
-            port: parse_port_or_default(Some("46002")),
-        };
-        assert!(config.enabled);
-        assert_eq!(config.port, 46002);
+        assert!(matches!(thruport.mode, DevThruportMode::Disabled));
     }
 
     #[test]
@@ -1,4 +1,5 @@
-use std::collections::{HashMap, HashSet};
+use std::any::Any;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Display;
 use std::fs;
 use std::path::PathBuf;
@@ -6,14 +7,16 @@ use std::path::PathBuf;
 #[cfg(test)]
 use engine::ContentPlanRequest;
 use engine::{
-    resolve_app_paths, screen_to_world_px, ActionParams, ActionState, CardinalFacing,
-    DebugInfoSnapshot, DebugJobState, DebugMarker, DebugMarkerKind, EntityActionVisual,
-    EntityArchetype, EntityDefId, EntityId, FloorId, InputAction, InputSnapshot, Interactable,
-    InteractableKind, OrderState, RenderableDesc, RenderableKind, Scene, SceneCommand,
-    SceneDebugCommand, SceneDebugCommandResult, SceneDebugContext, SceneKey, SceneWorld, Tilemap,
-    Transform, Vec2,
+    profile_zone, resolve_app_paths, screen_to_world_px, ActionParams, ActionState,
+    CameraControllerConfig, CardinalFacing, CvarDef, CvarValue, DebugInfoSnapshot, DebugJobState,
+    DebugMarker, DebugMarkerKind, EntityActionVisual, EntityArchetype, EntityDefId, EntityId,
+    EntitySight, FactionId, FloorId, InputAction, InputSnapshot, Interactable, InteractableKind,
+    LightAnchor, MinimapMarkers, MinimapRole, OrderState, RenderModifier, RenderableDesc, Scene,
+    SceneCommand, SceneDebugCommand, SceneDebugCommandResult, SceneDebugContext, SceneKey,
+    SceneWorld, SnapshotOrderState, StateHasher, TileChunkCoord, TileFlags, Tilemap, Transform,
+    Vec2, WidgetAnchor, WorldSnapshot, WorldWidget, WorldWidgets, LEGACY_FLOOR_NAMES,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use tracing::{debug, info, warn};
 
 const CAMERA_SPEED_UNITS_PER_SECOND: f32 = 6.0;
@@ -21,7 +24,7 @@ const MOVE_ARRIVAL_THRESHOLD: f32 = 0.1;
 const JOB_DURATION_SECONDS: f32 = 2.0;
 const RESOURCE_PILE_INTERACTION_RADIUS: f32 = 0.75;
 const RESOURCE_PILE_STARTING_USES: u32 = 3;
-const SAVE_VERSION: u32 = 3;
+const SAVE_VERSION: u32 = 4;
 const SCENE_A_SAVE_FILE: &str = "scene_a.save.json";
 const SCENE_B_SAVE_FILE: &str = "scene_b.save.json";
 const ORDER_MARKER_TTL_SECONDS: f32 = 0.75;
@@ -54,9 +57,29 @@ const VISUAL_SANDBOX_SECOND_PROP_POS: Vec2 = Vec2 { x: -2.0, y: -1.75 };
 const VISUAL_SANDBOX_SECOND_WALL_POS: Vec2 = Vec2 { x: 1.5, y: -2.5 };
 const VISUAL_SANDBOX_SECOND_FLOOR_POS: Vec2 = Vec2 { x: 1.5, y: -3.5 };
 const VISUAL_SANDBOX_HIT_DURATION_TICKS: u32 = 8;
+const HIT_VISUAL_TIMER_TAG: &str = "gameplay.hit_visual";
+const AI_ATTACK_COOLDOWN_TIMER_TAG: &str = "gameplay.ai_attack_cooldown";
+const JOB_RESERVATION_TIMER_TAG: &str = "gameplay.job_reservation";
 const BOX_SELECT_MIN_DRAG_PX: f32 = 6.0;
+const STATUS_WIDGETS_CVAR: &str = "game.status_widgets";
+const MINIMAP_MARKERS_CVAR: &str = "game.minimap_markers";
+const WIDGET_HEALTH_BAR_OFFSET_Y_PX: f32 = -14.0;
+const WIDGET_PROGRESS_ARC_OFFSET_Y_PX: f32 = -26.0;
+const WIDGET_CARRY_LABEL_OFFSET_Y_PX: f32 = 14.0;
+const WIDGET_HEALTH_HIGH_COLOR: [u8; 4] = [84, 214, 96, 255];
+const WIDGET_HEALTH_MID_COLOR: [u8; 4] = [236, 196, 64, 255];
+const WIDGET_HEALTH_LOW_COLOR: [u8; 4] = [226, 72, 64, 255];
+const WIDGET_USE_PROGRESS_COLOR: [u8; 4] = [120, 200, 255, 255];
+const WIDGET_ATTACK_PROGRESS_COLOR: [u8; 4] = [255, 120, 96, 255];
+const WIDGET_CARRY_LABEL_COLOR: [u8; 4] = [240, 232, 200, 255];
+const PARTICLE_TRIGGER_DAMAGED: &str = "damaged";
+const PARTICLE_TRIGGER_INTERACTION_COMPLETED: &str = "interaction_completed";
+const PLAYER_DAMAGED_CAMERA_TRAUMA: f32 = 0.35;
+const BASEMENT_AMBIENT_RGB: [u8; 3] = [70, 74, 104];
+const FACTION_COLONY: FactionId = FactionId(0);
+const FACTION_HOSTILE: FactionId = FactionId(1);
 const JOB_PRIORITY_ABS_MAX: i32 = 100;
-const JOB_RESERVATION_TIMEOUT_TICKS: u64 = 90;
+const JOB_RESERVATION_TIMEOUT_TICKS: u32 = 90;
 const NAV_SANDBOX_MAP_WIDTH: u32 = 12;
 const NAV_SANDBOX_MAP_HEIGHT: u32 = 9;
 const NAV_SANDBOX_ORIGIN: Vec2 = Vec2 { x: -6.0, y: -4.5 };
@@ -69,11 +92,30 @@ const NAV_SANDBOX_INTERACT_TARGET_POS: Vec2 = Vec2 { x: 3.5, y: 0.5 };
 
 include!("types.rs");
 include!("nav.rs");
+include!("stairs.rs");
 include!("systems.rs");
 include!("scene_state.rs");
 include!("scene_impl.rs");
 include!("util.rs");
 
+/// Gameplay cvars; `GameplayScene::apply_cvar` handles them.
+pub(crate) fn cvar_defs() -> Vec<CvarDef> {
+    vec![
+        CvarDef::bool(
+            STATUS_WIDGETS_CVAR,
+            true,
+            "Draw health bars, progress arcs and carry labels over pawns",
+        )
+        .persistent(),
+        CvarDef::bool(
+            MINIMAP_MARKERS_CVAR,
+            true,
+            "Show pawn and interactable dots on the minimap",
+        )
+        .persistent(),
+    ]
+}
+
 pub(crate) fn build_scene_pair() -> (Box<dyn Scene>, Box<dyn Scene>) {
     let scene_a = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
     let scene_b = GameplayScene::new("B", SceneKey::A, Vec2 { x: 2.0, y: 2.0 });
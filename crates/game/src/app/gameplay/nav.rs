@@ -1,6 +1,4 @@
-const FNV1A_OFFSET_BASIS_64: u64 = 0xcbf2_9ce4_8422_2325;
-const FNV1A_PRIME_64: u64 = 0x0000_0100_0000_01b3;
-const NAV_BLOCKED_TILE_ID: u16 = 2;
+const WALL_TILE_ID: u16 = 2;
 const NAV_CARDINAL_COST: u32 = 10;
 const NAV_DIAGONAL_COST: u32 = 14;
 
@@ -16,7 +14,6 @@ struct TilemapNavKey {
     height: u32,
     origin_x_bits: u32,
     origin_y_bits: u32,
-    tiles_hash: u64,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -47,6 +44,7 @@ impl NavigationPathState {
 struct NavigationPassabilityCache {
     key: Option<TilemapNavKey>,
     tilemap_epoch: Option<u64>,
+    chunk_epochs: Vec<Option<u64>>,
     width: u32,
     height: u32,
     origin: Vec2,
@@ -58,6 +56,7 @@ impl Default for NavigationPassabilityCache {
         Self {
             key: None,
             tilemap_epoch: None,
+            chunk_epochs: Vec::new(),
             width: 0,
             height: 0,
             origin: Vec2 { x: 0.0, y: 0.0 },
@@ -70,12 +69,16 @@ impl NavigationPassabilityCache {
     fn clear(&mut self) {
         self.key = None;
         self.tilemap_epoch = None;
+        self.chunk_epochs.clear();
         self.width = 0;
         self.height = 0;
         self.origin = Vec2 { x: 0.0, y: 0.0 };
         self.walkable.clear();
     }
 
+    /// Rebuilds passability for chunks whose epoch changed since the last refresh.
+    ///
+    /// Chunk epochs are stamped by `SceneWorld`, so callers must pass the world's tilemap.
     fn refresh_from_tilemap(&mut self, tilemap: Option<&Tilemap>, tilemap_epoch: u64) {
         let Some(tilemap) = tilemap else {
             self.clear();
@@ -85,27 +88,41 @@ impl NavigationPassabilityCache {
             return;
         }
         let key = compute_tilemap_nav_key(tilemap);
-        if self.key == Some(key) {
-            self.tilemap_epoch = Some(tilemap_epoch);
-            return;
+        if self.key != Some(key) {
+            self.key = Some(key);
+            self.width = tilemap.width();
+            self.height = tilemap.height();
+            self.origin = tilemap.origin();
+            self.walkable = vec![false; (self.width * self.height) as usize];
+            self.chunk_epochs = vec![None; (tilemap.chunks_x() * tilemap.chunks_y()) as usize];
         }
 
-        let width = tilemap.width();
-        let height = tilemap.height();
-        let mut walkable = Vec::with_capacity((width * height) as usize);
-        for y in 0..height {
-            for x in 0..width {
-                let tile_id = tilemap.tile_at(x, y).unwrap_or(0);
-                walkable.push(tile_id != NAV_BLOCKED_TILE_ID);
+        for chunk_y in 0..tilemap.chunks_y() {
+            for chunk_x in 0..tilemap.chunks_x() {
+                let chunk = TileChunkCoord {
+                    x: chunk_x,
+                    y: chunk_y,
+                };
+                let chunk_index = (chunk_y * tilemap.chunks_x() + chunk_x) as usize;
+                let chunk_epoch = tilemap.chunk_epoch(chunk);
+                if self.chunk_epochs[chunk_index] == chunk_epoch {
+                    continue;
+                }
+                let Some((x_min, y_min, x_max, y_max)) = tilemap.chunk_tile_bounds(chunk) else {
+                    continue;
+                };
+                for y in y_min..y_max {
+                    for x in x_min..x_max {
+                        let blocked = tilemap
+                            .flags_at(x, y)
+                            .is_some_and(|flags| flags.contains(TileFlags::BLOCKS_MOVEMENT));
+                        self.walkable[(y * self.width + x) as usize] = !blocked;
+                    }
+                }
+                self.chunk_epochs[chunk_index] = chunk_epoch;
             }
         }
-
-        self.key = Some(key);
         self.tilemap_epoch = Some(tilemap_epoch);
-        self.width = width;
-        self.height = height;
-        self.origin = tilemap.origin();
-        self.walkable = walkable;
     }
 
     fn world_to_tile(&self, world: Vec2) -> Option<TileCoord> {
@@ -182,6 +199,7 @@ impl NavigationPassabilityCache {
     }
 
     fn find_path_tiles(&self, start: TileCoord, goal: TileCoord) -> Option<Vec<TileCoord>> {
+        let _zone = profile_zone("nav.path");
         let start_index = self.index_of(start)?;
         let goal_index = self.index_of(goal)?;
         if !self.is_walkable(start) || !self.is_walkable(goal) {
@@ -400,28 +418,12 @@ fn manhattan_distance(a: TileCoord, b: TileCoord) -> u32 {
 }
 
 fn compute_tilemap_nav_key(tilemap: &Tilemap) -> TilemapNavKey {
-    let mut tiles_hash = FNV1A_OFFSET_BASIS_64;
-    for y in 0..tilemap.height() {
-        for x in 0..tilemap.width() {
-            let tile_id = tilemap.tile_at(x, y).unwrap_or(0);
-            tiles_hash = fnv1a_update_u16(tiles_hash, tile_id);
-        }
-    }
     TilemapNavKey {
         width: tilemap.width(),
         height: tilemap.height(),
         origin_x_bits: tilemap.origin().x.to_bits(),
         origin_y_bits: tilemap.origin().y.to_bits(),
-        tiles_hash,
-    }
-}
-
-fn fnv1a_update_u16(mut hash: u64, value: u16) -> u64 {
-    for byte in value.to_le_bytes() {
-        hash ^= byte as u64;
-        hash = hash.wrapping_mul(FNV1A_PRIME_64);
     }
-    hash
 }
 
 #[cfg(test)]
@@ -435,7 +437,7 @@ mod nav_tests {
     }
 
     fn tilemap_with_tiles(width: u32, height: u32, tiles: Vec<u16>) -> Tilemap {
-        Tilemap::new(width, height, Vec2 { x: 0.0, y: 0.0 }, tiles).expect("tilemap")
+        wall_tilemap(width, height, Vec2 { x: 0.0, y: 0.0 }, tiles)
     }
 
     #[test]
@@ -446,7 +448,7 @@ mod nav_tests {
         for y in 0..height {
             if y != 4 {
                 let index = (y * width + 3) as usize;
-                tiles[index] = NAV_BLOCKED_TILE_ID;
+                tiles[index] = WALL_TILE_ID;
             }
         }
         let cache = cache_from_tilemap(tilemap_with_tiles(width, height, tiles));
@@ -468,7 +470,7 @@ mod nav_tests {
         let width = 5u32;
         let height = 5u32;
         let mut tiles = vec![0u16; (width * height) as usize];
-        tiles[(2 * width + 2) as usize] = NAV_BLOCKED_TILE_ID;
+        tiles[(2 * width + 2) as usize] = WALL_TILE_ID;
         let cache = cache_from_tilemap(tilemap_with_tiles(width, height, tiles));
 
         let start = cache.tile_center_world(TileCoord { x: 0, y: 2 });
@@ -487,8 +489,8 @@ mod nav_tests {
         let width = 2u32;
         let height = 2u32;
         let mut tiles = vec![0u16; (width * height) as usize];
-        tiles[(0 * width + 1) as usize] = NAV_BLOCKED_TILE_ID;
-        tiles[(1 * width + 0) as usize] = NAV_BLOCKED_TILE_ID;
+        tiles[(0 * width + 1) as usize] = WALL_TILE_ID;
+        tiles[(1 * width + 0) as usize] = WALL_TILE_ID;
         let cache = cache_from_tilemap(tilemap_with_tiles(width, height, tiles));
 
         let start = cache.tile_center_world(TileCoord { x: 0, y: 0 });
@@ -514,4 +516,58 @@ mod nav_tests {
             .expect("open grid path");
         assert_eq!(path.waypoints_world.len(), 4);
     }
+
+    #[test]
+    fn passability_reads_movement_flag_not_tile_id() {
+        let mut tilemap = Tilemap::new(3, 1, Vec2 { x: 0.0, y: 0.0 }, vec![WALL_TILE_ID, 0, 0])
+            .expect("tilemap");
+        tilemap.set_flags(2, 0, TileFlags::BLOCKS_MOVEMENT);
+        let cache = cache_from_tilemap(tilemap);
+
+        assert!(cache.is_walkable(TileCoord { x: 0, y: 0 }));
+        assert!(cache.is_walkable(TileCoord { x: 1, y: 0 }));
+        assert!(!cache.is_walkable(TileCoord { x: 2, y: 0 }));
+    }
+
+    #[test]
+    fn refresh_skips_chunks_whose_epoch_is_unchanged() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(tilemap_with_tiles(40, 4, vec![0; 160]));
+        let mut cache = NavigationPassabilityCache::default();
+        cache.refresh_from_tilemap(world.tilemap(), world.tilemap_epoch());
+        let stamped_epoch = world.tilemap_epoch();
+        assert!(cache
+            .chunk_epochs
+            .iter()
+            .all(|epoch| *epoch == Some(stamped_epoch)));
+
+        cache.walkable[0] = false;
+        cache.refresh_from_tilemap(world.tilemap(), u64::MAX);
+        assert!(
+            !cache.walkable[0],
+            "clean chunks should not be rebuilt when only the map epoch moves"
+        );
+
+        world.set_tilemap(tilemap_with_tiles(40, 4, vec![0; 160]));
+        cache.refresh_from_tilemap(world.tilemap(), world.tilemap_epoch());
+        assert!(cache.walkable[0]);
+    }
+
+    #[test]
+    fn world_tile_edit_rebuilds_only_the_dirty_chunk() {
+        let mut world = SceneWorld::default();
+        world.set_tilemap(tilemap_with_tiles(40, 4, vec![0; 160]));
+        let mut cache = NavigationPassabilityCache::default();
+        cache.refresh_from_tilemap(world.tilemap(), world.tilemap_epoch());
+        cache.walkable[0] = false;
+
+        assert!(world.set_tile_flags(35, 2, TileFlags::BLOCKS_MOVEMENT));
+        cache.refresh_from_tilemap(world.tilemap(), world.tilemap_epoch());
+
+        assert!(!cache.is_walkable(TileCoord { x: 35, y: 2 }));
+        assert!(
+            !cache.walkable[0],
+            "untouched chunk should keep its cached passability"
+        );
+    }
 }
@@ -5,16 +5,26 @@ impl Scene for GameplayScene {
         self.next_save_id = 0;
         self.reset_runtime_component_stores();
         let player_archetype = resolve_player_archetype(world);
+        world
+            .declare_floors(&LEGACY_FLOOR_NAMES, FloorId::MAIN)
+            .expect("legacy floor layout should be valid");
+        world.set_floor_ambient(FloorId::BASEMENT, BASEMENT_AMBIENT_RGB);
+        world.set_fog_viewer(Some(FACTION_COLONY));
+        world.set_camera_controller_config(CameraControllerConfig {
+            clamp_to_bounds: true,
+            ..CameraControllerConfig::default()
+        });
         world.set_tilemap(build_ground_tilemap(self.scene_key()));
         self.player_move_speed = player_archetype.move_speed;
         self.player_id = None;
         self.selected_entity = None;
+        self.camera_follow_selection = None;
         self.selected_settlers.clear();
         self.selection_drag_start_px = None;
         self.selection_drag_cursor_px = None;
-        self.active_floor = ActiveFloor::Main;
+        self.active_floor = world.default_floor();
         self.last_player_facing = CardinalFacing::South;
-        world.set_active_floor(self.active_floor_engine());
+        world.set_active_floor(self.active_floor);
         self.resource_count = 0;
         self.current_tick = 0;
         self.interactable_cache.clear();
@@ -23,7 +33,6 @@ impl Scene for GameplayScene {
         self.combat_chaser_scenario = CombatChaserScenarioSlot::default();
         self.visual_sandbox_demo_active = false;
         self.carry_visual_by_actor.clear();
-        self.hit_ticks_remaining_by_actor.clear();
         self.system_order_text = GAMEPLAY_SYSTEM_ORDER_TEXT.to_string();
         world.apply_pending();
         self.sync_save_id_map_with_world(world)
@@ -87,7 +96,7 @@ impl Scene for GameplayScene {
             return SceneCommand::SwitchTo(self.switch_target);
         }
 
-        world.set_active_floor(self.active_floor_engine());
+        world.set_active_floor(self.active_floor);
         self.run_gameplay_systems_once(fixed_dt_seconds, input, world);
         self.apply_system_outputs(fixed_dt_seconds, input, world);
 
@@ -165,10 +174,22 @@ impl Scene for GameplayScene {
                 self.sync_settler_multi_selection_from_primary(world);
                 SceneDebugCommandResult::Success(format!("selected entity {entity_id}"))
             }
-            SceneDebugCommand::OrderMove { x, y } => {
+            SceneDebugCommand::OrderMove { x, y, floor } => {
                 let Some(actor_id) = self.selected_entity else {
                     return SceneDebugCommandResult::Error("no selected entity".to_string());
                 };
+                let goal_floor = match floor.as_deref() {
+                    Some(token) => match world.resolve_floor_token(token) {
+                        Some(goal_floor) => goal_floor,
+                        None => {
+                            return SceneDebugCommandResult::Error(format!(
+                                "invalid floor '{token}' (expected floor name or index < {})",
+                                world.floor_count()
+                            ));
+                        }
+                    },
+                    None => self.active_floor,
+                };
                 let Some(actor) = world.find_entity(actor_id) else {
                     return SceneDebugCommandResult::Error(format!(
                         "selected entity {} not found",
@@ -196,6 +217,22 @@ impl Scene for GameplayScene {
                     ));
                 }
                 let point = Vec2 { x, y };
+                if goal_floor != actor.floor {
+                    let actor_floor = actor.floor;
+                    if !self.issue_floor_aware_move(actor_id, goal_floor, point, world) {
+                        return SceneDebugCommandResult::Error(format!(
+                            "no stair route from floor {} to floor {} for entity {}",
+                            actor_floor.0, goal_floor.0, actor_id.0
+                        ));
+                    }
+                    return SceneDebugCommandResult::Success(format!(
+                        "queued move for entity {} to ({:.2}, {:.2}) on floor {}",
+                        actor_id.0,
+                        x,
+                        y,
+                        world.floor_name(goal_floor).unwrap_or_default()
+                    ));
+                }
                 if self.actor_uses_settler_navigation(actor_id) {
                     if self
                         .assign_job_to_actor_with_interruption(
@@ -338,12 +375,13 @@ impl Scene for GameplayScene {
                 ))
             }
             SceneDebugCommand::FloorSet { floor } => {
-                self.active_floor = ActiveFloor::from_engine_floor(floor);
-                world.set_active_floor(self.active_floor_engine());
-                world.set_hovered_interactable_visual(None);
-                world.set_selected_actor_visual(None);
-                world.set_targeted_interactable_visual(None);
-                world.clear_debug_markers();
+                let Some(floor_id) = world.resolve_floor_token(&floor) else {
+                    return SceneDebugCommandResult::Error(format!(
+                        "invalid floor '{floor}' (expected floor name or index < {})",
+                        world.floor_count()
+                    ));
+                };
+                self.switch_active_floor(floor_id, world);
 
                 if self.selected_entity.is_some_and(|selected_id| {
                     match world.find_entity(selected_id) {
@@ -358,7 +396,7 @@ impl Scene for GameplayScene {
 
                 SceneDebugCommandResult::Success(format!(
                     "floor.set v1 active:{}",
-                    self.active_floor.as_token()
+                    world.floor_name(self.active_floor).unwrap_or_default()
                 ))
             }
             SceneDebugCommand::DumpState => {
@@ -402,6 +440,18 @@ impl Scene for GameplayScene {
 
     fn render(&mut self, _world: &SceneWorld) {}
 
+    fn submit_world_widgets(&mut self, world: &SceneWorld, widgets: &mut WorldWidgets) {
+        if self.show_status_widgets {
+            self.submit_status_widgets(world, widgets);
+        }
+    }
+
+    fn submit_minimap_markers(&mut self, world: &SceneWorld, markers: &mut MinimapMarkers) {
+        if self.show_minimap_markers {
+            self.submit_role_markers(world, markers);
+        }
+    }
+
     fn unload(&mut self, world: &mut SceneWorld) {
         info!(
             scene = self.scene_name,
@@ -410,12 +460,13 @@ impl Scene for GameplayScene {
         );
         self.player_id = None;
         self.selected_entity = None;
+        self.camera_follow_selection = None;
         self.selected_settlers.clear();
         self.selection_drag_start_px = None;
         self.selection_drag_cursor_px = None;
-        self.active_floor = ActiveFloor::Main;
+        self.active_floor = world.default_floor();
         self.last_player_facing = CardinalFacing::South;
-        world.set_active_floor(self.active_floor_engine());
+        world.set_active_floor(self.active_floor);
         self.resource_count = 0;
         self.current_tick = 0;
         self.interactable_cache.clear();
@@ -431,7 +482,6 @@ impl Scene for GameplayScene {
         self.combat_chaser_scenario = CombatChaserScenarioSlot::default();
         self.visual_sandbox_demo_active = false;
         self.carry_visual_by_actor.clear();
-        self.hit_ticks_remaining_by_actor.clear();
         self.selected_completion_enqueued_this_tick = false;
         self.reselect_player_on_respawn = false;
     }
@@ -662,4 +712,31 @@ impl Scene for GameplayScene {
             extra_debug_lines: Some(extra_debug_lines),
         })
     }
+
+    fn hash_state(&self, world: &SceneWorld, hasher: &mut StateHasher) {
+        self.hash_gameplay_state(world, hasher);
+    }
+
+    fn debug_event_fired_last_tick(&self, event: &str) -> Option<bool> {
+        let kind = GameplayEventKind::from_name(event)?;
+        Some(self.system_events.last_tick_counts().count(kind) > 0)
+    }
+
+    fn capture_rewind_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn restore_rewind_state(&mut self, state: &dyn Any) {
+        if let Some(state) = state.downcast_ref::<GameplayScene>() {
+            self.clone_from(state);
+        }
+    }
+
+    fn apply_cvar(&mut self, name: &str, value: &CvarValue) {
+        match (name, value.as_bool()) {
+            (STATUS_WIDGETS_CVAR, Some(show)) => self.show_status_widgets = show,
+            (MINIMAP_MARKERS_CVAR, Some(show)) => self.show_minimap_markers = show,
+            _ => {}
+        }
+    }
 }
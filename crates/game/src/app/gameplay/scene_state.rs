@@ -1,13 +1,15 @@
+#[derive(Clone)]
 struct GameplayScene {
     scene_name: &'static str,
     switch_target: SceneKey,
     player_spawn: Vec2,
     player_id: Option<EntityId>,
     selected_entity: Option<EntityId>,
+    camera_follow_selection: Option<EntityId>,
     selected_settlers: Vec<EntityId>,
     selection_drag_start_px: Option<Vec2>,
     selection_drag_cursor_px: Option<Vec2>,
-    active_floor: ActiveFloor,
+    active_floor: FloorId,
     last_player_facing: CardinalFacing,
     player_move_speed: f32,
     resource_count: u32,
@@ -32,8 +34,8 @@ struct GameplayScene {
     selected_completion_enqueued_this_tick: bool,
     visual_sandbox_demo_active: bool,
     carry_visual_by_actor: HashMap<EntityId, String>,
-    hit_ticks_remaining_by_actor: HashMap<EntityId, u32>,
-    nav_passability_cache: NavigationPassabilityCache,
+    nav_passability_by_floor: Vec<NavigationPassabilityCache>,
+    floor_route_by_entity: HashMap<EntityId, FloorRoute>,
     nav_path_by_entity: HashMap<EntityId, NavigationPathState>,
     job_board: JobBoard,
     job_phase_by_entity: HashMap<EntityId, JobPhase>,
@@ -42,6 +44,8 @@ struct GameplayScene {
     system_intents: GameplayIntentQueue,
     system_order_text: String,
     combat_chaser_scenario: CombatChaserScenarioSlot,
+    show_status_widgets: bool,
+    show_minimap_markers: bool,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -66,10 +70,11 @@ impl GameplayScene {
             player_spawn,
             player_id: None,
             selected_entity: None,
+            camera_follow_selection: None,
             selected_settlers: Vec::new(),
             selection_drag_start_px: None,
             selection_drag_cursor_px: None,
-            active_floor: ActiveFloor::Main,
+            active_floor: FloorId::MAIN,
             last_player_facing: CardinalFacing::South,
             player_move_speed: 5.0,
             resource_count: 0,
@@ -94,8 +99,8 @@ impl GameplayScene {
             selected_completion_enqueued_this_tick: false,
             visual_sandbox_demo_active: false,
             carry_visual_by_actor: HashMap::new(),
-            hit_ticks_remaining_by_actor: HashMap::new(),
-            nav_passability_cache: NavigationPassabilityCache::default(),
+            nav_passability_by_floor: Vec::new(),
+            floor_route_by_entity: HashMap::new(),
             nav_path_by_entity: HashMap::new(),
             job_board: JobBoard::default(),
             job_phase_by_entity: HashMap::new(),
@@ -104,6 +109,8 @@ impl GameplayScene {
             system_intents: GameplayIntentQueue::default(),
             system_order_text: String::new(),
             combat_chaser_scenario: CombatChaserScenarioSlot::default(),
+            show_status_widgets: true,
+            show_minimap_markers: true,
         }
     }
 
@@ -114,12 +121,8 @@ impl GameplayScene {
         }
     }
 
-    fn active_floor_engine(&self) -> FloorId {
-        self.active_floor.to_engine_floor()
-    }
-
     fn entity_is_on_active_floor(&self, entity: &engine::Entity) -> bool {
-        entity.floor == self.active_floor_engine()
+        entity.floor == self.active_floor
     }
 
     fn classify_actor_role_from_archetype_tags(archetype: &EntityArchetype) -> PawnControlRole {
@@ -207,9 +210,37 @@ impl GameplayScene {
         if let Some(job_id) = self.job_board.clear_assignment_for_entity(actor_id) {
             self.job_board.mark_job_state(job_id, state);
         }
+        world.cancel_owner_timers(actor_id, JOB_RESERVATION_TIMER_TAG);
         self.clear_actor_locomotion_state(actor_id, world, enqueue_cancel_if_needed);
     }
 
+    fn reserve_job_for_actor(
+        &mut self,
+        job_id: JobId,
+        actor_id: EntityId,
+        world: &mut SceneWorld,
+    ) -> bool {
+        if !self
+            .job_board
+            .assign_job_to_entity_at_tick(job_id, actor_id, self.current_tick)
+        {
+            return false;
+        }
+        Self::rearm_job_reservation_timer(actor_id, world);
+        true
+    }
+
+    /// Restarts the actor's reservation timeout; it fires after `JOB_RESERVATION_TIMEOUT_TICKS`
+    /// ticks without job progress.
+    fn rearm_job_reservation_timer(actor_id: EntityId, world: &mut SceneWorld) {
+        world.cancel_owner_timers(actor_id, JOB_RESERVATION_TIMER_TAG);
+        let _ = world.schedule_timer(
+            Some(actor_id),
+            JOB_RESERVATION_TIMER_TAG,
+            JOB_RESERVATION_TIMEOUT_TICKS,
+        );
+    }
+
     fn assign_job_to_actor_with_interruption(
         &mut self,
         actor_id: EntityId,
@@ -221,6 +252,7 @@ impl GameplayScene {
         if !actor.actor || !self.actor_uses_settler_navigation(actor_id) {
             return None;
         }
+        self.floor_route_by_entity.remove(&actor_id);
         if let Some(existing_job_id) = self.job_board.assigned_job_id(actor_id) {
             self.job_board.mark_job_state(existing_job_id, JobState::Failed);
             self.job_board.clear_assignment_for_entity(actor_id);
@@ -229,10 +261,7 @@ impl GameplayScene {
         let job_id = self
             .job_board
             .create_job(kind, target, Self::clamp_job_priority(0));
-        if !self
-            .job_board
-            .assign_job_to_entity_at_tick(job_id, actor_id, self.current_tick)
-        {
+        if !self.reserve_job_for_actor(job_id, actor_id, world) {
             self.job_board.mark_job_state(job_id, JobState::Failed);
             return None;
         }
@@ -246,21 +275,33 @@ impl GameplayScene {
     }
 
     fn refresh_nav_cache_from_world(&mut self, world: &SceneWorld) {
-        self.nav_passability_cache
-            .refresh_from_tilemap(world.tilemap(), world.tilemap_epoch());
+        self.nav_passability_by_floor
+            .resize_with(world.floor_count(), NavigationPassabilityCache::default);
+        for floor in world.floor_ids() {
+            self.nav_passability_by_floor[floor.index()]
+                .refresh_from_tilemap(world.floor_tilemap(floor), world.tilemap_epoch());
+        }
+    }
+
+    fn nav_cache_for_floor(&self, floor: FloorId) -> Option<&NavigationPassabilityCache> {
+        self.nav_passability_by_floor.get(floor.index())
+    }
+
+    fn nav_world_to_tile(&self, floor: FloorId, world_point: Vec2) -> Option<TileCoord> {
+        self.nav_cache_for_floor(floor)?.world_to_tile(world_point)
     }
 
     fn rebuild_settler_path_from_world_targets(
         &mut self,
         actor_id: EntityId,
+        floor: FloorId,
         actor_world: Vec2,
         goal_world: Vec2,
         planned_epoch: u64,
     ) -> bool {
-        let Some(path_state) = self
-            .nav_passability_cache
-            .build_path_state_from_world(actor_world, goal_world, planned_epoch)
-        else {
+        let Some(path_state) = self.nav_cache_for_floor(floor).and_then(|cache| {
+            cache.build_path_state_from_world(actor_world, goal_world, planned_epoch)
+        }) else {
             self.nav_path_by_entity.remove(&actor_id);
             return false;
         };
@@ -281,13 +322,15 @@ impl GameplayScene {
             return false;
         }
         let actor_world = actor.transform.position;
+        let actor_floor = actor.floor;
         let is_settler = self.actor_uses_settler_navigation(actor_id);
 
         let path_result = if is_settler {
             self.refresh_nav_cache_from_world(world);
             let planned_epoch = world.tilemap_epoch();
-            self.nav_passability_cache
-                .build_path_state_from_world(actor_world, target_world, planned_epoch)
+            self.nav_cache_for_floor(actor_floor).and_then(|cache| {
+                cache.build_path_state_from_world(actor_world, target_world, planned_epoch)
+            })
         } else {
             None
         };
@@ -335,7 +378,7 @@ impl GameplayScene {
     }
 
     fn sanitize_settler_multi_selection(&mut self, world: &SceneWorld) {
-        let active_floor = self.active_floor_engine();
+        let active_floor = self.active_floor;
         let mut retained = Vec::with_capacity(self.selected_settlers.len());
         for entity_id in self.selected_settlers.iter().copied() {
             let on_active_floor = world
@@ -413,7 +456,6 @@ impl GameplayScene {
         self.nav_path_by_entity.remove(&entity_id);
         self.job_phase_by_entity.remove(&entity_id);
         self.carry_visual_by_actor.remove(&entity_id);
-        self.hit_ticks_remaining_by_actor.remove(&entity_id);
         self.selected_settlers.retain(|selected_id| *selected_id != entity_id);
         if let Some(job_id) = self.job_board.clear_assignment_for_entity(entity_id) {
             self.job_board.mark_job_state(job_id, JobState::Failed);
@@ -563,29 +605,18 @@ impl GameplayScene {
                 format!("{:?}", save.scene_key),
             ));
         }
-        if !save.camera_position.x.is_finite() {
-            return Err(Self::expected_actual(
-                "camera_position.x",
-                "finite number",
-                save.camera_position.x,
-            ));
-        }
-        if !save.camera_position.y.is_finite() {
-            return Err(Self::expected_actual(
-                "camera_position.y",
-                "finite number",
-                save.camera_position.y,
-            ));
-        }
-        if !save.camera_zoom.is_finite() {
-            return Err(Self::expected_actual(
-                "camera_zoom",
-                "finite number",
-                save.camera_zoom,
-            ));
-        }
+        save.world
+            .validate()
+            .map_err(|error| Self::validation_err("world", error.to_string()))?;
 
+        let world_entity_ids = save
+            .world
+            .entities
+            .iter()
+            .map(|entity| entity.id)
+            .collect::<HashSet<_>>();
         let mut known_save_ids = HashMap::with_capacity(save.entities.len());
+        let mut sectioned_entity_ids = HashMap::with_capacity(save.entities.len());
         for (index, entity) in save.entities.iter().enumerate() {
             let save_id_path = format!("entities[{index}].save_id");
             if let Some(first_index) = known_save_ids.insert(entity.save_id, index) {
@@ -597,85 +628,29 @@ impl GameplayScene {
                     ),
                 ));
             }
-
-            let pos_x_path = format!("entities[{index}].position.x");
-            let pos_y_path = format!("entities[{index}].position.y");
-            if !entity.position.x.is_finite() {
-                return Err(Self::expected_actual(
-                    &pos_x_path,
-                    "finite number",
-                    entity.position.x,
+            let entity_id_path = format!("entities[{index}].entity_id");
+            if !world_entity_ids.contains(&entity.entity_id) {
+                return Err(Self::validation_err(
+                    &entity_id_path,
+                    format!("references unknown world entity id {}", entity.entity_id),
                 ));
             }
-            if !entity.position.y.is_finite() {
-                return Err(Self::expected_actual(
-                    &pos_y_path,
-                    "finite number",
-                    entity.position.y,
+            if let Some(first_index) = sectioned_entity_ids.insert(entity.entity_id, index) {
+                return Err(Self::validation_err(
+                    &entity_id_path,
+                    format!(
+                        "duplicate entity_id {} (first seen at entities[{first_index}].entity_id)",
+                        entity.entity_id
+                    ),
                 ));
             }
-
-            if let Some(rotation_radians) = entity.rotation_radians {
-                let path = format!("entities[{index}].rotation_radians");
-                if !rotation_radians.is_finite() {
-                    return Err(Self::expected_actual(
-                        &path,
-                        "finite number",
-                        rotation_radians,
-                    ));
-                }
-            }
-
-            if let Some(move_target_world) = entity.move_target_world {
-                let move_x_path = format!("entities[{index}].move_target_world.x");
-                let move_y_path = format!("entities[{index}].move_target_world.y");
-                if !move_target_world.x.is_finite() {
-                    return Err(Self::expected_actual(
-                        &move_x_path,
-                        "finite number",
-                        move_target_world.x,
-                    ));
-                }
-                if !move_target_world.y.is_finite() {
-                    return Err(Self::expected_actual(
-                        &move_y_path,
-                        "finite number",
-                        move_target_world.y,
-                    ));
-                }
-            }
-
-            if let Some(interactable) = entity.interactable {
-                let radius_path = format!("entities[{index}].interactable.interaction_radius");
-                if !interactable.interaction_radius.is_finite() {
-                    return Err(Self::expected_actual(
-                        &radius_path,
-                        "finite number",
-                        interactable.interaction_radius,
-                    ));
-                }
-                if interactable.interaction_radius < 0.0 {
-                    return Err(Self::expected_actual(
-                        &radius_path,
-                        ">= 0",
-                        interactable.interaction_radius,
-                    ));
-                }
-            }
-
-            if let SavedJobState::Working { remaining_time, .. } = entity.job_state {
-                let path = format!("entities[{index}].job_state.remaining_time");
-                if !remaining_time.is_finite() {
-                    return Err(Self::expected_actual(
-                        &path,
-                        "finite number",
-                        remaining_time,
-                    ));
-                }
-                if remaining_time < 0.0 {
-                    return Err(Self::expected_actual(&path, ">= 0", remaining_time));
-                }
-            }
+        }
+        if sectioned_entity_ids.len() != world_entity_ids.len() {
+            return Err(Self::expected_actual(
+                "entities",
+                format!("one entry per world entity ({})", world_entity_ids.len()),
+                sectioned_entity_ids.len(),
+            ));
         }
         let known_save_ids = known_save_ids.keys().copied().collect::<HashSet<_>>();
 
@@ -696,24 +671,18 @@ impl GameplayScene {
             }
         }
 
-        for (index, entity) in save.entities.iter().enumerate() {
-            if let Some(target_save_id) = entity.interaction_target_save_id {
-                if !known_save_ids.contains(&target_save_id) {
-                    let path = format!("entities[{index}].interaction_target_save_id");
-                    return Err(Self::validation_err(
-                        &path,
-                        format!("references unknown save_id {target_save_id}"),
-                    ));
-                }
-            }
-            if let SavedJobState::Working { target_save_id, .. } = entity.job_state {
-                if !known_save_ids.contains(&target_save_id) {
-                    let path = format!("entities[{index}].job_state.target_save_id");
-                    return Err(Self::validation_err(
-                        &path,
-                        format!("references unknown save_id {target_save_id}"),
-                    ));
-                }
+        for (index, entity) in save.world.entities.iter().enumerate() {
+            let target_save_id = match entity.order_state {
+                SnapshotOrderState::Interact { target_save_id }
+                | SnapshotOrderState::Working { target_save_id, .. } => target_save_id,
+                SnapshotOrderState::Idle | SnapshotOrderState::MoveTo { .. } => continue,
+            };
+            if !known_save_ids.contains(&target_save_id) {
+                let path = format!("world.entities[{index}].order_state.target_save_id");
+                return Err(Self::validation_err(
+                    &path,
+                    format!("references unknown save_id {target_save_id}"),
+                ));
             }
         }
 
@@ -739,103 +708,34 @@ impl GameplayScene {
         Ok(())
     }
 
-    fn saved_order_fields_from_runtime(
-        order_state: OrderState,
-    ) -> (Option<SavedVec2>, Option<u64>, SavedJobState) {
-        match order_state {
-            OrderState::Idle => (None, None, SavedJobState::Idle),
-            OrderState::MoveTo { point } => {
-                (Some(SavedVec2::from_vec2(point)), None, SavedJobState::Idle)
-            }
-            OrderState::Interact { target_save_id } => {
-                (None, Some(target_save_id), SavedJobState::Idle)
-            }
-            OrderState::Working {
-                target_save_id,
-                remaining_time,
-            } => (
-                None,
-                Some(target_save_id),
-                SavedJobState::Working {
-                    target_save_id,
-                    remaining_time,
-                },
-            ),
-        }
-    }
-
-    fn runtime_order_state_from_saved(entity: &SavedEntityRuntime) -> OrderState {
-        match entity.job_state {
-            SavedJobState::Working {
-                target_save_id,
-                remaining_time,
-            } => OrderState::Working {
-                target_save_id,
-                remaining_time,
-            },
-            SavedJobState::Idle => {
-                if let Some(target_save_id) = entity.interaction_target_save_id {
-                    OrderState::Interact { target_save_id }
-                } else if let Some(point) = entity.move_target_world {
-                    OrderState::MoveTo {
-                        point: point.to_vec2(),
-                    }
-                } else {
-                    OrderState::Idle
-                }
-            }
-        }
-    }
-
     fn build_save_game(&mut self, world: &SceneWorld) -> SaveLoadResult<SaveGame> {
         self.sync_save_id_map_with_world(world)?;
         let def_db = world.def_database();
 
-        let entities = world
-            .entities()
+        let snapshot = world.capture_snapshot();
+        let entities = snapshot
+            .entities
             .iter()
-            .map(|entity| {
+            .map(|saved| {
+                let entity_id = EntityId(saved.id);
                 let save_id = self
                     .entity_save_ids
-                    .get(&entity.id)
+                    .get(&entity_id)
                     .copied()
-                    .ok_or_else(|| {
-                        format!("missing save_id mapping for entity id {}", entity.id.0)
-                    })?;
-                let (move_target_world, interaction_target_save_id, job_state) =
-                    Self::saved_order_fields_from_runtime(entity.order_state);
+                    .ok_or_else(|| format!("missing save_id mapping for entity id {}", saved.id))?;
                 let archetype_def_name = self
                     .entity_archetype_id_by_entity
-                    .get(&entity.id)
+                    .get(&entity_id)
                     .and_then(|def_id| {
                         def_db
                             .and_then(|db| db.entity_def(*def_id))
                             .map(|archetype| archetype.def_name.clone())
                     });
-
                 Ok(SavedEntityRuntime {
+                    entity_id: saved.id,
                     save_id,
-                    position: SavedVec2::from_vec2(entity.transform.position),
-                    rotation_radians: entity.transform.rotation_radians,
-                    floor: Some(SavedFloorId::from_engine_floor(entity.floor)),
-                    selectable: entity.selectable,
-                    actor: entity.actor,
                     archetype_def_name,
-                    move_target_world,
-                    interaction_target_save_id,
-                    job_state,
-                    carry_visual_def: self.actor_held_visual(entity.id),
-                    interactable: entity.interactable.map(|interactable| {
-                        SavedInteractableRuntime {
-                            kind: match interactable.kind {
-                                InteractableKind::ResourcePile => {
-                                    SavedInteractableKind::ResourcePile
-                                }
-                            },
-                            interaction_radius: interactable.interaction_radius,
-                            remaining_uses: interactable.remaining_uses,
-                        }
-                    }),
+                    carry_visual_def: self.actor_held_visual(entity_id),
                 })
             })
             .collect::<SaveLoadResult<Vec<_>>>()?;
@@ -843,9 +743,7 @@ impl GameplayScene {
         Ok(SaveGame {
             save_version: SAVE_VERSION,
             scene_key: SavedSceneKey::from_scene_key(self.scene_key()),
-            active_floor: Some(SavedFloorId::from_engine_floor(self.active_floor_engine())),
-            camera_position: SavedVec2::from_vec2(world.camera().position),
-            camera_zoom: world.camera().zoom,
+            world: snapshot,
             selected_entity_save_id: self
                 .selected_entity
                 .and_then(|id| self.entity_save_ids.get(&id).copied()),
@@ -859,31 +757,12 @@ impl GameplayScene {
     }
 
     fn apply_save_game(&mut self, save: SaveGame, world: &mut SceneWorld) -> SaveLoadResult<()> {
-        let saved_active_floor = save
-            .active_floor
-            .unwrap_or(SavedFloorId::Main)
-            .to_engine_floor();
-        let def_db = world.def_database().cloned();
-        let needs_actor_archetype = save.entities.iter().any(|entity| entity.actor);
-        let needs_pile_archetype = save
-            .entities
-            .iter()
-            .any(|entity| entity.interactable.is_some());
-        let player_archetype = if needs_actor_archetype {
-            Some(try_resolve_player_archetype(world)?)
-        } else {
-            None
-        };
-        let pile_archetype = if needs_pile_archetype {
-            Some(try_resolve_resource_pile_archetype(world)?)
-        } else {
-            None
-        };
-        if let Some(archetype) = &player_archetype {
-            self.player_move_speed = archetype.move_speed;
+        if save.world.entities.iter().any(|entity| entity.actor) {
+            self.player_move_speed = try_resolve_player_archetype(world)?.move_speed;
         }
-
-        world.clear();
+        let remap = world
+            .restore_snapshot(&save.world)
+            .map_err(|error| error.to_string())?;
         self.interactable_cache.clear();
         self.interactable_lookup_by_save_id.clear();
         self.target_lookup_by_save_id.clear();
@@ -896,50 +775,17 @@ impl GameplayScene {
         self.entity_archetype_id_by_entity.clear();
         self.pawn_role_by_entity.clear();
         self.reset_runtime_component_stores();
-        world.camera_mut().position = save.camera_position.to_vec2();
-        world.camera_mut().set_zoom_clamped(save.camera_zoom);
 
-        let mut spawned_ids = Vec::with_capacity(save.entities.len());
+        let def_db = world.def_database();
         let mut spawned_ids_by_save_id = HashMap::with_capacity(save.entities.len());
         let mut loaded_archetype_id_by_entity = HashMap::with_capacity(save.entities.len());
         for saved_entity in &save.entities {
-            let resolved_archetype = saved_entity.archetype_def_name.as_deref().and_then(|name| {
-                let db = def_db.as_ref()?;
-                let id = db.entity_def_id_by_name(name)?;
-                let archetype = db.entity_def(id)?;
-                Some((id, archetype.clone()))
-            });
-            let renderable_kind = if let Some((_, archetype)) = &resolved_archetype {
-                archetype.renderable.clone()
-            } else if saved_entity.interactable.is_some() {
-                pile_archetype
-                    .as_ref()
-                    .map(|archetype| archetype.renderable.clone())
-                    .unwrap_or(RenderableKind::Placeholder)
-            } else if saved_entity.actor {
-                player_archetype
-                    .as_ref()
-                    .map(|archetype| archetype.renderable.clone())
-                    .unwrap_or(RenderableKind::Placeholder)
-            } else {
-                RenderableKind::Placeholder
+            let Some(id) = remap.runtime_id(saved_entity.entity_id) else {
+                return Err(format!(
+                    "restored entity missing for save_id {}",
+                    saved_entity.save_id
+                ));
             };
-            let floor = saved_entity
-                .floor
-                .unwrap_or(SavedFloorId::Main)
-                .to_engine_floor();
-            world.set_active_floor(floor);
-            let id = world.spawn(
-                Transform {
-                    position: saved_entity.position.to_vec2(),
-                    rotation_radians: saved_entity.rotation_radians,
-                },
-                RenderableDesc {
-                    kind: renderable_kind,
-                    debug_name: "saved",
-                },
-            );
-            spawned_ids.push(id);
             if spawned_ids_by_save_id
                 .insert(saved_entity.save_id, id)
                 .is_some()
@@ -949,36 +795,13 @@ impl GameplayScene {
                     saved_entity.save_id
                 ));
             }
-            if let Some((archetype_id, _)) = resolved_archetype {
+            let archetype_id = saved_entity
+                .archetype_def_name
+                .as_deref()
+                .and_then(|name| def_db?.entity_def_id_by_name(name));
+            if let Some(archetype_id) = archetype_id {
                 loaded_archetype_id_by_entity.insert(id, archetype_id);
             }
-        }
-        world.apply_pending();
-
-        for (saved_entity, id) in save.entities.iter().zip(spawned_ids.into_iter()) {
-            let Some(entity) = world.find_entity_mut(id) else {
-                return Err(format!(
-                    "spawned entity missing for save_id {}",
-                    saved_entity.save_id
-                ));
-            };
-
-            entity.transform.position = saved_entity.position.to_vec2();
-            entity.transform.rotation_radians = saved_entity.rotation_radians;
-            entity.floor = saved_entity
-                .floor
-                .unwrap_or(SavedFloorId::Main)
-                .to_engine_floor();
-            entity.selectable = saved_entity.selectable;
-            entity.actor = saved_entity.actor;
-            entity.order_state = Self::runtime_order_state_from_saved(saved_entity);
-            entity.interactable = saved_entity.interactable.map(|interactable| Interactable {
-                kind: match interactable.kind {
-                    SavedInteractableKind::ResourcePile => InteractableKind::ResourcePile,
-                },
-                interaction_radius: interactable.interaction_radius,
-                remaining_uses: interactable.remaining_uses,
-            });
             if let Some(carry_visual_def) = &saved_entity.carry_visual_def {
                 self.carry_visual_by_actor
                     .insert(id, carry_visual_def.clone());
@@ -995,8 +818,7 @@ impl GameplayScene {
         self.rebuild_save_id_map_from_loaded(world, &spawned_ids_by_save_id, save.next_save_id)?;
         self.entity_archetype_id_by_entity = loaded_archetype_id_by_entity;
         self.resource_count = save.resource_count;
-        self.active_floor = ActiveFloor::from_engine_floor(saved_active_floor);
-        world.set_active_floor(self.active_floor_engine());
+        self.active_floor = world.active_floor();
         self.rebuild_pawn_roles_from_world(world);
         self.sync_runtime_component_stores_with_world(world);
         self.rebuild_active_interactions_from_world_order(world);
@@ -1017,11 +839,11 @@ impl GameplayScene {
         self.pawn_role_by_entity.clear();
         self.target_lookup_by_save_id.clear();
         self.nav_path_by_entity.clear();
-        self.nav_passability_cache.clear();
+        self.nav_passability_by_floor.clear();
+        self.floor_route_by_entity.clear();
         self.job_board.clear();
         self.job_phase_by_entity.clear();
         self.carry_visual_by_actor.clear();
-        self.hit_ticks_remaining_by_actor.clear();
         self.next_interaction_id = 0;
     }
 
@@ -1306,7 +1128,6 @@ impl GameplayScene {
         self.combat_chaser_scenario = CombatChaserScenarioSlot::default();
         self.visual_sandbox_demo_active = false;
         self.carry_visual_by_actor.clear();
-        self.hit_ticks_remaining_by_actor.clear();
         self.nav_path_by_entity.clear();
 
         let player_id =
@@ -1345,7 +1166,6 @@ impl GameplayScene {
         self.selection_drag_cursor_px = None;
         self.visual_sandbox_demo_active = false;
         self.carry_visual_by_actor.clear();
-        self.hit_ticks_remaining_by_actor.clear();
         self.nav_path_by_entity.clear();
 
         let player_id =
@@ -1406,7 +1226,6 @@ impl GameplayScene {
         self.visual_sandbox_demo_active = false;
         self.combat_chaser_scenario = CombatChaserScenarioSlot::default();
         self.carry_visual_by_actor.clear();
-        self.hit_ticks_remaining_by_actor.clear();
         self.nav_path_by_entity.clear();
 
         let player_id = self.apply_spawn_intent_now(world, "proto.player", NAV_SANDBOX_PLAYER_POS)?;
@@ -1451,6 +1270,164 @@ impl GameplayScene {
         self.carry_visual_by_actor.get(&actor_id).cloned()
     }
 
+    /// Health bars for damaged entities, progress arcs for timed interactions, and a short
+    /// label for whatever an actor carries; emitted in entity-id order for a stable draw order.
+    fn submit_status_widgets(&self, world: &SceneWorld, widgets: &mut WorldWidgets) {
+        let mut damaged: Vec<(EntityId, Health)> = self
+            .health_by_entity
+            .iter()
+            .filter(|(_, health)| health.max > 0 && health.current < health.max)
+            .map(|(id, health)| (*id, *health))
+            .collect();
+        damaged.sort_by_key(|(id, _)| id.0);
+        for (id, health) in damaged {
+            let fraction = health.current as f32 / health.max as f32;
+            widgets.push(
+                WorldWidget::bar(WidgetAnchor::Entity(id), fraction, health_bar_color(fraction))
+                    .with_offset_px(0.0, WIDGET_HEALTH_BAR_OFFSET_Y_PX),
+            );
+        }
+
+        let mut timed: Vec<&ActiveInteraction> = self
+            .active_interactions_by_actor
+            .values()
+            .filter(|interaction| {
+                interaction.remaining_seconds.is_some() && interaction.duration_seconds > 0.0
+            })
+            .collect();
+        timed.sort_by_key(|interaction| interaction.actor_id.0);
+        for interaction in timed {
+            let remaining = interaction.remaining_seconds.unwrap_or(0.0);
+            let fraction = 1.0 - remaining / interaction.duration_seconds;
+            let color = match interaction.kind {
+                ActiveInteractionKind::Use => WIDGET_USE_PROGRESS_COLOR,
+                ActiveInteractionKind::Attack => WIDGET_ATTACK_PROGRESS_COLOR,
+            };
+            widgets.push(
+                WorldWidget::progress_arc(WidgetAnchor::Entity(interaction.actor_id), fraction, color)
+                    .with_offset_px(0.0, WIDGET_PROGRESS_ARC_OFFSET_Y_PX),
+            );
+        }
+
+        let mut carried: Vec<(EntityId, &str)> = self
+            .carry_visual_by_actor
+            .iter()
+            .map(|(id, def_name)| (*id, def_name.as_str()))
+            .collect();
+        carried.sort_by_key(|(id, _)| id.0);
+        for (id, def_name) in carried {
+            if world.find_entity(id).is_none() {
+                continue;
+            }
+            let short_name = def_name.rsplit('.').next().unwrap_or(def_name);
+            widgets.push(
+                WorldWidget::label(WidgetAnchor::Entity(id), short_name, WIDGET_CARRY_LABEL_COLOR)
+                    .with_offset_px(0.0, WIDGET_CARRY_LABEL_OFFSET_Y_PX),
+            );
+        }
+    }
+
+    /// One minimap dot per pawn (by control role) and per interactable, in entity order.
+    fn submit_role_markers(&self, world: &SceneWorld, markers: &mut MinimapMarkers) {
+        for entity in world.entities() {
+            let role = match self.pawn_role_by_entity.get(&entity.id) {
+                Some(PawnControlRole::PlayerPawn) => MinimapRole::Player,
+                Some(PawnControlRole::Settler) => MinimapRole::Settler,
+                Some(PawnControlRole::Npc) => MinimapRole::Npc,
+                None if entity.interactable.is_some() => MinimapRole::Interactable,
+                None => continue,
+            };
+            markers.push(entity.id, role);
+        }
+    }
+
+    /// Gameplay half of the per-tick state hash. Per-entity stores are walked in world entity
+    /// order and the job board in id order, so `HashMap` iteration order never leaks in.
+    /// Selection, drag boxes, caches and other derived or UI-only state are left out.
+    fn hash_gameplay_state(&self, world: &SceneWorld, hasher: &mut StateHasher) {
+        hasher.write_u64(self.current_tick);
+        hasher.write_u32(self.resource_count);
+        hasher.write_u64(self.next_save_id);
+        hasher.write_u64(self.next_interaction_id);
+        hasher.write_u16(self.active_floor.0);
+        for entity in world.entities() {
+            let id = entity.id;
+            hasher.write_u64(id.0);
+            hasher.write_u64(self.entity_save_ids.get(&id).copied().unwrap_or(u64::MAX));
+            hasher.write_bytes(&[self.pawn_role_by_entity.get(&id).map_or(0, |role| *role as u8 + 1)]);
+            match self.health_by_entity.get(&id) {
+                Some(health) => {
+                    hasher.write_u32(health.current);
+                    hasher.write_u32(health.max);
+                }
+                None => hasher.write_u32(u32::MAX),
+            }
+            hasher.write_u32(self.damage_by_entity.get(&id).copied().unwrap_or(0));
+            let statuses = self
+                .status_sets_by_entity
+                .get(&id)
+                .map_or(&[][..], |set| set.active.as_slice());
+            hasher.write_u64(statuses.len() as u64);
+            for status in statuses {
+                hasher.write_str(status.status_id.0);
+                hasher.write_f32(status.remaining_seconds);
+            }
+            match self.ai_agents_by_entity.get(&id) {
+                Some(agent) => {
+                    hasher.write_bytes(&[agent.state as u8 + 1]);
+                    hasher.write_vec2(agent.home_position);
+                    hasher.write_bool(agent.wander_target.is_some());
+                    hasher.write_vec2(agent.wander_target.unwrap_or_default());
+                }
+                None => hasher.write_bytes(&[0]),
+            }
+            match self.active_interactions_by_actor.get(&id) {
+                Some(interaction) => {
+                    hasher.write_bytes(&[interaction.kind as u8 + 1]);
+                    hasher.write_u64(interaction.target_id.0);
+                    hasher.write_u64(interaction.interaction_id.0);
+                    hasher.write_f32(interaction.remaining_seconds.unwrap_or(-1.0));
+                }
+                None => hasher.write_bytes(&[0]),
+            }
+            hasher.write_bytes(&[self.job_phase_by_entity.get(&id).map_or(0, |phase| *phase as u8 + 1)]);
+            hasher.write_u64(
+                self.job_board
+                    .assigned_job_by_entity
+                    .get(&id)
+                    .map_or(u64::MAX, |job_id| job_id.0),
+            );
+            match self.nav_path_by_entity.get(&id) {
+                Some(path) => {
+                    hasher.write_u32(path.goal_tile.x);
+                    hasher.write_u32(path.goal_tile.y);
+                    hasher.write_u64(path.waypoints_world.len() as u64);
+                    hasher.write_u64(path.next_waypoint_index as u64);
+                }
+                None => hasher.write_u64(u64::MAX),
+            }
+        }
+        hasher.write_u64(self.job_board.next_job_id);
+        for job in self.job_board.jobs_by_id.values() {
+            hasher.write_u64(job.id.0);
+            hasher.write_bytes(&[job.kind as u8, job.state as u8]);
+            match job.target {
+                JobTarget::WorldPoint(point) => {
+                    hasher.write_bytes(&[0]);
+                    hasher.write_vec2(point);
+                }
+                JobTarget::TargetSaveId(save_id) => {
+                    hasher.write_bytes(&[1]);
+                    hasher.write_u64(save_id);
+                }
+            }
+            hasher.write_i32(job.priority);
+            hasher.write_u64(job.reserved_by.map_or(u64::MAX, |id| id.0));
+            hasher.write_u64(job.reserved_tick.unwrap_or(u64::MAX));
+            hasher.write_u64(job.last_progress_tick.unwrap_or(u64::MAX));
+        }
+    }
+
     fn actor_is_using_sandbox_workbench(&self, world: &SceneWorld, actor_id: EntityId) -> bool {
         if !self.visual_sandbox_demo_active {
             return false;
@@ -1464,15 +1441,6 @@ impl GameplayScene {
         self.entity_has_archetype_tag(world, interaction.target_id, "workbench_demo")
     }
 
-    fn tick_hit_timers(&mut self) {
-        self.hit_ticks_remaining_by_actor.retain(|_, remaining| {
-            if *remaining > 0 {
-                *remaining = remaining.saturating_sub(1);
-            }
-            *remaining > 0
-        });
-    }
-
     fn entity_has_archetype_tag(
         &self,
         world: &SceneWorld,
@@ -1491,6 +1459,27 @@ impl GameplayScene {
         archetype.tags.iter().any(|candidate| candidate == tag)
     }
 
+    /// Starts the `trigger` emitters of the entity's archetype at its current floor and position.
+    fn start_entity_particles(&self, world: &mut SceneWorld, entity_id: EntityId, trigger: &str) {
+        let Some((floor, position)) = world
+            .find_entity(entity_id)
+            .map(|entity| (entity.floor, entity.transform.position))
+        else {
+            return;
+        };
+        let Some(archetype_id) = self.entity_archetype_id_by_entity.get(&entity_id).copied() else {
+            return;
+        };
+        let Some(emitters) = world
+            .def_database()
+            .and_then(|def_db| def_db.entity_def(archetype_id))
+            .map(|archetype| archetype.particle_emitters.clone())
+        else {
+            return;
+        };
+        world.start_particle_emitters_on(&emitters, trigger, floor, position);
+    }
+
     fn interaction_outcome_target_kind(
         &self,
         world: &SceneWorld,
@@ -1647,6 +1636,7 @@ impl GameplayScene {
         intents: Vec<GameplayIntent>,
         world: &mut SceneWorld,
     ) -> GameplayIntentApplyStats {
+        let _zone = profile_zone("intents");
         let mut stats = GameplayIntentApplyStats::default();
         let mut pending = intents;
         let mut cursor = 0usize;
@@ -1707,6 +1697,7 @@ impl GameplayScene {
                             },
                         )
                     };
+                    apply_archetype_presentation(world, entity_id, &archetype);
                     world.apply_pending();
                     if has_actor_tag {
                         if let Some(entity) = world.find_entity_mut(entity_id) {
@@ -1777,6 +1768,7 @@ impl GameplayScene {
                         stats.record_invalid_target();
                         continue;
                     }
+                    self.floor_route_by_entity.remove(&actor_id);
                     if !self.issue_move_order_with_optional_settler_path(actor_id, point, world) {
                         stats.record_invalid_target();
                     }
@@ -1817,18 +1809,25 @@ impl GameplayScene {
                     };
                     let applied = amount.min(health.current);
                     let was_alive = health.current > 0;
+                    health.current = health.current.saturating_sub(applied);
+                    let died = was_alive && health.current == 0;
+                    if died && self.player_id == Some(entity_id) {
+                        health.current = health.max;
+                    }
                     if applied > 0 {
-                        health.current = health.current.saturating_sub(applied);
                         self.system_events.emit(GameplayEvent::EntityDamaged {
                             entity_id,
                             amount: applied,
                         });
+                        self.start_entity_particles(world, entity_id, PARTICLE_TRIGGER_DAMAGED);
+                        if self.player_id == Some(entity_id) {
+                            world.add_camera_trauma(PLAYER_DAMAGED_CAMERA_TRAUMA);
+                        }
                     }
-                    if was_alive && health.current == 0 {
+                    if died {
                         self.system_events
                             .emit(GameplayEvent::EntityDied { entity_id });
                         if self.player_id == Some(entity_id) {
-                            health.current = health.max;
                             debug!(
                                 entity_id = entity_id.0,
                                 "authoritative_player_death_is_non_despawning"
@@ -1895,6 +1894,7 @@ impl GameplayScene {
                         stats.record_invalid_target();
                         continue;
                     };
+                    self.floor_route_by_entity.remove(&actor_id);
                     let Some(actor) = world.find_entity_mut(actor_id) else {
                         stats.record_invalid_target();
                         continue;
@@ -1938,7 +1938,7 @@ impl GameplayScene {
                     }
                     actor.order_state = OrderState::Idle;
                     self.nav_path_by_entity.remove(&actor_id);
-                    let _ = target_id;
+                    self.start_entity_particles(world, target_id, PARTICLE_TRIGGER_INTERACTION_COMPLETED);
                 }
                 GameplayIntent::SetCarryVisual {
                     actor_id,
@@ -1984,10 +1984,23 @@ impl GameplayScene {
                         stats.record_invalid_target();
                         continue;
                     }
-                    if ticks == 0 {
-                        self.hit_ticks_remaining_by_actor.remove(&actor_id);
-                    } else {
-                        self.hit_ticks_remaining_by_actor.insert(actor_id, ticks);
+                    world.cancel_owner_timers(actor_id, HIT_VISUAL_TIMER_TAG);
+                    if ticks > 0 {
+                        let _ = world.schedule_timer(Some(actor_id), HIT_VISUAL_TIMER_TAG, ticks);
+                    }
+                }
+                GameplayIntent::StartAttackCooldownTimer { actor_id, ticks } => {
+                    if world.find_entity(actor_id).is_none() {
+                        stats.record_invalid_target();
+                        continue;
+                    }
+                    world.cancel_owner_timers(actor_id, AI_ATTACK_COOLDOWN_TIMER_TAG);
+                    if ticks > 0 {
+                        let _ = world.schedule_timer(
+                            Some(actor_id),
+                            AI_ATTACK_COOLDOWN_TIMER_TAG,
+                            ticks,
+                        );
                     }
                 }
             }
@@ -2005,6 +2018,7 @@ impl GameplayScene {
         let intents = self.system_intents.drain_current_tick();
         let stats = self.apply_gameplay_intents_at_safe_point(intents, world);
         self.system_intents.set_last_tick_apply_stats(stats);
+        self.run_floor_route_pass(world);
         self.apply_gameplay_tick_at_safe_point(fixed_dt_seconds, input, world);
         self.system_events.finish_tick_rollover();
     }
@@ -2036,10 +2050,11 @@ impl GameplayScene {
                 rotation_radians: None,
             },
             RenderableDesc {
-                kind: player_archetype.renderable,
+                kind: player_archetype.renderable.clone(),
                 debug_name: "player_auto",
             },
         );
+        apply_archetype_presentation(world, player_id, &player_archetype);
         if let Some(player) = world.find_entity_mut(player_id) {
             player.selectable = true;
         }
@@ -2085,17 +2100,23 @@ impl GameplayScene {
 
     fn run_job_reservation_timeout_release_pass(&mut self, world: &mut SceneWorld) {
         let mut timed_out = Vec::<(JobId, EntityId)>::new();
-        for (job_id, job) in &self.job_board.jobs_by_id {
-            if !matches!(job.state, JobState::Reserved | JobState::InProgress) {
+        for fired in world.fired_timers() {
+            if fired.tag != JOB_RESERVATION_TIMER_TAG {
                 continue;
             }
-            let Some(actor_id) = job.reserved_by else {
+            let Some(actor_id) = fired.owner else {
+                continue;
+            };
+            let Some(job_id) = self.job_board.assigned_job_id(actor_id) else {
                 continue;
             };
-            let last_progress_tick = job.last_progress_tick.or(job.reserved_tick).unwrap_or(0);
-            if self.current_tick.saturating_sub(last_progress_tick) >= JOB_RESERVATION_TIMEOUT_TICKS
+            let Some(job) = self.job_board.job(job_id) else {
+                continue;
+            };
+            if job.reserved_by == Some(actor_id)
+                && matches!(job.state, JobState::Reserved | JobState::InProgress)
             {
-                timed_out.push((*job_id, actor_id));
+                timed_out.push((job_id, actor_id));
             }
         }
         timed_out.sort_by_key(|(job_id, actor_id)| (job_id.0, actor_id.0));
@@ -2138,7 +2159,7 @@ impl GameplayScene {
     }
 
     fn run_settler_open_job_auto_pick(&mut self, world: &mut SceneWorld) {
-        let active_floor = self.active_floor_engine();
+        let active_floor = self.active_floor;
         let mut idle_settlers = world
             .entities()
             .iter()
@@ -2195,10 +2216,7 @@ impl GameplayScene {
             let Some((job_id, _, _)) = best_job else {
                 continue;
             };
-            if self
-                .job_board
-                .assign_job_to_entity_at_tick(job_id, actor_id, self.current_tick)
-            {
+            if self.reserve_job_for_actor(job_id, actor_id, world) {
                 self.job_phase_by_entity.insert(actor_id, JobPhase::Navigating);
             }
         }
@@ -2247,10 +2265,12 @@ impl GameplayScene {
             if matches!(job.state, JobState::Reserved) {
                 self.job_board
                     .mark_job_in_progress_at_tick(job_id, self.current_tick);
+                Self::rearm_job_reservation_timer(actor_id, world);
             } else if !matches!(actor.order_state, OrderState::Idle) {
                 if let Some(job) = self.job_board.jobs_by_id.get_mut(&job_id) {
                     job.last_progress_tick = Some(self.current_tick);
                 }
+                Self::rearm_job_reservation_timer(actor_id, world);
             }
 
             match job.kind {
@@ -2400,7 +2420,16 @@ impl GameplayScene {
 
             match (job.kind, job.target) {
                 (JobKind::MoveToPoint, JobTarget::WorldPoint(target_world)) => {
-                    let Some(goal_tile) = self.nav_passability_cache.world_to_tile(target_world) else {
+                    let Some(nav_cache) = self.nav_cache_for_floor(actor.floor) else {
+                        self.finalize_assigned_job_and_clear_locomotion(
+                            actor_id,
+                            JobState::Failed,
+                            world,
+                            true,
+                        );
+                        continue;
+                    };
+                    let Some(goal_tile) = nav_cache.world_to_tile(target_world) else {
                         self.finalize_assigned_job_and_clear_locomotion(
                             actor_id,
                             JobState::Failed,
@@ -2409,7 +2438,7 @@ impl GameplayScene {
                         );
                         continue;
                     };
-                    let goal_world = self.nav_passability_cache.tile_center_world(goal_tile);
+                    let goal_world = nav_cache.tile_center_world(goal_tile);
                     let dx = goal_world.x - actor.transform.position.x;
                     let dy = goal_world.y - actor.transform.position.y;
                     let arrived = dx * dx + dy * dy <= MOVE_ARRIVAL_THRESHOLD * MOVE_ARRIVAL_THRESHOLD;
@@ -2479,18 +2508,23 @@ impl GameplayScene {
         world: &mut SceneWorld,
     ) {
         self.current_tick = self.current_tick.saturating_add(1);
-        world
-            .camera_mut()
-            .apply_zoom_steps(input.zoom_delta_steps());
+        world.zoom_camera_at(
+            input.zoom_delta_steps(),
+            input.cursor_position_px(),
+            input.window_size(),
+        );
         world.tick_debug_markers(fixed_dt_seconds);
+        world.tick_timers();
+        world.tick_particles(fixed_dt_seconds);
+        world.update_visibility();
         let hovered_interactable = input.cursor_position_px().and_then(|cursor_px| {
             world.pick_topmost_interactable_at_cursor(
                 cursor_px,
                 input.window_size(),
-                Some(self.active_floor.to_engine_floor()),
+                Some(self.active_floor),
             )
         });
-        let active_floor = self.active_floor_engine();
+        let active_floor = self.active_floor;
         if self.selected_entity.is_some_and(|selected_id| match world.find_entity(selected_id) {
             Some(entity) => entity.floor != active_floor,
             None => true,
@@ -2507,7 +2541,7 @@ impl GameplayScene {
                 world.pick_topmost_selectable_at_cursor(
                     cursor_px,
                     input.window_size(),
-                    Some(self.active_floor.to_engine_floor()),
+                    Some(self.active_floor),
                 )
             });
             self.sync_settler_multi_selection_from_primary(world);
@@ -2767,7 +2801,7 @@ impl GameplayScene {
                     let move_speed =
                         self.effective_move_speed_for_entity(entity.id, self.player_move_speed);
                     if self.actor_uses_settler_navigation(entity.id) {
-                        let Some(goal_tile) = self.nav_passability_cache.world_to_tile(point) else {
+                        let Some(goal_tile) = self.nav_world_to_tile(entity.floor, point) else {
                             entity.order_state = OrderState::Idle;
                             self.nav_path_by_entity.remove(&entity.id);
                             continue;
@@ -2782,6 +2816,7 @@ impl GameplayScene {
                         if needs_rebuild
                             && !self.rebuild_settler_path_from_world_targets(
                                 entity.id,
+                                entity.floor,
                                 entity.transform.position,
                                 point,
                                 tilemap_epoch,
@@ -2853,7 +2888,7 @@ impl GameplayScene {
                                 .effective_move_speed_for_entity(entity.id, self.player_move_speed);
                             if self.actor_uses_settler_navigation(entity.id) {
                                 let Some(goal_tile) =
-                                    self.nav_passability_cache.world_to_tile(target_world)
+                                    self.nav_world_to_tile(entity.floor, target_world)
                                 else {
                                     entity.order_state = OrderState::Idle;
                                     self.nav_path_by_entity.remove(&entity.id);
@@ -2870,6 +2905,7 @@ impl GameplayScene {
                                 if needs_rebuild
                                     && !self.rebuild_settler_path_from_world_targets(
                                         entity.id,
+                                        entity.floor,
                                         entity.transform.position,
                                         target_world,
                                         tilemap_epoch,
@@ -2937,7 +2973,7 @@ impl GameplayScene {
                                 .effective_move_speed_for_entity(entity.id, self.player_move_speed);
                             if self.actor_uses_settler_navigation(entity.id) {
                                 let Some(goal_tile) =
-                                    self.nav_passability_cache.world_to_tile(target_world)
+                                    self.nav_world_to_tile(entity.floor, target_world)
                                 else {
                                     entity.order_state = OrderState::Idle;
                                     self.nav_path_by_entity.remove(&entity.id);
@@ -2954,6 +2990,7 @@ impl GameplayScene {
                                 if needs_rebuild
                                     && !self.rebuild_settler_path_from_world_targets(
                                         entity.id,
+                                        entity.floor,
                                         entity.transform.position,
                                         target_world,
                                         tilemap_epoch,
@@ -3061,12 +3098,9 @@ impl GameplayScene {
                 } else {
                     ActionState::Interact
                 }
-            } else if self
-                .hit_ticks_remaining_by_actor
-                .get(&actor_id)
-                .copied()
-                .unwrap_or(0)
-                > 0
+            } else if world
+                .find_owner_timer(actor_id, HIT_VISUAL_TIMER_TAG)
+                .is_some()
             {
                 ActionState::Hit
             } else if self.actor_is_carrying(actor_id) {
@@ -3096,10 +3130,15 @@ impl GameplayScene {
             );
         }
 
-        self.tick_hit_timers();
-
         let camera_delta = camera_delta(input, fixed_dt_seconds, CAMERA_SPEED_UNITS_PER_SECOND);
         world.camera_mut().position.x += camera_delta.x;
         world.camera_mut().position.y += camera_delta.y;
+        if camera_delta.x != 0.0 || camera_delta.y != 0.0 {
+            world.set_camera_follow(None);
+        } else if self.selected_entity != self.camera_follow_selection {
+            world.set_camera_follow(self.selected_entity);
+        }
+        self.camera_follow_selection = self.selected_entity;
+        world.tick_camera(fixed_dt_seconds, input.window_size());
     }
 }
@@ -0,0 +1,203 @@
+/// Distance from a stair entity at which a routed actor takes the stair.
+const STAIR_USE_RADIUS: f32 = 0.75;
+
+/// Cross-floor move in progress: the actor walks to `stair_id`, transits, and repeats until it
+/// stands on `goal_floor`, then moves to `goal_point`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FloorRoute {
+    goal_floor: FloorId,
+    goal_point: Vec2,
+    stair_id: EntityId,
+}
+
+/// First stair to take from `from` on a fewest-hops route to `to`.
+///
+/// Floors are searched breadth-first and stairs are visited in world order, so equal-length
+/// routes resolve deterministically.
+fn next_stair_toward(world: &SceneWorld, from: FloorId, to: FloorId) -> Option<EntityId> {
+    let mut first_stair_by_floor = HashMap::<FloorId, EntityId>::new();
+    let mut visited = HashSet::from([from]);
+    let mut frontier = VecDeque::from([from]);
+    while let Some(floor) = frontier.pop_front() {
+        for entity in world.entities() {
+            let Some(link) = entity.stair else {
+                continue;
+            };
+            if entity.floor != floor || !visited.insert(link.to_floor) {
+                continue;
+            }
+            let first_stair = first_stair_by_floor
+                .get(&floor)
+                .copied()
+                .unwrap_or(entity.id);
+            if link.to_floor == to {
+                return Some(first_stair);
+            }
+            first_stair_by_floor.insert(link.to_floor, first_stair);
+            frontier.push_back(link.to_floor);
+        }
+    }
+    None
+}
+
+impl GameplayScene {
+    fn switch_active_floor(&mut self, floor: FloorId, world: &mut SceneWorld) {
+        self.active_floor = floor;
+        world.set_active_floor(floor);
+        world.set_hovered_interactable_visual(None);
+        world.set_selected_actor_visual(None);
+        world.set_targeted_interactable_visual(None);
+        world.clear_debug_markers();
+    }
+
+    /// Same-floor move through the actor's usual locomotion path (settler job or direct order).
+    fn queue_move_for_actor(
+        &mut self,
+        actor_id: EntityId,
+        point: Vec2,
+        world: &mut SceneWorld,
+    ) -> bool {
+        if self.actor_uses_settler_navigation(actor_id) {
+            self.assign_job_to_actor_with_interruption(
+                actor_id,
+                JobKind::MoveToPoint,
+                JobTarget::WorldPoint(point),
+                world,
+            )
+            .is_some()
+        } else {
+            self.issue_move_order_with_optional_settler_path(actor_id, point, world)
+        }
+    }
+
+    /// Orders `actor_id` to `goal_point` on `goal_floor`, routing over stairs when the goal is
+    /// on another floor. Returns false when no stair route reaches `goal_floor`.
+    fn issue_floor_aware_move(
+        &mut self,
+        actor_id: EntityId,
+        goal_floor: FloorId,
+        goal_point: Vec2,
+        world: &mut SceneWorld,
+    ) -> bool {
+        self.floor_route_by_entity.remove(&actor_id);
+        let Some(actor_floor) = world.find_entity(actor_id).map(|actor| actor.floor) else {
+            return false;
+        };
+        if actor_floor == goal_floor {
+            return self.queue_move_for_actor(actor_id, goal_point, world);
+        }
+        self.route_actor_to_next_stair(actor_id, actor_floor, goal_floor, goal_point, world)
+    }
+
+    fn route_actor_to_next_stair(
+        &mut self,
+        actor_id: EntityId,
+        actor_floor: FloorId,
+        goal_floor: FloorId,
+        goal_point: Vec2,
+        world: &mut SceneWorld,
+    ) -> bool {
+        let Some(stair_id) = next_stair_toward(world, actor_floor, goal_floor) else {
+            return false;
+        };
+        let Some(stair_point) = world
+            .find_entity(stair_id)
+            .map(|stair| stair.transform.position)
+        else {
+            return false;
+        };
+        if !self.queue_move_for_actor(actor_id, stair_point, world) {
+            return false;
+        }
+        self.floor_route_by_entity.insert(
+            actor_id,
+            FloorRoute {
+                goal_floor,
+                goal_point,
+                stair_id,
+            },
+        );
+        true
+    }
+
+    /// Moves routed actors that reached their stair onto the linked floor and issues the next
+    /// leg. When the selected actor changes floor, the active floor follows it.
+    fn run_floor_route_pass(&mut self, world: &mut SceneWorld) {
+        if self.floor_route_by_entity.is_empty() {
+            return;
+        }
+        let mut actor_ids = self
+            .floor_route_by_entity
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        self.sort_entities_stable(&mut actor_ids);
+
+        for actor_id in actor_ids {
+            let Some(route) = self.floor_route_by_entity.get(&actor_id).copied() else {
+                continue;
+            };
+            let Some(actor) = world.find_entity(actor_id) else {
+                self.floor_route_by_entity.remove(&actor_id);
+                continue;
+            };
+            let (actor_floor, actor_position, actor_idle) = (
+                actor.floor,
+                actor.transform.position,
+                matches!(actor.order_state, OrderState::Idle),
+            );
+            let stair = world.find_entity(route.stair_id).and_then(|stair| {
+                (stair.floor == actor_floor).then_some((stair.transform.position, stair.stair?))
+            });
+            let Some((stair_position, link)) = stair else {
+                self.floor_route_by_entity.remove(&actor_id);
+                continue;
+            };
+
+            let dx = stair_position.x - actor_position.x;
+            let dy = stair_position.y - actor_position.y;
+            if dx * dx + dy * dy > STAIR_USE_RADIUS * STAIR_USE_RADIUS {
+                let stalled = actor_idle && self.job_board.assigned_job_id(actor_id).is_none();
+                if stalled {
+                    self.floor_route_by_entity.remove(&actor_id);
+                }
+                continue;
+            }
+
+            self.floor_route_by_entity.remove(&actor_id);
+            self.finalize_assigned_job_and_clear_locomotion(
+                actor_id,
+                JobState::Completed,
+                world,
+                true,
+            );
+            if let Some(actor) = world.find_entity_mut(actor_id) {
+                actor.floor = link.to_floor;
+                actor.transform.position = link.to_position;
+            }
+            if self.selected_entity == Some(actor_id) {
+                self.switch_active_floor(link.to_floor, world);
+            }
+
+            let continued = if link.to_floor == route.goal_floor {
+                self.queue_move_for_actor(actor_id, route.goal_point, world)
+            } else {
+                self.route_actor_to_next_stair(
+                    actor_id,
+                    link.to_floor,
+                    route.goal_floor,
+                    route.goal_point,
+                    world,
+                )
+            };
+            if !continued {
+                debug!(
+                    actor = actor_id.0,
+                    floor = link.to_floor.0,
+                    "floor_route_abandoned"
+                );
+            }
+        }
+        self.sanitize_settler_multi_selection(world);
+    }
+}
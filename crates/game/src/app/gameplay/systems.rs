@@ -9,7 +9,6 @@ enum GameplaySystemId {
 }
 
 impl GameplaySystemId {
-    #[cfg(test)]
     fn name(self) -> &'static str {
         match self {
             Self::InputIntent => "InputIntent",
@@ -52,7 +51,7 @@ struct GameplaySystemContext<'a> {
     intents: &'a mut GameplayIntentQueue,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct GameplaySystemsHost {
     last_tick_order: Vec<GameplaySystemId>,
 }
@@ -102,6 +101,7 @@ impl GameplaySystemsHost {
                 events,
                 intents,
             };
+            let _zone = profile_zone(system_id.name());
             self.run_system(system_id, &mut context);
         }
     }
@@ -315,9 +315,6 @@ impl GameplaySystemsHost {
                 continue;
             }
 
-            agent.cooldown_remaining_seconds =
-                (agent.cooldown_remaining_seconds - context.fixed_dt_seconds).max(0.0);
-
             let has_runtime_interaction =
                 context.active_interactions_by_actor.contains_key(&actor_id);
             let has_world_interaction = Self::order_state_indicates_interaction(actor.order_state);
@@ -335,7 +332,10 @@ impl GameplaySystemsHost {
                 if in_aggro {
                     if in_attack_range {
                         agent.state = AiState::UseInteraction;
-                        if !movement_blocked && agent.cooldown_remaining_seconds <= 0.0 {
+                        let cooling_down = context
+                            .world_view
+                            .has_owner_timer(actor_id, AI_ATTACK_COOLDOWN_TIMER_TAG);
+                        if !movement_blocked && !cooling_down {
                             let interaction_id =
                                 Self::alloc_interaction_id(context.next_interaction_id);
                             context.active_interactions_by_actor.insert(
@@ -358,7 +358,11 @@ impl GameplaySystemsHost {
                                 actor_id,
                                 target_id: player_entity.id,
                             });
-                            agent.cooldown_remaining_seconds = agent.cooldown_seconds;
+                            context.intents.enqueue(GameplayIntent::StartAttackCooldownTimer {
+                                actor_id,
+                                ticks: (agent.cooldown_seconds / context.fixed_dt_seconds).ceil()
+                                    as u32,
+                            });
                         }
                     } else {
                         agent.state = AiState::Chase;
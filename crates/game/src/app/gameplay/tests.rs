@@ -1,4 +1,5 @@
     use super::*;
+    use engine::RenderableKind;
     use serde_json::json;
 
     fn snapshot_from_actions(actions: &[InputAction]) -> InputSnapshot {
@@ -59,51 +60,75 @@
     }
 
     fn sample_save_game(scene_key: SavedSceneKey) -> SaveGame {
+        let legacy_floor = |name: &str| engine::SnapshotFloor {
+            name: name.to_string(),
+            tilemap: None,
+            ambient_rgb: engine::AMBIENT_UNLIT,
+        };
+        let sample_entity = |id: u64, x: f32, y: f32| engine::SnapshotEntity {
+            id,
+            position: engine::SnapshotVec2 { x, y },
+            rotation_radians: None,
+            renderable: engine::SnapshotRenderableKind::Placeholder,
+            floor: FloorId::MAIN.0,
+            selectable: false,
+            actor: false,
+            order_state: SnapshotOrderState::Idle,
+            interactable: None,
+            stair: None,
+            render_layer: engine::SnapshotRenderLayer::Standing,
+            sort_pivot_y: 0.0,
+            sight: None,
+        };
+        let mut actor = sample_entity(1, 1.0, 2.0);
+        actor.selectable = true;
+        actor.actor = true;
+        actor.order_state = SnapshotOrderState::Working {
+            target_save_id: 20,
+            remaining_time: 1.5,
+        };
+        let mut pile = sample_entity(2, 5.0, 6.0);
+        pile.interactable = Some(engine::SnapshotInteractable {
+            kind: engine::SnapshotInteractableKind::ResourcePile,
+            interaction_radius: 0.75,
+            remaining_uses: 2,
+        });
         SaveGame {
             save_version: SAVE_VERSION,
             scene_key,
-            active_floor: None,
-            camera_position: SavedVec2 { x: 3.0, y: -1.0 },
-            camera_zoom: 1.4,
+            world: WorldSnapshot {
+                version: engine::WORLD_SNAPSHOT_VERSION,
+                floors: LEGACY_FLOOR_NAMES.iter().map(|name| legacy_floor(name)).collect(),
+                default_floor: FloorId::MAIN.0,
+                active_floor: FloorId::MAIN.0,
+                camera: engine::SnapshotCamera {
+                    position: engine::SnapshotVec2 { x: 3.0, y: -1.0 },
+                    zoom: 1.4,
+                },
+                entities: vec![actor, pile],
+                visual_state: engine::SnapshotVisualState::default(),
+                debug_markers: Vec::new(),
+                timers: Vec::new(),
+                lights: Vec::new(),
+                fog_viewer: None,
+                visibility: Vec::new(),
+            },
             selected_entity_save_id: Some(10),
             player_entity_save_id: Some(10),
             next_save_id: 21,
             resource_count: 2,
             entities: vec![
                 SavedEntityRuntime {
+                    entity_id: 1,
                     save_id: 10,
-                    position: SavedVec2 { x: 1.0, y: 2.0 },
-                    rotation_radians: None,
-                    floor: None,
-                    selectable: true,
-                    actor: true,
                     archetype_def_name: None,
-                    move_target_world: Some(SavedVec2 { x: 4.0, y: 2.0 }),
-                    interaction_target_save_id: Some(20),
-                    job_state: SavedJobState::Working {
-                        target_save_id: 20,
-                        remaining_time: 1.5,
-                    },
                     carry_visual_def: None,
-                    interactable: None,
                 },
                 SavedEntityRuntime {
+                    entity_id: 2,
                     save_id: 20,
-                    position: SavedVec2 { x: 5.0, y: 6.0 },
-                    rotation_radians: None,
-                    floor: None,
-                    selectable: false,
-                    actor: false,
                     archetype_def_name: None,
-                    move_target_world: None,
-                    interaction_target_save_id: None,
-                    job_state: SavedJobState::Idle,
                     carry_visual_def: None,
-                    interactable: Some(SavedInteractableRuntime {
-                        kind: SavedInteractableKind::ResourcePile,
-                        interaction_radius: 0.75,
-                        remaining_uses: 2,
-                    }),
                 },
             ],
         }
@@ -1087,6 +1112,134 @@
         assert_eq!(stats.invalid_target_count, 1);
     }
 
+    #[test]
+    fn status_widgets_cover_damage_timed_interactions_and_carried_items() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        let spawn = |world: &mut SceneWorld, debug_name: &'static str| {
+            world.spawn_actor(
+                Transform {
+                    position: Vec2 { x: 0.0, y: 0.0 },
+                    rotation_radians: None,
+                },
+                RenderableDesc {
+                    kind: engine::RenderableKind::Placeholder,
+                    debug_name,
+                },
+            )
+        };
+        let hurt_id = spawn(&mut world, "hurt");
+        let worker_id = spawn(&mut world, "worker");
+        world.apply_pending();
+        scene.health_by_entity.clear();
+        scene.health_by_entity.insert(
+            hurt_id,
+            Health {
+                current: 25,
+                max: DEFAULT_MAX_HEALTH,
+            },
+        );
+        scene.health_by_entity.insert(
+            worker_id,
+            Health {
+                current: DEFAULT_MAX_HEALTH,
+                max: DEFAULT_MAX_HEALTH,
+            },
+        );
+        scene.active_interactions_by_actor.insert(
+            worker_id,
+            ActiveInteraction {
+                actor_id: worker_id,
+                target_id: hurt_id,
+                interaction_id: InteractionId(1),
+                kind: ActiveInteractionKind::Use,
+                interaction_range: RESOURCE_PILE_INTERACTION_RADIUS,
+                duration_seconds: 2.0,
+                remaining_seconds: Some(0.5),
+            },
+        );
+        scene
+            .carry_visual_by_actor
+            .insert(worker_id, VISUAL_SANDBOX_CARRY_VISUAL_DEF.to_string());
+
+        let mut widgets = WorldWidgets::default();
+        scene.submit_world_widgets(&world, &mut widgets);
+        let widgets = widgets.as_slice();
+        assert_eq!(widgets.len(), 3);
+        assert_eq!(widgets[0].anchor, WidgetAnchor::Entity(hurt_id));
+        assert!(matches!(
+            widgets[0].kind,
+            engine::WorldWidgetKind::Bar { fraction, fill_rgba, .. }
+                if fraction == 0.25 && fill_rgba == WIDGET_HEALTH_LOW_COLOR
+        ));
+        assert_eq!(widgets[1].anchor, WidgetAnchor::Entity(worker_id));
+        assert!(matches!(
+            widgets[1].kind,
+            engine::WorldWidgetKind::ProgressArc { fraction, .. } if fraction == 0.75
+        ));
+        assert_eq!(
+            widgets[2].kind,
+            engine::WorldWidgetKind::Label {
+                text: "visual_carry_item".to_string(),
+                rgba: WIDGET_CARRY_LABEL_COLOR,
+            }
+        );
+
+        scene.apply_cvar(STATUS_WIDGETS_CVAR, &CvarValue::Bool(false));
+        let mut hidden = WorldWidgets::default();
+        scene.submit_world_widgets(&world, &mut hidden);
+        assert!(hidden.as_slice().is_empty());
+    }
+
+    #[test]
+    fn minimap_markers_follow_pawn_roles_and_interactables() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+        let player_id =
+            spawn_authoritative_player_via_console(&mut scene, &mut world, Vec2 { x: 0.5, y: 0.5 });
+        let settler_id =
+            spawn_def_via_console(&mut scene, &mut world, "proto.settler", Vec2 { x: 2.5, y: 0.5 });
+        let npc_id =
+            spawn_def_via_console(&mut scene, &mut world, "proto.npc_dummy", Vec2 { x: 4.5, y: 0.5 });
+        let pile_id = spawn_interactable_pile(&mut world, Vec2 { x: 6.5, y: 0.5 }, 3);
+        let scenery_id = world.spawn(
+            Transform {
+                position: Vec2 { x: 8.5, y: 0.5 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: engine::RenderableKind::Placeholder,
+                debug_name: "scenery",
+            },
+        );
+        world.apply_pending();
+
+        let mut markers = MinimapMarkers::default();
+        scene.submit_minimap_markers(&world, &mut markers);
+        let role_of = |id: EntityId| {
+            markers
+                .as_slice()
+                .iter()
+                .find(|marker| marker.entity == id)
+                .map(|marker| marker.role)
+        };
+        assert_eq!(role_of(player_id), Some(MinimapRole::Player));
+        assert_eq!(role_of(settler_id), Some(MinimapRole::Settler));
+        assert_eq!(role_of(npc_id), Some(MinimapRole::Npc));
+        assert_eq!(role_of(pile_id), Some(MinimapRole::Interactable));
+        assert_eq!(role_of(scenery_id), None);
+
+        scene.apply_cvar(MINIMAP_MARKERS_CVAR, &CvarValue::Bool(false));
+        let mut hidden = MinimapMarkers::default();
+        scene.submit_minimap_markers(&world, &mut hidden);
+        assert!(hidden.as_slice().is_empty());
+    }
+
     #[test]
     fn apply_damage_reduces_health_and_zero_triggers_died_and_same_tick_despawn() {
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
@@ -1143,6 +1296,50 @@
         assert_eq!(died_events, 1);
     }
 
+    #[test]
+    fn damage_starts_archetype_particle_bursts_that_expire_on_the_fixed_tick() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+        let npc = spawn_def_via_console(&mut scene, &mut world, "proto.npc_dummy", Vec2 { x: 2.0, y: 1.0 });
+        assert!(world.particles().is_empty());
+
+        scene.apply_gameplay_intents_at_safe_point(
+            vec![GameplayIntent::ApplyDamage {
+                entity_id: npc,
+                amount: 5,
+            }],
+            &mut world,
+        );
+        let def_db = world.def_database().expect("def db");
+        let archetype = def_db
+            .entity_def(
+                def_db
+                    .entity_def_id_by_name("proto.npc_dummy")
+                    .expect("npc_dummy id"),
+            )
+            .expect("npc_dummy def");
+        let expected_burst = archetype
+            .particle_emitters
+            .iter()
+            .filter(|emitter| emitter.on == PARTICLE_TRIGGER_DAMAGED)
+            .map(|emitter| usize::from(emitter.burst))
+            .sum::<usize>();
+        assert!(expected_burst > 0);
+        assert_eq!(world.particles().len(), expected_burst);
+        assert!(world
+            .particles()
+            .iter()
+            .all(|particle| particle.position == Vec2 { x: 2.0, y: 1.0 }));
+
+        for _ in 0..120 {
+            scene.update(1.0 / 60.0, &InputSnapshot::empty(), &mut world);
+        }
+        assert!(world.particles().is_empty());
+    }
+
     #[test]
     fn apply_damage_to_entity_without_health_is_ignored() {
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
@@ -1598,6 +1795,7 @@
             .expect("player health")
             .current;
         assert!(after < before);
+        assert!(world.camera_trauma() > 0.0);
     }
 
     #[test]
@@ -1790,6 +1988,125 @@
         assert!(!scene.ai_agents_by_entity.contains_key(&dummy_id));
     }
 
+    fn scenario_state_hash_stream(scenario_id: &str, ticks: usize) -> Vec<u64> {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+        let result = scene.execute_debug_command(
+            SceneDebugCommand::ScenarioSetup {
+                scenario_id: scenario_id.to_string(),
+            },
+            SceneDebugContext::default(),
+            &mut world,
+        );
+        assert!(
+            matches!(result, SceneDebugCommandResult::Success(_)),
+            "{scenario_id}: {result:?}"
+        );
+        world.apply_pending();
+
+        let mut hashes = Vec::with_capacity(ticks);
+        for tick in 0..ticks {
+            let input = if tick < ticks / 4 {
+                snapshot_from_actions(&[InputAction::MoveRight])
+            } else {
+                InputSnapshot::empty()
+            };
+            scene.update(1.0 / 60.0, &input, &mut world);
+            world.apply_pending();
+            hashes.push(world.state_hash(&scene));
+        }
+        hashes
+    }
+
+    #[test]
+    fn debug_event_hook_reports_last_tick_events_by_name() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+        let result = scene.execute_debug_command(
+            SceneDebugCommand::ScenarioSetup {
+                scenario_id: "combat_chaser".to_string(),
+            },
+            SceneDebugContext::default(),
+            &mut world,
+        );
+        assert!(matches!(result, SceneDebugCommandResult::Success(_)));
+        world.apply_pending();
+
+        assert_eq!(scene.debug_event_fired_last_tick("not_an_event"), None);
+        assert_eq!(scene.debug_event_fired_last_tick("entity_damaged"), Some(false));
+        let fired_at = (0..600).find(|_| {
+            scene.update(1.0 / 60.0, &InputSnapshot::empty(), &mut world);
+            world.apply_pending();
+            scene.debug_event_fired_last_tick("entity_damaged") == Some(true)
+        });
+        assert!(fired_at.is_some(), "combat_chaser never dealt damage");
+    }
+
+    #[test]
+    fn same_scenario_run_twice_produces_identical_state_hash_streams() {
+        for scenario_id in ["combat_chaser", "nav_sandbox", "visual_sandbox"] {
+            let first = scenario_state_hash_stream(scenario_id, 240);
+            let second = scenario_state_hash_stream(scenario_id, 240);
+            assert_eq!(first.len(), 240);
+            if let Some(tick) = first.iter().zip(&second).position(|(a, b)| a != b) {
+                panic!("{scenario_id}: state hash diverged at tick {tick}");
+            }
+            let distinct = first.iter().collect::<HashSet<_>>();
+            assert_eq!(distinct.len(), first.len(), "{scenario_id}: every tick advances");
+        }
+    }
+
+    #[test]
+    fn rewind_state_restores_and_resimulates_identical_hashes() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+        let result = scene.execute_debug_command(
+            SceneDebugCommand::ScenarioSetup {
+                scenario_id: "combat_chaser".to_string(),
+            },
+            SceneDebugContext::default(),
+            &mut world,
+        );
+        assert!(matches!(result, SceneDebugCommandResult::Success(_)));
+        world.apply_pending();
+
+        let input_for = |tick: usize| {
+            if tick % 7 < 3 {
+                snapshot_from_actions(&[InputAction::MoveRight])
+            } else {
+                InputSnapshot::empty()
+            }
+        };
+        let mut saved = None;
+        let mut hashes = Vec::new();
+        for tick in 0..180 {
+            if tick == 90 {
+                saved = Some((world.clone(), scene.capture_rewind_state().expect("state")));
+            }
+            scene.update(1.0 / 60.0, &input_for(tick), &mut world);
+            world.apply_pending();
+            hashes.push(world.state_hash(&scene));
+        }
+
+        let (saved_world, saved_scene) = saved.expect("snapshot");
+        world = saved_world;
+        scene.restore_rewind_state(saved_scene.as_ref());
+        for (tick, expected) in hashes.iter().enumerate().skip(90) {
+            scene.update(1.0 / 60.0, &input_for(tick), &mut world);
+            world.apply_pending();
+            assert_eq!(world.state_hash(&scene), *expected, "diverged at tick {tick}");
+        }
+    }
+
     #[test]
     fn scenario_setup_combat_chaser_wires_distinct_sprite_keys() {
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
@@ -2050,7 +2367,7 @@
         scene.selected_entity = Some(actor);
 
         let result = scene.execute_debug_command(
-            SceneDebugCommand::OrderMove { x: 3.0, y: -2.5 },
+            SceneDebugCommand::OrderMove { x: 3.0, y: -2.5, floor: None },
             SceneDebugContext::default(),
             &mut world,
         );
@@ -2077,7 +2394,7 @@
         world.apply_pending();
 
         let no_selection = scene.execute_debug_command(
-            SceneDebugCommand::OrderMove { x: 1.0, y: 1.0 },
+            SceneDebugCommand::OrderMove { x: 1.0, y: 1.0, floor: None },
             SceneDebugContext::default(),
             &mut world,
         );
@@ -2097,7 +2414,7 @@
         scene.selected_entity = Some(non_actor);
 
         let non_actor_result = scene.execute_debug_command(
-            SceneDebugCommand::OrderMove { x: 1.0, y: 1.0 },
+            SceneDebugCommand::OrderMove { x: 1.0, y: 1.0, floor: None },
             SceneDebugContext::default(),
             &mut world,
         );
@@ -2120,7 +2437,7 @@
         scene.selected_entity = Some(settler_id);
 
         let result = scene.execute_debug_command(
-            SceneDebugCommand::OrderMove { x: 3.0, y: -2.5 },
+            SceneDebugCommand::OrderMove { x: 3.0, y: -2.5, floor: None },
             SceneDebugContext::default(),
             &mut world,
         );
@@ -2255,20 +2572,26 @@
             JobTarget::WorldPoint(Vec2 { x: 1.0, y: 0.0 }),
             0,
         );
-        assert!(scene
-            .job_board
-            .assign_job_to_entity_at_tick(job_id, settler, scene.current_tick));
-        let reserved_tick = scene
-            .job_board
-            .job(job_id)
-            .and_then(|job| job.reserved_tick)
-            .expect("reserved tick");
+        assert!(scene.reserve_job_for_actor(job_id, settler, &mut world));
+        let timer = world
+            .find_owner_timer(settler, JOB_RESERVATION_TIMER_TAG)
+            .expect("reservation timer");
+        assert_eq!(
+            world.timer_remaining_ticks(timer),
+            JOB_RESERVATION_TIMEOUT_TICKS
+        );
         scene.job_phase_by_entity.insert(settler, JobPhase::Navigating);
         world.find_entity_mut(settler).expect("settler").order_state = OrderState::MoveTo {
             point: Vec2 { x: 1.0, y: 0.0 },
         };
 
-        scene.current_tick = reserved_tick.saturating_add(JOB_RESERVATION_TIMEOUT_TICKS);
+        for _ in 1..JOB_RESERVATION_TIMEOUT_TICKS {
+            world.tick_timers();
+        }
+        scene.run_job_reservation_timeout_release_pass(&mut world);
+        assert_eq!(scene.job_board.assigned_job_id(settler), Some(job_id));
+
+        world.tick_timers();
         scene.run_job_reservation_timeout_release_pass(&mut world);
         assert_eq!(scene.job_board.assigned_job_id(settler), None);
         let released = scene.job_board.job(job_id).expect("job record");
@@ -2956,6 +3279,45 @@
         assert!((magnitude - 6.0).abs() < 0.0001);
     }
 
+    #[test]
+    fn selecting_an_entity_makes_the_camera_follow_until_panned() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+
+        let selectable = world.spawn_selectable(
+            Transform {
+                position: Vec2 { x: 4.0, y: -3.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "follow_target",
+            },
+        );
+        world.apply_pending();
+        scene.selected_entity = Some(selectable);
+
+        for _ in 0..60 {
+            scene.update(0.1, &InputSnapshot::empty(), &mut world);
+            world.apply_pending();
+        }
+        assert_eq!(world.camera_follow(), Some(selectable));
+        let camera = world.camera().position;
+        assert!((camera.x - 4.0).abs() < 0.01 && (camera.y + 3.0).abs() < 0.01);
+
+        let pan = snapshot_from_actions(&[InputAction::CameraRight]);
+        scene.update(0.1, &pan, &mut world);
+        world.apply_pending();
+        assert_eq!(world.camera_follow(), None);
+        assert_eq!(scene.selected_entity, Some(selectable));
+
+        scene.update(0.1, &InputSnapshot::empty(), &mut world);
+        assert_eq!(world.camera_follow(), None);
+    }
+
     #[test]
     fn left_click_selects_entity_under_cursor() {
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
@@ -3385,10 +3747,16 @@
             let tile = tilemap
                 .tile_at(NAV_SANDBOX_BLOCKED_STRIP_X, y)
                 .expect("blocked strip tile");
+            let blocks_movement = tilemap
+                .flags_at(NAV_SANDBOX_BLOCKED_STRIP_X, y)
+                .expect("blocked strip flags")
+                .contains(TileFlags::BLOCKS_MOVEMENT);
             if y == NAV_SANDBOX_BLOCKED_GAP_Y {
-                assert_ne!(tile, NAV_BLOCKED_TILE_ID);
+                assert_ne!(tile, WALL_TILE_ID);
+                assert!(!blocks_movement);
             } else {
-                assert_eq!(tile, NAV_BLOCKED_TILE_ID);
+                assert_eq!(tile, WALL_TILE_ID);
+                assert!(blocks_movement);
             }
         }
 
@@ -3429,6 +3797,97 @@
         );
     }
 
+    #[test]
+    fn basement_is_dark_and_the_sandbox_workbench_carries_its_def_light() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+        assert_eq!(
+            world.floor_ambient(FloorId::BASEMENT),
+            BASEMENT_AMBIENT_RGB
+        );
+        assert_eq!(world.floor_ambient(FloorId::MAIN), engine::AMBIENT_UNLIT);
+
+        let setup_result = scene.execute_debug_command(
+            SceneDebugCommand::ScenarioSetup {
+                scenario_id: "visual_sandbox".to_string(),
+            },
+            SceneDebugContext::default(),
+            &mut world,
+        );
+        assert!(matches!(setup_result, SceneDebugCommandResult::Success(_)));
+        let workbench_id = world
+            .entities()
+            .iter()
+            .find(|entity| {
+                archetype_def_name_for_entity(&scene, &world, entity.id)
+                    == VISUAL_SANDBOX_EXTRA_INTERACTABLE_DEF
+            })
+            .map(|entity| entity.id)
+            .expect("sandbox workbench");
+        let anchors = world
+            .lights()
+            .map(|(_, light)| light.anchor)
+            .collect::<Vec<_>>();
+        assert_eq!(anchors, vec![LightAnchor::Entity(workbench_id)]);
+
+        let save = scene.build_save_game(&world).expect("save");
+        assert_eq!(save.world.floors[FloorId::BASEMENT.index()].ambient_rgb, BASEMENT_AMBIENT_RGB);
+        world.set_floor_ambient(FloorId::BASEMENT, engine::AMBIENT_UNLIT);
+        scene.apply_save_game(save, &mut world).expect("load");
+        world.apply_pending();
+        assert_eq!(
+            world.floor_ambient(FloorId::BASEMENT),
+            BASEMENT_AMBIENT_RGB
+        );
+        assert_eq!(world.lights().count(), 1);
+    }
+
+    #[test]
+    fn player_sight_reveals_colony_fog_and_fog_survives_save_load() {
+        let mut scene = GameplayScene::new("A", SceneKey::A, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+        let player_id =
+            spawn_authoritative_player_via_console(&mut scene, &mut world, Vec2 { x: 0.5, y: 0.5 });
+        assert_eq!(world.fog_viewer(), Some(FACTION_COLONY));
+        assert_eq!(
+            world.entity_sight(player_id).map(|sight| sight.faction),
+            Some(FACTION_COLONY)
+        );
+
+        world.update_visibility();
+        let player = world.find_entity(player_id).expect("player entity");
+        assert_eq!(
+            world.viewer_visibility_at(player.floor, player.transform.position),
+            engine::TileVisibility::Visible
+        );
+        let grids = world
+            .visibility_grids()
+            .map(|(faction, floor, width, height, cells)| {
+                (faction, floor, width, height, cells.to_vec())
+            })
+            .collect::<Vec<_>>();
+        assert!(!grids.is_empty());
+
+        let save = scene.build_save_game(&world).expect("save");
+        assert_eq!(save.world.visibility.len(), grids.len());
+        scene.apply_save_game(save, &mut world).expect("load");
+        world.apply_pending();
+        let restored_player = scene.player_id.expect("restored player");
+        assert!(world.entity_sight(restored_player).is_some());
+        assert!(world
+            .visibility_grids()
+            .map(|(faction, floor, width, height, cells)| {
+                (faction, floor, width, height, cells.to_vec())
+            })
+            .eq(grids));
+    }
+
     #[test]
     fn visual_sandbox_forces_demo_action_states_deterministically() {
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
@@ -3627,7 +4086,7 @@
         scene.selected_entity = Some(npc);
 
         let result = scene.execute_debug_command(
-            SceneDebugCommand::OrderMove { x: 1.0, y: 1.0 },
+            SceneDebugCommand::OrderMove { x: 1.0, y: 1.0, floor: None },
             SceneDebugContext::default(),
             &mut world,
         );
@@ -3643,7 +4102,7 @@
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
         let mut world = SceneWorld::default();
 
-        world.set_active_floor(engine::FloorId::Main);
+        world.set_active_floor(engine::FloorId::MAIN);
         let main_id = world.spawn_selectable(
             Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -3654,7 +4113,7 @@
                 debug_name: "main_selectable",
             },
         );
-        world.set_active_floor(engine::FloorId::Basement);
+        world.set_active_floor(engine::FloorId::BASEMENT);
         let basement_id = world.spawn_selectable(
             Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -3673,7 +4132,7 @@
 
         let floor_result = scene.execute_debug_command(
             SceneDebugCommand::FloorSet {
-                floor: engine::FloorId::Basement,
+                floor: "basement".to_string(),
             },
             SceneDebugContext::default(),
             &mut world,
@@ -3692,7 +4151,7 @@
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
         let mut world = SceneWorld::default();
 
-        world.set_active_floor(engine::FloorId::Main);
+        world.set_active_floor(engine::FloorId::MAIN);
         let actor = world.spawn_actor(
             Transform {
                 position: Vec2 { x: 0.0, y: 0.0 },
@@ -3708,9 +4167,9 @@
         scene.player_id = Some(actor);
         scene.selected_entity = Some(actor);
 
-        world.set_active_floor(engine::FloorId::Basement);
+        world.set_active_floor(engine::FloorId::BASEMENT);
         let basement_target = spawn_interactable_pile(&mut world, Vec2 { x: 0.0, y: 0.0 }, 1);
-        world.set_active_floor(engine::FloorId::Main);
+        world.set_active_floor(engine::FloorId::MAIN);
 
         let result = scene.execute_debug_command(
             SceneDebugCommand::OrderInteract {
@@ -3736,7 +4195,7 @@
 
         let floor_result = scene.execute_debug_command(
             SceneDebugCommand::FloorSet {
-                floor: engine::FloorId::Basement,
+                floor: "basement".to_string(),
             },
             SceneDebugContext::default(),
             &mut world,
@@ -3754,10 +4213,95 @@
         );
         assert_eq!(
             world.find_entity(spawned).expect("spawned").floor,
-            engine::FloorId::Basement
+            engine::FloorId::BASEMENT
         );
     }
 
+    #[test]
+    fn cross_floor_order_move_routes_over_stair_and_active_floor_follows_selection() {
+        let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut world = SceneWorld::default();
+        seed_def_database(&mut world);
+        scene.load(&mut world);
+        world.apply_pending();
+
+        let actor = world.spawn_actor(
+            Transform {
+                position: Vec2 { x: 0.0, y: 0.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "actor",
+            },
+        );
+        let stair = world.spawn(
+            Transform {
+                position: Vec2 { x: 1.5, y: 0.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "stair",
+            },
+        );
+        world.apply_pending();
+        world.find_entity_mut(actor).expect("actor").selectable = true;
+        world.find_entity_mut(stair).expect("stair").stair = Some(engine::StairLink {
+            to_floor: engine::FloorId::BASEMENT,
+            to_position: Vec2 { x: -1.0, y: 0.0 },
+        });
+        scene.player_id = Some(actor);
+        scene.selected_entity = Some(actor);
+
+        let no_route = scene.execute_debug_command(
+            SceneDebugCommand::OrderMove {
+                x: 0.0,
+                y: 0.0,
+                floor: Some("rooftop".to_string()),
+            },
+            SceneDebugContext::default(),
+            &mut world,
+        );
+        assert!(matches!(
+            no_route,
+            SceneDebugCommandResult::Error(message) if message.contains("no stair route")
+        ));
+
+        let result = scene.execute_debug_command(
+            SceneDebugCommand::OrderMove {
+                x: -1.0,
+                y: 2.0,
+                floor: Some("2".to_string()),
+            },
+            SceneDebugContext::default(),
+            &mut world,
+        );
+        assert!(matches!(result, SceneDebugCommandResult::Success(_)));
+
+        for _ in 0..120 {
+            scene.update(1.0 / 60.0, &InputSnapshot::empty(), &mut world);
+            world.apply_pending();
+            if world.find_entity(actor).expect("actor").floor == engine::FloorId::BASEMENT {
+                break;
+            }
+        }
+        assert_eq!(
+            world.find_entity(actor).expect("actor").floor,
+            engine::FloorId::BASEMENT
+        );
+        assert_eq!(scene.active_floor, engine::FloorId::BASEMENT);
+        assert_eq!(world.active_floor(), engine::FloorId::BASEMENT);
+        assert!(scene.floor_route_by_entity.is_empty());
+
+        for _ in 0..120 {
+            scene.update(1.0 / 60.0, &InputSnapshot::empty(), &mut world);
+            world.apply_pending();
+        }
+        let position = world.find_entity(actor).expect("actor").transform.position;
+        assert!((position.x + 1.0).abs() < 0.1 && (position.y - 2.0).abs() < 0.1);
+    }
+
     #[test]
     fn step_toward_moves_by_speed_times_dt_without_overshoot() {
         let (next, arrived) = step_toward(
@@ -3821,7 +4365,7 @@
         scene.selected_entity = Some(settler_id);
 
         let order_result = scene.execute_debug_command(
-            SceneDebugCommand::OrderMove { x: 3.0, y: 0.0 },
+            SceneDebugCommand::OrderMove { x: 3.0, y: 0.0, floor: None },
             SceneDebugContext::default(),
             &mut world,
         );
@@ -3905,6 +4449,7 @@
             SceneDebugCommand::OrderMove {
                 x: NAV_SANDBOX_DEFAULT_MOVE_GOAL_WORLD.x,
                 y: NAV_SANDBOX_DEFAULT_MOVE_GOAL_WORLD.y,
+                floor: None,
             },
             SceneDebugContext::default(),
             &mut world,
@@ -3960,6 +4505,7 @@
             SceneDebugCommand::OrderMove {
                 x: NAV_SANDBOX_DEFAULT_MOVE_GOAL_WORLD.x,
                 y: NAV_SANDBOX_DEFAULT_MOVE_GOAL_WORLD.y,
+                floor: None,
             },
             SceneDebugContext::default(),
             &mut world,
@@ -3982,13 +4528,17 @@
             for x in 0..tilemap.width() {
                 let mut tile_id = tilemap.tile_at(x, y).expect("tile");
                 if x == 0 && y == 0 {
-                    tile_id = NAV_BLOCKED_TILE_ID;
+                    tile_id = WALL_TILE_ID;
                 }
                 mutated_tiles.push(tile_id);
             }
         }
-        let mutated = Tilemap::new(tilemap.width(), tilemap.height(), tilemap.origin(), mutated_tiles)
-            .expect("mutated tilemap");
+        let mutated = wall_tilemap(
+            tilemap.width(),
+            tilemap.height(),
+            tilemap.origin(),
+            mutated_tiles,
+        );
         world.set_tilemap(mutated);
         let mutated_epoch = world.tilemap_epoch();
         assert!(mutated_epoch > initial_epoch);
@@ -4105,11 +4655,9 @@
         let mut tiles = vec![0u16; 25];
         for y in 0..5u32 {
             let index = (y * 5 + 2) as usize;
-            tiles[index] = NAV_BLOCKED_TILE_ID;
+            tiles[index] = WALL_TILE_ID;
         }
-        world.set_tilemap(
-            Tilemap::new(5, 5, Vec2 { x: 0.0, y: 0.0 }, tiles).expect("unreachable tilemap"),
-        );
+        world.set_tilemap(wall_tilemap(5, 5, Vec2 { x: 0.0, y: 0.0 }, tiles));
 
         let settler_id =
             spawn_def_via_console(&mut scene, &mut world, "proto.settler", Vec2 { x: 0.5, y: 2.5 });
@@ -4117,7 +4665,7 @@
         let start_position = world.find_entity(settler_id).expect("settler").transform.position;
 
         let move_result = scene.execute_debug_command(
-            SceneDebugCommand::OrderMove { x: 4.5, y: 2.5 },
+            SceneDebugCommand::OrderMove { x: 4.5, y: 2.5, floor: None },
             SceneDebugContext::default(),
             &mut world,
         );
@@ -4206,8 +4754,9 @@
             OrderState::MoveTo { point } => point,
             _ => panic!("expected move order"),
         };
+        // Zoom is anchored at the cursor, so the point under it stays at the same world position.
         assert!((world.camera().zoom - 1.1).abs() < 0.0001);
-        assert!((target.x - (32.0 / (32.0 * 1.1))).abs() < 0.0001);
+        assert!((target.x - 1.0).abs() < 0.0001);
         assert!(target.y.abs() < 0.0001);
     }
 
@@ -4650,6 +5199,16 @@
             .expect("actor")
             .transform
             .position = Vec2 { x: 0.0, y: 0.0 };
+        // The camera has been following the selected actor, so re-aim the click at the pile.
+        let (pile_x, pile_y) =
+            engine::world_to_screen_px(world.camera(), (1280, 720), Vec2 { x: 0.0, y: 0.0 });
+        let click = right_click_snapshot(
+            Vec2 {
+                x: pile_x as f32,
+                y: pile_y as f32,
+            },
+            (1280, 720),
+        );
         scene.update(1.0 / 60.0, &click, &mut world);
         world.apply_pending();
         let mut saw_completed_event = false;
@@ -5057,6 +5616,10 @@
                     actor_id,
                     ticks: 5,
                 },
+                GameplayIntent::StartAttackCooldownTimer {
+                    actor_id,
+                    ticks: 3,
+                },
             ],
             &mut world,
         );
@@ -5064,6 +5627,11 @@
         assert_eq!(stats.decrement_interactable_uses, 1);
         assert_eq!(stats.increment_resource_count, 1);
         assert_eq!(stats.start_hit_visual_timer, 1);
+        assert_eq!(stats.start_attack_cooldown_timer, 1);
+        let cooldown = world
+            .find_owner_timer(actor_id, AI_ATTACK_COOLDOWN_TIMER_TAG)
+            .expect("cooldown timer");
+        assert_eq!(world.timer_remaining_ticks(cooldown), 3);
         assert_eq!(
             scene.carry_visual_by_actor.get(&actor_id).map(String::as_str),
             Some(VISUAL_SANDBOX_CARRY_VISUAL_DEF)
@@ -5075,7 +5643,12 @@
             .map(|interactable| interactable.remaining_uses)
             .expect("pile uses");
         assert_eq!(uses_after, uses_before - 1);
-        assert_eq!(scene.hit_ticks_remaining_by_actor.get(&actor_id), Some(&5));
+        assert_eq!(
+            world
+                .find_owner_timer(actor_id, HIT_VISUAL_TIMER_TAG)
+                .map(|timer| world.timer_remaining_ticks(timer)),
+            Some(5)
+        );
 
         let clear_stats = scene.apply_gameplay_intents_at_safe_point(
             vec![GameplayIntent::ClearCarryVisual { actor_id }],
@@ -5348,8 +5921,14 @@
         scene.run_gameplay_systems_once(0.1, &InputSnapshot::empty(), &world);
         let use_agent = scene.ai_agents_by_entity.get(&npc_id).expect("agent");
         assert_eq!(use_agent.state, AiState::UseInteraction);
-        assert!(use_agent.cooldown_remaining_seconds > 0.0);
         let use_intents = scene.system_intents.drain_current_tick();
+        assert!(use_intents.iter().any(|intent| {
+            matches!(
+                intent,
+                GameplayIntent::StartAttackCooldownTimer { actor_id, ticks }
+                    if *actor_id == npc_id && *ticks > 0
+            )
+        }));
         assert!(use_intents.iter().any(|intent| {
             matches!(
                 intent,
@@ -5994,13 +6573,13 @@
     #[test]
     fn parse_save_game_json_reports_unknown_enum_tag_path() {
         let mut value = serde_json::to_value(sample_save_game(SavedSceneKey::A)).expect("to_value");
-        value["entities"][0]["job_state"] = json!("Broken");
+        value["scene_key"] = json!("Broken");
         let raw = serde_json::to_string(&value).expect("json");
 
         let error =
             GameplayScene::parse_save_game_json(&raw).expect_err("unknown enum tag should fail");
         assert!(error.contains("parse save json"));
-        assert!(error.contains("entities[0].job_state"));
+        assert!(error.contains("scene_key"));
         assert!(error.contains("unknown variant"));
     }
 
@@ -6085,55 +6664,60 @@
     #[test]
     fn load_validation_rejects_non_finite_camera_zoom() {
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.camera_zoom = f32::NAN;
+        save.world.camera.zoom = f32::NAN;
         assert!(GameplayScene::validate_save_game(&save, SavedSceneKey::A).is_err());
     }
 
     #[test]
     fn validate_reports_field_path_for_dangling_target_refs() {
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].interaction_target_save_id = Some(9999);
+        save.world.entities[0].order_state = SnapshotOrderState::Interact {
+            target_save_id: 9999,
+        };
         let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("dangling target");
-        assert!(error.contains("entities[0].interaction_target_save_id"));
+        assert!(error.contains("world.entities[0].order_state.target_save_id"));
         assert!(error.contains("references unknown save_id 9999"));
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].job_state = SavedJobState::Working {
+        save.world.entities[0].order_state = SnapshotOrderState::Working {
             target_save_id: 9999,
             remaining_time: 1.0,
         };
         let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("dangling job target");
-        assert!(error.contains("entities[0].job_state.target_save_id"));
+        assert!(error.contains("world.entities[0].order_state.target_save_id"));
         assert!(error.contains("references unknown save_id 9999"));
     }
 
     #[test]
     fn validate_reports_field_paths_for_non_finite_and_invalid_numbers() {
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.camera_position.x = f32::NAN;
+        save.world.camera.position.x = f32::NAN;
         let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("non-finite camera x");
-        assert!(error.contains("camera_position.x"));
+        assert!(error.contains("world"));
+        assert!(error.contains("camera.position.x"));
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].position.y = f32::INFINITY;
+        save.world.entities[0].position.y = f32::INFINITY;
         let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("non-finite position y");
         assert!(error.contains("entities[0].position.y"));
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].move_target_world = Some(SavedVec2 {
-            x: f32::NEG_INFINITY,
-            y: 0.0,
-        });
+        save.world.entities[0].order_state = SnapshotOrderState::MoveTo {
+            point: engine::SnapshotVec2 {
+                x: f32::NEG_INFINITY,
+                y: 0.0,
+            },
+        };
         let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("non-finite move target");
-        assert!(error.contains("entities[0].move_target_world.x"));
+        assert!(error.contains("entities[0].order_state.point.x"));
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[1]
+        save.world.entities[1]
             .interactable
             .as_mut()
             .expect("interactable")
@@ -6141,17 +6725,17 @@
         let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("negative interaction radius");
         assert!(error.contains("entities[1].interactable.interaction_radius"));
-        assert!(error.contains("expected >= 0"));
+        assert!(error.contains(">= 0"));
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].job_state = SavedJobState::Working {
+        save.world.entities[0].order_state = SnapshotOrderState::Working {
             target_save_id: 20,
             remaining_time: -0.1,
         };
         let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("negative remaining time");
-        assert!(error.contains("entities[0].job_state.remaining_time"));
-        assert!(error.contains("expected >= 0"));
+        assert!(error.contains("entities[0].order_state.remaining_time"));
+        assert!(error.contains(">= 0"));
     }
 
     #[test]
@@ -6176,16 +6760,24 @@
         let before = capture_scene_restore_state(&scene, &world);
 
         let mut value = serde_json::to_value(sample_save_game(SavedSceneKey::A)).expect("to_value");
-        value["entities"][0]["job_state"] = json!("Broken");
+        value["entities"][0]["save_id"] = json!("Broken");
         let raw = serde_json::to_string(&value).expect("json");
         let parse_error = GameplayScene::parse_save_game_json(&raw).expect_err("parse should fail");
-        assert!(parse_error.contains("entities[0].job_state"));
+        assert!(parse_error.contains("entities[0].save_id"));
+
+        let mut value = serde_json::to_value(sample_save_game(SavedSceneKey::A)).expect("to_value");
+        value["world"]["entities"][0]["order_state"] = json!("Broken");
+        let raw = serde_json::to_string(&value).expect("json");
+        let parse_error = GameplayScene::parse_save_game_json(&raw).expect_err("parse should fail");
+        assert!(parse_error.contains("at world"));
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].interaction_target_save_id = Some(9999);
+        save.world.entities[0].order_state = SnapshotOrderState::Interact {
+            target_save_id: 9999,
+        };
         let validation_error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
             .expect_err("validation should fail");
-        assert!(validation_error.contains("entities[0].interaction_target_save_id"));
+        assert!(validation_error.contains("world.entities[0].order_state.target_save_id"));
 
         let after = capture_scene_restore_state(&scene, &world);
         assert_eq!(after, before);
@@ -6269,14 +6861,14 @@
         seed_def_database(&mut world);
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.active_floor = Some(SavedFloorId::Basement);
-        save.entities[0].floor = Some(SavedFloorId::Main);
-        save.entities[1].floor = Some(SavedFloorId::Rooftop);
+        save.world.active_floor = engine::FloorId::BASEMENT.0;
+        save.world.entities[0].floor = engine::FloorId::MAIN.0;
+        save.world.entities[1].floor = engine::FloorId::ROOFTOP.0;
 
         scene.apply_save_game(save, &mut world).expect("apply");
 
-        assert_eq!(scene.active_floor, ActiveFloor::Basement);
-        assert_eq!(world.active_floor(), engine::FloorId::Basement);
+        assert_eq!(scene.active_floor, engine::FloorId::BASEMENT);
+        assert_eq!(world.active_floor(), engine::FloorId::BASEMENT);
 
         let actor_id = scene
             .save_id_to_entity
@@ -6290,32 +6882,210 @@
             .expect("target save id mapped");
         assert_eq!(
             world.find_entity(actor_id).expect("actor").floor,
-            engine::FloorId::Main
+            engine::FloorId::MAIN
         );
         assert_eq!(
             world.find_entity(target_id).expect("target").floor,
-            engine::FloorId::Rooftop
+            engine::FloorId::ROOFTOP
+        );
+    }
+
+    #[test]
+    fn save_round_trip_preserves_declared_floors_and_stairs() {
+        let mut source_scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut source_world = SceneWorld::default();
+        seed_def_database(&mut source_world);
+        source_scene.load(&mut source_world);
+        source_world.apply_pending();
+        source_world
+            .declare_floors(&["cellar", "ground", "loft", "roof"], engine::FloorId(1))
+            .expect("declare");
+        source_world.clear_tilemap();
+        assert!(source_world.set_floor_tilemap(
+            engine::FloorId(3),
+            build_ground_tilemap(SceneKey::A)
+        ));
+        let stair = source_world.spawn(
+            Transform {
+                position: Vec2 { x: 2.0, y: 0.0 },
+                rotation_radians: None,
+            },
+            RenderableDesc {
+                kind: RenderableKind::Placeholder,
+                debug_name: "stair",
+            },
+        );
+        source_world.apply_pending();
+        source_world.find_entity_mut(stair).expect("stair").stair = Some(engine::StairLink {
+            to_floor: engine::FloorId(3),
+            to_position: Vec2 { x: 4.0, y: 1.0 },
+        });
+
+        let save = source_scene.build_save_game(&source_world).expect("save");
+        assert_eq!(save.world.floors.len(), 4);
+        let raw = serde_json::to_string(&save).expect("serialize");
+        let parsed = GameplayScene::parse_save_game_json(&raw).expect("parse");
+        GameplayScene::validate_save_game(&parsed, SavedSceneKey::A).expect("validate");
+
+        let mut resumed_scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut resumed_world = SceneWorld::default();
+        seed_def_database(&mut resumed_world);
+        resumed_scene.load(&mut resumed_world);
+        resumed_world.apply_pending();
+        resumed_scene
+            .apply_save_game(parsed, &mut resumed_world)
+            .expect("apply");
+
+        assert_eq!(resumed_world.floor_count(), 4);
+        assert_eq!(resumed_world.floor_name(engine::FloorId(2)), Some("loft"));
+        assert_eq!(resumed_world.default_floor(), engine::FloorId(1));
+        assert!(resumed_world.floor_tilemap(engine::FloorId(1)).is_none());
+        assert_eq!(
+            resumed_world.floor_tilemap(engine::FloorId(3)),
+            source_world.floor_tilemap(engine::FloorId(3))
+        );
+        let links = resumed_world
+            .entities()
+            .iter()
+            .filter_map(|entity| entity.stair)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![engine::StairLink {
+                to_floor: engine::FloorId(3),
+                to_position: Vec2 { x: 4.0, y: 1.0 },
+            }]
+        );
+    }
+
+    #[test]
+    fn save_round_trip_preserves_pending_owned_timers() {
+        let mut source_scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut source_world = SceneWorld::default();
+        seed_def_database(&mut source_world);
+        source_scene.load(&mut source_world);
+        source_world.apply_pending();
+        let actor_id = spawn_def_via_console(
+            &mut source_scene,
+            &mut source_world,
+            "proto.settler",
+            Vec2 { x: 0.0, y: 0.0 },
+        );
+        source_scene.apply_gameplay_intents_at_safe_point(
+            vec![GameplayIntent::StartHitVisualTimer { actor_id, ticks: 4 }],
+            &mut source_world,
         );
+        source_world
+            .schedule_repeating_timer(None, "test.pulse", 2, 3)
+            .expect("pulse");
+        source_world.tick_timers();
+
+        let save = source_scene.build_save_game(&source_world).expect("save");
+        let actor_save_id = source_scene.entity_save_ids[&actor_id];
+        let saved_timers = save
+            .world
+            .timers
+            .iter()
+            .map(|timer| {
+                let owner_save_id = timer.owner.map(|owner| {
+                    save.entities
+                        .iter()
+                        .find(|entity| entity.entity_id == owner)
+                        .expect("timer owner saved")
+                        .save_id
+                });
+                (
+                    owner_save_id,
+                    timer.tag.as_str(),
+                    timer.remaining_ticks,
+                    timer.repeat_every_ticks,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            saved_timers,
+            vec![
+                (None, "test.pulse", 1, Some(3)),
+                (Some(actor_save_id), HIT_VISUAL_TIMER_TAG, 3, None),
+            ]
+        );
+        GameplayScene::validate_save_game(&save, SavedSceneKey::A).expect("validate");
+
+        let mut resumed_scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut resumed_world = SceneWorld::default();
+        seed_def_database(&mut resumed_world);
+        resumed_scene.load(&mut resumed_world);
+        resumed_world.apply_pending();
+        resumed_scene
+            .apply_save_game(save, &mut resumed_world)
+            .expect("apply");
+
+        let resumed_actor = resumed_scene.save_id_to_entity[&actor_save_id];
+        let hit_timer = resumed_world
+            .find_owner_timer(resumed_actor, HIT_VISUAL_TIMER_TAG)
+            .expect("hit timer restored");
+        assert_eq!(resumed_world.timer_remaining_ticks(hit_timer), 3);
+        assert_eq!(resumed_world.timers()[0].tag, "test.pulse");
     }
 
     #[test]
-    fn old_save_json_without_optional_floor_and_archetype_fields_still_loads() {
+    fn save_validation_rejects_timer_with_unknown_owner_or_zero_ticks() {
+        let mut save = sample_save_game(SavedSceneKey::A);
+        save.world.timers.push(engine::SnapshotTimer {
+            id: 1,
+            owner: Some(99),
+            tag: "test.timer".to_string(),
+            remaining_ticks: 1,
+            repeat_every_ticks: None,
+        });
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("unknown owner");
+        assert!(error.contains("timers[0].owner"), "{error}");
+
+        save.world.timers[0].owner = Some(1);
+        save.world.timers[0].remaining_ticks = 0;
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("zero ticks");
+        assert!(error.contains("timers[0].remaining_ticks"), "{error}");
+    }
+
+    #[test]
+    fn save_validation_rejects_references_to_undeclared_floors() {
+        let mut save = sample_save_game(SavedSceneKey::A);
+        save.world.entities[0].floor = 3;
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("undeclared entity floor");
+        assert!(error.contains("floor"), "{error}");
+
+        let mut save = sample_save_game(SavedSceneKey::A);
+        save.world.floors.truncate(1);
+        save.world.default_floor = 0;
+        save.world.active_floor = 0;
+        for entity in &mut save.world.entities {
+            entity.floor = 0;
+        }
+        save.world.entities[1].stair = Some(engine::SnapshotStairLink {
+            to_floor: engine::FloorId::MAIN.0,
+            to_position: engine::SnapshotVec2 { x: 0.0, y: 0.0 },
+        });
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("undeclared stair floor");
+        assert!(error.contains("stair"), "{error}");
+    }
+
+    #[test]
+    fn save_json_without_optional_archetype_fields_still_loads() {
         let mut scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
         let mut world = SceneWorld::default();
         seed_def_database(&mut world);
 
         let mut value = serde_json::to_value(sample_save_game(SavedSceneKey::A)).expect("to_value");
-        value
-            .as_object_mut()
-            .expect("save object")
-            .remove("active_floor");
         let entities = value
             .get_mut("entities")
             .and_then(|entities| entities.as_array_mut())
             .expect("entities");
         for entity in entities {
             let object = entity.as_object_mut().expect("entity object");
-            object.remove("floor");
             object.remove("archetype_def_name");
             object.remove("carry_visual_def");
         }
@@ -6325,12 +7095,12 @@
         GameplayScene::validate_save_game(&parsed, SavedSceneKey::A).expect("validate");
         scene.apply_save_game(parsed, &mut world).expect("apply");
 
-        assert_eq!(scene.active_floor, ActiveFloor::Main);
-        assert_eq!(world.active_floor(), engine::FloorId::Main);
+        assert_eq!(scene.active_floor, FloorId::MAIN);
+        assert_eq!(world.active_floor(), engine::FloorId::MAIN);
         assert!(world
             .entities()
             .iter()
-            .all(|entity| entity.floor == engine::FloorId::Main));
+            .all(|entity| entity.floor == engine::FloorId::MAIN));
         assert!(scene.carry_visual_by_actor.is_empty());
     }
 
@@ -6389,6 +7159,50 @@
         );
     }
 
+    #[test]
+    fn edited_tilemap_persists_across_save_and_load() {
+        let mut source_scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut source_world = SceneWorld::default();
+        seed_def_database(&mut source_world);
+        source_scene.load(&mut source_world);
+        source_world.apply_pending();
+        assert!(source_world.set_tile(0, 3, 4, WALL_TILE_ID));
+        assert!(source_world.set_tile_flags(3, 4, TileFlags::BLOCKS_MOVEMENT));
+
+        let save = source_scene.build_save_game(&source_world).expect("save");
+        let raw = serde_json::to_string(&save).expect("serialize");
+        let parsed = GameplayScene::parse_save_game_json(&raw).expect("parse");
+        GameplayScene::validate_save_game(&parsed, SavedSceneKey::A).expect("validate");
+
+        let mut resumed_scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
+        let mut resumed_world = SceneWorld::default();
+        seed_def_database(&mut resumed_world);
+        resumed_scene.load(&mut resumed_world);
+        resumed_world.apply_pending();
+        assert_ne!(resumed_world.tilemap().expect("tilemap").tile_at(3, 4), Some(WALL_TILE_ID));
+        resumed_scene
+            .apply_save_game(parsed, &mut resumed_world)
+            .expect("apply");
+
+        let tilemap = resumed_world.tilemap().expect("tilemap");
+        assert_eq!(tilemap.tile_at(3, 4), Some(WALL_TILE_ID));
+        assert_eq!(tilemap.flags_at(3, 4), Some(TileFlags::BLOCKS_MOVEMENT));
+        assert_eq!(tilemap, source_world.tilemap().expect("source tilemap"));
+    }
+
+    #[test]
+    fn save_validation_rejects_malformed_tilemap() {
+        let mut save = sample_save_game(SavedSceneKey::A);
+        let mut tilemap =
+            engine::SnapshotTilemap::from_tilemap(&build_ground_tilemap(SceneKey::A));
+        tilemap.flags.pop();
+        save.world.floors[1].tilemap = Some(tilemap);
+
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("malformed tilemap");
+        assert!(error.contains("tilemap"), "{error}");
+    }
+
     #[test]
     fn archetype_identity_persists_across_save_and_load_and_drives_combat_defaults() {
         let mut source_scene = GameplayScene::new("A", SceneKey::B, Vec2 { x: 0.0, y: 0.0 });
@@ -6574,11 +7388,13 @@
     #[test]
     fn validate_rejects_missing_save_id_references() {
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].interaction_target_save_id = Some(9999);
+        save.world.entities[0].order_state = SnapshotOrderState::Interact {
+            target_save_id: 9999,
+        };
         assert!(GameplayScene::validate_save_game(&save, SavedSceneKey::A).is_err());
 
         let mut save = sample_save_game(SavedSceneKey::A);
-        save.entities[0].job_state = SavedJobState::Working {
+        save.world.entities[0].order_state = SnapshotOrderState::Working {
             target_save_id: 9999,
             remaining_time: 1.5,
         };
@@ -6593,6 +7409,7 @@
 
         let mut save = sample_save_game(SavedSceneKey::A);
         save.entities.clear();
+        save.world.entities.clear();
         save.selected_entity_save_id = None;
         save.player_entity_save_id = None;
         save.next_save_id = 1;
@@ -6600,38 +7417,26 @@
     }
 
     #[test]
-    fn saved_runtime_order_precedence_working_then_interact_then_move() {
-        let mut saved = SavedEntityRuntime {
-            save_id: 10,
-            position: SavedVec2 { x: 0.0, y: 0.0 },
-            rotation_radians: None,
-            floor: None,
-            selectable: true,
-            actor: true,
-            archetype_def_name: None,
-            move_target_world: Some(SavedVec2 { x: 9.0, y: 9.0 }),
-            interaction_target_save_id: Some(20),
-            job_state: SavedJobState::Idle,
-            carry_visual_def: None,
-            interactable: None,
-        };
+    fn validate_requires_one_game_section_entry_per_world_entity() {
+        let mut save = sample_save_game(SavedSceneKey::A);
+        save.entities[1].entity_id = 99;
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("unknown world entity");
+        assert!(error.contains("entities[1].entity_id"), "{error}");
 
-        assert_eq!(
-            GameplayScene::runtime_order_state_from_saved(&saved),
-            OrderState::Interact { target_save_id: 20 }
-        );
+        let mut save = sample_save_game(SavedSceneKey::A);
+        save.entities[1].entity_id = 1;
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("duplicate world entity");
+        assert!(error.contains("duplicate entity_id 1"), "{error}");
 
-        saved.job_state = SavedJobState::Working {
-            target_save_id: 20,
-            remaining_time: 1.25,
-        };
-        assert_eq!(
-            GameplayScene::runtime_order_state_from_saved(&saved),
-            OrderState::Working {
-                target_save_id: 20,
-                remaining_time: 1.25
-            }
-        );
+        let mut save = sample_save_game(SavedSceneKey::A);
+        save.entities.pop();
+        save.world.entities[0].order_state = SnapshotOrderState::Idle;
+        save.next_save_id = 11;
+        let error = GameplayScene::validate_save_game(&save, SavedSceneKey::A)
+            .expect_err("unsectioned world entity");
+        assert!(error.contains("validation failed at entities:"), "{error}");
     }
 
     #[test]
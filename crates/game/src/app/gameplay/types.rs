@@ -13,62 +13,6 @@ impl SavedSceneKey {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-struct SavedVec2 {
-    x: f32,
-    y: f32,
-}
-
-impl SavedVec2 {
-    fn from_vec2(value: Vec2) -> Self {
-        Self {
-            x: value.x,
-            y: value.y,
-        }
-    }
-
-    fn to_vec2(self) -> Vec2 {
-        Vec2 {
-            x: self.x,
-            y: self.y,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
-enum ActiveFloor {
-    Rooftop,
-    #[default]
-    Main,
-    Basement,
-}
-
-impl ActiveFloor {
-    fn from_engine_floor(value: engine::FloorId) -> Self {
-        match value {
-            engine::FloorId::Rooftop => Self::Rooftop,
-            engine::FloorId::Main => Self::Main,
-            engine::FloorId::Basement => Self::Basement,
-        }
-    }
-
-    fn to_engine_floor(self) -> engine::FloorId {
-        match self {
-            Self::Rooftop => engine::FloorId::Rooftop,
-            Self::Main => engine::FloorId::Main,
-            Self::Basement => engine::FloorId::Basement,
-        }
-    }
-
-    fn as_token(self) -> &'static str {
-        match self {
-            Self::Rooftop => "rooftop",
-            Self::Main => "main",
-            Self::Basement => "basement",
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PawnControlRole {
     PlayerPawn,
@@ -226,79 +170,25 @@ impl JobBoard {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-enum SavedInteractableKind {
-    ResourcePile,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-enum SavedFloorId {
-    Rooftop,
-    Main,
-    Basement,
-}
-
-impl SavedFloorId {
-    fn from_engine_floor(value: engine::FloorId) -> Self {
-        match value {
-            engine::FloorId::Rooftop => Self::Rooftop,
-            engine::FloorId::Main => Self::Main,
-            engine::FloorId::Basement => Self::Basement,
-        }
-    }
-
-    fn to_engine_floor(self) -> engine::FloorId {
-        match self {
-            Self::Rooftop => engine::FloorId::Rooftop,
-            Self::Main => engine::FloorId::Main,
-            Self::Basement => engine::FloorId::Basement,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-struct SavedInteractableRuntime {
-    kind: SavedInteractableKind,
-    interaction_radius: f32,
-    remaining_uses: u32,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-enum SavedJobState {
-    Idle,
-    Working {
-        target_save_id: u64,
-        remaining_time: f32,
-    },
-}
-
+/// Game-owned state of one saved entity; `entity_id` is its id inside `SaveGame::world`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct SavedEntityRuntime {
+    entity_id: u64,
     save_id: u64,
-    position: SavedVec2,
-    rotation_radians: Option<f32>,
-    #[serde(default)]
-    floor: Option<SavedFloorId>,
-    selectable: bool,
-    actor: bool,
     #[serde(default)]
     archetype_def_name: Option<String>,
-    move_target_world: Option<SavedVec2>,
-    interaction_target_save_id: Option<u64>,
-    job_state: SavedJobState,
     #[serde(default)]
     carry_visual_def: Option<String>,
-    interactable: Option<SavedInteractableRuntime>,
 }
 
+/// Engine world snapshot plus the gameplay sections the engine does not own. Order targets in
+/// the snapshot already hold save ids; everything else maps through `entities`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct SaveGame {
     save_version: u32,
     scene_key: SavedSceneKey,
-    #[serde(default)]
-    active_floor: Option<SavedFloorId>,
-    camera_position: SavedVec2,
-    camera_zoom: f32,
+    #[serde(deserialize_with = "deserialize_world_snapshot")]
+    world: WorldSnapshot,
     selected_entity_save_id: Option<u64>,
     player_entity_save_id: Option<u64>,
     next_save_id: u64,
@@ -306,16 +196,25 @@ struct SaveGame {
     entities: Vec<SavedEntityRuntime>,
 }
 
+/// Decodes through the engine so snapshots written by older engine versions are migrated.
+fn deserialize_world_snapshot<'de, D>(deserializer: D) -> Result<WorldSnapshot, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let document = serde_json::Value::deserialize(deserializer)?;
+    WorldSnapshot::from_json_value(document).map_err(serde::de::Error::custom)
+}
+
 type SaveLoadResult<T> = Result<T, String>;
 
 #[derive(Clone, Copy)]
 struct WorldView<'a> {
     world: &'a SceneWorld,
-    active_floor: ActiveFloor,
+    active_floor: FloorId,
 }
 
 impl<'a> WorldView<'a> {
-    fn new(world: &'a SceneWorld, active_floor: ActiveFloor) -> Self {
+    fn new(world: &'a SceneWorld, active_floor: FloorId) -> Self {
         Self { world, active_floor }
     }
 
@@ -336,7 +235,7 @@ impl<'a> WorldView<'a> {
             .pick_topmost_interactable_at_cursor(
                 cursor_position_px,
                 window_size,
-                Some(self.active_floor.to_engine_floor()),
+                Some(self.active_floor),
             )
     }
 
@@ -349,9 +248,13 @@ impl<'a> WorldView<'a> {
             .pick_topmost_selectable_at_cursor(
                 cursor_position_px,
                 window_size,
-                Some(self.active_floor.to_engine_floor()),
+                Some(self.active_floor),
             )
     }
+
+    fn has_owner_timer(&self, owner: EntityId, tag: &str) -> bool {
+        self.world.find_owner_timer(owner, tag).is_some()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -419,7 +322,6 @@ struct AiAgent {
     aggro_radius: f32,
     attack_range: f32,
     cooldown_seconds: f32,
-    cooldown_remaining_seconds: f32,
 }
 
 impl AiAgent {
@@ -431,7 +333,6 @@ impl AiAgent {
             aggro_radius: params.aggro_radius,
             attack_range: params.attack_range,
             cooldown_seconds: params.attack_cooldown_seconds,
-            cooldown_remaining_seconds: 0.0,
         }
     }
 }
@@ -494,6 +395,21 @@ enum GameplayEventKind {
     StatusExpired,
 }
 
+impl GameplayEventKind {
+    /// Snake-case names used by debug commands such as `tick.until`.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "interaction_started" => Some(Self::InteractionStarted),
+            "interaction_completed" => Some(Self::InteractionCompleted),
+            "entity_damaged" => Some(Self::EntityDamaged),
+            "entity_died" => Some(Self::EntityDied),
+            "status_applied" => Some(Self::StatusApplied),
+            "status_expired" => Some(Self::StatusExpired),
+            _ => None,
+        }
+    }
+}
+
 impl GameplayEvent {
     fn kind(self) -> GameplayEventKind {
         match self {
@@ -540,9 +456,20 @@ impl GameplayEventCounts {
             }
         }
     }
+
+    fn count(&self, kind: GameplayEventKind) -> u32 {
+        match kind {
+            GameplayEventKind::InteractionStarted => self.interaction_started,
+            GameplayEventKind::InteractionCompleted => self.interaction_completed,
+            GameplayEventKind::EntityDamaged => self.entity_damaged,
+            GameplayEventKind::EntityDied => self.entity_died,
+            GameplayEventKind::StatusApplied => self.status_applied,
+            GameplayEventKind::StatusExpired => self.status_expired,
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct GameplayEventBus {
     current_tick_events: Vec<GameplayEvent>,
     last_tick_counts: GameplayEventCounts,
@@ -631,6 +558,10 @@ enum GameplayIntent {
         actor_id: EntityId,
         ticks: u32,
     },
+    StartAttackCooldownTimer {
+        actor_id: EntityId,
+        ticks: u32,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -649,6 +580,7 @@ enum GameplayIntentKind {
     DecrementInteractableUses,
     IncrementResourceCount,
     StartHitVisualTimer,
+    StartAttackCooldownTimer,
 }
 
 impl GameplayIntent {
@@ -668,6 +600,7 @@ impl GameplayIntent {
             Self::DecrementInteractableUses { .. } => GameplayIntentKind::DecrementInteractableUses,
             Self::IncrementResourceCount { .. } => GameplayIntentKind::IncrementResourceCount,
             Self::StartHitVisualTimer { .. } => GameplayIntentKind::StartHitVisualTimer,
+            Self::StartAttackCooldownTimer { .. } => GameplayIntentKind::StartAttackCooldownTimer,
         }
     }
 }
@@ -689,6 +622,7 @@ struct GameplayIntentApplyStats {
     decrement_interactable_uses: u32,
     increment_resource_count: u32,
     start_hit_visual_timer: u32,
+    start_attack_cooldown_timer: u32,
     invalid_target_count: u32,
     spawned_entity_ids: Vec<EntityId>,
 }
@@ -738,6 +672,10 @@ impl GameplayIntentApplyStats {
             GameplayIntentKind::StartHitVisualTimer => {
                 self.start_hit_visual_timer = self.start_hit_visual_timer.saturating_add(1)
             }
+            GameplayIntentKind::StartAttackCooldownTimer => {
+                self.start_attack_cooldown_timer =
+                    self.start_attack_cooldown_timer.saturating_add(1)
+            }
         }
     }
 
@@ -746,7 +684,7 @@ impl GameplayIntentApplyStats {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct GameplayIntentQueue {
     intents: Vec<GameplayIntent>,
     last_tick_apply_stats: GameplayIntentApplyStats,
@@ -32,13 +32,27 @@ fn build_nav_sandbox_tilemap() -> Tilemap {
         for x in 0..width {
             let index = (y * width + x) as usize;
             if x == NAV_SANDBOX_BLOCKED_STRIP_X && y != NAV_SANDBOX_BLOCKED_GAP_Y {
-                tiles[index] = NAV_BLOCKED_TILE_ID;
+                tiles[index] = WALL_TILE_ID;
             } else if (x + y) % 3 == 0 {
                 tiles[index] = 1;
             }
         }
     }
-    Tilemap::new(width, height, NAV_SANDBOX_ORIGIN, tiles).expect("static tilemap shape is valid")
+    wall_tilemap(width, height, NAV_SANDBOX_ORIGIN, tiles)
+}
+
+/// Builds a terrain tilemap and flags every `WALL_TILE_ID` tile as blocking movement and sight.
+fn wall_tilemap(width: u32, height: u32, origin: Vec2, tiles: Vec<u16>) -> Tilemap {
+    let mut tilemap =
+        Tilemap::new(width, height, origin, tiles).expect("static tilemap shape is valid");
+    for y in 0..height {
+        for x in 0..width {
+            if tilemap.tile_at(x, y) == Some(WALL_TILE_ID) {
+                tilemap.set_flags(x, y, TileFlags::BLOCKS_MOVEMENT | TileFlags::BLOCKS_SIGHT);
+            }
+        }
+    }
+    tilemap
 }
 
 fn try_resolve_archetype_by_name(
@@ -77,35 +91,32 @@ fn try_resolve_player_archetype(world: &SceneWorld) -> SaveLoadResult<EntityArch
         .cloned()
 }
 
-fn resolve_resource_pile_archetype(world: &SceneWorld) -> EntityArchetype {
-    try_resolve_resource_pile_archetype(world).unwrap_or_else(|error| panic!("{error}"))
-}
-
-fn try_resolve_resource_pile_archetype(world: &SceneWorld) -> SaveLoadResult<EntityArchetype> {
-    let def_db = world
-        .def_database()
-        .ok_or_else(|| "DefDatabase not set on SceneWorld before scene load".to_string())?;
-    let pile_id = def_db
-        .entity_def_id_by_name("proto.resource_pile")
-        .ok_or_else(|| {
-            "missing EntityDef 'proto.resource_pile'; add it to assets/base or enabled mods and fix XML compile errors"
-                .to_string()
-        })?;
-    let pile = def_db
-        .entity_def(pile_id)
-        .ok_or_else(|| {
-            "EntityDef id for 'proto.resource_pile' is missing from DefDatabase".to_string()
-        })?
-        .clone();
-    let has_interactable_tag = pile.tags.iter().any(|tag| tag == "interactable");
-    let has_resource_pile_tag = pile.tags.iter().any(|tag| tag == "resource_pile");
-    if !(has_interactable_tag && has_resource_pile_tag) {
-        return Err(
-            "EntityDef 'proto.resource_pile' must include tags 'interactable' and 'resource_pile'"
-                .to_string(),
-        );
-    }
-    Ok(pile)
+/// Copies def-driven presentation (render layer, sort pivot, palette swap, light) onto a freshly
+/// spawned entity; works before `apply_pending`.
+fn apply_archetype_presentation(world: &mut SceneWorld, id: EntityId, archetype: &EntityArchetype) {
+    world.set_entity_render_order(id, archetype.render_layer, archetype.sort_pivot_y);
+    world.set_entity_render_modifier(
+        id,
+        RenderModifier {
+            palette_swap: archetype.palette_swap.clone(),
+            ..RenderModifier::default()
+        },
+    );
+    if let Some(light) = archetype.light {
+        if let Err(error) = world.add_light(light.at(LightAnchor::Entity(id))) {
+            warn!(entity_id = id.0, %error, "archetype_light_rejected");
+        }
+    }
+    if let Some(radius) = archetype.sight_radius {
+        let faction = if archetype.tags.iter().any(|tag| tag == "npc") {
+            FACTION_HOSTILE
+        } else {
+            FACTION_COLONY
+        };
+        if let Err(error) = world.set_entity_sight(id, EntitySight { faction, radius }) {
+            warn!(entity_id = id.0, %error, "archetype_sight_rejected");
+        }
+    }
 }
 
 fn movement_delta(input: &InputSnapshot, fixed_dt_seconds: f32, speed: f32) -> Vec2 {
@@ -198,3 +209,13 @@ fn step_toward(
         false,
     )
 }
+
+fn health_bar_color(fraction: f32) -> [u8; 4] {
+    if fraction > 0.5 {
+        WIDGET_HEALTH_HIGH_COLOR
+    } else if fraction > 0.25 {
+        WIDGET_HEALTH_MID_COLOR
+    } else {
+        WIDGET_HEALTH_LOW_COLOR
+    }
+}
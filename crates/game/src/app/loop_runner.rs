@@ -11,10 +11,12 @@ pub(crate) fn run(app: AppWiring) -> ExitCode {
         scene_a,
         scene_b,
         dev_thruport,
+        thruport_diag,
     } = app;
 
     let hooks = LoopRuntimeHooks {
         remote_console_pump: Some(Box::new(dev_thruport)),
+        thruport_diag,
     };
 
     if let Err(err) = run_app_with_hooks(config, scene_a, scene_b, hooks) {
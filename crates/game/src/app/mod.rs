@@ -2,3 +2,4 @@ pub(crate) mod bootstrap;
 pub(crate) mod dev_thruport;
 pub(crate) mod gameplay;
 pub(crate) mod loop_runner;
+pub(crate) mod project_config;
@@ -0,0 +1,822 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use engine::{
+    read_user_cvars, ContentPlanRequest, LoopConfig, METRICS_EXPORT_ENV_VAR, RECORD_REPLAY_ENV_VAR,
+    REPLAY_ENV_VAR, SLOW_FRAME_ENV_VAR, USER_CVARS_FILE_NAME,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing_subscriber::EnvFilter;
+
+use super::dev_thruport::{DevThruportConfig, THRUPORT_DEFAULT_PORT, THRUPORT_DIAG_ENV_VAR};
+
+pub(crate) const PROJECT_CONFIG_FILE_NAME: &str = "protoge.json";
+const DUMP_CONFIG_FLAG: &str = "--dump-config";
+const CONFIG_PATH_FLAG: &str = "--config";
+
+/// Env vars that override a config key; the file and CLI use the key itself.
+const ENV_OVERRIDES: [(&str, &str); 10] = [
+    ("PROTOGE_ENABLED_MODS", "content.enabled_mods"),
+    (SLOW_FRAME_ENV_VAR, "debug.slow_frame_ms"),
+    ("PROTOGE_THRUPORT", "thruport.enabled"),
+    ("PROTOGE_THRUPORT_PORT", "thruport.port"),
+    ("PROTOGE_THRUPORT_TELEMETRY", "thruport.telemetry"),
+    (THRUPORT_DIAG_ENV_VAR, "thruport.diag"),
+    (METRICS_EXPORT_ENV_VAR, "metrics.export_on_exit"),
+    (REPLAY_ENV_VAR, "replay.play"),
+    (RECORD_REPLAY_ENV_VAR, "replay.record"),
+    ("RUST_LOG", "log.filter"),
+];
+
+const METRICS_EXPORT_CHOICES: [&str; 3] = ["off", "csv", "json"];
+
+/// Runtime knobs read from `protoge.json` in the project root. Every key is optional; missing
+/// keys keep their defaults and unknown keys are rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ProjectConfig {
+    pub window: WindowSection,
+    pub sim: SimSection,
+    pub render: RenderSection,
+    pub budgets: BudgetSection,
+    pub content: ContentSection,
+    pub thruport: ThruportSection,
+    pub metrics: MetricsSection,
+    pub replay: ReplaySection,
+    pub log: LogSection,
+    pub debug: DebugSection,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct WindowSection {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct SimSection {
+    pub target_tps: u32,
+    pub max_ticks_per_frame: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct RenderSection {
+    pub fps_cap: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct BudgetSection {
+    pub sim_ms: Option<f32>,
+    pub render_ms: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ContentSection {
+    pub enabled_mods: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ThruportSection {
+    pub enabled: bool,
+    pub port: u16,
+    pub telemetry: bool,
+    /// Logs every line the transport and loop handle.
+    pub diag: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct MetricsSection {
+    /// `off`, `csv` or `json`; the initial value of the `metrics.export_on_exit` cvar.
+    pub export_on_exit: String,
+}
+
+/// Replay file paths, relative to the working directory; empty is off.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ReplaySection {
+    pub play: String,
+    pub record: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct LogSection {
+    /// `tracing_subscriber::EnvFilter` directives, e.g. `info,engine=debug`.
+    pub filter: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct DebugSection {
+    pub slow_frame_ms: u64,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        let loop_defaults = LoopConfig::default();
+        Self {
+            window: WindowSection {
+                title: loop_defaults.window_title,
+                width: loop_defaults.window_width,
+                height: loop_defaults.window_height,
+            },
+            sim: SimSection {
+                target_tps: loop_defaults.target_tps,
+                max_ticks_per_frame: loop_defaults.max_ticks_per_frame,
+            },
+            render: RenderSection {
+                fps_cap: loop_defaults.fps_cap,
+            },
+            budgets: BudgetSection {
+                sim_ms: loop_defaults.sim_budget_ms,
+                render_ms: loop_defaults.render_budget_ms,
+            },
+            content: ContentSection::default(),
+            thruport: ThruportSection::default(),
+            metrics: MetricsSection::default(),
+            replay: ReplaySection::default(),
+            log: LogSection::default(),
+            debug: DebugSection {
+                slow_frame_ms: loop_defaults.simulated_slow_frame_ms,
+            },
+        }
+    }
+}
+
+impl Default for WindowSection {
+    fn default() -> Self {
+        ProjectConfig::default().window
+    }
+}
+
+impl Default for SimSection {
+    fn default() -> Self {
+        ProjectConfig::default().sim
+    }
+}
+
+impl Default for ThruportSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: THRUPORT_DEFAULT_PORT,
+            telemetry: false,
+            diag: false,
+        }
+    }
+}
+
+impl Default for MetricsSection {
+    fn default() -> Self {
+        Self {
+            export_on_exit: "off".to_string(),
+        }
+    }
+}
+
+impl Default for LogSection {
+    fn default() -> Self {
+        Self {
+            filter: "info".to_string(),
+        }
+    }
+}
+
+impl ProjectConfig {
+    pub(crate) fn loop_config(&self) -> LoopConfig {
+        LoopConfig {
+            window_title: self.window.title.clone(),
+            window_width: self.window.width,
+            window_height: self.window.height,
+            target_tps: self.sim.target_tps,
+            max_ticks_per_frame: self.sim.max_ticks_per_frame,
+            simulated_slow_frame_ms: self.debug.slow_frame_ms,
+            fps_cap: self.render.fps_cap,
+            sim_budget_ms: self.budgets.sim_ms,
+            render_budget_ms: self.budgets.render_ms,
+            thruport_telemetry: self.thruport.telemetry,
+            metrics_export_on_exit: self.metrics.export_on_exit.clone(),
+            replay_path: non_empty_path(&self.replay.play),
+            record_replay_path: non_empty_path(&self.replay.record),
+            content_plan_request: ContentPlanRequest {
+                enabled_mods: self.content.enabled_mods.clone(),
+                compiler_version: env!("CARGO_PKG_VERSION").to_string(),
+                game_version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            ..LoopConfig::default()
+        }
+    }
+
+    pub(crate) fn dev_thruport_config(&self) -> DevThruportConfig {
+        DevThruportConfig {
+            enabled: self.thruport.enabled,
+            port: self.thruport.port,
+            diag: self.thruport.diag,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.window.width == 0 || self.window.height == 0 {
+            return Err("window.width and window.height must be > 0".to_string());
+        }
+        if self.sim.target_tps == 0 {
+            return Err("sim.target_tps must be > 0".to_string());
+        }
+        if self.sim.max_ticks_per_frame == 0 {
+            return Err("sim.max_ticks_per_frame must be > 0".to_string());
+        }
+        if !METRICS_EXPORT_CHOICES.contains(&self.metrics.export_on_exit.as_str()) {
+            return Err(format!(
+                "metrics.export_on_exit '{}' is invalid (expected {})",
+                self.metrics.export_on_exit,
+                METRICS_EXPORT_CHOICES.join("|")
+            ));
+        }
+        EnvFilter::try_new(&self.log.filter)
+            .map_err(|error| format!("log.filter '{}' is invalid: {error}", self.log.filter))?;
+        Ok(())
+    }
+}
+
+fn non_empty_path(raw: &str) -> Option<PathBuf> {
+    (!raw.is_empty()).then(|| PathBuf::from(raw))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfigSource {
+    Default,
+    File,
+    /// A value saved in `user_cvars.json` by the `set` console command.
+    User,
+    Env,
+    Cli,
+}
+
+impl ConfigSource {
+    fn token(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::File => "file",
+            Self::User => "user",
+            Self::Env => "env",
+            Self::Cli => "cli",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ProjectConfigError {
+    Read {
+        path: PathBuf,
+        reason: String,
+    },
+    Parse {
+        path: PathBuf,
+        reason: String,
+    },
+    UnknownKey {
+        key: String,
+        source: ConfigSource,
+    },
+    InvalidValue {
+        key: String,
+        source: ConfigSource,
+        reason: String,
+    },
+    Invalid(String),
+    Cli(String),
+}
+
+impl fmt::Display for ProjectConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read { path, reason } => {
+                write!(f, "failed to read config {}: {reason}", path.display())
+            }
+            Self::Parse { path, reason } => {
+                write!(f, "invalid config {}: {reason}", path.display())
+            }
+            Self::UnknownKey { key, source } => {
+                write!(f, "unknown config key '{key}' ({})", source.token())
+            }
+            Self::InvalidValue {
+                key,
+                source,
+                reason,
+            } => write!(
+                f,
+                "invalid value for '{key}' ({}): {reason}",
+                source.token()
+            ),
+            Self::Invalid(reason) => write!(f, "invalid config: {reason}"),
+            Self::Cli(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// What the command line asked for besides key overrides.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct CliArgs {
+    pub config_path: Option<PathBuf>,
+    pub dump_config: bool,
+    pub overrides: Vec<(String, String)>,
+}
+
+impl CliArgs {
+    /// Parses `--config <path>`, `--dump-config` and `--<key> <value>` / `--<key>=<value>`.
+    pub(crate) fn parse(
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Self, ProjectConfigError> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                return Err(ProjectConfigError::Cli(format!(
+                    "unexpected argument '{arg}' (expected --<key> <value>)"
+                )));
+            };
+            if arg == DUMP_CONFIG_FLAG {
+                parsed.dump_config = true;
+                continue;
+            }
+            let (name, inline_value) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (flag, None),
+            };
+            let Some(value) = inline_value.or_else(|| args.next()) else {
+                return Err(ProjectConfigError::Cli(format!(
+                    "missing value for --{name}"
+                )));
+            };
+            if format!("--{name}") == CONFIG_PATH_FLAG {
+                parsed.config_path = Some(PathBuf::from(value));
+            } else {
+                parsed.overrides.push((name.to_string(), value));
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// The effective config and where each key's value came from.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ResolvedConfig {
+    /// Defaults, file, env and CLI. Saved user values stay out of it so they do not become
+    /// cvar defaults; the engine applies them over it at startup.
+    pub config: ProjectConfig,
+    pub file_path: Option<PathBuf>,
+    pub sources: BTreeMap<String, ConfigSource>,
+    /// JSON text of the `user_cvars.json` values that take effect, by key.
+    pub user_values: BTreeMap<String, String>,
+}
+
+impl ResolvedConfig {
+    /// One `key = value (source)` line per key, in key order.
+    pub(crate) fn dump_lines(&self) -> Vec<String> {
+        let mut values = BTreeMap::new();
+        flatten_keys(
+            &serde_json::to_value(&self.config).unwrap_or(Value::Null),
+            "",
+            &mut values,
+        );
+        let mut lines = vec![match &self.file_path {
+            Some(path) => format!("protoge config v1 file:{}", path.display()),
+            None => "protoge config v1 file:none".to_string(),
+        }];
+        lines.extend(values.iter().map(|(key, value)| {
+            let value = self.user_values.get(key).unwrap_or(value);
+            let source = self
+                .sources
+                .get(key)
+                .copied()
+                .unwrap_or(ConfigSource::Default);
+            format!("{key} = {value} ({})", source.token())
+        }));
+        lines
+    }
+
+    /// Keys set by env or CLI, which win over `user_cvars.json` for the cvars they name.
+    pub(crate) fn pinned_keys(&self) -> Vec<String> {
+        self.sources
+            .iter()
+            .filter(|(_, source)| matches!(source, ConfigSource::Env | ConfigSource::Cli))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    pub(crate) fn count_from(&self, source: ConfigSource) -> usize {
+        self.sources
+            .values()
+            .filter(|value| **value == source)
+            .count()
+    }
+}
+
+/// Loads the config with CLI args over env vars over saved user cvars over the config file
+/// over defaults. The file is `cli.config_path` when given (and must exist), else
+/// `<root>/protoge.json` if present. An unreadable `user_cvars.json` is left for the engine to
+/// report.
+pub(crate) fn load_project_config(
+    root: &Path,
+    cli: &CliArgs,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<ResolvedConfig, ProjectConfigError> {
+    let (path, required) = match &cli.config_path {
+        Some(path) => (path.clone(), true),
+        None => (root.join(PROJECT_CONFIG_FILE_NAME), false),
+    };
+    let file = if required || path.is_file() {
+        let text = fs::read_to_string(&path).map_err(|error| ProjectConfigError::Read {
+            path: path.clone(),
+            reason: error.to_string(),
+        })?;
+        Some((path, text))
+    } else {
+        None
+    };
+    let user = read_user_cvars(&root.join(USER_CVARS_FILE_NAME)).unwrap_or_default();
+    resolve_project_config(
+        file.as_ref()
+            .map(|(path, text)| (path.as_path(), text.as_str())),
+        &user,
+        env,
+        &cli.overrides,
+    )
+}
+
+fn resolve_project_config(
+    file: Option<(&Path, &str)>,
+    user: &BTreeMap<String, Value>,
+    env: impl Fn(&str) -> Option<String>,
+    cli_overrides: &[(String, String)],
+) -> Result<ResolvedConfig, ProjectConfigError> {
+    let mut sources = BTreeMap::new();
+    let mut known_keys = BTreeMap::new();
+    flatten_keys(
+        &serde_json::to_value(ProjectConfig::default()).unwrap_or(Value::Null),
+        "",
+        &mut known_keys,
+    );
+
+    let mut merged = match file {
+        Some((path, text)) => {
+            let parse_error = |reason: String| ProjectConfigError::Parse {
+                path: path.to_path_buf(),
+                reason,
+            };
+            let mut deserializer = serde_json::Deserializer::from_str(text);
+            let config: ProjectConfig = serde_path_to_error::deserialize(&mut deserializer)
+                .map_err(|error| parse_error(format!("{}: {}", error.path(), error.inner())))?;
+            let mut file_keys = BTreeMap::new();
+            let raw: Value =
+                serde_json::from_str(text).map_err(|error| parse_error(error.to_string()))?;
+            flatten_keys(&raw, "", &mut file_keys);
+            for key in file_keys.into_keys() {
+                sources.insert(key, ConfigSource::File);
+            }
+            serde_json::to_value(config).map_err(|error| parse_error(error.to_string()))?
+        }
+        None => serde_json::to_value(ProjectConfig::default()).unwrap_or(Value::Null),
+    };
+
+    let env_overrides = ENV_OVERRIDES.iter().filter_map(|(var, key)| {
+        env(var)
+            .filter(|value| !value.is_empty())
+            .map(|value| (key.to_string(), value, ConfigSource::Env))
+    });
+    let cli_overrides = cli_overrides
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone(), ConfigSource::Cli));
+    for (key, raw, source) in env_overrides.chain(cli_overrides) {
+        if !known_keys.contains_key(&key) {
+            return Err(ProjectConfigError::UnknownKey { key, source });
+        }
+        apply_override(&mut merged, &key, &raw).map_err(|reason| {
+            ProjectConfigError::InvalidValue {
+                key: key.clone(),
+                source,
+                reason,
+            }
+        })?;
+        sources.insert(key, source);
+    }
+
+    let config: ProjectConfig = serde_path_to_error::deserialize(merged).map_err(|error| {
+        ProjectConfigError::InvalidValue {
+            key: error.path().to_string(),
+            source: sources
+                .get(&error.path().to_string())
+                .copied()
+                .unwrap_or(ConfigSource::Default),
+            reason: error.inner().to_string(),
+        }
+    })?;
+    config.validate().map_err(ProjectConfigError::Invalid)?;
+
+    let mut user_values = BTreeMap::new();
+    for (key, value) in user {
+        let pinned = matches!(
+            sources.get(key),
+            Some(ConfigSource::Env | ConfigSource::Cli)
+        );
+        if known_keys.contains_key(key) && !pinned {
+            user_values.insert(key.clone(), value.to_string());
+            sources.insert(key.clone(), ConfigSource::User);
+        }
+    }
+    Ok(ResolvedConfig {
+        config,
+        file_path: file.map(|(path, _)| path.to_path_buf()),
+        sources,
+        user_values,
+    })
+}
+
+/// Collects every leaf of `value` as `section.key` -> JSON text.
+fn flatten_keys(value: &Value, prefix: &str, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map {
+                let key = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{prefix}.{name}")
+                };
+                flatten_keys(child, &key, out);
+            }
+        }
+        leaf => {
+            out.insert(prefix.to_string(), leaf.to_string());
+        }
+    }
+}
+
+/// Replaces the leaf at `key` with `raw`, parsed by the leaf's current JSON type. Type and
+/// range errors for numbers are left to the final typed deserialize.
+fn apply_override(root: &mut Value, key: &str, raw: &str) -> Result<(), String> {
+    let mut slot = root;
+    for part in key.split('.') {
+        slot = slot
+            .as_object_mut()
+            .and_then(|map: &mut Map<String, Value>| map.get_mut(part))
+            .ok_or_else(|| "unknown key".to_string())?;
+    }
+    let raw = raw.trim();
+    *slot = match &*slot {
+        Value::Bool(_) => Value::Bool(parse_bool(raw)?),
+        Value::String(_) => Value::String(raw.to_string()),
+        Value::Array(_) => Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| Value::String(entry.to_string()))
+                .collect(),
+        ),
+        current @ (Value::Number(_) | Value::Null) => {
+            if current.is_null() && matches!(raw, "" | "none" | "off") {
+                Value::Null
+            } else {
+                serde_json::from_str::<serde_json::Number>(raw)
+                    .map(Value::Number)
+                    .map_err(|_| format!("'{raw}' is not a number"))?
+            }
+        }
+        Value::Object(_) => return Err("is a section, not a key".to_string()),
+    };
+    Ok(())
+}
+
+fn parse_bool(raw: &str) -> Result<bool, String> {
+    match raw.to_ascii_lowercase().as_str() {
+        "1" | "true" | "on" => Ok(true),
+        "0" | "false" | "off" => Ok(false),
+        _ => Err(format!(
+            "'{raw}' is not a bool (expected 1|0|true|false|on|off)"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_from(pairs: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |var| {
+            pairs
+                .iter()
+                .find(|(name, _)| *name == var)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    fn cli(args: &[&str]) -> CliArgs {
+        CliArgs::parse(args.iter().map(ToString::to_string)).expect("cli")
+    }
+
+    #[test]
+    fn cli_overrides_env_overrides_file_overrides_defaults() {
+        let file = r#"{ "window": { "width": 1600, "title": "From file" }, "render": { "fps_cap": 144 } }"#;
+        let env = env_from(&[
+            ("PROTOGE_THRUPORT", "1"),
+            ("PROTOGE_THRUPORT_PORT", "46002"),
+            ("PROTOGE_ENABLED_MODS", "core, extra,"),
+            ("RUST_LOG", ""),
+        ]);
+        let args = cli(&["--window.width=1920", "--thruport.port", "47000"]);
+        let resolved = resolve_project_config(
+            Some((Path::new("protoge.json"), file)),
+            &BTreeMap::new(),
+            env,
+            &args.overrides,
+        )
+        .expect("resolve");
+
+        let config = &resolved.config;
+        assert_eq!(config.window.width, 1920);
+        assert_eq!(config.window.title, "From file");
+        assert_eq!(config.window.height, 720);
+        assert_eq!(config.render.fps_cap, Some(144));
+        assert!(config.thruport.enabled);
+        assert_eq!(config.thruport.port, 47000);
+        assert_eq!(config.content.enabled_mods, ["core", "extra"]);
+        assert_eq!(config.log.filter, "info");
+
+        assert_eq!(resolved.sources["window.width"], ConfigSource::Cli);
+        assert_eq!(resolved.sources["window.title"], ConfigSource::File);
+        assert_eq!(resolved.sources["thruport.enabled"], ConfigSource::Env);
+        assert!(!resolved.sources.contains_key("log.filter"));
+        assert_eq!(resolved.count_from(ConfigSource::Cli), 2);
+
+        let dump = resolved.dump_lines();
+        assert_eq!(dump[0], "protoge config v1 file:protoge.json");
+        assert!(dump.contains(&"window.width = 1920 (cli)".to_string()));
+        assert!(dump.contains(&"render.fps_cap = 144 (file)".to_string()));
+        assert!(dump.contains(&"budgets.sim_ms = null (default)".to_string()));
+
+        let loop_config = config.loop_config();
+        assert_eq!(loop_config.window_width, 1920);
+        assert_eq!(loop_config.fps_cap, Some(144));
+        assert_eq!(
+            loop_config.content_plan_request.enabled_mods,
+            ["core", "extra"]
+        );
+        assert_eq!(config.dev_thruport_config().port, 47000);
+    }
+
+    #[test]
+    fn unknown_keys_and_bad_values_are_rejected_with_their_source() {
+        let file_error = resolve_project_config(
+            Some((
+                Path::new("protoge.json"),
+                r#"{ "window": { "widht": 10 } }"#,
+            )),
+            &BTreeMap::new(),
+            env_from(&[]),
+            &[],
+        )
+        .unwrap_err();
+        assert!(
+            file_error
+                .to_string()
+                .starts_with("invalid config protoge.json: window.widht: unknown field `widht`"),
+            "{file_error}"
+        );
+
+        let cli_error = resolve_project_config(
+            None,
+            &BTreeMap::new(),
+            env_from(&[]),
+            &cli(&["--sim.speed", "2"]).overrides,
+        )
+        .unwrap_err();
+        assert_eq!(
+            cli_error.to_string(),
+            "unknown config key 'sim.speed' (cli)"
+        );
+
+        let env_error = resolve_project_config(
+            None,
+            &BTreeMap::new(),
+            env_from(&[("PROTOGE_THRUPORT", "yes")]),
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(
+            env_error.to_string(),
+            "invalid value for 'thruport.enabled' (env): 'yes' is not a bool (expected 1|0|true|false|on|off)"
+        );
+
+        let range_error = resolve_project_config(
+            None,
+            &BTreeMap::new(),
+            env_from(&[("PROTOGE_THRUPORT_PORT", "not-a-port")]),
+            &[],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            range_error,
+            ProjectConfigError::InvalidValue { ref key, source: ConfigSource::Env, .. } if key == "thruport.port"
+        ));
+
+        let invalid = resolve_project_config(
+            None,
+            &BTreeMap::new(),
+            env_from(&[]),
+            &cli(&["--sim.target_tps=0"]).overrides,
+        )
+        .unwrap_err();
+        assert_eq!(
+            invalid.to_string(),
+            "invalid config: sim.target_tps must be > 0"
+        );
+
+        assert!(CliArgs::parse(["--window.width".to_string()]).is_err());
+        assert!(CliArgs::parse(["width".to_string()]).is_err());
+        let args = cli(&[
+            "--config",
+            "alt.json",
+            "--dump-config",
+            "--render.fps_cap=none",
+        ]);
+        assert_eq!(args.config_path, Some(PathBuf::from("alt.json")));
+        assert!(args.dump_config);
+        let resolved =
+            resolve_project_config(None, &BTreeMap::new(), env_from(&[]), &args.overrides)
+                .expect("resolve");
+        assert_eq!(resolved.config.render.fps_cap, None);
+    }
+
+    #[test]
+    fn saved_user_cvars_sit_between_file_and_env() {
+        let file = r#"{ "render": { "fps_cap": 60 }, "budgets": { "sim_ms": 8.0 } }"#;
+        let user: BTreeMap<String, Value> = serde_json::from_str(
+            r#"{ "render.fps_cap": 30, "budgets.sim_ms": 4.0, "budgets.render_ms": 12.0, "game.flag": false }"#,
+        )
+        .expect("user");
+        let resolved = resolve_project_config(
+            Some((Path::new("protoge.json"), file)),
+            &user,
+            env_from(&[]),
+            &cli(&["--render.fps_cap", "144"]).overrides,
+        )
+        .expect("resolve");
+
+        assert_eq!(resolved.config.render.fps_cap, Some(144));
+        assert_eq!(resolved.config.budgets.sim_ms, Some(8.0));
+        assert_eq!(resolved.pinned_keys(), ["render.fps_cap"]);
+        let dump = resolved.dump_lines();
+        assert!(dump.contains(&"render.fps_cap = 144 (cli)".to_string()));
+        assert!(dump.contains(&"budgets.sim_ms = 4.0 (user)".to_string()));
+        assert!(dump.contains(&"budgets.render_ms = 12.0 (user)".to_string()));
+        assert!(!dump.iter().any(|line| line.starts_with("game.flag")));
+        assert_eq!(resolved.count_from(ConfigSource::User), 2);
+    }
+
+    #[test]
+    fn replay_metrics_and_diag_env_vars_map_to_config_keys() {
+        let env = env_from(&[
+            ("PROTOGE_METRICS_EXPORT", "csv"),
+            ("PROTOGE_REPLAY", "runs/a.replay"),
+            ("PROTOGE_RECORD_REPLAY", ""),
+            ("PROTOGE_THRUPORT_DIAG", "1"),
+        ]);
+        let resolved = resolve_project_config(None, &BTreeMap::new(), env, &[]).expect("resolve");
+        let dump = resolved.dump_lines();
+        assert!(dump.contains(&"metrics.export_on_exit = \"csv\" (env)".to_string()));
+        assert!(dump.contains(&"replay.play = \"runs/a.replay\" (env)".to_string()));
+        assert!(dump.contains(&"replay.record = \"\" (default)".to_string()));
+        assert!(dump.contains(&"thruport.diag = true (env)".to_string()));
+
+        let loop_config = resolved.config.loop_config();
+        assert_eq!(loop_config.metrics_export_on_exit, "csv");
+        assert_eq!(
+            loop_config.replay_path,
+            Some(PathBuf::from("runs/a.replay"))
+        );
+        assert_eq!(loop_config.record_replay_path, None);
+        assert!(resolved.config.dev_thruport_config().diag);
+
+        let invalid = resolve_project_config(
+            None,
+            &BTreeMap::new(),
+            env_from(&[("PROTOGE_METRICS_EXPORT", "xml")]),
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(
+            invalid.to_string(),
+            "invalid config: metrics.export_on_exit 'xml' is invalid (expected off|csv|json)"
+        );
+    }
+}
@@ -2,7 +2,15 @@ mod app;
 
 use std::process::ExitCode;
 
+use app::bootstrap::Startup;
+
 fn main() -> ExitCode {
-    let app_wiring = app::bootstrap::build_app();
-    app::loop_runner::run(app_wiring)
+    match app::bootstrap::build_app(std::env::args().skip(1)) {
+        Ok(Startup::Run(app_wiring)) => app::loop_runner::run(*app_wiring),
+        Ok(Startup::DumpedConfig) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("startup_failed: {error}");
+            ExitCode::FAILURE
+        }
+    }
 }